@@ -0,0 +1,156 @@
+use crate::guidance_utils::sanitize_log_message;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Entries kept in memory. Older entries are dropped first.
+pub const LOG_BUFFER_CAPACITY: usize = 500;
+const LOG_MESSAGE_MAX_CHARS: usize = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "info" => Some(Self::Info),
+            "warn" | "warning" => Some(Self::Warn),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    pub level: LogLevel,
+    pub module: String,
+    pub message: String,
+    pub created_at_ms: i64,
+}
+
+/// Bounded ring buffer of redacted log entries, so support can see background failures
+/// without stdout access.
+pub struct LogBuffer {
+    capacity: usize,
+    entries: Mutex<VecDeque<LogEntry>>,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Process-wide buffer shared by background threads and commands.
+    pub fn global() -> &'static Self {
+        static BUFFER: OnceLock<LogBuffer> = OnceLock::new();
+        BUFFER.get_or_init(|| Self::new(LOG_BUFFER_CAPACITY))
+    }
+
+    pub fn push(&self, level: LogLevel, module: &str, message: &str) -> LogEntry {
+        let entry = LogEntry {
+            level,
+            module: module.to_string(),
+            message: sanitize_log_message(message)
+                .chars()
+                .take(LOG_MESSAGE_MAX_CHARS)
+                .collect(),
+            created_at_ms: now_ms(),
+        };
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        while entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry.clone());
+        entry
+    }
+
+    /// Newest first, keeping entries at or above `min_level`.
+    pub fn recent(&self, min_level: Option<LogLevel>, limit: usize) -> Vec<LogEntry> {
+        let entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        entries
+            .iter()
+            .rev()
+            .filter(|entry| min_level.is_none_or(|level| entry.level >= level))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Records to the global buffer and mirrors the redacted line to stderr.
+pub fn log(level: LogLevel, module: &str, message: &str) {
+    let entry = LogBuffer::global().push(level, module, message);
+    eprintln!("[{}] {}: {}", level.as_str(), module, entry.message);
+}
+
+pub fn error(module: &str, message: &str) {
+    log(LogLevel::Error, module, message);
+}
+
+pub fn warn(module: &str, message: &str) {
+    log(LogLevel::Warn, module, message);
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LogBuffer, LogLevel};
+
+    #[test]
+    fn logged_error_is_redacted_and_filtered_by_level() {
+        let buffer = LogBuffer::new(3);
+        buffer.push(LogLevel::Info, "runner", "cycle started");
+        buffer.push(
+            LogLevel::Error,
+            "relay",
+            "relay push sync failed: Bearer sk-abcdefghijklmnop123",
+        );
+
+        let errors = buffer.recent(Some(LogLevel::Error), 10);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].module, "relay");
+        assert!(errors[0].message.contains("relay push sync failed"));
+        assert!(!errors[0].message.contains("sk-abcdefghijklmnop123"));
+        assert_eq!(buffer.recent(Some(LogLevel::Warn), 10).len(), 1);
+        assert_eq!(buffer.recent(None, 10)[0].level, LogLevel::Error);
+
+        buffer.push(LogLevel::Warn, "watcher", "one");
+        buffer.push(LogLevel::Warn, "watcher", "two");
+        let all = buffer.recent(None, 10);
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].message, "two");
+        assert!(all.iter().all(|entry| entry.message != "cycle started"));
+        assert_eq!(buffer.recent(None, 1).len(), 1);
+    }
+}
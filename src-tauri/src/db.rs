@@ -32,10 +32,51 @@ pub struct SuppressedAutopilotNotice {
 
 #[derive(Debug, Clone, Serialize)]
 pub struct HomeSnapshot {
+    pub range: String,
     pub surfaces: Vec<HomeSurface>,
     pub runner: RunnerStatus,
 }
 
+pub const MS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+
+/// Window for Home counts. Days are UTC buckets, matching the daily spend caps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HomeSnapshotRange {
+    Today,
+    Week,
+    #[default]
+    AllTime,
+}
+
+impl HomeSnapshotRange {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "today" => Some(Self::Today),
+            "week" => Some(Self::Week),
+            "all_time" => Some(Self::AllTime),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Today => "today",
+            Self::Week => "week",
+            Self::AllTime => "all_time",
+        }
+    }
+
+    /// Inclusive lower bound in epoch ms; `None` means unbounded.
+    pub fn since_ms(self, now_ms: i64) -> Option<i64> {
+        let start_of_today = now_ms - now_ms.rem_euclid(MS_PER_DAY);
+        match self {
+            Self::Today => Some(start_of_today),
+            Self::Week => Some(start_of_today - 6 * MS_PER_DAY),
+            Self::AllTime => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PrimaryOutcomeRecord {
@@ -59,6 +100,11 @@ pub struct RunnerControlRecord {
     pub microsoft_autopilot_id: String,
     pub watcher_last_tick_ms: Option<i64>,
     pub missed_runs_count: i64,
+    pub max_catch_up_cycles: i64,
+    pub relay_sync_batch_size: i64,
+    pub relay_connect_timeout_seconds: i64,
+    pub relay_read_timeout_seconds: i64,
+    pub relay_max_retries: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +117,10 @@ pub struct AutopilotSendPolicyRecord {
     pub quiet_hours_start_local: i64,
     pub quiet_hours_end_local: i64,
     pub allow_outside_quiet_hours: bool,
+    /// Minimum gap between sends to the same recipient. 0 turns the cooldown off.
+    pub recipient_cooldown_minutes: i64,
+    /// Email each successful run's outcome to the connected account's own address.
+    pub self_send_outcomes: bool,
     pub updated_at_ms: i64,
 }
 
@@ -96,6 +146,196 @@ pub struct AutopilotVoiceConfigRecord {
     pub updated_at_ms: i64,
 }
 
+/// Per-Autopilot overrides for runner context limits. `None` keeps the runner default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutopilotContextLimitsRecord {
+    pub autopilot_id: String,
+    pub inbox_text_max_chars: Option<i64>,
+    pub daily_source_max_items: Option<i64>,
+    /// Summarize inbox text past the limit with an extra model call instead of rejecting it.
+    pub summarize_overflow: bool,
+    pub updated_at_ms: i64,
+}
+
+/// Per-Autopilot tracking parameters stripped from fetched pages before diffing. `None` keeps
+/// the built-in list; an empty list strips nothing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutopilotWebTrackingRecord {
+    pub autopilot_id: String,
+    pub tracking_params: Option<Vec<String>>,
+    pub updated_at_ms: i64,
+}
+
+/// How long this Autopilot's approvals stay open, by the risk tier of the gated step.
+/// `None` never expires. Expiry is opt-in: without a saved row no tier expires.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalTtlRecord {
+    pub autopilot_id: String,
+    pub high_ttl_ms: Option<i64>,
+    pub medium_ttl_ms: Option<i64>,
+    pub low_ttl_ms: Option<i64>,
+    pub updated_at_ms: i64,
+}
+
+impl ApprovalTtlRecord {
+    /// When an approval for a `risk` step created at `created_at_ms` lapses.
+    pub fn expires_at(&self, risk: crate::schema::RiskTier, created_at_ms: i64) -> Option<i64> {
+        let ttl_ms = match risk {
+            crate::schema::RiskTier::High => self.high_ttl_ms,
+            crate::schema::RiskTier::Medium => self.medium_ttl_ms,
+            crate::schema::RiskTier::Low => self.low_ttl_ms,
+        };
+        ttl_ms.map(|ttl| created_at_ms.saturating_add(ttl))
+    }
+}
+
+/// Channels allowed to resolve this Autopilot's approvals (`local_ui`, `relay`, `email`).
+/// Without a saved row every channel is allowed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalChannelPolicyRecord {
+    pub autopilot_id: String,
+    pub allowed_channels: Vec<String>,
+    pub updated_at_ms: i64,
+}
+
+pub const APPROVAL_CHANNELS: [&str; 3] = ["local_ui", "relay", "email"];
+
+/// Sends recorded today (UTC day) against the Autopilot's `max_sends_per_day`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutopilotSendStatsRecord {
+    pub autopilot_id: String,
+    pub day_bucket: i64,
+    pub sends_today: i64,
+    pub max_sends_per_day: i64,
+    pub remaining_today: i64,
+}
+
+const SEND_COUNTER_MS_PER_DAY: i64 = 86_400_000;
+
+/// Upper bound for `AutopilotSendPolicyRecord::recipient_cooldown_minutes` (one week).
+pub const MAX_RECIPIENT_COOLDOWN_MINUTES: i64 = 10_080;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipientCooldownRecord {
+    pub autopilot_id: String,
+    pub recipient: String,
+    pub last_sent_at_ms: i64,
+    pub cooldown_until_ms: i64,
+}
+
+/// One row of the global activity feed. `source` is `run` for run transitions (`activities`)
+/// and `autopilot` for Autopilot-level events (`activity`), which carry no run or states.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityFeedItem {
+    pub id: String,
+    pub source: String,
+    pub autopilot_id: Option<String>,
+    pub run_id: Option<String>,
+    pub kind: String,
+    pub message: String,
+    pub from_state: Option<String>,
+    pub to_state: Option<String>,
+    pub created_at_ms: i64,
+}
+
+pub const ACTIVITY_FEED_MAX_LIMIT: usize = 500;
+
+/// Where an Autopilot files triaged mail for one provider. Without a row, triage archives.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutopilotTriageLabelRecord {
+    pub autopilot_id: String,
+    pub provider: String,
+    pub label_id: String,
+    pub label_name: String,
+    pub updated_at_ms: i64,
+}
+
+/// User-maintained phrase that forces the intent classifier. `kind` is `one_off_run` or
+/// `draft_autopilot`; `phrase` is stored lowercased.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntentKindOverrideRecord {
+    pub phrase: String,
+    pub kind: String,
+    pub created_at_ms: i64,
+    pub updated_at_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutopilotTransportConfigRecord {
+    pub autopilot_id: String,
+    pub transport_mode: String, // inherit | mock | local_http | relay
+    pub updated_at_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppSettingsRecord {
+    pub global_pause: bool,
+    /// Primitives custom and webhook plans may not use on this install.
+    pub disallowed_primitives: Vec<crate::schema::PrimitiveId>,
+    pub updated_at_ms: i64,
+}
+
+/// Primitives the runner cannot execute yet. They stay disallowed whatever the admin picks.
+pub const UNSUPPORTED_PRIMITIVES: [crate::schema::PrimitiveId; 2] = [
+    crate::schema::PrimitiveId::ScheduleRun,
+    crate::schema::PrimitiveId::ReadVaultFile,
+];
+
+/// One persisted runner cycle. `trigger` is `manual` or `background`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunnerCycleLogRecord {
+    pub id: String,
+    pub trigger: String,
+    pub watcher_status: String,
+    pub relay_sync_status: String,
+    pub providers_polled: i64,
+    pub fetched: i64,
+    pub deduped: i64,
+    pub started_runs: i64,
+    pub failed: i64,
+    pub resumed_due_runs: i64,
+    pub relay_decisions_applied: i64,
+    pub missed_runs_detected: i64,
+    pub catch_up_cycles_run: i64,
+    pub created_at_ms: i64,
+}
+
+/// A relay approval decision as received from the phone or webhook channel. `status` stays
+/// `received` when the decision never made it onto the approval.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayCallbackEventRecord {
+    pub request_id: String,
+    pub approval_id: String,
+    pub decision: String,
+    pub status: String,
+    pub channel: Option<String>,
+    pub actor_label: Option<String>,
+    pub created_at_ms: i64,
+}
+
+/// Bookkeeping for an API key ref. The secret itself only ever lives in the Keychain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyRefMetadataRecord {
+    pub ref_name: String,
+    pub created_at_ms: i64,
+    pub updated_at_ms: i64,
+    pub last_used_at_ms: Option<i64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OnboardingStateRecord {
@@ -110,6 +350,27 @@ pub struct OnboardingStateRecord {
     pub completed_at_ms: Option<i64>,
     pub dismissed_at_ms: Option<i64>,
     pub first_successful_run_at_ms: Option<i64>,
+    pub first_autopilot_created_at_ms: Option<i64>,
+    pub first_approval_resolved_at_ms: Option<i64>,
+    pub first_webhook_trigger_created_at_ms: Option<i64>,
+}
+
+/// Onboarding checklist steps. Each is stamped once, the first time it happens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnboardingMilestone {
+    AutopilotCreated,
+    ApprovalResolved,
+    WebhookTriggerCreated,
+}
+
+impl OnboardingMilestone {
+    fn column(self) -> &'static str {
+        match self {
+            Self::AutopilotCreated => "first_autopilot_created_at_ms",
+            Self::ApprovalResolved => "first_approval_resolved_at_ms",
+            Self::WebhookTriggerCreated => "first_webhook_trigger_created_at_ms",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -168,6 +429,8 @@ pub struct MemoryCardUpsert {
     pub created_from_run_id: Option<String>,
     pub updated_at_ms: i64,
     pub version: i64,
+    /// Saved by the user; learned updates and decay leave manual cards alone.
+    pub manual: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -185,6 +448,37 @@ pub struct GuidanceEventInsert {
     pub created_at_ms: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderPricingRecord {
+    pub provider_kind: String,
+    pub model: String,
+    pub input_cents_per_1k: f64,
+    pub output_cents_per_1k: f64,
+    pub is_fallback: bool,
+    pub updated_at_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeModelOverrideRecord {
+    pub provider_kind: String,
+    pub recipe_kind: String,
+    pub model: String,
+    pub updated_at_ms: i64,
+}
+
+/// Seeded list prices in USD cents per 1k tokens: (provider_kind, model, input, output).
+const DEFAULT_PROVIDER_PRICING: [(&str, &str, f64, f64); 3] = [
+    ("openai", "gpt-4o-mini", 0.015, 0.06),
+    ("anthropic", "claude-3-5-sonnet-latest", 0.3, 1.5),
+    ("gemini", "gemini-2.5-flash", 0.03, 0.25),
+];
+
+/// Unknown models are priced conservatively (high) so caps trip early rather than late.
+const FALLBACK_INPUT_CENTS_PER_1K: f64 = 0.5;
+const FALLBACK_OUTPUT_CENTS_PER_1K: f64 = 2.0;
+
 pub fn bootstrap_sqlite(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
     let app_data = app_handle
         .path()
@@ -193,13 +487,82 @@ pub fn bootstrap_sqlite(app_handle: &tauri::AppHandle) -> Result<PathBuf, String
     fs::create_dir_all(&app_data).map_err(|e| format!("Failed to create app data dir: {e}"))?;
 
     let db_path = app_data.join("terminus.sqlite");
+    let boot_started_at_ms = current_time_ms();
     let mut connection =
         Connection::open(&db_path).map_err(|e| format!("Failed to open sqlite db: {e}"))?;
     configure_connection(&connection)?;
     bootstrap_schema(&mut connection)?;
+    record_boot(&connection, boot_started_at_ms)?;
     Ok(db_path)
 }
 
+/// Bump whenever `bootstrap_schema` gains tables or columns so upgraded vaults record a migration.
+pub const SCHEMA_VERSION: &str = "2026-10-18-web-tracking-params";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaStatus {
+    pub current_version: String,
+    pub stored_version: Option<String>,
+    pub up_to_date: bool,
+    pub migrated_from_version: Option<String>,
+    pub last_migrated_at_ms: Option<i64>,
+    pub last_boot_at_ms: Option<i64>,
+    pub migrated_on_last_boot: bool,
+}
+
+fn get_schema_meta(connection: &Connection, key: &str) -> Result<Option<String>, String> {
+    connection
+        .query_row(
+            "SELECT value FROM schema_meta WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read schema metadata: {e}"))
+}
+
+fn set_schema_meta(connection: &Connection, key: &str, value: &str) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO schema_meta (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )
+        .map_err(|e| format!("Failed to update schema metadata: {e}"))?;
+    Ok(())
+}
+
+/// Marks the start of an app launch; a migration stamped at or after it ran on this boot.
+pub fn record_boot(connection: &Connection, boot_started_at_ms: i64) -> Result<(), String> {
+    set_schema_meta(
+        connection,
+        "last_boot_at_ms",
+        &boot_started_at_ms.to_string(),
+    )
+}
+
+pub fn get_schema_status(connection: &Connection) -> Result<SchemaStatus, String> {
+    let stored_version = get_schema_meta(connection, "schema_version")?;
+    let read_ms = |key: &str| -> Result<Option<i64>, String> {
+        Ok(get_schema_meta(connection, key)?.and_then(|value| value.parse().ok()))
+    };
+    let last_migrated_at_ms = read_ms("last_migrated_at_ms")?;
+    let last_boot_at_ms = read_ms("last_boot_at_ms")?;
+    Ok(SchemaStatus {
+        current_version: SCHEMA_VERSION.to_string(),
+        up_to_date: stored_version.as_deref() == Some(SCHEMA_VERSION),
+        stored_version,
+        migrated_from_version: get_schema_meta(connection, "migrated_from_version")?,
+        last_migrated_at_ms,
+        last_boot_at_ms,
+        migrated_on_last_boot: matches!(
+            (last_migrated_at_ms, last_boot_at_ms),
+            (Some(migrated), Some(boot)) if migrated >= boot
+        ),
+    })
+}
+
 pub fn configure_connection(connection: &Connection) -> Result<(), String> {
     connection
         .busy_timeout(std::time::Duration::from_millis(5_000))
@@ -242,6 +605,13 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               max_retries INTEGER NOT NULL DEFAULT 2,
               next_retry_backoff_ms INTEGER,
               next_retry_at_ms INTEGER,
+              backoff_base_ms INTEGER NOT NULL DEFAULT 200,
+              backoff_max_ms INTEGER NOT NULL DEFAULT 2000,
+              resume_after_ms INTEGER,
+              pause_reason TEXT,
+              pause_code TEXT,
+              blocked_redirect_host TEXT,
+              priority TEXT NOT NULL DEFAULT 'normal',
               soft_cap_approved INTEGER NOT NULL DEFAULT 0,
               spend_usd_estimate REAL NOT NULL DEFAULT 0.0,
               spend_usd_actual REAL NOT NULL DEFAULT 0.0,
@@ -265,9 +635,11 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               reason TEXT,
               decided_channel TEXT,
               decided_by TEXT,
+              reply_token TEXT,
               created_at INTEGER NOT NULL,
               updated_at INTEGER NOT NULL,
               decided_at INTEGER,
+              expires_at_ms INTEGER,
               UNIQUE (run_id, step_id),
               FOREIGN KEY (run_id) REFERENCES runs(id)
             );
@@ -338,6 +710,17 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               FOREIGN KEY (mission_id) REFERENCES missions(id)
             );
 
+            CREATE TABLE IF NOT EXISTS mission_templates (
+              id TEXT PRIMARY KEY,
+              name TEXT NOT NULL UNIQUE,
+              template_kind TEXT NOT NULL,
+              draft_json TEXT NOT NULL,
+              stages_json TEXT NOT NULL DEFAULT '[]',
+              parameters_json TEXT NOT NULL DEFAULT '[]',
+              created_at_ms INTEGER NOT NULL,
+              updated_at_ms INTEGER NOT NULL
+            );
+
             CREATE TABLE IF NOT EXISTS relay_callback_events (
               id TEXT PRIMARY KEY,
               request_id TEXT NOT NULL UNIQUE,
@@ -346,6 +729,7 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               status TEXT NOT NULL,
               channel TEXT,
               actor_label TEXT,
+              applied_via TEXT,
               created_at_ms INTEGER NOT NULL
             );
 
@@ -358,6 +742,7 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               last_error TEXT,
               last_processed_count INTEGER NOT NULL DEFAULT 0,
               total_processed_count INTEGER NOT NULL DEFAULT 0,
+              deduped_by_other_channel INTEGER NOT NULL DEFAULT 0,
               updated_at_ms INTEGER NOT NULL
             );
 
@@ -376,6 +761,7 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               request_id TEXT NOT NULL UNIQUE,
               status TEXT NOT NULL,
               channel TEXT,
+              event_dedupe_key TEXT,
               created_at_ms INTEGER NOT NULL
             );
 
@@ -409,7 +795,8 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               last_event_at_ms INTEGER,
               last_error TEXT,
               consecutive_failures INTEGER NOT NULL DEFAULT 0,
-              updated_at_ms INTEGER NOT NULL
+              updated_at_ms INTEGER NOT NULL,
+              renewal_window_ms INTEGER NOT NULL DEFAULT 86400000
             );
 
             CREATE TABLE IF NOT EXISTS gmail_pubsub_events (
@@ -465,6 +852,8 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               status TEXT NOT NULL,
               content TEXT NOT NULL,
               failure_reason TEXT,
+              review_action TEXT,
+              reviewed_at INTEGER,
               created_at INTEGER NOT NULL,
               updated_at INTEGER NOT NULL,
               UNIQUE (run_id, step_id, kind),
@@ -595,6 +984,9 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               created_from_run_id TEXT,
               updated_at_ms INTEGER NOT NULL,
               version INTEGER NOT NULL DEFAULT 1,
+              reinforced_at_ms INTEGER,
+              decayed_at_ms INTEGER,
+              manual INTEGER NOT NULL DEFAULT 0,
               FOREIGN KEY (autopilot_id) REFERENCES autopilots(id),
               FOREIGN KEY (created_from_run_id) REFERENCES runs(id)
             );
@@ -650,6 +1042,24 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               last_error TEXT
             );
 
+            CREATE TABLE IF NOT EXISTS email_label_cache (
+              provider TEXT NOT NULL,
+              label_id TEXT NOT NULL,
+              name TEXT NOT NULL,
+              fetched_at_ms INTEGER NOT NULL,
+              PRIMARY KEY (provider, label_id)
+            );
+
+            CREATE TABLE IF NOT EXISTS autopilot_triage_labels (
+              autopilot_id TEXT NOT NULL,
+              provider TEXT NOT NULL,
+              label_id TEXT NOT NULL,
+              label_name TEXT NOT NULL,
+              updated_at_ms INTEGER NOT NULL,
+              PRIMARY KEY (autopilot_id, provider),
+              FOREIGN KEY (autopilot_id) REFERENCES autopilots(id)
+            );
+
             CREATE TABLE IF NOT EXISTS email_ingest_events (
               id TEXT PRIMARY KEY,
               provider TEXT NOT NULL,
@@ -670,9 +1080,19 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               backoff_until_ms INTEGER,
               consecutive_failures INTEGER NOT NULL DEFAULT 0,
               last_error TEXT,
+              last_poll_at_ms INTEGER,
               updated_at_ms INTEGER NOT NULL
             );
 
+            CREATE TABLE IF NOT EXISTS inbox_watcher_stats (
+              provider TEXT PRIMARY KEY,
+              fetched INTEGER NOT NULL DEFAULT 0,
+              deduped INTEGER NOT NULL DEFAULT 0,
+              started_runs INTEGER NOT NULL DEFAULT 0,
+              failed INTEGER NOT NULL DEFAULT 0,
+              last_run_at_ms INTEGER NOT NULL
+            );
+
             CREATE TABLE IF NOT EXISTS webhook_triggers (
               id TEXT PRIMARY KEY,
               autopilot_id TEXT NOT NULL,
@@ -684,6 +1104,10 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               allowed_content_types_json TEXT NOT NULL DEFAULT '[\"application/json\"]',
               plan_json TEXT NOT NULL DEFAULT '{}',
               provider_kind TEXT NOT NULL DEFAULT 'openai',
+              secret_rotated_at_ms INTEGER,
+              paused_by_bulk INTEGER NOT NULL DEFAULT 0,
+              max_deliveries_per_minute INTEGER NOT NULL DEFAULT 30,
+              payload_schema_json TEXT,
               last_event_at_ms INTEGER,
               last_error TEXT,
               created_at_ms INTEGER NOT NULL,
@@ -704,6 +1128,23 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               payload_hash TEXT NOT NULL,
               failure_reason TEXT,
               run_id TEXT,
+              authenticated INTEGER NOT NULL DEFAULT 0,
+              FOREIGN KEY (trigger_id) REFERENCES webhook_triggers(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS webhook_dead_letters (
+              id TEXT PRIMARY KEY,
+              trigger_id TEXT NOT NULL,
+              delivery_id TEXT NOT NULL,
+              event_idempotency_key TEXT NOT NULL,
+              relay_channel TEXT,
+              reason TEXT NOT NULL,
+              body_json TEXT NOT NULL,
+              status TEXT NOT NULL DEFAULT 'pending',
+              retry_count INTEGER NOT NULL DEFAULT 0,
+              run_id TEXT,
+              created_at_ms INTEGER NOT NULL,
+              updated_at_ms INTEGER NOT NULL,
               FOREIGN KEY (trigger_id) REFERENCES webhook_triggers(id)
             );
 
@@ -718,9 +1159,78 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               microsoft_autopilot_id TEXT NOT NULL DEFAULT 'auto_inbox_watch_microsoft365',
               watcher_last_tick_ms INTEGER,
               missed_runs_count INTEGER NOT NULL DEFAULT 0,
+              max_catch_up_cycles INTEGER NOT NULL DEFAULT 3,
+              relay_sync_batch_size INTEGER NOT NULL DEFAULT 20,
+              relay_connect_timeout_seconds INTEGER NOT NULL DEFAULT 10,
+              relay_read_timeout_seconds INTEGER NOT NULL DEFAULT 30,
+              relay_max_retries INTEGER NOT NULL DEFAULT 2,
+              updated_at_ms INTEGER NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS app_settings (
+              singleton_id INTEGER PRIMARY KEY CHECK(singleton_id = 1),
+              global_pause INTEGER NOT NULL DEFAULT 0,
+              disallowed_primitives_json TEXT,
+              updated_at_ms INTEGER NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS runner_cycle_log (
+              id TEXT PRIMARY KEY,
+              trigger TEXT NOT NULL,
+              watcher_status TEXT NOT NULL,
+              relay_sync_status TEXT NOT NULL,
+              providers_polled INTEGER NOT NULL DEFAULT 0,
+              fetched INTEGER NOT NULL DEFAULT 0,
+              deduped INTEGER NOT NULL DEFAULT 0,
+              started_runs INTEGER NOT NULL DEFAULT 0,
+              failed INTEGER NOT NULL DEFAULT 0,
+              resumed_due_runs INTEGER NOT NULL DEFAULT 0,
+              relay_decisions_applied INTEGER NOT NULL DEFAULT 0,
+              missed_runs_detected INTEGER NOT NULL DEFAULT 0,
+              catch_up_cycles_run INTEGER NOT NULL DEFAULT 0,
+              created_at_ms INTEGER NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS audit_log (
+              id TEXT PRIMARY KEY,
+              actor TEXT NOT NULL,
+              action TEXT NOT NULL,
+              target TEXT NOT NULL,
+              before_excerpt TEXT,
+              after_excerpt TEXT,
+              created_at_ms INTEGER NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS provider_traces (
+              id TEXT PRIMARY KEY,
+              correlation_id TEXT,
+              provider_kind TEXT NOT NULL,
+              model TEXT NOT NULL,
+              status TEXT NOT NULL,
+              request_input TEXT NOT NULL,
+              response_text TEXT,
+              error_message TEXT,
+              latency_ms INTEGER NOT NULL,
+              created_at_ms INTEGER NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS intent_kind_overrides (
+              phrase TEXT PRIMARY KEY,
+              kind TEXT NOT NULL,
+              created_at_ms INTEGER NOT NULL,
               updated_at_ms INTEGER NOT NULL
             );
 
+            CREATE TABLE IF NOT EXISTS relay_ping_log (
+              id TEXT PRIMARY KEY,
+              reachable INTEGER NOT NULL,
+              status TEXT NOT NULL,
+              latency_ms INTEGER NOT NULL,
+              http_status INTEGER,
+              message TEXT NOT NULL,
+              created_at_ms INTEGER NOT NULL
+            );
+
             CREATE TABLE IF NOT EXISTS onboarding_state (
               singleton_id INTEGER PRIMARY KEY CHECK(singleton_id = 1),
               onboarding_complete INTEGER NOT NULL DEFAULT 0,
@@ -732,7 +1242,11 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               started_at_ms INTEGER NOT NULL,
               updated_at_ms INTEGER NOT NULL,
               completed_at_ms INTEGER,
-              dismissed_at_ms INTEGER
+              dismissed_at_ms INTEGER,
+              first_autopilot_created_at_ms INTEGER,
+              first_approval_resolved_at_ms INTEGER,
+              first_webhook_trigger_created_at_ms INTEGER,
+              reset_at_ms INTEGER
             );
 
             CREATE TABLE IF NOT EXISTS voice_config (
@@ -763,60 +1277,168 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
               quiet_hours_start_local INTEGER NOT NULL DEFAULT 18,
               quiet_hours_end_local INTEGER NOT NULL DEFAULT 9,
               allow_outside_quiet_hours INTEGER NOT NULL DEFAULT 0,
+              recipient_cooldown_minutes INTEGER NOT NULL DEFAULT 0,
+              self_send_outcomes INTEGER NOT NULL DEFAULT 0,
               updated_at_ms INTEGER NOT NULL,
               FOREIGN KEY (autopilot_id) REFERENCES autopilots(id)
             );
 
-            -- Legacy compatibility from earlier bootstrap versions.
-            CREATE TABLE IF NOT EXISTS activity (
-              id TEXT PRIMARY KEY,
-              autopilot_id TEXT,
-              event TEXT,
-              created_at INTEGER
+            CREATE TABLE IF NOT EXISTS recipient_cooldowns (
+              autopilot_id TEXT NOT NULL,
+              recipient TEXT NOT NULL,
+              last_sent_at_ms INTEGER NOT NULL,
+              PRIMARY KEY (autopilot_id, recipient)
             );
-            ",
-        )
-        .map_err(|e| format!("Failed to bootstrap schema: {e}"))?;
-    connection
-        .execute(
-            "INSERT INTO schema_meta (key, value) VALUES ('schema_version', '2026-02-22-hardening')
-             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
-            [],
-        )
-        .map_err(|e| format!("Failed to update schema version: {e}"))?;
 
-    ensure_column(connection, "runs", "next_retry_at_ms", "INTEGER")?;
-    ensure_column(
-        connection,
-        "runs",
-        "provider_kind",
-        "TEXT NOT NULL DEFAULT 'openai'",
-    )?;
-    ensure_column(
-        connection,
-        "runs",
-        "provider_tier",
-        "TEXT NOT NULL DEFAULT 'supported'",
-    )?;
-    ensure_column(
-        connection,
-        "runs",
-        "soft_cap_approved",
-        "INTEGER NOT NULL DEFAULT 0",
-    )?;
-    ensure_column(
-        connection,
-        "runs",
-        "spend_usd_estimate",
-        "REAL NOT NULL DEFAULT 0.0",
-    )?;
-    ensure_column(
-        connection,
-        "runs",
-        "spend_usd_actual",
-        "REAL NOT NULL DEFAULT 0.0",
-    )?;
-    ensure_column(
+            CREATE TABLE IF NOT EXISTS autopilot_transport_config (
+              autopilot_id TEXT PRIMARY KEY,
+              transport_mode TEXT NOT NULL DEFAULT 'inherit',
+              updated_at_ms INTEGER NOT NULL,
+              FOREIGN KEY (autopilot_id) REFERENCES autopilots(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS autopilot_approval_ttls (
+              autopilot_id TEXT PRIMARY KEY,
+              high_ttl_ms INTEGER,
+              medium_ttl_ms INTEGER,
+              low_ttl_ms INTEGER,
+              updated_at_ms INTEGER NOT NULL,
+              FOREIGN KEY (autopilot_id) REFERENCES autopilots(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS autopilot_context_limits (
+              autopilot_id TEXT PRIMARY KEY,
+              inbox_text_max_chars INTEGER,
+              daily_source_max_items INTEGER,
+              summarize_overflow INTEGER NOT NULL DEFAULT 0,
+              updated_at_ms INTEGER NOT NULL,
+              FOREIGN KEY (autopilot_id) REFERENCES autopilots(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS autopilot_web_tracking (
+              autopilot_id TEXT PRIMARY KEY,
+              tracking_params_json TEXT,
+              updated_at_ms INTEGER NOT NULL,
+              FOREIGN KEY (autopilot_id) REFERENCES autopilots(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS send_counters (
+              autopilot_id TEXT NOT NULL,
+              day_bucket INTEGER NOT NULL,
+              send_count INTEGER NOT NULL DEFAULT 0,
+              updated_at_ms INTEGER NOT NULL,
+              PRIMARY KEY (autopilot_id, day_bucket)
+            );
+
+            CREATE TABLE IF NOT EXISTS approval_channel_policy (
+              autopilot_id TEXT PRIMARY KEY,
+              allowed_channels_json TEXT NOT NULL,
+              updated_at_ms INTEGER NOT NULL,
+              FOREIGN KEY (autopilot_id) REFERENCES autopilots(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS provider_pricing (
+              provider_kind TEXT NOT NULL,
+              model TEXT NOT NULL,
+              input_cents_per_1k REAL NOT NULL,
+              output_cents_per_1k REAL NOT NULL,
+              updated_at_ms INTEGER NOT NULL,
+              PRIMARY KEY (provider_kind, model)
+            );
+
+            CREATE TABLE IF NOT EXISTS recipe_model_overrides (
+              provider_kind TEXT NOT NULL,
+              recipe_kind TEXT NOT NULL,
+              model TEXT NOT NULL,
+              updated_at_ms INTEGER NOT NULL,
+              PRIMARY KEY (provider_kind, recipe_kind)
+            );
+
+            CREATE TABLE IF NOT EXISTS api_key_refs (
+              ref_name TEXT PRIMARY KEY,
+              created_at_ms INTEGER NOT NULL,
+              updated_at_ms INTEGER NOT NULL,
+              last_used_at_ms INTEGER
+            );
+
+            CREATE TABLE IF NOT EXISTS primitive_guard_log (
+              id TEXT PRIMARY KEY,
+              run_id TEXT NOT NULL,
+              step_id TEXT NOT NULL,
+              primitive TEXT NOT NULL,
+              decision TEXT NOT NULL,
+              reason TEXT NOT NULL,
+              created_at_ms INTEGER NOT NULL
+            );
+
+            -- Legacy compatibility from earlier bootstrap versions.
+            CREATE TABLE IF NOT EXISTS activity (
+              id TEXT PRIMARY KEY,
+              autopilot_id TEXT,
+              event TEXT,
+              created_at INTEGER
+            );
+            ",
+        )
+        .map_err(|e| format!("Failed to bootstrap schema: {e}"))?;
+    // A vault stamped with an older version is being upgraded; a fresh one is not a migration.
+    let previous_version = get_schema_meta(connection, "schema_version")?;
+    if let Some(previous) = previous_version.as_deref() {
+        if previous != SCHEMA_VERSION {
+            set_schema_meta(connection, "migrated_from_version", previous)?;
+            set_schema_meta(
+                connection,
+                "last_migrated_at_ms",
+                &current_time_ms().to_string(),
+            )?;
+        }
+    }
+    set_schema_meta(connection, "schema_version", SCHEMA_VERSION)?;
+
+    ensure_column(connection, "runs", "next_retry_at_ms", "INTEGER")?;
+    ensure_column(
+        connection,
+        "runs",
+        "backoff_base_ms",
+        "INTEGER NOT NULL DEFAULT 200",
+    )?;
+    ensure_column(
+        connection,
+        "runs",
+        "backoff_max_ms",
+        "INTEGER NOT NULL DEFAULT 2000",
+    )?;
+    ensure_column(
+        connection,
+        "runs",
+        "provider_kind",
+        "TEXT NOT NULL DEFAULT 'openai'",
+    )?;
+    ensure_column(
+        connection,
+        "runs",
+        "provider_tier",
+        "TEXT NOT NULL DEFAULT 'supported'",
+    )?;
+    ensure_column(
+        connection,
+        "runs",
+        "soft_cap_approved",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    ensure_column(
+        connection,
+        "runs",
+        "spend_usd_estimate",
+        "REAL NOT NULL DEFAULT 0.0",
+    )?;
+    ensure_column(
+        connection,
+        "runs",
+        "spend_usd_actual",
+        "REAL NOT NULL DEFAULT 0.0",
+    )?;
+    ensure_column(
         connection,
         "runs",
         "usd_cents_estimate",
@@ -828,6 +1450,14 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
         "usd_cents_actual",
         "INTEGER NOT NULL DEFAULT 0",
     )?;
+    ensure_column(connection, "runs", "resume_after_ms", "INTEGER")?;
+    ensure_column(connection, "runs", "pause_reason", "TEXT")?;
+    ensure_column(
+        connection,
+        "runs",
+        "priority",
+        "TEXT NOT NULL DEFAULT 'normal'",
+    )?;
     ensure_column(
         connection,
         "spend_ledger",
@@ -871,6 +1501,48 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
         "missed_runs_count",
         "INTEGER NOT NULL DEFAULT 0",
     )?;
+    ensure_column(
+        connection,
+        "runner_control",
+        "max_catch_up_cycles",
+        "INTEGER NOT NULL DEFAULT 3",
+    )?;
+    ensure_column(
+        connection,
+        "runner_control",
+        "relay_sync_batch_size",
+        "INTEGER NOT NULL DEFAULT 20",
+    )?;
+    ensure_column(
+        connection,
+        "runner_control",
+        "relay_connect_timeout_seconds",
+        "INTEGER NOT NULL DEFAULT 10",
+    )?;
+    ensure_column(
+        connection,
+        "runner_control",
+        "relay_read_timeout_seconds",
+        "INTEGER NOT NULL DEFAULT 30",
+    )?;
+    ensure_column(
+        connection,
+        "runner_control",
+        "relay_max_retries",
+        "INTEGER NOT NULL DEFAULT 2",
+    )?;
+    ensure_column(
+        connection,
+        "autopilot_send_policy",
+        "recipient_cooldown_minutes",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    ensure_column(
+        connection,
+        "autopilot_send_policy",
+        "self_send_outcomes",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
     ensure_column(
         connection,
         "web_snapshots",
@@ -891,16 +1563,43 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
     )?;
     ensure_column(connection, "approvals", "action_id", "TEXT")?;
     ensure_column(connection, "approvals", "decided_channel", "TEXT")?;
+    ensure_column(connection, "approvals", "reply_token", "TEXT")?;
     ensure_column(connection, "approvals", "decided_by", "TEXT")?;
     ensure_column(connection, "relay_callback_events", "channel", "TEXT")?;
     ensure_column(connection, "relay_callback_events", "actor_label", "TEXT")?;
+    ensure_column(connection, "relay_callback_events", "applied_via", "TEXT")?;
     ensure_column(
         connection,
         "relay_webhook_callback_events",
         "channel",
         "TEXT",
     )?;
+    ensure_column(
+        connection,
+        "relay_gmail_pubsub_callback_events",
+        "event_dedupe_key",
+        "TEXT",
+    )?;
     ensure_column(connection, "onboarding_state", "recommended_intent", "TEXT")?;
+    ensure_column(
+        connection,
+        "onboarding_state",
+        "first_autopilot_created_at_ms",
+        "INTEGER",
+    )?;
+    ensure_column(
+        connection,
+        "onboarding_state",
+        "first_approval_resolved_at_ms",
+        "INTEGER",
+    )?;
+    ensure_column(
+        connection,
+        "onboarding_state",
+        "first_webhook_trigger_created_at_ms",
+        "INTEGER",
+    )?;
+    ensure_column(connection, "onboarding_state", "reset_at_ms", "INTEGER")?;
     ensure_column(
         connection,
         "voice_config",
@@ -931,19 +1630,92 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
         "provider_kind",
         "TEXT NOT NULL DEFAULT 'openai'",
     )?;
+    ensure_column(
+        connection,
+        "webhook_triggers",
+        "secret_rotated_at_ms",
+        "INTEGER",
+    )?;
+    ensure_column(
+        connection,
+        "webhook_triggers",
+        "paused_by_bulk",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    ensure_column(
+        connection,
+        "webhook_triggers",
+        "max_deliveries_per_minute",
+        "INTEGER NOT NULL DEFAULT 30",
+    )?;
+    ensure_column(
+        connection,
+        "webhook_triggers",
+        "payload_schema_json",
+        "TEXT",
+    )?;
+    ensure_column(connection, "memory_cards", "reinforced_at_ms", "INTEGER")?;
+    ensure_column(connection, "memory_cards", "decayed_at_ms", "INTEGER")?;
+    ensure_column(
+        connection,
+        "autopilot_context_limits",
+        "summarize_overflow",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
     ensure_column(
         connection,
         "relay_sync_state",
         "total_processed_count",
         "INTEGER NOT NULL DEFAULT 0",
     )?;
+    ensure_column(
+        connection,
+        "relay_sync_state",
+        "deduped_by_other_channel",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
     ensure_column(connection, "decision_events", "client_event_id", "TEXT")?;
+    ensure_column(
+        connection,
+        "gmail_pubsub_state",
+        "renewal_window_ms",
+        "INTEGER NOT NULL DEFAULT 86400000",
+    )?;
     ensure_column(
         connection,
         "adaptation_log",
         "adaptation_hash",
         "TEXT NOT NULL DEFAULT ''",
     )?;
+    ensure_column(
+        connection,
+        "inbox_watcher_state",
+        "last_poll_at_ms",
+        "INTEGER",
+    )?;
+    ensure_column(connection, "approvals", "expires_at_ms", "INTEGER")?;
+    ensure_column(
+        connection,
+        "app_settings",
+        "disallowed_primitives_json",
+        "TEXT",
+    )?;
+    ensure_column(connection, "runs", "blocked_redirect_host", "TEXT")?;
+    ensure_column(connection, "outcomes", "review_action", "TEXT")?;
+    ensure_column(connection, "outcomes", "reviewed_at", "INTEGER")?;
+    ensure_column(
+        connection,
+        "webhook_trigger_events",
+        "authenticated",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    ensure_column(
+        connection,
+        "memory_cards",
+        "manual",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    ensure_column(connection, "runs", "pause_code", "TEXT")?;
 
     // Best-effort backfill from legacy float columns for existing vaults.
     connection
@@ -970,6 +1742,18 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
             [],
         )
         .map_err(|e| format!("Failed to backfill spend_ledger cents: {e}"))?;
+    // Days with sends but no counter row predate send_counters; seed them from sent outcomes.
+    connection
+        .execute(
+            "INSERT OR IGNORE INTO send_counters (autopilot_id, day_bucket, send_count, updated_at_ms)
+             SELECT r.autopilot_id, o.created_at / ?1, COUNT(*), MAX(o.created_at)
+             FROM outcomes o
+             JOIN runs r ON r.id = o.run_id
+             WHERE o.kind = 'email_sent' OR (o.kind = 'self_send' AND o.status = 'sent')
+             GROUP BY r.autopilot_id, o.created_at / ?1",
+            params![SEND_COUNTER_MS_PER_DAY],
+        )
+        .map_err(|e| format!("Failed to backfill send counters: {e}"))?;
 
     // Replace legacy uniqueness (run_id, step_id) with (run_id, step_id, entry_kind).
     connection
@@ -1077,6 +1861,24 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
             [],
         )
         .map_err(|e| format!("Failed to create webhook event status index: {e}"))?;
+    connection
+        .execute(
+            "CREATE INDEX IF NOT EXISTS idx_webhook_dead_letters_status_created ON webhook_dead_letters(status, created_at_ms DESC)",
+            [],
+        )
+        .map_err(|e| format!("Failed to create webhook dead letter index: {e}"))?;
+    connection
+        .execute(
+            "CREATE INDEX IF NOT EXISTS idx_audit_log_created ON audit_log(created_at_ms DESC)",
+            [],
+        )
+        .map_err(|e| format!("Failed to create audit log index: {e}"))?;
+    connection
+        .execute(
+            "CREATE INDEX IF NOT EXISTS idx_primitive_guard_log_run_created ON primitive_guard_log(run_id, created_at_ms)",
+            [],
+        )
+        .map_err(|e| format!("Failed to create primitive guard log index: {e}"))?;
     connection
         .execute(
             "CREATE INDEX IF NOT EXISTS idx_runs_state_updated ON runs(state, updated_at DESC)",
@@ -1089,6 +1891,36 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
             [],
         )
         .map_err(|e| format!("Failed to create runs autopilot index: {e}"))?;
+    connection
+        .execute(
+            "CREATE INDEX IF NOT EXISTS idx_runs_created ON runs(created_at)",
+            [],
+        )
+        .map_err(|e| format!("Failed to create runs created index: {e}"))?;
+    connection
+        .execute(
+            "CREATE INDEX IF NOT EXISTS idx_approvals_created ON approvals(created_at)",
+            [],
+        )
+        .map_err(|e| format!("Failed to create approvals created index: {e}"))?;
+    connection
+        .execute(
+            "CREATE INDEX IF NOT EXISTS idx_activities_created ON activities(created_at)",
+            [],
+        )
+        .map_err(|e| format!("Failed to create activities created index: {e}"))?;
+    connection
+        .execute(
+            "CREATE INDEX IF NOT EXISTS idx_runner_cycle_log_created ON runner_cycle_log(created_at_ms DESC)",
+            [],
+        )
+        .map_err(|e| format!("Failed to create runner cycle log index: {e}"))?;
+    connection
+        .execute(
+            "CREATE INDEX IF NOT EXISTS idx_relay_callback_events_status_created ON relay_callback_events(status, created_at_ms DESC)",
+            [],
+        )
+        .map_err(|e| format!("Failed to create relay callback events index: {e}"))?;
     connection
         .execute(
             "CREATE INDEX IF NOT EXISTS idx_approvals_run_status_created ON approvals(run_id, status, created_at ASC)",
@@ -1191,6 +2023,13 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
             [],
         )
         .map_err(|e| format!("Failed to seed onboarding state: {e}"))?;
+    connection
+        .execute(
+            "INSERT OR IGNORE INTO app_settings (singleton_id, global_pause, updated_at_ms)
+             VALUES (1, 0, strftime('%s','now') * 1000)",
+            [],
+        )
+        .map_err(|e| format!("Failed to seed app settings: {e}"))?;
     connection
         .execute(
             "INSERT OR IGNORE INTO voice_config (
@@ -1207,6 +2046,22 @@ pub fn bootstrap_schema(connection: &mut Connection) -> Result<(), String> {
             [],
         )
         .map_err(|e| format!("Failed to seed relay routing policy: {e}"))?;
+    for (provider_kind, model, input_cents_per_1k, output_cents_per_1k) in DEFAULT_PROVIDER_PRICING
+    {
+        connection
+            .execute(
+                "INSERT OR IGNORE INTO provider_pricing (
+                   provider_kind, model, input_cents_per_1k, output_cents_per_1k, updated_at_ms
+                 ) VALUES (?1, ?2, ?3, ?4, strftime('%s','now') * 1000)",
+                params![
+                    provider_kind,
+                    model,
+                    input_cents_per_1k,
+                    output_cents_per_1k
+                ],
+            )
+            .map_err(|e| format!("Failed to seed provider pricing: {e}"))?;
+    }
 
     Ok(())
 }
@@ -1364,15 +2219,20 @@ pub fn upsert_memory_card(
         .execute(
             "
             INSERT INTO memory_cards (
-              card_id, autopilot_id, card_type, title, content_json, confidence, created_from_run_id, updated_at_ms, version
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+              card_id, autopilot_id, card_type, title, content_json, confidence, created_from_run_id, updated_at_ms, version,
+              reinforced_at_ms, decayed_at_ms, manual
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?8, NULL, ?10)
             ON CONFLICT(card_id) DO UPDATE SET
               title = excluded.title,
               content_json = excluded.content_json,
               confidence = excluded.confidence,
               created_from_run_id = excluded.created_from_run_id,
               updated_at_ms = excluded.updated_at_ms,
-              version = excluded.version
+              version = excluded.version,
+              reinforced_at_ms = excluded.reinforced_at_ms,
+              decayed_at_ms = NULL,
+              manual = excluded.manual
+            WHERE memory_cards.manual = 0 OR excluded.manual = 1
             ",
             params![
                 &payload.card_id,
@@ -1383,13 +2243,28 @@ pub fn upsert_memory_card(
                 payload.confidence,
                 &payload.created_from_run_id,
                 payload.updated_at_ms,
-                payload.version
+                payload.version,
+                payload.manual
             ],
         )
         .map_err(|e| format!("Failed to upsert memory card: {e}"))?;
     Ok(())
 }
 
+pub fn delete_memory_card(
+    connection: &Connection,
+    autopilot_id: &str,
+    card_id: &str,
+) -> Result<bool, String> {
+    let deleted = connection
+        .execute(
+            "DELETE FROM memory_cards WHERE autopilot_id = ?1 AND card_id = ?2",
+            params![autopilot_id, card_id],
+        )
+        .map_err(|e| format!("Failed to delete memory card: {e}"))?;
+    Ok(deleted > 0)
+}
+
 pub fn insert_guidance_event(
     connection: &Connection,
     payload: &GuidanceEventInsert,
@@ -1420,23 +2295,41 @@ pub fn insert_guidance_event(
     Ok(())
 }
 
-pub fn get_home_snapshot(db_path: PathBuf) -> Result<HomeSnapshot, String> {
+pub fn get_home_snapshot(
+    db_path: PathBuf,
+    range: HomeSnapshotRange,
+) -> Result<HomeSnapshot, String> {
     let connection =
         Connection::open(db_path).map_err(|e| format!("Failed to open sqlite db: {e}"))?;
     configure_connection(&connection)?;
+    build_home_snapshot(&connection, range, current_time_ms())
+}
 
+pub fn build_home_snapshot(
+    connection: &Connection,
+    range: HomeSnapshotRange,
+    now_ms: i64,
+) -> Result<HomeSnapshot, String> {
+    let since_ms = range.since_ms(now_ms);
+    // Only the windowed surface counts are scoped; backlog and suppression reflect live state.
     let count = |table: &str| -> Result<i64, String> {
-        let sql = format!("SELECT COUNT(*) FROM {table}");
-        connection
-            .query_row(&sql, [], |row| row.get(0))
-            .map_err(|e| format!("Failed to count {table}: {e}"))
+        match since_ms {
+            Some(since) => connection.query_row(
+                &format!("SELECT COUNT(*) FROM {table} WHERE created_at >= ?1"),
+                params![since],
+                |row| row.get(0),
+            ),
+            None => connection.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+                row.get(0)
+            }),
+        }
+        .map_err(|e| format!("Failed to count {table}: {e}"))
     };
 
-    let runner_control = get_runner_control(&connection)?;
-    let now_ms = current_time_ms();
+    let runner_control = get_runner_control(connection)?;
     let backlog_count: i64 = connection
         .query_row(
-            "SELECT COUNT(*) FROM runs WHERE state IN ('ready', 'retrying', 'needs_approval', 'needs_clarification')",
+            "SELECT COUNT(*) FROM runs WHERE state IN ('ready', 'retrying', 'paused', 'needs_approval', 'needs_clarification')",
             [],
             |row| row.get(0),
         )
@@ -1512,9 +2405,10 @@ pub fn get_home_snapshot(db_path: PathBuf) -> Result<HomeSnapshot, String> {
         status_line
     };
 
-    let primary_outcome_count = count_primary_outcomes(&connection)?;
+    let primary_outcome_count = count_primary_outcomes_since(connection, since_ms)?;
 
     Ok(HomeSnapshot {
+        range: range.as_str().to_string(),
         surfaces: vec![
             HomeSurface {
                 title: "Autopilots".into(),
@@ -1566,33 +2460,67 @@ fn current_time_ms() -> i64 {
         .as_millis() as i64
 }
 
-pub fn count_primary_outcomes(connection: &Connection) -> Result<i64, String> {
+/// Counts primary outcomes for runs created at or after `since_ms` (all runs when `None`).
+pub fn count_primary_outcomes_since(
+    connection: &Connection,
+    since_ms: Option<i64>,
+) -> Result<i64, String> {
     connection
         .query_row(
             "
             SELECT COUNT(*)
             FROM runs r
-            WHERE r.state IN ('succeeded', 'failed', 'canceled')
-               OR r.state = 'needs_approval'
-               OR r.state = 'needs_clarification'
-               OR (
-                    r.state = 'blocked'
-                    AND EXISTS (
-                      SELECT 1 FROM clarifications c
-                      WHERE c.run_id = r.id AND c.status = 'pending'
-                    )
-                  )
+            WHERE (?1 IS NULL OR r.created_at >= ?1)
+              AND (
+                r.state IN ('succeeded', 'failed', 'canceled')
+                OR r.state = 'needs_approval'
+                OR r.state = 'needs_clarification'
+                OR (
+                     r.state = 'blocked'
+                     AND EXISTS (
+                       SELECT 1 FROM clarifications c
+                       WHERE c.run_id = r.id AND c.status = 'pending'
+                     )
+                   )
+              )
             ",
-            [],
+            params![since_ms],
             |row| row.get(0),
         )
         .map_err(|e| format!("Failed to count primary outcomes: {e}"))
 }
 
+/// Outcome kinds the runner writes; `list_primary_outcomes` ignores any other `kind` filter.
+const KNOWN_OUTCOME_KINDS: &[&str] = &[
+    "action_payload_email",
+    "action_payload_outcome",
+    "api_call_result",
+    "completed_outcome",
+    "daily_sources",
+    "daily_summary",
+    "email_draft",
+    "email_sent",
+    "email_triage_executed",
+    "inbox_read",
+    "notification_delivered",
+    "outcome_draft",
+    "receipt",
+    "self_send",
+    "web_read",
+];
+
+/// Lists the most recent primary outcomes, optionally narrowed to one Autopilot and to runs
+/// that produced an outcome of `kind`. Blank or unknown filters are ignored.
 pub fn list_primary_outcomes(
     connection: &Connection,
     limit: usize,
+    autopilot_id: Option<&str>,
+    kind: Option<&str>,
 ) -> Result<Vec<PrimaryOutcomeRecord>, String> {
+    let autopilot_id = autopilot_id.map(str::trim).filter(|v| !v.is_empty());
+    let kind = kind
+        .map(str::trim)
+        .filter(|v| KNOWN_OUTCOME_KINDS.contains(v));
     let mut stmt = connection
         .prepare(
             "
@@ -1600,6 +2528,11 @@ pub fn list_primary_outcomes(
               SELECT id, autopilot_id, state, failure_reason, created_at, updated_at
               FROM runs
               WHERE state IN ('succeeded', 'failed', 'canceled', 'needs_approval', 'needs_clarification', 'blocked')
+                AND (?2 IS NULL OR autopilot_id = ?2)
+                AND (
+                  ?3 IS NULL
+                  OR EXISTS (SELECT 1 FROM outcomes o WHERE o.run_id = runs.id AND o.kind = ?3)
+                )
               ORDER BY updated_at DESC
               LIMIT ?1
             )
@@ -1631,7 +2564,7 @@ pub fn list_primary_outcomes(
         )
         .map_err(|e| format!("Failed to prepare primary outcomes query: {e}"))?;
     let rows = stmt
-        .query_map(params![limit as i64], |row| {
+        .query_map(params![limit as i64, autopilot_id, kind], |row| {
             let state: String = row.get(2)?;
             let failure_reason: Option<String> = row.get(3)?;
             let pending_preview: Option<String> = row.get(6)?;
@@ -1693,11 +2626,92 @@ pub fn list_primary_outcomes(
     Ok(out)
 }
 
-pub fn get_runner_control(connection: &Connection) -> Result<RunnerControlRecord, String> {
-    connection
+pub fn get_app_settings(connection: &Connection) -> Result<AppSettingsRecord, String> {
+    let (global_pause, updated_at_ms, disallowed_json) = connection
         .query_row(
-            "SELECT background_enabled, watcher_enabled, gmail_trigger_mode, watcher_poll_seconds, watcher_max_items, gmail_autopilot_id, microsoft_autopilot_id, watcher_last_tick_ms, missed_runs_count
-             FROM runner_control WHERE singleton_id = 1",
+            "SELECT global_pause, updated_at_ms, disallowed_primitives_json
+             FROM app_settings WHERE singleton_id = 1",
+            [],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                ))
+            },
+        )
+        .map_err(|e| format!("Failed to read app settings: {e}"))?;
+    let disallowed_primitives = match disallowed_json {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| format!("Disallowed primitive setting is corrupt: {e}"))?,
+        None => UNSUPPORTED_PRIMITIVES.to_vec(),
+    };
+    Ok(AppSettingsRecord {
+        global_pause: global_pause == 1,
+        disallowed_primitives,
+        updated_at_ms,
+    })
+}
+
+/// Replaces the install-wide disallowed primitive set. `UNSUPPORTED_PRIMITIVES` are always
+/// kept in it.
+pub fn set_disallowed_primitives(
+    connection: &Connection,
+    primitives: &[crate::schema::PrimitiveId],
+    at_ms: i64,
+) -> Result<AppSettingsRecord, String> {
+    let mut disallowed = UNSUPPORTED_PRIMITIVES.to_vec();
+    for primitive in primitives {
+        if !disallowed.contains(primitive) {
+            disallowed.push(*primitive);
+        }
+    }
+    let json = serde_json::to_string(&disallowed)
+        .map_err(|e| format!("Failed to encode disallowed primitives: {e}"))?;
+    connection
+        .execute(
+            "UPDATE app_settings SET disallowed_primitives_json = ?1, updated_at_ms = ?2
+             WHERE singleton_id = 1",
+            params![json, at_ms],
+        )
+        .map_err(|e| format!("Failed to update disallowed primitives: {e}"))?;
+    get_app_settings(connection)
+}
+
+/// Flips the kill switch that halts watchers, relay sync and scheduled runs in the
+/// background threads. Manual commands keep working while paused.
+pub fn set_global_pause(
+    connection: &Connection,
+    enabled: bool,
+    at_ms: i64,
+) -> Result<AppSettingsRecord, String> {
+    connection
+        .execute(
+            "UPDATE app_settings SET global_pause = ?1, updated_at_ms = ?2 WHERE singleton_id = 1",
+            params![if enabled { 1 } else { 0 }, at_ms],
+        )
+        .map_err(|e| format!("Failed to update global pause: {e}"))?;
+    get_app_settings(connection)
+}
+
+/// Upper bound for `RunnerControlRecord::max_catch_up_cycles`.
+pub const MAX_CATCH_UP_CYCLES_LIMIT: i64 = 10;
+/// Bounds for `RunnerControlRecord::relay_sync_batch_size`.
+pub const RELAY_SYNC_BATCH_SIZE_MIN: i64 = 1;
+pub const RELAY_SYNC_BATCH_SIZE_MAX: i64 = 100;
+/// Bounds for the relay timeout and retry settings on `RunnerControlRecord`.
+pub const RELAY_CONNECT_TIMEOUT_SECONDS_MIN: i64 = 1;
+pub const RELAY_CONNECT_TIMEOUT_SECONDS_MAX: i64 = 60;
+pub const RELAY_READ_TIMEOUT_SECONDS_MIN: i64 = 5;
+pub const RELAY_READ_TIMEOUT_SECONDS_MAX: i64 = 120;
+pub const RELAY_MAX_RETRIES_LIMIT: i64 = 5;
+
+pub fn get_runner_control(connection: &Connection) -> Result<RunnerControlRecord, String> {
+    connection
+        .query_row(
+            "SELECT background_enabled, watcher_enabled, gmail_trigger_mode, watcher_poll_seconds, watcher_max_items, gmail_autopilot_id, microsoft_autopilot_id, watcher_last_tick_ms, missed_runs_count, max_catch_up_cycles, relay_sync_batch_size,
+                    relay_connect_timeout_seconds, relay_read_timeout_seconds, relay_max_retries
+             FROM runner_control WHERE singleton_id = 1",
             [],
             |row| {
                 Ok(RunnerControlRecord {
@@ -1710,6 +2724,11 @@ pub fn get_runner_control(connection: &Connection) -> Result<RunnerControlRecord
                     microsoft_autopilot_id: row.get(6)?,
                     watcher_last_tick_ms: row.get(7)?,
                     missed_runs_count: row.get(8)?,
+                    max_catch_up_cycles: row.get(9)?,
+                    relay_sync_batch_size: row.get(10)?,
+                    relay_connect_timeout_seconds: row.get(11)?,
+                    relay_read_timeout_seconds: row.get(12)?,
+                    relay_max_retries: row.get(13)?,
                 })
             },
         )
@@ -1719,21 +2738,23 @@ pub fn get_runner_control(connection: &Connection) -> Result<RunnerControlRecord
 pub fn get_onboarding_state(connection: &Connection) -> Result<OnboardingStateRecord, String> {
     let first_successful_run_at_ms: Option<i64> = connection
         .query_row(
-            "SELECT created_at * 1000 FROM runs WHERE state = 'succeeded' ORDER BY created_at ASC LIMIT 1",
+            "SELECT created_at FROM runs WHERE state = 'succeeded' ORDER BY created_at ASC LIMIT 1",
             [],
             |row| row.get(0),
         )
         .optional()
         .map_err(|e| format!("Failed to query first successful run: {e}"))?;
 
-    let mut record: OnboardingStateRecord = connection
+    let (mut record, reset_at_ms): (OnboardingStateRecord, Option<i64>) = connection
         .query_row(
             "SELECT onboarding_complete, dismissed, role_text, work_focus_text, biggest_pain_text,
-                    recommended_intent, started_at_ms, updated_at_ms, completed_at_ms, dismissed_at_ms
+                    recommended_intent, started_at_ms, updated_at_ms, completed_at_ms, dismissed_at_ms,
+                    first_autopilot_created_at_ms, first_approval_resolved_at_ms,
+                    first_webhook_trigger_created_at_ms, reset_at_ms
              FROM onboarding_state WHERE singleton_id = 1",
             [],
             |row| {
-                Ok(OnboardingStateRecord {
+                let record = OnboardingStateRecord {
                     onboarding_complete: row.get::<_, i64>(0)? == 1,
                     dismissed: row.get::<_, i64>(1)? == 1,
                     role_text: row.get(2)?,
@@ -1745,13 +2766,31 @@ pub fn get_onboarding_state(connection: &Connection) -> Result<OnboardingStateRe
                     completed_at_ms: row.get(8)?,
                     dismissed_at_ms: row.get(9)?,
                     first_successful_run_at_ms,
-                })
+                    first_autopilot_created_at_ms: row.get(10)?,
+                    first_approval_resolved_at_ms: row.get(11)?,
+                    first_webhook_trigger_created_at_ms: row.get(12)?,
+                };
+                Ok((record, row.get(13)?))
             },
         )
         .map_err(|e| format!("Failed to read onboarding state: {e}"))?;
 
+    // After a reset, only a success since then completes the replayed onboarding.
+    let completing_success_ms = match reset_at_ms {
+        Some(reset_at_ms) => connection
+            .query_row(
+                "SELECT created_at FROM runs
+                 WHERE state = 'succeeded' AND created_at >= ?1
+                 ORDER BY created_at ASC LIMIT 1",
+                params![reset_at_ms],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to query successful run since reset: {e}"))?,
+        None => first_successful_run_at_ms,
+    };
     if !record.onboarding_complete {
-        if let Some(success_ms) = first_successful_run_at_ms {
+        if let Some(success_ms) = completing_success_ms {
             let completed_at_ms = record.completed_at_ms.unwrap_or(success_ms);
             connection
                 .execute(
@@ -1773,6 +2812,47 @@ pub fn get_onboarding_state(connection: &Connection) -> Result<OnboardingStateRe
     Ok(record)
 }
 
+/// Stamps `milestone` at `at_ms` unless it was already reached.
+pub fn record_onboarding_milestone(
+    connection: &Connection,
+    milestone: OnboardingMilestone,
+    at_ms: i64,
+) -> Result<(), String> {
+    let column = milestone.column();
+    connection
+        .execute(
+            &format!(
+                "UPDATE onboarding_state SET {column} = COALESCE({column}, ?1) WHERE singleton_id = 1"
+            ),
+            params![at_ms],
+        )
+        .map_err(|e| format!("Failed to record onboarding milestone: {e}"))?;
+    Ok(())
+}
+
+/// Puts onboarding back to its unfinished state so the intro can be replayed. The answers and
+/// first-time milestones are kept.
+pub fn reset_onboarding_state(
+    connection: &Connection,
+    now_ms: i64,
+) -> Result<OnboardingStateRecord, String> {
+    connection
+        .execute(
+            "UPDATE onboarding_state
+             SET onboarding_complete = 0,
+                 dismissed = 0,
+                 completed_at_ms = NULL,
+                 dismissed_at_ms = NULL,
+                 started_at_ms = ?1,
+                 reset_at_ms = ?1,
+                 updated_at_ms = ?1
+             WHERE singleton_id = 1",
+            params![now_ms],
+        )
+        .map_err(|e| format!("Failed to reset onboarding state: {e}"))?;
+    get_onboarding_state(connection)
+}
+
 pub fn upsert_onboarding_state(
     connection: &Connection,
     payload: &OnboardingStateRecord,
@@ -1825,6 +2905,11 @@ pub fn upsert_runner_control(
                  microsoft_autopilot_id = ?7,
                  watcher_last_tick_ms = ?8,
                  missed_runs_count = ?9,
+                 max_catch_up_cycles = ?10,
+                 relay_sync_batch_size = ?11,
+                 relay_connect_timeout_seconds = ?12,
+                 relay_read_timeout_seconds = ?13,
+                 relay_max_retries = ?14,
                  updated_at_ms = strftime('%s','now') * 1000
              WHERE singleton_id = 1",
             params![
@@ -1836,21 +2921,39 @@ pub fn upsert_runner_control(
                 payload.gmail_autopilot_id,
                 payload.microsoft_autopilot_id,
                 payload.watcher_last_tick_ms,
-                payload.missed_runs_count
+                payload.missed_runs_count,
+                payload
+                    .max_catch_up_cycles
+                    .clamp(0, MAX_CATCH_UP_CYCLES_LIMIT),
+                payload
+                    .relay_sync_batch_size
+                    .clamp(RELAY_SYNC_BATCH_SIZE_MIN, RELAY_SYNC_BATCH_SIZE_MAX),
+                payload.relay_connect_timeout_seconds.clamp(
+                    RELAY_CONNECT_TIMEOUT_SECONDS_MIN,
+                    RELAY_CONNECT_TIMEOUT_SECONDS_MAX
+                ),
+                payload.relay_read_timeout_seconds.clamp(
+                    RELAY_READ_TIMEOUT_SECONDS_MIN,
+                    RELAY_READ_TIMEOUT_SECONDS_MAX
+                ),
+                payload.relay_max_retries.clamp(0, RELAY_MAX_RETRIES_LIMIT)
             ],
         )
         .map_err(|e| format!("Failed to update runner control: {e}"))?;
     Ok(())
 }
 
+type SendPolicyRow = (i64, String, i64, i64, i64, i64, i64, i64, i64);
+
 pub fn get_autopilot_send_policy(
     connection: &Connection,
     autopilot_id: &str,
 ) -> Result<AutopilotSendPolicyRecord, String> {
-    let row: Option<(i64, String, i64, i64, i64, i64, i64)> = connection
+    let row: Option<SendPolicyRow> = connection
         .query_row(
             "SELECT allow_sending, recipient_allowlist_json, max_sends_per_day,
-                    quiet_hours_start_local, quiet_hours_end_local, allow_outside_quiet_hours, updated_at_ms
+                    quiet_hours_start_local, quiet_hours_end_local, allow_outside_quiet_hours, updated_at_ms,
+                    recipient_cooldown_minutes, self_send_outcomes
              FROM autopilot_send_policy
              WHERE autopilot_id = ?1",
             params![autopilot_id],
@@ -1863,6 +2966,8 @@ pub fn get_autopilot_send_policy(
                     row.get(4)?,
                     row.get(5)?,
                     row.get(6)?,
+                    row.get(7)?,
+                    row.get(8)?,
                 ))
             },
         )
@@ -1877,6 +2982,8 @@ pub fn get_autopilot_send_policy(
         end,
         allow_outside,
         updated_at_ms,
+        recipient_cooldown_minutes,
+        self_send_outcomes,
     )) = row
     else {
         return Ok(AutopilotSendPolicyRecord {
@@ -1887,6 +2994,8 @@ pub fn get_autopilot_send_policy(
             quiet_hours_start_local: 18,
             quiet_hours_end_local: 9,
             allow_outside_quiet_hours: false,
+            recipient_cooldown_minutes: 0,
+            self_send_outcomes: false,
             updated_at_ms: 0,
         });
     };
@@ -1901,6 +3010,8 @@ pub fn get_autopilot_send_policy(
         quiet_hours_start_local: start,
         quiet_hours_end_local: end,
         allow_outside_quiet_hours: allow_outside == 1,
+        recipient_cooldown_minutes,
+        self_send_outcomes: self_send_outcomes == 1,
         updated_at_ms,
     })
 }
@@ -2065,8 +3176,9 @@ pub fn upsert_autopilot_send_policy(
         .execute(
             "INSERT INTO autopilot_send_policy (
                autopilot_id, allow_sending, recipient_allowlist_json, max_sends_per_day,
-               quiet_hours_start_local, quiet_hours_end_local, allow_outside_quiet_hours, updated_at_ms
-             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+               quiet_hours_start_local, quiet_hours_end_local, allow_outside_quiet_hours, updated_at_ms,
+               recipient_cooldown_minutes, self_send_outcomes
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
              ON CONFLICT(autopilot_id) DO UPDATE SET
                allow_sending = excluded.allow_sending,
                recipient_allowlist_json = excluded.recipient_allowlist_json,
@@ -2074,6 +3186,8 @@ pub fn upsert_autopilot_send_policy(
                quiet_hours_start_local = excluded.quiet_hours_start_local,
                quiet_hours_end_local = excluded.quiet_hours_end_local,
                allow_outside_quiet_hours = excluded.allow_outside_quiet_hours,
+               recipient_cooldown_minutes = excluded.recipient_cooldown_minutes,
+               self_send_outcomes = excluded.self_send_outcomes,
                updated_at_ms = excluded.updated_at_ms",
             params![
                 payload.autopilot_id,
@@ -2084,8 +3198,1344 @@ pub fn upsert_autopilot_send_policy(
                 payload.quiet_hours_end_local,
                 if payload.allow_outside_quiet_hours { 1 } else { 0 },
                 payload.updated_at_ms,
+                payload.recipient_cooldown_minutes,
+                if payload.self_send_outcomes { 1 } else { 0 },
             ],
         )
         .map_err(|e| format!("Failed to upsert send policy: {e}"))?;
     Ok(())
 }
+
+pub fn list_provider_pricing(
+    connection: &Connection,
+) -> Result<Vec<ProviderPricingRecord>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT provider_kind, model, input_cents_per_1k, output_cents_per_1k, updated_at_ms
+             FROM provider_pricing
+             ORDER BY provider_kind ASC, model ASC",
+        )
+        .map_err(|e| format!("Failed to prepare provider pricing query: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(ProviderPricingRecord {
+                provider_kind: row.get(0)?,
+                model: row.get(1)?,
+                input_cents_per_1k: row.get(2)?,
+                output_cents_per_1k: row.get(3)?,
+                is_fallback: false,
+                updated_at_ms: row.get(4)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query provider pricing: {e}"))?;
+    let mut out = Vec::new();
+    for row in rows {
+        out.push(row.map_err(|e| format!("Failed to parse provider pricing row: {e}"))?);
+    }
+    Ok(out)
+}
+
+pub fn get_provider_pricing(
+    connection: &Connection,
+    provider_kind: &str,
+    model: &str,
+) -> Result<ProviderPricingRecord, String> {
+    let row: Option<(f64, f64, i64)> = connection
+        .query_row(
+            "SELECT input_cents_per_1k, output_cents_per_1k, updated_at_ms
+             FROM provider_pricing
+             WHERE provider_kind = ?1 AND model = ?2",
+            params![provider_kind, model],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read provider pricing: {e}"))?;
+    Ok(match row {
+        Some((input_cents_per_1k, output_cents_per_1k, updated_at_ms)) => ProviderPricingRecord {
+            provider_kind: provider_kind.to_string(),
+            model: model.to_string(),
+            input_cents_per_1k,
+            output_cents_per_1k,
+            is_fallback: false,
+            updated_at_ms,
+        },
+        None => ProviderPricingRecord {
+            provider_kind: provider_kind.to_string(),
+            model: model.to_string(),
+            input_cents_per_1k: FALLBACK_INPUT_CENTS_PER_1K,
+            output_cents_per_1k: FALLBACK_OUTPUT_CENTS_PER_1K,
+            is_fallback: true,
+            updated_at_ms: 0,
+        },
+    })
+}
+
+pub fn upsert_provider_pricing(
+    connection: &Connection,
+    payload: &ProviderPricingRecord,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO provider_pricing (
+               provider_kind, model, input_cents_per_1k, output_cents_per_1k, updated_at_ms
+             ) VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(provider_kind, model) DO UPDATE SET
+               input_cents_per_1k = excluded.input_cents_per_1k,
+               output_cents_per_1k = excluded.output_cents_per_1k,
+               updated_at_ms = excluded.updated_at_ms",
+            params![
+                payload.provider_kind,
+                payload.model,
+                payload.input_cents_per_1k,
+                payload.output_cents_per_1k,
+                payload.updated_at_ms,
+            ],
+        )
+        .map_err(|e| format!("Failed to upsert provider pricing: {e}"))?;
+    Ok(())
+}
+
+/// Prices a token count against `provider_pricing`, rounding up to whole cents.
+pub fn estimate_provider_cost_usd_cents(
+    connection: &Connection,
+    provider_kind: &str,
+    model: &str,
+    input_tokens: i64,
+    output_tokens: i64,
+) -> Result<i64, String> {
+    let pricing = get_provider_pricing(connection, provider_kind, model)?;
+    let cents = (input_tokens.max(0) as f64 * pricing.input_cents_per_1k / 1000.0)
+        + (output_tokens.max(0) as f64 * pricing.output_cents_per_1k / 1000.0);
+    Ok(cents.ceil() as i64)
+}
+
+/// Lowest-priced model with a pricing row for the provider, if any.
+pub fn cheapest_priced_model(
+    connection: &Connection,
+    provider_kind: &str,
+) -> Result<Option<String>, String> {
+    connection
+        .query_row(
+            "SELECT model FROM provider_pricing
+             WHERE provider_kind = ?1
+             ORDER BY input_cents_per_1k + output_cents_per_1k ASC, model ASC
+             LIMIT 1",
+            params![provider_kind],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read provider pricing: {e}"))
+}
+
+/// Models Terminus knows for a provider: its default plus any model with a pricing row.
+pub fn list_known_models(
+    connection: &Connection,
+    provider_kind: &str,
+    default_model: &str,
+) -> Result<Vec<String>, String> {
+    let mut models = vec![default_model.to_string()];
+    for pricing in list_provider_pricing(connection)? {
+        if pricing.provider_kind == provider_kind && !models.contains(&pricing.model) {
+            models.push(pricing.model);
+        }
+    }
+    Ok(models)
+}
+
+pub fn list_recipe_model_overrides(
+    connection: &Connection,
+) -> Result<Vec<RecipeModelOverrideRecord>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT provider_kind, recipe_kind, model, updated_at_ms
+             FROM recipe_model_overrides
+             ORDER BY provider_kind ASC, recipe_kind ASC",
+        )
+        .map_err(|e| format!("Failed to prepare recipe model override query: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(RecipeModelOverrideRecord {
+                provider_kind: row.get(0)?,
+                recipe_kind: row.get(1)?,
+                model: row.get(2)?,
+                updated_at_ms: row.get(3)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query recipe model overrides: {e}"))?;
+    let mut out = Vec::new();
+    for row in rows {
+        out.push(row.map_err(|e| format!("Failed to parse recipe model override row: {e}"))?);
+    }
+    Ok(out)
+}
+
+pub fn get_recipe_model_override(
+    connection: &Connection,
+    provider_kind: &str,
+    recipe_kind: &str,
+) -> Result<Option<String>, String> {
+    connection
+        .query_row(
+            "SELECT model FROM recipe_model_overrides
+             WHERE provider_kind = ?1 AND recipe_kind = ?2",
+            params![provider_kind, recipe_kind],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read recipe model override: {e}"))
+}
+
+pub fn upsert_recipe_model_override(
+    connection: &Connection,
+    payload: &RecipeModelOverrideRecord,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO recipe_model_overrides (provider_kind, recipe_kind, model, updated_at_ms)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(provider_kind, recipe_kind) DO UPDATE SET
+               model = excluded.model,
+               updated_at_ms = excluded.updated_at_ms",
+            params![
+                payload.provider_kind,
+                payload.recipe_kind,
+                payload.model,
+                payload.updated_at_ms,
+            ],
+        )
+        .map_err(|e| format!("Failed to upsert recipe model override: {e}"))?;
+    Ok(())
+}
+
+pub fn delete_recipe_model_override(
+    connection: &Connection,
+    provider_kind: &str,
+    recipe_kind: &str,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "DELETE FROM recipe_model_overrides WHERE provider_kind = ?1 AND recipe_kind = ?2",
+            params![provider_kind, recipe_kind],
+        )
+        .map_err(|e| format!("Failed to clear recipe model override: {e}"))?;
+    Ok(())
+}
+
+pub fn get_autopilot_transport_config(
+    connection: &Connection,
+    autopilot_id: &str,
+) -> Result<AutopilotTransportConfigRecord, String> {
+    let row = connection
+        .query_row(
+            "SELECT transport_mode, updated_at_ms
+             FROM autopilot_transport_config WHERE autopilot_id = ?1",
+            params![autopilot_id],
+            |row| {
+                Ok(AutopilotTransportConfigRecord {
+                    autopilot_id: autopilot_id.to_string(),
+                    transport_mode: row.get(0)?,
+                    updated_at_ms: row.get(1)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read Autopilot transport config: {e}"))?;
+    Ok(row.unwrap_or_else(|| AutopilotTransportConfigRecord {
+        autopilot_id: autopilot_id.to_string(),
+        transport_mode: "inherit".to_string(),
+        updated_at_ms: 0,
+    }))
+}
+
+pub fn upsert_autopilot_transport_config(
+    connection: &Connection,
+    payload: &AutopilotTransportConfigRecord,
+) -> Result<AutopilotTransportConfigRecord, String> {
+    connection
+        .execute(
+            "INSERT INTO autopilot_transport_config (autopilot_id, transport_mode, updated_at_ms)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(autopilot_id) DO UPDATE SET
+               transport_mode = excluded.transport_mode,
+               updated_at_ms = excluded.updated_at_ms",
+            params![
+                payload.autopilot_id,
+                payload.transport_mode,
+                payload.updated_at_ms
+            ],
+        )
+        .map_err(|e| format!("Failed to save Autopilot transport config: {e}"))?;
+    get_autopilot_transport_config(connection, &payload.autopilot_id)
+}
+
+pub fn get_autopilot_context_limits(
+    connection: &Connection,
+    autopilot_id: &str,
+) -> Result<AutopilotContextLimitsRecord, String> {
+    let row = connection
+        .query_row(
+            "SELECT inbox_text_max_chars, daily_source_max_items, summarize_overflow, updated_at_ms
+             FROM autopilot_context_limits WHERE autopilot_id = ?1",
+            params![autopilot_id],
+            |row| {
+                Ok(AutopilotContextLimitsRecord {
+                    autopilot_id: autopilot_id.to_string(),
+                    inbox_text_max_chars: row.get(0)?,
+                    daily_source_max_items: row.get(1)?,
+                    summarize_overflow: row.get::<_, i64>(2)? == 1,
+                    updated_at_ms: row.get(3)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read Autopilot context limits: {e}"))?;
+    Ok(row.unwrap_or_else(|| AutopilotContextLimitsRecord {
+        autopilot_id: autopilot_id.to_string(),
+        inbox_text_max_chars: None,
+        daily_source_max_items: None,
+        summarize_overflow: false,
+        updated_at_ms: 0,
+    }))
+}
+
+pub fn upsert_autopilot_context_limits(
+    connection: &Connection,
+    payload: &AutopilotContextLimitsRecord,
+) -> Result<AutopilotContextLimitsRecord, String> {
+    connection
+        .execute(
+            "INSERT INTO autopilot_context_limits (autopilot_id, inbox_text_max_chars, daily_source_max_items, summarize_overflow, updated_at_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(autopilot_id) DO UPDATE SET
+               inbox_text_max_chars = excluded.inbox_text_max_chars,
+               daily_source_max_items = excluded.daily_source_max_items,
+               summarize_overflow = excluded.summarize_overflow,
+               updated_at_ms = excluded.updated_at_ms",
+            params![
+                payload.autopilot_id,
+                payload.inbox_text_max_chars,
+                payload.daily_source_max_items,
+                if payload.summarize_overflow { 1 } else { 0 },
+                payload.updated_at_ms
+            ],
+        )
+        .map_err(|e| format!("Failed to save Autopilot context limits: {e}"))?;
+    get_autopilot_context_limits(connection, &payload.autopilot_id)
+}
+
+pub fn get_autopilot_web_tracking(
+    connection: &Connection,
+    autopilot_id: &str,
+) -> Result<AutopilotWebTrackingRecord, String> {
+    let row = connection
+        .query_row(
+            "SELECT tracking_params_json, updated_at_ms
+             FROM autopilot_web_tracking WHERE autopilot_id = ?1",
+            params![autopilot_id],
+            |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, i64>(1)?)),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read Autopilot tracking parameters: {e}"))?;
+    Ok(match row {
+        Some((params_json, updated_at_ms)) => AutopilotWebTrackingRecord {
+            autopilot_id: autopilot_id.to_string(),
+            tracking_params: params_json
+                .map(|json| serde_json::from_str(&json))
+                .transpose()
+                .map_err(|e| format!("Autopilot tracking parameters are corrupt: {e}"))?,
+            updated_at_ms,
+        },
+        None => AutopilotWebTrackingRecord {
+            autopilot_id: autopilot_id.to_string(),
+            tracking_params: None,
+            updated_at_ms: 0,
+        },
+    })
+}
+
+pub fn upsert_autopilot_web_tracking(
+    connection: &Connection,
+    payload: &AutopilotWebTrackingRecord,
+) -> Result<AutopilotWebTrackingRecord, String> {
+    let params_json = payload
+        .tracking_params
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| format!("Failed to encode Autopilot tracking parameters: {e}"))?;
+    connection
+        .execute(
+            "INSERT INTO autopilot_web_tracking (autopilot_id, tracking_params_json, updated_at_ms)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(autopilot_id) DO UPDATE SET
+               tracking_params_json = excluded.tracking_params_json,
+               updated_at_ms = excluded.updated_at_ms",
+            params![payload.autopilot_id, params_json, payload.updated_at_ms],
+        )
+        .map_err(|e| format!("Failed to save Autopilot tracking parameters: {e}"))?;
+    get_autopilot_web_tracking(connection, &payload.autopilot_id)
+}
+
+pub fn get_approval_ttls(
+    connection: &Connection,
+    autopilot_id: &str,
+) -> Result<ApprovalTtlRecord, String> {
+    let row = connection
+        .query_row(
+            "SELECT high_ttl_ms, medium_ttl_ms, low_ttl_ms, updated_at_ms
+             FROM autopilot_approval_ttls WHERE autopilot_id = ?1",
+            params![autopilot_id],
+            |row| {
+                Ok(ApprovalTtlRecord {
+                    autopilot_id: autopilot_id.to_string(),
+                    high_ttl_ms: row.get(0)?,
+                    medium_ttl_ms: row.get(1)?,
+                    low_ttl_ms: row.get(2)?,
+                    updated_at_ms: row.get(3)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read approval timeouts: {e}"))?;
+    Ok(row.unwrap_or_else(|| ApprovalTtlRecord {
+        autopilot_id: autopilot_id.to_string(),
+        high_ttl_ms: None,
+        medium_ttl_ms: None,
+        low_ttl_ms: None,
+        updated_at_ms: 0,
+    }))
+}
+
+pub fn upsert_approval_ttls(
+    connection: &Connection,
+    payload: &ApprovalTtlRecord,
+) -> Result<ApprovalTtlRecord, String> {
+    connection
+        .execute(
+            "INSERT INTO autopilot_approval_ttls (autopilot_id, high_ttl_ms, medium_ttl_ms, low_ttl_ms, updated_at_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(autopilot_id) DO UPDATE SET
+               high_ttl_ms = excluded.high_ttl_ms,
+               medium_ttl_ms = excluded.medium_ttl_ms,
+               low_ttl_ms = excluded.low_ttl_ms,
+               updated_at_ms = excluded.updated_at_ms",
+            params![
+                payload.autopilot_id,
+                payload.high_ttl_ms,
+                payload.medium_ttl_ms,
+                payload.low_ttl_ms,
+                payload.updated_at_ms
+            ],
+        )
+        .map_err(|e| format!("Failed to save approval timeouts: {e}"))?;
+    get_approval_ttls(connection, &payload.autopilot_id)
+}
+
+pub fn get_approval_channel_policy(
+    connection: &Connection,
+    autopilot_id: &str,
+) -> Result<ApprovalChannelPolicyRecord, String> {
+    let row = connection
+        .query_row(
+            "SELECT allowed_channels_json, updated_at_ms
+             FROM approval_channel_policy WHERE autopilot_id = ?1",
+            params![autopilot_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read approval channel policy: {e}"))?;
+    Ok(match row {
+        Some((channels_json, updated_at_ms)) => ApprovalChannelPolicyRecord {
+            autopilot_id: autopilot_id.to_string(),
+            allowed_channels: serde_json::from_str(&channels_json)
+                .map_err(|e| format!("Approval channel policy is corrupt: {e}"))?,
+            updated_at_ms,
+        },
+        None => ApprovalChannelPolicyRecord {
+            autopilot_id: autopilot_id.to_string(),
+            allowed_channels: APPROVAL_CHANNELS.iter().map(|c| c.to_string()).collect(),
+            updated_at_ms: 0,
+        },
+    })
+}
+
+pub fn upsert_approval_channel_policy(
+    connection: &Connection,
+    payload: &ApprovalChannelPolicyRecord,
+) -> Result<ApprovalChannelPolicyRecord, String> {
+    let channels_json = serde_json::to_string(&payload.allowed_channels)
+        .map_err(|e| format!("Failed to encode approval channels: {e}"))?;
+    connection
+        .execute(
+            "INSERT INTO approval_channel_policy (autopilot_id, allowed_channels_json, updated_at_ms)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(autopilot_id) DO UPDATE SET
+               allowed_channels_json = excluded.allowed_channels_json,
+               updated_at_ms = excluded.updated_at_ms",
+            params![payload.autopilot_id, channels_json, payload.updated_at_ms],
+        )
+        .map_err(|e| format!("Failed to save approval channel policy: {e}"))?;
+    get_approval_channel_policy(connection, &payload.autopilot_id)
+}
+
+pub fn get_send_count(
+    connection: &Connection,
+    autopilot_id: &str,
+    now_ms: i64,
+) -> Result<i64, String> {
+    connection
+        .query_row(
+            "SELECT send_count FROM send_counters WHERE autopilot_id = ?1 AND day_bucket = ?2",
+            params![autopilot_id, now_ms.div_euclid(SEND_COUNTER_MS_PER_DAY)],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()
+        .map(|count| count.unwrap_or(0))
+        .map_err(|e| format!("Failed to read send counter: {e}"))
+}
+
+/// Adds one send to today's counter and returns the new count.
+pub fn increment_send_count(
+    connection: &Connection,
+    autopilot_id: &str,
+    now_ms: i64,
+) -> Result<i64, String> {
+    connection
+        .execute(
+            "INSERT INTO send_counters (autopilot_id, day_bucket, send_count, updated_at_ms)
+             VALUES (?1, ?2, 1, ?3)
+             ON CONFLICT(autopilot_id, day_bucket) DO UPDATE SET
+               send_count = send_count + 1,
+               updated_at_ms = excluded.updated_at_ms",
+            params![
+                autopilot_id,
+                now_ms.div_euclid(SEND_COUNTER_MS_PER_DAY),
+                now_ms
+            ],
+        )
+        .map_err(|e| format!("Failed to update send counter: {e}"))?;
+    get_send_count(connection, autopilot_id, now_ms)
+}
+
+pub fn get_recipient_last_sent_at(
+    connection: &Connection,
+    autopilot_id: &str,
+    recipient: &str,
+) -> Result<Option<i64>, String> {
+    connection
+        .query_row(
+            "SELECT last_sent_at_ms FROM recipient_cooldowns WHERE autopilot_id = ?1 AND recipient = ?2",
+            params![autopilot_id, recipient.trim().to_ascii_lowercase()],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read recipient cooldown: {e}"))
+}
+
+pub fn record_recipient_send(
+    connection: &Connection,
+    autopilot_id: &str,
+    recipient: &str,
+    sent_at_ms: i64,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO recipient_cooldowns (autopilot_id, recipient, last_sent_at_ms)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(autopilot_id, recipient) DO UPDATE SET
+               last_sent_at_ms = excluded.last_sent_at_ms",
+            params![
+                autopilot_id,
+                recipient.trim().to_ascii_lowercase(),
+                sent_at_ms
+            ],
+        )
+        .map_err(|e| format!("Failed to record recipient send: {e}"))?;
+    Ok(())
+}
+
+/// Recipients still inside the Autopilot's cooldown window at `now_ms`, soonest-free first.
+pub fn list_recipient_cooldowns(
+    connection: &Connection,
+    autopilot_id: &str,
+    now_ms: i64,
+) -> Result<Vec<RecipientCooldownRecord>, String> {
+    let window_ms = get_autopilot_send_policy(connection, autopilot_id)?
+        .recipient_cooldown_minutes
+        .saturating_mul(60_000);
+    if window_ms <= 0 {
+        return Ok(Vec::new());
+    }
+    let mut stmt = connection
+        .prepare(
+            "SELECT recipient, last_sent_at_ms FROM recipient_cooldowns
+             WHERE autopilot_id = ?1 AND last_sent_at_ms > ?2
+             ORDER BY last_sent_at_ms ASC, recipient ASC",
+        )
+        .map_err(|e| format!("Failed to prepare recipient cooldowns: {e}"))?;
+    let rows = stmt
+        .query_map(params![autopilot_id, now_ms - window_ms], |row| {
+            let last_sent_at_ms: i64 = row.get(1)?;
+            Ok(RecipientCooldownRecord {
+                autopilot_id: autopilot_id.to_string(),
+                recipient: row.get(0)?,
+                last_sent_at_ms,
+                cooldown_until_ms: last_sent_at_ms.saturating_add(window_ms),
+            })
+        })
+        .map_err(|e| format!("Failed to list recipient cooldowns: {e}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read recipient cooldowns: {e}"))
+}
+
+/// Clears one recipient's cooldown, or every recipient's when `recipient` is `None`. Returns
+/// the number of entries removed.
+pub fn clear_recipient_cooldowns(
+    connection: &Connection,
+    autopilot_id: &str,
+    recipient: Option<&str>,
+) -> Result<usize, String> {
+    match recipient {
+        Some(recipient) => connection.execute(
+            "DELETE FROM recipient_cooldowns WHERE autopilot_id = ?1 AND recipient = ?2",
+            params![autopilot_id, recipient.trim().to_ascii_lowercase()],
+        ),
+        None => connection.execute(
+            "DELETE FROM recipient_cooldowns WHERE autopilot_id = ?1",
+            params![autopilot_id],
+        ),
+    }
+    .map_err(|e| format!("Failed to clear recipient cooldowns: {e}"))
+}
+
+/// Newest-first merge of run and Autopilot activity, strictly older than `before_ms` when set.
+pub fn get_activity_feed(
+    connection: &Connection,
+    limit: usize,
+    before_ms: Option<i64>,
+) -> Result<Vec<ActivityFeedItem>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT a.id, 'run' AS source, r.autopilot_id, a.run_id, a.activity_type,
+                    a.user_message, a.from_state, a.to_state, a.created_at AS created_at_ms
+             FROM activities a
+             LEFT JOIN runs r ON r.id = a.run_id
+             WHERE ?2 IS NULL OR a.created_at < ?2
+             UNION ALL
+             SELECT id, 'autopilot', autopilot_id, NULL, 'autopilot_event',
+                    COALESCE(event, ''), NULL, NULL, COALESCE(created_at, 0)
+             FROM activity
+             WHERE ?2 IS NULL OR COALESCE(created_at, 0) < ?2
+             ORDER BY created_at_ms DESC, id DESC
+             LIMIT ?1",
+        )
+        .map_err(|e| format!("Failed to prepare activity feed: {e}"))?;
+    let rows = stmt
+        .query_map(
+            params![limit.clamp(1, ACTIVITY_FEED_MAX_LIMIT) as i64, before_ms],
+            |row| {
+                Ok(ActivityFeedItem {
+                    id: row.get(0)?,
+                    source: row.get(1)?,
+                    autopilot_id: row.get(2)?,
+                    run_id: row.get(3)?,
+                    kind: row.get(4)?,
+                    message: row.get(5)?,
+                    from_state: row.get(6)?,
+                    to_state: row.get(7)?,
+                    created_at_ms: row.get(8)?,
+                })
+            },
+        )
+        .map_err(|e| format!("Failed to query activity feed: {e}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read activity feed: {e}"))
+}
+
+pub fn list_autopilot_triage_labels(
+    connection: &Connection,
+    autopilot_id: &str,
+) -> Result<Vec<AutopilotTriageLabelRecord>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT provider, label_id, label_name, updated_at_ms
+             FROM autopilot_triage_labels
+             WHERE autopilot_id = ?1
+             ORDER BY provider ASC",
+        )
+        .map_err(|e| format!("Failed to prepare triage labels: {e}"))?;
+    let rows = stmt
+        .query_map(params![autopilot_id], |row| {
+            Ok(AutopilotTriageLabelRecord {
+                autopilot_id: autopilot_id.to_string(),
+                provider: row.get(0)?,
+                label_id: row.get(1)?,
+                label_name: row.get(2)?,
+                updated_at_ms: row.get(3)?,
+            })
+        })
+        .map_err(|e| format!("Failed to list triage labels: {e}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read triage labels: {e}"))
+}
+
+pub fn get_autopilot_triage_label(
+    connection: &Connection,
+    autopilot_id: &str,
+    provider: &str,
+) -> Result<Option<AutopilotTriageLabelRecord>, String> {
+    Ok(list_autopilot_triage_labels(connection, autopilot_id)?
+        .into_iter()
+        .find(|record| record.provider == provider))
+}
+
+pub fn upsert_autopilot_triage_label(
+    connection: &Connection,
+    record: &AutopilotTriageLabelRecord,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO autopilot_triage_labels (
+               autopilot_id, provider, label_id, label_name, updated_at_ms
+             ) VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(autopilot_id, provider) DO UPDATE SET
+               label_id = excluded.label_id,
+               label_name = excluded.label_name,
+               updated_at_ms = excluded.updated_at_ms",
+            params![
+                record.autopilot_id,
+                record.provider,
+                record.label_id,
+                record.label_name,
+                record.updated_at_ms
+            ],
+        )
+        .map_err(|e| format!("Failed to save triage label: {e}"))?;
+    Ok(())
+}
+
+pub fn clear_autopilot_triage_label(
+    connection: &Connection,
+    autopilot_id: &str,
+    provider: &str,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "DELETE FROM autopilot_triage_labels WHERE autopilot_id = ?1 AND provider = ?2",
+            params![autopilot_id, provider],
+        )
+        .map_err(|e| format!("Failed to clear triage label: {e}"))?;
+    Ok(())
+}
+
+pub fn list_intent_kind_overrides(
+    connection: &Connection,
+) -> Result<Vec<IntentKindOverrideRecord>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT phrase, kind, created_at_ms, updated_at_ms
+             FROM intent_kind_overrides
+             ORDER BY phrase ASC",
+        )
+        .map_err(|e| format!("Failed to prepare intent overrides: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(IntentKindOverrideRecord {
+                phrase: row.get(0)?,
+                kind: row.get(1)?,
+                created_at_ms: row.get(2)?,
+                updated_at_ms: row.get(3)?,
+            })
+        })
+        .map_err(|e| format!("Failed to list intent overrides: {e}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read intent overrides: {e}"))
+}
+
+pub fn upsert_intent_kind_override(
+    connection: &Connection,
+    phrase: &str,
+    kind: &str,
+    now_ms: i64,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO intent_kind_overrides (phrase, kind, created_at_ms, updated_at_ms)
+             VALUES (?1, ?2, ?3, ?3)
+             ON CONFLICT(phrase) DO UPDATE SET
+               kind = excluded.kind,
+               updated_at_ms = excluded.updated_at_ms",
+            params![phrase.trim().to_ascii_lowercase(), kind, now_ms],
+        )
+        .map_err(|e| format!("Failed to save intent override: {e}"))?;
+    Ok(())
+}
+
+/// Returns whether an override for `phrase` existed.
+pub fn delete_intent_kind_override(connection: &Connection, phrase: &str) -> Result<bool, String> {
+    connection
+        .execute(
+            "DELETE FROM intent_kind_overrides WHERE phrase = ?1",
+            params![phrase.trim().to_ascii_lowercase()],
+        )
+        .map(|removed| removed > 0)
+        .map_err(|e| format!("Failed to delete intent override: {e}"))
+}
+
+pub fn get_autopilot_send_stats(
+    connection: &Connection,
+    autopilot_id: &str,
+    now_ms: i64,
+) -> Result<AutopilotSendStatsRecord, String> {
+    let policy = get_autopilot_send_policy(connection, autopilot_id)?;
+    let sends_today = get_send_count(connection, autopilot_id, now_ms)?;
+    Ok(AutopilotSendStatsRecord {
+        autopilot_id: autopilot_id.to_string(),
+        day_bucket: now_ms.div_euclid(SEND_COUNTER_MS_PER_DAY),
+        sends_today,
+        max_sends_per_day: policy.max_sends_per_day,
+        remaining_today: policy.max_sends_per_day.saturating_sub(sends_today).max(0),
+    })
+}
+
+/// Records that the secret for `ref_name` was saved. `created_at_ms` keeps the first save;
+/// `updated_at_ms` tracks the latest one.
+pub fn record_api_key_ref_saved(
+    connection: &Connection,
+    ref_name: &str,
+    at_ms: i64,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO api_key_refs (ref_name, created_at_ms, updated_at_ms, last_used_at_ms)
+             VALUES (?1, ?2, ?2, NULL)
+             ON CONFLICT(ref_name) DO UPDATE SET updated_at_ms = excluded.updated_at_ms",
+            params![ref_name, at_ms],
+        )
+        .map_err(|e| format!("Failed to save API key ref metadata: {e}"))?;
+    Ok(())
+}
+
+/// Stamps the last time a CallApi step resolved `ref_name`. Refs saved before metadata was
+/// tracked get a row on first use.
+pub fn record_api_key_ref_used(
+    connection: &Connection,
+    ref_name: &str,
+    at_ms: i64,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO api_key_refs (ref_name, created_at_ms, updated_at_ms, last_used_at_ms)
+             VALUES (?1, ?2, ?2, ?2)
+             ON CONFLICT(ref_name) DO UPDATE SET last_used_at_ms = excluded.last_used_at_ms",
+            params![ref_name, at_ms],
+        )
+        .map_err(|e| format!("Failed to update API key ref usage: {e}"))?;
+    Ok(())
+}
+
+pub fn delete_api_key_ref_metadata(connection: &Connection, ref_name: &str) -> Result<(), String> {
+    connection
+        .execute(
+            "DELETE FROM api_key_refs WHERE ref_name = ?1",
+            params![ref_name],
+        )
+        .map_err(|e| format!("Failed to delete API key ref metadata: {e}"))?;
+    Ok(())
+}
+
+pub fn get_api_key_ref_metadata(
+    connection: &Connection,
+    ref_name: &str,
+) -> Result<Option<ApiKeyRefMetadataRecord>, String> {
+    connection
+        .query_row(
+            "SELECT ref_name, created_at_ms, updated_at_ms, last_used_at_ms
+             FROM api_key_refs WHERE ref_name = ?1",
+            params![ref_name],
+            map_api_key_ref_metadata,
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read API key ref metadata: {e}"))
+}
+
+pub fn list_api_key_ref_metadata(
+    connection: &Connection,
+) -> Result<Vec<ApiKeyRefMetadataRecord>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT ref_name, created_at_ms, updated_at_ms, last_used_at_ms
+             FROM api_key_refs ORDER BY ref_name ASC",
+        )
+        .map_err(|e| format!("Failed to prepare API key ref query: {e}"))?;
+    let rows = stmt
+        .query_map([], map_api_key_ref_metadata)
+        .map_err(|e| format!("Failed to query API key refs: {e}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse API key refs: {e}"))
+}
+
+fn map_api_key_ref_metadata(row: &rusqlite::Row<'_>) -> rusqlite::Result<ApiKeyRefMetadataRecord> {
+    Ok(ApiKeyRefMetadataRecord {
+        ref_name: row.get(0)?,
+        created_at_ms: row.get(1)?,
+        updated_at_ms: row.get(2)?,
+        last_used_at_ms: row.get(3)?,
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogRecord {
+    pub id: String,
+    pub actor: String,
+    pub action: String,
+    pub target: String,
+    pub before_excerpt: Option<String>,
+    pub after_excerpt: Option<String>,
+    pub created_at_ms: i64,
+}
+
+pub const AUDIT_LOG_MAX_ROWS: i64 = 1_000;
+const AUDIT_EXCERPT_MAX_CHARS: usize = 1_000;
+
+/// Appends an audit entry and prunes the log to `AUDIT_LOG_MAX_ROWS`. Build excerpts with
+/// `audit_excerpt` so secret values never reach the table.
+pub fn insert_audit_log(connection: &Connection, record: &AuditLogRecord) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO audit_log (
+               id, actor, action, target, before_excerpt, after_excerpt, created_at_ms
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                record.id,
+                record.actor,
+                record.action,
+                record.target,
+                record.before_excerpt,
+                record.after_excerpt,
+                record.created_at_ms,
+            ],
+        )
+        .map_err(|e| format!("Failed to record audit event: {e}"))?;
+    connection
+        .execute(
+            "DELETE FROM audit_log
+             WHERE id NOT IN (
+               SELECT id FROM audit_log
+               ORDER BY created_at_ms DESC, rowid DESC
+               LIMIT ?1
+             )",
+            params![AUDIT_LOG_MAX_ROWS],
+        )
+        .map_err(|e| format!("Failed to prune audit log: {e}"))?;
+    Ok(())
+}
+
+pub fn list_audit_log(
+    connection: &Connection,
+    limit: usize,
+) -> Result<Vec<AuditLogRecord>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT id, actor, action, target, before_excerpt, after_excerpt, created_at_ms
+             FROM audit_log
+             ORDER BY created_at_ms DESC, rowid DESC
+             LIMIT ?1",
+        )
+        .map_err(|e| format!("Failed to prepare audit log query: {e}"))?;
+    let rows = stmt
+        .query_map(params![limit.clamp(1, 500) as i64], |row| {
+            Ok(AuditLogRecord {
+                id: row.get(0)?,
+                actor: row.get(1)?,
+                action: row.get(2)?,
+                target: row.get(3)?,
+                before_excerpt: row.get(4)?,
+                after_excerpt: row.get(5)?,
+                created_at_ms: row.get(6)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query audit log: {e}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse audit log: {e}"))
+}
+
+/// Serializes `value` for the audit log with secret-looking fields masked and the result
+/// bounded to `AUDIT_EXCERPT_MAX_CHARS`.
+pub fn audit_excerpt<T: Serialize>(value: &T) -> String {
+    let mut json = serde_json::to_value(value).unwrap_or(serde_json::Value::Null);
+    redact_audit_secrets(&mut json);
+    let text = json.to_string();
+    if text.chars().count() > AUDIT_EXCERPT_MAX_CHARS {
+        let mut cut = text
+            .chars()
+            .take(AUDIT_EXCERPT_MAX_CHARS)
+            .collect::<String>();
+        cut.push('…');
+        cut
+    } else {
+        text
+    }
+}
+
+fn redact_audit_secrets(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, inner) in map.iter_mut() {
+                let key = key.to_ascii_lowercase().replace(['_', '-'], "");
+                if [
+                    "secret",
+                    "token",
+                    "password",
+                    "apikey",
+                    "authorization",
+                    "credential",
+                ]
+                .iter()
+                .any(|marker| key.contains(marker))
+                {
+                    *inner = serde_json::Value::String("[redacted]".to_string());
+                } else {
+                    redact_audit_secrets(inner);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_audit_secrets),
+        _ => {}
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayPingRecord {
+    pub id: String,
+    pub reachable: bool,
+    pub status: String,
+    pub latency_ms: i64,
+    pub http_status: Option<i64>,
+    pub message: String,
+    pub created_at_ms: i64,
+}
+
+/// Enough recent pings for the UI to show a short uptime strip.
+pub const RELAY_PING_LOG_MAX_ROWS: i64 = 20;
+
+/// Appends a relay ping and prunes the log to `RELAY_PING_LOG_MAX_ROWS`.
+pub fn insert_relay_ping(connection: &Connection, record: &RelayPingRecord) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO relay_ping_log (
+               id, reachable, status, latency_ms, http_status, message, created_at_ms
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                record.id,
+                record.reachable as i64,
+                record.status,
+                record.latency_ms,
+                record.http_status,
+                record.message,
+                record.created_at_ms,
+            ],
+        )
+        .map_err(|e| format!("Failed to record relay ping: {e}"))?;
+    connection
+        .execute(
+            "DELETE FROM relay_ping_log
+             WHERE id NOT IN (
+               SELECT id FROM relay_ping_log
+               ORDER BY created_at_ms DESC, rowid DESC
+               LIMIT ?1
+             )",
+            params![RELAY_PING_LOG_MAX_ROWS],
+        )
+        .map_err(|e| format!("Failed to prune relay ping log: {e}"))?;
+    Ok(())
+}
+
+pub fn list_relay_pings(
+    connection: &Connection,
+    limit: usize,
+) -> Result<Vec<RelayPingRecord>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT id, reachable, status, latency_ms, http_status, message, created_at_ms
+             FROM relay_ping_log
+             ORDER BY created_at_ms DESC, rowid DESC
+             LIMIT ?1",
+        )
+        .map_err(|e| format!("Failed to prepare relay ping query: {e}"))?;
+    let rows = stmt
+        .query_map(
+            params![limit.clamp(1, RELAY_PING_LOG_MAX_ROWS as usize) as i64],
+            |row| {
+                Ok(RelayPingRecord {
+                    id: row.get(0)?,
+                    reachable: row.get::<_, i64>(1)? == 1,
+                    status: row.get(2)?,
+                    latency_ms: row.get(3)?,
+                    http_status: row.get(4)?,
+                    message: row.get(5)?,
+                    created_at_ms: row.get(6)?,
+                })
+            },
+        )
+        .map_err(|e| format!("Failed to query relay pings: {e}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse relay pings: {e}"))
+}
+
+/// Redacted provider round-trip captured while trace mode is on. `status` is `ok` or `error`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderTraceRecord {
+    pub id: String,
+    pub correlation_id: Option<String>,
+    pub provider_kind: String,
+    pub model: String,
+    pub status: String,
+    pub request_input: String,
+    pub response_text: Option<String>,
+    pub error_message: Option<String>,
+    pub latency_ms: i64,
+    pub created_at_ms: i64,
+}
+
+pub const PROVIDER_TRACE_MAX_ROWS: i64 = 200;
+
+/// Appends a trace and prunes the table to `PROVIDER_TRACE_MAX_ROWS`.
+pub fn insert_provider_trace(
+    connection: &Connection,
+    record: &ProviderTraceRecord,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO provider_traces (
+               id, correlation_id, provider_kind, model, status, request_input,
+               response_text, error_message, latency_ms, created_at_ms
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                record.id,
+                record.correlation_id,
+                record.provider_kind,
+                record.model,
+                record.status,
+                record.request_input,
+                record.response_text,
+                record.error_message,
+                record.latency_ms,
+                record.created_at_ms,
+            ],
+        )
+        .map_err(|e| format!("Failed to record provider trace: {e}"))?;
+    connection
+        .execute(
+            "DELETE FROM provider_traces
+             WHERE id NOT IN (
+               SELECT id FROM provider_traces
+               ORDER BY created_at_ms DESC, rowid DESC
+               LIMIT ?1
+             )",
+            params![PROVIDER_TRACE_MAX_ROWS],
+        )
+        .map_err(|e| format!("Failed to prune provider traces: {e}"))?;
+    Ok(())
+}
+
+/// Trims an append-only log table (one of the `*_MAX_ROWS` logs above) to its newest
+/// `max_rows`. Returns how many rows were (or, on a dry run, would be) removed.
+pub fn prune_capped_log(
+    connection: &Connection,
+    table: &'static str,
+    max_rows: i64,
+    dry_run: bool,
+) -> Result<i64, String> {
+    let total: i64 = connection
+        .query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+            row.get(0)
+        })
+        .map_err(|e| format!("Failed to count {table}: {e}"))?;
+    let excess = (total - max_rows).max(0);
+    if dry_run || excess == 0 {
+        return Ok(excess);
+    }
+    connection
+        .execute(
+            &format!(
+                "DELETE FROM {table} WHERE id IN (
+                   SELECT id FROM {table} ORDER BY created_at_ms ASC, rowid ASC LIMIT ?1
+                 )"
+            ),
+            params![excess],
+        )
+        .map_err(|e| format!("Failed to prune {table}: {e}"))?;
+    Ok(excess)
+}
+
+pub fn list_provider_traces(
+    connection: &Connection,
+    limit: usize,
+) -> Result<Vec<ProviderTraceRecord>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT id, correlation_id, provider_kind, model, status, request_input,
+                    response_text, error_message, latency_ms, created_at_ms
+             FROM provider_traces
+             ORDER BY created_at_ms DESC, rowid DESC
+             LIMIT ?1",
+        )
+        .map_err(|e| format!("Failed to prepare provider trace query: {e}"))?;
+    let rows = stmt
+        .query_map(
+            params![limit.clamp(1, PROVIDER_TRACE_MAX_ROWS as usize) as i64],
+            |row| {
+                Ok(ProviderTraceRecord {
+                    id: row.get(0)?,
+                    correlation_id: row.get(1)?,
+                    provider_kind: row.get(2)?,
+                    model: row.get(3)?,
+                    status: row.get(4)?,
+                    request_input: row.get(5)?,
+                    response_text: row.get(6)?,
+                    error_message: row.get(7)?,
+                    latency_ms: row.get(8)?,
+                    created_at_ms: row.get(9)?,
+                })
+            },
+        )
+        .map_err(|e| format!("Failed to query provider traces: {e}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse provider traces: {e}"))
+}
+
+/// Keeps roughly a day of background cycles at the default 60s watcher poll.
+pub const RUNNER_CYCLE_LOG_MAX_ROWS: i64 = 2_000;
+
+/// Appends a cycle summary and prunes the log to `RUNNER_CYCLE_LOG_MAX_ROWS`.
+pub fn insert_runner_cycle_log(
+    connection: &Connection,
+    record: &RunnerCycleLogRecord,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO runner_cycle_log (
+               id, trigger, watcher_status, relay_sync_status, providers_polled, fetched,
+               deduped, started_runs, failed, resumed_due_runs, relay_decisions_applied,
+               missed_runs_detected, catch_up_cycles_run, created_at_ms
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            params![
+                record.id,
+                record.trigger,
+                record.watcher_status,
+                record.relay_sync_status,
+                record.providers_polled,
+                record.fetched,
+                record.deduped,
+                record.started_runs,
+                record.failed,
+                record.resumed_due_runs,
+                record.relay_decisions_applied,
+                record.missed_runs_detected,
+                record.catch_up_cycles_run,
+                record.created_at_ms,
+            ],
+        )
+        .map_err(|e| format!("Failed to record runner cycle: {e}"))?;
+    connection
+        .execute(
+            "DELETE FROM runner_cycle_log
+             WHERE id NOT IN (
+               SELECT id FROM runner_cycle_log
+               ORDER BY created_at_ms DESC, rowid DESC
+               LIMIT ?1
+             )",
+            params![RUNNER_CYCLE_LOG_MAX_ROWS],
+        )
+        .map_err(|e| format!("Failed to prune runner cycle log: {e}"))?;
+    Ok(())
+}
+
+pub fn list_runner_cycle_log(
+    connection: &Connection,
+    limit: usize,
+) -> Result<Vec<RunnerCycleLogRecord>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT id, trigger, watcher_status, relay_sync_status, providers_polled, fetched,
+                    deduped, started_runs, failed, resumed_due_runs, relay_decisions_applied,
+                    missed_runs_detected, catch_up_cycles_run, created_at_ms
+             FROM runner_cycle_log
+             ORDER BY created_at_ms DESC, rowid DESC
+             LIMIT ?1",
+        )
+        .map_err(|e| format!("Failed to prepare runner cycle query: {e}"))?;
+    let rows = stmt
+        .query_map(params![limit.clamp(1, 500) as i64], |row| {
+            Ok(RunnerCycleLogRecord {
+                id: row.get(0)?,
+                trigger: row.get(1)?,
+                watcher_status: row.get(2)?,
+                relay_sync_status: row.get(3)?,
+                providers_polled: row.get(4)?,
+                fetched: row.get(5)?,
+                deduped: row.get(6)?,
+                started_runs: row.get(7)?,
+                failed: row.get(8)?,
+                resumed_due_runs: row.get(9)?,
+                relay_decisions_applied: row.get(10)?,
+                missed_runs_detected: row.get(11)?,
+                catch_up_cycles_run: row.get(12)?,
+                created_at_ms: row.get(13)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query runner cycles: {e}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse runner cycles: {e}"))
+}
+
+/// Statuses a relay callback event moves through.
+pub const RELAY_CALLBACK_EVENT_STATUSES: &[&str] = &["received", "applied"];
+
+/// Lists recent relay callback events, newest first. A blank status lists every event; an
+/// unknown one is rejected.
+pub fn list_relay_callback_events(
+    connection: &Connection,
+    status: Option<&str>,
+    limit: usize,
+) -> Result<Vec<RelayCallbackEventRecord>, String> {
+    let status = status
+        .map(|v| v.trim().to_ascii_lowercase())
+        .filter(|v| !v.is_empty());
+    if let Some(status) = status.as_deref() {
+        if !RELAY_CALLBACK_EVENT_STATUSES.contains(&status) {
+            return Err(format!(
+                "Unknown relay callback status '{status}'. Use one of: {}.",
+                RELAY_CALLBACK_EVENT_STATUSES.join(", ")
+            ));
+        }
+    }
+    let mut stmt = connection
+        .prepare(
+            "SELECT request_id, approval_id, decision, status, channel, actor_label, created_at_ms
+             FROM relay_callback_events
+             WHERE (?1 IS NULL OR status = ?1)
+             ORDER BY created_at_ms DESC, rowid DESC
+             LIMIT ?2",
+        )
+        .map_err(|e| format!("Failed to prepare relay callback query: {e}"))?;
+    let rows = stmt
+        .query_map(params![status, limit.clamp(1, 500) as i64], |row| {
+            Ok(RelayCallbackEventRecord {
+                request_id: row.get(0)?,
+                approval_id: row.get(1)?,
+                decision: row.get(2)?,
+                status: row.get(3)?,
+                channel: row.get(4)?,
+                actor_label: row.get(5)?,
+                created_at_ms: row.get(6)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query relay callback events: {e}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse relay callback events: {e}"))
+}
@@ -17,6 +17,7 @@ pub enum RunHealthStatus {
     WaitingForClarification,
     RetryingTransient,
     RetryingStuck,
+    Paused,
     PolicyBlocked,
     ProviderMisconfigured,
     SourceUnreachable,
@@ -33,6 +34,7 @@ impl RunHealthStatus {
             Self::WaitingForClarification => "waiting_for_clarification",
             Self::RetryingTransient => "retrying_transient",
             Self::RetryingStuck => "retrying_stuck",
+            Self::Paused => "paused",
             Self::PolicyBlocked => "policy_blocked",
             Self::ProviderMisconfigured => "provider_misconfigured",
             Self::SourceUnreachable => "source_unreachable",
@@ -66,12 +68,27 @@ pub struct RunDiagnosticRecord {
     pub created_at_ms: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RunExplanation {
+    pub run_id: String,
+    pub run_state: String,
+    pub cause_code: String,
+    pub cause: String,
+    pub suggestion: String,
+    pub retryable: bool,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApplyInterventionInput {
     pub run_id: String,
+    #[serde(default)]
     pub kind: String,
     pub answer_text: Option<String>,
+    /// One-click preset that expands into a run mutation server-side; takes precedence over `kind`.
+    #[serde(default)]
+    pub preset: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -113,6 +130,178 @@ pub fn list_run_diagnostics(
     Ok(out)
 }
 
+/// Plain-language explanation of why a run stopped (or where it is waiting) and what to do next.
+pub fn explain_run(connection: &Connection, run_id: &str) -> Result<RunExplanation, String> {
+    let run: Option<(String, Option<String>, i64, i64)> = connection
+        .query_row(
+            "SELECT state, COALESCE(pause_reason, failure_reason), retry_count, max_retries FROM runs WHERE id = ?1",
+            params![run_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to load run: {e}"))?;
+    let Some((state, failure_reason, retry_count, max_retries)) = run else {
+        return Err("Run not found.".to_string());
+    };
+    // Older rows may not carry a failure_reason; fall back to the last failure activity or guard deny.
+    let last_failure_activity: Option<String> = connection
+        .query_row(
+            "SELECT user_message FROM activities
+             WHERE run_id = ?1 AND to_state IN ('retrying', 'failed', 'blocked')
+             ORDER BY created_at DESC LIMIT 1",
+            params![run_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to load run activity: {e}"))?;
+    let last_guard_deny: Option<String> = connection
+        .query_row(
+            "SELECT reason FROM primitive_guard_log
+             WHERE run_id = ?1 AND decision = 'deny'
+             ORDER BY created_at_ms DESC LIMIT 1",
+            params![run_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to load guard log: {e}"))?;
+    let reason = failure_reason
+        .filter(|r| !r.trim().is_empty())
+        .or(last_failure_activity)
+        .or(last_guard_deny)
+        .unwrap_or_default();
+
+    let (cause_code, cause, suggestion, retryable) =
+        explain_state(&state, &reason, retry_count, max_retries);
+    Ok(RunExplanation {
+        run_id: run_id.to_string(),
+        run_state: state,
+        cause_code: cause_code.to_string(),
+        cause,
+        suggestion: suggestion.to_string(),
+        retryable,
+    })
+}
+
+fn explain_state(
+    state: &str,
+    reason: &str,
+    retry_count: i64,
+    max_retries: i64,
+) -> (&'static str, String, &'static str, bool) {
+    let reason_lower = reason.to_ascii_lowercase();
+    let cause_or = |fallback: &str| {
+        if reason.trim().is_empty() {
+            fallback.to_string()
+        } else {
+            truncate_summary(reason)
+        }
+    };
+    match state {
+        "succeeded" => (
+            "completed",
+            "The run finished successfully.".to_string(),
+            "No action needed.",
+            false,
+        ),
+        "canceled" => (
+            "canceled",
+            "The run was canceled before it finished.".to_string(),
+            "Start a new run when you're ready.",
+            false,
+        ),
+        "needs_approval" => (
+            "approval_pending",
+            "The run is waiting for you to approve a step.".to_string(),
+            "Review the pending approval to continue.",
+            false,
+        ),
+        "needs_clarification" => (
+            "clarification_pending",
+            "The run needs one more detail from you.".to_string(),
+            "Answer the open question to continue.",
+            false,
+        ),
+        "ready" | "running" => (
+            "in_progress",
+            "The run is still in progress.".to_string(),
+            "No action needed yet.",
+            false,
+        ),
+        "retrying" if is_rate_limited(&reason_lower) => (
+            "provider_rate_limited",
+            cause_or("The provider is throttling requests."),
+            "Terminus will retry with backoff. If this keeps happening, run this Autopilot less often.",
+            true,
+        ),
+        "paused" => (
+            "paused",
+            cause_or("The run is waiting out a deferral, such as quiet hours."),
+            "Terminus will resume it automatically when the pause ends.",
+            false,
+        ),
+        "retrying" => (
+            "retrying_transient",
+            cause_or("The run hit a temporary failure."),
+            "Terminus will retry automatically; no action needed unless retries run out.",
+            true,
+        ),
+        _ if is_cap_block(&reason_lower) => (
+            "spend_cap",
+            cause_or("The run was stopped by a spend cap."),
+            "Reduce the run's scope or raise the spend caps in Settings, then retry.",
+            false,
+        ),
+        _ if is_rate_limited(&reason_lower) => (
+            "provider_rate_limited",
+            cause_or("The provider rejected requests for being too frequent."),
+            "Wait a few minutes and retry. Consider running this Autopilot less often.",
+            true,
+        ),
+        _ if is_allowlist_block(&reason_lower) => (
+            "allowlist",
+            cause_or("A website or API host is outside this Autopilot's allowlist."),
+            "Add the domain to the Autopilot's allowed domains (use *.example.com for subdomains), then retry.",
+            false,
+        ),
+        _ if is_signature_failure(&reason_lower) => (
+            "signature_invalid",
+            cause_or("A webhook signature did not verify."),
+            "Make sure the sender signs requests with the current webhook secret, or rotate the secret and update the sender.",
+            false,
+        ),
+        _ if is_provider_auth(&reason_lower) => (
+            "provider_auth",
+            cause_or("Provider credentials or configuration look invalid."),
+            "Re-enter the provider API key in Settings and check the model name.",
+            false,
+        ),
+        _ if is_source_type_changed(&reason_lower) || is_source_limit_exceeded(&reason_lower) => (
+            "source_unreadable",
+            cause_or("A monitored page could not be read as text."),
+            "Point the Autopilot at a smaller text page or update the monitored URL.",
+            false,
+        ),
+        _ if is_source_unreachable(&reason_lower) => (
+            "source_unreachable",
+            cause_or("A configured source could not be reached."),
+            "Check that the URL opens in your browser, then retry.",
+            true,
+        ),
+        _ if is_policy_blocked(&reason_lower) => (
+            "policy_block",
+            cause_or("Terminus blocked an action due to a safety rule."),
+            "Review the plan's steps and approvals; regenerate the plan if it uses an action that isn't allowed.",
+            false,
+        ),
+        _ => (
+            "failed_unclassified",
+            cause_or("The run failed for a reason Terminus couldn't classify."),
+            "Check the run's activity log for details, then retry.",
+            retry_count < max_retries,
+        ),
+    }
+}
+
 pub fn apply_intervention(
     connection: &mut Connection,
     input: ApplyInterventionInput,
@@ -121,6 +310,14 @@ pub fn apply_intervention(
     if run_id.is_empty() {
         return Err("Run ID is required.".to_string());
     }
+    if let Some(preset) = input
+        .preset
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    {
+        return apply_intervention_preset(connection, &run_id, preset);
+    }
     let kind = input.kind.trim().to_string();
     if kind.is_empty() {
         return Err("Intervention kind is required.".to_string());
@@ -242,6 +439,126 @@ pub fn apply_intervention(
     })
 }
 
+fn apply_intervention_preset(
+    connection: &mut Connection,
+    run_id: &str,
+    preset: &str,
+) -> Result<ApplyInterventionResult, String> {
+    let run = RunnerEngine::get_run(connection, run_id).map_err(|e| e.to_string())?;
+    let mut updated_state = None;
+    let message = match preset {
+        "widen_allowlist_redirect_host" => {
+            if run.state.is_terminal() {
+                return Err(
+                    "This run has already finished; its allowed domains can't be widened."
+                        .to_string(),
+                );
+            }
+            let pending: Option<(String, Option<String>)> = connection
+                .query_row(
+                    "SELECT c.id, r.blocked_redirect_host
+                     FROM clarifications c JOIN runs r ON r.id = c.run_id
+                     WHERE c.run_id = ?1 AND c.status = 'pending' AND c.field_key = 'web_redirect_host'
+                     ORDER BY c.created_at_ms DESC LIMIT 1",
+                    params![run_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()
+                .map_err(|e| format!("Failed to load blocked redirect: {e}"))?;
+            let (clarification_id, host) = match pending {
+                Some((id, Some(host))) => (id, host),
+                _ => {
+                    return Err("This run is not waiting on an off-allowlist redirect.".to_string())
+                }
+            };
+            let host = crate::web::normalize_allowlist_entry(&host)?;
+            if run
+                .plan
+                .web_allowed_domains
+                .iter()
+                .any(|entry| crate::web::host_matches_allowlist(&host, std::slice::from_ref(entry)))
+            {
+                return Err(format!("{host} is already in this run's allowed domains."));
+            }
+            let updated = RunnerEngine::submit_clarification_answer(
+                connection,
+                &clarification_id,
+                &serde_json::json!({ "value": host }).to_string(),
+            )
+            .map_err(|e| e.to_string())?;
+            updated_state = Some(updated.state.as_str().to_string());
+            format!("Added {host} to this run's allowed domains and resumed the run.")
+        }
+        "raise_run_soft_cap" => {
+            if run.state.is_terminal() {
+                return Err(
+                    "This run has already finished; its spend cap can't be raised.".to_string(),
+                );
+            }
+            let soft_cap_approval_id: Option<String> = connection
+                .query_row(
+                    "SELECT id FROM approvals
+                     WHERE run_id = ?1 AND status = 'pending' AND step_id = '__soft_cap__'
+                     ORDER BY created_at ASC LIMIT 1",
+                    params![run_id],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| format!("Failed to load spend approval: {e}"))?;
+            if let Some(approval_id) = soft_cap_approval_id {
+                let updated =
+                    RunnerEngine::approve(connection, &approval_id).map_err(|e| e.to_string())?;
+                updated_state = Some(updated.state.as_str().to_string());
+                "Raised this run's soft cap and resumed the run. Hard caps still apply.".to_string()
+            } else if run.soft_cap_approved {
+                return Err("This run's soft cap is already raised.".to_string());
+            } else {
+                connection
+                    .execute(
+                        "UPDATE runs SET soft_cap_approved = 1, updated_at = ?2 WHERE id = ?1",
+                        params![run_id, now_ms()],
+                    )
+                    .map_err(|e| format!("Failed to raise run soft cap: {e}"))?;
+                "Raised this run's soft cap. Hard caps still apply.".to_string()
+            }
+        }
+        "skip_failing_step" => {
+            if run.state != RunState::Retrying {
+                return Err("Only a step that is currently retrying can be skipped.".to_string());
+            }
+            let step = run
+                .plan
+                .steps
+                .get(run.current_step_index as usize)
+                .ok_or_else(|| "This run has no failing step to skip.".to_string())?;
+            let skipped_message = format!("Skipped step '{}' at the user's request.", step.label);
+            RunnerEngine::transition_state_with_activity(
+                connection,
+                run_id,
+                run.state,
+                RunState::Ready,
+                "step_skipped",
+                &skipped_message,
+                None,
+                Some(run.current_step_index + 1),
+            )
+            .map_err(|e| e.to_string())?;
+            let updated = RunnerEngine::run_tick(connection, run_id).map_err(|e| e.to_string())?;
+            updated_state = Some(updated.state.as_str().to_string());
+            format!("Skipped step '{}' and resumed the run.", step.label)
+        }
+        _ => return Err("That intervention preset is not available in Terminus.".to_string()),
+    };
+
+    log_intervention(connection, run_id, &run.autopilot_id, preset, &message)?;
+    Ok(ApplyInterventionResult {
+        ok: true,
+        run_id: run_id.to_string(),
+        message,
+        updated_run_state: updated_state,
+    })
+}
+
 fn load_run_diagnostic_seeds(
     connection: &Connection,
     limit: usize,
@@ -308,82 +625,103 @@ fn derive_run_diagnostic(seed: &RunDiagnosticSeed) -> RunDiagnosticRecord {
     let state = seed.state.as_str();
     let failure = seed.failure_reason.clone().unwrap_or_default();
     let failure_lower = failure.to_ascii_lowercase();
-    let (health, reason_code, summary) =
-        if state == "needs_approval" || seed.pending_approval_id.is_some() {
-            (
-                RunHealthStatus::WaitingForApproval,
-                "approval_pending".to_string(),
-                "A write/send action is waiting for your approval.".to_string(),
-            )
-        } else if state == "needs_clarification" || seed.pending_clarification_id.is_some() {
-            (
-                RunHealthStatus::WaitingForClarification,
-                "clarification_pending".to_string(),
-                "One missing detail is blocking progress until you answer.".to_string(),
-            )
-        } else if matches!(state, "succeeded" | "canceled") {
+    let (health, reason_code, summary) = if state == "needs_approval"
+        || seed.pending_approval_id.is_some()
+    {
+        (
+            RunHealthStatus::WaitingForApproval,
+            "approval_pending".to_string(),
+            "A write/send action is waiting for your approval.".to_string(),
+        )
+    } else if state == "needs_clarification" || seed.pending_clarification_id.is_some() {
+        (
+            RunHealthStatus::WaitingForClarification,
+            "clarification_pending".to_string(),
+            "One missing detail is blocking progress until you answer.".to_string(),
+        )
+    } else if matches!(state, "succeeded" | "canceled") {
+        (
+            RunHealthStatus::Completed,
+            "terminal_complete".to_string(),
+            "Run reached a terminal state.".to_string(),
+        )
+    } else if state == "paused" {
+        (
+            RunHealthStatus::Paused,
+            "paused".to_string(),
+            "The run is paused and will resume automatically when its wait ends.".to_string(),
+        )
+    } else if state == "retrying" && is_rate_limited(&failure_lower) {
+        (
+            RunHealthStatus::ResourceThrottled,
+            "provider_rate_limited".to_string(),
+            "Provider or source is throttling requests. Terminus will retry with backoff."
+                .to_string(),
+        )
+    } else if state == "retrying" {
+        if seed.retry_count >= 2 || seed.retry_count >= seed.max_retries.saturating_sub(1) {
             (
-                RunHealthStatus::Completed,
-                "terminal_complete".to_string(),
-                "Run reached a terminal state.".to_string(),
+                RunHealthStatus::RetryingStuck,
+                "retrying_stuck".to_string(),
+                "The run is retrying repeatedly and may need intervention.".to_string(),
             )
-        } else if state == "retrying" && is_rate_limited(&failure_lower) {
+        } else {
             (
-                RunHealthStatus::ResourceThrottled,
-                "provider_rate_limited".to_string(),
-                "Provider or source is throttling requests. Terminus will retry with backoff."
+                RunHealthStatus::RetryingTransient,
+                "retrying_transient".to_string(),
+                "The run hit a retryable failure and is waiting for the next retry window."
                     .to_string(),
             )
-        } else if state == "retrying" {
-            if seed.retry_count >= 2 || seed.retry_count >= seed.max_retries.saturating_sub(1) {
-                (
-                    RunHealthStatus::RetryingStuck,
-                    "retrying_stuck".to_string(),
-                    "The run is retrying repeatedly and may need intervention.".to_string(),
-                )
+        }
+    } else if matches!(state, "failed" | "blocked") && is_provider_auth(&failure_lower) {
+        (
+            RunHealthStatus::ProviderMisconfigured,
+            "provider_auth".to_string(),
+            "Provider credentials or configuration look invalid.".to_string(),
+        )
+    } else if matches!(state, "failed" | "blocked") && is_source_type_changed(&failure_lower) {
+        (
+            RunHealthStatus::SourceUnreachable,
+            "source_content_type".to_string(),
+            "A monitored page stopped returning readable text (for example it now serves an image or PDF)."
+                .to_string(),
+        )
+    } else if matches!(state, "failed" | "blocked") && is_source_limit_exceeded(&failure_lower) {
+        (
+            RunHealthStatus::SourceUnreachable,
+            "source_limit_exceeded".to_string(),
+            "A website response was too large or too slow for this Autopilot's fetch limits."
+                .to_string(),
+        )
+    } else if matches!(state, "failed" | "blocked") && is_source_unreachable(&failure_lower) {
+        (
+            RunHealthStatus::SourceUnreachable,
+            "source_unreachable".to_string(),
+            "A configured web/source input could not be reached.".to_string(),
+        )
+    } else if matches!(state, "failed" | "blocked") && is_policy_blocked(&failure_lower) {
+        (
+            RunHealthStatus::PolicyBlocked,
+            "policy_block".to_string(),
+            "Terminus blocked an action due to a safety or policy rule.".to_string(),
+        )
+    } else if matches!(state, "failed" | "blocked") {
+        (
+            RunHealthStatus::FailedUnclassified,
+            "failed_unclassified".to_string(),
+            if failure.is_empty() {
+                "The run failed for a reason that could not be classified yet.".to_string()
             } else {
-                (
-                    RunHealthStatus::RetryingTransient,
-                    "retrying_transient".to_string(),
-                    "The run hit a retryable failure and is waiting for the next retry window."
-                        .to_string(),
-                )
-            }
-        } else if matches!(state, "failed" | "blocked") && is_provider_auth(&failure_lower) {
-            (
-                RunHealthStatus::ProviderMisconfigured,
-                "provider_auth".to_string(),
-                "Provider credentials or configuration look invalid.".to_string(),
-            )
-        } else if matches!(state, "failed" | "blocked") && is_source_unreachable(&failure_lower) {
-            (
-                RunHealthStatus::SourceUnreachable,
-                "source_unreachable".to_string(),
-                "A configured web/source input could not be reached.".to_string(),
-            )
-        } else if matches!(state, "failed" | "blocked") && is_policy_blocked(&failure_lower) {
-            (
-                RunHealthStatus::PolicyBlocked,
-                "policy_block".to_string(),
-                "Terminus blocked an action due to a safety or policy rule.".to_string(),
-            )
-        } else if matches!(state, "failed" | "blocked") {
-            (
-                RunHealthStatus::FailedUnclassified,
-                "failed_unclassified".to_string(),
-                if failure.is_empty() {
-                    "The run failed for a reason that could not be classified yet.".to_string()
-                } else {
-                    truncate_summary(&failure)
-                },
-            )
-        } else {
-            (
-                RunHealthStatus::HealthyRunning,
-                "in_progress".to_string(),
-                "Run is progressing within normal bounds.".to_string(),
-            )
-        };
+                truncate_summary(&failure)
+            },
+        )
+    } else {
+        (
+            RunHealthStatus::HealthyRunning,
+            "in_progress".to_string(),
+            "Run is progressing within normal bounds.".to_string(),
+        )
+    };
 
     let suggestions = build_suggestions(seed, health);
     RunDiagnosticRecord {
@@ -460,6 +798,7 @@ fn build_suggestions(
         }),
         RunHealthStatus::Completed
         | RunHealthStatus::HealthyRunning
+        | RunHealthStatus::Paused
         | RunHealthStatus::FailedUnclassified => {}
     }
 
@@ -592,6 +931,31 @@ fn is_source_unreachable(reason: &str) -> bool {
         || reason.contains("network")
 }
 
+fn is_source_type_changed(reason: &str) -> bool {
+    let reason = reason.to_ascii_lowercase();
+    reason.contains("changed type") || reason.contains("can't read as text")
+}
+
+fn is_source_limit_exceeded(reason: &str) -> bool {
+    let reason = reason.to_ascii_lowercase();
+    reason.contains("fetch size limit") || reason.contains("fetch time limit")
+}
+
+fn is_cap_block(reason: &str) -> bool {
+    let reason = reason.to_ascii_lowercase();
+    reason.contains("hard cap") || reason.contains("cap is reached") || reason.contains("spend cap")
+}
+
+fn is_allowlist_block(reason: &str) -> bool {
+    let reason = reason.to_ascii_lowercase();
+    reason.contains("allowlist")
+}
+
+fn is_signature_failure(reason: &str) -> bool {
+    let reason = reason.to_ascii_lowercase();
+    reason.contains("signature")
+}
+
 fn is_policy_blocked(reason: &str) -> bool {
     let reason = reason.to_ascii_lowercase();
     reason.contains("not allowed")
@@ -627,7 +991,107 @@ fn make_id(prefix: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{is_policy_blocked, is_provider_auth, is_rate_limited, is_source_unreachable};
+    use super::{
+        apply_intervention, explain_run, explain_state, is_policy_blocked, is_provider_auth,
+        is_rate_limited, is_source_limit_exceeded, is_source_type_changed, is_source_unreachable,
+        ApplyInterventionInput,
+    };
+    use crate::db::bootstrap_schema;
+    use crate::runner::RunnerEngine;
+    use crate::schema::{AutopilotPlan, PlanStep, PrimitiveId, ProviderId, RecipeKind, RiskTier};
+    use rusqlite::{params, Connection};
+
+    fn setup_preset_run(idempotency_key: &str) -> (Connection, String) {
+        std::env::set_var("TERMINUS_TRANSPORT", "mock");
+        let mut conn = Connection::open_in_memory().expect("open memory db");
+        bootstrap_schema(&mut conn).expect("bootstrap schema");
+        let mut plan = AutopilotPlan::from_intent(
+            RecipeKind::Custom,
+            "Preset test".to_string(),
+            ProviderId::OpenAi,
+        );
+        plan.allowed_primitives = vec![PrimitiveId::WriteOutcomeDraft];
+        plan.steps = vec![PlanStep {
+            id: "step_1".to_string(),
+            label: "Write draft outcome".to_string(),
+            primitive: PrimitiveId::WriteOutcomeDraft,
+            requires_approval: false,
+            risk_tier: RiskTier::Low,
+        }];
+        let run = RunnerEngine::start_run(&mut conn, "auto_preset", plan, idempotency_key, 2)
+            .expect("start run");
+        (conn, run.id)
+    }
+
+    fn preset_input(run_id: &str, preset: &str) -> ApplyInterventionInput {
+        ApplyInterventionInput {
+            run_id: run_id.to_string(),
+            kind: String::new(),
+            answer_text: None,
+            preset: Some(preset.to_string()),
+        }
+    }
+
+    #[test]
+    fn explains_representative_failure_states() {
+        let (code, _, suggestion, retryable) =
+            explain_state("failed", "Provider returned HTTP 429 rate limit", 2, 2);
+        assert_eq!(code, "provider_rate_limited");
+        assert!(retryable);
+        assert!(suggestion.contains("Wait"));
+
+        let (code, cause, suggestion, retryable) = explain_state(
+            "failed",
+            "This website is not in the allowlist for this Autopilot.",
+            0,
+            2,
+        );
+        assert_eq!(code, "allowlist");
+        assert!(cause.contains("allowlist"));
+        assert!(suggestion.contains("allowed domains"));
+        assert!(!retryable);
+
+        let (code, _, _, retryable) = explain_state("failed", "Webhook signature mismatch", 0, 2);
+        assert_eq!(code, "signature_invalid");
+        assert!(!retryable);
+
+        let (code, _, suggestion, _) = explain_state(
+            "blocked",
+            "This run is blocked before execution: projected cost is about $1.20, over the per-run hard cap of $0.90.",
+            0,
+            2,
+        );
+        assert_eq!(code, "spend_cap");
+        assert!(suggestion.contains("spend caps"));
+
+        let (code, _, _, retryable) = explain_state("retrying", "", 1, 3);
+        assert_eq!(code, "retrying_transient");
+        assert!(retryable);
+    }
+
+    #[test]
+    fn explain_run_reads_failure_reason_from_run() {
+        let mut conn = Connection::open_in_memory().expect("open memory db");
+        bootstrap_schema(&mut conn).expect("bootstrap schema");
+        conn.execute(
+            "INSERT INTO autopilots (id, name, created_at) VALUES ('auto_explain', 'Explain', 0)",
+            [],
+        )
+        .expect("autopilot");
+        conn.execute(
+            "INSERT INTO runs (id, autopilot_id, idempotency_key, plan_json, state, failure_reason, created_at, updated_at)
+             VALUES ('run_explain', 'auto_explain', 'idem_explain', '{}', 'failed', ?1, 0, 0)",
+            params!["Invalid API key"],
+        )
+        .expect("run");
+
+        let explanation = explain_run(&conn, "run_explain").expect("explain");
+        assert_eq!(explanation.run_state, "failed");
+        assert_eq!(explanation.cause_code, "provider_auth");
+        assert_eq!(explanation.cause, "Invalid API key");
+        assert!(!explanation.retryable);
+        assert!(explain_run(&conn, "run_missing").is_err());
+    }
 
     #[test]
     fn classifies_reason_patterns() {
@@ -637,5 +1101,142 @@ mod tests {
             "Could not read source due to timeout"
         ));
         assert!(is_policy_blocked("This action is not allowed by policy"));
+        assert!(is_source_limit_exceeded(
+            &crate::web::WebFetchError::TooLarge.to_string()
+        ));
+        assert!(is_source_limit_exceeded(
+            &crate::web::WebFetchError::Timeout.to_string()
+        ));
+        assert!(is_source_type_changed(
+            &crate::web::WebFetchError::UnsupportedContentType("application/pdf".to_string())
+                .to_string()
+        ));
+    }
+
+    #[test]
+    fn skip_failing_step_preset_advances_past_retrying_step() {
+        let (mut conn, run_id) = setup_preset_run("preset_skip");
+        conn.execute(
+            "UPDATE runs SET state = 'retrying', retry_count = 1, next_retry_at_ms = ?2,
+             failure_reason = 'Provider timed out' WHERE id = ?1",
+            params![&run_id, i64::MAX],
+        )
+        .expect("mark retrying");
+
+        let result = apply_intervention(&mut conn, preset_input(&run_id, "skip_failing_step"))
+            .expect("skip step");
+        assert!(result.ok);
+        assert_eq!(result.updated_run_state.as_deref(), Some("succeeded"));
+
+        let run = RunnerEngine::get_run(&conn, &run_id).expect("run");
+        assert_eq!(run.current_step_index, 1);
+        assert_eq!(run.next_retry_at_ms, None);
+        let skipped: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM activities WHERE run_id = ?1 AND activity_type = 'step_skipped'",
+                params![&run_id],
+                |row| row.get(0),
+            )
+            .expect("count activities");
+        assert_eq!(skipped, 1);
+
+        let err = apply_intervention(&mut conn, preset_input(&run_id, "skip_failing_step"))
+            .expect_err("terminal run cannot skip");
+        assert!(err.contains("retrying"));
+    }
+
+    #[test]
+    fn raise_run_soft_cap_preset_sets_flag_and_resolves_pending_spend_approval() {
+        let (mut conn, run_id) = setup_preset_run("preset_cap_flag");
+        conn.execute(
+            "UPDATE runs SET state = 'retrying', next_retry_at_ms = ?2 WHERE id = ?1",
+            params![&run_id, i64::MAX],
+        )
+        .expect("mark retrying");
+        let result = apply_intervention(&mut conn, preset_input(&run_id, "raise_run_soft_cap"))
+            .expect("raise cap");
+        assert_eq!(result.updated_run_state, None);
+        let run = RunnerEngine::get_run(&conn, &run_id).expect("run");
+        assert!(run.soft_cap_approved);
+        assert_eq!(run.state.as_str(), "retrying");
+        assert!(
+            apply_intervention(&mut conn, preset_input(&run_id, "raise_run_soft_cap")).is_err()
+        );
+
+        let (mut conn, run_id) = setup_preset_run("preset_cap_pending");
+        conn.execute(
+            "UPDATE runs SET state = 'needs_approval' WHERE id = ?1",
+            params![&run_id],
+        )
+        .expect("mark needs approval");
+        conn.execute(
+            "INSERT INTO approvals (id, run_id, step_id, status, preview, created_at, updated_at)
+             VALUES ('approval_cap', ?1, '__soft_cap__', 'pending', 'Spend approval', 0, 0)",
+            params![&run_id],
+        )
+        .expect("insert approval");
+        let result = apply_intervention(&mut conn, preset_input(&run_id, "raise_run_soft_cap"))
+            .expect("raise cap via approval");
+        assert_eq!(result.updated_run_state.as_deref(), Some("succeeded"));
+        let run = RunnerEngine::get_run(&conn, &run_id).expect("run");
+        assert!(run.soft_cap_approved);
+        let status: String = conn
+            .query_row(
+                "SELECT status FROM approvals WHERE id = 'approval_cap'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("approval status");
+        assert_eq!(status, "approved");
+    }
+
+    #[test]
+    fn widen_redirect_preset_allows_the_recorded_host_and_resumes() {
+        let (mut conn, run_id) = setup_preset_run("preset_widen_redirect");
+        conn.execute(
+            "UPDATE runs SET state = 'failed', blocked_redirect_host = 'cdn.example.com'
+             WHERE id = ?1",
+            params![&run_id],
+        )
+        .expect("mark failed");
+        let err = apply_intervention(
+            &mut conn,
+            preset_input(&run_id, "widen_allowlist_redirect_host"),
+        )
+        .expect_err("finished run cannot be widened");
+        assert!(err.contains("already finished"));
+
+        conn.execute(
+            "UPDATE runs SET state = 'needs_clarification' WHERE id = ?1",
+            params![&run_id],
+        )
+        .expect("mark waiting");
+        conn.execute(
+            "INSERT INTO clarifications
+              (id, run_id, step_id, field_key, question, options_json, answer_json, status, created_at_ms, updated_at_ms)
+             VALUES ('clar_redirect', ?1, 'step_1', 'web_redirect_host', 'Allow it?', '[\"cdn.example.com\"]', NULL, 'pending', 1, 1)",
+            params![&run_id],
+        )
+        .expect("insert clarification");
+
+        let result = apply_intervention(
+            &mut conn,
+            preset_input(&run_id, "widen_allowlist_redirect_host"),
+        )
+        .expect("widen allowlist");
+        assert_eq!(result.updated_run_state.as_deref(), Some("succeeded"));
+        let run = RunnerEngine::get_run(&conn, &run_id).expect("run");
+        assert!(run
+            .plan
+            .web_allowed_domains
+            .contains(&"cdn.example.com".to_string()));
+        let blocked: Option<String> = conn
+            .query_row(
+                "SELECT blocked_redirect_host FROM runs WHERE id = ?1",
+                params![&run_id],
+                |row| row.get(0),
+            )
+            .expect("blocked host");
+        assert_eq!(blocked, None);
     }
 }
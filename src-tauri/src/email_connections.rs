@@ -21,9 +21,34 @@ pub enum EffectorMode {
     LocalHttp,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+const EMAIL_LABEL_CACHE_TTL_MS: i64 = 60 * 60 * 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TriageAction {
     Archive,
+    /// Files the message under a user label (Gmail) or folder (Microsoft 365), by provider id.
+    MoveToLabel {
+        label_id: String,
+    },
+}
+
+impl TriageAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Archive => "archive",
+            Self::MoveToLabel { .. } => "move_to_label",
+        }
+    }
+}
+
+/// A label or folder the connected account can triage into, as last fetched from the provider.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailLabelRecord {
+    pub provider: String,
+    pub label_id: String,
+    pub name: String,
+    pub fetched_at_ms: i64,
 }
 
 #[derive(Debug, Clone)]
@@ -126,6 +151,13 @@ impl EmailProvider {
         }
     }
 
+    fn display_name(&self) -> &'static str {
+        match self {
+            Self::Gmail => "Gmail",
+            Self::Microsoft365 => "Microsoft 365",
+        }
+    }
+
     fn userinfo_url(&self) -> &'static str {
         match self {
             Self::Gmail => "https://www.googleapis.com/oauth2/v3/userinfo",
@@ -717,6 +749,42 @@ fn send_outbound_email_live(
     }
 }
 
+/// The endpoint and JSON body that apply `action` to one message. Gmail labels are added while
+/// the message leaves the inbox; Microsoft 365 moves the message into the folder.
+pub fn build_triage_mutation(
+    provider: EmailProvider,
+    provider_message_id: &str,
+    action: &TriageAction,
+) -> (String, Value) {
+    match provider {
+        EmailProvider::Gmail => {
+            let endpoint = format!(
+                "https://gmail.googleapis.com/gmail/v1/users/me/messages/{}/modify",
+                provider_message_id
+            );
+            let body = match action {
+                TriageAction::Archive => json!({ "removeLabelIds": ["INBOX"] }),
+                TriageAction::MoveToLabel { label_id } => json!({
+                    "addLabelIds": [label_id],
+                    "removeLabelIds": ["INBOX"],
+                }),
+            };
+            (endpoint, body)
+        }
+        EmailProvider::Microsoft365 => {
+            let endpoint = format!(
+                "https://graph.microsoft.com/v1.0/me/messages/{}/move",
+                provider_message_id
+            );
+            let destination = match action {
+                TriageAction::Archive => "archive",
+                TriageAction::MoveToLabel { label_id } => label_id.as_str(),
+            };
+            (endpoint, json!({ "destinationId": destination }))
+        }
+    }
+}
+
 fn apply_triage_action_live(
     connection: &Connection,
     provider: EmailProvider,
@@ -730,16 +798,13 @@ fn apply_triage_action_live(
         .build()
         .map_err(|_| EffectorError::retryable("Could not initialize secure network client."))?;
 
-    match (provider, action) {
-        (EmailProvider::Gmail, TriageAction::Archive) => {
-            let endpoint = format!(
-                "https://gmail.googleapis.com/gmail/v1/users/me/messages/{}/modify",
-                provider_message_id
-            );
+    let (endpoint, body) = build_triage_mutation(provider, provider_message_id, &action);
+    match provider {
+        EmailProvider::Gmail => {
             let response = client
                 .post(endpoint)
                 .bearer_auth(&token)
-                .json(&json!({"removeLabelIds": ["INBOX"]}))
+                .json(&body)
                 .send()
                 .map_err(|_| {
                     EffectorError::retryable(
@@ -757,15 +822,11 @@ fn apply_triage_action_live(
                 ));
             }
         }
-        (EmailProvider::Microsoft365, TriageAction::Archive) => {
-            let endpoint = format!(
-                "https://graph.microsoft.com/v1.0/me/messages/{}/move",
-                provider_message_id
-            );
+        EmailProvider::Microsoft365 => {
             let response = client
                 .post(endpoint)
                 .bearer_auth(&token)
-                .json(&json!({ "destinationId": "archive" }))
+                .json(&body)
                 .send()
                 .map_err(|_| {
                     EffectorError::retryable(
@@ -791,6 +852,163 @@ fn apply_triage_action_live(
     })
 }
 
+/// Labels (Gmail) or mail folders (Microsoft 365) for the connected account. The provider is
+/// only asked again once the cache is an hour old or `force_refresh` is set.
+pub fn list_email_labels(
+    connection: &Connection,
+    provider: EmailProvider,
+    force_refresh: bool,
+) -> Result<Vec<EmailLabelRecord>, String> {
+    let cached = load_cached_email_labels(connection, provider)?;
+    let fresh = cached
+        .first()
+        .is_some_and(|label| now_ms() - label.fetched_at_ms < EMAIL_LABEL_CACHE_TTL_MS);
+    if fresh && !force_refresh {
+        return Ok(cached);
+    }
+    let fetched = match current_effector_mode() {
+        EffectorMode::Mock => vec![
+            ("Label_receipts".to_string(), "Receipts".to_string()),
+            ("Label_newsletters".to_string(), "Newsletters".to_string()),
+        ],
+        EffectorMode::LocalHttp => {
+            let token = get_access_token(connection, provider)?;
+            fetch_email_labels_live(provider, &token)?
+        }
+    };
+    store_email_labels(connection, provider, &fetched, now_ms())?;
+    load_cached_email_labels(connection, provider)
+}
+
+/// Matches `target` against the account's labels by id or case-insensitive name, refreshing
+/// the cache once before giving up.
+pub fn resolve_email_label(
+    connection: &Connection,
+    provider: EmailProvider,
+    target: &str,
+) -> Result<EmailLabelRecord, String> {
+    let target = target.trim();
+    if target.is_empty() {
+        return Err("Choose a label or folder to file into.".to_string());
+    }
+    let find = |labels: Vec<EmailLabelRecord>| {
+        labels
+            .into_iter()
+            .find(|l| l.label_id == target || l.name.eq_ignore_ascii_case(target))
+    };
+    if let Some(label) = find(list_email_labels(connection, provider, false)?) {
+        return Ok(label);
+    }
+    find(list_email_labels(connection, provider, true)?).ok_or_else(|| {
+        format!(
+            "\"{target}\" is not a label or folder in your connected {} account.",
+            provider.display_name()
+        )
+    })
+}
+
+fn load_cached_email_labels(
+    connection: &Connection,
+    provider: EmailProvider,
+) -> Result<Vec<EmailLabelRecord>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT label_id, name, fetched_at_ms FROM email_label_cache
+             WHERE provider = ?1
+             ORDER BY name COLLATE NOCASE ASC",
+        )
+        .map_err(|e| format!("Failed to prepare label cache query: {e}"))?;
+    let rows = stmt
+        .query_map(params![provider.as_str()], |row| {
+            Ok(EmailLabelRecord {
+                provider: provider.as_str().to_string(),
+                label_id: row.get(0)?,
+                name: row.get(1)?,
+                fetched_at_ms: row.get(2)?,
+            })
+        })
+        .map_err(|e| format!("Failed to read label cache: {e}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse label cache: {e}"))
+}
+
+fn store_email_labels(
+    connection: &Connection,
+    provider: EmailProvider,
+    labels: &[(String, String)],
+    fetched_at_ms: i64,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "DELETE FROM email_label_cache WHERE provider = ?1",
+            params![provider.as_str()],
+        )
+        .map_err(|e| format!("Failed to clear label cache: {e}"))?;
+    for (label_id, name) in labels {
+        connection
+            .execute(
+                "INSERT OR REPLACE INTO email_label_cache (provider, label_id, name, fetched_at_ms)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![provider.as_str(), label_id, name, fetched_at_ms],
+            )
+            .map_err(|e| format!("Failed to cache label: {e}"))?;
+    }
+    Ok(())
+}
+
+fn fetch_email_labels_live(
+    provider: EmailProvider,
+    access_token: &str,
+) -> Result<Vec<(String, String)>, String> {
+    let client = Client::builder()
+        .timeout(std::time::Duration::from_secs(20))
+        .build()
+        .map_err(|_| "Could not initialize secure network client.".to_string())?;
+    let endpoint = match provider {
+        EmailProvider::Gmail => "https://gmail.googleapis.com/gmail/v1/users/me/labels",
+        EmailProvider::Microsoft365 => "https://graph.microsoft.com/v1.0/me/mailFolders?$top=100",
+    };
+    let response = client
+        .get(endpoint)
+        .bearer_auth(access_token)
+        .send()
+        .map_err(|_| "Could not reach your mail provider to list labels.".to_string())?;
+    if !response.status().is_success() {
+        return Err(
+            "Your mail provider did not return its labels. Reconnect and retry.".to_string(),
+        );
+    }
+    let json = response
+        .json::<Value>()
+        .map_err(|_| "Could not read the label list from your mail provider.".to_string())?;
+    let labels = match provider {
+        EmailProvider::Gmail => json.get("labels").and_then(|v| v.as_array()).map(|items| {
+            items
+                .iter()
+                .filter(|item| item.get("type").and_then(|v| v.as_str()) == Some("user"))
+                .filter_map(|item| {
+                    Some((
+                        item.get("id")?.as_str()?.to_string(),
+                        item.get("name")?.as_str()?.to_string(),
+                    ))
+                })
+                .collect::<Vec<_>>()
+        }),
+        EmailProvider::Microsoft365 => json.get("value").and_then(|v| v.as_array()).map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    Some((
+                        item.get("id")?.as_str()?.to_string(),
+                        item.get("displayName")?.as_str()?.to_string(),
+                    ))
+                })
+                .collect::<Vec<_>>()
+        }),
+    };
+    Ok(labels.unwrap_or_default())
+}
+
 fn load_oauth_config(
     connection: &Connection,
     provider: EmailProvider,
@@ -5,6 +5,13 @@ use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+/// Gmail watches last seven days; renew this long before they lapse unless configured otherwise.
+pub const DEFAULT_WATCH_RENEWAL_WINDOW_MS: i64 = 24 * 60 * 60 * 1000;
+pub const MIN_WATCH_RENEWAL_WINDOW_MS: i64 = 10 * 60 * 1000;
+pub const MAX_WATCH_RENEWAL_WINDOW_MS: i64 = 3 * 24 * 60 * 60 * 1000;
+/// Minimum spacing between automatic renewal attempts after a failed one.
+const WATCH_RENEWAL_RETRY_BACKOFF_MS: i64 = 15 * 60 * 1000;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GmailPubSubStatus {
@@ -20,6 +27,8 @@ pub struct GmailPubSubStatus {
     pub last_error: Option<String>,
     pub consecutive_failures: i64,
     pub updated_at_ms: i64,
+    pub renewal_window_ms: i64,
+    pub next_renewal_at_ms: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,15 +75,17 @@ pub fn get_status(connection: &Connection) -> Result<GmailPubSubStatus, String>
         .query_row(
             "SELECT provider, status, trigger_mode, watch_expiration_ms, history_id, topic_name,
                     subscription_name, callback_mode, last_event_at_ms, last_error,
-                    consecutive_failures, updated_at_ms
+                    consecutive_failures, updated_at_ms, renewal_window_ms
              FROM gmail_pubsub_state WHERE provider = 'gmail'",
             [],
             |r| {
+                let watch_expiration_ms: Option<i64> = r.get(3)?;
+                let renewal_window_ms: i64 = r.get(12)?;
                 Ok(GmailPubSubStatus {
                     provider: r.get(0)?,
                     status: r.get(1)?,
                     trigger_mode: r.get(2)?,
-                    watch_expiration_ms: r.get(3)?,
+                    watch_expiration_ms,
                     history_id: r.get(4)?,
                     topic_name: r.get(5)?,
                     subscription_name: r.get(6)?,
@@ -83,6 +94,8 @@ pub fn get_status(connection: &Connection) -> Result<GmailPubSubStatus, String>
                     last_error: r.get(9)?,
                     consecutive_failures: r.get(10)?,
                     updated_at_ms: r.get(11)?,
+                    renewal_window_ms,
+                    next_renewal_at_ms: next_renewal_at(watch_expiration_ms, renewal_window_ms),
                 })
             },
         )
@@ -160,6 +173,48 @@ pub fn update_watch_success(
     get_status(connection)
 }
 
+pub fn set_watch_renewal_window(
+    connection: &Connection,
+    renewal_window_ms: i64,
+    now: i64,
+) -> Result<GmailPubSubStatus, String> {
+    connection
+        .execute(
+            "UPDATE gmail_pubsub_state
+             SET renewal_window_ms = ?1, updated_at_ms = ?2
+             WHERE provider = 'gmail'",
+            params![
+                renewal_window_ms.clamp(MIN_WATCH_RENEWAL_WINDOW_MS, MAX_WATCH_RENEWAL_WINDOW_MS),
+                now
+            ],
+        )
+        .map_err(|e| format!("Failed to update Gmail watch renewal window: {e}"))?;
+    get_status(connection)
+}
+
+/// True when the background cycle should re-register the Gmail watch: PubSub is in use, a
+/// watch has been registered before, and its expiry falls inside the renewal window. After a
+/// failed attempt the next one waits out a short backoff.
+pub fn needs_watch_renewal(status: &GmailPubSubStatus, now: i64) -> bool {
+    if status.trigger_mode == "polling"
+        || matches!(status.status.as_str(), "disabled" | "pending_setup")
+        || status
+            .topic_name
+            .as_deref()
+            .is_none_or(|t| t.trim().is_empty())
+    {
+        return false;
+    }
+    let Some(renew_at) = status.next_renewal_at_ms else {
+        return false;
+    };
+    if now < renew_at {
+        return false;
+    }
+    status.consecutive_failures == 0
+        || now.saturating_sub(status.updated_at_ms) >= WATCH_RENEWAL_RETRY_BACKOFF_MS
+}
+
 pub fn touch_event_success(
     connection: &Connection,
     now: i64,
@@ -373,9 +428,17 @@ fn default_status() -> GmailPubSubStatus {
         last_error: None,
         consecutive_failures: 0,
         updated_at_ms: 0,
+        renewal_window_ms: DEFAULT_WATCH_RENEWAL_WINDOW_MS,
+        next_renewal_at_ms: None,
     }
 }
 
+fn next_renewal_at(watch_expiration_ms: Option<i64>, renewal_window_ms: i64) -> Option<i64> {
+    watch_expiration_ms
+        .filter(|exp| *exp > 0)
+        .map(|exp| exp.saturating_sub(renewal_window_ms))
+}
+
 fn parse_rfc3339_ms(input: &str) -> Option<i64> {
     DateTime::parse_from_rfc3339(input)
         .ok()
@@ -437,4 +500,52 @@ mod tests {
         assert!(insert_event(&conn, &row).expect("insert1"));
         assert!(!insert_event(&conn, &row).expect("insert2"));
     }
+
+    #[test]
+    fn watch_near_expiry_is_flagged_for_renewal() {
+        let mut conn = Connection::open_in_memory().expect("db");
+        bootstrap_schema(&mut conn).expect("bootstrap");
+        let hour = 60 * 60 * 1000;
+        let now = 10 * 24 * hour;
+        upsert_state(
+            &conn,
+            "pending_setup",
+            "auto",
+            Some("projects/x/topics/t"),
+            Some("projects/x/subscriptions/s"),
+            "relay",
+            None,
+            None,
+            None,
+            0,
+            now,
+        )
+        .expect("state");
+        assert!(!needs_watch_renewal(
+            &get_status(&conn).expect("status"),
+            now
+        ));
+
+        let far =
+            update_watch_success(&conn, Some(now + 5 * 24 * hour), Some("h1"), now).expect("watch");
+        assert_eq!(far.renewal_window_ms, DEFAULT_WATCH_RENEWAL_WINDOW_MS);
+        assert_eq!(far.next_renewal_at_ms, Some(now + 4 * 24 * hour));
+        assert!(!needs_watch_renewal(&far, now));
+
+        let near = update_watch_success(&conn, Some(now + 2 * hour), None, now).expect("watch");
+        assert!(needs_watch_renewal(&near, now));
+
+        let narrow = set_watch_renewal_window(&conn, hour, now).expect("window");
+        assert_eq!(narrow.next_renewal_at_ms, Some(now + hour));
+        assert!(!needs_watch_renewal(&narrow, now));
+        assert!(needs_watch_renewal(&narrow, now + hour));
+
+        record_failure(&conn, "watch failed", now + hour).expect("failure");
+        let failed = get_status(&conn).expect("status");
+        assert!(!needs_watch_renewal(&failed, now + hour + 60_000));
+        assert!(needs_watch_renewal(
+            &failed,
+            now + hour + WATCH_RENEWAL_RETRY_BACKOFF_MS
+        ));
+    }
 }
@@ -1,6 +1,10 @@
 use crate::email_connections::{self, EmailProvider};
-use crate::runner::RunnerEngine;
+use crate::runner::{RunPriority, RunnerEngine};
 use crate::schema::{AutopilotPlan, ProviderId, RecipeKind};
+use rand::{
+    distributions::{Alphanumeric, DistString},
+    rngs::OsRng,
+};
 use reqwest::blocking::Client;
 use reqwest::header::CONTENT_TYPE;
 use rusqlite::{params, Connection, OptionalExtension};
@@ -11,6 +15,8 @@ use std::time::{SystemTime, UNIX_EPOCH};
 const MAX_EMAIL_BODY_CHARS: usize = 12_000;
 const WATCHER_BASE_BACKOFF_MS: i64 = 30_000;
 const WATCHER_MAX_BACKOFF_MS: i64 = 15 * 60_000;
+const APPROVAL_REPLY_TAG: &str = "[tmx-approval:";
+const APPROVAL_REPLY_TOKEN_LEN: usize = 24;
 
 #[derive(Debug, Clone)]
 struct InboundMessage {
@@ -31,13 +37,41 @@ pub struct InboxWatcherTickSummary {
     pub deduped: usize,
     pub started_runs: usize,
     pub failed: usize,
+    pub approvals_resolved: usize,
 }
 
+/// Running totals across every tick for one provider; never reset.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InboxWatcherStats {
+    pub provider: String,
+    pub fetched: i64,
+    pub deduped: i64,
+    pub started_runs: i64,
+    pub failed: i64,
+    pub last_run_at_ms: i64,
+    pub consecutive_failures: i64,
+    /// Polling is skipped until this time after repeated failures.
+    pub backoff_until_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalReplyDecision {
+    Approve,
+    Reject,
+}
+
+/// Applies a decision read from an approval reply: `(connection, approval_id, decision, sender)`.
+/// The watcher hands this off so channel policy and run resumption stay with the caller.
+pub type ApprovalReplyResolver =
+    dyn Fn(&mut Connection, &str, ApprovalReplyDecision, &str) -> Result<(), String>;
+
 pub fn run_watcher_tick(
     connection: &mut Connection,
     provider_raw: &str,
     autopilot_id: &str,
     max_items: usize,
+    resolve_approval: &ApprovalReplyResolver,
 ) -> Result<InboxWatcherTickSummary, String> {
     let provider = EmailProvider::parse(provider_raw)
         .ok_or_else(|| "Unsupported email provider.".to_string())?;
@@ -51,30 +85,73 @@ pub fn run_watcher_tick(
                 deduped: 0,
                 started_runs: 0,
                 failed: 0,
+                approvals_resolved: 0,
             });
         }
     }
-    let token = email_connections::get_access_token(connection, provider)?;
-    let messages = match fetch_messages(provider, &token, max_items) {
-        Ok(messages) => {
+    let result = poll_and_ingest(
+        connection,
+        provider,
+        autopilot_id,
+        max_items,
+        resolve_approval,
+    );
+    record_tick_outcome(connection, provider, autopilot_id, &result, now)?;
+    result
+}
+
+/// Folds a tick into the provider's stats. A failed tick, whatever the cause, pushes the next
+/// poll out exponentially; a successful one clears the backoff.
+fn record_tick_outcome(
+    connection: &Connection,
+    provider: EmailProvider,
+    autopilot_id: &str,
+    result: &Result<InboxWatcherTickSummary, String>,
+    now: i64,
+) -> Result<(), String> {
+    match result {
+        Ok(summary) => {
             clear_watcher_backoff(connection, provider)?;
-            messages
+            record_tick_stats(connection, provider, summary, now)
         }
         Err(err) => {
-            let retry_after_ms = if is_rate_limited_error(&err) {
-                Some(next_backoff_ms(connection, provider)?)
-            } else if is_retryable_watcher_error(&err) {
-                Some(next_backoff_ms(connection, provider)?)
-            } else {
-                None
-            };
-            record_watcher_failure(connection, provider, &err, retry_after_ms)?;
-            return Err(err);
+            let backoff_ms = next_backoff_ms(connection, provider)?;
+            record_watcher_failure(connection, provider, err, Some(backoff_ms))?;
+            record_tick_stats(
+                connection,
+                provider,
+                &InboxWatcherTickSummary {
+                    provider: provider.as_str().to_string(),
+                    autopilot_id: autopilot_id.to_string(),
+                    fetched: 0,
+                    deduped: 0,
+                    started_runs: 0,
+                    failed: 1,
+                    approvals_resolved: 0,
+                },
+                now,
+            )
         }
-    };
+    }
+}
+
+fn poll_and_ingest(
+    connection: &mut Connection,
+    provider: EmailProvider,
+    autopilot_id: &str,
+    max_items: usize,
+    resolve_approval: &ApprovalReplyResolver,
+) -> Result<InboxWatcherTickSummary, String> {
+    let token = email_connections::get_access_token(connection, provider)?;
+    let messages = fetch_messages(provider, &token, max_items)?;
+    let owner_email = email_connections::list_connections(connection)?
+        .into_iter()
+        .find(|record| record.provider == provider.as_str())
+        .and_then(|record| record.account_email);
     let mut deduped = 0usize;
     let mut started_runs = 0usize;
     let mut failed = 0usize;
+    let mut approvals_resolved = 0usize;
 
     for message in &messages {
         let dedupe_key = format!("{}:{}", provider.as_str(), message.provider_message_id);
@@ -91,6 +168,29 @@ pub fn run_watcher_tick(
             continue;
         }
 
+        if let Some(status) = resolve_approval_reply(
+            connection,
+            owner_email.as_deref(),
+            message.sender_email.as_deref(),
+            &message.subject,
+            &message.body_preview,
+            resolve_approval,
+        )? {
+            if status == "approval_resolved" {
+                approvals_resolved += 1;
+            }
+            record_ingest_event(
+                connection,
+                provider,
+                message,
+                &dedupe_key,
+                autopilot_id,
+                None,
+                status,
+            )?;
+            continue;
+        }
+
         let intent = format!("Triage inbox message: {}", message.subject);
         let provider_id = preferred_provider_for_autopilot(connection, autopilot_id)
             .unwrap_or(ProviderId::OpenAi);
@@ -114,8 +214,14 @@ pub fn run_watcher_tick(
             message.provider_message_id
         );
 
-        let run_result =
-            RunnerEngine::start_run(connection, autopilot_id, plan, &idempotency_key, 2);
+        let run_result = RunnerEngine::start_run_with_priority(
+            connection,
+            autopilot_id,
+            plan,
+            &idempotency_key,
+            2,
+            RunPriority::Background,
+        );
         let (status, run_id) = match run_result {
             Ok(run) => {
                 started_runs += 1;
@@ -127,27 +233,15 @@ pub fn run_watcher_tick(
             }
         };
 
-        connection
-            .execute(
-                "INSERT INTO email_ingest_events (
-                   id, provider, provider_message_id, provider_thread_id, sender_email, dedupe_key, autopilot_id, subject, received_at_ms, run_id, status, created_at_ms
-                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
-                params![
-                    make_id("ingest"),
-                    provider.as_str(),
-                    message.provider_message_id,
-                    message.provider_thread_id.as_deref(),
-                    message.sender_email.as_deref(),
-                    dedupe_key,
-                    autopilot_id,
-                    message.subject,
-                    message.received_at_ms,
-                    run_id,
-                    status,
-                    now_ms()
-                ],
-            )
-            .map_err(|e| format!("Failed to persist ingest event: {e}"))?;
+        record_ingest_event(
+            connection,
+            provider,
+            message,
+            &dedupe_key,
+            autopilot_id,
+            run_id.as_deref(),
+            &status,
+        )?;
     }
 
     Ok(InboxWatcherTickSummary {
@@ -157,9 +251,202 @@ pub fn run_watcher_tick(
         deduped,
         started_runs,
         failed,
+        approvals_resolved,
     })
 }
 
+fn record_ingest_event(
+    connection: &Connection,
+    provider: EmailProvider,
+    message: &InboundMessage,
+    dedupe_key: &str,
+    autopilot_id: &str,
+    run_id: Option<&str>,
+    status: &str,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO email_ingest_events (
+               id, provider, provider_message_id, provider_thread_id, sender_email, dedupe_key, autopilot_id, subject, received_at_ms, run_id, status, created_at_ms
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                make_id("ingest"),
+                provider.as_str(),
+                message.provider_message_id,
+                message.provider_thread_id.as_deref(),
+                message.sender_email.as_deref(),
+                dedupe_key,
+                autopilot_id,
+                message.subject,
+                message.received_at_ms,
+                run_id,
+                status,
+                now_ms()
+            ],
+        )
+        .map_err(|e| format!("Failed to persist ingest event: {e}"))?;
+    Ok(())
+}
+
+/// Adds one tick's counts to the provider's cumulative stats.
+pub fn record_tick_stats(
+    connection: &Connection,
+    provider: EmailProvider,
+    summary: &InboxWatcherTickSummary,
+    now: i64,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO inbox_watcher_stats (provider, fetched, deduped, started_runs, failed, last_run_at_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(provider) DO UPDATE SET
+               fetched = inbox_watcher_stats.fetched + excluded.fetched,
+               deduped = inbox_watcher_stats.deduped + excluded.deduped,
+               started_runs = inbox_watcher_stats.started_runs + excluded.started_runs,
+               failed = inbox_watcher_stats.failed + excluded.failed,
+               last_run_at_ms = excluded.last_run_at_ms",
+            params![
+                provider.as_str(),
+                summary.fetched as i64,
+                summary.deduped as i64,
+                summary.started_runs as i64,
+                summary.failed as i64,
+                now
+            ],
+        )
+        .map_err(|e| format!("Failed to record inbox watcher stats: {e}"))?;
+    Ok(())
+}
+
+pub fn list_watcher_stats(connection: &Connection) -> Result<Vec<InboxWatcherStats>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT s.provider, s.fetched, s.deduped, s.started_runs, s.failed, s.last_run_at_ms,
+                    COALESCE(w.consecutive_failures, 0), w.backoff_until_ms
+             FROM inbox_watcher_stats s
+             LEFT JOIN inbox_watcher_state w ON w.provider = s.provider
+             ORDER BY s.provider ASC",
+        )
+        .map_err(|e| format!("Failed to prepare inbox watcher stats: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(InboxWatcherStats {
+                provider: row.get(0)?,
+                fetched: row.get(1)?,
+                deduped: row.get(2)?,
+                started_runs: row.get(3)?,
+                failed: row.get(4)?,
+                last_run_at_ms: row.get(5)?,
+                consecutive_failures: row.get(6)?,
+                backoff_until_ms: row.get(7)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query inbox watcher stats: {e}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read inbox watcher stats: {e}"))
+}
+
+/// Subject line for an approval-notification email. Replying keeps the tag, which is how the
+/// watcher finds the approval again; the token is created on first use and reused after.
+pub fn approval_reply_subject(
+    connection: &Connection,
+    approval_id: &str,
+) -> Result<String, String> {
+    let (preview, token): (String, Option<String>) = connection
+        .query_row(
+            "SELECT preview, reply_token FROM approvals WHERE id = ?1",
+            params![approval_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to load approval: {e}"))?
+        .ok_or_else(|| "Approval not found.".to_string())?;
+    let token = match token {
+        Some(token) => token,
+        None => {
+            let token = Alphanumeric.sample_string(&mut OsRng, APPROVAL_REPLY_TOKEN_LEN);
+            connection
+                .execute(
+                    "UPDATE approvals SET reply_token = ?1 WHERE id = ?2",
+                    params![token, approval_id],
+                )
+                .map_err(|e| format!("Failed to save approval reply token: {e}"))?;
+            token
+        }
+    };
+    let preview = preview.lines().next().unwrap_or("").trim();
+    Ok(format!(
+        "Approval needed: {} {}{}]",
+        preview.chars().take(80).collect::<String>(),
+        APPROVAL_REPLY_TAG,
+        token
+    ))
+}
+
+/// Handles a message that carries an approval reply tag. Returns `None` for ordinary mail so
+/// it goes on to triage; otherwise the ingest status to record. Only the connected account's
+/// owner may resolve approvals, so a spoofed or forwarded reply is recorded and ignored.
+pub fn resolve_approval_reply(
+    connection: &mut Connection,
+    owner_email: Option<&str>,
+    sender_email: Option<&str>,
+    subject: &str,
+    body: &str,
+    resolve_approval: &ApprovalReplyResolver,
+) -> Result<Option<&'static str>, String> {
+    let Some(token) = extract_approval_reply_token(subject) else {
+        return Ok(None);
+    };
+    let owner = owner_email.map(extract_email_address);
+    let sender = sender_email.map(extract_email_address);
+    let Some(sender) = sender.filter(|sender| owner.as_deref() == Some(sender.as_str())) else {
+        return Ok(Some("approval_spoofed"));
+    };
+    let approval_id: Option<String> = connection
+        .query_row(
+            "SELECT id FROM approvals WHERE reply_token = ?1 AND status = 'pending'",
+            params![token],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to look up approval reply: {e}"))?;
+    let Some(approval_id) = approval_id else {
+        return Ok(Some("approval_unmatched"));
+    };
+    let Some(decision) = parse_approval_reply_decision(body) else {
+        return Ok(Some("approval_unclear"));
+    };
+    match resolve_approval(connection, &approval_id, decision, &sender) {
+        Ok(()) => Ok(Some("approval_resolved")),
+        Err(_) => Ok(Some("approval_failed")),
+    }
+}
+
+fn extract_approval_reply_token(subject: &str) -> Option<String> {
+    let start = subject.find(APPROVAL_REPLY_TAG)? + APPROVAL_REPLY_TAG.len();
+    let rest = &subject[start..];
+    let token = &rest[..rest.find(']')?];
+    if token.len() == APPROVAL_REPLY_TOKEN_LEN && token.chars().all(|c| c.is_ascii_alphanumeric()) {
+        Some(token.to_string())
+    } else {
+        None
+    }
+}
+
+/// Reads the decision from the first word of the reply, above any quoted original.
+fn parse_approval_reply_decision(body: &str) -> Option<ApprovalReplyDecision> {
+    let first_word = body
+        .split_whitespace()
+        .next()?
+        .trim_matches(|c: char| !c.is_ascii_alphanumeric())
+        .to_ascii_lowercase();
+    match first_word.as_str() {
+        "approve" | "approved" | "yes" => Some(ApprovalReplyDecision::Approve),
+        "reject" | "rejected" | "no" => Some(ApprovalReplyDecision::Reject),
+        _ => None,
+    }
+}
+
 fn fetch_messages(
     provider: EmailProvider,
     access_token: &str,
@@ -536,6 +823,43 @@ fn preferred_provider_for_autopilot(
     }
 }
 
+/// True once `min_interval_ms` has passed since this provider was last polled (or it never was).
+pub fn provider_poll_due(
+    connection: &Connection,
+    provider: EmailProvider,
+    now: i64,
+    min_interval_ms: i64,
+) -> Result<bool, String> {
+    let last_poll_at_ms = connection
+        .query_row(
+            "SELECT last_poll_at_ms FROM inbox_watcher_state WHERE provider = ?1",
+            params![provider.as_str()],
+            |row| row.get::<_, Option<i64>>(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to load watcher poll time: {e}"))?
+        .flatten();
+    Ok(last_poll_at_ms.is_none_or(|last| now.saturating_sub(last) >= min_interval_ms))
+}
+
+pub fn record_provider_poll(
+    connection: &Connection,
+    provider: EmailProvider,
+    now: i64,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO inbox_watcher_state (provider, backoff_until_ms, consecutive_failures, last_error, last_poll_at_ms, updated_at_ms)
+             VALUES (?1, NULL, 0, NULL, ?2, ?2)
+             ON CONFLICT(provider) DO UPDATE SET
+               last_poll_at_ms = excluded.last_poll_at_ms,
+               updated_at_ms = excluded.updated_at_ms",
+            params![provider.as_str(), now],
+        )
+        .map_err(|e| format!("Failed to record watcher poll time: {e}"))?;
+    Ok(())
+}
+
 fn watcher_backoff_until(
     connection: &Connection,
     provider: EmailProvider,
@@ -606,19 +930,6 @@ fn clear_watcher_backoff(connection: &Connection, provider: EmailProvider) -> Re
     Ok(())
 }
 
-fn is_rate_limited_error(error: &str) -> bool {
-    let lower = error.to_ascii_lowercase();
-    lower.contains("rate-limit") || lower.contains("rate limited")
-}
-
-fn is_retryable_watcher_error(error: &str) -> bool {
-    let lower = error.to_ascii_lowercase();
-    lower.contains("try again")
-        || lower.contains("temporarily")
-        || lower.contains("could not read")
-        || lower.contains("could not parse")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -666,4 +977,102 @@ mod tests {
         let cleared = watcher_backoff_until(&conn, EmailProvider::Gmail).expect("state");
         assert!(cleared.is_none());
     }
+
+    #[test]
+    fn watcher_stats_accumulate_across_ticks() {
+        let mut conn = Connection::open_in_memory().expect("db");
+        bootstrap_schema(&mut conn).expect("schema");
+        let tick = |fetched, deduped, started_runs, failed| InboxWatcherTickSummary {
+            provider: "gmail".to_string(),
+            autopilot_id: "auto_inbox".to_string(),
+            fetched,
+            deduped,
+            started_runs,
+            failed,
+            approvals_resolved: 0,
+        };
+
+        record_tick_stats(&conn, EmailProvider::Gmail, &tick(5, 2, 3, 0), 1_000).expect("tick");
+        record_tick_stats(&conn, EmailProvider::Gmail, &tick(4, 4, 0, 1), 2_000).expect("tick");
+
+        let stats = list_watcher_stats(&conn).expect("stats");
+        assert_eq!(stats.len(), 1);
+        let gmail = &stats[0];
+        assert_eq!(gmail.provider, "gmail");
+        assert_eq!(
+            (
+                gmail.fetched,
+                gmail.deduped,
+                gmail.started_runs,
+                gmail.failed
+            ),
+            (9, 6, 3, 1)
+        );
+        assert_eq!(gmail.last_run_at_ms, 2_000);
+    }
+
+    #[test]
+    fn watcher_tick_records_stats_and_skips_them_while_backed_off() {
+        let _keychain = crate::providers::keychain::scoped_test_keychain();
+        let mut conn = Connection::open_in_memory().expect("db");
+        bootstrap_schema(&mut conn).expect("schema");
+        let resolver = |_: &mut Connection, _: &str, _: ApprovalReplyDecision, _: &str| Ok(());
+
+        let err = run_watcher_tick(&mut conn, "gmail", "auto_inbox", 10, &resolver)
+            .expect_err("no stored session");
+        assert!(err.contains("not connected"));
+        let stats = list_watcher_stats(&conn).expect("stats");
+        assert_eq!(stats.len(), 1);
+        assert_eq!((stats[0].fetched, stats[0].failed), (0, 1));
+        assert_eq!(stats[0].consecutive_failures, 1);
+        let last_run_at_ms = stats[0].last_run_at_ms;
+
+        let skipped = run_watcher_tick(&mut conn, "gmail", "auto_inbox", 10, &resolver)
+            .expect("backed-off tick");
+        assert_eq!((skipped.fetched, skipped.failed), (0, 0));
+        let stats = list_watcher_stats(&conn).expect("stats");
+        assert_eq!(stats[0].failed, 1);
+        assert_eq!(stats[0].last_run_at_ms, last_run_at_ms);
+    }
+
+    #[test]
+    fn repeated_tick_failures_push_out_next_poll_until_success() {
+        let mut conn = Connection::open_in_memory().expect("db");
+        bootstrap_schema(&mut conn).expect("schema");
+        let provider = EmailProvider::Microsoft365;
+        let failure: Result<InboxWatcherTickSummary, String> =
+            Err("Provider is not connected yet.".to_string());
+
+        record_tick_outcome(&conn, provider, "auto_inbox", &failure, 1_000).expect("first");
+        let first = watcher_backoff_until(&conn, provider)
+            .expect("state")
+            .expect("backoff set");
+        record_tick_outcome(&conn, provider, "auto_inbox", &failure, 2_000).expect("second");
+        let second = watcher_backoff_until(&conn, provider)
+            .expect("state")
+            .expect("backoff set");
+        assert!(second > first, "{second} <= {first}");
+
+        let stats = list_watcher_stats(&conn).expect("stats");
+        assert_eq!(stats[0].consecutive_failures, 2);
+        assert_eq!(stats[0].failed, 2);
+        assert_eq!(stats[0].backoff_until_ms, Some(second));
+
+        let success = Ok(InboxWatcherTickSummary {
+            provider: provider.as_str().to_string(),
+            autopilot_id: "auto_inbox".to_string(),
+            fetched: 1,
+            deduped: 0,
+            started_runs: 1,
+            failed: 0,
+            approvals_resolved: 0,
+        });
+        record_tick_outcome(&conn, provider, "auto_inbox", &success, 3_000).expect("success");
+        assert!(watcher_backoff_until(&conn, provider)
+            .expect("state")
+            .is_none());
+        let stats = list_watcher_stats(&conn).expect("stats");
+        assert_eq!(stats[0].consecutive_failures, 0);
+        assert_eq!(stats[0].backoff_until_ms, None);
+    }
 }
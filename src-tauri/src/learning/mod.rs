@@ -16,6 +16,11 @@ const MAX_MEMORY_CARD_CONTENT_BYTES: usize = 4096;
 const MAX_MEMORY_CARD_TITLE_CHARS: usize = 80;
 const MAX_MEMORY_CONTEXT_CARDS: usize = 5;
 const MAX_MEMORY_CONTEXT_CHARS: usize = 1500;
+/// Cards not reinforced for a window lose `MEMORY_CARD_DECAY_STEP` confidence per window.
+const MEMORY_CARD_DECAY_WINDOW_MS: i64 = 14 * 24 * 60 * 60 * 1000;
+const MEMORY_CARD_DECAY_STEP: i64 = 15;
+const MEMORY_CARD_DELETE_BELOW_CONFIDENCE: i64 = 20;
+const MEMORY_CONTEXT_MIN_CONFIDENCE: i64 = 40;
 const DECISION_EVENTS_RATE_LIMIT_PER_MINUTE: i64 = 30;
 const DECISION_EVENTS_RETENTION_MAX_PER_AUTOPILOT: i64 = 500;
 const ADAPTATION_LOG_RETENTION_MAX_PER_AUTOPILOT: i64 = 200;
@@ -27,7 +32,7 @@ const COMPACTION_TRIGGER_EVENT_INTERVAL: i64 = 25;
 const COMPACTION_DELETE_CHUNK: i64 = 200;
 static LEARNING_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
 
-const REDACTION_FORBIDDEN_SUBSTRINGS: [&str; 6] = [
+pub(crate) const REDACTION_FORBIDDEN_SUBSTRINGS: [&str; 6] = [
     "bearer ",
     "sk-",
     "api_key",
@@ -184,6 +189,20 @@ pub struct MemoryContext {
     pub prompt_block: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryCardRecord {
+    pub card_id: String,
+    pub autopilot_id: String,
+    pub card_type: String,
+    pub title: String,
+    pub content: Value,
+    pub confidence: i64,
+    pub created_from_run_id: Option<String>,
+    pub updated_at_ms: i64,
+    pub version: i64,
+    pub manual: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct LearningCompactionSummary {
     pub autopilot_id: Option<String>,
@@ -191,6 +210,12 @@ pub struct LearningCompactionSummary {
     pub decision_events_deleted: i64,
     pub adaptation_log_deleted: i64,
     pub run_evaluations_deleted: i64,
+    #[serde(default)]
+    pub primitive_guard_events_deleted: i64,
+    #[serde(default)]
+    pub memory_cards_decayed: i64,
+    #[serde(default)]
+    pub memory_cards_deleted: i64,
 }
 
 #[derive(Debug, Clone)]
@@ -224,6 +249,18 @@ impl MemoryCardType {
             Self::RecurringEntities => "recurring_entities",
         }
     }
+
+    fn parse(value: &str) -> Result<Self, LearningError> {
+        match value.trim() {
+            "format_preference" => Ok(Self::FormatPreference),
+            "source_preference" => Ok(Self::SourcePreference),
+            "suppression_rationale" => Ok(Self::SuppressionRationale),
+            "recurring_entities" => Ok(Self::RecurringEntities),
+            other => Err(LearningError::Invalid(format!(
+                "unknown memory card type: {other}"
+            ))),
+        }
+    }
 }
 
 pub fn record_decision_event(
@@ -290,6 +327,59 @@ pub fn record_decision_event_from_json(
     )
 }
 
+/// Marks an outcome reviewed and records the matching learning signal. `used` is recorded as an
+/// open with a `used` reason code, since using an outcome implies reading it. The review lands in
+/// `review_action`; the outcome's delivery `status` is left alone.
+pub fn mark_outcome_reviewed(
+    connection: &Connection,
+    outcome_id: &str,
+    action: &str,
+) -> Result<(), LearningError> {
+    let action = action.trim();
+    let event_type = match action {
+        "opened" | "used" => DecisionEventType::OutcomeOpened,
+        "ignored" => DecisionEventType::OutcomeIgnored,
+        _ => {
+            return Err(LearningError::Invalid(
+                "Outcome review action must be opened, ignored, or used.".to_string(),
+            ))
+        }
+    };
+    let target: Option<(String, String, String)> = connection
+        .query_row(
+            "SELECT o.run_id, o.step_id, r.autopilot_id
+             FROM outcomes o JOIN runs r ON r.id = o.run_id
+             WHERE o.id = ?1",
+            params![outcome_id.trim()],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()
+        .map_err(|e| LearningError::Db(e.to_string()))?;
+    let Some((run_id, step_id, autopilot_id)) = target else {
+        return Err(LearningError::Invalid("Outcome not found.".to_string()));
+    };
+
+    record_decision_event(
+        connection,
+        &autopilot_id,
+        &run_id,
+        Some(&step_id),
+        event_type,
+        DecisionEventMetadata {
+            reason_code: Some(action.to_string()),
+            ..Default::default()
+        },
+        None,
+    )?;
+    connection
+        .execute(
+            "UPDATE outcomes SET review_action = ?2, reviewed_at = ?3 WHERE id = ?1",
+            params![outcome_id.trim(), action, now_ms()],
+        )
+        .map_err(|e| LearningError::Db(e.to_string()))?;
+    Ok(())
+}
+
 pub fn ensure_autopilot_profile(
     connection: &Connection,
     autopilot_id: &str,
@@ -696,7 +786,7 @@ pub fn update_memory_cards(
             "Preferred reply style",
             &content,
             (50 + (draft_edited_count as i64 * 10)).clamp(50, 95),
-            Some(run_id),
+            MemoryCardSource::Learned(run_id),
             now,
         )?;
     }
@@ -721,7 +811,7 @@ pub fn update_memory_cards(
             "Minor changes can be suppressed",
             &content,
             70,
-            Some(run_id),
+            MemoryCardSource::Learned(run_id),
             now,
         )?;
     }
@@ -741,7 +831,7 @@ pub fn update_memory_cards(
                     "Preferred source scope",
                     &content,
                     75,
-                    Some(run_id),
+                    MemoryCardSource::Learned(run_id),
                     now,
                 )?;
             }
@@ -751,6 +841,107 @@ pub fn update_memory_cards(
     Ok(())
 }
 
+pub fn list_memory_cards(
+    connection: &Connection,
+    autopilot_id: &str,
+) -> Result<Vec<MemoryCardRecord>, LearningError> {
+    let mut stmt = connection
+        .prepare(
+            "
+            SELECT card_id, autopilot_id, card_type, title, content_json, confidence,
+                   created_from_run_id, updated_at_ms, version, manual
+            FROM memory_cards
+            WHERE autopilot_id = ?1
+            ORDER BY updated_at_ms DESC, card_id ASC
+            ",
+        )
+        .map_err(|e| LearningError::Db(e.to_string()))?;
+    let rows = stmt
+        .query_map(params![autopilot_id], |row| {
+            Ok((
+                MemoryCardRecord {
+                    card_id: row.get(0)?,
+                    autopilot_id: row.get(1)?,
+                    card_type: row.get(2)?,
+                    title: row.get(3)?,
+                    content: Value::Null,
+                    confidence: row.get(5)?,
+                    created_from_run_id: row.get(6)?,
+                    updated_at_ms: row.get(7)?,
+                    version: row.get(8)?,
+                    manual: row.get(9)?,
+                },
+                row.get::<_, String>(4)?,
+            ))
+        })
+        .map_err(|e| LearningError::Db(e.to_string()))?;
+    let mut cards = Vec::new();
+    for row in rows {
+        let (mut card, content_json) = row.map_err(|e| LearningError::Db(e.to_string()))?;
+        card.content =
+            serde_json::from_str(&content_json).map_err(|e| LearningError::Serde(e.to_string()))?;
+        cards.push(card);
+    }
+    Ok(cards)
+}
+
+/// Manual edit of a learned card. Cards are keyed by type, so this replaces the Autopilot's
+/// card of that type; the same bounds as learned cards apply. The saved card is pinned: later
+/// learning and decay leave it as the user wrote it.
+pub fn save_memory_card(
+    connection: &Connection,
+    autopilot_id: &str,
+    card_type: &str,
+    title: &str,
+    content_json: &str,
+    confidence: i64,
+) -> Result<MemoryCardRecord, LearningError> {
+    let card_type = MemoryCardType::parse(card_type)?;
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(LearningError::Invalid(
+            "memory card title is required".to_string(),
+        ));
+    }
+    // Cards below the context threshold would never reach a prompt.
+    if !(MEMORY_CONTEXT_MIN_CONFIDENCE..=100).contains(&confidence) {
+        return Err(LearningError::Invalid(format!(
+            "memory card confidence must be between {MEMORY_CONTEXT_MIN_CONFIDENCE} and 100"
+        )));
+    }
+    let content: Value =
+        serde_json::from_str(content_json).map_err(|e| LearningError::Serde(e.to_string()))?;
+    if !content.is_object() {
+        return Err(LearningError::Invalid(
+            "memory card content must be an object".to_string(),
+        ));
+    }
+    summarize_card(card_type.as_str(), &content)?;
+    upsert_memory_card_internal(
+        connection,
+        autopilot_id,
+        card_type,
+        title,
+        &content,
+        confidence,
+        MemoryCardSource::Manual,
+        now_ms(),
+    )?;
+    let card_id = memory_card_id(autopilot_id, card_type);
+    list_memory_cards(connection, autopilot_id)?
+        .into_iter()
+        .find(|card| card.card_id == card_id)
+        .ok_or_else(|| LearningError::Db("memory card was not saved".to_string()))
+}
+
+pub fn delete_memory_card(
+    connection: &Connection,
+    autopilot_id: &str,
+    card_id: &str,
+) -> Result<bool, LearningError> {
+    db::delete_memory_card(connection, autopilot_id, card_id).map_err(LearningError::Db)
+}
+
 pub fn build_memory_context(
     connection: &Connection,
     autopilot_id: &str,
@@ -761,7 +952,7 @@ pub fn build_memory_context(
             "
             SELECT card_type, title, content_json
             FROM memory_cards
-            WHERE autopilot_id = ?1
+            WHERE autopilot_id = ?1 AND confidence >= ?2
             ORDER BY updated_at_ms DESC
             LIMIT 20
             ",
@@ -769,13 +960,16 @@ pub fn build_memory_context(
         .map_err(|e| LearningError::Db(e.to_string()))?;
 
     let rows = stmt
-        .query_map(params![autopilot_id], |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, String>(2)?,
-            ))
-        })
+        .query_map(
+            params![autopilot_id, MEMORY_CONTEXT_MIN_CONFIDENCE],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            },
+        )
         .map_err(|e| LearningError::Db(e.to_string()))?;
 
     let mut snippets = Vec::new();
@@ -1035,6 +1229,56 @@ fn compact_adaptation_log_for_autopilot(
     delete_ids_chunked(connection, "adaptation_log", "id", &to_delete, dry_run)
 }
 
+/// Lowers confidence on cards not reinforced within a decay window, at most once per window,
+/// and deletes cards that fall below the floor. Re-upserting a card resets its decay clock.
+fn decay_memory_cards_for_autopilot(
+    connection: &Connection,
+    autopilot_id: &str,
+    now: i64,
+    dry_run: bool,
+) -> Result<(i64, i64), LearningError> {
+    let cutoff = now - MEMORY_CARD_DECAY_WINDOW_MS;
+    let mut stmt = connection
+        .prepare(
+            "
+            SELECT card_id, confidence
+            FROM memory_cards
+            WHERE autopilot_id = ?1
+              AND manual = 0
+              AND COALESCE(decayed_at_ms, reinforced_at_ms, updated_at_ms) <= ?2
+            ",
+        )
+        .map_err(|e| LearningError::Db(e.to_string()))?;
+    let rows = stmt
+        .query_map(params![autopilot_id, cutoff], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })
+        .map_err(|e| LearningError::Db(e.to_string()))?;
+    let mut to_decay = Vec::new();
+    let mut to_delete = Vec::new();
+    for row in rows {
+        let (card_id, confidence) = row.map_err(|e| LearningError::Db(e.to_string()))?;
+        let next = confidence - MEMORY_CARD_DECAY_STEP;
+        if next < MEMORY_CARD_DELETE_BELOW_CONFIDENCE {
+            to_delete.push(card_id);
+        } else {
+            to_decay.push((card_id, next));
+        }
+    }
+    if !dry_run {
+        for (card_id, confidence) in &to_decay {
+            connection
+                .execute(
+                    "UPDATE memory_cards SET confidence = ?1, decayed_at_ms = ?2 WHERE card_id = ?3",
+                    params![confidence, now, card_id],
+                )
+                .map_err(|e| LearningError::Db(e.to_string()))?;
+        }
+    }
+    let deleted = delete_ids_chunked(connection, "memory_cards", "card_id", &to_delete, dry_run)?;
+    Ok((to_decay.len() as i64, deleted))
+}
+
 fn compact_run_evaluations_for_autopilot(
     connection: &Connection,
     autopilot_id: &str,
@@ -1104,8 +1348,13 @@ fn write_compaction_activity(
     summary: &LearningCompactionSummary,
 ) -> Result<(), LearningError> {
     let event = format!(
-        "learning_compaction: decision_events_deleted={}, adaptation_log_deleted={}, run_evaluations_deleted={}",
-        summary.decision_events_deleted, summary.adaptation_log_deleted, summary.run_evaluations_deleted
+        "learning_compaction: decision_events_deleted={}, adaptation_log_deleted={}, run_evaluations_deleted={}, primitive_guard_events_deleted={}, memory_cards_decayed={}, memory_cards_deleted={}",
+        summary.decision_events_deleted,
+        summary.adaptation_log_deleted,
+        summary.run_evaluations_deleted,
+        summary.primitive_guard_events_deleted,
+        summary.memory_cards_decayed,
+        summary.memory_cards_deleted
     );
     let created_at = now_ms();
     if let Some(ap_id) = autopilot_id {
@@ -1202,8 +1451,16 @@ pub fn compact_learning_data(
             compact_adaptation_log_for_autopilot(connection, &id, dry_run)?;
         summary.run_evaluations_deleted +=
             compact_run_evaluations_for_autopilot(connection, &id, &protected_runs, dry_run)?;
+        let (decayed, deleted) =
+            decay_memory_cards_for_autopilot(connection, &id, now_ms(), dry_run)?;
+        summary.memory_cards_decayed += decayed;
+        summary.memory_cards_deleted += deleted;
     }
 
+    summary.primitive_guard_events_deleted =
+        crate::primitives::prune_primitive_guard_log(connection, dry_run)
+            .map_err(LearningError::Db)?;
+
     if !dry_run {
         write_compaction_activity(connection, autopilot_id, &summary)?;
     }
@@ -1332,6 +1589,13 @@ fn normalize_reply_length_hint(value: Option<&str>) -> String {
     }
 }
 
+/// Where a memory card write comes from. Learned writes never replace a manual card.
+#[derive(Debug, Clone, Copy)]
+enum MemoryCardSource<'a> {
+    Learned(&'a str),
+    Manual,
+}
+
 fn upsert_memory_card_internal(
     connection: &Connection,
     autopilot_id: &str,
@@ -1339,7 +1603,7 @@ fn upsert_memory_card_internal(
     title: &str,
     content: &Value,
     confidence: i64,
-    created_from_run_id: Option<&str>,
+    source: MemoryCardSource<'_>,
     now: i64,
 ) -> Result<(), LearningError> {
     if title.chars().count() > MAX_MEMORY_CARD_TITLE_CHARS {
@@ -1351,20 +1615,28 @@ fn upsert_memory_card_internal(
     db::upsert_memory_card(
         connection,
         &MemoryCardUpsert {
-            card_id: format!("mem_{}_{}", autopilot_id, card_type.as_str()),
+            card_id: memory_card_id(autopilot_id, card_type),
             autopilot_id: autopilot_id.to_string(),
             card_type: card_type.as_str().to_string(),
             title: title.to_string(),
             content_json,
             confidence: confidence.clamp(0, 100),
-            created_from_run_id: created_from_run_id.map(|s| s.to_string()),
+            created_from_run_id: match source {
+                MemoryCardSource::Learned(run_id) => Some(run_id.to_string()),
+                MemoryCardSource::Manual => None,
+            },
             updated_at_ms: now,
             version: 1,
+            manual: matches!(source, MemoryCardSource::Manual),
         },
     )
     .map_err(LearningError::Db)
 }
 
+fn memory_card_id(autopilot_id: &str, card_type: MemoryCardType) -> String {
+    format!("mem_{}_{}", autopilot_id, card_type.as_str())
+}
+
 fn summarize_card(card_type: &str, content: &Value) -> Result<String, LearningError> {
     let text = match card_type {
         "format_preference" => {
@@ -2153,6 +2425,44 @@ mod tests {
         assert!(too_long_result.is_err());
     }
 
+    #[test]
+    fn marking_outcome_ignored_records_outcome_ignored_event() {
+        let connection = setup_conn();
+        insert_terminal_run(&connection, "auto_review", "run_review");
+        connection
+            .execute(
+                "INSERT INTO outcomes (id, run_id, step_id, kind, status, content, created_at, updated_at)
+                 VALUES ('outcome_review', 'run_review', 'step_1', 'outcome_draft', 'captured', 'Draft', 1, 1)",
+                [],
+            )
+            .expect("insert outcome");
+
+        mark_outcome_reviewed(&connection, "outcome_review", "ignored").expect("mark ignored");
+
+        let (event_type, step_id, metadata_json): (String, Option<String>, String) = connection
+            .query_row(
+                "SELECT event_type, step_id, metadata_json FROM decision_events WHERE run_id = 'run_review'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .expect("decision event");
+        assert_eq!(event_type, "outcome_ignored");
+        assert_eq!(step_id.as_deref(), Some("step_1"));
+        assert!(metadata_json.contains("ignored"));
+        let (status, review_action): (String, Option<String>) = connection
+            .query_row(
+                "SELECT status, review_action FROM outcomes WHERE id = 'outcome_review'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("status");
+        assert_eq!(status, "captured");
+        assert_eq!(review_action.as_deref(), Some("ignored"));
+
+        assert!(mark_outcome_reviewed(&connection, "outcome_review", "archived").is_err());
+        assert!(mark_outcome_reviewed(&connection, "outcome_missing", "opened").is_err());
+    }
+
     #[test]
     fn decision_event_rate_limit_is_enforced() {
         let connection = setup_conn();
@@ -2296,4 +2606,189 @@ mod tests {
             .expect("count cards");
         assert_eq!(count, 1);
     }
+
+    #[test]
+    fn memory_cards_can_be_listed_edited_and_deleted() {
+        let connection = setup_conn();
+        insert_terminal_run(&connection, "auto_curate", "run_curate");
+        upsert_memory_card_internal(
+            &connection,
+            "auto_curate",
+            MemoryCardType::SourcePreference,
+            "Preferred source scope",
+            &json!({"max_sources": 3}),
+            75,
+            MemoryCardSource::Learned("run_curate"),
+            1,
+        )
+        .expect("learned card");
+
+        let cards = list_memory_cards(&connection, "auto_curate").expect("list");
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].card_type, "source_preference");
+        assert_eq!(cards[0].content["max_sources"], 3);
+        assert_eq!(cards[0].confidence, 75);
+
+        let edited = save_memory_card(
+            &connection,
+            "auto_curate",
+            "source_preference",
+            "Use more sources",
+            r#"{"max_sources": 8}"#,
+            90,
+        )
+        .expect("edit");
+        assert_eq!(edited.card_id, cards[0].card_id);
+        assert_eq!(edited.title, "Use more sources");
+        assert_eq!(edited.confidence, 90);
+        assert!(edited.created_from_run_id.is_none());
+        let context = build_memory_context(&connection, "auto_curate", RecipeKind::DailyBrief)
+            .expect("context");
+        assert!(context.prompt_block.contains("Use up to 8"));
+        assert!(edited.manual);
+
+        // Learning no longer overwrites the pinned card.
+        upsert_memory_card_internal(
+            &connection,
+            "auto_curate",
+            MemoryCardType::SourcePreference,
+            "Preferred source scope",
+            &json!({"max_sources": 3}),
+            75,
+            MemoryCardSource::Learned("run_curate"),
+            2,
+        )
+        .expect("learned update");
+        let cards = list_memory_cards(&connection, "auto_curate").expect("list");
+        assert_eq!(cards[0].title, "Use more sources");
+        assert_eq!(cards[0].content["max_sources"], 8);
+        assert_eq!(
+            decay_memory_cards_for_autopilot(&connection, "auto_curate", i64::MAX, false)
+                .expect("decay"),
+            (0, 0)
+        );
+
+        let long_title = "t".repeat(MAX_MEMORY_CARD_TITLE_CHARS + 1);
+        assert!(save_memory_card(
+            &connection,
+            "auto_curate",
+            "source_preference",
+            &long_title,
+            "{}",
+            50
+        )
+        .is_err());
+        assert!(
+            save_memory_card(&connection, "auto_curate", "mystery", "Title", "{}", 50).is_err()
+        );
+        assert!(save_memory_card(
+            &connection,
+            "auto_curate",
+            "source_preference",
+            "Title",
+            "{}",
+            101
+        )
+        .is_err());
+        assert!(save_memory_card(
+            &connection,
+            "auto_curate",
+            "source_preference",
+            "Title",
+            "{}",
+            MEMORY_CONTEXT_MIN_CONFIDENCE - 1
+        )
+        .is_err());
+
+        assert!(
+            !delete_memory_card(&connection, "other_autopilot", &edited.card_id)
+                .expect("scoped delete")
+        );
+        assert!(delete_memory_card(&connection, "auto_curate", &edited.card_id).expect("delete"));
+        assert!(list_memory_cards(&connection, "auto_curate")
+            .expect("list")
+            .is_empty());
+    }
+
+    #[test]
+    fn unreinforced_memory_card_decays_across_passes_until_dropped() {
+        let connection = setup_conn();
+        insert_terminal_run(&connection, "auto_decay", "run_decay");
+        let start = 1_000_000;
+        upsert_memory_card_internal(
+            &connection,
+            "auto_decay",
+            MemoryCardType::SourcePreference,
+            "Preferred source scope",
+            &json!({"max_sources": 3}),
+            60,
+            MemoryCardSource::Learned("run_decay"),
+            start,
+        )
+        .expect("card");
+        upsert_memory_card_internal(
+            &connection,
+            "auto_decay",
+            MemoryCardType::FormatPreference,
+            "Preferred reply style",
+            &json!({"tone": "concise"}),
+            60,
+            MemoryCardSource::Learned("run_decay"),
+            start,
+        )
+        .expect("card");
+        let confidence = |card_type: &str| -> Option<i64> {
+            connection
+                .query_row(
+                    "SELECT confidence FROM memory_cards WHERE autopilot_id = 'auto_decay' AND card_type = ?1",
+                    params![card_type],
+                    |row| row.get(0),
+                )
+                .optional()
+                .expect("confidence")
+        };
+
+        let first_pass = start + MEMORY_CARD_DECAY_WINDOW_MS;
+        assert_eq!(
+            decay_memory_cards_for_autopilot(&connection, "auto_decay", first_pass, false)
+                .expect("decay"),
+            (2, 0)
+        );
+        assert_eq!(confidence("source_preference"), Some(45));
+        // A second pass inside the same window is a no-op.
+        decay_memory_cards_for_autopilot(&connection, "auto_decay", first_pass + 1, false)
+            .expect("decay");
+        assert_eq!(confidence("source_preference"), Some(45));
+
+        // Reinforcement from a recent run resets the format card's decay clock.
+        upsert_memory_card_internal(
+            &connection,
+            "auto_decay",
+            MemoryCardType::FormatPreference,
+            "Preferred reply style",
+            &json!({"tone": "concise"}),
+            60,
+            MemoryCardSource::Learned("run_decay"),
+            first_pass + 10,
+        )
+        .expect("reinforce");
+
+        let second_pass = first_pass + MEMORY_CARD_DECAY_WINDOW_MS;
+        decay_memory_cards_for_autopilot(&connection, "auto_decay", second_pass, false)
+            .expect("decay");
+        assert_eq!(confidence("source_preference"), Some(30));
+        assert_eq!(confidence("format_preference"), Some(60));
+        let context = build_memory_context(&connection, "auto_decay", RecipeKind::DailyBrief)
+            .expect("context");
+        assert_eq!(context.titles, vec!["Preferred reply style".to_string()]);
+
+        let third_pass = second_pass + MEMORY_CARD_DECAY_WINDOW_MS;
+        assert_eq!(
+            decay_memory_cards_for_autopilot(&connection, "auto_decay", third_pass, false)
+                .expect("decay"),
+            (1, 1)
+        );
+        assert_eq!(confidence("source_preference"), None);
+        assert_eq!(confidence("format_preference"), Some(45));
+    }
 }
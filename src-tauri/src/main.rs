@@ -1,3 +1,4 @@
+mod app_log;
 mod db;
 mod diagnostics;
 mod email_connections;
@@ -21,13 +22,17 @@ use guidance_utils::{
     GuidanceMode,
 };
 use hmac::{Hmac, Mac};
-use providers::runtime::{ProviderRuntime, TransportStatus};
+use providers::runtime::{ProviderRuntime, TransportMode, TransportStatus};
+use providers::trace;
 use providers::types::{
-    ProviderErrorKind, ProviderKind as ApiProviderKind, ProviderRequest,
-    ProviderTier as ApiProviderTier,
+    ProviderKind as ApiProviderKind, ProviderRequest, ProviderTier as ApiProviderTier,
 };
 use reqwest::blocking::Client as HttpClient;
-use runner::{ApprovalRecord, ClarificationRecord, RunReceipt, RunRecord, RunnerEngine};
+use runner::{
+    ApprovalRecord, ClarificationRecord, RetryBackoff, RunCostBreakdown, RunPriority,
+    RunProgressEvent, RunProgressSink, RunReceipt, RunReceiptComparison, RunRecord, RunStepDetail,
+    RunWithPlan, RunnerEngine,
+};
 use rusqlite::OptionalExtension;
 use schema::{
     ApiCallRequest, AutopilotPlan, PlanStep, PrimitiveId, ProviderId, RecipeKind, RiskTier,
@@ -42,8 +47,11 @@ use std::time::Duration;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::menu::{MenuBuilder, MenuEvent, MenuItemBuilder};
 use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
-use tauri::Manager;
-use transport::{RelayApprovalDecision, RelayTransport};
+use tauri::{Emitter, Manager};
+use transport::{
+    LocalHttpTransport, RelayApprovalDecision, RelayApprovalDecisionSource, RelayTimeoutPolicy,
+    RelayTransport,
+};
 use webhook_triggers::{CreateWebhookTriggerInput, WebhookTriggerCreateResponse};
 
 #[derive(Default)]
@@ -53,6 +61,16 @@ struct AppState {
 
 static MAIN_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
 
+/// How long a generated custom plan is reused for identical intent text.
+const CUSTOM_PLAN_CACHE_TTL_MS: i64 = 10 * 60 * 1000;
+const CUSTOM_PLAN_CACHE_MAX_ENTRIES: usize = 32;
+
+/// Validated custom plans keyed by a hash of `(provider, intent)`, so re-drafting the same
+/// text during onboarding doesn't re-spend on plan generation.
+static CUSTOM_PLAN_CACHE: std::sync::OnceLock<
+    std::sync::Mutex<std::collections::HashMap<String, (i64, AutopilotPlan)>>,
+> = std::sync::OnceLock::new();
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
 enum IntentDraftKind {
@@ -77,6 +95,70 @@ struct IntentDraftResponse {
     classification_reason: String,
     plan: AutopilotPlan,
     preview: IntentDraftPreview,
+    warnings: Vec<PlanWarning>,
+    /// The provider was unreachable, so `plan` is the safe template; the UI offers a retry.
+    degraded: bool,
+}
+
+/// Non-fatal finding surfaced alongside a draft. The plan is still usable; the UI shows these
+/// so the user can review before running.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PlanWarning {
+    code: String,
+    message: String,
+}
+
+/// Largest number of intents `start_batch_runs` accepts in one call.
+const MAX_BATCH_RUN_ITEMS: usize = 50;
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchRunItemInput {
+    autopilot_id: String,
+    recipe: String,
+    intent: String,
+    provider: String,
+}
+
+/// Per-item outcome. `status` is `started`, `failed`, or `skipped_cap` once the batch would
+/// push today's spend over the daily hard cap.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchRunItemResult {
+    index: usize,
+    autopilot_id: String,
+    status: String,
+    run_id: Option<String>,
+    estimated_cost_usd_cents: Option<i64>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchRunResponse {
+    started_count: usize,
+    failed_count: usize,
+    skipped_count: usize,
+    items: Vec<BatchRunItemResult>,
+}
+
+/// Result of checking a hand-authored plan. `plan` is the normalized form a run would execute;
+/// `error` is the first hard failure when the plan would be refused.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PlanValidationResponse {
+    valid: bool,
+    plan: Option<AutopilotPlan>,
+    error: Option<String>,
+    warnings: Vec<PlanWarning>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OnboardingRecommendation {
+    recipe: RecipeKind,
+    intent: String,
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -89,6 +171,16 @@ struct RunnerControlInput {
     watcher_max_items: i64,
     gmail_autopilot_id: String,
     microsoft_autopilot_id: String,
+    #[serde(default)]
+    max_catch_up_cycles: Option<i64>,
+    #[serde(default)]
+    relay_sync_batch_size: Option<i64>,
+    #[serde(default)]
+    relay_connect_timeout_seconds: Option<i64>,
+    #[serde(default)]
+    relay_read_timeout_seconds: Option<i64>,
+    #[serde(default)]
+    relay_max_retries: Option<i64>,
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -111,6 +203,15 @@ struct VoiceConfigInput {
     notes: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VoicePreview {
+    tone: String,
+    length: String,
+    humor: String,
+    sample_text: String,
+}
+
 #[derive(Debug, Clone, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct AutopilotVoiceConfigInput {
@@ -138,6 +239,25 @@ struct RunnerCycleSummary {
     catch_up_cycles_run: i64,
 }
 
+impl RunnerCycleSummary {
+    /// A cycle that did no work; `status` explains why.
+    fn skipped(status: &str) -> Self {
+        Self {
+            watcher_status: status.to_string(),
+            relay_sync_status: status.to_string(),
+            providers_polled: 0,
+            fetched: 0,
+            deduped: 0,
+            started_runs: 0,
+            failed: 0,
+            resumed_due_runs: 0,
+            relay_decisions_applied: 0,
+            missed_runs_detected: 0,
+            catch_up_cycles_run: 0,
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct AutopilotSendPolicyInput {
@@ -148,6 +268,10 @@ struct AutopilotSendPolicyInput {
     quiet_hours_start_local: i64,
     quiet_hours_end_local: i64,
     allow_outside_quiet_hours: bool,
+    #[serde(default)]
+    recipient_cooldown_minutes: Option<i64>,
+    #[serde(default)]
+    self_send_outcomes: Option<bool>,
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -166,12 +290,144 @@ struct GuidanceResponse {
     proposed_rule: Option<String>,
 }
 
+/// How an instruction would be understood, shown before the user commits it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GuidancePreviewResponse {
+    normalized_instruction: String,
+    mode: GuidanceMode,
+    message: String,
+    proposed_rule: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct TransportStatusResponse {
     mode: String,
     relay_configured: bool,
     relay_url: String,
+    local_base_url: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AutopilotTransportConfigInput {
+    autopilot_id: String,
+    transport_mode: String,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AutopilotContextLimitsInput {
+    autopilot_id: String,
+    inbox_text_max_chars: Option<i64>,
+    daily_source_max_items: Option<i64>,
+    #[serde(default)]
+    summarize_overflow: bool,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AutopilotWebTrackingInput {
+    autopilot_id: String,
+    tracking_params: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApprovalTtlsInput {
+    autopilot_id: String,
+    high_ttl_ms: Option<i64>,
+    medium_ttl_ms: Option<i64>,
+    low_ttl_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApprovalChannelPolicyInput {
+    autopilot_id: String,
+    allowed_channels: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MemoryCardInput {
+    autopilot_id: String,
+    card_type: String,
+    title: String,
+    content_json: String,
+    confidence: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AppHealthcheck {
+    overall: String, // ok | degraded | error
+    issues: Vec<String>,
+    transport: TransportStatusResponse,
+    relay_callback_ready: bool,
+    email_connections: Vec<email_connections::EmailConnectionRecord>,
+    gmail_pubsub: gmail_pubsub::GmailPubSubStatus,
+    background_enabled: bool,
+    stale_secret_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RelayPingResponse {
+    latest: db::RelayPingRecord,
+    recent: Vec<db::RelayPingRecord>,
+    uptime_percent: i64,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LocalTransportBaseUrlInput {
+    base_url: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LocalTransportProbeResponse {
+    base_url: String,
+    reachable: bool,
+    latency_ms: i64,
+    http_status: Option<u16>,
+    message: String,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProviderPricingInput {
+    provider_kind: String,
+    model: String,
+    input_cents_per_1k: f64,
+    output_cents_per_1k: f64,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecipeModelOverrideInput {
+    provider_kind: String,
+    recipe_kind: String,
+    /// Empty or missing clears the override so the provider default applies again.
+    model: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProviderCostEstimateInput {
+    provider_kind: String,
+    model: String,
+    input_tokens: i64,
+    output_tokens: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProviderCostEstimateResponse {
+    pricing: db::ProviderPricingRecord,
+    estimated_cost_usd_cents: i64,
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -205,6 +461,45 @@ struct VaultExtractionProbeInput {
 struct ApiKeyRefStatus {
     ref_name: String,
     configured: bool,
+    created_at_ms: Option<i64>,
+    updated_at_ms: Option<i64>,
+    last_used_at_ms: Option<i64>,
+}
+
+impl ApiKeyRefStatus {
+    fn new(
+        ref_name: String,
+        configured: bool,
+        metadata: Option<db::ApiKeyRefMetadataRecord>,
+    ) -> Self {
+        Self {
+            ref_name,
+            configured,
+            created_at_ms: metadata.as_ref().map(|m| m.created_at_ms),
+            updated_at_ms: metadata.as_ref().map(|m| m.updated_at_ms),
+            last_used_at_ms: metadata.and_then(|m| m.last_used_at_ms),
+        }
+    }
+}
+
+const SECRET_ROTATION_DEFAULT_MAX_AGE_DAYS: i64 = 90;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SecretRotationWarning {
+    kind: String, // api_key_ref | webhook_secret
+    name: String,
+    autopilot_id: Option<String>,
+    secret_set_at_ms: i64,
+    age_days: i64,
+    severity: String, // warning | critical
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KeychainBundleExport {
+    bundle: String,
+    entry_count: usize,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -274,6 +569,8 @@ struct GmailPubSubEnableInput {
     topic_name: String,
     subscription_name: String,
     callback_mode: Option<String>, // relay|local_debug
+    #[serde(default)]
+    renewal_window_minutes: Option<i64>,
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -311,6 +608,40 @@ struct WebhookIngestResult {
     message: String,
 }
 
+/// What Terminus would accept for a test delivery. Carries the signature only, never the secret.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookSignatureSimulation {
+    trigger_id: String,
+    signature_mode: String,
+    signature: String,
+    /// Set for the Terminus scheme, whose timestamp travels separately from the signature.
+    signature_ts_ms: Option<i64>,
+}
+
+/// Everything an external source needs to deliver to a trigger. Never carries the secret.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookEndpointSetup {
+    trigger_id: String,
+    endpoint_url: String,
+    signature_mode: String,
+    signature_header: String,
+    /// Only the Terminus scheme sends its timestamp in a separate header.
+    timestamp_header: Option<String>,
+    instructions: String,
+    sample_curl: String,
+}
+
+/// The run a sample delivery would produce. Nothing is persisted when this is built.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookRunPreview {
+    trigger_id: String,
+    plan: AutopilotPlan,
+    preview: IntentDraftPreview,
+}
+
 #[derive(Debug, Clone)]
 struct WebhookIngestInput {
     relay_request_id: Option<String>,
@@ -362,6 +693,11 @@ struct RelayApprovalSyncStatusResponse {
     last_error: Option<String>,
     last_processed_count: i64,
     total_processed_count: i64,
+    /// Decisions this channel received after the other channel had already applied them.
+    deduped_by_other_channel: i64,
+    /// Push channel only: whether the relay streams decisions. `None` until first probed.
+    streaming_supported: Option<bool>,
+    stream_probed_at_ms: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -369,6 +705,7 @@ struct RelayApprovalSyncStatusResponse {
 struct RelayApprovalSyncTickResponse {
     status: RelayApprovalSyncStatusResponse,
     applied_count: usize,
+    deduped_by_other_channel: usize,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -392,6 +729,17 @@ struct RelayRoutingPolicyResponse {
     updated_at_ms: i64,
 }
 
+/// How a proposed routing policy would treat one registered device.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RelayRoutingPreviewItem {
+    device_id: String,
+    device_label: String,
+    status: String,
+    would_receive_decisions: bool,
+    reason: String,
+}
+
 #[derive(Debug, Clone, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct RelayDeviceStatusInput {
@@ -431,7 +779,15 @@ fn open_connection_from_path(db_path: &PathBuf) -> Result<rusqlite::Connection,
 }
 
 #[tauri::command]
-fn get_home_snapshot(state: tauri::State<AppState>) -> Result<db::HomeSnapshot, String> {
+fn get_home_snapshot(
+    state: tauri::State<AppState>,
+    range: Option<String>,
+) -> Result<db::HomeSnapshot, String> {
+    let range = match range.as_deref() {
+        Some(value) => db::HomeSnapshotRange::parse(value)
+            .ok_or_else(|| "Home range must be today, week, or all_time.".to_string())?,
+        None => db::HomeSnapshotRange::default(),
+    };
     let db_path = state
         .db_path
         .lock()
@@ -439,16 +795,23 @@ fn get_home_snapshot(state: tauri::State<AppState>) -> Result<db::HomeSnapshot,
         .clone()
         .ok_or_else(|| "Database is not initialized yet".to_string())?;
 
-    db::get_home_snapshot(db_path)
+    db::get_home_snapshot(db_path, range)
 }
 
 #[tauri::command]
 fn list_primary_outcomes(
     state: tauri::State<AppState>,
     limit: Option<usize>,
+    autopilot_id: Option<String>,
+    kind: Option<String>,
 ) -> Result<Vec<db::PrimaryOutcomeRecord>, String> {
     let connection = open_connection(&state)?;
-    db::list_primary_outcomes(&connection, limit.unwrap_or(50))
+    db::list_primary_outcomes(
+        &connection,
+        limit.unwrap_or(50),
+        autopilot_id.as_deref(),
+        kind.as_deref(),
+    )
 }
 
 #[tauri::command]
@@ -658,31 +1021,43 @@ fn relay_local_execution_allowed(
             policy.approval_target_mode.as_str()
         }
     };
-    let local: Option<(String, i64)> = connection
+    relay_routing_block_reason(connection, local_device_id, "This device", mode)
+}
+
+/// Why `device_id` would not pull relay decisions under target `mode`, or `None` when it
+/// would. Reasons name the device as `subject`: "This device" for the live check, the device
+/// label in the policy preview.
+fn relay_routing_block_reason(
+    connection: &rusqlite::Connection,
+    device_id: &str,
+    subject: &str,
+    mode: &str,
+) -> Result<Option<String>, String> {
+    let device: Option<(String, i64)> = connection
         .query_row(
             "SELECT status, is_preferred_target FROM relay_devices WHERE device_id = ?1",
-            rusqlite::params![local_device_id],
+            rusqlite::params![device_id],
             |row| Ok((row.get(0)?, row.get(1)?)),
         )
         .optional()
-        .map_err(|e| format!("Could not read local relay device status: {e}"))?;
-    let Some((status, preferred_flag)) = local else {
+        .map_err(|e| format!("Could not read relay device status: {e}"))?;
+    let Some((status, preferred_flag)) = device else {
         return Ok(Some("Relay device is not registered yet.".to_string()));
     };
     if status == "disabled" {
-        return Ok(Some(
-            "This device is disabled for relay routing. Re-enable it in Relay Devices.".to_string(),
-        ));
+        return Ok(Some(format!(
+            "{subject} is disabled for relay routing. Re-enable it in Relay Devices."
+        )));
     }
     if status == "offline" {
-        return Ok(Some(
-            "This device is marked offline for relay routing. Set it to Active to receive relay decisions.".to_string(),
-        ));
+        return Ok(Some(format!(
+            "{subject} is marked offline for relay routing. Set it to Active to receive relay decisions."
+        )));
     }
     if mode == "manual_target_only" {
-        return Ok(Some(
-            "Relay routing is set to manual target only. This device will not pull decisions automatically.".to_string(),
-        ));
+        return Ok(Some(format!(
+            "Relay routing is set to manual target only. {subject} will not pull decisions automatically."
+        )));
     }
     if preferred_flag == 0 {
         let preferred: Option<String> = connection
@@ -697,13 +1072,54 @@ fn relay_local_execution_allowed(
             .map_err(|e| format!("Could not read preferred relay device: {e}"))?;
         if let Some(label) = preferred {
             return Ok(Some(format!(
-                "This device is standby. Relay decisions are routed to preferred device {label}."
+                "{subject} is standby. Relay decisions are routed to preferred device {label}."
             )));
         }
     }
     Ok(None)
 }
 
+/// Previews which registered devices would pull relay decisions under `input` without
+/// saving it.
+#[tauri::command]
+fn evaluate_routing_policy(
+    state: tauri::State<AppState>,
+    input: RelayRoutingPolicyInput,
+) -> Result<Vec<RelayRoutingPreviewItem>, String> {
+    let connection = open_connection(&state)?;
+    let _ = ensure_local_relay_device_registered(&connection)?;
+    evaluate_routing_policy_internal(&connection, &input)
+}
+
+fn evaluate_routing_policy_internal(
+    connection: &rusqlite::Connection,
+    input: &RelayRoutingPolicyInput,
+) -> Result<Vec<RelayRoutingPreviewItem>, String> {
+    let approval_target_mode = normalize_relay_target_mode(&input.approval_target_mode)?;
+    normalize_relay_target_mode(&input.trigger_target_mode)?;
+    normalize_relay_fallback_policy(&input.fallback_policy)?;
+    list_relay_devices_internal(connection)?
+        .into_iter()
+        .map(|device| {
+            let blocked = relay_routing_block_reason(
+                connection,
+                &device.device_id,
+                &device.device_label,
+                &approval_target_mode,
+            )?;
+            Ok(RelayRoutingPreviewItem {
+                would_receive_decisions: blocked.is_none(),
+                reason: blocked.unwrap_or_else(|| {
+                    format!("{} would pull relay decisions.", device.device_label)
+                }),
+                device_id: device.device_id,
+                device_label: device.device_label,
+                status: device.status,
+            })
+        })
+        .collect()
+}
+
 #[tauri::command]
 fn list_relay_devices(state: tauri::State<AppState>) -> Result<Vec<RelayDeviceRecord>, String> {
     let connection = open_connection(&state)?;
@@ -784,23 +1200,34 @@ fn update_relay_routing_policy(
     let approval_target_mode = normalize_relay_target_mode(&input.approval_target_mode)?;
     let trigger_target_mode = normalize_relay_target_mode(&input.trigger_target_mode)?;
     let fallback_policy = normalize_relay_fallback_policy(&input.fallback_policy)?;
-    connection
-        .execute(
-            "UPDATE relay_routing_policy
-             SET approval_target_mode = ?1,
-                 trigger_target_mode = ?2,
-                 fallback_policy = ?3,
-                 updated_at_ms = ?4
-             WHERE singleton_id = 1",
-            rusqlite::params![
-                approval_target_mode,
-                trigger_target_mode,
-                fallback_policy,
-                now_ms()
-            ],
-        )
-        .map_err(|e| format!("Could not update relay routing policy: {e}"))?;
-    get_relay_routing_policy_internal(&connection)
+    with_config_audit(&connection, |connection| {
+        let before = get_relay_routing_policy_internal(connection)?;
+        connection
+            .execute(
+                "UPDATE relay_routing_policy
+                 SET approval_target_mode = ?1,
+                     trigger_target_mode = ?2,
+                     fallback_policy = ?3,
+                     updated_at_ms = ?4
+                 WHERE singleton_id = 1",
+                rusqlite::params![
+                    approval_target_mode,
+                    trigger_target_mode,
+                    fallback_policy,
+                    now_ms()
+                ],
+            )
+            .map_err(|e| format!("Could not update relay routing policy: {e}"))?;
+        let updated = get_relay_routing_policy_internal(connection)?;
+        record_config_change(
+            connection,
+            "update_relay_routing_policy",
+            "relay_routing_policy",
+            Some(&before),
+            Some(&updated),
+        )?;
+        Ok(updated)
+    })
 }
 
 #[derive(Debug, Clone, Default)]
@@ -812,6 +1239,7 @@ struct RelaySyncStateRow {
     last_error: Option<String>,
     last_processed_count: i64,
     total_processed_count: i64,
+    deduped_by_other_channel: i64,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -834,6 +1262,14 @@ impl RelayDecisionSyncChannel {
             Self::Push => "push",
         }
     }
+
+    fn parse(raw: &str) -> Result<Self, String> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "poll" => Ok(Self::Poll),
+            "push" => Ok(Self::Push),
+            _ => Err("Relay sync channel must be poll or push.".to_string()),
+        }
+    }
 }
 
 #[tauri::command]
@@ -854,18 +1290,54 @@ fn get_relay_push_status(
 
 #[tauri::command]
 fn tick_relay_approval_sync(
+    app: tauri::AppHandle,
     state: tauri::State<AppState>,
 ) -> Result<RelayApprovalSyncTickResponse, String> {
     let mut connection = open_connection(&state)?;
-    tick_relay_approval_sync_internal(&mut connection, true, RelayDecisionSyncChannel::Poll)
+    tick_relay_approval_sync_internal(
+        &mut connection,
+        true,
+        RelayDecisionSyncChannel::Poll,
+        &TauriProgressSink { app },
+    )
 }
 
 #[tauri::command]
 fn tick_relay_approval_push(
+    app: tauri::AppHandle,
     state: tauri::State<AppState>,
 ) -> Result<RelayApprovalSyncTickResponse, String> {
     let mut connection = open_connection(&state)?;
-    tick_relay_approval_sync_internal(&mut connection, true, RelayDecisionSyncChannel::Push)
+    tick_relay_approval_sync_internal(
+        &mut connection,
+        true,
+        RelayDecisionSyncChannel::Push,
+        &TauriProgressSink { app },
+    )
+}
+
+/// Clears backoff and failure bookkeeping so the next tick on `channel` runs immediately.
+/// Poll timestamps and processed counters are kept.
+#[tauri::command]
+fn reset_relay_sync_state(
+    state: tauri::State<AppState>,
+    channel: String,
+) -> Result<RelayApprovalSyncStatusResponse, String> {
+    let channel = RelayDecisionSyncChannel::parse(&channel)?;
+    let connection = open_connection(&state)?;
+    reset_relay_sync_state_internal(&connection, channel)?;
+    get_relay_sync_status_internal(&connection, channel)
+}
+
+fn reset_relay_sync_state_internal(
+    connection: &rusqlite::Connection,
+    channel: RelayDecisionSyncChannel,
+) -> Result<(), String> {
+    let mut sync_state = load_relay_sync_state(connection, channel)?;
+    sync_state.backoff_until_ms = None;
+    sync_state.consecutive_failures = 0;
+    sync_state.last_error = None;
+    persist_relay_sync_state(connection, channel, &sync_state, now_ms())
 }
 
 fn get_relay_sync_status_internal(
@@ -881,6 +1353,12 @@ fn get_relay_sync_status_internal(
     let state = load_relay_sync_state(connection, channel)?;
     let routing_block_reason = relay_local_execution_allowed(connection, &device_id, channel)?;
     let enabled = relay_configured && callback_ready;
+    let stream_support = match channel {
+        RelayDecisionSyncChannel::Push => {
+            RelayTransport::new(RelayTransport::default_url()).stream_support()
+        }
+        RelayDecisionSyncChannel::Poll => None,
+    };
     let now = now_ms();
     let status = if !relay_configured {
         "relay_not_configured"
@@ -916,13 +1394,44 @@ fn get_relay_sync_status_internal(
         last_error: routing_block_reason.or(state.last_error),
         last_processed_count: state.last_processed_count,
         total_processed_count: state.total_processed_count,
+        deduped_by_other_channel: state.deduped_by_other_channel,
+        streaming_supported: stream_support.map(|support| support.supported),
+        stream_probed_at_ms: stream_support.map(|support| support.probed_at_ms),
     })
 }
 
+/// How long a relay that rejected streaming is polled directly before streaming is tried again.
+const RELAY_STREAM_REPROBE_MS: i64 = 15 * 60_000;
+
 fn tick_relay_approval_sync_internal(
     connection: &mut rusqlite::Connection,
     manual: bool,
     channel: RelayDecisionSyncChannel,
+    progress: &dyn RunProgressSink,
+) -> Result<RelayApprovalSyncTickResponse, String> {
+    let control = db::get_runner_control(connection)?;
+    let relay = RelayTransport::new(RelayTransport::default_url())
+        .with_policy(relay_timeout_policy(&control));
+    tick_relay_approval_sync_with_source(connection, manual, channel, &relay, progress)
+}
+
+fn relay_timeout_policy(control: &db::RunnerControlRecord) -> RelayTimeoutPolicy {
+    RelayTimeoutPolicy {
+        connect_timeout_seconds: control.relay_connect_timeout_seconds,
+        read_timeout_seconds: control.relay_read_timeout_seconds,
+        max_retries: control
+            .relay_max_retries
+            .clamp(0, db::RELAY_MAX_RETRIES_LIMIT) as u32,
+        ..RelayTimeoutPolicy::default()
+    }
+}
+
+fn tick_relay_approval_sync_with_source(
+    connection: &mut rusqlite::Connection,
+    manual: bool,
+    channel: RelayDecisionSyncChannel,
+    relay: &dyn RelayApprovalDecisionSource,
+    progress: &dyn RunProgressSink,
 ) -> Result<RelayApprovalSyncTickResponse, String> {
     let status = ProviderRuntime::default().transport_status();
     let relay_token = providers::keychain::get_relay_subscriber_token()
@@ -942,6 +1451,7 @@ fn tick_relay_approval_sync_internal(
         return Ok(RelayApprovalSyncTickResponse {
             status: get_relay_sync_status_internal(connection, channel)?,
             applied_count: 0,
+            deduped_by_other_channel: 0,
         });
     }
     if callback_secret.is_none() {
@@ -952,6 +1462,7 @@ fn tick_relay_approval_sync_internal(
         return Ok(RelayApprovalSyncTickResponse {
             status: get_relay_sync_status_internal(connection, channel)?,
             applied_count: 0,
+            deduped_by_other_channel: 0,
         });
     }
     if let Some(reason) = relay_local_execution_allowed(connection, &device_id, channel)? {
@@ -962,57 +1473,83 @@ fn tick_relay_approval_sync_internal(
         return Ok(RelayApprovalSyncTickResponse {
             status: get_relay_sync_status_internal(connection, channel)?,
             applied_count: 0,
+            deduped_by_other_channel: 0,
         });
     }
     if !manual && sync_state.backoff_until_ms.is_some_and(|until| until > now) {
         return Ok(RelayApprovalSyncTickResponse {
             status: get_relay_sync_status_internal(connection, channel)?,
             applied_count: 0,
+            deduped_by_other_channel: 0,
         });
     }
 
     sync_state.last_poll_at_ms = Some(now);
     persist_relay_sync_state(connection, channel, &sync_state, now)?;
 
-    let relay = RelayTransport::new(RelayTransport::default_url());
+    let batch_size = db::get_runner_control(connection)?
+        .relay_sync_batch_size
+        .clamp(db::RELAY_SYNC_BATCH_SIZE_MIN, db::RELAY_SYNC_BATCH_SIZE_MAX)
+        as usize;
     let poll_result = match channel {
         RelayDecisionSyncChannel::Poll => relay.poll_approval_decisions(
             relay_token.as_deref().unwrap_or_default(),
             &device_id,
-            20,
+            batch_size,
         ),
-        RelayDecisionSyncChannel::Push => relay
-            .stream_approval_decisions(
-                relay_token.as_deref().unwrap_or_default(),
-                &device_id,
-                20,
-                20,
-            )
-            .or_else(|stream_err| {
-                if stream_err.is_retryable() {
-                    Err(stream_err)
-                } else {
-                    relay.poll_approval_decisions(
-                        relay_token.as_deref().unwrap_or_default(),
-                        &device_id,
-                        20,
-                    )
-                }
-            }),
+        RelayDecisionSyncChannel::Push => {
+            let stream_known_unsupported = relay.stream_support().is_some_and(|support| {
+                !support.supported
+                    && now.saturating_sub(support.probed_at_ms) < RELAY_STREAM_REPROBE_MS
+            });
+            if stream_known_unsupported {
+                relay.poll_approval_decisions(
+                    relay_token.as_deref().unwrap_or_default(),
+                    &device_id,
+                    batch_size,
+                )
+            } else {
+                match relay.stream_approval_decisions(
+                    relay_token.as_deref().unwrap_or_default(),
+                    &device_id,
+                    batch_size,
+                    20,
+                ) {
+                    Ok(payload) => {
+                        relay.record_stream_support(true);
+                        Ok(payload)
+                    }
+                    Err(stream_err) if stream_err.is_retryable() => Err(stream_err),
+                    Err(_) => {
+                        relay.record_stream_support(false);
+                        relay.poll_approval_decisions(
+                            relay_token.as_deref().unwrap_or_default(),
+                            &device_id,
+                            batch_size,
+                        )
+                    }
+                }
+            }
+        }
     };
 
     let mut applied_count = 0usize;
+    let mut deduped_by_other_channel = 0usize;
     match poll_result {
         Ok(payload) => {
             for decision in payload.decisions {
-                if apply_relay_polled_decision(
+                match apply_relay_polled_decision(
                     connection,
                     &decision,
                     callback_secret.as_deref().unwrap_or_default(),
-                )?
-                .is_some()
-                {
-                    applied_count += 1;
+                    channel,
+                    progress,
+                )? {
+                    RelayDecisionApplyOutcome::Applied => applied_count += 1,
+                    RelayDecisionApplyOutcome::DedupedByOtherChannel => {
+                        deduped_by_other_channel += 1
+                    }
+                    RelayDecisionApplyOutcome::NothingToDo => {}
                 }
             }
             sync_state.last_success_at_ms = Some(now_ms());
@@ -1023,13 +1560,18 @@ fn tick_relay_approval_sync_internal(
             sync_state.total_processed_count = sync_state
                 .total_processed_count
                 .saturating_add(applied_count as i64);
+            sync_state.deduped_by_other_channel = sync_state
+                .deduped_by_other_channel
+                .saturating_add(deduped_by_other_channel as i64);
             persist_relay_sync_state(connection, channel, &sync_state, now_ms())?;
         }
         Err(err) => {
             sync_state.consecutive_failures = sync_state.consecutive_failures.saturating_add(1);
             sync_state.last_error = Some(err.message.clone());
             sync_state.last_processed_count = 0;
-            if matches!(err.kind, ProviderErrorKind::Retryable) {
+            if let Some(retry_after_ms) = err.retry_after_ms() {
+                sync_state.backoff_until_ms = Some(now.saturating_add(retry_after_ms as i64));
+            } else if err.is_retryable() {
                 let base = 5_000_i64;
                 let step = (sync_state.consecutive_failures - 1).clamp(0, 5) as u32;
                 let delay = base.saturating_mul(2_i64.saturating_pow(step));
@@ -1044,16 +1586,36 @@ fn tick_relay_approval_sync_internal(
     Ok(RelayApprovalSyncTickResponse {
         status: get_relay_sync_status_internal(connection, channel)?,
         applied_count,
+        deduped_by_other_channel,
     })
 }
 
+#[derive(Debug)]
+enum RelayDecisionApplyOutcome {
+    Applied,
+    /// The other sync channel (or the direct callback) recorded this request first.
+    DedupedByOtherChannel,
+    /// Malformed decision, or one this channel already applied on an earlier tick.
+    NothingToDo,
+}
+
+/// Applies one relayed decision. When this channel wins, the run's new state is pushed to
+/// `progress` so an open run view updates without waiting for its next poll.
 fn apply_relay_polled_decision(
     connection: &mut rusqlite::Connection,
     decision: &RelayApprovalDecision,
     callback_secret: &str,
-) -> Result<Option<RunRecord>, String> {
+    channel: RelayDecisionSyncChannel,
+    progress: &dyn RunProgressSink,
+) -> Result<RelayDecisionApplyOutcome, String> {
     if decision.request_id.trim().is_empty() || decision.approval_id.trim().is_empty() {
-        return Ok(None);
+        return Ok(RelayDecisionApplyOutcome::NothingToDo);
+    }
+    let via = channel.as_api_label();
+    match relay_callback_event_applied_via(connection, &decision.request_id)? {
+        Some(existing) if existing == via => return Ok(RelayDecisionApplyOutcome::NothingToDo),
+        Some(_) => return Ok(RelayDecisionApplyOutcome::DedupedByOtherChannel),
+        None => {}
     }
     let input = RelayApprovalCallbackInput {
         request_id: decision.request_id.clone(),
@@ -1065,7 +1627,30 @@ fn apply_relay_polled_decision(
         reason: decision.reason.clone(),
         issued_at_ms: decision.issued_at_ms,
     };
-    resolve_relay_approval_callback_with_connection(connection, &input).map(Some)
+    let run = resolve_relay_approval_callback_with_connection(connection, &input, via)?;
+    // Both channels can pass the check above at once; whoever reserved the event row wins.
+    match relay_callback_event_applied_via(connection, &decision.request_id)? {
+        Some(existing) if existing == via => {
+            progress.emit(&RunProgressEvent::for_run(&run));
+            Ok(RelayDecisionApplyOutcome::Applied)
+        }
+        _ => Ok(RelayDecisionApplyOutcome::DedupedByOtherChannel),
+    }
+}
+
+fn relay_callback_event_applied_via(
+    connection: &rusqlite::Connection,
+    request_id: &str,
+) -> Result<Option<String>, String> {
+    connection
+        .query_row(
+            "SELECT COALESCE(applied_via, 'callback') FROM relay_callback_events
+             WHERE request_id = ?1 LIMIT 1",
+            rusqlite::params![request_id.trim()],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Could not read relay callback history: {e}"))
 }
 
 fn load_relay_sync_state(
@@ -1075,7 +1660,8 @@ fn load_relay_sync_state(
     connection
         .query_row(
             "SELECT last_poll_at_ms, last_success_at_ms, consecutive_failures, backoff_until_ms,
-                    last_error, last_processed_count, total_processed_count
+                    last_error, last_processed_count, total_processed_count,
+                    deduped_by_other_channel
              FROM relay_sync_state WHERE channel = ?1 LIMIT 1",
             rusqlite::params![channel.as_row_key()],
             |row| {
@@ -1087,6 +1673,7 @@ fn load_relay_sync_state(
                     last_error: row.get(4)?,
                     last_processed_count: row.get::<_, Option<i64>>(5)?.unwrap_or(0),
                     total_processed_count: row.get::<_, Option<i64>>(6)?.unwrap_or(0),
+                    deduped_by_other_channel: row.get::<_, Option<i64>>(7)?.unwrap_or(0),
                 })
             },
         )
@@ -1105,8 +1692,9 @@ fn persist_relay_sync_state(
         .execute(
             "INSERT INTO relay_sync_state (
                 channel, last_poll_at_ms, last_success_at_ms, consecutive_failures, backoff_until_ms,
-                last_error, last_processed_count, total_processed_count, updated_at_ms
-             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                last_error, last_processed_count, total_processed_count,
+                deduped_by_other_channel, updated_at_ms
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
              ON CONFLICT(channel) DO UPDATE SET
                 last_poll_at_ms = excluded.last_poll_at_ms,
                 last_success_at_ms = excluded.last_success_at_ms,
@@ -1115,6 +1703,7 @@ fn persist_relay_sync_state(
                 last_error = excluded.last_error,
                 last_processed_count = excluded.last_processed_count,
                 total_processed_count = excluded.total_processed_count,
+                deduped_by_other_channel = excluded.deduped_by_other_channel,
                 updated_at_ms = excluded.updated_at_ms",
             rusqlite::params![
                 channel.as_row_key(),
@@ -1125,6 +1714,7 @@ fn persist_relay_sync_state(
                 state.last_error,
                 state.last_processed_count,
                 state.total_processed_count,
+                state.deduped_by_other_channel,
                 now
             ],
         )
@@ -1139,3123 +1729,8689 @@ fn get_transport_status() -> Result<TransportStatusResponse, String> {
         mode: status.mode.as_str().to_string(),
         relay_configured: status.relay_configured,
         relay_url: status.relay_url,
+        local_base_url: status.local_base_url,
     })
 }
 
+/// In-flight and maximum concurrent dispatches per provider.
 #[tauri::command]
-fn set_subscriber_token(
-    input: RelaySubscriberTokenInput,
-) -> Result<TransportStatusResponse, String> {
-    providers::keychain::set_relay_subscriber_token(input.token.trim())
-        .map_err(|e| e.to_string())?;
-    get_transport_status()
+fn get_provider_health() -> Result<Vec<providers::concurrency::ProviderConcurrencySnapshot>, String>
+{
+    Ok(providers::concurrency::ProviderConcurrencyLimiter::global().snapshot())
 }
 
 #[tauri::command]
-fn remove_subscriber_token() -> Result<TransportStatusResponse, String> {
-    providers::keychain::delete_relay_subscriber_token().map_err(|e| e.to_string())?;
+fn set_local_transport_base_url(
+    input: LocalTransportBaseUrlInput,
+) -> Result<TransportStatusResponse, String> {
+    let base_url = normalize_local_transport_base_url(&input.base_url)?;
+    providers::keychain::set_local_transport_base_url(&base_url).map_err(|e| e.to_string())?;
     get_transport_status()
 }
 
 #[tauri::command]
-fn set_api_key_ref(input: ApiKeyRefInput) -> Result<ApiKeyRefStatus, String> {
-    let ref_name = sanitize_api_key_ref_name(&input.ref_name)?;
-    providers::keychain::set_api_key_ref_secret(&ref_name, input.secret.trim())
-        .map_err(|e| e.to_string())?;
-    Ok(ApiKeyRefStatus {
-        ref_name,
-        configured: true,
-    })
-}
-
-#[tauri::command]
-fn remove_api_key_ref(input: ApiKeyRefDeleteInput) -> Result<ApiKeyRefStatus, String> {
-    let ref_name = sanitize_api_key_ref_name(&input.ref_name)?;
-    providers::keychain::delete_api_key_ref_secret(&ref_name).map_err(|e| e.to_string())?;
-    Ok(ApiKeyRefStatus {
-        ref_name,
-        configured: false,
-    })
+fn clear_local_transport_base_url() -> Result<TransportStatusResponse, String> {
+    providers::keychain::delete_local_transport_base_url().map_err(|e| e.to_string())?;
+    get_transport_status()
 }
 
 #[tauri::command]
-fn get_api_key_ref_status(ref_name: String) -> Result<ApiKeyRefStatus, String> {
-    let ref_name = sanitize_api_key_ref_name(&ref_name)?;
-    let configured = providers::keychain::get_api_key_ref_secret(&ref_name)
-        .map_err(|e| e.to_string())?
-        .is_some_and(|v| !v.trim().is_empty());
-    Ok(ApiKeyRefStatus {
-        ref_name,
-        configured,
-    })
+fn get_autopilot_transport_config(
+    state: tauri::State<AppState>,
+    autopilot_id: String,
+) -> Result<db::AutopilotTransportConfigRecord, String> {
+    let autopilot_id = autopilot_id.trim();
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
+    }
+    let connection = open_connection(&state)?;
+    db::get_autopilot_transport_config(&connection, autopilot_id)
 }
 
 #[tauri::command]
-fn probe_vault_extraction(
-    input: VaultExtractionProbeInput,
-) -> Result<vault_spike::VaultExtractionProbe, String> {
-    vault_spike::probe_extraction(&input.path, input.max_preview_chars).map_err(|e| e.to_string())
-}
-
-fn codex_oauth_status_response() -> Result<CodexOauthStatusResponse, String> {
-    let local_path = providers::keychain::codex_cli_auth_path()
-        .map(|p| p.display().to_string())
-        .unwrap_or_else(|| "~/.codex/auth.json".to_string());
-    let local_snapshot =
-        providers::keychain::read_codex_cli_auth_snapshot().map_err(|e| e.to_string())?;
-    let imported = providers::keychain::get_codex_oauth_bundle().map_err(|e| e.to_string())?;
-    Ok(CodexOauthStatusResponse {
-        configured: imported.is_some(),
-        local_auth_found: local_snapshot.is_some(),
-        local_auth_path: local_path,
-        local_auth_mode: local_snapshot.as_ref().map(|s| s.auth_mode.clone()),
-        imported_auth_mode: imported.as_ref().map(|b| b.auth_mode.clone()),
-        imported_at_ms: imported.as_ref().map(|b| b.imported_at_ms),
-        last_refresh: imported
-            .as_ref()
-            .and_then(|b| b.last_refresh.clone())
-            .or_else(|| local_snapshot.as_ref().and_then(|s| s.last_refresh.clone())),
-        has_refresh_token: imported
-            .as_ref()
-            .and_then(|b| b.refresh_token.as_ref())
-            .is_some_and(|v| !v.trim().is_empty()),
-    })
+fn update_autopilot_transport_config(
+    state: tauri::State<AppState>,
+    input: AutopilotTransportConfigInput,
+) -> Result<db::AutopilotTransportConfigRecord, String> {
+    let autopilot_id = input.autopilot_id.trim();
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
+    }
+    let relay_configured = ProviderRuntime::default()
+        .transport_status()
+        .relay_configured;
+    let transport_mode =
+        validate_autopilot_transport_mode(&input.transport_mode, relay_configured)?;
+    let connection = open_connection(&state)?;
+    db::upsert_autopilot_transport_config(
+        &connection,
+        &db::AutopilotTransportConfigRecord {
+            autopilot_id: autopilot_id.to_string(),
+            transport_mode,
+            updated_at_ms: now_ms(),
+        },
+    )
 }
 
 #[tauri::command]
-fn get_codex_oauth_status() -> Result<CodexOauthStatusResponse, String> {
-    codex_oauth_status_response()
+fn get_autopilot_context_limits(
+    state: tauri::State<AppState>,
+    autopilot_id: String,
+) -> Result<db::AutopilotContextLimitsRecord, String> {
+    let autopilot_id = autopilot_id.trim();
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
+    }
+    let connection = open_connection(&state)?;
+    db::get_autopilot_context_limits(&connection, autopilot_id)
 }
 
 #[tauri::command]
-fn import_codex_oauth_from_local_auth() -> Result<CodexOauthStatusResponse, String> {
-    let _bundle = providers::keychain::import_codex_oauth_from_local_auth(now_ms())
-        .map_err(|e| e.to_string())?;
-    codex_oauth_status_response()
+fn update_autopilot_context_limits(
+    state: tauri::State<AppState>,
+    input: AutopilotContextLimitsInput,
+) -> Result<db::AutopilotContextLimitsRecord, String> {
+    let connection = open_connection(&state)?;
+    update_autopilot_context_limits_internal(&connection, input)
 }
 
-#[tauri::command]
-fn remove_codex_oauth() -> Result<CodexOauthStatusResponse, String> {
-    providers::keychain::delete_codex_oauth_bundle().map_err(|e| e.to_string())?;
-    codex_oauth_status_response()
+fn update_autopilot_context_limits_internal(
+    connection: &rusqlite::Connection,
+    input: AutopilotContextLimitsInput,
+) -> Result<db::AutopilotContextLimitsRecord, String> {
+    let autopilot_id = input.autopilot_id.trim();
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
+    }
+    if let Some(chars) = input.inbox_text_max_chars {
+        if !(1_000..=runner::INBOX_TEXT_MAX_CHARS_CEILING as i64).contains(&chars) {
+            return Err(format!(
+                "Inbox text limit must be between 1000 and {} characters.",
+                runner::INBOX_TEXT_MAX_CHARS_CEILING
+            ));
+        }
+    }
+    if let Some(items) = input.daily_source_max_items {
+        if !(1..=runner::DAILY_SOURCE_MAX_ITEMS_CEILING as i64).contains(&items) {
+            return Err(format!(
+                "Daily source limit must be between 1 and {}.",
+                runner::DAILY_SOURCE_MAX_ITEMS_CEILING
+            ));
+        }
+    }
+    with_config_audit(connection, |connection| {
+        let before = db::get_autopilot_context_limits(connection, autopilot_id)?;
+        let updated = db::upsert_autopilot_context_limits(
+            connection,
+            &db::AutopilotContextLimitsRecord {
+                autopilot_id: autopilot_id.to_string(),
+                inbox_text_max_chars: input.inbox_text_max_chars,
+                daily_source_max_items: input.daily_source_max_items,
+                summarize_overflow: input.summarize_overflow,
+                updated_at_ms: now_ms(),
+            },
+        )?;
+        record_config_change(
+            connection,
+            "update_autopilot_context_limits",
+            autopilot_id,
+            Some(&before),
+            Some(&updated),
+        )?;
+        Ok(updated)
+    })
 }
 
 #[tauri::command]
-fn list_webhook_triggers(
+fn get_autopilot_web_tracking(
     state: tauri::State<AppState>,
-    autopilot_id: Option<String>,
-) -> Result<Vec<webhook_triggers::WebhookTriggerRecord>, String> {
+    autopilot_id: String,
+) -> Result<db::AutopilotWebTrackingRecord, String> {
+    let autopilot_id = autopilot_id.trim();
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
+    }
     let connection = open_connection(&state)?;
-    let relay_base = relay_webhook_base_url();
-    webhook_triggers::list_webhook_triggers(
-        &connection,
-        autopilot_id
-            .as_deref()
-            .map(|s| s.trim())
-            .filter(|s| !s.is_empty()),
-        &relay_base,
-        &|trigger_id| {
-            providers::keychain::get_webhook_trigger_secret(trigger_id)
-                .ok()
-                .flatten()
-                .is_some_and(|v| !v.trim().is_empty())
-        },
-    )
+    db::get_autopilot_web_tracking(&connection, autopilot_id)
 }
 
 #[tauri::command]
-fn create_webhook_trigger(
+fn update_autopilot_web_tracking(
     state: tauri::State<AppState>,
-    input: CreateWebhookTriggerInput,
-) -> Result<WebhookTriggerCreateResponse, String> {
+    input: AutopilotWebTrackingInput,
+) -> Result<db::AutopilotWebTrackingRecord, String> {
     let connection = open_connection(&state)?;
+    update_autopilot_web_tracking_internal(&connection, input)
+}
+
+fn update_autopilot_web_tracking_internal(
+    connection: &rusqlite::Connection,
+    input: AutopilotWebTrackingInput,
+) -> Result<db::AutopilotWebTrackingRecord, String> {
     let autopilot_id = input.autopilot_id.trim();
     if autopilot_id.is_empty() {
-        return Err("Autopilot ID is required to create a webhook trigger.".to_string());
+        return Err("Autopilot ID is required.".to_string());
     }
-    let (plan_json, provider_kind) = latest_run_plan_snapshot(&connection, autopilot_id)?;
-    let trigger_id = make_main_id("whtrig");
-    let endpoint_path = format!("hooks/{}", make_hashed_token("wh", &trigger_id));
-    let now = now_ms();
-    let max_payload_bytes = input
-        .max_payload_bytes
-        .unwrap_or(32_768)
-        .clamp(1_024, 65_536);
-    let description = input
-        .description
-        .unwrap_or_else(|| format!("Webhook trigger for {autopilot_id}"));
-    let payload = webhook_triggers::WebhookTriggerCreateInternal {
-        id: trigger_id.clone(),
-        autopilot_id: autopilot_id.to_string(),
-        status: "active".to_string(),
-        endpoint_path,
-        signature_mode: "terminus_hmac_sha256".to_string(),
-        description: description.chars().take(120).collect(),
-        max_payload_bytes,
-        allowed_content_types_json: "[\"application/json\"]".to_string(),
-        plan_json,
-        provider_kind,
-        created_at_ms: now,
-        updated_at_ms: now,
+    let tracking_params = match input.tracking_params {
+        Some(params) => {
+            let mut cleaned = Vec::<String>::new();
+            for param in params {
+                let param = param.trim().to_ascii_lowercase();
+                let name = param.strip_suffix('*').unwrap_or(&param);
+                if name.is_empty()
+                    || name.len() > 64
+                    || !name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+                {
+                    return Err(format!(
+                        "Tracking parameter \"{param}\" must be a query parameter name, optionally ending in *."
+                    ));
+                }
+                if !cleaned.contains(&param) {
+                    cleaned.push(param);
+                }
+            }
+            if cleaned.len() > 32 {
+                return Err("Use at most 32 tracking parameters.".to_string());
+            }
+            Some(cleaned)
+        }
+        None => None,
     };
-    let signing_secret = generate_secret_token("whsec");
-    providers::keychain::set_webhook_trigger_secret(&trigger_id, &signing_secret)
-        .map_err(|e| e.to_string())?;
-    let relay_base = relay_webhook_base_url();
-    let trigger =
-        webhook_triggers::create_webhook_trigger(&connection, &payload, &relay_base, &|id| {
-            providers::keychain::get_webhook_trigger_secret(id)
-                .ok()
-                .flatten()
-                .is_some_and(|v| !v.trim().is_empty())
-        })?;
-    Ok(WebhookTriggerCreateResponse {
-        trigger,
-        signing_secret_preview: signing_secret,
+    with_config_audit(connection, |connection| {
+        let before = db::get_autopilot_web_tracking(connection, autopilot_id)?;
+        let updated = db::upsert_autopilot_web_tracking(
+            connection,
+            &db::AutopilotWebTrackingRecord {
+                autopilot_id: autopilot_id.to_string(),
+                tracking_params,
+                updated_at_ms: now_ms(),
+            },
+        )?;
+        record_config_change(
+            connection,
+            "update_autopilot_web_tracking",
+            autopilot_id,
+            Some(&before),
+            Some(&updated),
+        )?;
+        Ok(updated)
     })
 }
 
 #[tauri::command]
-fn rotate_webhook_trigger_secret(
+fn get_approval_ttls(
     state: tauri::State<AppState>,
-    trigger_id: String,
-) -> Result<WebhookTriggerCreateResponse, String> {
-    let connection = open_connection(&state)?;
-    let trigger_id = trigger_id.trim();
-    if trigger_id.is_empty() {
-        return Err("Trigger ID is required.".to_string());
+    autopilot_id: String,
+) -> Result<db::ApprovalTtlRecord, String> {
+    let autopilot_id = autopilot_id.trim();
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
     }
-    let new_secret = generate_secret_token("whsec");
-    providers::keychain::set_webhook_trigger_secret(trigger_id, &new_secret)
-        .map_err(|e| e.to_string())?;
-    let relay_base = relay_webhook_base_url();
-    let trigger =
-        webhook_triggers::get_webhook_trigger(&connection, trigger_id, &relay_base, &|id| {
-            providers::keychain::get_webhook_trigger_secret(id)
-                .ok()
-                .flatten()
-                .is_some_and(|v| !v.trim().is_empty())
-        })?
-        .ok_or_else(|| "Webhook trigger not found.".to_string())?;
-    Ok(WebhookTriggerCreateResponse {
-        trigger,
-        signing_secret_preview: new_secret,
-    })
+    let connection = open_connection(&state)?;
+    db::get_approval_ttls(&connection, autopilot_id)
 }
 
 #[tauri::command]
-fn disable_webhook_trigger(
+fn update_approval_ttls(
     state: tauri::State<AppState>,
-    trigger_id: String,
-) -> Result<webhook_triggers::WebhookTriggerRecord, String> {
-    update_webhook_trigger_enabled(state, trigger_id, false)
+    input: ApprovalTtlsInput,
+) -> Result<db::ApprovalTtlRecord, String> {
+    let connection = open_connection(&state)?;
+    update_approval_ttls_internal(&connection, input)
 }
 
-#[tauri::command]
-fn enable_webhook_trigger(
-    state: tauri::State<AppState>,
-    trigger_id: String,
-) -> Result<webhook_triggers::WebhookTriggerRecord, String> {
-    update_webhook_trigger_enabled(state, trigger_id, true)
-}
+const APPROVAL_TTL_MIN_MS: i64 = 60_000;
+const APPROVAL_TTL_MAX_MS: i64 = 30 * 24 * 60 * 60 * 1000;
 
-#[tauri::command]
-fn get_webhook_trigger_events(
-    state: tauri::State<AppState>,
-    trigger_id: String,
-    limit: Option<usize>,
-) -> Result<Vec<webhook_triggers::WebhookTriggerEventRecord>, String> {
-    let connection = open_connection(&state)?;
-    let trigger_id = trigger_id.trim();
-    if trigger_id.is_empty() {
-        return Err("Trigger ID is required.".to_string());
+fn update_approval_ttls_internal(
+    connection: &rusqlite::Connection,
+    input: ApprovalTtlsInput,
+) -> Result<db::ApprovalTtlRecord, String> {
+    let autopilot_id = input.autopilot_id.trim();
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
     }
-    webhook_triggers::list_webhook_trigger_events(&connection, trigger_id, limit.unwrap_or(20))
+    let tiers = [
+        ("High", input.high_ttl_ms),
+        ("Medium", input.medium_ttl_ms),
+        ("Low", input.low_ttl_ms),
+    ];
+    for (label, ttl) in tiers {
+        if let Some(ttl) = ttl {
+            if !(APPROVAL_TTL_MIN_MS..=APPROVAL_TTL_MAX_MS).contains(&ttl) {
+                return Err(format!(
+                    "{label} approval timeout must be between 1 minute and 30 days."
+                ));
+            }
+        }
+    }
+    // Riskier steps never wait longer than safer ones; a missing timeout means "never".
+    let as_bound = |ttl: Option<i64>| ttl.unwrap_or(i64::MAX);
+    if as_bound(input.high_ttl_ms) > as_bound(input.medium_ttl_ms)
+        || as_bound(input.medium_ttl_ms) > as_bound(input.low_ttl_ms)
+    {
+        return Err(
+            "Approval timeouts must be shortest for High risk and longest for Low risk."
+                .to_string(),
+        );
+    }
+    with_config_audit(connection, |connection| {
+        let before = db::get_approval_ttls(connection, autopilot_id)?;
+        let updated = db::upsert_approval_ttls(
+            connection,
+            &db::ApprovalTtlRecord {
+                autopilot_id: autopilot_id.to_string(),
+                high_ttl_ms: input.high_ttl_ms,
+                medium_ttl_ms: input.medium_ttl_ms,
+                low_ttl_ms: input.low_ttl_ms,
+                updated_at_ms: now_ms(),
+            },
+        )?;
+        record_config_change(
+            connection,
+            "update_approval_ttls",
+            autopilot_id,
+            Some(&before),
+            Some(&updated),
+        )?;
+        Ok(updated)
+    })
 }
 
 #[tauri::command]
-fn ingest_webhook_event_local_debug(
+fn get_approval_channel_policy(
     state: tauri::State<AppState>,
-    input: WebhookEventLocalDebugInput,
-) -> Result<WebhookIngestResult, String> {
-    if !cfg!(debug_assertions) {
-        return Err("Webhook debug ingestion is only available in development builds.".to_string());
+    autopilot_id: String,
+) -> Result<db::ApprovalChannelPolicyRecord, String> {
+    let autopilot_id = autopilot_id.trim();
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
     }
-    let mut connection = open_connection(&state)?;
-    ingest_webhook_event_internal(
-        &mut connection,
-        WebhookIngestInput {
-            relay_request_id: Some(make_main_id("relay_wh_dbg")),
-            relay_callback_secret: None,
-            relay_issued_at_ms: Some(now_ms()),
-            trigger_id: input.trigger_id,
-            delivery_id: input.delivery_id,
-            content_type: input
-                .content_type
-                .unwrap_or_else(|| "application/json".to_string()),
-            body_json: input.body_json,
-            signature: None,
-            signature_ts_ms: None,
-            headers_redacted_json: None,
-            relay_channel: Some("local_debug".to_string()),
-            require_relay_callback_auth: false,
-            require_webhook_signature: false,
-        },
-    )
+    let connection = open_connection(&state)?;
+    db::get_approval_channel_policy(&connection, autopilot_id)
 }
 
 #[tauri::command]
-fn resolve_relay_webhook_callback(
+fn update_approval_channel_policy(
     state: tauri::State<AppState>,
-    input: RelayWebhookCallbackInput,
-) -> Result<WebhookIngestResult, String> {
-    let mut connection = open_connection(&state)?;
-    ingest_webhook_event_internal(
-        &mut connection,
-        WebhookIngestInput {
-            relay_request_id: Some(input.request_id),
-            relay_callback_secret: Some(input.callback_secret),
-            relay_issued_at_ms: Some(input.issued_at_ms),
-            trigger_id: input.trigger_id,
-            delivery_id: input.delivery_id,
-            content_type: input.content_type,
-            body_json: input.body_json,
-            signature: Some(input.signature),
-            signature_ts_ms: Some(input.signature_ts_ms),
-            headers_redacted_json: input.headers_redacted_json,
-            relay_channel: input.channel.or(Some("relay_webhook_callback".to_string())),
-            require_relay_callback_auth: true,
-            require_webhook_signature: true,
-        },
-    )
+    input: ApprovalChannelPolicyInput,
+) -> Result<db::ApprovalChannelPolicyRecord, String> {
+    let connection = open_connection(&state)?;
+    update_approval_channel_policy_internal(&connection, input)
+}
+
+fn update_approval_channel_policy_internal(
+    connection: &rusqlite::Connection,
+    input: ApprovalChannelPolicyInput,
+) -> Result<db::ApprovalChannelPolicyRecord, String> {
+    let autopilot_id = input.autopilot_id.trim();
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
+    }
+    let mut allowed_channels = Vec::new();
+    for raw in &input.allowed_channels {
+        let channel = raw.trim().to_ascii_lowercase();
+        if !db::APPROVAL_CHANNELS.contains(&channel.as_str()) {
+            return Err(format!(
+                "Unknown approval channel: {}. Use local_ui, relay, or email.",
+                raw.trim()
+            ));
+        }
+        if !allowed_channels.contains(&channel) {
+            allowed_channels.push(channel);
+        }
+    }
+    if allowed_channels.is_empty() {
+        return Err("Allow at least one approval channel.".to_string());
+    }
+    with_config_audit(connection, |connection| {
+        let before = db::get_approval_channel_policy(connection, autopilot_id)?;
+        let updated = db::upsert_approval_channel_policy(
+            connection,
+            &db::ApprovalChannelPolicyRecord {
+                autopilot_id: autopilot_id.to_string(),
+                allowed_channels,
+                updated_at_ms: now_ms(),
+            },
+        )?;
+        record_config_change(
+            connection,
+            "update_approval_channel_policy",
+            autopilot_id,
+            Some(&before),
+            Some(&updated),
+        )?;
+        Ok(updated)
+    })
+}
+
+fn validate_autopilot_transport_mode(
+    input: &str,
+    relay_configured: bool,
+) -> Result<String, String> {
+    if input.trim().eq_ignore_ascii_case("inherit") {
+        return Ok("inherit".to_string());
+    }
+    let mode = TransportMode::parse(input)
+        .ok_or_else(|| "Transport mode must be inherit, mock, local_http, or relay.".to_string())?;
+    if mode == TransportMode::Relay && !relay_configured {
+        return Err(
+            "Hosted relay needs a subscriber token. Sign in to Terminus before routing this Autopilot through the relay."
+                .to_string(),
+        );
+    }
+    Ok(mode.config_key().to_string())
 }
 
 #[tauri::command]
-fn get_gmail_pubsub_status(
-    state: tauri::State<AppState>,
-) -> Result<gmail_pubsub::GmailPubSubStatus, String> {
+fn probe_local_transport() -> Result<LocalTransportProbeResponse, String> {
+    let probe = LocalHttpTransport::from_config()
+        .probe()
+        .map_err(|e| e.to_string())?;
+    Ok(LocalTransportProbeResponse {
+        base_url: probe.base_url,
+        reachable: probe.reachable,
+        latency_ms: probe.latency_ms,
+        http_status: probe.http_status,
+        message: probe.message,
+    })
+}
+
+/// Round-trips the relay health endpoint and keeps the result in the short ping history.
+#[tauri::command]
+fn ping_relay(state: tauri::State<AppState>) -> Result<RelayPingResponse, String> {
     let connection = open_connection(&state)?;
-    gmail_pubsub::maybe_mark_expired(&connection, now_ms())
+    ping_relay_internal(
+        &connection,
+        &RelayTransport::new(RelayTransport::default_url()),
+    )
 }
 
 #[tauri::command]
-fn list_gmail_pubsub_events(
+fn list_relay_pings(
     state: tauri::State<AppState>,
     limit: Option<usize>,
-) -> Result<Vec<gmail_pubsub::GmailPubSubEventRecord>, String> {
+) -> Result<Vec<db::RelayPingRecord>, String> {
     let connection = open_connection(&state)?;
-    gmail_pubsub::list_events(&connection, limit.unwrap_or(20))
+    db::list_relay_pings(
+        &connection,
+        limit.unwrap_or(db::RELAY_PING_LOG_MAX_ROWS as usize),
+    )
+}
+
+fn ping_relay_internal(
+    connection: &rusqlite::Connection,
+    relay: &RelayTransport,
+) -> Result<RelayPingResponse, String> {
+    let ping = relay.ping();
+    let latest = db::RelayPingRecord {
+        id: make_main_id("relay_ping"),
+        reachable: ping.reachable,
+        status: ping.status,
+        latency_ms: ping.latency_ms,
+        http_status: ping.http_status.map(i64::from),
+        message: ping.message,
+        created_at_ms: now_ms(),
+    };
+    db::insert_relay_ping(connection, &latest)?;
+    let recent = db::list_relay_pings(connection, db::RELAY_PING_LOG_MAX_ROWS as usize)?;
+    let reachable = recent.iter().filter(|p| p.reachable).count();
+    let uptime_percent = (reachable * 100 / recent.len().max(1)) as i64;
+    Ok(RelayPingResponse {
+        latest,
+        recent,
+        uptime_percent,
+    })
+}
+
+fn normalize_local_transport_base_url(raw: &str) -> Result<String, String> {
+    let trimmed = raw.trim().trim_end_matches('/');
+    let parsed = url::Url::parse(trimmed)
+        .map_err(|_| "Local endpoint must be a valid http(s) URL.".to_string())?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err("Local endpoint must be a valid http(s) URL.".to_string());
+    }
+    if trimmed
+        .chars()
+        .any(|c| c.is_whitespace() || c == '"' || c == '\\')
+    {
+        return Err("Local endpoint URL contains unsupported characters.".to_string());
+    }
+    if parsed.query().is_some() || parsed.fragment().is_some() {
+        return Err("Local endpoint URL should not include a query or fragment.".to_string());
+    }
+    Ok(trimmed.to_string())
 }
 
 #[tauri::command]
-fn enable_gmail_pubsub(
+fn list_provider_pricing(
     state: tauri::State<AppState>,
-    input: GmailPubSubEnableInput,
-) -> Result<gmail_pubsub::GmailPubSubStatus, String> {
+) -> Result<Vec<db::ProviderPricingRecord>, String> {
     let connection = open_connection(&state)?;
-    let topic_name = sanitize_gmail_pubsub_resource_name(&input.topic_name, "topic")?;
-    let subscription_name =
-        sanitize_gmail_pubsub_resource_name(&input.subscription_name, "subscription")?;
-    let callback_mode = input
-        .callback_mode
-        .as_deref()
-        .map(validate_gmail_pubsub_callback_mode)
-        .transpose()?
-        .unwrap_or_else(|| "relay".to_string());
-    let trigger_mode = input
-        .trigger_mode
-        .as_deref()
-        .map(validate_gmail_trigger_mode)
-        .transpose()?
-        .unwrap_or_else(|| "auto".to_string());
-    gmail_pubsub::upsert_state(
-        &connection,
-        "pending_setup",
-        &trigger_mode,
-        Some(&topic_name),
-        Some(&subscription_name),
-        &callback_mode,
-        None,
-        None,
-        None,
-        0,
-        now_ms(),
-    )
+    db::list_provider_pricing(&connection)
 }
 
 #[tauri::command]
-fn disable_gmail_pubsub(
+fn update_provider_pricing(
     state: tauri::State<AppState>,
-) -> Result<gmail_pubsub::GmailPubSubStatus, String> {
+    input: ProviderPricingInput,
+) -> Result<db::ProviderPricingRecord, String> {
+    let provider_kind = normalize_pricing_provider_kind(&input.provider_kind)?;
+    let model = input.model.trim();
+    if model.is_empty() || model.len() > 120 {
+        return Err("Model name is required (max 120 characters).".to_string());
+    }
+    for price in [input.input_cents_per_1k, input.output_cents_per_1k] {
+        if !price.is_finite() || !(0.0..=10_000.0).contains(&price) {
+            return Err("Prices must be between 0 and 10000 cents per 1k tokens.".to_string());
+        }
+    }
     let connection = open_connection(&state)?;
-    let current = gmail_pubsub::get_status(&connection)?;
-    gmail_pubsub::upsert_state(
+    db::upsert_provider_pricing(
         &connection,
-        "disabled",
-        "polling",
-        current.topic_name.as_deref(),
-        current.subscription_name.as_deref(),
-        &current.callback_mode,
-        current.watch_expiration_ms,
-        current.history_id.as_deref(),
-        None,
-        0,
-        now_ms(),
-    )
+        &db::ProviderPricingRecord {
+            provider_kind: provider_kind.clone(),
+            model: model.to_string(),
+            input_cents_per_1k: input.input_cents_per_1k,
+            output_cents_per_1k: input.output_cents_per_1k,
+            is_fallback: false,
+            updated_at_ms: now_ms(),
+        },
+    )?;
+    db::get_provider_pricing(&connection, &provider_kind, model)
 }
 
 #[tauri::command]
-fn renew_gmail_pubsub_watch(
+fn estimate_provider_cost(
     state: tauri::State<AppState>,
-) -> Result<gmail_pubsub::GmailPubSubStatus, String> {
+    input: ProviderCostEstimateInput,
+) -> Result<ProviderCostEstimateResponse, String> {
+    let provider_kind = normalize_pricing_provider_kind(&input.provider_kind)?;
+    let model = input.model.trim();
     let connection = open_connection(&state)?;
-    let status = gmail_pubsub::get_status(&connection)?;
-    let topic = status
-        .topic_name
-        .as_deref()
-        .filter(|s| !s.trim().is_empty())
-        .ok_or_else(|| "Set a Gmail PubSub topic name before renewing the watch.".to_string())?;
-    let token =
-        email_connections::get_access_token(&connection, email_connections::EmailProvider::Gmail)?;
-    let (expiration_ms, history_id) = gmail_watch_register(&token, topic)?;
-    gmail_pubsub::update_watch_success(
+    let pricing = db::get_provider_pricing(&connection, &provider_kind, model)?;
+    let estimated_cost_usd_cents = db::estimate_provider_cost_usd_cents(
         &connection,
-        Some(expiration_ms),
-        Some(&history_id),
-        now_ms(),
-    )
+        &provider_kind,
+        model,
+        input.input_tokens,
+        input.output_tokens,
+    )?;
+    Ok(ProviderCostEstimateResponse {
+        pricing,
+        estimated_cost_usd_cents,
+    })
 }
 
 #[tauri::command]
-fn ingest_gmail_pubsub_local_debug(
+fn get_recipe_model_overrides(
     state: tauri::State<AppState>,
-    input: GmailPubSubLocalDebugInput,
-) -> Result<GmailPubSubIngestResult, String> {
-    if !cfg!(debug_assertions) {
-        return Err(
-            "Gmail PubSub debug ingestion is only available in development builds.".to_string(),
-        );
-    }
-    let mut connection = open_connection(&state)?;
-    ingest_gmail_pubsub_event_internal(
-        &mut connection,
-        Some(make_main_id("relay_gpub_dbg")),
-        Some("local_debug"),
-        &input.body_json,
-        false,
-        run_gmail_watcher_from_control,
-    )
+) -> Result<Vec<db::RecipeModelOverrideRecord>, String> {
+    let connection = open_connection(&state)?;
+    db::list_recipe_model_overrides(&connection)
 }
 
 #[tauri::command]
-fn resolve_relay_gmail_pubsub_callback(
+fn update_recipe_model_override(
     state: tauri::State<AppState>,
-    input: RelayGmailPubSubCallbackInput,
-) -> Result<GmailPubSubIngestResult, String> {
-    let mut connection = open_connection(&state)?;
-    validate_relay_callback_auth_fields(
-        &input.request_id,
-        &input.callback_secret,
-        input.issued_at_ms,
-        "Remote Gmail PubSub delivery is not ready yet. Generate a callback secret first.",
-    )?;
-    ingest_gmail_pubsub_event_internal(
-        &mut connection,
-        Some(input.request_id),
-        input.channel.as_deref(),
-        &input.body_json,
-        true,
-        run_gmail_watcher_from_control,
-    )
+    input: RecipeModelOverrideInput,
+) -> Result<Vec<db::RecipeModelOverrideRecord>, String> {
+    let connection = open_connection(&state)?;
+    update_recipe_model_override_internal(&connection, input)
+}
+
+fn update_recipe_model_override_internal(
+    connection: &rusqlite::Connection,
+    input: RecipeModelOverrideInput,
+) -> Result<Vec<db::RecipeModelOverrideRecord>, String> {
+    let provider_id = parse_provider(input.provider_kind.trim())?;
+    let provider_kind = provider_kind_for_schema(provider_id).as_str().to_string();
+    let recipe_kind = parse_recipe(input.recipe_kind.trim())?.as_str();
+    let target = format!("{provider_kind}:{recipe_kind}");
+    with_config_audit(connection, |connection| {
+        let before = db::get_recipe_model_override(connection, &provider_kind, recipe_kind)?;
+        let model = input.model.as_deref().map(str::trim).unwrap_or("");
+        if model.is_empty() {
+            db::delete_recipe_model_override(connection, &provider_kind, recipe_kind)?;
+        } else {
+            let default_model =
+                schema::ProviderMetadata::from_provider_id(provider_id).default_model;
+            let known = db::list_known_models(connection, &provider_kind, &default_model)?;
+            if !known.iter().any(|m| m == model) {
+                return Err(format!(
+                    "Unknown model '{model}' for {provider_kind}. Known models: {}. Add pricing for a model to make it selectable.",
+                    known.join(", ")
+                ));
+            }
+            db::upsert_recipe_model_override(
+                connection,
+                &db::RecipeModelOverrideRecord {
+                    provider_kind: provider_kind.clone(),
+                    recipe_kind: recipe_kind.to_string(),
+                    model: model.to_string(),
+                    updated_at_ms: now_ms(),
+                },
+            )?;
+        }
+        let after = db::get_recipe_model_override(connection, &provider_kind, recipe_kind)?;
+        if before != after {
+            record_config_change(
+                connection,
+                "update_recipe_model_override",
+                &target,
+                before.as_ref(),
+                after.as_ref(),
+            )?;
+        }
+        db::list_recipe_model_overrides(connection)
+    })
+}
+
+fn normalize_pricing_provider_kind(input: &str) -> Result<String, String> {
+    let provider_kind = parse_provider(input.trim())?;
+    Ok(provider_kind_for_schema(provider_kind).as_str().to_string())
 }
 
 #[tauri::command]
-fn start_recipe_run(
-    state: tauri::State<AppState>,
-    autopilot_id: String,
-    recipe: String,
-    intent: String,
-    pasted_text: Option<String>,
-    daily_sources: Option<Vec<String>>,
-    provider: String,
-    idempotency_key: String,
-    max_retries: Option<i64>,
-    plan_json: Option<String>,
-) -> Result<RunRecord, String> {
-    let mut connection = open_connection(&state)?;
-    let recipe_kind = parse_recipe(&recipe)?;
-    let provider_id = parse_provider(&provider)?;
-    let mut plan = match (recipe_kind, plan_json.as_deref()) {
-        (RecipeKind::Custom, Some(json)) => {
-            let parsed = serde_json::from_str::<AutopilotPlan>(json)
-                .map_err(|e| format!("Custom plan is invalid JSON: {e}"))?;
-            validate_custom_execution_plan(parsed, provider_id)?
-        }
-        (RecipeKind::Custom, None) => {
-            return Err(
-                "Custom runs require a generated plan. Draft the intent again and retry."
-                    .to_string(),
-            );
-        }
-        (_, _) => AutopilotPlan::from_intent(recipe_kind, intent, provider_id),
-    };
-    if let Some(text) = pasted_text {
-        if !text.trim().is_empty() {
-            plan.inbox_source_text = Some(text);
-        }
-    }
-    if let Some(sources) = daily_sources {
-        let cleaned = sources
-            .into_iter()
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect::<Vec<String>>();
-        if !cleaned.is_empty() {
-            for source in &cleaned {
-                if let Some((_, host)) = crate::web::parse_scheme_host(source) {
-                    if !plan
-                        .web_allowed_domains
-                        .iter()
-                        .any(|h| h.eq_ignore_ascii_case(&host))
-                    {
-                        plan.web_allowed_domains.push(host);
-                    }
-                }
-            }
-            plan.daily_sources = cleaned;
-        }
-    }
-
-    RunnerEngine::start_run(
-        &mut connection,
-        &autopilot_id,
-        plan,
-        &idempotency_key,
-        max_retries.unwrap_or(2),
-    )
-    .map_err(|e| e.to_string())
+fn set_subscriber_token(
+    input: RelaySubscriberTokenInput,
+) -> Result<TransportStatusResponse, String> {
+    providers::keychain::set_relay_subscriber_token(input.token.trim())
+        .map_err(|e| e.to_string())?;
+    get_transport_status()
 }
 
 #[tauri::command]
-fn run_tick(state: tauri::State<AppState>, run_id: String) -> Result<RunRecord, String> {
-    let mut connection = open_connection(&state)?;
-    RunnerEngine::run_tick(&mut connection, &run_id).map_err(|e| e.to_string())
+fn remove_subscriber_token() -> Result<TransportStatusResponse, String> {
+    providers::keychain::delete_relay_subscriber_token().map_err(|e| e.to_string())?;
+    get_transport_status()
 }
 
 #[tauri::command]
-fn resume_due_runs(
+fn set_api_key_ref(
     state: tauri::State<AppState>,
-    limit: Option<usize>,
-) -> Result<Vec<RunRecord>, String> {
-    let mut connection = open_connection(&state)?;
-    RunnerEngine::resume_due_runs(&mut connection, limit.unwrap_or(20)).map_err(|e| e.to_string())
-}
-
-#[tauri::command]
-fn create_mission_draft(
-    input: missions::CreateMissionDraftInput,
-) -> Result<missions::MissionDraft, String> {
-    missions::create_mission_draft(input)
+    input: ApiKeyRefInput,
+) -> Result<ApiKeyRefStatus, String> {
+    let ref_name = sanitize_api_key_ref_name(&input.ref_name)?;
+    providers::keychain::set_api_key_ref_secret(&ref_name, input.secret.trim())
+        .map_err(|e| e.to_string())?;
+    let connection = open_connection(&state)?;
+    db::record_api_key_ref_saved(&connection, &ref_name, now_ms())?;
+    let metadata = db::get_api_key_ref_metadata(&connection, &ref_name)?;
+    Ok(ApiKeyRefStatus::new(ref_name, true, metadata))
 }
 
 #[tauri::command]
-fn start_mission(
+fn remove_api_key_ref(
     state: tauri::State<AppState>,
-    input: missions::StartMissionInput,
-) -> Result<missions::MissionDetail, String> {
-    let mut connection = open_connection(&state)?;
-    missions::start_mission(&mut connection, input)
+    input: ApiKeyRefDeleteInput,
+) -> Result<ApiKeyRefStatus, String> {
+    let ref_name = sanitize_api_key_ref_name(&input.ref_name)?;
+    providers::keychain::delete_api_key_ref_secret(&ref_name).map_err(|e| e.to_string())?;
+    let connection = open_connection(&state)?;
+    db::delete_api_key_ref_metadata(&connection, &ref_name)?;
+    Ok(ApiKeyRefStatus::new(ref_name, false, None))
 }
 
 #[tauri::command]
-fn get_mission(
+fn get_api_key_ref_status(
     state: tauri::State<AppState>,
-    mission_id: String,
-) -> Result<missions::MissionDetail, String> {
+    ref_name: String,
+) -> Result<ApiKeyRefStatus, String> {
+    let ref_name = sanitize_api_key_ref_name(&ref_name)?;
     let connection = open_connection(&state)?;
-    missions::get_mission(&connection, &mission_id)
+    api_key_ref_status(&connection, ref_name)
 }
 
+/// Every API key ref Terminus knows about: refs saved through Connections plus refs named by
+/// stored plans. Secrets are never returned, only whether one is configured.
 #[tauri::command]
-fn list_missions(
-    state: tauri::State<AppState>,
-    limit: Option<usize>,
-) -> Result<Vec<missions::MissionRecord>, String> {
+fn list_api_key_refs(state: tauri::State<AppState>) -> Result<Vec<ApiKeyRefStatus>, String> {
     let connection = open_connection(&state)?;
-    missions::list_missions(&connection, limit.unwrap_or(20))
+    let (mut ref_names, _) = known_keychain_references(&connection)?;
+    for metadata in db::list_api_key_ref_metadata(&connection)? {
+        if !ref_names.contains(&metadata.ref_name) {
+            ref_names.push(metadata.ref_name);
+        }
+    }
+    ref_names.sort();
+    ref_names
+        .into_iter()
+        .map(|ref_name| api_key_ref_status(&connection, ref_name))
+        .collect()
 }
 
-#[tauri::command]
-fn run_mission_tick(
-    state: tauri::State<AppState>,
-    mission_id: String,
-) -> Result<missions::MissionTickResult, String> {
-    let mut connection = open_connection(&state)?;
-    missions::run_mission_tick(&mut connection, &mission_id)
+fn api_key_ref_status(
+    connection: &rusqlite::Connection,
+    ref_name: String,
+) -> Result<ApiKeyRefStatus, String> {
+    let configured = providers::keychain::get_api_key_ref_secret(&ref_name)
+        .map_err(|e| e.to_string())?
+        .is_some_and(|v| !v.trim().is_empty());
+    let metadata = db::get_api_key_ref_metadata(connection, &ref_name)?;
+    Ok(ApiKeyRefStatus::new(ref_name, configured, metadata))
 }
 
+/// Secrets whose last save or rotation is older than `max_age_days` (default 90). Anything
+/// past twice the threshold is `critical`.
 #[tauri::command]
-fn approve_run_approval(
+fn list_secret_rotation_warnings(
     state: tauri::State<AppState>,
-    approval_id: String,
-) -> Result<RunRecord, String> {
-    let mut connection = open_connection(&state)?;
-    approve_run_approval_with_context(
-        &mut connection,
-        &approval_id,
-        Some("local_ui"),
-        Some("User"),
-    )
+    max_age_days: Option<i64>,
+) -> Result<Vec<SecretRotationWarning>, String> {
+    let connection = open_connection(&state)?;
+    collect_secret_rotation_warnings(&connection, max_age_days, now_ms())
 }
 
-#[tauri::command]
-fn reject_run_approval(
-    state: tauri::State<AppState>,
-    approval_id: String,
-    reason: Option<String>,
-) -> Result<RunRecord, String> {
-    let mut connection = open_connection(&state)?;
-    reject_run_approval_with_context(
-        &mut connection,
-        &approval_id,
-        reason,
-        Some("local_ui"),
-        Some("User"),
-    )
-}
+fn collect_secret_rotation_warnings(
+    connection: &rusqlite::Connection,
+    max_age_days: Option<i64>,
+    now_ms: i64,
+) -> Result<Vec<SecretRotationWarning>, String> {
+    let max_age_days = max_age_days
+        .unwrap_or(SECRET_ROTATION_DEFAULT_MAX_AGE_DAYS)
+        .clamp(1, 3650);
+    let mut secrets: Vec<(&'static str, String, Option<String>, i64)> =
+        db::list_api_key_ref_metadata(connection)?
+            .into_iter()
+            .map(|meta| ("api_key_ref", meta.ref_name, None, meta.updated_at_ms))
+            .collect();
+    secrets.extend(
+        webhook_triggers::list_webhook_trigger_secret_ages(connection)?
+            .into_iter()
+            .map(|(id, autopilot_id, set_at_ms)| {
+                ("webhook_secret", id, Some(autopilot_id), set_at_ms)
+            }),
+    );
 
-#[tauri::command]
-fn approve_run_approval_remote(
-    state: tauri::State<AppState>,
-    input: ApprovalResolutionContextInput,
-) -> Result<RunRecord, String> {
-    let mut connection = open_connection(&state)?;
-    approve_run_approval_with_context(
-        &mut connection,
-        &input.approval_id,
-        input.channel.as_deref().or(Some("relay")),
-        input.actor_label.as_deref(),
-    )
+    let mut warnings = secrets
+        .into_iter()
+        .filter_map(|(kind, name, autopilot_id, set_at_ms)| {
+            let age_days = (now_ms - set_at_ms).max(0) / db::MS_PER_DAY;
+            if age_days <= max_age_days {
+                return None;
+            }
+            let severity = if age_days > max_age_days * 2 {
+                "critical"
+            } else {
+                "warning"
+            };
+            Some(SecretRotationWarning {
+                kind: kind.to_string(),
+                name,
+                autopilot_id,
+                secret_set_at_ms: set_at_ms,
+                age_days,
+                severity: severity.to_string(),
+            })
+        })
+        .collect::<Vec<_>>();
+    warnings.sort_by(|a, b| b.age_days.cmp(&a.age_days).then(a.name.cmp(&b.name)));
+    Ok(warnings)
 }
 
 #[tauri::command]
-fn reject_run_approval_remote(
+fn export_keychain_bundle(
     state: tauri::State<AppState>,
-    input: ApprovalResolutionContextInput,
-) -> Result<RunRecord, String> {
-    let mut connection = open_connection(&state)?;
-    reject_run_approval_with_context(
-        &mut connection,
-        &input.approval_id,
-        input.reason,
-        input.channel.as_deref().or(Some("relay")),
-        input.actor_label.as_deref(),
-    )
+    passphrase: String,
+) -> Result<KeychainBundleExport, String> {
+    let connection = open_connection(&state)?;
+    let (api_key_refs, webhook_trigger_ids) = known_keychain_references(&connection)?;
+    let entries =
+        providers::keychain::collect_managed_keychain_entries(&api_key_refs, &webhook_trigger_ids)
+            .map_err(|e| e.to_string())?;
+    let bundle = providers::keychain::seal_keychain_bundle(&entries, &passphrase)
+        .map_err(|e| e.to_string())?;
+    Ok(KeychainBundleExport {
+        bundle,
+        entry_count: entries.len(),
+    })
 }
 
 #[tauri::command]
-fn resolve_relay_approval_callback(
-    state: tauri::State<AppState>,
-    input: RelayApprovalCallbackInput,
-) -> Result<RunRecord, String> {
-    let mut connection = open_connection(&state)?;
-    resolve_relay_approval_callback_with_connection(&mut connection, &input)
+fn import_keychain_bundle(bundle: String, passphrase: String) -> Result<usize, String> {
+    let entries = providers::keychain::open_keychain_bundle(&bundle, &passphrase)
+        .map_err(|e| e.to_string())?;
+    providers::keychain::restore_keychain_entries(&entries).map_err(|e| e.to_string())
 }
 
-fn resolve_relay_approval_callback_with_connection(
-    connection: &mut rusqlite::Connection,
-    input: &RelayApprovalCallbackInput,
-) -> Result<RunRecord, String> {
-    validate_relay_callback_auth(&input)?;
-    if let Some(existing) = get_relay_callback_existing_run(connection, &input.request_id)? {
-        return Ok(existing);
-    }
-    let channel = input.channel.as_deref().or(Some("relay_callback"));
-    let actor = input.actor_label.as_deref();
-    if let Err(err) = reserve_relay_callback_event(
-        connection,
-        &input.request_id,
-        &input.approval_id,
-        &input.decision,
-        channel,
-        actor,
-    ) {
-        if err.contains("already processed") {
-            if let Some(existing) = get_relay_callback_existing_run(connection, &input.request_id)?
-            {
-                return Ok(existing);
+/// API key ref names referenced by any stored plan, plus every webhook trigger id. These are
+/// the Keychain items that can't be discovered from the Keychain itself.
+fn known_keychain_references(
+    connection: &rusqlite::Connection,
+) -> Result<(Vec<String>, Vec<String>), String> {
+    let mut stmt = connection
+        .prepare(
+            "
+            WITH plans AS (
+              SELECT plan_json FROM runs WHERE json_valid(plan_json)
+              UNION ALL
+              SELECT plan_json FROM webhook_triggers WHERE json_valid(plan_json)
+            )
+            SELECT json_extract(plan_json, '$.api_call_request.header_key_ref') AS ref_name
+            FROM plans
+            WHERE ref_name IS NOT NULL
+            UNION
+            SELECT json_extract(requests.value, '$.header_key_ref')
+            FROM plans, json_each(plans.plan_json, '$.api_call_requests') AS requests
+            WHERE json_extract(requests.value, '$.header_key_ref') IS NOT NULL
+            ",
+        )
+        .map_err(|e| format!("Failed to prepare API key ref lookup: {e}"))?;
+    let raw_refs = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Failed to query API key refs: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse API key refs: {e}"))?;
+    let mut api_key_refs = Vec::new();
+    for raw in raw_refs {
+        if let Ok(name) = sanitize_api_key_ref_name(&raw) {
+            if !api_key_refs.contains(&name) {
+                api_key_refs.push(name);
             }
         }
-        return Err(err);
     }
-    let run = match input.decision.trim().to_ascii_lowercase().as_str() {
-        "approve" | "approved" => {
-            approve_run_approval_with_context(connection, &input.approval_id, channel, actor)?
-        }
-        "reject" | "rejected" => reject_run_approval_with_context(
-            connection,
-            &input.approval_id,
-            input.reason.clone(),
-            channel,
-            actor,
-        )?,
-        _ => return Err("Unknown approval decision. Use approve or reject.".to_string()),
-    };
-    update_relay_callback_event_status(connection, &input.request_id, "applied")?;
-    Ok(run)
-}
-
-#[tauri::command]
-fn list_pending_approvals(state: tauri::State<AppState>) -> Result<Vec<ApprovalRecord>, String> {
-    let connection = open_connection(&state)?;
-    RunnerEngine::list_pending_approvals(&connection).map_err(|e| e.to_string())
-}
 
-#[tauri::command]
-fn list_pending_clarifications(
-    state: tauri::State<AppState>,
-) -> Result<Vec<ClarificationRecord>, String> {
-    let connection = open_connection(&state)?;
-    RunnerEngine::list_pending_clarifications(&connection).map_err(|e| e.to_string())
+    let mut stmt = connection
+        .prepare("SELECT id FROM webhook_triggers ORDER BY created_at_ms ASC")
+        .map_err(|e| format!("Failed to prepare webhook trigger lookup: {e}"))?;
+    let webhook_trigger_ids = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Failed to query webhook triggers: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse webhook triggers: {e}"))?;
+    Ok((api_key_refs, webhook_trigger_ids))
 }
 
 #[tauri::command]
-fn list_run_diagnostics(
-    state: tauri::State<AppState>,
-    limit: Option<usize>,
-) -> Result<Vec<diagnostics::RunDiagnosticRecord>, String> {
-    let connection = open_connection(&state)?;
-    diagnostics::list_run_diagnostics(&connection, limit.unwrap_or(20))
+fn probe_vault_extraction(
+    input: VaultExtractionProbeInput,
+) -> Result<vault_spike::VaultExtractionProbe, String> {
+    vault_spike::probe_extraction(&input.path, input.max_preview_chars).map_err(|e| e.to_string())
 }
 
-#[tauri::command]
-fn apply_intervention(
-    state: tauri::State<AppState>,
-    input: diagnostics::ApplyInterventionInput,
-) -> Result<diagnostics::ApplyInterventionResult, String> {
-    let mut connection = open_connection(&state)?;
-    diagnostics::apply_intervention(&mut connection, input)
-}
+fn codex_oauth_status_response() -> Result<CodexOauthStatusResponse, String> {
+    let local_path = providers::keychain::codex_cli_auth_path()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "~/.codex/auth.json".to_string());
+    let local_snapshot =
+        providers::keychain::read_codex_cli_auth_snapshot().map_err(|e| e.to_string())?;
+    let imported = providers::keychain::get_codex_oauth_bundle().map_err(|e| e.to_string())?;
+    Ok(CodexOauthStatusResponse {
+        configured: imported.is_some(),
+        local_auth_found: local_snapshot.is_some(),
+        local_auth_path: local_path,
+        local_auth_mode: local_snapshot.as_ref().map(|s| s.auth_mode.clone()),
+        imported_auth_mode: imported.as_ref().map(|b| b.auth_mode.clone()),
+        imported_at_ms: imported.as_ref().map(|b| b.imported_at_ms),
+        last_refresh: imported
+            .as_ref()
+            .and_then(|b| b.last_refresh.clone())
+            .or_else(|| local_snapshot.as_ref().and_then(|s| s.last_refresh.clone())),
+        has_refresh_token: imported
+            .as_ref()
+            .and_then(|b| b.refresh_token.as_ref())
+            .is_some_and(|v| !v.trim().is_empty()),
+    })
+}
 
 #[tauri::command]
-fn submit_clarification_answer(
-    state: tauri::State<AppState>,
-    clarification_id: String,
-    answer_json: String,
-) -> Result<RunRecord, String> {
-    let mut connection = open_connection(&state)?;
-    RunnerEngine::submit_clarification_answer(&mut connection, &clarification_id, &answer_json)
-        .map_err(|e| e.to_string())
+fn get_codex_oauth_status() -> Result<CodexOauthStatusResponse, String> {
+    codex_oauth_status_response()
 }
 
 #[tauri::command]
-fn get_run(state: tauri::State<AppState>, run_id: String) -> Result<RunRecord, String> {
-    let connection = open_connection(&state)?;
-    RunnerEngine::get_run(&connection, &run_id).map_err(|e| e.to_string())
+fn import_codex_oauth_from_local_auth() -> Result<CodexOauthStatusResponse, String> {
+    let _bundle = providers::keychain::import_codex_oauth_from_local_auth(now_ms())
+        .map_err(|e| e.to_string())?;
+    codex_oauth_status_response()
 }
 
 #[tauri::command]
-fn get_terminal_receipt(
-    state: tauri::State<AppState>,
-    run_id: String,
-) -> Result<Option<RunReceipt>, String> {
-    let connection = open_connection(&state)?;
-    RunnerEngine::get_terminal_receipt(&connection, &run_id).map_err(|e| e.to_string())
+fn remove_codex_oauth() -> Result<CodexOauthStatusResponse, String> {
+    providers::keychain::delete_codex_oauth_bundle().map_err(|e| e.to_string())?;
+    codex_oauth_status_response()
 }
 
 #[tauri::command]
-fn list_email_connections(
+fn list_webhook_triggers(
     state: tauri::State<AppState>,
-) -> Result<Vec<email_connections::EmailConnectionRecord>, String> {
+    autopilot_id: Option<String>,
+) -> Result<Vec<webhook_triggers::WebhookTriggerRecord>, String> {
     let connection = open_connection(&state)?;
-    email_connections::list_connections(&connection)
+    let relay_base = relay_webhook_base_url();
+    webhook_triggers::list_webhook_triggers(
+        &connection,
+        autopilot_id
+            .as_deref()
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty()),
+        &relay_base,
+        &|trigger_id| {
+            providers::keychain::get_webhook_trigger_secret(trigger_id)
+                .ok()
+                .flatten()
+                .is_some_and(|v| !v.trim().is_empty())
+        },
+    )
 }
 
 #[tauri::command]
-fn save_email_oauth_config(
+fn create_webhook_trigger(
     state: tauri::State<AppState>,
-    input: email_connections::OAuthConfigInput,
-) -> Result<(), String> {
+    input: CreateWebhookTriggerInput,
+) -> Result<WebhookTriggerCreateResponse, String> {
     let connection = open_connection(&state)?;
-    email_connections::upsert_oauth_config(&connection, input)
+    let autopilot_id = input.autopilot_id.trim();
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required to create a webhook trigger.".to_string());
+    }
+    let (plan_json, provider_kind) = latest_run_plan_snapshot(&connection, autopilot_id)?;
+    let trigger_id = make_main_id("whtrig");
+    let endpoint_path = format!("hooks/{}", make_hashed_token("wh", &trigger_id));
+    let now = now_ms();
+    let max_payload_bytes = input
+        .max_payload_bytes
+        .unwrap_or(32_768)
+        .clamp(1_024, 65_536);
+    let max_deliveries_per_minute = input
+        .max_deliveries_per_minute
+        .unwrap_or(webhook_triggers::DEFAULT_MAX_DELIVERIES_PER_MINUTE)
+        .clamp(1, webhook_triggers::MAX_DELIVERIES_PER_MINUTE_LIMIT);
+    let signature_mode = input
+        .signature_mode
+        .as_deref()
+        .map(validate_webhook_signature_mode)
+        .transpose()?
+        .unwrap_or_else(|| "terminus_hmac_sha256".to_string());
+    let description = input
+        .description
+        .unwrap_or_else(|| format!("Webhook trigger for {autopilot_id}"));
+    let payload = webhook_triggers::WebhookTriggerCreateInternal {
+        id: trigger_id.clone(),
+        autopilot_id: autopilot_id.to_string(),
+        status: "active".to_string(),
+        endpoint_path,
+        signature_mode,
+        description: description.chars().take(120).collect(),
+        max_payload_bytes,
+        max_deliveries_per_minute,
+        allowed_content_types_json: "[\"application/json\"]".to_string(),
+        plan_json,
+        provider_kind,
+        created_at_ms: now,
+        updated_at_ms: now,
+    };
+    let signing_secret =
+        webhook_signing_secret(&payload.signature_mode, input.signing_secret.as_deref())?;
+    providers::keychain::set_webhook_trigger_secret(&trigger_id, &signing_secret)
+        .map_err(|e| e.to_string())?;
+    let relay_base = relay_webhook_base_url();
+    let trigger =
+        webhook_triggers::create_webhook_trigger(&connection, &payload, &relay_base, &|id| {
+            providers::keychain::get_webhook_trigger_secret(id)
+                .ok()
+                .flatten()
+                .is_some_and(|v| !v.trim().is_empty())
+        })?;
+    Ok(WebhookTriggerCreateResponse {
+        trigger,
+        signing_secret_preview: signing_secret,
+    })
 }
 
 #[tauri::command]
-fn start_email_oauth(
+fn rotate_webhook_trigger_secret(
     state: tauri::State<AppState>,
-    provider: String,
-) -> Result<email_connections::OAuthStartResponse, String> {
+    trigger_id: String,
+    signing_secret: Option<String>,
+) -> Result<WebhookTriggerCreateResponse, String> {
     let connection = open_connection(&state)?;
-    email_connections::start_oauth(&connection, &provider)
+    let trigger_id = trigger_id.trim();
+    if trigger_id.is_empty() {
+        return Err("Trigger ID is required.".to_string());
+    }
+    let route = webhook_triggers::get_webhook_trigger_route_config(&connection, trigger_id)?
+        .ok_or_else(|| "Webhook trigger not found.".to_string())?;
+    let new_secret = webhook_signing_secret(&route.signature_mode, signing_secret.as_deref())?;
+    providers::keychain::set_webhook_trigger_secret(trigger_id, &new_secret)
+        .map_err(|e| e.to_string())?;
+    webhook_triggers::record_webhook_trigger_secret_rotated(&connection, trigger_id, now_ms())?;
+    let relay_base = relay_webhook_base_url();
+    let trigger =
+        webhook_triggers::get_webhook_trigger(&connection, trigger_id, &relay_base, &|id| {
+            providers::keychain::get_webhook_trigger_secret(id)
+                .ok()
+                .flatten()
+                .is_some_and(|v| !v.trim().is_empty())
+        })?
+        .ok_or_else(|| "Webhook trigger not found.".to_string())?;
+    Ok(WebhookTriggerCreateResponse {
+        trigger,
+        signing_secret_preview: new_secret,
+    })
 }
 
 #[tauri::command]
-fn complete_email_oauth(
+fn disable_webhook_trigger(
     state: tauri::State<AppState>,
-    input: email_connections::OAuthCompleteInput,
-) -> Result<email_connections::EmailConnectionRecord, String> {
-    let connection = open_connection(&state)?;
-    email_connections::complete_oauth(&connection, input)
+    trigger_id: String,
+) -> Result<webhook_triggers::WebhookTriggerRecord, String> {
+    update_webhook_trigger_enabled(state, trigger_id, false)
 }
 
 #[tauri::command]
-fn disconnect_email_provider(
+fn enable_webhook_trigger(
     state: tauri::State<AppState>,
-    provider: String,
-) -> Result<(), String> {
-    let connection = open_connection(&state)?;
-    email_connections::disconnect(&connection, &provider)
+    trigger_id: String,
+) -> Result<webhook_triggers::WebhookTriggerRecord, String> {
+    update_webhook_trigger_enabled(state, trigger_id, true)
 }
 
 #[tauri::command]
-fn run_inbox_watcher_tick(
+fn disable_all_webhook_triggers(
     state: tauri::State<AppState>,
-    provider: String,
-    autopilot_id: String,
-    max_items: Option<usize>,
-) -> Result<inbox_watcher::InboxWatcherTickSummary, String> {
+    autopilot_id: Option<String>,
+) -> Result<usize, String> {
     let mut connection = open_connection(&state)?;
-    inbox_watcher::run_watcher_tick(
+    webhook_triggers::pause_all_webhook_triggers(
         &mut connection,
-        &provider,
-        &autopilot_id,
-        max_items.unwrap_or(10),
+        autopilot_id
+            .as_deref()
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty()),
+        now_ms(),
     )
 }
 
 #[tauri::command]
-fn get_runner_control(state: tauri::State<AppState>) -> Result<db::RunnerControlRecord, String> {
-    let connection = open_connection(&state)?;
-    db::get_runner_control(&connection)
+fn enable_all_webhook_triggers(
+    state: tauri::State<AppState>,
+    autopilot_id: Option<String>,
+) -> Result<usize, String> {
+    let mut connection = open_connection(&state)?;
+    webhook_triggers::resume_bulk_paused_webhook_triggers(
+        &mut connection,
+        autopilot_id
+            .as_deref()
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty()),
+        now_ms(),
+    )
 }
 
 #[tauri::command]
-fn update_runner_control(
+fn get_webhook_trigger_events(
     state: tauri::State<AppState>,
-    input: RunnerControlInput,
-) -> Result<db::RunnerControlRecord, String> {
-    if !(15..=900).contains(&input.watcher_poll_seconds) {
-        return Err("Watcher poll interval must be between 15 and 900 seconds.".to_string());
-    }
-    if !(1..=25).contains(&input.watcher_max_items) {
-        return Err("Watcher max emails must be between 1 and 25.".to_string());
-    }
-    if input.gmail_autopilot_id.trim().is_empty() || input.microsoft_autopilot_id.trim().is_empty()
-    {
-        return Err("Autopilot IDs cannot be empty.".to_string());
-    }
-
+    trigger_id: String,
+    limit: Option<usize>,
+) -> Result<Vec<webhook_triggers::WebhookTriggerEventRecord>, String> {
     let connection = open_connection(&state)?;
-    let mut current = db::get_runner_control(&connection)?;
-    current.gmail_trigger_mode = validate_gmail_trigger_mode(&input.gmail_trigger_mode)?;
-    current.background_enabled = input.background_enabled;
-    current.watcher_enabled = input.watcher_enabled;
-    current.watcher_poll_seconds = input.watcher_poll_seconds;
-    current.watcher_max_items = input.watcher_max_items;
-    current.gmail_autopilot_id = input.gmail_autopilot_id.trim().to_string();
-    current.microsoft_autopilot_id = input.microsoft_autopilot_id.trim().to_string();
-    db::upsert_runner_control(&connection, &current)?;
-    db::get_runner_control(&connection)
+    let trigger_id = trigger_id.trim();
+    if trigger_id.is_empty() {
+        return Err("Trigger ID is required.".to_string());
+    }
+    webhook_triggers::list_webhook_trigger_events(&connection, trigger_id, limit.unwrap_or(20))
 }
 
 #[tauri::command]
-fn get_onboarding_state(
+fn list_webhook_dead_letters(
     state: tauri::State<AppState>,
-) -> Result<db::OnboardingStateRecord, String> {
+    limit: Option<usize>,
+) -> Result<Vec<webhook_triggers::WebhookDeadLetterRecord>, String> {
     let connection = open_connection(&state)?;
-    db::get_onboarding_state(&connection)
+    webhook_triggers::list_webhook_dead_letters(&connection, limit.unwrap_or(20))
 }
 
 #[tauri::command]
-fn save_onboarding_state(
+fn preview_webhook_run(
     state: tauri::State<AppState>,
-    input: OnboardingStateInput,
-) -> Result<db::OnboardingStateRecord, String> {
+    trigger_id: String,
+    body_json: String,
+) -> Result<WebhookRunPreview, String> {
     let connection = open_connection(&state)?;
-    let current = db::get_onboarding_state(&connection)?;
-    let now = now_ms();
-    let mark_complete = input
-        .onboarding_complete
-        .unwrap_or(current.onboarding_complete);
-    let dismissed = input.dismissed.unwrap_or(current.dismissed);
-    let payload = db::OnboardingStateRecord {
-        onboarding_complete: mark_complete,
-        dismissed,
-        role_text: input.role_text,
-        work_focus_text: input.work_focus_text,
-        biggest_pain_text: input.biggest_pain_text,
-        recommended_intent: input.recommended_intent,
-        started_at_ms: current.started_at_ms,
-        updated_at_ms: current.updated_at_ms,
-        completed_at_ms: if mark_complete {
-            current.completed_at_ms.or(Some(now))
-        } else {
-            None
-        },
-        dismissed_at_ms: if dismissed { Some(now) } else { None },
-        first_successful_run_at_ms: current.first_successful_run_at_ms,
-    };
-    db::upsert_onboarding_state(&connection, &payload)
+    preview_webhook_run_internal(&connection, trigger_id.trim(), &body_json, now_ms())
 }
 
 #[tauri::command]
-fn dismiss_onboarding(state: tauri::State<AppState>) -> Result<db::OnboardingStateRecord, String> {
+fn simulate_webhook_signature(
+    state: tauri::State<AppState>,
+    trigger_id: String,
+    body_json: String,
+    timestamp_ms: Option<i64>,
+) -> Result<WebhookSignatureSimulation, String> {
     let connection = open_connection(&state)?;
-    let current = db::get_onboarding_state(&connection)?;
-    let now = now_ms();
-    let payload = db::OnboardingStateRecord {
-        onboarding_complete: current.onboarding_complete,
-        dismissed: true,
-        role_text: current.role_text,
-        work_focus_text: current.work_focus_text,
-        biggest_pain_text: current.biggest_pain_text,
-        recommended_intent: current.recommended_intent,
-        started_at_ms: current.started_at_ms,
-        updated_at_ms: current.updated_at_ms,
-        completed_at_ms: current.completed_at_ms,
-        dismissed_at_ms: Some(now),
-        first_successful_run_at_ms: current.first_successful_run_at_ms,
+    simulate_webhook_signature_internal(
+        &connection,
+        trigger_id.trim(),
+        &body_json,
+        timestamp_ms.unwrap_or_else(now_ms),
+        &|id| {
+            providers::keychain::get_webhook_trigger_secret(id)
+                .ok()
+                .flatten()
+        },
+    )
+}
+
+#[tauri::command]
+fn get_webhook_endpoint_url(
+    state: tauri::State<AppState>,
+    trigger_id: String,
+) -> Result<WebhookEndpointSetup, String> {
+    let connection = open_connection(&state)?;
+    webhook_endpoint_setup_internal(&connection, trigger_id.trim(), &relay_webhook_base_url())
+}
+
+fn webhook_endpoint_setup_internal(
+    connection: &rusqlite::Connection,
+    trigger_id: &str,
+    relay_base_url: &str,
+) -> Result<WebhookEndpointSetup, String> {
+    let trigger =
+        webhook_triggers::get_webhook_trigger(connection, trigger_id, relay_base_url, &|_| false)?
+            .ok_or_else(|| "Webhook trigger not found.".to_string())?;
+    let (signature_header, timestamp_header, instructions) = match trigger.signature_mode.as_str()
+    {
+        "stripe" => (
+            "Stripe-Signature",
+            None,
+            "In Stripe, add this URL as a webhook endpoint and paste the trigger's signing secret. Stripe signs each event for you.",
+        ),
+        "github_sha256" => (
+            "X-Hub-Signature-256",
+            None,
+            "In GitHub, add this URL as a webhook with content type application/json and paste the trigger's signing secret. GitHub signs each delivery for you.",
+        ),
+        _ => (
+            "X-Terminus-Signature",
+            Some("X-Terminus-Timestamp"),
+            "Send the current Unix time in milliseconds as the timestamp header, and sha256=<hex> of HMAC-SHA256(secret, \"<timestamp>.<body>\") as the signature header.",
+        ),
     };
-    db::upsert_onboarding_state(&connection, &payload)
+    let mut sample_curl = format!(
+        "curl -X POST '{}' -H 'Content-Type: application/json' -H '{signature_header}: <signature>'",
+        trigger.endpoint_url
+    );
+    if let Some(header) = timestamp_header {
+        sample_curl.push_str(&format!(" -H '{header}: <timestamp_ms>'"));
+    }
+    sample_curl.push_str(" -d '{\"event\":\"test\"}'");
+    Ok(WebhookEndpointSetup {
+        trigger_id: trigger.id,
+        endpoint_url: trigger.endpoint_url,
+        signature_mode: trigger.signature_mode,
+        signature_header: signature_header.to_string(),
+        timestamp_header: timestamp_header.map(str::to_string),
+        instructions: instructions.to_string(),
+        sample_curl,
+    })
 }
 
 #[tauri::command]
-fn get_global_voice_config(state: tauri::State<AppState>) -> Result<db::VoiceConfigRecord, String> {
+fn retry_dead_letter(
+    state: tauri::State<AppState>,
+    id: String,
+) -> Result<WebhookIngestResult, String> {
+    let mut connection = open_connection(&state)?;
+    let id = id.trim();
+    if id.is_empty() {
+        return Err("Dead letter ID is required.".to_string());
+    }
+    retry_webhook_dead_letter_internal(&mut connection, id)
+}
+
+#[tauri::command]
+fn ingest_webhook_event_local_debug(
+    state: tauri::State<AppState>,
+    input: WebhookEventLocalDebugInput,
+) -> Result<WebhookIngestResult, String> {
+    if !cfg!(debug_assertions) {
+        return Err("Webhook debug ingestion is only available in development builds.".to_string());
+    }
+    let mut connection = open_connection(&state)?;
+    ingest_webhook_event_internal(
+        &mut connection,
+        WebhookIngestInput {
+            relay_request_id: Some(make_main_id("relay_wh_dbg")),
+            relay_callback_secret: None,
+            relay_issued_at_ms: Some(now_ms()),
+            trigger_id: input.trigger_id,
+            delivery_id: input.delivery_id,
+            content_type: input
+                .content_type
+                .unwrap_or_else(|| "application/json".to_string()),
+            body_json: input.body_json,
+            signature: None,
+            signature_ts_ms: None,
+            headers_redacted_json: None,
+            relay_channel: Some("local_debug".to_string()),
+            require_relay_callback_auth: false,
+            require_webhook_signature: false,
+        },
+    )
+}
+
+#[tauri::command]
+fn resolve_relay_webhook_callback(
+    state: tauri::State<AppState>,
+    input: RelayWebhookCallbackInput,
+) -> Result<WebhookIngestResult, String> {
+    let mut connection = open_connection(&state)?;
+    ingest_webhook_event_internal(
+        &mut connection,
+        WebhookIngestInput {
+            relay_request_id: Some(input.request_id),
+            relay_callback_secret: Some(input.callback_secret),
+            relay_issued_at_ms: Some(input.issued_at_ms),
+            trigger_id: input.trigger_id,
+            delivery_id: input.delivery_id,
+            content_type: input.content_type,
+            body_json: input.body_json,
+            signature: Some(input.signature),
+            signature_ts_ms: Some(input.signature_ts_ms),
+            headers_redacted_json: input.headers_redacted_json,
+            relay_channel: input.channel.or(Some("relay_webhook_callback".to_string())),
+            require_relay_callback_auth: true,
+            require_webhook_signature: true,
+        },
+    )
+}
+
+#[tauri::command]
+fn get_gmail_pubsub_status(
+    state: tauri::State<AppState>,
+) -> Result<gmail_pubsub::GmailPubSubStatus, String> {
     let connection = open_connection(&state)?;
-    db::get_global_voice_config(&connection)
+    gmail_pubsub::maybe_mark_expired(&connection, now_ms())
 }
 
 #[tauri::command]
-fn update_global_voice_config(
+fn list_gmail_pubsub_events(
     state: tauri::State<AppState>,
-    input: VoiceConfigInput,
-) -> Result<db::VoiceConfigRecord, String> {
+    limit: Option<usize>,
+) -> Result<Vec<gmail_pubsub::GmailPubSubEventRecord>, String> {
     let connection = open_connection(&state)?;
-    let payload = db::VoiceConfigRecord {
-        tone: validate_voice_tone(&input.tone)?,
-        length: validate_voice_length(&input.length)?,
-        humor: validate_voice_humor(&input.humor)?,
-        notes: sanitize_voice_notes(&input.notes),
-        updated_at_ms: now_ms(),
-    };
-    db::upsert_global_voice_config(&connection, &payload)
+    gmail_pubsub::list_events(&connection, limit.unwrap_or(20))
 }
 
 #[tauri::command]
-fn get_autopilot_voice_config(
+fn enable_gmail_pubsub(
     state: tauri::State<AppState>,
-    autopilot_id: String,
-) -> Result<db::AutopilotVoiceConfigRecord, String> {
+    input: GmailPubSubEnableInput,
+) -> Result<gmail_pubsub::GmailPubSubStatus, String> {
     let connection = open_connection(&state)?;
-    if autopilot_id.trim().is_empty() {
-        return Err("Autopilot ID is required.".to_string());
+    let topic_name = sanitize_gmail_pubsub_resource_name(&input.topic_name, "topic")?;
+    let subscription_name =
+        sanitize_gmail_pubsub_resource_name(&input.subscription_name, "subscription")?;
+    let callback_mode = input
+        .callback_mode
+        .as_deref()
+        .map(validate_gmail_pubsub_callback_mode)
+        .transpose()?
+        .unwrap_or_else(|| "relay".to_string());
+    let trigger_mode = input
+        .trigger_mode
+        .as_deref()
+        .map(validate_gmail_trigger_mode)
+        .transpose()?
+        .unwrap_or_else(|| "auto".to_string());
+    let renewal_window_ms = input
+        .renewal_window_minutes
+        .map(|minutes| {
+            let ms = minutes.saturating_mul(60_000);
+            if (gmail_pubsub::MIN_WATCH_RENEWAL_WINDOW_MS
+                ..=gmail_pubsub::MAX_WATCH_RENEWAL_WINDOW_MS)
+                .contains(&ms)
+            {
+                Ok(ms)
+            } else {
+                Err(format!(
+                    "Gmail watch renewal window must be between {} and {} minutes.",
+                    gmail_pubsub::MIN_WATCH_RENEWAL_WINDOW_MS / 60_000,
+                    gmail_pubsub::MAX_WATCH_RENEWAL_WINDOW_MS / 60_000
+                ))
+            }
+        })
+        .transpose()?;
+    let now = now_ms();
+    let status = gmail_pubsub::upsert_state(
+        &connection,
+        "pending_setup",
+        &trigger_mode,
+        Some(&topic_name),
+        Some(&subscription_name),
+        &callback_mode,
+        None,
+        None,
+        None,
+        0,
+        now,
+    )?;
+    match renewal_window_ms {
+        Some(ms) => gmail_pubsub::set_watch_renewal_window(&connection, ms, now),
+        None => Ok(status),
     }
-    db::get_autopilot_voice_config(&connection, autopilot_id.trim())
 }
 
 #[tauri::command]
-fn update_autopilot_voice_config(
+fn disable_gmail_pubsub(
     state: tauri::State<AppState>,
-    input: AutopilotVoiceConfigInput,
-) -> Result<db::AutopilotVoiceConfigRecord, String> {
+) -> Result<gmail_pubsub::GmailPubSubStatus, String> {
     let connection = open_connection(&state)?;
-    let autopilot_id = input.autopilot_id.trim();
-    if autopilot_id.is_empty() {
-        return Err("Autopilot ID is required.".to_string());
-    }
-    let payload = db::AutopilotVoiceConfigRecord {
-        autopilot_id: autopilot_id.to_string(),
-        enabled: input.enabled,
-        tone: validate_voice_tone(&input.tone)?,
-        length: validate_voice_length(&input.length)?,
-        humor: validate_voice_humor(&input.humor)?,
-        notes: sanitize_voice_notes(&input.notes),
-        updated_at_ms: now_ms(),
-    };
-    db::upsert_autopilot_voice_config(&connection, &payload)
+    let current = gmail_pubsub::get_status(&connection)?;
+    gmail_pubsub::upsert_state(
+        &connection,
+        "disabled",
+        "polling",
+        current.topic_name.as_deref(),
+        current.subscription_name.as_deref(),
+        &current.callback_mode,
+        current.watch_expiration_ms,
+        current.history_id.as_deref(),
+        None,
+        0,
+        now_ms(),
+    )
 }
 
 #[tauri::command]
-fn clear_autopilot_voice_config(
+fn renew_gmail_pubsub_watch(
     state: tauri::State<AppState>,
-    autopilot_id: String,
-) -> Result<db::AutopilotVoiceConfigRecord, String> {
+) -> Result<gmail_pubsub::GmailPubSubStatus, String> {
     let connection = open_connection(&state)?;
-    let trimmed = autopilot_id.trim();
-    if trimmed.is_empty() {
-        return Err("Autopilot ID is required.".to_string());
+    renew_gmail_pubsub_watch_internal(&connection, gmail_watch_register)
+}
+
+fn renew_gmail_pubsub_watch_internal(
+    connection: &rusqlite::Connection,
+    register: impl FnOnce(&str, &str) -> Result<(i64, String), String>,
+) -> Result<gmail_pubsub::GmailPubSubStatus, String> {
+    let status = gmail_pubsub::get_status(connection)?;
+    let topic = status
+        .topic_name
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| "Set a Gmail PubSub topic name before renewing the watch.".to_string())?;
+    let token =
+        email_connections::get_access_token(connection, email_connections::EmailProvider::Gmail)?;
+    let (expiration_ms, history_id) = register(&token, topic)?;
+    gmail_pubsub::update_watch_success(connection, Some(expiration_ms), Some(&history_id), now_ms())
+}
+
+/// Re-registers the Gmail watch from the background cycle once it is inside its renewal
+/// window, so PubSub delivery does not silently lapse into polling after seven days.
+fn maybe_auto_renew_gmail_watch(
+    connection: &rusqlite::Connection,
+    now: i64,
+    register: impl FnOnce(&str, &str) -> Result<(i64, String), String>,
+) -> Result<bool, String> {
+    let status = gmail_pubsub::get_status(connection)?;
+    if !gmail_pubsub::needs_watch_renewal(&status, now) {
+        return Ok(false);
+    }
+    match renew_gmail_pubsub_watch_internal(connection, register) {
+        Ok(_) => Ok(true),
+        Err(err) => {
+            gmail_pubsub::record_failure(connection, &err, now)?;
+            Err(err)
+        }
     }
-    db::clear_autopilot_voice_config(&connection, trimmed)?;
-    db::get_autopilot_voice_config(&connection, trimmed)
 }
 
 #[tauri::command]
-fn tick_runner_cycle(state: tauri::State<AppState>) -> Result<RunnerCycleSummary, String> {
+fn ingest_gmail_pubsub_local_debug(
+    state: tauri::State<AppState>,
+    input: GmailPubSubLocalDebugInput,
+) -> Result<GmailPubSubIngestResult, String> {
+    if !cfg!(debug_assertions) {
+        return Err(
+            "Gmail PubSub debug ingestion is only available in development builds.".to_string(),
+        );
+    }
     let mut connection = open_connection(&state)?;
-    tick_runner_cycle_internal(&mut connection, false)
+    ingest_gmail_pubsub_event_internal(
+        &mut connection,
+        Some(make_main_id("relay_gpub_dbg")),
+        Some("local_debug"),
+        &input.body_json,
+        false,
+        run_gmail_watcher_from_control,
+    )
 }
 
-fn tick_runner_cycle_internal(
-    connection: &mut rusqlite::Connection,
-    require_background_enabled: bool,
-) -> Result<RunnerCycleSummary, String> {
-    let mut control = db::get_runner_control(&connection)?;
-    if require_background_enabled && !control.background_enabled {
-        return Ok(RunnerCycleSummary {
-            watcher_status: "background_off".to_string(),
-            relay_sync_status: "background_off".to_string(),
-            providers_polled: 0,
-            fetched: 0,
-            deduped: 0,
-            started_runs: 0,
-            failed: 0,
-            resumed_due_runs: 0,
-            relay_decisions_applied: 0,
-            missed_runs_detected: 0,
-            catch_up_cycles_run: 0,
-        });
-    }
-    let now = now_ms();
-    let poll_ms = control.watcher_poll_seconds.saturating_mul(1000);
-
-    let mut summary = RunnerCycleSummary {
-        watcher_status: "idle".to_string(),
-        relay_sync_status: "idle".to_string(),
-        providers_polled: 0,
-        fetched: 0,
-        deduped: 0,
-        started_runs: 0,
-        failed: 0,
-        resumed_due_runs: 0,
-        relay_decisions_applied: 0,
-        missed_runs_detected: 0,
-        catch_up_cycles_run: 0,
-    };
-
-    let missed_cycles = compute_missed_cycles(control.watcher_last_tick_ms, now, poll_ms);
-    if missed_cycles > 0 {
-        summary.missed_runs_detected = missed_cycles;
-        control.missed_runs_count = missed_cycles;
-    }
-
-    if !control.watcher_enabled {
-        summary.watcher_status = "paused".to_string();
-    } else if let Some(last_tick) = control.watcher_last_tick_ms {
-        if now - last_tick < poll_ms {
-            summary.watcher_status = "throttled".to_string();
-        } else {
-            let catch_up_cycles = missed_cycles.min(3);
-            for _ in 0..catch_up_cycles {
-                run_watchers(connection, &control, &mut summary)?;
-                summary.catch_up_cycles_run += 1;
-            }
-            run_watchers(connection, &control, &mut summary)?;
-            control.watcher_last_tick_ms = Some(now);
-            control.missed_runs_count = 0;
-            db::upsert_runner_control(&connection, &control)?;
-            summary.watcher_status = "ran".to_string();
-        }
-    } else {
-        run_watchers(connection, &control, &mut summary)?;
-        control.watcher_last_tick_ms = Some(now);
-        control.missed_runs_count = 0;
-        db::upsert_runner_control(&connection, &control)?;
-        summary.watcher_status = "ran".to_string();
-    }
-
-    let resumed = RunnerEngine::resume_due_runs(connection, 20).map_err(|e| e.to_string())?;
-    summary.resumed_due_runs = resumed.len();
-    match tick_relay_approval_sync_internal(connection, false, RelayDecisionSyncChannel::Poll) {
-        Ok(sync) => {
-            summary.relay_sync_status = sync.status.status;
-            summary.relay_decisions_applied = sync.applied_count;
-        }
-        Err(err) => {
-            summary.relay_sync_status = "error".to_string();
-            eprintln!("relay approval sync failed: {}", sanitize_log_message(&err));
-        }
-    }
-    if summary.watcher_status == "throttled" && control.missed_runs_count > 0 {
-        db::upsert_runner_control(&connection, &control)?;
-    }
-    Ok(summary)
+#[tauri::command]
+fn resolve_relay_gmail_pubsub_callback(
+    state: tauri::State<AppState>,
+    input: RelayGmailPubSubCallbackInput,
+) -> Result<GmailPubSubIngestResult, String> {
+    let mut connection = open_connection(&state)?;
+    validate_relay_callback_auth_fields(
+        &input.request_id,
+        &input.callback_secret,
+        input.issued_at_ms,
+        "Remote Gmail PubSub delivery is not ready yet. Generate a callback secret first.",
+    )?;
+    ingest_gmail_pubsub_event_internal(
+        &mut connection,
+        Some(input.request_id),
+        input.channel.as_deref(),
+        &input.body_json,
+        true,
+        run_gmail_watcher_from_control,
+    )
 }
 
-fn spawn_background_cycle_thread(app: &tauri::AppHandle, db_path: PathBuf) {
-    let app_handle = app.clone();
-    thread::spawn(move || loop {
-        thread::sleep(Duration::from_secs(10));
-        let app_state = app_handle.state::<AppState>();
-        if app_state
-            .db_path
-            .lock()
-            .ok()
-            .and_then(|g| g.clone())
-            .is_none()
-        {
-            continue;
-        }
-        let mut connection = match open_connection_from_path(&db_path) {
-            Ok(conn) => conn,
-            Err(_) => continue,
-        };
-        if let Err(err) = tick_runner_cycle_internal(&mut connection, true) {
-            eprintln!(
-                "background runner cycle failed: {}",
-                sanitize_log_message(&err)
-            );
-        }
-    });
+#[tauri::command]
+fn start_batch_runs(
+    state: tauri::State<AppState>,
+    items: Vec<BatchRunItemInput>,
+) -> Result<BatchRunResponse, String> {
+    let mut connection = open_connection(&state)?;
+    start_batch_runs_internal(&mut connection, items)
 }
 
-fn spawn_background_relay_push_thread(app: &tauri::AppHandle, db_path: PathBuf) {
-    let app_handle = app.clone();
-    thread::spawn(move || loop {
-        thread::sleep(Duration::from_secs(5));
-        let app_state = app_handle.state::<AppState>();
-        if app_state
-            .db_path
-            .lock()
-            .ok()
-            .and_then(|g| g.clone())
-            .is_none()
-        {
+/// Starts each item in order. Idempotency keys derive from the item's position and content, so
+/// resubmitting the same batch returns the runs it already started. The daily hard cap is
+/// applied to the batch as a whole: once the next item's estimate no longer fits, it and every
+/// later item are skipped.
+fn start_batch_runs_internal(
+    connection: &mut rusqlite::Connection,
+    items: Vec<BatchRunItemInput>,
+) -> Result<BatchRunResponse, String> {
+    if items.is_empty() {
+        return Err("Add at least one intent to the batch.".to_string());
+    }
+    if items.len() > MAX_BATCH_RUN_ITEMS {
+        return Err(format!(
+            "A batch can start at most {MAX_BATCH_RUN_ITEMS} runs."
+        ));
+    }
+    let mut headroom =
+        RunnerEngine::daily_spend_headroom_usd_cents(connection).map_err(|e| e.to_string())?;
+    let mut cap_reached = false;
+    let mut results = Vec::with_capacity(items.len());
+    for (index, item) in items.into_iter().enumerate() {
+        let autopilot_id = item.autopilot_id.trim().to_string();
+        let mut result = BatchRunItemResult {
+            index,
+            autopilot_id: autopilot_id.clone(),
+            status: "failed".to_string(),
+            run_id: None,
+            estimated_cost_usd_cents: None,
+            error: None,
+        };
+        if cap_reached {
+            result.status = "skipped_cap".to_string();
+            results.push(result);
             continue;
         }
-        let mut connection = match open_connection_from_path(&db_path) {
-            Ok(conn) => conn,
-            Err(_) => continue,
-        };
-        let control = match db::get_runner_control(&connection) {
-            Ok(c) => c,
-            Err(_) => continue,
+        let plan = batch_item_plan(&autopilot_id, &item);
+        let plan = match plan {
+            Ok(plan) => plan,
+            Err(error) => {
+                result.error = Some(error);
+                results.push(result);
+                continue;
+            }
         };
-        if !control.background_enabled {
+        let estimate = RunnerEngine::estimate_plan_cost_usd_cents(connection, &plan);
+        result.estimated_cost_usd_cents = Some(estimate);
+        if estimate > headroom {
+            cap_reached = true;
+            result.status = "skipped_cap".to_string();
+            result.error = Some(
+                "Starting this run would go over today's spend cap. Try later or adjust caps."
+                    .to_string(),
+            );
+            results.push(result);
             continue;
         }
-        if let Err(err) = tick_relay_approval_sync_internal(
-            &mut connection,
-            false,
-            RelayDecisionSyncChannel::Push,
-        ) {
-            eprintln!("relay push sync failed: {}", sanitize_log_message(&err));
-        }
-    });
-}
-
-fn install_tray(app: &tauri::AppHandle) -> Result<(), String> {
-    let open_item = MenuItemBuilder::with_id("tray_open", "Open Terminus")
-        .build(app)
-        .map_err(|e| e.to_string())?;
-    let run_item = MenuItemBuilder::with_id("tray_run_now", "Run Cycle Now")
-        .build(app)
-        .map_err(|e| e.to_string())?;
-    let quit_item = MenuItemBuilder::with_id("tray_quit", "Quit")
-        .build(app)
-        .map_err(|e| e.to_string())?;
-    let menu = MenuBuilder::new(app)
-        .items(&[&open_item, &run_item, &quit_item])
-        .build()
-        .map_err(|e| e.to_string())?;
-
-    let app_handle = app.clone();
-    TrayIconBuilder::new()
-        .menu(&menu)
-        .on_menu_event(move |_, event: MenuEvent| match event.id().as_ref() {
-            "tray_open" => {
-                if let Some(window) = app_handle.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
-            }
-            "tray_run_now" => {
-                let app_state = app_handle.state::<AppState>();
-                let db_path = app_state.db_path.lock().ok().and_then(|g| g.clone());
-                if let Some(path) = db_path {
-                    if let Ok(mut connection) = open_connection_from_path(&path) {
-                        if let Err(err) = tick_runner_cycle_internal(&mut connection, false) {
-                            eprintln!("tray run cycle failed: {}", sanitize_log_message(&err));
-                        }
-                    }
-                }
-            }
-            "tray_quit" => {
-                app_handle.exit(0);
-            }
-            _ => {}
-        })
-        .on_tray_icon_event(move |tray: &TrayIcon, event: TrayIconEvent| {
-            if let TrayIconEvent::Click {
-                button: MouseButton::Left,
-                button_state: MouseButtonState::Up,
-                ..
-            } = event
-            {
-                if let Some(window) = tray.app_handle().get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
+        let idempotency_key = format!(
+            "batch:{index}:{:x}",
+            Sha256::digest(
+                format!(
+                    "{autopilot_id}\n{}\n{}\n{}",
+                    item.recipe.trim(),
+                    item.intent.trim(),
+                    item.provider.trim()
+                )
+                .as_bytes()
+            )
+        );
+        match RunnerEngine::start_run(connection, &autopilot_id, plan, &idempotency_key, 2) {
+            Ok(run) => {
+                headroom = headroom.saturating_sub(estimate);
+                result.status = "started".to_string();
+                result.run_id = Some(run.id);
             }
-        })
-        .build(app)
-        .map_err(|e| e.to_string())?;
-
-    Ok(())
-}
-
-#[tauri::command]
-fn get_autopilot_send_policy(
-    state: tauri::State<AppState>,
-    autopilot_id: String,
-) -> Result<db::AutopilotSendPolicyRecord, String> {
-    let connection = open_connection(&state)?;
-    db::get_autopilot_send_policy(&connection, autopilot_id.trim())
+            Err(error) => result.error = Some(error.to_string()),
+        }
+        results.push(result);
+    }
+    let count = |status: &str| results.iter().filter(|r| r.status == status).count();
+    Ok(BatchRunResponse {
+        started_count: count("started"),
+        failed_count: count("failed"),
+        skipped_count: count("skipped_cap"),
+        items: results,
+    })
 }
 
-#[tauri::command]
-fn update_autopilot_send_policy(
-    state: tauri::State<AppState>,
-    input: AutopilotSendPolicyInput,
-) -> Result<db::AutopilotSendPolicyRecord, String> {
-    let autopilot_id = input.autopilot_id.trim();
+fn batch_item_plan(autopilot_id: &str, item: &BatchRunItemInput) -> Result<AutopilotPlan, String> {
     if autopilot_id.is_empty() {
         return Err("Autopilot ID is required.".to_string());
     }
-    if !(1..=200).contains(&input.max_sends_per_day) {
-        return Err("Max sends per day must be between 1 and 200.".to_string());
-    }
-    if !(0..=23).contains(&input.quiet_hours_start_local)
-        || !(0..=23).contains(&input.quiet_hours_end_local)
-    {
-        return Err("Quiet hours must use 0-23 clock values.".to_string());
+    let intent = item.intent.trim();
+    if intent.is_empty() {
+        return Err("Intent is required.".to_string());
     }
-    if input.allow_sending && input.recipient_allowlist.is_empty() {
-        return Err("Add at least one allowed recipient before enabling sending.".to_string());
+    let recipe = parse_recipe(item.recipe.trim())?;
+    if matches!(recipe, RecipeKind::Custom | RecipeKind::Webhook) {
+        return Err(
+            "Batch runs support built-in recipes only. Start custom plans one at a time."
+                .to_string(),
+        );
     }
+    let provider_id = parse_provider(item.provider.trim())?;
+    Ok(AutopilotPlan::from_intent(
+        recipe,
+        intent.to_string(),
+        provider_id,
+    ))
+}
 
+/// Runs a hand-written plan through the same enforcement as `start_recipe_run` without
+/// starting anything.
+#[tauri::command]
+fn validate_plan_json(
+    state: tauri::State<AppState>,
+    plan_json: String,
+    provider: String,
+) -> Result<PlanValidationResponse, String> {
     let connection = open_connection(&state)?;
-    let cleaned_allowlist = input
-        .recipient_allowlist
-        .into_iter()
-        .map(|r| r.trim().to_ascii_lowercase())
-        .filter(|r| !r.is_empty())
-        .collect::<Vec<String>>();
-    let updated = db::AutopilotSendPolicyRecord {
-        autopilot_id: autopilot_id.to_string(),
-        allow_sending: input.allow_sending,
-        recipient_allowlist: cleaned_allowlist,
-        max_sends_per_day: input.max_sends_per_day,
-        quiet_hours_start_local: input.quiet_hours_start_local,
-        quiet_hours_end_local: input.quiet_hours_end_local,
-        allow_outside_quiet_hours: input.allow_outside_quiet_hours,
-        updated_at_ms: now_ms(),
-    };
-    db::upsert_autopilot_send_policy(&connection, &updated)?;
-    db::get_autopilot_send_policy(&connection, autopilot_id)
+    let own_domains = connected_mailbox_domains(&connection);
+    let disallowed = db::get_app_settings(&connection)?.disallowed_primitives;
+    Ok(validate_plan_json_internal(
+        &plan_json,
+        &provider,
+        &own_domains,
+        &disallowed,
+    ))
 }
 
 #[tauri::command]
-fn submit_guidance(
+fn start_recipe_run(
     state: tauri::State<AppState>,
-    input: GuidanceInput,
-) -> Result<GuidanceResponse, String> {
-    let scope_type = input.scope_type.trim().to_ascii_lowercase();
-    if !matches!(
-        scope_type.as_str(),
-        "autopilot" | "run" | "approval" | "outcome"
-    ) {
-        return Err("Choose a valid guidance scope.".to_string());
-    }
-    let scope_id = input.scope_id.trim();
-    if scope_id.is_empty() {
-        return Err("Scope ID is required.".to_string());
-    }
-    let cleaned_instruction = normalize_guidance_instruction(&input.instruction)?;
-    let (mode, message, proposed_rule) = classify_guidance(&cleaned_instruction);
-
-    let connection = open_connection(&state)?;
-    let (autopilot_id, run_id, approval_id, outcome_id) = match scope_type.as_str() {
-        "autopilot" => (Some(scope_id.to_string()), None, None, None),
-        "run" => {
-            let autopilot: Option<String> = connection
-                .query_row(
-                    "SELECT autopilot_id FROM runs WHERE id = ?1 LIMIT 1",
-                    rusqlite::params![scope_id],
-                    |row| row.get(0),
-                )
-                .optional()
-                .map_err(|e| format!("Failed to resolve run scope: {e}"))?;
-            (autopilot, Some(scope_id.to_string()), None, None)
-        }
-        "approval" => {
-            let run_ref: Option<(String, String)> = connection
-                .query_row(
-                    "SELECT a.run_id, r.autopilot_id
-                     FROM approvals a
-                     JOIN runs r ON r.id = a.run_id
-                     WHERE a.id = ?1
-                     LIMIT 1",
-                    rusqlite::params![scope_id],
-                    |row| Ok((row.get(0)?, row.get(1)?)),
-                )
-                .optional()
-                .map_err(|e| format!("Failed to resolve approval scope: {e}"))?;
-            match run_ref {
-                Some((run, auto)) => (Some(auto), Some(run), Some(scope_id.to_string()), None),
-                None => (None, None, Some(scope_id.to_string()), None),
+    autopilot_id: String,
+    recipe: String,
+    intent: String,
+    pasted_text: Option<String>,
+    daily_sources: Option<Vec<String>>,
+    provider: String,
+    idempotency_key: String,
+    max_retries: Option<i64>,
+    plan_json: Option<String>,
+    backoff_base_ms: Option<u32>,
+    backoff_max_ms: Option<u32>,
+    priority: Option<String>,
+) -> Result<RunRecord, String> {
+    let backoff =
+        RetryBackoff::from_overrides(backoff_base_ms, backoff_max_ms).map_err(|e| e.to_string())?;
+    // Runs started from the UI have someone waiting on them, so they default to interactive.
+    let priority = match priority.as_deref().map(str::trim) {
+        None | Some("") => RunPriority::Interactive,
+        Some(value) => value.parse::<RunPriority>().map_err(|e| e.to_string())?,
+    };
+    let mut connection = open_connection(&state)?;
+    let recipe_kind = parse_recipe(&recipe)?;
+    let provider_id = parse_provider(&provider)?;
+    let mut plan = match (recipe_kind, plan_json.as_deref()) {
+        (RecipeKind::Custom | RecipeKind::Webhook, Some(json)) => {
+            let parsed = serde_json::from_str::<AutopilotPlan>(json)
+                .map_err(|e| format!("Custom plan is invalid JSON: {e}"))?;
+            if parsed.recipe != recipe_kind {
+                return Err(format!(
+                    "Plan payload must use recipe={}.",
+                    recipe_kind.as_str()
+                ));
             }
+            let disallowed = db::get_app_settings(&connection)?.disallowed_primitives;
+            validate_custom_execution_plan(parsed, provider_id, &disallowed)?
         }
-        _ => (None, None, None, Some(scope_id.to_string())),
-    };
-
-    let response = GuidanceResponse {
-        mode,
-        message,
-        proposed_rule: proposed_rule.clone(),
+        (RecipeKind::Custom, None) => {
+            return Err(
+                "Custom runs require a generated plan. Draft the intent again and retry."
+                    .to_string(),
+            );
+        }
+        (_, _) => AutopilotPlan::from_intent(recipe_kind, intent, provider_id),
     };
-    let result_json =
-        serde_json::to_string(&response).map_err(|e| format!("Failed to store guidance: {e}"))?;
+    if let Some(text) = pasted_text {
+        if !text.trim().is_empty() {
+            plan.inbox_source_text = Some(text);
+        }
+    }
+    if let Some(sources) = daily_sources {
+        let cleaned = sources
+            .into_iter()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<String>>();
+        if !cleaned.is_empty() {
+            for source in &cleaned {
+                if let Some((_, host)) = crate::web::parse_scheme_host(source) {
+                    if !plan
+                        .web_allowed_domains
+                        .iter()
+                        .any(|h| h.eq_ignore_ascii_case(&host))
+                    {
+                        plan.web_allowed_domains.push(host);
+                    }
+                }
+            }
+            plan.daily_sources = cleaned;
+        }
+    }
 
-    db::insert_guidance_event(
-        &connection,
-        &db::GuidanceEventInsert {
-            id: make_main_id("guide"),
-            scope_type: scope_type.clone(),
-            scope_id: scope_id.to_string(),
-            autopilot_id,
-            run_id: run_id.clone(),
-            approval_id,
-            outcome_id,
-            mode: match mode {
-                GuidanceMode::Applied => "applied".to_string(),
-                GuidanceMode::ProposedRule => "proposed_rule".to_string(),
-                GuidanceMode::NeedsApproval => "needs_approval".to_string(),
-            },
-            instruction: cleaned_instruction.clone(),
-            result_json,
-            created_at_ms: now_ms(),
-        },
-    )?;
+    RunnerEngine::start_run_with_backoff(
+        &mut connection,
+        &autopilot_id,
+        plan,
+        &idempotency_key,
+        max_retries.unwrap_or(2),
+        backoff,
+        priority,
+    )
+    .map_err(|e| e.to_string())
+}
 
-    if let Some(run_id) = run_id {
-        let _ = connection.execute(
-            "
-            INSERT INTO activities (id, run_id, activity_type, from_state, to_state, user_message, created_at)
-            VALUES (?1, ?2, 'guidance_received', NULL, NULL, ?3, ?4)
-            ",
-            rusqlite::params![
-                make_main_id("activity"),
-                run_id,
-                truncate_for_activity(&cleaned_instruction),
-                now_ms()
-            ],
-        );
-    }
+#[tauri::command]
+fn run_tick(
+    app: tauri::AppHandle,
+    state: tauri::State<AppState>,
+    run_id: String,
+) -> Result<RunRecord, String> {
+    let mut connection = open_connection(&state)?;
+    RunnerEngine::run_tick_with_progress(&mut connection, &run_id, &TauriProgressSink { app })
+        .map_err(|e| e.to_string())
+}
 
-    Ok(response)
+#[tauri::command]
+fn resume_due_runs(
+    app: tauri::AppHandle,
+    state: tauri::State<AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<RunRecord>, String> {
+    let mut connection = open_connection(&state)?;
+    RunnerEngine::resume_due_runs_with_progress(
+        &mut connection,
+        limit.unwrap_or(20),
+        &TauriProgressSink { app },
+    )
+    .map_err(|e| e.to_string())
 }
 
-fn run_watchers(
-    connection: &mut rusqlite::Connection,
-    control: &db::RunnerControlRecord,
-    summary: &mut RunnerCycleSummary,
-) -> Result<(), String> {
-    let connections = email_connections::list_connections(connection)?;
-    for provider in connections
-        .into_iter()
-        .filter(|record| record.status == "connected")
-    {
-        if provider.provider == "gmail" {
-            let mut pubsub = gmail_pubsub::maybe_mark_expired(connection, now_ms())?;
-            pubsub.trigger_mode = control.gmail_trigger_mode.clone();
-            if !gmail_pubsub::should_poll_gmail(&pubsub, now_ms()) {
-                summary.providers_polled += 1;
-                continue;
-            }
-        }
-        let autopilot_id = if provider.provider == "gmail" {
-            control.gmail_autopilot_id.as_str()
-        } else {
-            control.microsoft_autopilot_id.as_str()
-        };
-        match inbox_watcher::run_watcher_tick(
-            connection,
-            &provider.provider,
-            autopilot_id,
-            control.watcher_max_items as usize,
-        ) {
-            Ok(result) => {
-                summary.providers_polled += 1;
-                summary.fetched += result.fetched;
-                summary.deduped += result.deduped;
-                summary.started_runs += result.started_runs;
-                summary.failed += result.failed;
-            }
-            Err(err) => {
-                summary.providers_polled += 1;
-                summary.failed += 1;
-                eprintln!(
-                    "inbox watcher tick failed for {}: {}",
-                    provider.provider,
-                    sanitize_log_message(&err)
-                );
-            }
-        }
-    }
-    Ok(())
+const RUN_PROGRESS_EVENT: &str = "run://progress";
+
+/// Forwards runner progress to the webview as `run://progress` events.
+struct TauriProgressSink {
+    app: tauri::AppHandle,
 }
 
-fn truncate_for_activity(input: &str) -> String {
-    let max = 180;
-    if input.chars().count() <= max {
-        return input.to_string();
+impl RunProgressSink for TauriProgressSink {
+    fn emit(&self, event: &RunProgressEvent) {
+        // Progress is best-effort UI feedback; the persisted run row stays authoritative.
+        let _ = self.app.emit(RUN_PROGRESS_EVENT, event);
     }
-    input.chars().take(max).collect::<String>()
 }
 
-fn now_ms() -> i64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as i64
+#[tauri::command]
+fn create_mission_draft(
+    input: missions::CreateMissionDraftInput,
+) -> Result<missions::MissionDraft, String> {
+    missions::create_mission_draft(input)
 }
 
-fn make_main_id(prefix: &str) -> String {
-    let seq = MAIN_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
-    format!("{}_{}_{}", prefix, now_ms(), seq)
+#[tauri::command]
+fn start_mission(
+    state: tauri::State<AppState>,
+    input: missions::StartMissionInput,
+) -> Result<missions::MissionDetail, String> {
+    let mut connection = open_connection(&state)?;
+    missions::start_mission(&mut connection, input)
 }
 
 #[tauri::command]
-fn record_decision_event(
+fn get_mission(
     state: tauri::State<AppState>,
-    autopilot_id: String,
-    run_id: String,
-    event_type: String,
-    step_id: Option<String>,
-    metadata_json: Option<String>,
-    client_event_id: Option<String>,
-) -> Result<(), String> {
+    mission_id: String,
+) -> Result<missions::MissionDetail, String> {
     let connection = open_connection(&state)?;
-    learning::record_decision_event_from_json(
-        &connection,
-        &autopilot_id,
-        &run_id,
-        step_id.as_deref(),
-        &event_type,
-        metadata_json.as_deref(),
-        client_event_id.as_deref(),
-    )
-    .map_err(|e| e.to_string())
+    missions::get_mission(&connection, &mission_id)
 }
 
 #[tauri::command]
-fn compact_learning_data(
+fn list_missions(
     state: tauri::State<AppState>,
-    autopilot_id: Option<String>,
-    dry_run: Option<bool>,
-) -> Result<learning::LearningCompactionSummary, String> {
+    limit: Option<usize>,
+    filter: Option<missions::MissionListFilter>,
+) -> Result<Vec<missions::MissionRecord>, String> {
     let connection = open_connection(&state)?;
-    learning::compact_learning_data(
+    missions::list_missions(
         &connection,
-        autopilot_id.as_deref(),
-        dry_run.unwrap_or(false),
+        limit.unwrap_or(20),
+        &filter.unwrap_or_default(),
     )
-    .map_err(|e| e.to_string())
 }
 
-fn generate_secret_token(prefix: &str) -> String {
-    let raw = format!(
-        "{}:{}:{}:{}",
-        prefix,
-        now_ms(),
-        make_main_id("tok"),
-        std::process::id()
-    );
-    let digest = Sha256::digest(raw.as_bytes());
-    format!(
-        "{}_{}",
-        prefix,
-        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(digest)
-    )
-}
-
-fn ensure_relay_device_id() -> Result<String, providers::types::ProviderError> {
-    if let Some(existing) =
-        providers::keychain::get_relay_device_id()?.filter(|v| !v.trim().is_empty())
-    {
-        return Ok(existing);
-    }
-    let device_id = generate_secret_token("device");
-    providers::keychain::set_relay_device_id(&device_id)?;
-    Ok(device_id)
-}
-
-fn constant_time_eq(a: &str, b: &str) -> bool {
-    let a_bytes = a.as_bytes();
-    let b_bytes = b.as_bytes();
-    if a_bytes.len() != b_bytes.len() {
-        return false;
-    }
-    let mut diff = 0u8;
-    for (x, y) in a_bytes.iter().zip(b_bytes.iter()) {
-        diff |= x ^ y;
-    }
-    diff == 0
+#[tauri::command]
+fn run_mission_tick(
+    state: tauri::State<AppState>,
+    mission_id: String,
+) -> Result<missions::MissionTickResult, String> {
+    let mut connection = open_connection(&state)?;
+    missions::run_mission_tick(&mut connection, &mission_id)
 }
 
-fn validate_relay_callback_auth(input: &RelayApprovalCallbackInput) -> Result<(), String> {
-    validate_relay_callback_auth_fields(
-        &input.request_id,
-        &input.callback_secret,
-        input.issued_at_ms,
-        "Remote approvals are not ready yet. Generate a callback secret first.",
-    )
+#[tauri::command]
+fn save_mission_template(
+    state: tauri::State<AppState>,
+    input: missions::SaveMissionTemplateInput,
+) -> Result<missions::MissionTemplateRecord, String> {
+    let connection = open_connection(&state)?;
+    missions::save_mission_template(&connection, input)
 }
 
-fn validate_relay_callback_auth_fields(
-    request_id: &str,
-    callback_secret: &str,
-    issued_at_ms: i64,
-    missing_secret_message: &str,
-) -> Result<(), String> {
-    let request_id = request_id.trim();
-    if request_id.is_empty() || request_id.len() > 120 {
-        return Err("Relay callback request id is invalid.".to_string());
-    }
-    let expected = providers::keychain::get_relay_callback_secret()
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| missing_secret_message.to_string())?;
-    if !constant_time_eq(expected.trim(), callback_secret.trim()) {
-        return Err("Relay callback authentication failed.".to_string());
-    }
-    let now = now_ms();
-    if issued_at_ms <= 0 || (now - issued_at_ms).abs() > 15 * 60 * 1000 {
-        return Err("Relay callback request expired. Retry from Terminus relay.".to_string());
-    }
-    Ok(())
+#[tauri::command]
+fn list_mission_templates(
+    state: tauri::State<AppState>,
+) -> Result<Vec<missions::MissionTemplateRecord>, String> {
+    let connection = open_connection(&state)?;
+    missions::list_mission_templates(&connection)
 }
 
-fn get_relay_callback_existing_run(
-    connection: &rusqlite::Connection,
-    request_id: &str,
-) -> Result<Option<RunRecord>, String> {
-    let approval_id: Option<String> = connection
-        .query_row(
-            "SELECT approval_id FROM relay_callback_events WHERE request_id = ?1 LIMIT 1",
-            rusqlite::params![request_id],
-            |row| row.get(0),
-        )
-        .optional()
-        .map_err(|e| format!("Could not read relay callback history: {e}"))?;
-    let Some(approval_id) = approval_id else {
-        return Ok(None);
-    };
-    let approval = RunnerEngine::get_approval_for_external(connection, &approval_id)
-        .map_err(|e| e.to_string())?;
-    let run = RunnerEngine::get_run_for_external(connection, &approval.run_id)
-        .map_err(|e| e.to_string())?;
-    Ok(Some(run))
+#[tauri::command]
+fn instantiate_mission_template(
+    state: tauri::State<AppState>,
+    input: missions::InstantiateMissionTemplateInput,
+) -> Result<missions::MissionTemplateInstance, String> {
+    let connection = open_connection(&state)?;
+    missions::instantiate_mission_template(&connection, input)
 }
 
-fn reserve_relay_callback_event(
-    connection: &rusqlite::Connection,
-    request_id: &str,
-    approval_id: &str,
-    decision: &str,
-    channel: Option<&str>,
-    actor_label: Option<&str>,
-) -> Result<(), String> {
-    let id = make_main_id("relay_cb");
-    let inserted = connection
-        .execute(
-            "INSERT OR IGNORE INTO relay_callback_events
-             (id, request_id, approval_id, decision, status, channel, actor_label, created_at_ms)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-            rusqlite::params![
-                id,
-                request_id.trim(),
-                approval_id,
-                decision.trim().to_ascii_lowercase(),
-                "received",
-                sanitize_approval_resolution_field(channel, 32),
-                sanitize_approval_resolution_field(actor_label, 80),
-                now_ms()
-            ],
-        )
-        .map_err(|e| format!("Could not record relay callback event: {e}"))?;
-    if inserted == 0 {
-        return Err("Relay callback request was already processed.".to_string());
-    }
-    Ok(())
+/// Regenerates the draft behind a pending approval with `provider`, leaving it pending.
+#[tauri::command]
+fn regenerate_step_with_provider(
+    state: tauri::State<AppState>,
+    approval_id: String,
+    provider: String,
+) -> Result<ApprovalRecord, String> {
+    let provider_kind = provider_kind_for_schema(parse_provider(&provider)?);
+    let mut connection = open_connection(&state)?;
+    RunnerEngine::regenerate_step_with_provider(&mut connection, &approval_id, provider_kind)
+        .map_err(|e| e.to_string())
 }
 
-fn update_relay_callback_event_status(
-    connection: &rusqlite::Connection,
-    request_id: &str,
-    status: &str,
-) -> Result<(), String> {
-    connection
-        .execute(
-            "UPDATE relay_callback_events SET status = ?1 WHERE request_id = ?2",
-            rusqlite::params![status, request_id.trim()],
-        )
-        .map_err(|e| format!("Could not update relay callback status: {e}"))?;
-    Ok(())
+#[tauri::command]
+fn approve_run_approval(
+    state: tauri::State<AppState>,
+    approval_id: String,
+) -> Result<RunRecord, String> {
+    let mut connection = open_connection(&state)?;
+    approve_run_approval_with_context(
+        &mut connection,
+        &approval_id,
+        ApprovalEntryPoint::LocalUi,
+        Some("local_ui"),
+        Some("User"),
+    )
 }
 
-fn sanitize_approval_resolution_field(value: Option<&str>, max_len: usize) -> Option<String> {
-    let raw = value?.trim();
-    if raw.is_empty() {
-        return None;
-    }
-    let bounded = raw
-        .chars()
-        .take(max_len)
-        .filter(|c| {
-            c.is_ascii_alphanumeric() || matches!(c, ' ' | '_' | '-' | '.' | ':' | '@' | '/')
-        })
-        .collect::<String>()
-        .trim()
-        .to_string();
-    if bounded.is_empty() {
-        None
-    } else {
-        Some(bounded)
-    }
+#[tauri::command]
+fn reject_run_approval(
+    state: tauri::State<AppState>,
+    approval_id: String,
+    reason: Option<String>,
+) -> Result<RunRecord, String> {
+    let mut connection = open_connection(&state)?;
+    reject_run_approval_with_context(
+        &mut connection,
+        &approval_id,
+        reason,
+        ApprovalEntryPoint::LocalUi,
+        Some("local_ui"),
+        Some("User"),
+    )
 }
 
-fn relay_webhook_base_url() -> String {
-    if let Ok(url) = std::env::var("TERMINUS_RELAY_WEBHOOK_URL") {
-        if !url.trim().is_empty() {
-            return url;
-        }
-    }
-    let dispatch = transport::RelayTransport::default_url();
-    if let Some((prefix, _)) = dispatch.rsplit_once('/') {
-        format!("{prefix}/webhooks")
-    } else {
-        format!("{dispatch}/webhooks")
-    }
+#[tauri::command]
+fn approve_run_approval_remote(
+    state: tauri::State<AppState>,
+    input: ApprovalResolutionContextInput,
+) -> Result<RunRecord, String> {
+    let mut connection = open_connection(&state)?;
+    approve_run_approval_with_context(
+        &mut connection,
+        &input.approval_id,
+        ApprovalEntryPoint::Relay,
+        input.channel.as_deref().or(Some("relay")),
+        input.actor_label.as_deref(),
+    )
 }
 
-fn make_hashed_token(prefix: &str, seed: &str) -> String {
-    let digest = Sha256::digest(format!("{prefix}:{seed}:{}", now_ms()).as_bytes());
-    let encoded = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(digest);
-    format!(
-        "{}_{}",
-        prefix,
-        encoded.chars().take(24).collect::<String>()
+#[tauri::command]
+fn reject_run_approval_remote(
+    state: tauri::State<AppState>,
+    input: ApprovalResolutionContextInput,
+) -> Result<RunRecord, String> {
+    let mut connection = open_connection(&state)?;
+    reject_run_approval_with_context(
+        &mut connection,
+        &input.approval_id,
+        input.reason,
+        ApprovalEntryPoint::Relay,
+        input.channel.as_deref().or(Some("relay")),
+        input.actor_label.as_deref(),
     )
 }
 
-fn latest_run_plan_snapshot(
-    connection: &rusqlite::Connection,
-    autopilot_id: &str,
-) -> Result<(String, String), String> {
-    connection
-        .query_row(
-            "SELECT plan_json, provider_kind
-             FROM runs
-             WHERE autopilot_id = ?1
-             ORDER BY updated_at DESC, created_at DESC
-             LIMIT 1",
-            rusqlite::params![autopilot_id],
-            |row| Ok((row.get(0)?, row.get(1)?)),
-        )
-        .optional()
-        .map_err(|e| format!("Could not load latest run plan for this Autopilot: {e}"))?
-        .ok_or_else(|| {
-            "Run a test once before adding a webhook trigger so Terminus can snapshot the plan."
-                .to_string()
-        })
+#[tauri::command]
+fn resolve_relay_approval_callback(
+    state: tauri::State<AppState>,
+    input: RelayApprovalCallbackInput,
+) -> Result<RunRecord, String> {
+    let mut connection = open_connection(&state)?;
+    resolve_relay_approval_callback_with_connection(&mut connection, &input, "callback")
 }
 
-fn update_webhook_trigger_enabled(
+/// `via` records which path delivered the decision (`callback`, `poll` or `push`).
+fn resolve_relay_approval_callback_with_connection(
+    connection: &mut rusqlite::Connection,
+    input: &RelayApprovalCallbackInput,
+    via: &str,
+) -> Result<RunRecord, String> {
+    validate_relay_callback_auth(&input)?;
+    if let Some(existing) = get_relay_callback_existing_run(connection, &input.request_id)? {
+        return Ok(existing);
+    }
+    let channel = input.channel.as_deref().or(Some("relay_callback"));
+    let actor = input.actor_label.as_deref();
+    if let Err(err) = reserve_relay_callback_event(
+        connection,
+        &input.request_id,
+        &input.approval_id,
+        &input.decision,
+        channel,
+        actor,
+        via,
+    ) {
+        if err.contains("already processed") {
+            if let Some(existing) = get_relay_callback_existing_run(connection, &input.request_id)?
+            {
+                return Ok(existing);
+            }
+        }
+        return Err(err);
+    }
+    let run = match input.decision.trim().to_ascii_lowercase().as_str() {
+        "approve" | "approved" => approve_run_approval_with_context(
+            connection,
+            &input.approval_id,
+            ApprovalEntryPoint::Relay,
+            channel,
+            actor,
+        )?,
+        "reject" | "rejected" => reject_run_approval_with_context(
+            connection,
+            &input.approval_id,
+            input.reason.clone(),
+            ApprovalEntryPoint::Relay,
+            channel,
+            actor,
+        )?,
+        _ => return Err("Unknown approval decision. Use approve or reject.".to_string()),
+    };
+    update_relay_callback_event_status(connection, &input.request_id, "applied")?;
+    Ok(run)
+}
+
+#[tauri::command]
+fn get_recent_logs(
+    level: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<app_log::LogEntry>, String> {
+    let min_level = match level.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+        Some(raw) => Some(
+            app_log::LogLevel::parse(raw)
+                .ok_or_else(|| format!("Unknown log level: {raw}. Use info, warn, or error."))?,
+        ),
+        None => None,
+    };
+    Ok(app_log::LogBuffer::global().recent(min_level, limit.unwrap_or(100).clamp(1, 500)))
+}
+
+#[tauri::command]
+fn get_run_queue(
     state: tauri::State<AppState>,
-    trigger_id: String,
-    enabled: bool,
-) -> Result<webhook_triggers::WebhookTriggerRecord, String> {
+    limit: Option<usize>,
+) -> Result<Vec<runner::QueuedRunRecord>, String> {
     let connection = open_connection(&state)?;
-    let trigger_id = trigger_id.trim();
-    if trigger_id.is_empty() {
-        return Err("Trigger ID is required.".to_string());
-    }
-    let status = if enabled { "active" } else { "paused" };
-    webhook_triggers::update_webhook_trigger_status(&connection, trigger_id, status, None)?;
-    let relay_base = relay_webhook_base_url();
-    webhook_triggers::get_webhook_trigger(&connection, trigger_id, &relay_base, &|id| {
-        providers::keychain::get_webhook_trigger_secret(id)
-            .ok()
-            .flatten()
-            .is_some_and(|v| !v.trim().is_empty())
-    })?
-    .ok_or_else(|| "Webhook trigger not found.".to_string())
+    let slots = providers::concurrency::ProviderConcurrencyLimiter::global().snapshot();
+    RunnerEngine::get_run_queue(
+        &connection,
+        limit.unwrap_or(50).clamp(1, 200),
+        &|provider| {
+            slots.iter().any(|slot| {
+                slot.provider == provider.as_str() && slot.in_flight >= slot.max_concurrent
+            })
+        },
+    )
+    .map_err(|e| e.to_string())
 }
 
-fn reserve_relay_webhook_callback_event(
-    connection: &rusqlite::Connection,
-    request_id: &str,
-    trigger_id: &str,
-    delivery_id: &str,
-    channel: Option<&str>,
-) -> Result<(), String> {
-    let inserted = connection
-        .execute(
-            "INSERT OR IGNORE INTO relay_webhook_callback_events
-             (id, request_id, trigger_id, delivery_id, status, channel, created_at_ms)
-             VALUES (?1, ?2, ?3, ?4, 'received', ?5, ?6)",
-            rusqlite::params![
-                make_main_id("relay_wh"),
-                request_id.trim(),
-                trigger_id,
-                delivery_id,
-                sanitize_approval_resolution_field(channel, 32),
-                now_ms(),
-            ],
-        )
-        .map_err(|e| format!("Could not record relay webhook callback event: {e}"))?;
-    if inserted == 0 {
-        return Err("Relay webhook callback request was already processed.".to_string());
-    }
-    Ok(())
+#[tauri::command]
+fn get_approval_context(
+    state: tauri::State<AppState>,
+    approval_id: String,
+) -> Result<runner::ApprovalContext, String> {
+    let connection = open_connection(&state)?;
+    RunnerEngine::get_approval_context(&connection, approval_id.trim()).map_err(|e| e.to_string())
 }
 
-fn update_relay_webhook_callback_event_status(
-    connection: &rusqlite::Connection,
-    request_id: &str,
-    status: &str,
-) -> Result<(), String> {
-    connection
-        .execute(
-            "UPDATE relay_webhook_callback_events SET status = ?1 WHERE request_id = ?2",
-            rusqlite::params![status, request_id.trim()],
-        )
-        .map_err(|e| format!("Could not update relay webhook callback status: {e}"))?;
-    Ok(())
+#[tauri::command]
+fn get_approval_reply_subject(
+    state: tauri::State<AppState>,
+    approval_id: String,
+) -> Result<String, String> {
+    let connection = open_connection(&state)?;
+    inbox_watcher::approval_reply_subject(&connection, approval_id.trim())
 }
 
-fn redact_webhook_headers_json(input: Option<&str>) -> String {
-    let Some(raw) = input.map(str::trim).filter(|v| !v.is_empty()) else {
-        return "{}".to_string();
-    };
-    let parsed = serde_json::from_str::<serde_json::Map<String, Value>>(raw);
-    let Ok(map) = parsed else {
-        return "{}".to_string();
-    };
-    let mut out = serde_json::Map::new();
-    for (key, value) in map.into_iter().take(24) {
-        let lower = key.to_ascii_lowercase();
-        let redacted = if lower.contains("authorization")
-            || lower.contains("cookie")
-            || lower.contains("secret")
-            || lower.contains("signature")
-            || lower.contains("token")
-        {
-            Value::String("[REDACTED]".to_string())
-        } else {
-            let text = match value {
-                Value::String(s) => s.chars().take(120).collect::<String>(),
-                other => other.to_string().chars().take(120).collect::<String>(),
-            };
-            Value::String(sanitize_log_message(&text))
-        };
-        out.insert(key.chars().take(48).collect(), redacted);
-    }
-    serde_json::to_string(&out).unwrap_or_else(|_| "{}".to_string())
+#[tauri::command]
+fn list_pending_approvals(state: tauri::State<AppState>) -> Result<Vec<ApprovalRecord>, String> {
+    let connection = open_connection(&state)?;
+    RunnerEngine::list_pending_approvals(&connection).map_err(|e| e.to_string())
 }
 
-fn normalize_content_type(content_type: &str) -> String {
-    content_type
-        .split(';')
-        .next()
-        .unwrap_or("")
-        .trim()
-        .to_ascii_lowercase()
+#[tauri::command]
+fn list_pending_clarifications(
+    state: tauri::State<AppState>,
+) -> Result<Vec<ClarificationRecord>, String> {
+    let connection = open_connection(&state)?;
+    RunnerEngine::list_pending_clarifications(&connection).map_err(|e| e.to_string())
 }
 
-fn payload_excerpt_from_json(body_json: &str) -> String {
-    let compact = serde_json::from_str::<Value>(body_json)
-        .map(|v| v.to_string())
-        .unwrap_or_else(|_| body_json.to_string());
-    sanitize_log_message(&compact.chars().take(280).collect::<String>())
+#[tauri::command]
+fn list_run_diagnostics(
+    state: tauri::State<AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<diagnostics::RunDiagnosticRecord>, String> {
+    let connection = open_connection(&state)?;
+    diagnostics::list_run_diagnostics(&connection, limit.unwrap_or(20))
 }
 
-fn payload_hash(body_json: &str) -> String {
-    format!("{:x}", Sha256::digest(body_json.as_bytes()))
+#[tauri::command]
+fn explain_run(
+    state: tauri::State<AppState>,
+    run_id: String,
+) -> Result<diagnostics::RunExplanation, String> {
+    let connection = open_connection(&state)?;
+    diagnostics::explain_run(&connection, &run_id)
 }
 
-fn validate_gmail_trigger_mode(input: &str) -> Result<String, String> {
-    let v = input.trim().to_ascii_lowercase();
-    match v.as_str() {
-        "polling" | "gmail_pubsub" | "auto" => Ok(v),
-        _ => Err("Gmail trigger mode must be Polling, Gmail PubSub, or Auto.".to_string()),
-    }
+#[tauri::command]
+fn apply_intervention(
+    state: tauri::State<AppState>,
+    input: diagnostics::ApplyInterventionInput,
+) -> Result<diagnostics::ApplyInterventionResult, String> {
+    let mut connection = open_connection(&state)?;
+    diagnostics::apply_intervention(&mut connection, input)
 }
 
-fn validate_gmail_pubsub_callback_mode(input: &str) -> Result<String, String> {
-    let v = input.trim().to_ascii_lowercase();
-    match v.as_str() {
-        "relay" | "local_debug" => Ok(v),
-        _ => Err("Gmail PubSub callback mode must be Relay or Local Debug.".to_string()),
-    }
+#[tauri::command]
+fn submit_clarification_answer(
+    state: tauri::State<AppState>,
+    clarification_id: String,
+    answer_json: String,
+) -> Result<RunRecord, String> {
+    let mut connection = open_connection(&state)?;
+    RunnerEngine::submit_clarification_answer(&mut connection, &clarification_id, &answer_json)
+        .map_err(|e| e.to_string())
 }
 
-fn sanitize_gmail_pubsub_resource_name(raw: &str, label: &str) -> Result<String, String> {
-    let trimmed = raw.trim();
-    if trimmed.is_empty() || trimmed.len() > 240 {
-        return Err(format!("Gmail PubSub {label} is required."));
-    }
-    let bounded = trimmed
-        .chars()
-        .take(240)
-        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '_' | '-' | '.' | ':'))
-        .collect::<String>();
-    if bounded.is_empty() || !bounded.contains('/') {
-        return Err(format!("Gmail PubSub {label} format is invalid."));
-    }
-    Ok(bounded)
+#[tauri::command]
+fn get_run(state: tauri::State<AppState>, run_id: String) -> Result<RunRecord, String> {
+    let connection = open_connection(&state)?;
+    RunnerEngine::get_run(&connection, &run_id).map_err(|e| e.to_string())
 }
 
-fn gmail_watch_register(access_token: &str, topic_name: &str) -> Result<(i64, String), String> {
-    let client = HttpClient::builder()
-        .timeout(Duration::from_secs(20))
-        .build()
-        .map_err(|_| "Could not initialize Gmail watch client.".to_string())?;
-    let body = serde_json::json!({
-        "topicName": topic_name,
-        "labelIds": ["INBOX"],
-        "labelFilterBehavior": "INCLUDE"
-    });
-    let json = client
-        .post("https://gmail.googleapis.com/gmail/v1/users/me/watch")
-        .bearer_auth(access_token)
-        .json(&body)
-        .send()
-        .map_err(|_| "Could not register Gmail PubSub watch. Check connection and try again.".to_string())?
-        .error_for_status()
-        .map_err(|e| {
-            if e.status().map(|s| s.as_u16()) == Some(429) {
-                "Gmail watch registration is rate-limited right now. Try again shortly.".to_string()
-            } else {
-                "Could not register Gmail PubSub watch. Check Gmail PubSub topic settings and try again.".to_string()
-            }
-        })?
-        .json::<Value>()
-        .map_err(|_| "Could not parse Gmail watch registration response.".to_string())?;
-    let expiration_ms = json
-        .get("expiration")
-        .and_then(|v| {
-            v.as_str()
-                .and_then(|s| s.parse::<i64>().ok())
-                .or_else(|| v.as_i64())
-        })
-        .ok_or_else(|| "Gmail watch response is missing expiration.".to_string())?;
-    let history_id = json
-        .get("historyId")
-        .and_then(|v| {
-            v.as_str()
-                .map(|s| s.to_string())
-                .or_else(|| v.as_i64().map(|n| n.to_string()))
-        })
-        .ok_or_else(|| "Gmail watch response is missing history id.".to_string())?;
-    Ok((expiration_ms, history_id))
+/// Same as `get_run`, plus the schema version the stored plan had before migration.
+#[tauri::command]
+fn get_run_with_plan(state: tauri::State<AppState>, run_id: String) -> Result<RunWithPlan, String> {
+    let connection = open_connection(&state)?;
+    RunnerEngine::get_run_with_plan(&connection, &run_id).map_err(|e| e.to_string())
 }
 
-fn reserve_relay_gmail_pubsub_callback_event(
-    connection: &rusqlite::Connection,
-    request_id: &str,
-    channel: Option<&str>,
+#[tauri::command]
+fn get_run_step_details(
+    state: tauri::State<AppState>,
+    run_id: String,
+) -> Result<Vec<RunStepDetail>, String> {
+    let connection = open_connection(&state)?;
+    RunnerEngine::get_run_step_details(&connection, &run_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_run_cost_breakdown(
+    state: tauri::State<AppState>,
+    run_id: String,
+) -> Result<RunCostBreakdown, String> {
+    let connection = open_connection(&state)?;
+    RunnerEngine::get_run_cost_breakdown(&connection, &run_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn list_primitive_guard_events(
+    state: tauri::State<AppState>,
+    run_id: String,
+) -> Result<Vec<primitives::PrimitiveGuardEvent>, String> {
+    let connection = open_connection(&state)?;
+    primitives::list_primitive_guard_events(&connection, &run_id)
+}
+
+#[tauri::command]
+fn get_terminal_receipt(
+    state: tauri::State<AppState>,
+    run_id: String,
+) -> Result<Option<RunReceipt>, String> {
+    let connection = open_connection(&state)?;
+    RunnerEngine::get_terminal_receipt(&connection, &run_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn compare_run_receipts(
+    state: tauri::State<AppState>,
+    run_id_a: String,
+    run_id_b: String,
+) -> Result<RunReceiptComparison, String> {
+    let connection = open_connection(&state)?;
+    RunnerEngine::compare_run_receipts(&connection, &run_id_a, &run_id_b).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn list_email_connections(
+    state: tauri::State<AppState>,
+) -> Result<Vec<email_connections::EmailConnectionRecord>, String> {
+    let connection = open_connection(&state)?;
+    email_connections::list_connections(&connection)
+}
+
+#[tauri::command]
+fn save_email_oauth_config(
+    state: tauri::State<AppState>,
+    input: email_connections::OAuthConfigInput,
 ) -> Result<(), String> {
-    let inserted = connection
-        .execute(
-            "INSERT OR IGNORE INTO relay_gmail_pubsub_callback_events
-             (id, request_id, status, channel, created_at_ms)
-             VALUES (?1, ?2, 'received', ?3, ?4)",
-            rusqlite::params![
-                make_main_id("relay_gp"),
-                request_id.trim(),
-                sanitize_approval_resolution_field(channel, 32),
-                now_ms()
-            ],
-        )
-        .map_err(|e| format!("Could not record relay Gmail PubSub callback event: {e}"))?;
-    if inserted == 0 {
-        return Err("Relay Gmail PubSub callback request was already processed.".to_string());
-    }
-    Ok(())
+    let connection = open_connection(&state)?;
+    email_connections::upsert_oauth_config(&connection, input)
 }
 
-fn update_relay_gmail_pubsub_callback_event_status(
-    connection: &rusqlite::Connection,
-    request_id: &str,
-    status: &str,
+#[tauri::command]
+fn start_email_oauth(
+    state: tauri::State<AppState>,
+    provider: String,
+) -> Result<email_connections::OAuthStartResponse, String> {
+    let connection = open_connection(&state)?;
+    email_connections::start_oauth(&connection, &provider)
+}
+
+#[tauri::command]
+fn complete_email_oauth(
+    state: tauri::State<AppState>,
+    input: email_connections::OAuthCompleteInput,
+) -> Result<email_connections::EmailConnectionRecord, String> {
+    let connection = open_connection(&state)?;
+    email_connections::complete_oauth(&connection, input)
+}
+
+#[tauri::command]
+fn disconnect_email_provider(
+    state: tauri::State<AppState>,
+    provider: String,
 ) -> Result<(), String> {
-    connection
-        .execute(
-            "UPDATE relay_gmail_pubsub_callback_events SET status = ?1 WHERE request_id = ?2",
-            rusqlite::params![status, request_id.trim()],
-        )
-        .map_err(|e| format!("Could not update relay Gmail PubSub callback status: {e}"))?;
-    Ok(())
+    let connection = open_connection(&state)?;
+    email_connections::disconnect(&connection, &provider)
 }
 
-fn run_gmail_watcher_from_control(
-    connection: &mut rusqlite::Connection,
+#[tauri::command]
+fn list_email_labels(
+    state: tauri::State<AppState>,
+    provider: String,
+    refresh: Option<bool>,
+) -> Result<Vec<email_connections::EmailLabelRecord>, String> {
+    let provider = email_connections::EmailProvider::parse(provider.trim())
+        .ok_or_else(|| "Unsupported email provider.".to_string())?;
+    let connection = open_connection(&state)?;
+    email_connections::list_email_labels(&connection, provider, refresh.unwrap_or(false))
+}
+
+#[tauri::command]
+fn list_autopilot_triage_labels(
+    state: tauri::State<AppState>,
+    autopilot_id: String,
+) -> Result<Vec<db::AutopilotTriageLabelRecord>, String> {
+    let connection = open_connection(&state)?;
+    db::list_autopilot_triage_labels(&connection, autopilot_id.trim())
+}
+
+/// Files the Autopilot's triaged mail under `label` (id or name) for `provider`, or back to
+/// plain archiving when `label` is omitted.
+#[tauri::command]
+fn set_autopilot_triage_label(
+    state: tauri::State<AppState>,
+    autopilot_id: String,
+    provider: String,
+    label: Option<String>,
+) -> Result<Vec<db::AutopilotTriageLabelRecord>, String> {
+    let connection = open_connection(&state)?;
+    set_autopilot_triage_label_internal(
+        &connection,
+        autopilot_id.trim(),
+        provider.trim(),
+        label.as_deref(),
+    )
+}
+
+fn set_autopilot_triage_label_internal(
+    connection: &rusqlite::Connection,
+    autopilot_id: &str,
+    provider: &str,
+    label: Option<&str>,
+) -> Result<Vec<db::AutopilotTriageLabelRecord>, String> {
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
+    }
+    let provider = email_connections::EmailProvider::parse(provider)
+        .ok_or_else(|| "Unsupported email provider.".to_string())?;
+    // Resolving may refresh the provider's label list, so it stays outside the transaction.
+    let resolved = label
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|target| email_connections::resolve_email_label(connection, provider, target))
+        .transpose()?;
+    with_config_audit(connection, |connection| {
+        let before = db::list_autopilot_triage_labels(connection, autopilot_id)?;
+        match resolved {
+            Some(resolved) => {
+                db::upsert_autopilot_triage_label(
+                    connection,
+                    &db::AutopilotTriageLabelRecord {
+                        autopilot_id: autopilot_id.to_string(),
+                        provider: provider.as_str().to_string(),
+                        label_id: resolved.label_id,
+                        label_name: resolved.name,
+                        updated_at_ms: now_ms(),
+                    },
+                )?;
+            }
+            None => db::clear_autopilot_triage_label(connection, autopilot_id, provider.as_str())?,
+        }
+        let after = db::list_autopilot_triage_labels(connection, autopilot_id)?;
+        record_config_change(
+            connection,
+            "set_autopilot_triage_label",
+            &format!("autopilot:{autopilot_id}"),
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
+}
+
+#[tauri::command]
+fn run_inbox_watcher_tick(
+    state: tauri::State<AppState>,
+    provider: String,
+    autopilot_id: String,
+    max_items: Option<usize>,
 ) -> Result<inbox_watcher::InboxWatcherTickSummary, String> {
-    let control = db::get_runner_control(connection)?;
+    let mut connection = open_connection(&state)?;
     inbox_watcher::run_watcher_tick(
-        connection,
-        "gmail",
-        &control.gmail_autopilot_id,
-        control.watcher_max_items as usize,
+        &mut connection,
+        &provider,
+        &autopilot_id,
+        max_items.unwrap_or(10),
+        &resolve_email_reply_approval,
     )
 }
 
-fn ingest_gmail_pubsub_event_internal<F>(
-    connection: &mut rusqlite::Connection,
-    relay_request_id: Option<String>,
-    relay_channel: Option<&str>,
-    body_json: &str,
-    require_relay_callback_auth: bool,
-    fetch_and_queue: F,
-) -> Result<GmailPubSubIngestResult, String>
-where
-    F: FnOnce(&mut rusqlite::Connection) -> Result<inbox_watcher::InboxWatcherTickSummary, String>,
-{
-    let now = now_ms();
-    if require_relay_callback_auth {
-        let request_id = relay_request_id.as_deref().unwrap_or("");
-        if let Err(err) =
-            reserve_relay_gmail_pubsub_callback_event(connection, request_id, relay_channel)
-        {
-            if err.contains("already processed") {
-                return Ok(GmailPubSubIngestResult {
-                    status: "duplicate".to_string(),
-                    event_dedupe_key: request_id.to_string(),
-                    created_run_count: 0,
-                    message: "Relay Gmail PubSub callback request was already processed."
-                        .to_string(),
-                });
-            }
-            return Err(err);
+/// One-stop "is everything set up right?" view for support. Rolls transport, relay, email,
+/// Gmail PubSub, background and secret-age checks into a single `ok | degraded | error`.
+#[tauri::command]
+fn get_app_healthcheck(state: tauri::State<AppState>) -> Result<AppHealthcheck, String> {
+    let transport = ProviderRuntime::default().transport_status();
+    let relay_callback_ready = providers::keychain::get_relay_callback_secret()
+        .ok()
+        .flatten()
+        .is_some_and(|v| !v.trim().is_empty());
+    let connection = open_connection(&state)?;
+    build_app_healthcheck(&connection, transport, relay_callback_ready, now_ms())
+}
+
+fn build_app_healthcheck(
+    connection: &rusqlite::Connection,
+    transport: TransportStatus,
+    relay_callback_ready: bool,
+    now_ms: i64,
+) -> Result<AppHealthcheck, String> {
+    let email_connections = email_connections::list_connections(connection)?;
+    let gmail_pubsub = gmail_pubsub::maybe_mark_expired(connection, now_ms)?;
+    let background_enabled = db::get_runner_control(connection)?.background_enabled;
+    let stale_secret_count = collect_secret_rotation_warnings(connection, None, now_ms)?.len();
+
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    if transport.mode == TransportMode::Relay && !transport.relay_configured {
+        errors.push("Hosted relay is selected but no subscriber token is saved.".to_string());
+    }
+    if transport.relay_configured && !relay_callback_ready {
+        warnings.push("Remote approvals need a relay callback secret.".to_string());
+    }
+    for email in &email_connections {
+        let unhealthy = !matches!(email.status.as_str(), "connected" | "disconnected")
+            || email.last_error.is_some()
+            || (email.status == "connected" && email.watcher_consecutive_failures > 0);
+        if unhealthy {
+            warnings.push(format!("{} connection needs attention.", email.provider));
         }
     }
+    if matches!(gmail_pubsub.status.as_str(), "error" | "expired") {
+        warnings.push(format!("Gmail PubSub is {}.", gmail_pubsub.status));
+    }
+    if stale_secret_count > 0 {
+        warnings.push(format!(
+            "{stale_secret_count} secret(s) are due for rotation."
+        ));
+    }
 
-    let env = match gmail_pubsub::parse_pubsub_envelope(body_json) {
-        Ok(v) => v,
-        Err(err) => {
-            let _ = gmail_pubsub::record_failure(connection, &err, now);
-            if require_relay_callback_auth {
-                let _ = update_relay_gmail_pubsub_callback_event_status(
-                    connection,
-                    relay_request_id.as_deref().unwrap_or(""),
-                    "rejected",
-                );
-            }
-            return Ok(GmailPubSubIngestResult {
-                status: "rejected".to_string(),
-                event_dedupe_key: "invalid".to_string(),
-                created_run_count: 0,
-                message: err,
-            });
-        }
+    let overall = if !errors.is_empty() {
+        "error"
+    } else if !warnings.is_empty() {
+        "degraded"
+    } else {
+        "ok"
     };
-
-    let inserted = gmail_pubsub::insert_event(
-        connection,
-        &gmail_pubsub::GmailPubSubEventInsert {
-            id: make_main_id("gpub_evt"),
-            provider: "gmail".to_string(),
-            message_id: Some(env.message_id.clone()),
-            event_dedupe_key: env.dedupe_key.clone(),
-            history_id: env.history_id.clone(),
-            published_at_ms: env.published_at_ms,
-            received_at_ms: now,
-            status: "accepted".to_string(),
-            failure_reason: None,
-            created_run_count: 0,
-            created_at_ms: now,
+    errors.extend(warnings);
+    Ok(AppHealthcheck {
+        overall: overall.to_string(),
+        issues: errors,
+        transport: TransportStatusResponse {
+            mode: transport.mode.as_str().to_string(),
+            relay_configured: transport.relay_configured,
+            relay_url: transport.relay_url,
+            local_base_url: transport.local_base_url,
         },
-    )?;
-    if !inserted {
-        if require_relay_callback_auth {
-            let _ = update_relay_gmail_pubsub_callback_event_status(
-                connection,
-                relay_request_id.as_deref().unwrap_or(""),
-                "duplicate",
-            );
-        }
-        return Ok(GmailPubSubIngestResult {
-            status: "duplicate".to_string(),
-            event_dedupe_key: env.dedupe_key,
-            created_run_count: 0,
-            message: "Duplicate Gmail PubSub event ignored.".to_string(),
-        });
-    }
+        relay_callback_ready,
+        email_connections,
+        gmail_pubsub,
+        background_enabled,
+        stale_secret_count,
+    })
+}
 
-    gmail_pubsub::update_event_status(connection, &env.dedupe_key, "queued_fetch", None, None)?;
-    match fetch_and_queue(connection) {
-        Ok(summary) => {
-            gmail_pubsub::update_event_status(
-                connection,
-                &env.dedupe_key,
-                "accepted",
-                None,
-                Some(summary.started_runs as i64),
-            )?;
-            gmail_pubsub::touch_event_success(connection, now, env.history_id.as_deref())?;
-            if require_relay_callback_auth {
-                update_relay_gmail_pubsub_callback_event_status(
-                    connection,
-                    relay_request_id.as_deref().unwrap_or(""),
-                    "applied",
-                )?;
-            }
-            Ok(GmailPubSubIngestResult {
-                status: "accepted".to_string(),
-                event_dedupe_key: env.dedupe_key,
-                created_run_count: summary.started_runs as i64,
-                message: "Gmail PubSub event accepted and inbox fetch queued.".to_string(),
-            })
-        }
-        Err(err) => {
-            let msg = sanitize_log_message(&err);
-            let _ = gmail_pubsub::update_event_status(
-                connection,
-                &env.dedupe_key,
-                "fetch_failed",
-                Some(&msg),
-                Some(0),
-            );
-            let _ = gmail_pubsub::record_failure(connection, &msg, now);
-            if require_relay_callback_auth {
-                let _ = update_relay_gmail_pubsub_callback_event_status(
-                    connection,
-                    relay_request_id.as_deref().unwrap_or(""),
-                    "fetch_failed",
-                );
-            }
-            Ok(GmailPubSubIngestResult {
-                status: "fetch_failed".to_string(),
-                event_dedupe_key: env.dedupe_key,
-                created_run_count: 0,
-                message: "Gmail PubSub event received, but inbox fetch failed. Terminus will keep polling fallback available.".to_string(),
-            })
-        }
-    }
+#[tauri::command]
+fn get_app_settings(state: tauri::State<AppState>) -> Result<db::AppSettingsRecord, String> {
+    let connection = open_connection(&state)?;
+    db::get_app_settings(&connection)
 }
 
-fn validate_webhook_signature(
-    secret: &str,
-    body_json: &str,
-    signature: &str,
-    signature_ts_ms: i64,
-) -> Result<(), String> {
-    if signature_ts_ms <= 0 || (now_ms() - signature_ts_ms).abs() > 15 * 60 * 1000 {
-        return Err(
-            "Webhook signature timestamp is expired. Retry from the source system.".to_string(),
-        );
+#[tauri::command]
+fn set_global_pause(
+    state: tauri::State<AppState>,
+    enabled: bool,
+) -> Result<db::AppSettingsRecord, String> {
+    let connection = open_connection(&state)?;
+    db::set_global_pause(&connection, enabled, now_ms())
+}
+
+/// Replaces the primitives custom and webhook plans may not use on this install, e.g. to turn
+/// off sending entirely. Unknown names are rejected rather than ignored.
+#[tauri::command]
+fn set_disallowed_primitives(
+    state: tauri::State<AppState>,
+    primitives: Vec<String>,
+) -> Result<db::AppSettingsRecord, String> {
+    let connection = open_connection(&state)?;
+    set_disallowed_primitives_internal(&connection, &primitives)
+}
+
+fn set_disallowed_primitives_internal(
+    connection: &rusqlite::Connection,
+    primitives: &[String],
+) -> Result<db::AppSettingsRecord, String> {
+    let parsed = primitives
+        .iter()
+        .map(|name| {
+            serde_json::from_value::<PrimitiveId>(Value::String(name.trim().to_string()))
+                .map_err(|_| format!("Unknown primitive: {}.", name.trim()))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    with_config_audit(connection, |connection| {
+        let before = db::get_app_settings(connection)?;
+        let updated = db::set_disallowed_primitives(connection, &parsed, now_ms())?;
+        record_config_change(
+            connection,
+            "set_disallowed_primitives",
+            "app_settings",
+            Some(&before),
+            Some(&updated),
+        )?;
+        Ok(updated)
+    })
+}
+
+#[tauri::command]
+fn get_runner_control(state: tauri::State<AppState>) -> Result<db::RunnerControlRecord, String> {
+    let connection = open_connection(&state)?;
+    db::get_runner_control(&connection)
+}
+
+#[tauri::command]
+fn update_runner_control(
+    state: tauri::State<AppState>,
+    input: RunnerControlInput,
+) -> Result<db::RunnerControlRecord, String> {
+    if !(15..=900).contains(&input.watcher_poll_seconds) {
+        return Err("Watcher poll interval must be between 15 and 900 seconds.".to_string());
     }
-    let provided = signature
-        .trim()
-        .strip_prefix("sha256=")
-        .unwrap_or(signature.trim())
-        .to_ascii_lowercase();
-    if provided.is_empty() || provided.len() > 256 {
-        return Err("Webhook signature is invalid.".to_string());
+    if !(1..=25).contains(&input.watcher_max_items) {
+        return Err("Watcher max emails must be between 1 and 25.".to_string());
     }
-    type HmacSha256 = Hmac<Sha256>;
-    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
-        .map_err(|_| "Webhook signature key is invalid.".to_string())?;
-    let signed = format!("{}.{}", signature_ts_ms, body_json);
-    mac.update(signed.as_bytes());
-    let expected = format!("{:x}", mac.finalize().into_bytes());
-    if !constant_time_eq(&expected, &provided) {
-        return Err("Webhook signature check failed.".to_string());
+    if input.gmail_autopilot_id.trim().is_empty() || input.microsoft_autopilot_id.trim().is_empty()
+    {
+        return Err("Autopilot IDs cannot be empty.".to_string());
     }
-    Ok(())
-}
-
-fn build_webhook_run_plan(
-    route: &webhook_triggers::WebhookTriggerRouteConfig,
-    body_json: &str,
-    payload_hash_hex: &str,
-    received_at_ms: i64,
-) -> Result<AutopilotPlan, String> {
-    let mut plan: AutopilotPlan = serde_json::from_str(&route.plan_json)
-        .map_err(|e| format!("Webhook trigger plan snapshot is invalid: {e}"))?;
-    if plan.recipe == RecipeKind::Custom {
-        let provider_id = parse_provider(&route.provider_kind)?;
-        plan = validate_custom_execution_plan(plan, provider_id)?;
+    if input
+        .max_catch_up_cycles
+        .is_some_and(|cap| !(0..=db::MAX_CATCH_UP_CYCLES_LIMIT).contains(&cap))
+    {
+        return Err(format!(
+            "Catch-up cycles must be between 0 and {}.",
+            db::MAX_CATCH_UP_CYCLES_LIMIT
+        ));
     }
-    let excerpt = payload_excerpt_from_json(body_json);
-    let event_summary = format!(
-        "Webhook event from trigger {} at {} (hash {}). Payload excerpt: {}",
-        route.trigger_id,
-        received_at_ms,
-        &payload_hash_hex[..payload_hash_hex.len().min(12)],
-        excerpt
-    );
-    if plan
-        .inbox_source_text
-        .as_ref()
-        .is_none_or(|v| v.trim().is_empty())
+    if input.relay_sync_batch_size.is_some_and(|size| {
+        !(db::RELAY_SYNC_BATCH_SIZE_MIN..=db::RELAY_SYNC_BATCH_SIZE_MAX).contains(&size)
+    }) {
+        return Err(format!(
+            "Relay sync batch size must be between {} and {}.",
+            db::RELAY_SYNC_BATCH_SIZE_MIN,
+            db::RELAY_SYNC_BATCH_SIZE_MAX
+        ));
+    }
+    if input.relay_connect_timeout_seconds.is_some_and(|secs| {
+        !(db::RELAY_CONNECT_TIMEOUT_SECONDS_MIN..=db::RELAY_CONNECT_TIMEOUT_SECONDS_MAX)
+            .contains(&secs)
+    }) {
+        return Err(format!(
+            "Relay connect timeout must be between {} and {} seconds.",
+            db::RELAY_CONNECT_TIMEOUT_SECONDS_MIN,
+            db::RELAY_CONNECT_TIMEOUT_SECONDS_MAX
+        ));
+    }
+    if input.relay_read_timeout_seconds.is_some_and(|secs| {
+        !(db::RELAY_READ_TIMEOUT_SECONDS_MIN..=db::RELAY_READ_TIMEOUT_SECONDS_MAX).contains(&secs)
+    }) {
+        return Err(format!(
+            "Relay read timeout must be between {} and {} seconds.",
+            db::RELAY_READ_TIMEOUT_SECONDS_MIN,
+            db::RELAY_READ_TIMEOUT_SECONDS_MAX
+        ));
+    }
+    if input
+        .relay_max_retries
+        .is_some_and(|retries| !(0..=db::RELAY_MAX_RETRIES_LIMIT).contains(&retries))
     {
-        plan.inbox_source_text = Some(event_summary.clone());
-    } else if let Some(existing) = plan.inbox_source_text.clone() {
-        let mut merged = existing;
-        merged.push_str("\n\n");
-        merged.push_str(&event_summary);
-        plan.inbox_source_text = Some(merged.chars().take(4_000).collect());
+        return Err(format!(
+            "Relay retries must be between 0 and {}.",
+            db::RELAY_MAX_RETRIES_LIMIT
+        ));
     }
-    plan.intent = format!(
-        "{} [Webhook trigger {}]",
-        plan.intent.trim(),
-        route.trigger_id
+
+    let connection = open_connection(&state)?;
+    with_config_audit(&connection, |connection| {
+        let mut current = db::get_runner_control(connection)?;
+        let before = current.clone();
+        current.gmail_trigger_mode = validate_gmail_trigger_mode(&input.gmail_trigger_mode)?;
+        current.background_enabled = input.background_enabled;
+        current.watcher_enabled = input.watcher_enabled;
+        current.watcher_poll_seconds = input.watcher_poll_seconds;
+        current.watcher_max_items = input.watcher_max_items;
+        current.gmail_autopilot_id = input.gmail_autopilot_id.trim().to_string();
+        current.microsoft_autopilot_id = input.microsoft_autopilot_id.trim().to_string();
+        if let Some(cap) = input.max_catch_up_cycles {
+            current.max_catch_up_cycles = cap;
+        }
+        if let Some(size) = input.relay_sync_batch_size {
+            current.relay_sync_batch_size = size;
+        }
+        if let Some(secs) = input.relay_connect_timeout_seconds {
+            current.relay_connect_timeout_seconds = secs;
+        }
+        if let Some(secs) = input.relay_read_timeout_seconds {
+            current.relay_read_timeout_seconds = secs;
+        }
+        if let Some(retries) = input.relay_max_retries {
+            current.relay_max_retries = retries;
+        }
+        db::upsert_runner_control(connection, &current)?;
+        let updated = db::get_runner_control(connection)?;
+        record_config_change(
+            connection,
+            "update_runner_control",
+            "runner_control",
+            Some(&before),
+            Some(&updated),
+        )?;
+        Ok(updated)
+    })
+}
+
+#[tauri::command]
+fn list_audit_log(
+    state: tauri::State<AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<db::AuditLogRecord>, String> {
+    let connection = open_connection(&state)?;
+    db::list_audit_log(&connection, limit.unwrap_or(50))
+}
+
+/// Records a user-initiated configuration change in the audit log.
+fn record_config_change<T: Serialize>(
+    connection: &rusqlite::Connection,
+    action: &str,
+    target: &str,
+    before: Option<&T>,
+    after: Option<&T>,
+) -> Result<(), String> {
+    let now = now_ms();
+    db::insert_audit_log(
+        connection,
+        &db::AuditLogRecord {
+            id: make_main_id("audit"),
+            actor: "user".to_string(),
+            action: action.to_string(),
+            target: target.to_string(),
+            before_excerpt: before.map(db::audit_excerpt),
+            after_excerpt: after.map(db::audit_excerpt),
+            created_at_ms: now,
+        },
     )
-    .chars()
-    .take(240)
-    .collect();
-    Ok(plan)
 }
 
-fn insert_webhook_run_activity(
+/// Runs a configuration change and its `record_config_change` entry in one transaction, so
+/// neither is saved without the other.
+fn with_config_audit<T>(
     connection: &rusqlite::Connection,
-    run_id: &str,
-    trigger_id: &str,
-    delivery_id: &str,
-    channel: Option<&str>,
-) {
-    let message = format!(
-        "Webhook event queued from {} (delivery {}).",
-        trigger_id,
-        delivery_id.chars().take(48).collect::<String>()
-    );
-    let _ = connection.execute(
-        "INSERT INTO activities (id, run_id, activity_type, from_state, to_state, user_message, created_at)
-         VALUES (?1, ?2, 'webhook_event_queued', NULL, NULL, ?3, ?4)",
-        rusqlite::params![
-            make_main_id("activity"),
-            run_id,
-            truncate_for_activity(&message),
-            now_ms(),
-        ],
-    );
-    let _ = connection.execute(
-        "INSERT INTO activities (id, run_id, activity_type, from_state, to_state, user_message, created_at)
-         VALUES (?1, ?2, 'webhook_origin', NULL, NULL, ?3, ?4)",
-        rusqlite::params![
-            make_main_id("activity"),
-            run_id,
-            truncate_for_activity(&format!(
-                "Origin: webhook via {}",
-                channel.unwrap_or("relay_webhook")
-            )),
-            now_ms(),
-        ],
-    );
+    change: impl FnOnce(&rusqlite::Connection) -> Result<T, String>,
+) -> Result<T, String> {
+    let tx = connection
+        .unchecked_transaction()
+        .map_err(|e| format!("Could not start the configuration change: {e}"))?;
+    let result = change(&tx)?;
+    tx.commit()
+        .map_err(|e| format!("Could not save the configuration change: {e}"))?;
+    Ok(result)
 }
 
-fn ingest_webhook_event_internal(
-    connection: &mut rusqlite::Connection,
-    input: WebhookIngestInput,
-) -> Result<WebhookIngestResult, String> {
-    let trigger_id = input.trigger_id.trim().to_string();
-    if trigger_id.is_empty() {
-        return Err("Trigger ID is required.".to_string());
-    }
-    let delivery_id = input.delivery_id.trim().to_string();
-    if delivery_id.is_empty() || delivery_id.len() > 200 {
-        return Err("Webhook delivery ID is invalid.".to_string());
-    }
-    if input.require_relay_callback_auth {
-        validate_relay_callback_auth_fields(
-            input.relay_request_id.as_deref().unwrap_or(""),
-            input.relay_callback_secret.as_deref().unwrap_or(""),
-            input.relay_issued_at_ms.unwrap_or_default(),
-            "Remote webhook delivery is not ready yet. Generate a callback secret first.",
-        )?;
-        if let Err(err) = reserve_relay_webhook_callback_event(
-            connection,
-            input.relay_request_id.as_deref().unwrap_or(""),
-            &trigger_id,
-            &delivery_id,
-            input.relay_channel.as_deref(),
-        ) {
-            if err.contains("already processed") {
-                return Ok(WebhookIngestResult {
-                    status: "duplicate".to_string(),
-                    trigger_id,
-                    delivery_id,
-                    run_id: None,
-                    message: "Relay webhook callback request was already processed.".to_string(),
-                });
-            }
-            return Err(err);
-        }
+#[tauri::command]
+fn get_onboarding_state(
+    state: tauri::State<AppState>,
+) -> Result<db::OnboardingStateRecord, String> {
+    let connection = open_connection(&state)?;
+    db::get_onboarding_state(&connection)
+}
+
+#[tauri::command]
+fn save_onboarding_state(
+    state: tauri::State<AppState>,
+    input: OnboardingStateInput,
+) -> Result<db::OnboardingStateRecord, String> {
+    let connection = open_connection(&state)?;
+    let current = db::get_onboarding_state(&connection)?;
+    let now = now_ms();
+    let mark_complete = input
+        .onboarding_complete
+        .unwrap_or(current.onboarding_complete);
+    let dismissed = input.dismissed.unwrap_or(current.dismissed);
+    let payload = db::OnboardingStateRecord {
+        onboarding_complete: mark_complete,
+        dismissed,
+        role_text: input.role_text,
+        work_focus_text: input.work_focus_text,
+        biggest_pain_text: input.biggest_pain_text,
+        recommended_intent: input.recommended_intent,
+        started_at_ms: current.started_at_ms,
+        updated_at_ms: current.updated_at_ms,
+        completed_at_ms: if mark_complete {
+            current.completed_at_ms.or(Some(now))
+        } else {
+            None
+        },
+        dismissed_at_ms: if dismissed { Some(now) } else { None },
+        first_successful_run_at_ms: current.first_successful_run_at_ms,
+        first_autopilot_created_at_ms: current.first_autopilot_created_at_ms,
+        first_approval_resolved_at_ms: current.first_approval_resolved_at_ms,
+        first_webhook_trigger_created_at_ms: current.first_webhook_trigger_created_at_ms,
+    };
+    db::upsert_onboarding_state(&connection, &payload)
+}
+
+#[tauri::command]
+fn get_onboarding_recommendation(
+    state: tauri::State<AppState>,
+) -> Result<OnboardingRecommendation, String> {
+    let connection = open_connection(&state)?;
+    refresh_onboarding_recommendation(&connection)
+}
+
+#[tauri::command]
+fn dismiss_onboarding(state: tauri::State<AppState>) -> Result<db::OnboardingStateRecord, String> {
+    let connection = open_connection(&state)?;
+    let current = db::get_onboarding_state(&connection)?;
+    let now = now_ms();
+    let payload = db::OnboardingStateRecord {
+        onboarding_complete: current.onboarding_complete,
+        dismissed: true,
+        role_text: current.role_text,
+        work_focus_text: current.work_focus_text,
+        biggest_pain_text: current.biggest_pain_text,
+        recommended_intent: current.recommended_intent,
+        started_at_ms: current.started_at_ms,
+        updated_at_ms: current.updated_at_ms,
+        completed_at_ms: current.completed_at_ms,
+        dismissed_at_ms: Some(now),
+        first_successful_run_at_ms: current.first_successful_run_at_ms,
+        first_autopilot_created_at_ms: current.first_autopilot_created_at_ms,
+        first_approval_resolved_at_ms: current.first_approval_resolved_at_ms,
+        first_webhook_trigger_created_at_ms: current.first_webhook_trigger_created_at_ms,
+    };
+    db::upsert_onboarding_state(&connection, &payload)
+}
+
+/// Schema version of this build versus the vault, for support diagnosing version mismatches.
+#[tauri::command]
+fn get_schema_status(state: tauri::State<AppState>) -> Result<db::SchemaStatus, String> {
+    let connection = open_connection(&state)?;
+    db::get_schema_status(&connection)
+}
+
+#[tauri::command]
+fn get_inbox_watcher_stats(
+    state: tauri::State<AppState>,
+) -> Result<Vec<inbox_watcher::InboxWatcherStats>, String> {
+    let connection = open_connection(&state)?;
+    inbox_watcher::list_watcher_stats(&connection)
+}
+
+/// Global "what happened lately" view across every Autopilot and run. Page with `before_ms`.
+#[tauri::command]
+fn get_activity_feed(
+    state: tauri::State<AppState>,
+    limit: Option<usize>,
+    before_ms: Option<i64>,
+) -> Result<Vec<db::ActivityFeedItem>, String> {
+    let connection = open_connection(&state)?;
+    db::get_activity_feed(&connection, limit.unwrap_or(50), before_ms)
+}
+
+#[tauri::command]
+fn reset_onboarding(state: tauri::State<AppState>) -> Result<db::OnboardingStateRecord, String> {
+    let connection = open_connection(&state)?;
+    db::reset_onboarding_state(&connection, now_ms())
+}
+
+#[tauri::command]
+fn get_global_voice_config(state: tauri::State<AppState>) -> Result<db::VoiceConfigRecord, String> {
+    let connection = open_connection(&state)?;
+    db::get_global_voice_config(&connection)
+}
+
+#[tauri::command]
+fn update_global_voice_config(
+    state: tauri::State<AppState>,
+    input: VoiceConfigInput,
+) -> Result<db::VoiceConfigRecord, String> {
+    let connection = open_connection(&state)?;
+    let payload = db::VoiceConfigRecord {
+        tone: validate_voice_tone(&input.tone)?,
+        length: validate_voice_length(&input.length)?,
+        humor: validate_voice_humor(&input.humor)?,
+        notes: sanitize_voice_notes(&input.notes),
+        updated_at_ms: now_ms(),
+    };
+    db::upsert_global_voice_config(&connection, &payload)
+}
+
+#[tauri::command]
+fn preview_voice(
+    config: VoiceConfigInput,
+    sample_context: Option<String>,
+) -> Result<VoicePreview, String> {
+    let voice = db::VoiceConfigRecord {
+        tone: validate_voice_tone(&config.tone)?,
+        length: validate_voice_length(&config.length)?,
+        humor: validate_voice_humor(&config.humor)?,
+        notes: sanitize_voice_notes(&config.notes),
+        updated_at_ms: now_ms(),
+    };
+    let sample_text = render_voice_preview(&voice, sample_context.as_deref().unwrap_or(""));
+    Ok(VoicePreview {
+        tone: voice.tone,
+        length: voice.length,
+        humor: voice.humor,
+        sample_text,
+    })
+}
+
+#[tauri::command]
+fn get_autopilot_voice_config(
+    state: tauri::State<AppState>,
+    autopilot_id: String,
+) -> Result<db::AutopilotVoiceConfigRecord, String> {
+    let connection = open_connection(&state)?;
+    if autopilot_id.trim().is_empty() {
+        return Err("Autopilot ID is required.".to_string());
     }
+    db::get_autopilot_voice_config(&connection, autopilot_id.trim())
+}
 
-    let route = webhook_triggers::get_webhook_trigger_route_config(connection, &trigger_id)?
-        .ok_or_else(|| "Webhook trigger not found.".to_string())?;
+#[tauri::command]
+fn update_autopilot_voice_config(
+    state: tauri::State<AppState>,
+    input: AutopilotVoiceConfigInput,
+) -> Result<db::AutopilotVoiceConfigRecord, String> {
+    let connection = open_connection(&state)?;
+    let autopilot_id = input.autopilot_id.trim();
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
+    }
+    let payload = db::AutopilotVoiceConfigRecord {
+        autopilot_id: autopilot_id.to_string(),
+        enabled: input.enabled,
+        tone: validate_voice_tone(&input.tone)?,
+        length: validate_voice_length(&input.length)?,
+        humor: validate_voice_humor(&input.humor)?,
+        notes: sanitize_voice_notes(&input.notes),
+        updated_at_ms: now_ms(),
+    };
+    db::upsert_autopilot_voice_config(&connection, &payload)
+}
+
+#[tauri::command]
+fn clear_autopilot_voice_config(
+    state: tauri::State<AppState>,
+    autopilot_id: String,
+) -> Result<db::AutopilotVoiceConfigRecord, String> {
+    let connection = open_connection(&state)?;
+    let trimmed = autopilot_id.trim();
+    if trimmed.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
+    }
+    db::clear_autopilot_voice_config(&connection, trimmed)?;
+    db::get_autopilot_voice_config(&connection, trimmed)
+}
+
+#[tauri::command]
+fn tick_runner_cycle(
+    app: tauri::AppHandle,
+    state: tauri::State<AppState>,
+) -> Result<RunnerCycleSummary, String> {
+    let mut connection = open_connection(&state)?;
+    tick_runner_cycle_internal(&mut connection, false, &TauriProgressSink { app })
+}
+
+fn tick_runner_cycle_internal(
+    connection: &mut rusqlite::Connection,
+    require_background_enabled: bool,
+    progress: &dyn RunProgressSink,
+) -> Result<RunnerCycleSummary, String> {
+    let mut control = db::get_runner_control(&connection)?;
+    if require_background_enabled && db::get_app_settings(connection)?.global_pause {
+        return Ok(RunnerCycleSummary::skipped("globally_paused"));
+    }
+    if require_background_enabled && !control.background_enabled {
+        return Ok(RunnerCycleSummary::skipped("background_off"));
+    }
     let now = now_ms();
-    let content_type = normalize_content_type(&input.content_type);
-    let body_json = input.body_json.trim().to_string();
-    let body_len = body_json.as_bytes().len() as i64;
-    let hash = payload_hash(&body_json);
-    let event_key = format!("{}:{}", delivery_id, &hash[..hash.len().min(16)]);
-    let headers_redacted_json = redact_webhook_headers_json(input.headers_redacted_json.as_deref());
-    let payload_excerpt = payload_excerpt_from_json(&body_json);
+    let poll_ms = control.watcher_poll_seconds.saturating_mul(1000);
 
-    let base_event = webhook_triggers::WebhookTriggerEventInsert {
-        id: make_main_id("wh_event"),
-        trigger_id: trigger_id.clone(),
-        delivery_id: delivery_id.clone(),
-        event_idempotency_key: event_key.clone(),
-        received_at_ms: now,
-        status: "accepted".to_string(),
-        http_status: Some(202),
-        headers_redacted_json,
-        payload_excerpt,
-        payload_hash: hash.clone(),
-        failure_reason: None,
-        run_id: None,
+    let mut summary = RunnerCycleSummary {
+        watcher_status: "idle".to_string(),
+        relay_sync_status: "idle".to_string(),
+        providers_polled: 0,
+        fetched: 0,
+        deduped: 0,
+        started_runs: 0,
+        failed: 0,
+        resumed_due_runs: 0,
+        relay_decisions_applied: 0,
+        missed_runs_detected: 0,
+        catch_up_cycles_run: 0,
     };
-    let inserted = webhook_triggers::insert_webhook_trigger_event(connection, &base_event)?;
-    if !inserted {
-        if input.require_relay_callback_auth {
-            let _ = update_relay_webhook_callback_event_status(
-                connection,
-                input.relay_request_id.as_deref().unwrap_or(""),
-                "duplicate",
+
+    let missed_cycles = compute_missed_cycles(control.watcher_last_tick_ms, now, poll_ms);
+    if missed_cycles > 0 {
+        summary.missed_runs_detected = missed_cycles;
+        control.missed_runs_count = missed_cycles;
+    }
+
+    if !control.watcher_enabled {
+        summary.watcher_status = "paused".to_string();
+    } else if let Some(last_tick) = control.watcher_last_tick_ms {
+        if now - last_tick < poll_ms {
+            summary.watcher_status = "throttled".to_string();
+        } else {
+            let catch_up_cycles = missed_cycles.min(
+                control
+                    .max_catch_up_cycles
+                    .clamp(0, db::MAX_CATCH_UP_CYCLES_LIMIT),
             );
+            for _ in 0..catch_up_cycles {
+                run_watchers(connection, &control, &mut summary, false)?;
+                summary.catch_up_cycles_run += 1;
+            }
+            // Catch-up already polled every provider this cycle; only gate a normal cycle.
+            run_watchers(connection, &control, &mut summary, catch_up_cycles == 0)?;
+            control.watcher_last_tick_ms = Some(now);
+            control.missed_runs_count = 0;
+            db::upsert_runner_control(&connection, &control)?;
+            summary.watcher_status = "ran".to_string();
         }
-        return Ok(WebhookIngestResult {
-            status: "duplicate".to_string(),
-            trigger_id,
-            delivery_id,
-            run_id: None,
-            message: "Duplicate webhook delivery ignored.".to_string(),
-        });
+    } else {
+        run_watchers(connection, &control, &mut summary, true)?;
+        control.watcher_last_tick_ms = Some(now);
+        control.missed_runs_count = 0;
+        db::upsert_runner_control(&connection, &control)?;
+        summary.watcher_status = "ran".to_string();
     }
 
-    let fail = |status: &str,
-                reason: &str,
-                http_status: Option<i64>|
-     -> Result<WebhookIngestResult, String> {
-        let _ = webhook_triggers::update_webhook_trigger_event_status(
-            connection,
-            &trigger_id,
-            &event_key,
-            status,
-            Some(reason),
-            None,
-        );
-        let _ = webhook_triggers::touch_webhook_trigger_delivery(
-            connection,
-            &trigger_id,
-            now,
-            Some(reason),
+    if let Err(err) = maybe_auto_renew_gmail_watch(connection, now, gmail_watch_register) {
+        app_log::error(
+            "gmail_pubsub",
+            &format!("gmail watch renewal failed: {err}"),
+        );
+    }
+
+    let expired = RunnerEngine::expire_due_approvals(connection, now).map_err(|e| e.to_string())?;
+    if !expired.is_empty() {
+        app_log::warn(
+            "runner",
+            &format!("{} approval(s) expired without a decision", expired.len()),
+        );
+    }
+
+    let resumed = RunnerEngine::resume_due_runs_with_progress(connection, 20, progress)
+        .map_err(|e| e.to_string())?;
+    summary.resumed_due_runs = resumed.len();
+    match tick_relay_approval_sync_internal(
+        connection,
+        false,
+        RelayDecisionSyncChannel::Poll,
+        progress,
+    ) {
+        Ok(sync) => {
+            summary.relay_sync_status = sync.status.status;
+            summary.relay_decisions_applied = sync.applied_count;
+        }
+        Err(err) => {
+            summary.relay_sync_status = "error".to_string();
+            app_log::error("relay", &format!("relay approval sync failed: {err}"));
+        }
+    }
+    if summary.watcher_status == "throttled" && control.missed_runs_count > 0 {
+        db::upsert_runner_control(&connection, &control)?;
+    }
+    record_runner_cycle(connection, require_background_enabled, &summary, now);
+    Ok(summary)
+}
+
+/// Persists a cycle summary for `list_runner_cycles`. Background cycles that were throttled
+/// and touched nothing are skipped so the bounded log keeps the cycles that did work.
+fn record_runner_cycle(
+    connection: &rusqlite::Connection,
+    background: bool,
+    summary: &RunnerCycleSummary,
+    at_ms: i64,
+) {
+    let did_work = summary.watcher_status != "throttled"
+        || summary.started_runs > 0
+        || summary.failed > 0
+        || summary.resumed_due_runs > 0
+        || summary.relay_decisions_applied > 0;
+    if background && !did_work {
+        return;
+    }
+    let record = db::RunnerCycleLogRecord {
+        id: make_main_id("cycle"),
+        trigger: if background { "background" } else { "manual" }.to_string(),
+        watcher_status: summary.watcher_status.clone(),
+        relay_sync_status: summary.relay_sync_status.clone(),
+        providers_polled: summary.providers_polled as i64,
+        fetched: summary.fetched as i64,
+        deduped: summary.deduped as i64,
+        started_runs: summary.started_runs as i64,
+        failed: summary.failed as i64,
+        resumed_due_runs: summary.resumed_due_runs as i64,
+        relay_decisions_applied: summary.relay_decisions_applied as i64,
+        missed_runs_detected: summary.missed_runs_detected,
+        catch_up_cycles_run: summary.catch_up_cycles_run,
+        created_at_ms: at_ms,
+    };
+    if let Err(err) = db::insert_runner_cycle_log(connection, &record) {
+        app_log::error("runner", &format!("runner cycle log failed: {err}"));
+    }
+}
+
+#[tauri::command]
+fn list_runner_cycles(
+    state: tauri::State<AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<db::RunnerCycleLogRecord>, String> {
+    let connection = open_connection(&state)?;
+    db::list_runner_cycle_log(&connection, limit.unwrap_or(50))
+}
+
+#[tauri::command]
+fn list_relay_callback_events(
+    state: tauri::State<AppState>,
+    status: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<db::RelayCallbackEventRecord>, String> {
+    let connection = open_connection(&state)?;
+    db::list_relay_callback_events(&connection, status.as_deref(), limit.unwrap_or(50))
+}
+
+/// Recent redacted provider round-trips; empty unless trace mode is on.
+#[tauri::command]
+fn list_provider_traces(
+    state: tauri::State<AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<db::ProviderTraceRecord>, String> {
+    let connection = open_connection(&state)?;
+    db::list_provider_traces(&connection, limit.unwrap_or(50))
+}
+
+fn spawn_background_cycle_thread(app: &tauri::AppHandle, db_path: PathBuf) {
+    let app_handle = app.clone();
+    thread::spawn(move || loop {
+        thread::sleep(Duration::from_secs(10));
+        let app_state = app_handle.state::<AppState>();
+        if app_state
+            .db_path
+            .lock()
+            .ok()
+            .and_then(|g| g.clone())
+            .is_none()
+        {
+            continue;
+        }
+        let mut connection = match open_connection_from_path(&db_path) {
+            Ok(conn) => conn,
+            Err(_) => continue,
+        };
+        if db::get_app_settings(&connection).map_or(true, |settings| settings.global_pause) {
+            continue;
+        }
+        let progress = TauriProgressSink {
+            app: app_handle.clone(),
+        };
+        if let Err(err) = tick_runner_cycle_internal(&mut connection, true, &progress) {
+            app_log::error("runner", &format!("background runner cycle failed: {err}"));
+        }
+    });
+}
+
+fn spawn_background_relay_push_thread(app: &tauri::AppHandle, db_path: PathBuf) {
+    let app_handle = app.clone();
+    thread::spawn(move || loop {
+        thread::sleep(Duration::from_secs(5));
+        let app_state = app_handle.state::<AppState>();
+        if app_state
+            .db_path
+            .lock()
+            .ok()
+            .and_then(|g| g.clone())
+            .is_none()
+        {
+            continue;
+        }
+        let mut connection = match open_connection_from_path(&db_path) {
+            Ok(conn) => conn,
+            Err(_) => continue,
+        };
+        let control = match db::get_runner_control(&connection) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        if !control.background_enabled
+            || db::get_app_settings(&connection).map_or(true, |settings| settings.global_pause)
+        {
+            continue;
+        }
+        let progress = TauriProgressSink {
+            app: app_handle.clone(),
+        };
+        if let Err(err) = tick_relay_approval_sync_internal(
+            &mut connection,
+            false,
+            RelayDecisionSyncChannel::Push,
+            &progress,
+        ) {
+            app_log::error("relay", &format!("relay push sync failed: {err}"));
+        }
+    });
+}
+
+fn install_tray(app: &tauri::AppHandle) -> Result<(), String> {
+    let open_item = MenuItemBuilder::with_id("tray_open", "Open Terminus")
+        .build(app)
+        .map_err(|e| e.to_string())?;
+    let run_item = MenuItemBuilder::with_id("tray_run_now", "Run Cycle Now")
+        .build(app)
+        .map_err(|e| e.to_string())?;
+    let quit_item = MenuItemBuilder::with_id("tray_quit", "Quit")
+        .build(app)
+        .map_err(|e| e.to_string())?;
+    let menu = MenuBuilder::new(app)
+        .items(&[&open_item, &run_item, &quit_item])
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let app_handle = app.clone();
+    TrayIconBuilder::new()
+        .menu(&menu)
+        .on_menu_event(move |_, event: MenuEvent| match event.id().as_ref() {
+            "tray_open" => {
+                if let Some(window) = app_handle.get_webview_window("main") {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+            }
+            "tray_run_now" => {
+                let app_state = app_handle.state::<AppState>();
+                let db_path = app_state.db_path.lock().ok().and_then(|g| g.clone());
+                if let Some(path) = db_path {
+                    if let Ok(mut connection) = open_connection_from_path(&path) {
+                        let progress = TauriProgressSink {
+                            app: app_handle.clone(),
+                        };
+                        if let Err(err) =
+                            tick_runner_cycle_internal(&mut connection, false, &progress)
+                        {
+                            app_log::error("runner", &format!("tray run cycle failed: {err}"));
+                        }
+                    }
+                }
+            }
+            "tray_quit" => {
+                app_handle.exit(0);
+            }
+            _ => {}
+        })
+        .on_tray_icon_event(move |tray: &TrayIcon, event: TrayIconEvent| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                if let Some(window) = tray.app_handle().get_webview_window("main") {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+            }
+        })
+        .build(app)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+fn get_autopilot_send_policy(
+    state: tauri::State<AppState>,
+    autopilot_id: String,
+) -> Result<db::AutopilotSendPolicyRecord, String> {
+    let connection = open_connection(&state)?;
+    db::get_autopilot_send_policy(&connection, autopilot_id.trim())
+}
+
+#[tauri::command]
+fn list_recipient_cooldowns(
+    state: tauri::State<AppState>,
+    autopilot_id: String,
+) -> Result<Vec<db::RecipientCooldownRecord>, String> {
+    let connection = open_connection(&state)?;
+    db::list_recipient_cooldowns(&connection, autopilot_id.trim(), now_ms())
+}
+
+/// Clears the cooldown for `recipient`, or for every recipient of the Autopilot when omitted.
+#[tauri::command]
+fn clear_recipient_cooldowns(
+    state: tauri::State<AppState>,
+    autopilot_id: String,
+    recipient: Option<String>,
+) -> Result<Vec<db::RecipientCooldownRecord>, String> {
+    let connection = open_connection(&state)?;
+    clear_recipient_cooldowns_internal(&connection, autopilot_id.trim(), recipient.as_deref())
+}
+
+fn clear_recipient_cooldowns_internal(
+    connection: &rusqlite::Connection,
+    autopilot_id: &str,
+    recipient: Option<&str>,
+) -> Result<Vec<db::RecipientCooldownRecord>, String> {
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
+    }
+    let recipient = recipient.map(str::trim).filter(|r| !r.is_empty());
+    let now = now_ms();
+    with_config_audit(connection, |connection| {
+        let before = db::list_recipient_cooldowns(connection, autopilot_id, now)?;
+        db::clear_recipient_cooldowns(connection, autopilot_id, recipient)?;
+        let after = db::list_recipient_cooldowns(connection, autopilot_id, now)?;
+        record_config_change(
+            connection,
+            "clear_recipient_cooldowns",
+            &format!("autopilot:{autopilot_id}"),
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
+}
+
+const MAX_INTENT_OVERRIDE_PHRASE_CHARS: usize = 120;
+
+#[tauri::command]
+fn list_intent_kind_overrides(
+    state: tauri::State<AppState>,
+) -> Result<Vec<db::IntentKindOverrideRecord>, String> {
+    let connection = open_connection(&state)?;
+    db::list_intent_kind_overrides(&connection)
+}
+
+/// Saves a phrase that forces `draft_intent` to classify matching intents as `kind`
+/// (`one_off_run` or `draft_autopilot`).
+#[tauri::command]
+fn upsert_intent_kind_override(
+    state: tauri::State<AppState>,
+    phrase: String,
+    kind: String,
+) -> Result<Vec<db::IntentKindOverrideRecord>, String> {
+    let connection = open_connection(&state)?;
+    upsert_intent_kind_override_internal(&connection, &phrase, &kind)
+}
+
+fn upsert_intent_kind_override_internal(
+    connection: &rusqlite::Connection,
+    phrase: &str,
+    kind: &str,
+) -> Result<Vec<db::IntentKindOverrideRecord>, String> {
+    let phrase = phrase.trim().to_ascii_lowercase();
+    if phrase.is_empty() {
+        return Err("Add a phrase to match.".to_string());
+    }
+    if phrase.chars().count() > MAX_INTENT_OVERRIDE_PHRASE_CHARS {
+        return Err(format!(
+            "Keep the phrase under {MAX_INTENT_OVERRIDE_PHRASE_CHARS} characters."
+        ));
+    }
+    let kind = kind.trim();
+    parse_intent_kind(kind)?;
+    with_config_audit(connection, |connection| {
+        let before = db::list_intent_kind_overrides(connection)?;
+        db::upsert_intent_kind_override(connection, &phrase, kind, now_ms())?;
+        let after = db::list_intent_kind_overrides(connection)?;
+        record_config_change(
+            connection,
+            "upsert_intent_kind_override",
+            &format!("intent_phrase:{phrase}"),
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
+}
+
+#[tauri::command]
+fn delete_intent_kind_override(
+    state: tauri::State<AppState>,
+    phrase: String,
+) -> Result<Vec<db::IntentKindOverrideRecord>, String> {
+    let connection = open_connection(&state)?;
+    let phrase = phrase.trim().to_ascii_lowercase();
+    with_config_audit(&connection, |connection| {
+        let before = db::list_intent_kind_overrides(connection)?;
+        if !db::delete_intent_kind_override(connection, &phrase)? {
+            return Err("No saved override for that phrase.".to_string());
+        }
+        let after = db::list_intent_kind_overrides(connection)?;
+        record_config_change(
+            connection,
+            "delete_intent_kind_override",
+            &format!("intent_phrase:{phrase}"),
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
+}
+
+#[tauri::command]
+fn get_autopilot_send_stats(
+    state: tauri::State<AppState>,
+    autopilot_id: String,
+) -> Result<db::AutopilotSendStatsRecord, String> {
+    let autopilot_id = autopilot_id.trim();
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
+    }
+    let connection = open_connection(&state)?;
+    db::get_autopilot_send_stats(&connection, autopilot_id, now_ms())
+}
+
+#[tauri::command]
+fn update_autopilot_send_policy(
+    state: tauri::State<AppState>,
+    input: AutopilotSendPolicyInput,
+) -> Result<db::AutopilotSendPolicyRecord, String> {
+    let connection = open_connection(&state)?;
+    update_autopilot_send_policy_internal(&connection, input)
+}
+
+fn update_autopilot_send_policy_internal(
+    connection: &rusqlite::Connection,
+    input: AutopilotSendPolicyInput,
+) -> Result<db::AutopilotSendPolicyRecord, String> {
+    let autopilot_id = input.autopilot_id.trim().to_string();
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
+    }
+    if !(1..=200).contains(&input.max_sends_per_day) {
+        return Err("Max sends per day must be between 1 and 200.".to_string());
+    }
+    if !(0..=23).contains(&input.quiet_hours_start_local)
+        || !(0..=23).contains(&input.quiet_hours_end_local)
+    {
+        return Err("Quiet hours must use 0-23 clock values.".to_string());
+    }
+    if input.allow_sending && input.recipient_allowlist.is_empty() {
+        return Err("Add at least one allowed recipient before enabling sending.".to_string());
+    }
+    if input
+        .recipient_cooldown_minutes
+        .is_some_and(|minutes| !(0..=db::MAX_RECIPIENT_COOLDOWN_MINUTES).contains(&minutes))
+    {
+        return Err(format!(
+            "Recipient cooldown must be between 0 and {} minutes.",
+            db::MAX_RECIPIENT_COOLDOWN_MINUTES
+        ));
+    }
+
+    with_config_audit(connection, |connection| {
+        let before = db::get_autopilot_send_policy(connection, &autopilot_id)?;
+        let cleaned_allowlist = input
+            .recipient_allowlist
+            .into_iter()
+            .map(|r| r.trim().to_ascii_lowercase())
+            .filter(|r| !r.is_empty())
+            .collect::<Vec<String>>();
+        let updated = db::AutopilotSendPolicyRecord {
+            autopilot_id: autopilot_id.clone(),
+            allow_sending: input.allow_sending,
+            recipient_allowlist: cleaned_allowlist,
+            max_sends_per_day: input.max_sends_per_day,
+            quiet_hours_start_local: input.quiet_hours_start_local,
+            quiet_hours_end_local: input.quiet_hours_end_local,
+            allow_outside_quiet_hours: input.allow_outside_quiet_hours,
+            recipient_cooldown_minutes: input
+                .recipient_cooldown_minutes
+                .unwrap_or(before.recipient_cooldown_minutes),
+            self_send_outcomes: input
+                .self_send_outcomes
+                .unwrap_or(before.self_send_outcomes),
+            updated_at_ms: now_ms(),
+        };
+        db::upsert_autopilot_send_policy(connection, &updated)?;
+        let updated = db::get_autopilot_send_policy(connection, &autopilot_id)?;
+        record_config_change(
+            connection,
+            "update_autopilot_send_policy",
+            &format!("autopilot:{autopilot_id}"),
+            Some(&before),
+            Some(&updated),
+        )?;
+        Ok(updated)
+    })
+}
+
+/// Normalizes and classifies `instruction` exactly as `submit_guidance` would, without storing it.
+#[tauri::command]
+fn preview_guidance(instruction: String) -> Result<GuidancePreviewResponse, String> {
+    preview_guidance_internal(&instruction)
+}
+
+fn preview_guidance_internal(instruction: &str) -> Result<GuidancePreviewResponse, String> {
+    let normalized_instruction = normalize_guidance_instruction(instruction)?;
+    let (mode, message, proposed_rule) = classify_guidance(&normalized_instruction);
+    Ok(GuidancePreviewResponse {
+        normalized_instruction,
+        mode,
+        message,
+        proposed_rule,
+    })
+}
+
+#[tauri::command]
+fn submit_guidance(
+    state: tauri::State<AppState>,
+    input: GuidanceInput,
+) -> Result<GuidanceResponse, String> {
+    let connection = open_connection(&state)?;
+    submit_guidance_internal(&connection, input)
+}
+
+fn submit_guidance_internal(
+    connection: &rusqlite::Connection,
+    input: GuidanceInput,
+) -> Result<GuidanceResponse, String> {
+    let scope_type = input.scope_type.trim().to_ascii_lowercase();
+    if !matches!(
+        scope_type.as_str(),
+        "autopilot" | "run" | "approval" | "outcome"
+    ) {
+        return Err("Choose a valid guidance scope.".to_string());
+    }
+    let scope_id = input.scope_id.trim();
+    if scope_id.is_empty() {
+        return Err("Scope ID is required.".to_string());
+    }
+    let GuidancePreviewResponse {
+        normalized_instruction: cleaned_instruction,
+        mode,
+        message,
+        proposed_rule,
+    } = preview_guidance_internal(&input.instruction)?;
+
+    let (autopilot_id, run_id, approval_id, outcome_id) = match scope_type.as_str() {
+        "autopilot" => (Some(scope_id.to_string()), None, None, None),
+        "run" => {
+            let autopilot: Option<String> = connection
+                .query_row(
+                    "SELECT autopilot_id FROM runs WHERE id = ?1 LIMIT 1",
+                    rusqlite::params![scope_id],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| format!("Failed to resolve run scope: {e}"))?;
+            (autopilot, Some(scope_id.to_string()), None, None)
+        }
+        "approval" => {
+            let run_ref: Option<(String, String)> = connection
+                .query_row(
+                    "SELECT a.run_id, r.autopilot_id
+                     FROM approvals a
+                     JOIN runs r ON r.id = a.run_id
+                     WHERE a.id = ?1
+                     LIMIT 1",
+                    rusqlite::params![scope_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()
+                .map_err(|e| format!("Failed to resolve approval scope: {e}"))?;
+            match run_ref {
+                Some((run, auto)) => (Some(auto), Some(run), Some(scope_id.to_string()), None),
+                None => (None, None, Some(scope_id.to_string()), None),
+            }
+        }
+        _ => (None, None, None, Some(scope_id.to_string())),
+    };
+
+    let response = GuidanceResponse {
+        mode,
+        message,
+        proposed_rule: proposed_rule.clone(),
+    };
+    let result_json =
+        serde_json::to_string(&response).map_err(|e| format!("Failed to store guidance: {e}"))?;
+
+    db::insert_guidance_event(
+        connection,
+        &db::GuidanceEventInsert {
+            id: make_main_id("guide"),
+            scope_type: scope_type.clone(),
+            scope_id: scope_id.to_string(),
+            autopilot_id,
+            run_id: run_id.clone(),
+            approval_id,
+            outcome_id,
+            mode: match mode {
+                GuidanceMode::Applied => "applied".to_string(),
+                GuidanceMode::ProposedRule => "proposed_rule".to_string(),
+                GuidanceMode::NeedsApproval => "needs_approval".to_string(),
+            },
+            instruction: cleaned_instruction.clone(),
+            result_json,
+            created_at_ms: now_ms(),
+        },
+    )?;
+
+    if let Some(run_id) = run_id {
+        let _ = connection.execute(
+            "
+            INSERT INTO activities (id, run_id, activity_type, from_state, to_state, user_message, created_at)
+            VALUES (?1, ?2, 'guidance_received', NULL, NULL, ?3, ?4)
+            ",
+            rusqlite::params![
+                make_main_id("activity"),
+                run_id,
+                truncate_for_activity(&cleaned_instruction),
+                now_ms()
+            ],
+        );
+    }
+
+    Ok(response)
+}
+
+/// Polls each connected inbox. With `enforce_interval`, a provider polled less than
+/// `watcher_poll_seconds` ago is skipped, whatever the cycle cadence. Every provider's poll is
+/// recorded at the cycle's start so slow fetches do not push the next cycle's polls back.
+fn run_watchers(
+    connection: &mut rusqlite::Connection,
+    control: &db::RunnerControlRecord,
+    summary: &mut RunnerCycleSummary,
+    enforce_interval: bool,
+) -> Result<(), String> {
+    let now = now_ms();
+    let min_interval_ms = control.watcher_poll_seconds.saturating_mul(1000);
+    let connections = email_connections::list_connections(connection)?;
+    for provider in connections
+        .into_iter()
+        .filter(|record| record.status == "connected")
+    {
+        if provider.provider == "gmail" {
+            let mut pubsub = gmail_pubsub::maybe_mark_expired(connection, now)?;
+            pubsub.trigger_mode = control.gmail_trigger_mode.clone();
+            if !gmail_pubsub::should_poll_gmail(&pubsub, now) {
+                summary.providers_polled += 1;
+                continue;
+            }
+        }
+        if let Some(kind) = email_connections::EmailProvider::parse(&provider.provider) {
+            if enforce_interval
+                && !inbox_watcher::provider_poll_due(connection, kind, now, min_interval_ms)?
+            {
+                summary.providers_polled += 1;
+                continue;
+            }
+            inbox_watcher::record_provider_poll(connection, kind, now)?;
+        }
+        let autopilot_id = if provider.provider == "gmail" {
+            control.gmail_autopilot_id.as_str()
+        } else {
+            control.microsoft_autopilot_id.as_str()
+        };
+        match inbox_watcher::run_watcher_tick(
+            connection,
+            &provider.provider,
+            autopilot_id,
+            control.watcher_max_items as usize,
+            &resolve_email_reply_approval,
+        ) {
+            Ok(result) => {
+                summary.providers_polled += 1;
+                summary.fetched += result.fetched;
+                summary.deduped += result.deduped;
+                summary.started_runs += result.started_runs;
+                summary.failed += result.failed;
+            }
+            Err(err) => {
+                summary.providers_polled += 1;
+                summary.failed += 1;
+                app_log::error(
+                    "inbox_watcher",
+                    &format!("inbox watcher tick failed for {}: {err}", provider.provider),
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn truncate_for_activity(input: &str) -> String {
+    let max = 180;
+    if input.chars().count() <= max {
+        return input.to_string();
+    }
+    input.chars().take(max).collect::<String>()
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn make_main_id(prefix: &str) -> String {
+    let seq = MAIN_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("{}_{}_{}", prefix, now_ms(), seq)
+}
+
+#[tauri::command]
+fn record_decision_event(
+    state: tauri::State<AppState>,
+    autopilot_id: String,
+    run_id: String,
+    event_type: String,
+    step_id: Option<String>,
+    metadata_json: Option<String>,
+    client_event_id: Option<String>,
+) -> Result<(), String> {
+    let connection = open_connection(&state)?;
+    learning::record_decision_event_from_json(
+        &connection,
+        &autopilot_id,
+        &run_id,
+        step_id.as_deref(),
+        &event_type,
+        metadata_json.as_deref(),
+        client_event_id.as_deref(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn mark_outcome_reviewed(
+    state: tauri::State<AppState>,
+    outcome_id: String,
+    action: String,
+) -> Result<(), String> {
+    let connection = open_connection(&state)?;
+    learning::mark_outcome_reviewed(&connection, &outcome_id, &action).map_err(|e| e.to_string())
+}
+
+/// Result of one "tidy up" pass across every prune path.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MaintenanceSummary {
+    dry_run: bool,
+    learning: learning::LearningCompactionSummary,
+    webhook_dead_letters_deleted: i64,
+    audit_log_deleted: i64,
+    relay_ping_log_deleted: i64,
+    provider_traces_deleted: i64,
+    runner_cycle_log_deleted: i64,
+    total_deleted: i64,
+}
+
+fn run_maintenance_internal(
+    connection: &rusqlite::Connection,
+    dry_run: bool,
+    now: i64,
+) -> Result<MaintenanceSummary, String> {
+    let learning =
+        learning::compact_learning_data(connection, None, dry_run).map_err(|e| e.to_string())?;
+    let webhook_dead_letters_deleted =
+        webhook_triggers::prune_webhook_dead_letters(connection, now, dry_run)?;
+    let audit_log_deleted =
+        db::prune_capped_log(connection, "audit_log", db::AUDIT_LOG_MAX_ROWS, dry_run)?;
+    let relay_ping_log_deleted = db::prune_capped_log(
+        connection,
+        "relay_ping_log",
+        db::RELAY_PING_LOG_MAX_ROWS,
+        dry_run,
+    )?;
+    let provider_traces_deleted = db::prune_capped_log(
+        connection,
+        "provider_traces",
+        db::PROVIDER_TRACE_MAX_ROWS,
+        dry_run,
+    )?;
+    let runner_cycle_log_deleted = db::prune_capped_log(
+        connection,
+        "runner_cycle_log",
+        db::RUNNER_CYCLE_LOG_MAX_ROWS,
+        dry_run,
+    )?;
+    let total_deleted = learning.decision_events_deleted
+        + learning.adaptation_log_deleted
+        + learning.run_evaluations_deleted
+        + learning.primitive_guard_events_deleted
+        + learning.memory_cards_deleted
+        + webhook_dead_letters_deleted
+        + audit_log_deleted
+        + relay_ping_log_deleted
+        + provider_traces_deleted
+        + runner_cycle_log_deleted;
+    Ok(MaintenanceSummary {
+        dry_run,
+        learning,
+        webhook_dead_letters_deleted,
+        audit_log_deleted,
+        relay_ping_log_deleted,
+        provider_traces_deleted,
+        runner_cycle_log_deleted,
+        total_deleted,
+    })
+}
+
+#[tauri::command]
+fn run_maintenance(
+    state: tauri::State<AppState>,
+    dry_run: Option<bool>,
+) -> Result<MaintenanceSummary, String> {
+    let connection = open_connection(&state)?;
+    run_maintenance_internal(&connection, dry_run.unwrap_or(false), now_ms())
+}
+
+#[tauri::command]
+fn compact_learning_data(
+    state: tauri::State<AppState>,
+    autopilot_id: Option<String>,
+    dry_run: Option<bool>,
+) -> Result<learning::LearningCompactionSummary, String> {
+    let connection = open_connection(&state)?;
+    learning::compact_learning_data(
+        &connection,
+        autopilot_id.as_deref(),
+        dry_run.unwrap_or(false),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn list_memory_cards(
+    state: tauri::State<AppState>,
+    autopilot_id: String,
+) -> Result<Vec<learning::MemoryCardRecord>, String> {
+    let connection = open_connection(&state)?;
+    learning::list_memory_cards(&connection, autopilot_id.trim()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn upsert_memory_card(
+    state: tauri::State<AppState>,
+    input: MemoryCardInput,
+) -> Result<learning::MemoryCardRecord, String> {
+    let connection = open_connection(&state)?;
+    let autopilot_id = input.autopilot_id.trim();
+    if autopilot_id.is_empty() {
+        return Err("Autopilot ID is required.".to_string());
+    }
+    with_config_audit(&connection, |connection| {
+        let before = learning::list_memory_cards(connection, autopilot_id)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .find(|card| card.card_type == input.card_type.trim());
+        let saved = learning::save_memory_card(
+            connection,
+            autopilot_id,
+            &input.card_type,
+            &input.title,
+            &input.content_json,
+            input.confidence,
+        )
+        .map_err(|e| e.to_string())?;
+        record_config_change(
+            connection,
+            "upsert_memory_card",
+            &saved.card_id,
+            before.as_ref(),
+            Some(&saved),
+        )?;
+        Ok(saved)
+    })
+}
+
+#[tauri::command]
+fn delete_memory_card(
+    state: tauri::State<AppState>,
+    autopilot_id: String,
+    card_id: String,
+) -> Result<bool, String> {
+    let connection = open_connection(&state)?;
+    let autopilot_id = autopilot_id.trim();
+    with_config_audit(&connection, |connection| {
+        let before = learning::list_memory_cards(connection, autopilot_id)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .find(|card| card.card_id == card_id);
+        let deleted = learning::delete_memory_card(connection, autopilot_id, &card_id)
+            .map_err(|e| e.to_string())?;
+        if deleted {
+            record_config_change(
+                connection,
+                "delete_memory_card",
+                &card_id,
+                before.as_ref(),
+                None,
+            )?;
+        }
+        Ok(deleted)
+    })
+}
+
+fn generate_secret_token(prefix: &str) -> String {
+    let raw = format!(
+        "{}:{}:{}:{}",
+        prefix,
+        now_ms(),
+        make_main_id("tok"),
+        std::process::id()
+    );
+    let digest = Sha256::digest(raw.as_bytes());
+    format!(
+        "{}_{}",
+        prefix,
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(digest)
+    )
+}
+
+fn ensure_relay_device_id() -> Result<String, providers::types::ProviderError> {
+    if let Some(existing) =
+        providers::keychain::get_relay_device_id()?.filter(|v| !v.trim().is_empty())
+    {
+        return Ok(existing);
+    }
+    let device_id = generate_secret_token("device");
+    providers::keychain::set_relay_device_id(&device_id)?;
+    Ok(device_id)
+}
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    let a_bytes = a.as_bytes();
+    let b_bytes = b.as_bytes();
+    if a_bytes.len() != b_bytes.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a_bytes.iter().zip(b_bytes.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+fn validate_relay_callback_auth(input: &RelayApprovalCallbackInput) -> Result<(), String> {
+    validate_relay_callback_auth_fields(
+        &input.request_id,
+        &input.callback_secret,
+        input.issued_at_ms,
+        "Remote approvals are not ready yet. Generate a callback secret first.",
+    )
+}
+
+fn validate_relay_callback_auth_fields(
+    request_id: &str,
+    callback_secret: &str,
+    issued_at_ms: i64,
+    missing_secret_message: &str,
+) -> Result<(), String> {
+    let request_id = request_id.trim();
+    if request_id.is_empty() || request_id.len() > 120 {
+        return Err("Relay callback request id is invalid.".to_string());
+    }
+    let expected = providers::keychain::get_relay_callback_secret()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| missing_secret_message.to_string())?;
+    if !constant_time_eq(expected.trim(), callback_secret.trim()) {
+        return Err("Relay callback authentication failed.".to_string());
+    }
+    let now = now_ms();
+    if issued_at_ms <= 0 || (now - issued_at_ms).abs() > 15 * 60 * 1000 {
+        return Err("Relay callback request expired. Retry from Terminus relay.".to_string());
+    }
+    Ok(())
+}
+
+fn get_relay_callback_existing_run(
+    connection: &rusqlite::Connection,
+    request_id: &str,
+) -> Result<Option<RunRecord>, String> {
+    let approval_id: Option<String> = connection
+        .query_row(
+            "SELECT approval_id FROM relay_callback_events WHERE request_id = ?1 LIMIT 1",
+            rusqlite::params![request_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Could not read relay callback history: {e}"))?;
+    let Some(approval_id) = approval_id else {
+        return Ok(None);
+    };
+    let approval = RunnerEngine::get_approval_for_external(connection, &approval_id)
+        .map_err(|e| e.to_string())?;
+    let run = RunnerEngine::get_run_for_external(connection, &approval.run_id)
+        .map_err(|e| e.to_string())?;
+    Ok(Some(run))
+}
+
+fn reserve_relay_callback_event(
+    connection: &rusqlite::Connection,
+    request_id: &str,
+    approval_id: &str,
+    decision: &str,
+    channel: Option<&str>,
+    actor_label: Option<&str>,
+    applied_via: &str,
+) -> Result<(), String> {
+    let id = make_main_id("relay_cb");
+    let inserted = connection
+        .execute(
+            "INSERT OR IGNORE INTO relay_callback_events
+             (id, request_id, approval_id, decision, status, channel, actor_label, applied_via, created_at_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            rusqlite::params![
+                id,
+                request_id.trim(),
+                approval_id,
+                decision.trim().to_ascii_lowercase(),
+                "received",
+                sanitize_approval_resolution_field(channel, 32),
+                sanitize_approval_resolution_field(actor_label, 80),
+                applied_via,
+                now_ms()
+            ],
+        )
+        .map_err(|e| format!("Could not record relay callback event: {e}"))?;
+    if inserted == 0 {
+        return Err("Relay callback request was already processed.".to_string());
+    }
+    Ok(())
+}
+
+fn update_relay_callback_event_status(
+    connection: &rusqlite::Connection,
+    request_id: &str,
+    status: &str,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "UPDATE relay_callback_events SET status = ?1 WHERE request_id = ?2",
+            rusqlite::params![status, request_id.trim()],
+        )
+        .map_err(|e| format!("Could not update relay callback status: {e}"))?;
+    Ok(())
+}
+
+fn sanitize_approval_resolution_field(value: Option<&str>, max_len: usize) -> Option<String> {
+    let raw = value?.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    let bounded = raw
+        .chars()
+        .take(max_len)
+        .filter(|c| {
+            c.is_ascii_alphanumeric() || matches!(c, ' ' | '_' | '-' | '.' | ':' | '@' | '/')
+        })
+        .collect::<String>()
+        .trim()
+        .to_string();
+    if bounded.is_empty() {
+        None
+    } else {
+        Some(bounded)
+    }
+}
+
+fn relay_webhook_base_url() -> String {
+    if let Ok(url) = std::env::var("TERMINUS_RELAY_WEBHOOK_URL") {
+        if !url.trim().is_empty() {
+            return url;
+        }
+    }
+    let dispatch = transport::RelayTransport::default_url();
+    if let Some((prefix, _)) = dispatch.rsplit_once('/') {
+        format!("{prefix}/webhooks")
+    } else {
+        format!("{dispatch}/webhooks")
+    }
+}
+
+fn make_hashed_token(prefix: &str, seed: &str) -> String {
+    let digest = Sha256::digest(format!("{prefix}:{seed}:{}", now_ms()).as_bytes());
+    let encoded = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(digest);
+    format!(
+        "{}_{}",
+        prefix,
+        encoded.chars().take(24).collect::<String>()
+    )
+}
+
+/// Plan a new webhook trigger snapshots: the latest webhook-recipe run of the Autopilot, or its
+/// latest run of any recipe when it has never run a webhook plan.
+fn latest_run_plan_snapshot(
+    connection: &rusqlite::Connection,
+    autopilot_id: &str,
+) -> Result<(String, String), String> {
+    connection
+        .query_row(
+            "SELECT plan_json, provider_kind
+             FROM runs
+             WHERE autopilot_id = ?1
+             ORDER BY CASE WHEN json_extract(plan_json, '$.recipe') = 'webhook' THEN 0 ELSE 1 END,
+                      updated_at DESC, created_at DESC
+             LIMIT 1",
+            rusqlite::params![autopilot_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| format!("Could not load latest run plan for this Autopilot: {e}"))?
+        .ok_or_else(|| {
+            "Run a test once before adding a webhook trigger so Terminus can snapshot the plan."
+                .to_string()
+        })
+}
+
+#[tauri::command]
+fn update_webhook_trigger_payload_schema(
+    state: tauri::State<AppState>,
+    trigger_id: String,
+    payload_schema: Option<webhook_triggers::WebhookPayloadSchema>,
+) -> Result<webhook_triggers::WebhookTriggerRecord, String> {
+    let connection = open_connection(&state)?;
+    update_webhook_trigger_payload_schema_internal(&connection, trigger_id.trim(), payload_schema)?;
+    webhook_triggers::get_webhook_trigger(
+        &connection,
+        trigger_id.trim(),
+        &relay_webhook_base_url(),
+        &|id| {
+            providers::keychain::get_webhook_trigger_secret(id)
+                .ok()
+                .flatten()
+                .is_some_and(|v| !v.trim().is_empty())
+        },
+    )?
+    .ok_or_else(|| "Webhook trigger not found.".to_string())
+}
+
+fn update_webhook_trigger_payload_schema_internal(
+    connection: &rusqlite::Connection,
+    trigger_id: &str,
+    payload_schema: Option<webhook_triggers::WebhookPayloadSchema>,
+) -> Result<Option<webhook_triggers::WebhookPayloadSchema>, String> {
+    if trigger_id.is_empty() {
+        return Err("Trigger ID is required.".to_string());
+    }
+    with_config_audit(connection, |connection| {
+        let before = webhook_triggers::get_webhook_trigger_route_config(connection, trigger_id)?
+            .ok_or_else(|| "Webhook trigger not found.".to_string())?
+            .payload_schema;
+        let after = match payload_schema {
+            Some(schema) => webhook_triggers::normalize_webhook_payload_schema(schema)?,
+            None => None,
+        };
+        webhook_triggers::update_webhook_trigger_payload_schema(
+            connection,
+            trigger_id,
+            after.as_ref(),
+            now_ms(),
+        )?;
+        record_config_change(
+            connection,
+            "update_webhook_trigger_payload_schema",
+            trigger_id,
+            before.as_ref(),
+            after.as_ref(),
+        )?;
+        Ok(after)
+    })
+}
+
+fn update_webhook_trigger_enabled(
+    state: tauri::State<AppState>,
+    trigger_id: String,
+    enabled: bool,
+) -> Result<webhook_triggers::WebhookTriggerRecord, String> {
+    let connection = open_connection(&state)?;
+    let trigger_id = trigger_id.trim();
+    if trigger_id.is_empty() {
+        return Err("Trigger ID is required.".to_string());
+    }
+    let status = if enabled { "active" } else { "paused" };
+    webhook_triggers::update_webhook_trigger_status(&connection, trigger_id, status, None)?;
+    let relay_base = relay_webhook_base_url();
+    webhook_triggers::get_webhook_trigger(&connection, trigger_id, &relay_base, &|id| {
+        providers::keychain::get_webhook_trigger_secret(id)
+            .ok()
+            .flatten()
+            .is_some_and(|v| !v.trim().is_empty())
+    })?
+    .ok_or_else(|| "Webhook trigger not found.".to_string())
+}
+
+fn reserve_relay_webhook_callback_event(
+    connection: &rusqlite::Connection,
+    request_id: &str,
+    trigger_id: &str,
+    delivery_id: &str,
+    channel: Option<&str>,
+) -> Result<(), String> {
+    let inserted = connection
+        .execute(
+            "INSERT OR IGNORE INTO relay_webhook_callback_events
+             (id, request_id, trigger_id, delivery_id, status, channel, created_at_ms)
+             VALUES (?1, ?2, ?3, ?4, 'received', ?5, ?6)",
+            rusqlite::params![
+                make_main_id("relay_wh"),
+                request_id.trim(),
+                trigger_id,
+                delivery_id,
+                sanitize_approval_resolution_field(channel, 32),
+                now_ms(),
+            ],
+        )
+        .map_err(|e| format!("Could not record relay webhook callback event: {e}"))?;
+    if inserted == 0 {
+        return Err("Relay webhook callback request was already processed.".to_string());
+    }
+    Ok(())
+}
+
+fn update_relay_webhook_callback_event_status(
+    connection: &rusqlite::Connection,
+    request_id: &str,
+    status: &str,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "UPDATE relay_webhook_callback_events SET status = ?1 WHERE request_id = ?2",
+            rusqlite::params![status, request_id.trim()],
+        )
+        .map_err(|e| format!("Could not update relay webhook callback status: {e}"))?;
+    Ok(())
+}
+
+fn redact_webhook_headers_json(input: Option<&str>) -> String {
+    let Some(raw) = input.map(str::trim).filter(|v| !v.is_empty()) else {
+        return "{}".to_string();
+    };
+    let parsed = serde_json::from_str::<serde_json::Map<String, Value>>(raw);
+    let Ok(map) = parsed else {
+        return "{}".to_string();
+    };
+    let mut out = serde_json::Map::new();
+    for (key, value) in map.into_iter().take(24) {
+        let lower = key.to_ascii_lowercase();
+        let redacted = if lower.contains("authorization")
+            || lower.contains("cookie")
+            || lower.contains("secret")
+            || lower.contains("signature")
+            || lower.contains("token")
+        {
+            Value::String("[REDACTED]".to_string())
+        } else {
+            let text = match value {
+                Value::String(s) => s.chars().take(120).collect::<String>(),
+                other => other.to_string().chars().take(120).collect::<String>(),
+            };
+            Value::String(sanitize_log_message(&text))
+        };
+        out.insert(key.chars().take(48).collect(), redacted);
+    }
+    serde_json::to_string(&out).unwrap_or_else(|_| "{}".to_string())
+}
+
+fn normalize_content_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn payload_excerpt_from_json(body_json: &str) -> String {
+    let compact = serde_json::from_str::<Value>(body_json)
+        .map(|v| v.to_string())
+        .unwrap_or_else(|_| body_json.to_string());
+    sanitize_log_message(&compact.chars().take(280).collect::<String>())
+}
+
+fn payload_hash(body_json: &str) -> String {
+    format!("{:x}", Sha256::digest(body_json.as_bytes()))
+}
+
+fn validate_gmail_trigger_mode(input: &str) -> Result<String, String> {
+    let v = input.trim().to_ascii_lowercase();
+    match v.as_str() {
+        "polling" | "gmail_pubsub" | "auto" => Ok(v),
+        _ => Err("Gmail trigger mode must be Polling, Gmail PubSub, or Auto.".to_string()),
+    }
+}
+
+fn validate_gmail_pubsub_callback_mode(input: &str) -> Result<String, String> {
+    let v = input.trim().to_ascii_lowercase();
+    match v.as_str() {
+        "relay" | "local_debug" => Ok(v),
+        _ => Err("Gmail PubSub callback mode must be Relay or Local Debug.".to_string()),
+    }
+}
+
+fn sanitize_gmail_pubsub_resource_name(raw: &str, label: &str) -> Result<String, String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() || trimmed.len() > 240 {
+        return Err(format!("Gmail PubSub {label} is required."));
+    }
+    let bounded = trimmed
+        .chars()
+        .take(240)
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '_' | '-' | '.' | ':'))
+        .collect::<String>();
+    if bounded.is_empty() || !bounded.contains('/') {
+        return Err(format!("Gmail PubSub {label} format is invalid."));
+    }
+    Ok(bounded)
+}
+
+fn gmail_watch_register(access_token: &str, topic_name: &str) -> Result<(i64, String), String> {
+    let client = HttpClient::builder()
+        .timeout(Duration::from_secs(20))
+        .build()
+        .map_err(|_| "Could not initialize Gmail watch client.".to_string())?;
+    let body = serde_json::json!({
+        "topicName": topic_name,
+        "labelIds": ["INBOX"],
+        "labelFilterBehavior": "INCLUDE"
+    });
+    let json = client
+        .post("https://gmail.googleapis.com/gmail/v1/users/me/watch")
+        .bearer_auth(access_token)
+        .json(&body)
+        .send()
+        .map_err(|_| "Could not register Gmail PubSub watch. Check connection and try again.".to_string())?
+        .error_for_status()
+        .map_err(|e| {
+            if e.status().map(|s| s.as_u16()) == Some(429) {
+                "Gmail watch registration is rate-limited right now. Try again shortly.".to_string()
+            } else {
+                "Could not register Gmail PubSub watch. Check Gmail PubSub topic settings and try again.".to_string()
+            }
+        })?
+        .json::<Value>()
+        .map_err(|_| "Could not parse Gmail watch registration response.".to_string())?;
+    let expiration_ms = json
+        .get("expiration")
+        .and_then(|v| {
+            v.as_str()
+                .and_then(|s| s.parse::<i64>().ok())
+                .or_else(|| v.as_i64())
+        })
+        .ok_or_else(|| "Gmail watch response is missing expiration.".to_string())?;
+    let history_id = json
+        .get("historyId")
+        .and_then(|v| {
+            v.as_str()
+                .map(|s| s.to_string())
+                .or_else(|| v.as_i64().map(|n| n.to_string()))
+        })
+        .ok_or_else(|| "Gmail watch response is missing history id.".to_string())?;
+    Ok((expiration_ms, history_id))
+}
+
+fn reserve_relay_gmail_pubsub_callback_event(
+    connection: &rusqlite::Connection,
+    request_id: &str,
+    channel: Option<&str>,
+) -> Result<(), String> {
+    let inserted = connection
+        .execute(
+            "INSERT OR IGNORE INTO relay_gmail_pubsub_callback_events
+             (id, request_id, status, channel, created_at_ms)
+             VALUES (?1, ?2, 'received', ?3, ?4)",
+            rusqlite::params![
+                make_main_id("relay_gp"),
+                request_id.trim(),
+                sanitize_approval_resolution_field(channel, 32),
+                now_ms()
+            ],
+        )
+        .map_err(|e| format!("Could not record relay Gmail PubSub callback event: {e}"))?;
+    if inserted == 0 {
+        return Err("Relay Gmail PubSub callback request was already processed.".to_string());
+    }
+    Ok(())
+}
+
+fn update_relay_gmail_pubsub_callback_event_status(
+    connection: &rusqlite::Connection,
+    request_id: &str,
+    status: &str,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "UPDATE relay_gmail_pubsub_callback_events SET status = ?1 WHERE request_id = ?2",
+            rusqlite::params![status, request_id.trim()],
+        )
+        .map_err(|e| format!("Could not update relay Gmail PubSub callback status: {e}"))?;
+    Ok(())
+}
+
+/// Records which Gmail envelope a relay request carried, so re-deliveries under a new request
+/// id can be traced back to the original.
+fn link_relay_gmail_pubsub_callback_event(
+    connection: &rusqlite::Connection,
+    request_id: &str,
+    event_dedupe_key: &str,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "UPDATE relay_gmail_pubsub_callback_events SET event_dedupe_key = ?1 WHERE request_id = ?2",
+            rusqlite::params![event_dedupe_key, request_id.trim()],
+        )
+        .map_err(|e| format!("Could not update relay Gmail PubSub callback event: {e}"))?;
+    Ok(())
+}
+
+fn run_gmail_watcher_from_control(
+    connection: &mut rusqlite::Connection,
+) -> Result<inbox_watcher::InboxWatcherTickSummary, String> {
+    let control = db::get_runner_control(connection)?;
+    inbox_watcher::run_watcher_tick(
+        connection,
+        "gmail",
+        &control.gmail_autopilot_id,
+        control.watcher_max_items as usize,
+        &resolve_email_reply_approval,
+    )
+}
+
+fn ingest_gmail_pubsub_event_internal<F>(
+    connection: &mut rusqlite::Connection,
+    relay_request_id: Option<String>,
+    relay_channel: Option<&str>,
+    body_json: &str,
+    require_relay_callback_auth: bool,
+    fetch_and_queue: F,
+) -> Result<GmailPubSubIngestResult, String>
+where
+    F: FnOnce(&mut rusqlite::Connection) -> Result<inbox_watcher::InboxWatcherTickSummary, String>,
+{
+    let now = now_ms();
+    if require_relay_callback_auth {
+        let request_id = relay_request_id.as_deref().unwrap_or("");
+        if let Err(err) =
+            reserve_relay_gmail_pubsub_callback_event(connection, request_id, relay_channel)
+        {
+            if err.contains("already processed") {
+                return Ok(GmailPubSubIngestResult {
+                    status: "duplicate".to_string(),
+                    event_dedupe_key: request_id.to_string(),
+                    created_run_count: 0,
+                    message: "Relay Gmail PubSub callback request was already processed."
+                        .to_string(),
+                });
+            }
+            return Err(err);
+        }
+    }
+
+    let env = match gmail_pubsub::parse_pubsub_envelope(body_json) {
+        Ok(v) => v,
+        Err(err) => {
+            let _ = gmail_pubsub::record_failure(connection, &err, now);
+            if require_relay_callback_auth {
+                let _ = update_relay_gmail_pubsub_callback_event_status(
+                    connection,
+                    relay_request_id.as_deref().unwrap_or(""),
+                    "rejected",
+                );
+            }
+            return Ok(GmailPubSubIngestResult {
+                status: "rejected".to_string(),
+                event_dedupe_key: "invalid".to_string(),
+                created_run_count: 0,
+                message: err,
+            });
+        }
+    };
+
+    if require_relay_callback_auth {
+        link_relay_gmail_pubsub_callback_event(
+            connection,
+            relay_request_id.as_deref().unwrap_or(""),
+            &env.dedupe_key,
+        )?;
+    }
+    // The envelope key is unique across relay request ids, so a re-delivery after a restart
+    // lands here instead of re-running the watcher.
+    let inserted = gmail_pubsub::insert_event(
+        connection,
+        &gmail_pubsub::GmailPubSubEventInsert {
+            id: make_main_id("gpub_evt"),
+            provider: "gmail".to_string(),
+            message_id: Some(env.message_id.clone()),
+            event_dedupe_key: env.dedupe_key.clone(),
+            history_id: env.history_id.clone(),
+            published_at_ms: env.published_at_ms,
+            received_at_ms: now,
+            status: "accepted".to_string(),
+            failure_reason: None,
+            created_run_count: 0,
+            created_at_ms: now,
+        },
+    )?;
+    if !inserted {
+        if require_relay_callback_auth {
+            let _ = update_relay_gmail_pubsub_callback_event_status(
+                connection,
+                relay_request_id.as_deref().unwrap_or(""),
+                "duplicate",
+            );
+        }
+        return Ok(GmailPubSubIngestResult {
+            status: "duplicate".to_string(),
+            event_dedupe_key: env.dedupe_key,
+            created_run_count: 0,
+            message: if require_relay_callback_auth {
+                "This Gmail PubSub event was already delivered under another relay request. Ignored."
+                    .to_string()
+            } else {
+                "Duplicate Gmail PubSub event ignored.".to_string()
+            },
+        });
+    }
+
+    gmail_pubsub::update_event_status(connection, &env.dedupe_key, "queued_fetch", None, None)?;
+    match fetch_and_queue(connection) {
+        Ok(summary) => {
+            gmail_pubsub::update_event_status(
+                connection,
+                &env.dedupe_key,
+                "accepted",
+                None,
+                Some(summary.started_runs as i64),
+            )?;
+            gmail_pubsub::touch_event_success(connection, now, env.history_id.as_deref())?;
+            if require_relay_callback_auth {
+                update_relay_gmail_pubsub_callback_event_status(
+                    connection,
+                    relay_request_id.as_deref().unwrap_or(""),
+                    "applied",
+                )?;
+            }
+            Ok(GmailPubSubIngestResult {
+                status: "accepted".to_string(),
+                event_dedupe_key: env.dedupe_key,
+                created_run_count: summary.started_runs as i64,
+                message: "Gmail PubSub event accepted and inbox fetch queued.".to_string(),
+            })
+        }
+        Err(err) => {
+            let msg = sanitize_log_message(&err);
+            app_log::error(
+                "gmail_pubsub",
+                &format!("inbox fetch after push failed: {msg}"),
+            );
+            let _ = gmail_pubsub::update_event_status(
+                connection,
+                &env.dedupe_key,
+                "fetch_failed",
+                Some(&msg),
+                Some(0),
+            );
+            let _ = gmail_pubsub::record_failure(connection, &msg, now);
+            if require_relay_callback_auth {
+                let _ = update_relay_gmail_pubsub_callback_event_status(
+                    connection,
+                    relay_request_id.as_deref().unwrap_or(""),
+                    "fetch_failed",
+                );
+            }
+            Ok(GmailPubSubIngestResult {
+                status: "fetch_failed".to_string(),
+                event_dedupe_key: env.dedupe_key,
+                created_run_count: 0,
+                message: "Gmail PubSub event received, but inbox fetch failed. Terminus will keep polling fallback available.".to_string(),
+            })
+        }
+    }
+}
+
+fn validate_webhook_signature(
+    secret: &str,
+    body_json: &str,
+    signature: &str,
+    signature_ts_ms: i64,
+) -> Result<(), String> {
+    if signature_ts_ms <= 0 || (now_ms() - signature_ts_ms).abs() > 15 * 60 * 1000 {
+        return Err(
+            "Webhook signature timestamp is expired. Retry from the source system.".to_string(),
+        );
+    }
+    let provided = signature
+        .trim()
+        .strip_prefix("sha256=")
+        .unwrap_or(signature.trim())
+        .to_ascii_lowercase();
+    if provided.is_empty() || provided.len() > 256 {
+        return Err("Webhook signature is invalid.".to_string());
+    }
+    let expected = webhook_hmac_sha256_hex(secret, &format!("{}.{}", signature_ts_ms, body_json))?;
+    if !constant_time_eq(&expected, &provided) {
+        return Err("Webhook signature check failed.".to_string());
+    }
+    Ok(())
+}
+
+const WEBHOOK_SIGNATURE_MODES: [&str; 3] = ["terminus_hmac_sha256", "stripe", "github_sha256"];
+const STRIPE_SIGNATURE_TOLERANCE_SECONDS: i64 = 5 * 60;
+
+fn validate_webhook_signature_mode(input: &str) -> Result<String, String> {
+    let value = input.trim().to_ascii_lowercase();
+    if WEBHOOK_SIGNATURE_MODES.contains(&value.as_str()) {
+        Ok(value)
+    } else {
+        Err("Webhook signature mode must be Terminus, Stripe, or GitHub.".to_string())
+    }
+}
+
+/// Stripe issues its own `whsec_` secret per endpoint, so Stripe triggers store the secret the
+/// user pastes from Stripe; every other mode gets a generated one.
+fn webhook_signing_secret(signature_mode: &str, supplied: Option<&str>) -> Result<String, String> {
+    let supplied = supplied.map(str::trim).filter(|s| !s.is_empty());
+    match (signature_mode, supplied) {
+        ("stripe", Some(secret))
+            if secret.len() > "whsec_".len()
+                && secret.starts_with("whsec_")
+                && !secret.contains(char::is_whitespace) =>
+        {
+            Ok(secret.to_string())
+        }
+        ("stripe", Some(_)) => Err(
+            "Stripe signing secrets start with whsec_. Copy it from the Stripe webhook endpoint."
+                .to_string(),
+        ),
+        ("stripe", None) => Err(
+            "Paste the signing secret from the Stripe webhook endpoint to use Stripe signatures."
+                .to_string(),
+        ),
+        (_, Some(_)) => {
+            Err("Only Stripe triggers take a signing secret from the source.".to_string())
+        }
+        (_, None) => Ok(generate_secret_token("whsec")),
+    }
+}
+
+/// Checks `signature` with the verifier for the trigger's `signature_mode`. `signature` is the
+/// raw header value the source sent; `signature_ts_ms` only applies to the Terminus scheme.
+fn verify_webhook_signature(
+    signature_mode: &str,
+    secret: &str,
+    body_json: &str,
+    signature: &str,
+    signature_ts_ms: i64,
+) -> Result<(), String> {
+    match signature_mode {
+        "stripe" => validate_stripe_webhook_signature(secret, body_json, signature, now_ms()),
+        "github_sha256" => validate_github_webhook_signature(secret, body_json, signature),
+        _ => validate_webhook_signature(secret, body_json, signature, signature_ts_ms),
+    }
+}
+
+/// Stripe-Signature header: `t=<unix seconds>,v1=<hex>[,v1=<hex>...]`, signed over `t.body`.
+fn validate_stripe_webhook_signature(
+    secret: &str,
+    body_json: &str,
+    header: &str,
+    now: i64,
+) -> Result<(), String> {
+    let mut timestamp: Option<i64> = None;
+    let mut candidates = Vec::new();
+    for part in header.trim().split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.trim().parse::<i64>().ok(),
+            Some(("v1", value)) if !value.trim().is_empty() && value.len() <= 256 => {
+                candidates.push(value.trim().to_ascii_lowercase())
+            }
+            _ => {}
+        }
+    }
+    let timestamp = timestamp
+        .filter(|t| *t > 0)
+        .ok_or_else(|| "Webhook signature is invalid.".to_string())?;
+    if candidates.is_empty() {
+        return Err("Webhook signature is invalid.".to_string());
+    }
+    if (now / 1000 - timestamp).abs() > STRIPE_SIGNATURE_TOLERANCE_SECONDS {
+        return Err(
+            "Webhook signature timestamp is expired. Retry from the source system.".to_string(),
+        );
+    }
+    let expected = webhook_hmac_sha256_hex(secret, &format!("{timestamp}.{body_json}"))?;
+    if !candidates
+        .iter()
+        .any(|candidate| constant_time_eq(&expected, candidate))
+    {
+        return Err("Webhook signature check failed.".to_string());
+    }
+    Ok(())
+}
+
+/// X-Hub-Signature-256 header: `sha256=<hex>` over the raw body. GitHub sends no timestamp.
+fn validate_github_webhook_signature(
+    secret: &str,
+    body_json: &str,
+    header: &str,
+) -> Result<(), String> {
+    let provided = header
+        .trim()
+        .strip_prefix("sha256=")
+        .map(|v| v.to_ascii_lowercase())
+        .filter(|v| !v.is_empty() && v.len() <= 256)
+        .ok_or_else(|| "Webhook signature is invalid.".to_string())?;
+    let expected = webhook_hmac_sha256_hex(secret, body_json)?;
+    if !constant_time_eq(&expected, &provided) {
+        return Err("Webhook signature check failed.".to_string());
+    }
+    Ok(())
+}
+
+/// Builds the plan a delivery of `body_json` would start, applying the same size, JSON and
+/// payload-schema checks as ingest.
+fn preview_webhook_run_internal(
+    connection: &rusqlite::Connection,
+    trigger_id: &str,
+    body_json: &str,
+    now_ms: i64,
+) -> Result<WebhookRunPreview, String> {
+    let route = webhook_triggers::get_webhook_trigger_route_config(connection, trigger_id)?
+        .ok_or_else(|| "Webhook trigger not found.".to_string())?;
+    let body_len = body_json.len() as i64;
+    if body_len == 0 || body_len > route.max_payload_bytes {
+        return Err(format!(
+            "Sample payload must be between 1 and {} bytes for this trigger.",
+            route.max_payload_bytes
+        ));
+    }
+    let payload = serde_json::from_str::<Value>(body_json)
+        .map_err(|_| "Sample payload must be valid JSON.".to_string())?;
+    if let Some(schema) = route.payload_schema.as_ref() {
+        webhook_triggers::validate_webhook_payload(schema, &payload)?;
+    }
+    let plan = build_webhook_run_plan(
+        connection,
+        &route,
+        body_json,
+        &payload_hash(body_json),
+        now_ms,
+    )?;
+    let preview = preview_for_plan(&IntentDraftKind::DraftAutopilot, &plan);
+    Ok(WebhookRunPreview {
+        trigger_id: route.trigger_id,
+        plan,
+        preview,
+    })
+}
+
+/// Signs `body_json` the way a correct source would for `signature_mode`, so setup can compare
+/// against the source's own output. Only triggers stored locally can be simulated.
+fn simulate_webhook_signature_internal(
+    connection: &rusqlite::Connection,
+    trigger_id: &str,
+    body_json: &str,
+    timestamp_ms: i64,
+    secret_for: &dyn Fn(&str) -> Option<String>,
+) -> Result<WebhookSignatureSimulation, String> {
+    let route = webhook_triggers::get_webhook_trigger_route_config(connection, trigger_id)?
+        .ok_or_else(|| "Webhook trigger not found.".to_string())?;
+    let secret = secret_for(&route.trigger_id)
+        .filter(|v| !v.trim().is_empty())
+        .ok_or_else(|| {
+            "Webhook trigger signing secret is missing. Rotate the secret and retry.".to_string()
+        })?;
+    if timestamp_ms <= 0 {
+        return Err(
+            "Signature timestamp must be a positive Unix time in milliseconds.".to_string(),
+        );
+    }
+    let (signature, signature_ts_ms) = match route.signature_mode.as_str() {
+        "stripe" => {
+            let t = timestamp_ms / 1000;
+            let hex = webhook_hmac_sha256_hex(&secret, &format!("{t}.{body_json}"))?;
+            (format!("t={t},v1={hex}"), None)
+        }
+        "github_sha256" => {
+            let hex = webhook_hmac_sha256_hex(&secret, body_json)?;
+            (format!("sha256={hex}"), None)
+        }
+        _ => {
+            let hex = webhook_hmac_sha256_hex(&secret, &format!("{timestamp_ms}.{body_json}"))?;
+            (format!("sha256={hex}"), Some(timestamp_ms))
+        }
+    };
+    Ok(WebhookSignatureSimulation {
+        trigger_id: route.trigger_id,
+        signature_mode: route.signature_mode,
+        signature,
+        signature_ts_ms,
+    })
+}
+
+fn webhook_hmac_sha256_hex(secret: &str, message: &str) -> Result<String, String> {
+    type HmacSha256 = Hmac<Sha256>;
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .map_err(|_| "Webhook signature key is invalid.".to_string())?;
+    mac.update(message.as_bytes());
+    Ok(format!("{:x}", mac.finalize().into_bytes()))
+}
+
+fn build_webhook_run_plan(
+    connection: &rusqlite::Connection,
+    route: &webhook_triggers::WebhookTriggerRouteConfig,
+    body_json: &str,
+    payload_hash_hex: &str,
+    received_at_ms: i64,
+) -> Result<AutopilotPlan, String> {
+    let mut plan: AutopilotPlan = serde_json::from_str(&route.plan_json)
+        .map_err(|e| format!("Webhook trigger plan snapshot is invalid: {e}"))?;
+    if matches!(plan.recipe, RecipeKind::Custom | RecipeKind::Webhook) {
+        let provider_id = parse_provider(&route.provider_kind)?;
+        let disallowed = db::get_app_settings(connection)?.disallowed_primitives;
+        plan = validate_custom_execution_plan(plan, provider_id, &disallowed)?;
+    }
+    plan.event_context = serde_json::from_str::<Value>(body_json)
+        .ok()
+        .and_then(|event| runner::prune_event_context(&plan, &event));
+    if plan
+        .event_context
+        .as_ref()
+        .is_some_and(|event| event.to_string().len() > runner::EVENT_CONTEXT_MAX_BYTES)
+    {
+        return Err(
+            "The webhook event fields this Autopilot uses are too large to keep with the run."
+                .to_string(),
+        );
+    }
+    let excerpt = payload_excerpt_from_json(body_json);
+    let event_summary = format!(
+        "Webhook event from trigger {} at {} (hash {}). Payload excerpt: {}",
+        route.trigger_id,
+        received_at_ms,
+        &payload_hash_hex[..payload_hash_hex.len().min(12)],
+        excerpt
+    );
+    if plan
+        .inbox_source_text
+        .as_ref()
+        .is_none_or(|v| v.trim().is_empty())
+    {
+        plan.inbox_source_text = Some(event_summary.clone());
+    } else if let Some(existing) = plan.inbox_source_text.clone() {
+        let mut merged = existing;
+        merged.push_str("\n\n");
+        merged.push_str(&event_summary);
+        plan.inbox_source_text = Some(merged.chars().take(4_000).collect());
+    }
+    plan.intent = format!(
+        "{} [Webhook trigger {}]",
+        plan.intent.trim(),
+        route.trigger_id
+    )
+    .chars()
+    .take(240)
+    .collect();
+    Ok(plan)
+}
+
+fn insert_webhook_run_activity(
+    connection: &rusqlite::Connection,
+    run_id: &str,
+    trigger_id: &str,
+    delivery_id: &str,
+    channel: Option<&str>,
+) {
+    let message = format!(
+        "Webhook event queued from {} (delivery {}).",
+        trigger_id,
+        delivery_id.chars().take(48).collect::<String>()
+    );
+    let _ = connection.execute(
+        "INSERT INTO activities (id, run_id, activity_type, from_state, to_state, user_message, created_at)
+         VALUES (?1, ?2, 'webhook_event_queued', NULL, NULL, ?3, ?4)",
+        rusqlite::params![
+            make_main_id("activity"),
+            run_id,
+            truncate_for_activity(&message),
+            now_ms(),
+        ],
+    );
+    let _ = connection.execute(
+        "INSERT INTO activities (id, run_id, activity_type, from_state, to_state, user_message, created_at)
+         VALUES (?1, ?2, 'webhook_origin', NULL, NULL, ?3, ?4)",
+        rusqlite::params![
+            make_main_id("activity"),
+            run_id,
+            truncate_for_activity(&format!(
+                "Origin: webhook via {}",
+                channel.unwrap_or("relay_webhook")
+            )),
+            now_ms(),
+        ],
+    );
+}
+
+fn ingest_webhook_event_internal(
+    connection: &mut rusqlite::Connection,
+    input: WebhookIngestInput,
+) -> Result<WebhookIngestResult, String> {
+    let trigger_id = input.trigger_id.trim().to_string();
+    if trigger_id.is_empty() {
+        return Err("Trigger ID is required.".to_string());
+    }
+    let delivery_id = input.delivery_id.trim().to_string();
+    if delivery_id.is_empty() || delivery_id.len() > 200 {
+        return Err("Webhook delivery ID is invalid.".to_string());
+    }
+    if input.require_relay_callback_auth {
+        validate_relay_callback_auth_fields(
+            input.relay_request_id.as_deref().unwrap_or(""),
+            input.relay_callback_secret.as_deref().unwrap_or(""),
+            input.relay_issued_at_ms.unwrap_or_default(),
+            "Remote webhook delivery is not ready yet. Generate a callback secret first.",
+        )?;
+        if let Err(err) = reserve_relay_webhook_callback_event(
+            connection,
+            input.relay_request_id.as_deref().unwrap_or(""),
+            &trigger_id,
+            &delivery_id,
+            input.relay_channel.as_deref(),
+        ) {
+            if err.contains("already processed") {
+                return Ok(WebhookIngestResult {
+                    status: "duplicate".to_string(),
+                    trigger_id,
+                    delivery_id,
+                    run_id: None,
+                    message: "Relay webhook callback request was already processed.".to_string(),
+                });
+            }
+            return Err(err);
+        }
+    }
+
+    let route = webhook_triggers::get_webhook_trigger_route_config(connection, &trigger_id)?
+        .ok_or_else(|| "Webhook trigger not found.".to_string())?;
+    let now = now_ms();
+    let content_type = normalize_content_type(&input.content_type);
+    let body_json = input.body_json.trim().to_string();
+    let body_len = body_json.as_bytes().len() as i64;
+    let hash = payload_hash(&body_json);
+    let event_key = format!("{}:{}", delivery_id, &hash[..hash.len().min(16)]);
+    let headers_redacted_json = redact_webhook_headers_json(input.headers_redacted_json.as_deref());
+    let payload_excerpt = payload_excerpt_from_json(&body_json);
+
+    let base_event = webhook_triggers::WebhookTriggerEventInsert {
+        id: make_main_id("wh_event"),
+        trigger_id: trigger_id.clone(),
+        delivery_id: delivery_id.clone(),
+        event_idempotency_key: event_key.clone(),
+        received_at_ms: now,
+        status: "accepted".to_string(),
+        http_status: Some(202),
+        headers_redacted_json,
+        payload_excerpt,
+        payload_hash: hash.clone(),
+        failure_reason: None,
+        run_id: None,
+    };
+    let inserted = webhook_triggers::insert_webhook_trigger_event(connection, &base_event)?;
+    if !inserted {
+        if input.require_relay_callback_auth {
+            let _ = update_relay_webhook_callback_event_status(
+                connection,
+                input.relay_request_id.as_deref().unwrap_or(""),
+                "duplicate",
+            );
+        }
+        return Ok(WebhookIngestResult {
+            status: "duplicate".to_string(),
+            trigger_id,
+            delivery_id,
+            run_id: None,
+            message: "Duplicate webhook delivery ignored.".to_string(),
+        });
+    }
+
+    let fail = |status: &str,
+                reason: &str,
+                http_status: Option<i64>|
+     -> Result<WebhookIngestResult, String> {
+        let _ = webhook_triggers::update_webhook_trigger_event_status(
+            connection,
+            &trigger_id,
+            &event_key,
+            status,
+            Some(reason),
+            None,
+        );
+        let _ = webhook_triggers::touch_webhook_trigger_delivery(
+            connection,
+            &trigger_id,
+            now,
+            Some(reason),
+        );
+        if input.require_relay_callback_auth {
+            let _ = update_relay_webhook_callback_event_status(
+                connection,
+                input.relay_request_id.as_deref().unwrap_or(""),
+                status,
+            );
+        }
+        if let Some(code) = http_status {
+            let _ = connection.execute(
+                "UPDATE webhook_trigger_events SET http_status = ?1 WHERE trigger_id = ?2 AND event_idempotency_key = ?3",
+                rusqlite::params![code, &trigger_id, &event_key],
+            );
+        }
+        Ok(WebhookIngestResult {
+            status: status.to_string(),
+            trigger_id: trigger_id.clone(),
+            delivery_id: delivery_id.clone(),
+            run_id: None,
+            message: reason.to_string(),
+        })
+    };
+
+    if route.status != "active" {
+        return fail(
+            "rejected",
+            "Webhook trigger is paused. Enable it to accept events.",
+            Some(409),
+        );
+    }
+    if content_type != "application/json"
+        || !route
+            .allowed_content_types
+            .iter()
+            .any(|v| normalize_content_type(v) == content_type)
+    {
+        return fail(
+            "failed_validation",
+            "Unsupported webhook content type. Terminus currently accepts JSON only.",
+            Some(415),
+        );
+    }
+    if body_len <= 0 || body_len > route.max_payload_bytes {
+        return fail(
+            "failed_validation",
+            "Webhook payload is too large for this trigger. Reduce payload size or raise the trigger limit.",
+            Some(413),
+        );
+    }
+    if serde_json::from_str::<Value>(&body_json).is_err() {
+        return fail(
+            "failed_validation",
+            "Webhook payload must be valid JSON.",
+            Some(400),
+        );
+    }
+    if input.require_webhook_signature {
+        let secret = providers::keychain::get_webhook_trigger_secret(&trigger_id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| {
+                "Webhook trigger signing secret is missing. Rotate the secret and retry."
+                    .to_string()
+            })?;
+        if let Err(err) = verify_webhook_signature(
+            &route.signature_mode,
+            &secret,
+            // Sources sign the exact bytes they sent, so verify before any trimming.
+            &input.body_json,
+            input.signature.as_deref().unwrap_or(""),
+            input.signature_ts_ms.unwrap_or_default(),
+        ) {
+            return fail("rejected", &err, Some(401));
+        }
+    }
+    // Rate limited after the signature so forged deliveries cannot use up the trigger's budget.
+    webhook_triggers::mark_webhook_trigger_event_authenticated(connection, &base_event.id)?;
+    let recent_deliveries = webhook_triggers::count_recent_webhook_deliveries(
+        connection,
+        &trigger_id,
+        &base_event.id,
+        now,
+    )?;
+    if recent_deliveries >= route.max_deliveries_per_minute {
+        return fail(
+            "rate_limited",
+            "Webhook trigger received too many deliveries in the last minute. Slow the source down or raise the trigger limit.",
+            Some(429),
+        );
+    }
+    // Checked after the signature so unsigned callers cannot probe the expected shape.
+    if let Some(schema) = route.payload_schema.as_ref() {
+        let payload = serde_json::from_str::<Value>(&body_json).unwrap_or(Value::Null);
+        if let Err(err) = webhook_triggers::validate_webhook_payload(schema, &payload) {
+            return fail("failed_validation", &err, Some(422));
+        }
+    }
+
+    let run = match create_webhook_run(connection, &route, &event_key, &body_json, now) {
+        Ok(run) => run,
+        Err(reason) => {
+            let reason = sanitize_log_message(&reason);
+            app_log::warn(
+                "webhook_triggers",
+                &format!("webhook delivery {delivery_id} dead-lettered: {reason}"),
+            );
+            webhook_triggers::insert_webhook_dead_letter(
+                connection,
+                &webhook_triggers::WebhookDeadLetterRecord {
+                    id: make_main_id("wh_dead"),
+                    trigger_id: trigger_id.clone(),
+                    delivery_id: delivery_id.clone(),
+                    event_idempotency_key: event_key.clone(),
+                    relay_channel: input.relay_channel.clone(),
+                    reason: reason.clone(),
+                    body_json,
+                    status: "pending".to_string(),
+                    retry_count: 0,
+                    run_id: None,
+                    created_at_ms: now,
+                    updated_at_ms: now,
+                },
+            )?;
+            webhook_triggers::update_webhook_trigger_event_status(
+                connection,
+                &trigger_id,
+                &event_key,
+                "dead_lettered",
+                Some(&reason),
+                None,
+            )?;
+            webhook_triggers::touch_webhook_trigger_delivery(
+                connection,
+                &trigger_id,
+                now,
+                Some(&reason),
+            )?;
+            if input.require_relay_callback_auth {
+                update_relay_webhook_callback_event_status(
+                    connection,
+                    input.relay_request_id.as_deref().unwrap_or(""),
+                    "dead_lettered",
+                )?;
+            }
+            return Ok(WebhookIngestResult {
+                status: "dead_lettered".to_string(),
+                trigger_id,
+                delivery_id,
+                run_id: None,
+                message:
+                    "Webhook accepted, but its run could not be created. It was saved for retry."
+                        .to_string(),
+            });
+        }
+    };
+    insert_webhook_run_activity(
+        connection,
+        &run.id,
+        &trigger_id,
+        &delivery_id,
+        input.relay_channel.as_deref(),
+    );
+    webhook_triggers::update_webhook_trigger_event_status(
+        connection,
+        &trigger_id,
+        &event_key,
+        "queued",
+        None,
+        Some(&run.id),
+    )?;
+    webhook_triggers::touch_webhook_trigger_delivery(connection, &trigger_id, now, None)?;
+    if input.require_relay_callback_auth {
+        update_relay_webhook_callback_event_status(
+            connection,
+            input.relay_request_id.as_deref().unwrap_or(""),
+            "applied",
+        )?;
+    }
+    Ok(WebhookIngestResult {
+        status: "queued".to_string(),
+        trigger_id,
+        delivery_id,
+        run_id: Some(run.id),
+        message: "Webhook accepted and run queued.".to_string(),
+    })
+}
+
+fn create_webhook_run(
+    connection: &mut rusqlite::Connection,
+    route: &webhook_triggers::WebhookTriggerRouteConfig,
+    event_key: &str,
+    body_json: &str,
+    received_at_ms: i64,
+) -> Result<RunRecord, String> {
+    let plan = build_webhook_run_plan(
+        connection,
+        route,
+        body_json,
+        &payload_hash(body_json),
+        received_at_ms,
+    )?;
+    let run_idempotency_key = format!("webhook:{}:{}", route.trigger_id, event_key);
+    RunnerEngine::start_run_with_priority(
+        connection,
+        &route.autopilot_id,
+        plan,
+        &run_idempotency_key,
+        2,
+        RunPriority::Background,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Re-attempts run creation for a dead-lettered webhook delivery. Uses the original event key
+/// so a retry can never create a second run for the same delivery.
+fn retry_webhook_dead_letter_internal(
+    connection: &mut rusqlite::Connection,
+    id: &str,
+) -> Result<WebhookIngestResult, String> {
+    let dead_letter = webhook_triggers::get_webhook_dead_letter(connection, id)?
+        .ok_or_else(|| "Webhook dead letter not found.".to_string())?;
+    if dead_letter.status != "pending" {
+        return Err("This webhook delivery was already retried.".to_string());
+    }
+    let route =
+        webhook_triggers::get_webhook_trigger_route_config(connection, &dead_letter.trigger_id)?
+            .ok_or_else(|| "Webhook trigger not found.".to_string())?;
+    if route.status != "active" {
+        return Err("Webhook trigger is paused. Enable it before retrying.".to_string());
+    }
+    let now = now_ms();
+    let run = match create_webhook_run(
+        connection,
+        &route,
+        &dead_letter.event_idempotency_key,
+        &dead_letter.body_json,
+        now,
+    ) {
+        Ok(run) => run,
+        Err(err) => {
+            let reason = sanitize_log_message(&err);
+            webhook_triggers::record_webhook_dead_letter_retry(
+                connection,
+                id,
+                None,
+                Some(&reason),
+                now,
+            )?;
+            return Err(reason);
+        }
+    };
+    insert_webhook_run_activity(
+        connection,
+        &run.id,
+        &dead_letter.trigger_id,
+        &dead_letter.delivery_id,
+        dead_letter.relay_channel.as_deref(),
+    );
+    webhook_triggers::update_webhook_trigger_event_status(
+        connection,
+        &dead_letter.trigger_id,
+        &dead_letter.event_idempotency_key,
+        "queued",
+        None,
+        Some(&run.id),
+    )?;
+    webhook_triggers::record_webhook_dead_letter_retry(connection, id, Some(&run.id), None, now)?;
+    Ok(WebhookIngestResult {
+        status: "queued".to_string(),
+        trigger_id: dead_letter.trigger_id,
+        delivery_id: dead_letter.delivery_id,
+        run_id: Some(run.id),
+        message: "Webhook retried and run queued.".to_string(),
+    })
+}
+
+fn validate_voice_tone(input: &str) -> Result<String, String> {
+    let value = input.trim().to_ascii_lowercase();
+    match value.as_str() {
+        "professional" | "neutral" | "warm" => Ok(value),
+        _ => Err("Voice tone must be Professional, Neutral, or Warm.".to_string()),
+    }
+}
+
+fn validate_voice_length(input: &str) -> Result<String, String> {
+    let value = input.trim().to_ascii_lowercase();
+    match value.as_str() {
+        "concise" | "normal" | "detailed" => Ok(value),
+        _ => Err("Voice length must be Concise, Normal, or Detailed.".to_string()),
+    }
+}
+
+fn validate_voice_humor(input: &str) -> Result<String, String> {
+    let value = input.trim().to_ascii_lowercase();
+    match value.as_str() {
+        "off" | "light" => Ok(value),
+        _ => Err("Voice humor must be Off or Light.".to_string()),
+    }
+}
+
+fn sanitize_voice_notes(input: &str) -> String {
+    input
+        .trim()
+        .chars()
+        .take(800)
+        .collect::<String>()
+        .replace('\u{0000}', "")
+}
+
+/// Offline template for voice previews, so users can compare settings without a provider call.
+/// Expects already-validated tone/length/humor values.
+fn render_voice_preview(voice: &db::VoiceConfigRecord, sample_context: &str) -> String {
+    let context = sample_context
+        .trim()
+        .chars()
+        .take(160)
+        .collect::<String>()
+        .replace('\u{0000}', "");
+    let topic = if context.is_empty() {
+        "this week's project update".to_string()
+    } else {
+        context
+    };
+    let (greeting, opener, sign_off) = match voice.tone.as_str() {
+        "professional" => (
+            "Hello,",
+            format!("Please find a summary of {topic} below."),
+            "Best regards,",
+        ),
+        "warm" => (
+            "Hi there, hope your week is going well!",
+            format!("I wanted to share a quick note on {topic}."),
+            "Thanks so much,",
+        ),
+        _ => ("Hi,", format!("Here is an update on {topic}."), "Thanks,"),
+    };
+    let mut body = vec![opener];
+    if voice.length != "concise" {
+        body.push("The main items are on track, and one decision needs your input.".to_string());
+    }
+    if voice.length == "detailed" {
+        body.push(
+            "- Progress: the first milestone shipped on schedule.\n- Open question: confirm the next review date.\n- Risk: none blocking right now."
+                .to_string(),
+        );
+    }
+    if voice.humor == "light" {
+        body.push("No fire drills this time, promise.".to_string());
+    }
+    format!("{greeting}\n\n{}\n\n{sign_off}", body.join("\n\n"))
+}
+
+fn annotate_approval_resolution(
+    connection: &rusqlite::Connection,
+    approval_id: &str,
+    channel: Option<&str>,
+    actor_label: Option<&str>,
+) -> Result<(), String> {
+    let channel = sanitize_approval_resolution_field(channel, 32);
+    let actor = sanitize_approval_resolution_field(actor_label, 80);
+    if channel.is_none() && actor.is_none() {
+        return Ok(());
+    }
+    connection
+        .execute(
+            "UPDATE approvals
+             SET decided_channel = COALESCE(?1, decided_channel),
+                 decided_by = COALESCE(?2, decided_by)
+             WHERE id = ?3",
+            rusqlite::params![channel, actor, approval_id],
+        )
+        .map_err(|e| format!("Could not record approval source metadata: {e}"))?;
+    Ok(())
+}
+
+/// Resolves an approval from an inbox reply the watcher already matched to the account owner.
+fn resolve_email_reply_approval(
+    connection: &mut rusqlite::Connection,
+    approval_id: &str,
+    decision: inbox_watcher::ApprovalReplyDecision,
+    sender_email: &str,
+) -> Result<(), String> {
+    match decision {
+        inbox_watcher::ApprovalReplyDecision::Approve => approve_run_approval_with_context(
+            connection,
+            approval_id,
+            ApprovalEntryPoint::EmailReply,
+            Some("email_reply"),
+            Some(sender_email),
+        ),
+        inbox_watcher::ApprovalReplyDecision::Reject => reject_run_approval_with_context(
+            connection,
+            approval_id,
+            Some("Rejected by email reply.".to_string()),
+            ApprovalEntryPoint::EmailReply,
+            Some("email_reply"),
+            Some(sender_email),
+        ),
+    }
+    .map(|_| ())
+}
+
+/// The path an approval decision arrived through. The policy class comes from the entry point,
+/// never from the caller-supplied channel label, which is only recorded for display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ApprovalEntryPoint {
+    LocalUi,
+    Relay,
+    EmailReply,
+}
+
+impl ApprovalEntryPoint {
+    fn policy_class(self) -> &'static str {
+        match self {
+            Self::LocalUi => "local_ui",
+            Self::Relay => "relay",
+            Self::EmailReply => "email",
+        }
+    }
+}
+
+fn enforce_approval_channel_policy(
+    connection: &rusqlite::Connection,
+    approval_id: &str,
+    entry_point: ApprovalEntryPoint,
+) -> Result<(), String> {
+    let autopilot_id: Option<String> = connection
+        .query_row(
+            "SELECT r.autopilot_id FROM approvals a JOIN runs r ON r.id = a.run_id WHERE a.id = ?1",
+            rusqlite::params![approval_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Could not load approval for channel check: {e}"))?;
+    let Some(autopilot_id) = autopilot_id else {
+        return Ok(());
+    };
+    let policy = db::get_approval_channel_policy(connection, &autopilot_id)?;
+    let class = entry_point.policy_class();
+    if policy.allowed_channels.iter().any(|c| c == class) {
+        Ok(())
+    } else {
+        Err(format!(
+            "This Autopilot does not accept approvals from {class}. Resolve it from an allowed channel."
+        ))
+    }
+}
+
+fn approve_run_approval_with_context(
+    connection: &mut rusqlite::Connection,
+    approval_id: &str,
+    entry_point: ApprovalEntryPoint,
+    channel: Option<&str>,
+    actor_label: Option<&str>,
+) -> Result<RunRecord, String> {
+    enforce_approval_channel_policy(connection, approval_id, entry_point)?;
+    annotate_approval_resolution(connection, approval_id, channel, actor_label)?;
+    RunnerEngine::approve(connection, approval_id).map_err(|e| e.to_string())
+}
+
+fn reject_run_approval_with_context(
+    connection: &mut rusqlite::Connection,
+    approval_id: &str,
+    reason: Option<String>,
+    entry_point: ApprovalEntryPoint,
+    channel: Option<&str>,
+    actor_label: Option<&str>,
+) -> Result<RunRecord, String> {
+    enforce_approval_channel_policy(connection, approval_id, entry_point)?;
+    annotate_approval_resolution(connection, approval_id, channel, actor_label)?;
+    RunnerEngine::reject(connection, approval_id, reason).map_err(|e| e.to_string())
+}
+
+fn parse_recipe(value: &str) -> Result<RecipeKind, String> {
+    match value {
+        "website_monitor" => Ok(RecipeKind::WebsiteMonitor),
+        "inbox_triage" => Ok(RecipeKind::InboxTriage),
+        "daily_brief" => Ok(RecipeKind::DailyBrief),
+        "custom" => Ok(RecipeKind::Custom),
+        "webhook" => Ok(RecipeKind::Webhook),
+        _ => Err(format!("Unknown recipe: {value}")),
+    }
+}
+
+fn parse_provider(value: &str) -> Result<ProviderId, String> {
+    match value {
+        "openai" => Ok(ProviderId::OpenAi),
+        "anthropic" => Ok(ProviderId::Anthropic),
+        "gemini" => Ok(ProviderId::Gemini),
+        _ => Err(format!("Unknown provider: {value}")),
+    }
+}
+
+fn parse_intent_kind(value: &str) -> Result<IntentDraftKind, String> {
+    match value {
+        "one_off_run" => Ok(IntentDraftKind::OneOffRun),
+        "draft_autopilot" => Ok(IntentDraftKind::DraftAutopilot),
+        _ => Err(format!("Unknown intent kind: {value}")),
+    }
+}
+
+/// Saved phrase overrides win over the recurring hints; the longest matching phrase is used.
+fn classify_intent_kind(
+    intent: &str,
+    overrides: &[db::IntentKindOverrideRecord],
+) -> (IntentDraftKind, String) {
+    let normalized = intent.to_ascii_lowercase();
+    let matched_override = overrides
+        .iter()
+        .filter(|o| !o.phrase.is_empty() && normalized.contains(&o.phrase))
+        .max_by_key(|o| o.phrase.len())
+        .and_then(|o| {
+            parse_intent_kind(&o.kind)
+                .ok()
+                .map(|kind| (kind, &o.phrase))
+        });
+    if let Some((kind, phrase)) = matched_override {
+        let reason = match kind {
+            IntentDraftKind::DraftAutopilot => format!(
+                "Matches your saved phrase \"{phrase}\", so Terminus prepared an Autopilot setup."
+            ),
+            IntentDraftKind::OneOffRun => format!(
+                "Matches your saved phrase \"{phrase}\", so Terminus prepared a one-off Run."
+            ),
+        };
+        return (kind, reason);
+    }
+
+    let recurring_hints = [
+        "every",
+        "daily",
+        "weekly",
+        "monitor",
+        "watch",
+        "always",
+        "whenever",
+        "keep an eye",
+    ];
+    let should_recur = recurring_hints.iter().any(|hint| normalized.contains(hint))
+        || normalized.contains("inbox");
+
+    if should_recur {
+        (
+            IntentDraftKind::DraftAutopilot,
+            "Looks recurring, so Terminus prepared an Autopilot setup.".to_string(),
+        )
+    } else {
+        (
+            IntentDraftKind::OneOffRun,
+            "Looks one-time, so Terminus prepared a one-off Run.".to_string(),
+        )
+    }
+}
+
+fn classify_recipe(intent: &str) -> RecipeKind {
+    let normalized = intent.to_ascii_lowercase();
+    if normalized.contains("inbox")
+        || normalized.contains("email")
+        || normalized.contains("reply")
+        || normalized.contains("triage")
+    {
+        return RecipeKind::InboxTriage;
+    }
+    if normalized.contains("monitor")
+        || normalized.contains("website")
+        || normalized.contains("web page")
+        || normalized.contains("url")
+        || ((normalized.contains("http://") || normalized.contains("https://"))
+            && !normalized.contains("email"))
+    {
+        return RecipeKind::WebsiteMonitor;
+    }
+    if normalized.contains("brief")
+        || normalized.contains("summary")
+        || normalized.contains("digest")
+    {
+        return RecipeKind::DailyBrief;
+    }
+    let custom_signals = [
+        "chase",
+        "follow up",
+        "follow-up",
+        "remind",
+        "coordinate",
+        "parse",
+        "categorize",
+        "extract",
+        "prepare",
+        "compile",
+        "collect updates",
+        "generate report",
+        "proposal",
+        "contract",
+        "invoice",
+        "receipt",
+        "every friday",
+        "every monday",
+        "every week",
+        "spreadsheet",
+        "excel",
+        "automate",
+    ];
+    if custom_signals
+        .iter()
+        .any(|signal| normalized.contains(signal))
+    {
+        return RecipeKind::Custom;
+    }
+    RecipeKind::DailyBrief
+}
+
+/// Onboarding answers describe problems rather than tasks, so map common phrasings onto the
+/// signals `classify_recipe` already understands.
+const ONBOARDING_SIGNAL_HINTS: &[(&str, &str)] = &[
+    ("competitor", "monitor"),
+    ("pricing", "monitor"),
+    ("changes", "monitor"),
+    ("messages", "inbox"),
+    ("research", "brief"),
+    ("news", "brief"),
+];
+
+fn recommend_onboarding_intent(role: &str, focus: &str, pain: &str) -> OnboardingRecommendation {
+    let context = [focus.trim(), pain.trim()]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(". ");
+    let mut signals = format!("{} {}", pain, focus).to_ascii_lowercase();
+    for (phrase, signal) in ONBOARDING_SIGNAL_HINTS {
+        if signals.contains(phrase) {
+            signals.push(' ');
+            signals.push_str(signal);
+        }
+    }
+    let explicit_brief = ["brief", "summary", "digest"]
+        .iter()
+        .any(|signal| signals.contains(signal));
+    let role_lower = role.to_ascii_lowercase();
+    let operator_role = role_lower
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .any(|word| matches!(word, "founder" | "ops" | "operations" | "ea" | "pm"));
+
+    let (recipe, starter) = match classify_recipe(&signals) {
+        RecipeKind::InboxTriage => (
+            RecipeKind::InboxTriage,
+            "Handle my inbox each weekday morning. Classify important messages, draft replies when useful, and put anything risky into approvals.",
+        ),
+        RecipeKind::WebsiteMonitor => (
+            RecipeKind::WebsiteMonitor,
+            "Monitor the pages I care about for meaningful changes, ignore minor noise, summarize what changed, and queue approvals before any outbound message.",
+        ),
+        RecipeKind::Custom | RecipeKind::Webhook => (
+            RecipeKind::Custom,
+            "Automate this recurring task for me, and queue any risky follow-through in approvals.",
+        ),
+        RecipeKind::DailyBrief if explicit_brief => (
+            RecipeKind::DailyBrief,
+            "Create a weekday morning brief from my key sources, keep it concise, and deliver one outcome I can read quickly.",
+        ),
+        RecipeKind::DailyBrief if operator_role => (
+            RecipeKind::DailyBrief,
+            "Every weekday morning, prepare a concise brief from my key sources and inbox priorities, then queue any risky follow-through in approvals.",
+        ),
+        RecipeKind::DailyBrief => (
+            RecipeKind::DailyBrief,
+            "Help me automate the most repetitive part of my day. Start with a daily brief or inbox triage and queue any risky actions in approvals.",
+        ),
+    };
+    OnboardingRecommendation {
+        recipe,
+        intent: format!("{starter} {context}").trim().to_string(),
+    }
+}
+
+fn refresh_onboarding_recommendation(
+    connection: &rusqlite::Connection,
+) -> Result<OnboardingRecommendation, String> {
+    let mut current = db::get_onboarding_state(connection)?;
+    let recommendation = recommend_onboarding_intent(
+        &current.role_text,
+        &current.work_focus_text,
+        &current.biggest_pain_text,
+    );
+    current.recommended_intent = Some(recommendation.intent.clone());
+    db::upsert_onboarding_state(connection, &current)?;
+    Ok(recommendation)
+}
+
+#[derive(Debug, Deserialize)]
+struct GeneratedCustomPlan {
+    steps: Vec<GeneratedCustomStep>,
+    #[serde(default)]
+    web_allowed_domains: Vec<String>,
+    #[serde(default)]
+    recipient_hints: Vec<String>,
+    #[serde(default)]
+    allowed_primitives: Vec<String>,
+    #[serde(default)]
+    api_call_request: Option<GeneratedApiCallRequest>,
+    #[serde(default)]
+    api_call_requests: std::collections::BTreeMap<String, GeneratedApiCallRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeneratedCustomStep {
+    id: String,
+    label: String,
+    primitive: String,
+    requires_approval: bool,
+    risk_tier: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeneratedApiCallRequest {
+    url: String,
+    #[serde(default)]
+    method: Option<String>,
+    header_key_ref: String,
+    #[serde(default)]
+    auth_header_name: Option<String>,
+    #[serde(default)]
+    auth_scheme: Option<String>,
+    #[serde(default)]
+    body_json: Option<String>,
+    #[serde(default)]
+    response_fields: Vec<String>,
+}
+
+fn provider_kind_for_schema(provider_id: ProviderId) -> ApiProviderKind {
+    match provider_id {
+        ProviderId::OpenAi => ApiProviderKind::OpenAi,
+        ProviderId::Anthropic => ApiProviderKind::Anthropic,
+        ProviderId::Gemini => ApiProviderKind::Gemini,
+    }
+}
+
+fn provider_tier_for_schema(provider_id: ProviderId) -> ApiProviderTier {
+    match provider_id {
+        ProviderId::OpenAi | ProviderId::Anthropic => ApiProviderTier::Supported,
+        ProviderId::Gemini => ApiProviderTier::Experimental,
+    }
+}
+
+fn parse_generated_primitive_id(raw: &str) -> Result<PrimitiveId, String> {
+    let normalized = raw.trim().to_ascii_lowercase().replace(['.', '-'], "_");
+    match normalized.as_str() {
+        "readweb" | "read_web" => Ok(PrimitiveId::ReadWeb),
+        "readsources" | "read_sources" => Ok(PrimitiveId::ReadSources),
+        "readforwardedemail" | "read_forwarded_email" => Ok(PrimitiveId::ReadForwardedEmail),
+        "callapi" | "call_api" => Ok(PrimitiveId::CallApi),
+        "triageemail" | "triage_email" => Ok(PrimitiveId::TriageEmail),
+        "aggregatedailysummary" | "aggregate_daily_summary" => {
+            Ok(PrimitiveId::AggregateDailySummary)
+        }
+        "readvaultfile" | "read_vault_file" => Ok(PrimitiveId::ReadVaultFile),
+        "writeoutcomedraft" | "write_outcome_draft" => Ok(PrimitiveId::WriteOutcomeDraft),
+        "writeemaildraft" | "write_email_draft" => Ok(PrimitiveId::WriteEmailDraft),
+        "sendemail" | "send_email" => Ok(PrimitiveId::SendEmail),
+        "schedulerun" | "schedule_run" => Ok(PrimitiveId::ScheduleRun),
+        "notifyuser" | "notify_user" => Ok(PrimitiveId::NotifyUser),
+        _ => Err(format!("Unknown primitive in generated plan: {raw}")),
+    }
+}
+
+fn parse_generated_risk_tier(raw: &str) -> Result<RiskTier, String> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "low" => Ok(RiskTier::Low),
+        "medium" => Ok(RiskTier::Medium),
+        "high" => Ok(RiskTier::High),
+        _ => Err(format!("Unknown risk tier in generated plan: {raw}")),
+    }
+}
+
+fn sanitize_api_key_ref_name(raw: &str) -> Result<String, String> {
+    let cleaned = raw
+        .trim()
+        .chars()
+        .take(64)
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        .collect::<String>();
+    if cleaned.is_empty() {
+        return Err("API key reference name is required.".to_string());
+    }
+    Ok(cleaned)
+}
+
+fn normalize_api_call_method(raw: &str) -> Result<String, String> {
+    let method = raw.trim().to_ascii_uppercase();
+    match method.as_str() {
+        "GET" | "POST" => Ok(method),
+        _ => Err("CallApi method must be GET or POST in MVP.".to_string()),
+    }
+}
+
+fn normalize_auth_header_name(raw: &str) -> Result<String, String> {
+    let cleaned = raw
+        .trim()
+        .chars()
+        .take(48)
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect::<String>();
+    if cleaned.is_empty() {
+        return Err("CallApi auth header name is invalid.".to_string());
+    }
+    Ok(cleaned)
+}
+
+fn normalize_auth_scheme(raw: &str) -> Result<String, String> {
+    let value = raw.trim().to_ascii_lowercase();
+    match value.as_str() {
+        "bearer" | "raw" => Ok(value),
+        _ => Err("CallApi auth scheme must be bearer or raw in MVP.".to_string()),
+    }
+}
+
+fn validate_api_call_request_config(
+    config: ApiCallRequest,
+    allowlisted_domains: &mut Vec<String>,
+) -> Result<ApiCallRequest, String> {
+    let url = config.url.trim().to_string();
+    let (scheme, host) = crate::web::parse_scheme_host(&url)
+        .ok_or_else(|| "CallApi URL must be a valid HTTP/HTTPS URL.".to_string())?;
+    if scheme != "http" && scheme != "https" {
+        return Err("CallApi URL must use HTTP or HTTPS.".to_string());
+    }
+    if !crate::web::host_matches_allowlist(&host, allowlisted_domains) {
+        allowlisted_domains.push(host.to_ascii_lowercase());
+    }
+    let method = normalize_api_call_method(&config.method)?;
+    let header_key_ref = sanitize_api_key_ref_name(&config.header_key_ref)?;
+    let auth_header_name = normalize_auth_header_name(&config.auth_header_name)?;
+    let auth_scheme = normalize_auth_scheme(&config.auth_scheme)?;
+    let body_json = config
+        .body_json
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            if s.len() > runner::CALL_API_MAX_BODY_BYTES {
+                return Err("CallApi request body is too large for MVP.".to_string());
+            }
+            serde_json::from_str::<serde_json::Value>(s)
+                .map_err(|_| "CallApi request body must be valid JSON.".to_string())?;
+            if let Some(path) = runner::call_api_template_paths(s)
+                .into_iter()
+                .find(|p| !runner::is_supported_call_api_template_path(p))
+            {
+                return Err(format!(
+                    "CallApi body field {{{{{path}}}}} isn't supported. Use {{{{inbox_source_text}}}} or {{{{event.<field>}}}}."
+                ));
+            }
+            Ok(s.to_string())
+        })
+        .transpose()?;
+    if method == "GET" && body_json.is_some() {
+        return Err("CallApi GET requests cannot include a JSON body in MVP.".to_string());
+    }
+    let response_fields = normalize_api_response_fields(config.response_fields)?;
+    Ok(ApiCallRequest {
+        url,
+        method,
+        header_key_ref,
+        auth_header_name,
+        auth_scheme,
+        body_json,
+        response_fields,
+    })
+}
+
+fn normalize_api_response_fields(raw: Vec<String>) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    for field in raw {
+        let field = field.trim().to_string();
+        if field.is_empty() || fields.contains(&field) {
+            continue;
+        }
+        let valid = field.len() <= 80
+            && field.split('.').all(|seg| {
+                !seg.is_empty()
+                    && seg
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            });
+        if !valid {
+            return Err(format!(
+                "CallApi response field '{field}' must be a dot path like data.items.0.name."
+            ));
+        }
+        fields.push(field);
+    }
+    if fields.len() > runner::CALL_API_MAX_RESPONSE_FIELDS {
+        return Err(format!(
+            "CallApi can pass at most {} response fields to later steps.",
+            runner::CALL_API_MAX_RESPONSE_FIELDS
+        ));
+    }
+    Ok(fields)
+}
+
+fn generated_api_call_to_schema(
+    generated: GeneratedApiCallRequest,
+    allowlisted_domains: &mut Vec<String>,
+) -> Result<ApiCallRequest, String> {
+    validate_api_call_request_config(
+        ApiCallRequest {
+            url: generated.url,
+            method: generated.method.unwrap_or_else(|| "GET".to_string()),
+            header_key_ref: generated.header_key_ref,
+            auth_header_name: generated
+                .auth_header_name
+                .unwrap_or_else(|| "Authorization".to_string()),
+            auth_scheme: generated
+                .auth_scheme
+                .unwrap_or_else(|| "bearer".to_string()),
+            body_json: generated.body_json,
+            response_fields: generated.response_fields,
+        },
+        allowlisted_domains,
+    )
+}
+
+fn validate_custom_execution_plan(
+    mut plan: AutopilotPlan,
+    provider_id: ProviderId,
+    disallowed: &[PrimitiveId],
+) -> Result<AutopilotPlan, String> {
+    if !matches!(plan.recipe, RecipeKind::Custom | RecipeKind::Webhook) {
+        return Err("Custom plan payload must use recipe=custom or recipe=webhook.".to_string());
+    }
+    if plan.steps.is_empty() {
+        return Err("Custom plan must include at least one step.".to_string());
+    }
+    if plan.steps.len() > 10 {
+        return Err("Custom plan exceeds the maximum of 10 steps.".to_string());
+    }
+    if plan
+        .steps
+        .iter()
+        .any(|s| s.id.trim().is_empty() || s.label.trim().is_empty())
+    {
+        return Err("Every custom plan step needs an id and label.".to_string());
+    }
+
+    let mut used = Vec::<PrimitiveId>::new();
+    for step in &mut plan.steps {
+        if db::UNSUPPORTED_PRIMITIVES.contains(&step.primitive) {
+            return Err(format!(
+                "This action isn't allowed in Terminus yet: {}.",
+                step.label
+            ));
+        }
+        if disallowed.contains(&step.primitive) {
+            return Err(format!(
+                "This action is turned off for this Terminus install: {}.",
+                step.label
+            ));
+        }
+        match step.primitive {
+            PrimitiveId::CallApi => {
+                step.requires_approval = true;
+                step.risk_tier = RiskTier::High;
+            }
+            PrimitiveId::SendEmail => {
+                step.requires_approval = true;
+                step.risk_tier = RiskTier::High;
+            }
+            PrimitiveId::WriteOutcomeDraft
+            | PrimitiveId::WriteEmailDraft
+            | PrimitiveId::TriageEmail => {
+                step.requires_approval = true;
+                if step.risk_tier == RiskTier::Low {
+                    step.risk_tier = RiskTier::Medium;
+                }
+            }
+            _ => {}
+        }
+        if !used.contains(&step.primitive) {
+            used.push(step.primitive);
+        }
+    }
+    plan.allowed_primitives = used;
+
+    plan.provider = schema::ProviderMetadata::from_provider_id(provider_id);
+    plan.web_allowed_domains = plan
+        .web_allowed_domains
+        .into_iter()
+        .filter(|d| !d.trim().trim_matches('.').is_empty())
+        .map(|d| crate::web::normalize_allowlist_entry(&d))
+        .collect::<Result<Vec<String>, String>>()?;
+    if let Some(url) = plan.web_source_url.clone() {
+        if let Some((_, host)) = crate::web::parse_scheme_host(&url) {
+            if !crate::web::host_matches_allowlist(&host, &plan.web_allowed_domains) {
+                plan.web_allowed_domains.push(host);
+            }
+        }
+    }
+    for source in &plan.daily_sources {
+        if let Some((_, host)) = crate::web::parse_scheme_host(source) {
+            if !crate::web::host_matches_allowlist(&host, &plan.web_allowed_domains) {
+                plan.web_allowed_domains.push(host);
+            }
+        }
+    }
+    if let Some(config) = plan.api_call_request.clone() {
+        plan.api_call_request = Some(validate_api_call_request_config(
+            config,
+            &mut plan.web_allowed_domains,
+        )?);
+    }
+    let mut api_call_requests = std::collections::BTreeMap::new();
+    for (step_id, config) in std::mem::take(&mut plan.api_call_requests) {
+        let step_id = step_id.trim().to_string();
+        if !plan
+            .steps
+            .iter()
+            .any(|s| s.id == step_id && s.primitive == PrimitiveId::CallApi)
+        {
+            return Err(format!(
+                "API request configuration for \"{step_id}\" doesn't match a CallApi step."
+            ));
+        }
+        let config = validate_api_call_request_config(config, &mut plan.web_allowed_domains)?;
+        api_call_requests.insert(step_id, config);
+    }
+    plan.api_call_requests = api_call_requests;
+    plan.recipient_hints = plan
+        .recipient_hints
+        .into_iter()
+        .map(|e| e.trim().to_ascii_lowercase())
+        .filter(|e| e.contains('@'))
+        .collect();
+
+    if plan
+        .steps
+        .iter()
+        .any(|s| s.primitive == PrimitiveId::ReadWeb)
+        && (plan.web_source_url.as_deref().unwrap_or("").is_empty()
+            || plan.web_allowed_domains.is_empty())
+    {
+        return Err(
+            "Custom plan reads a website but has no allowed domains. Add a website domain and retry."
+                .to_string(),
+        );
+    }
+    if plan
+        .steps
+        .iter()
+        .any(|s| s.primitive == PrimitiveId::ReadSources)
+        && plan.daily_sources.is_empty()
+    {
+        return Err(
+            "Custom plan reads sources but no source URLs were detected. Add source URLs and retry."
+                .to_string(),
+        );
+    }
+    if plan
+        .steps
+        .iter()
+        .any(|s| s.primitive == PrimitiveId::CallApi && plan.api_call_for_step(&s.id).is_none())
+    {
+        return Err(
+            "Custom plan calls an API but has no API request configuration. Add URL and key ref and retry."
+                .to_string(),
+        );
+    }
+    if plan
+        .steps
+        .iter()
+        .any(|s| s.primitive == PrimitiveId::SendEmail)
+        && plan.recipient_hints.is_empty()
+    {
+        return Err(
+            "Custom plan sends email but has no recipient hints. Add at least one email recipient and retry."
+                .to_string(),
+        );
+    }
+    Ok(plan)
+}
+
+fn validate_plan_json_internal(
+    plan_json: &str,
+    provider: &str,
+    own_domains: &[String],
+    disallowed: &[PrimitiveId],
+) -> PlanValidationResponse {
+    let rejected = |error: String| PlanValidationResponse {
+        valid: false,
+        plan: None,
+        error: Some(error),
+        warnings: Vec::new(),
+    };
+    let provider_id = match parse_provider(provider) {
+        Ok(id) => id,
+        Err(error) => return rejected(error),
+    };
+    let parsed = match serde_json::from_str::<AutopilotPlan>(plan_json) {
+        Ok(plan) => plan,
+        Err(e) => return rejected(format!("Custom plan is invalid JSON: {e}")),
+    };
+    let original = parsed.clone();
+    let plan = match validate_custom_execution_plan(parsed, provider_id, disallowed) {
+        Ok(plan) => plan,
+        Err(error) => return rejected(error),
+    };
+
+    let mut warnings = Vec::new();
+    for (before, after) in original.steps.iter().zip(&plan.steps) {
+        if after.requires_approval && !before.requires_approval {
+            warnings.push(PlanWarning {
+                code: "approval_enforced".to_string(),
+                message: format!("\"{}\" will always wait for approval.", after.label),
+            });
+        }
+        if after.risk_tier != before.risk_tier {
+            warnings.push(PlanWarning {
+                code: "risk_raised".to_string(),
+                message: format!(
+                    "\"{}\" is treated as {} risk.",
+                    after.label,
+                    match after.risk_tier {
+                        RiskTier::High => "high",
+                        _ => "medium",
+                    }
+                ),
+            });
+        }
+    }
+    let added_domains = plan
+        .web_allowed_domains
+        .iter()
+        .filter(|domain| {
+            !original
+                .web_allowed_domains
+                .iter()
+                .any(|d| d.trim().eq_ignore_ascii_case(domain))
+        })
+        .cloned()
+        .collect::<Vec<String>>();
+    if !added_domains.is_empty() {
+        warnings.push(PlanWarning {
+            code: "domains_added".to_string(),
+            message: format!(
+                "Allowed domains were extended to cover the plan's URLs ({}).",
+                added_domains.join(", ")
+            ),
+        });
+    }
+    warnings.extend(collect_plan_warnings(&plan, own_domains));
+    PlanValidationResponse {
+        valid: true,
+        plan: Some(plan),
+        error: None,
+        warnings,
+    }
+}
+
+fn email_domain(address: &str) -> Option<String> {
+    address
+        .trim()
+        .rsplit_once('@')
+        .map(|(_, domain)| domain.trim().trim_end_matches('.').to_ascii_lowercase())
+        .filter(|domain| !domain.is_empty())
+}
+
+/// Soft checks run on a validated plan. `own_domains` are the domains of the user's connected
+/// mailboxes; recipients outside them are flagged as external.
+fn collect_plan_warnings(plan: &AutopilotPlan, own_domains: &[String]) -> Vec<PlanWarning> {
+    let mut warnings = Vec::new();
+    if plan
+        .steps
+        .iter()
+        .any(|s| s.primitive == PrimitiveId::SendEmail)
+    {
+        let mut external = plan
+            .recipient_hints
+            .iter()
+            .filter_map(|r| email_domain(r))
+            .filter(|domain| !own_domains.iter().any(|own| own == domain))
+            .collect::<Vec<String>>();
+        external.sort();
+        external.dedup();
+        if !external.is_empty() {
+            warnings.push(PlanWarning {
+                code: "external_recipient".to_string(),
+                message: format!(
+                    "This plan sends email outside your connected accounts ({}).",
+                    external.join(", ")
+                ),
+            });
+        }
+    }
+    for step in &plan.steps {
+        if step.risk_tier != RiskTier::Low && !step.requires_approval {
+            warnings.push(PlanWarning {
+                code: "unapproved_risky_step".to_string(),
+                message: format!(
+                    "\"{}\" is {} risk and runs without approval.",
+                    step.label,
+                    match step.risk_tier {
+                        RiskTier::High => "high",
+                        _ => "medium",
+                    }
+                ),
+            });
+        }
+    }
+    warnings
+}
+
+fn connected_mailbox_domains(connection: &rusqlite::Connection) -> Vec<String> {
+    email_connections::list_connections(connection)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|c| c.status == "connected")
+        .filter_map(|c| c.account_email.as_deref().and_then(email_domain))
+        .collect()
+}
+
+fn validate_and_build_custom_plan(
+    intent: &str,
+    provider_id: ProviderId,
+    generated: GeneratedCustomPlan,
+) -> Result<AutopilotPlan, String> {
+    if generated.steps.is_empty() {
+        return Err("Generated plan had no steps. Try a more specific request.".to_string());
+    }
+    if generated.steps.len() > 10 {
+        return Err("Generated plan exceeded the maximum of 10 steps.".to_string());
+    }
+
+    let mut used_primitives = Vec::<PrimitiveId>::new();
+    let mut steps = Vec::<PlanStep>::new();
+    for (index, generated_step) in generated.steps.iter().enumerate() {
+        let primitive = parse_generated_primitive_id(&generated_step.primitive)?;
+        let mut risk_tier = parse_generated_risk_tier(&generated_step.risk_tier)?;
+        let mut requires_approval = generated_step.requires_approval;
+        match primitive {
+            PrimitiveId::CallApi => {
+                requires_approval = true;
+                risk_tier = RiskTier::High;
+            }
+            PrimitiveId::SendEmail => {
+                requires_approval = true;
+                risk_tier = RiskTier::High;
+            }
+            PrimitiveId::WriteOutcomeDraft
+            | PrimitiveId::WriteEmailDraft
+            | PrimitiveId::TriageEmail => {
+                requires_approval = true;
+                if risk_tier == RiskTier::Low {
+                    risk_tier = RiskTier::Medium;
+                }
+            }
+            PrimitiveId::ScheduleRun | PrimitiveId::ReadVaultFile => {
+                return Err("This action isn't allowed in Terminus yet.".to_string())
+            }
+            _ => {}
+        }
+        if !used_primitives.contains(&primitive) {
+            used_primitives.push(primitive);
+        }
+        steps.push(PlanStep {
+            id: if generated_step.id.trim().is_empty() {
+                format!("step_{}", index + 1)
+            } else {
+                generated_step.id.trim().to_string()
+            },
+            label: generated_step.label.trim().to_string(),
+            primitive,
+            requires_approval,
+            risk_tier,
+        });
+    }
+
+    let inferred_urls = intent
+        .split_whitespace()
+        .filter_map(|token| {
+            let normalized = token.trim_matches(|c: char| ",.;:!?()[]{}<>\"'".contains(c));
+            if normalized.starts_with("http://") || normalized.starts_with("https://") {
+                Some(normalized.to_string())
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<String>>();
+    let web_source_url = inferred_urls.first().cloned();
+    let daily_sources = inferred_urls
+        .iter()
+        .take(5)
+        .cloned()
+        .collect::<Vec<String>>();
+
+    let mut web_allowed_domains = generated.web_allowed_domains;
+    let api_call_request = generated
+        .api_call_request
+        .map(|cfg| generated_api_call_to_schema(cfg, &mut web_allowed_domains))
+        .transpose()?;
+    let api_call_requests = generated
+        .api_call_requests
+        .into_iter()
+        .map(|(step_id, cfg)| {
+            generated_api_call_to_schema(cfg, &mut web_allowed_domains).map(|cfg| (step_id, cfg))
+        })
+        .collect::<Result<std::collections::BTreeMap<_, _>, String>>()?;
+
+    let plan = AutopilotPlan {
+        schema_version: "1.0".to_string(),
+        recipe: RecipeKind::Custom,
+        intent: intent.to_string(),
+        provider: schema::ProviderMetadata::from_provider_id(provider_id),
+        web_source_url,
+        web_allowed_domains,
+        web_raw_html: false,
+        inbox_source_text: None,
+        event_context: None,
+        daily_sources,
+        api_call_request,
+        api_call_requests,
+        recipient_hints: generated.recipient_hints,
+        allowed_primitives: if generated.allowed_primitives.is_empty() {
+            used_primitives
+        } else {
+            used_primitives
+        },
+        steps,
+    };
+    // Drafting has no database handle; the install's own disallowed set is enforced when the
+    // plan is validated or started.
+    validate_custom_execution_plan(plan, provider_id, &db::UNSUPPORTED_PRIMITIVES)
+}
+
+/// Why a custom plan could not be generated. Only a provider failure falls back to the
+/// template plan; a reply that fails validation is reported as-is.
+#[derive(Debug)]
+enum CustomPlanError {
+    ProviderUnavailable(String),
+    Invalid(String),
+}
+
+impl std::fmt::Display for CustomPlanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ProviderUnavailable(message) | Self::Invalid(message) => f.write_str(message),
+        }
+    }
+}
+
+fn generate_custom_plan(
+    connection: &rusqlite::Connection,
+    intent: &str,
+    provider_id: ProviderId,
+) -> Result<AutopilotPlan, CustomPlanError> {
+    let cache_key = custom_plan_cache_key(intent, provider_id);
+    let now = now_ms();
+    if let Some(plan) = cached_custom_plan(&cache_key, now) {
+        return Ok(plan);
+    }
+    let plan = generate_custom_plan_uncached(connection, intent, provider_id)?;
+    store_custom_plan(cache_key, now, plan.clone());
+    Ok(plan)
+}
+
+fn custom_plan_cache_key(intent: &str, provider_id: ProviderId) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(provider_kind_for_schema(provider_id).as_str().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(intent.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn cached_custom_plan(key: &str, now: i64) -> Option<AutopilotPlan> {
+    let mut cache = CUSTOM_PLAN_CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .ok()?;
+    cache.retain(|_, (stored_at, _)| now - *stored_at < CUSTOM_PLAN_CACHE_TTL_MS);
+    cache.get(key).map(|(_, plan)| plan.clone())
+}
+
+fn store_custom_plan(key: String, now: i64, plan: AutopilotPlan) {
+    let Ok(mut cache) = CUSTOM_PLAN_CACHE.get_or_init(Default::default).lock() else {
+        return;
+    };
+    if cache.len() >= CUSTOM_PLAN_CACHE_MAX_ENTRIES && !cache.contains_key(&key) {
+        if let Some(oldest) = cache
+            .iter()
+            .min_by_key(|(_, (stored_at, _))| *stored_at)
+            .map(|(k, _)| k.clone())
+        {
+            cache.remove(&oldest);
+        }
+    }
+    cache.insert(key, (now, plan));
+}
+
+fn generate_custom_plan_uncached(
+    connection: &rusqlite::Connection,
+    intent: &str,
+    provider_id: ProviderId,
+) -> Result<AutopilotPlan, CustomPlanError> {
+    let system_prompt = concat!(
+        "Generate a Terminus execution plan as JSON only for the user's intent.\n",
+        "Use only these primitive ids (snake_case): read_web, read_sources, read_forwarded_email, triage_email, aggregate_daily_summary, write_outcome_draft, write_email_draft, send_email, notify_user.\n",
+        "You may also use: call_api (approval-gated, bounded HTTP GET/POST to allowlisted domain with Keychain ref).\n",
+        "Do not use schedule_run or read_vault_file.\n",
+        "Required JSON shape:\n",
+        "{\"steps\":[{\"id\":\"step_1\",\"label\":\"...\",\"primitive\":\"read_web\",\"requires_approval\":false,\"risk_tier\":\"low\"}],\"web_allowed_domains\":[\"example.com\"],\"recipient_hints\":[\"person@example.com\"],\"allowed_primitives\":[\"read_web\"],\"api_call_request\":null}\n",
+        "If using call_api include api_call_request: {\"url\":\"https://api.example.com/v1/items\",\"method\":\"GET|POST\",\"header_key_ref\":\"crm_prod\",\"auth_header_name\":\"Authorization\",\"auth_scheme\":\"bearer|raw\",\"body_json\":\"{...}\",\"response_fields\":[\"data.id\"]}\n",
+        "If the plan has more than one call_api step, instead include api_call_requests keyed by step id: {\"step_1\":{...},\"step_3\":{...}}\n",
+        "Rules:\n",
+        "- call_api must be approval-gated and high risk\n",
+        "- send_email must be high risk and approval-gated\n",
+        "- write_outcome_draft and write_email_draft should be approval-gated\n",
+        "- Keep step count between 1 and 10\n",
+        "- Output JSON only, no markdown"
+    );
+    let request = ProviderRequest {
+        provider_kind: provider_kind_for_schema(provider_id),
+        provider_tier: provider_tier_for_schema(provider_id),
+        model: schema::ProviderMetadata::from_provider_id(provider_id).default_model,
+        system_prompt: Some(system_prompt.to_string()),
+        input: format!("Intent: {intent}"),
+        max_output_tokens: Some(900),
+        correlation_id: Some(format!("plan_gen:{}", make_main_id("req"))),
+    };
+    let response = ProviderRuntime::default()
+        .dispatch_traced(connection, &request, trace::trace_enabled())
+        .map_err(|e| {
+            CustomPlanError::ProviderUnavailable(format!(
+                "Could not generate a custom plan yet: {e}"
+            ))
+        })?;
+    let generated: GeneratedCustomPlan =
+        serde_json::from_str(response.text.trim()).map_err(|e| {
+            CustomPlanError::Invalid(format!("Plan generation returned invalid JSON: {e}"))
+        })?;
+    validate_and_build_custom_plan(intent, provider_id, generated).map_err(CustomPlanError::Invalid)
+}
+
+/// Minimal safe stand-in when plan generation can't reach the provider: read the URLs in the
+/// intent (or pasted text when there are none), then notify, with every step approval-gated.
+fn template_custom_plan(intent: &str, provider_id: ProviderId) -> Result<AutopilotPlan, String> {
+    let step = |id: &str, label: &str, primitive: &str| GeneratedCustomStep {
+        id: id.to_string(),
+        label: label.to_string(),
+        primitive: primitive.to_string(),
+        requires_approval: true,
+        risk_tier: "low".to_string(),
+    };
+    let has_urls = intent
+        .split_whitespace()
+        .any(|token| token.contains("http://") || token.contains("https://"));
+    let read_step = if has_urls {
+        step(
+            "step_1",
+            "Read the sources named in the request",
+            "read_sources",
+        )
+    } else {
+        step(
+            "step_1",
+            "Read the pasted or forwarded text",
+            "read_forwarded_email",
+        )
+    };
+    let mut plan = validate_and_build_custom_plan(
+        intent,
+        provider_id,
+        GeneratedCustomPlan {
+            steps: vec![
+                read_step,
+                step("step_2", "Notify you with what was found", "notify_user"),
+            ],
+            web_allowed_domains: Vec::new(),
+            recipient_hints: Vec::new(),
+            allowed_primitives: Vec::new(),
+            api_call_request: None,
+            api_call_requests: std::collections::BTreeMap::new(),
+        },
+    )?;
+    for step in &mut plan.steps {
+        step.requires_approval = true;
+    }
+    Ok(plan)
+}
+
+fn describe_primitive_read(primitive: PrimitiveId) -> Option<String> {
+    match primitive {
+        PrimitiveId::ReadWeb => Some("Read website content from allowlisted domains".to_string()),
+        PrimitiveId::ReadSources => Some("Read configured sources for this brief".to_string()),
+        PrimitiveId::ReadForwardedEmail => {
+            Some("Read forwarded or pasted inbox content".to_string())
+        }
+        PrimitiveId::CallApi => Some("Read or write a bounded external API endpoint".to_string()),
+        PrimitiveId::ReadVaultFile => Some("Read connected vault files".to_string()),
+        _ => None,
+    }
+}
+
+fn describe_primitive_write(primitive: PrimitiveId) -> Option<String> {
+    match primitive {
+        PrimitiveId::TriageEmail => Some("Apply inbox filing action".to_string()),
+        PrimitiveId::WriteOutcomeDraft => Some("Prepare a completed outcome".to_string()),
+        PrimitiveId::WriteEmailDraft => Some("Prepare an approval-ready message".to_string()),
+        PrimitiveId::SendEmail => Some("Send an email".to_string()),
+        PrimitiveId::ScheduleRun => Some("Schedule this autopilot".to_string()),
+        PrimitiveId::NotifyUser => Some("Send a notification".to_string()),
+        PrimitiveId::CallApi => Some("Call an external API (approval-gated)".to_string()),
+        _ => None,
+    }
+}
+
+fn preview_for_plan(kind: &IntentDraftKind, plan: &AutopilotPlan) -> IntentDraftPreview {
+    let mut reads = Vec::new();
+    let mut writes = Vec::new();
+    let mut approvals_required = Vec::new();
+
+    for step in &plan.steps {
+        if let Some(read) = describe_primitive_read(step.primitive) {
+            if !reads.contains(&read) {
+                reads.push(read);
+            }
+        }
+        if let Some(write) = describe_primitive_write(step.primitive) {
+            if !writes.contains(&write) {
+                writes.push(write);
+            }
+        }
+        if step.requires_approval {
+            approvals_required.push(step.label.clone());
+        }
+    }
+
+    IntentDraftPreview {
+        reads,
+        writes,
+        approvals_required,
+        estimated_spend: "About S$0.10–S$0.60 per run".to_string(),
+        primary_cta: match kind {
+            IntentDraftKind::OneOffRun => "Run now".to_string(),
+            IntentDraftKind::DraftAutopilot => "Run test".to_string(),
+        },
+    }
+}
+
+/// Re-drafts `intent` as `recipe` when auto-classification picked the wrong one.
+#[tauri::command]
+fn reclassify_recipe(
+    state: tauri::State<AppState>,
+    intent: String,
+    recipe: String,
+    provider: Option<String>,
+    forced_kind: Option<String>,
+) -> Result<IntentDraftResponse, String> {
+    draft_intent(state, intent, provider, forced_kind, Some(recipe))
+}
+
+/// Builds the plan for `intent`, using `forced_recipe` instead of `classify_recipe` when set.
+/// The flag is true when the provider was down and the template plan stands in.
+fn draft_plan_for_intent(
+    connection: &rusqlite::Connection,
+    intent: &str,
+    provider_id: ProviderId,
+    forced_recipe: Option<&str>,
+) -> Result<(AutopilotPlan, bool), String> {
+    let recipe = match forced_recipe {
+        Some(raw) => parse_recipe(raw.trim())?,
+        None => classify_recipe(intent),
+    };
+    if recipe != RecipeKind::Custom {
+        let plan = AutopilotPlan::from_intent(recipe, intent.to_string(), provider_id);
+        return Ok((plan, false));
+    }
+    match generate_custom_plan(connection, intent, provider_id) {
+        Ok(plan) => Ok((plan, false)),
+        Err(CustomPlanError::ProviderUnavailable(reason)) => {
+            app_log::warn("draft_intent", &reason);
+            Ok((template_custom_plan(intent, provider_id)?, true))
+        }
+        Err(CustomPlanError::Invalid(message)) => Err(message),
+    }
+}
+
+#[tauri::command]
+fn draft_intent(
+    state: tauri::State<AppState>,
+    intent: String,
+    provider: Option<String>,
+    forced_kind: Option<String>,
+    forced_recipe: Option<String>,
+) -> Result<IntentDraftResponse, String> {
+    let cleaned = intent.trim();
+    if cleaned.is_empty() {
+        return Err("Add a one-line intent to continue.".to_string());
+    }
+    let provider_id = match provider {
+        Some(raw) => parse_provider(&raw)?,
+        None => ProviderId::OpenAi,
+    };
+
+    let intent_overrides = open_connection(&state)
+        .and_then(|conn| db::list_intent_kind_overrides(&conn))
+        .unwrap_or_default();
+    let (auto_kind, auto_reason) = classify_intent_kind(cleaned, &intent_overrides);
+    let (kind, classification_reason) = match forced_kind {
+        Some(raw) => {
+            let forced = parse_intent_kind(raw.trim())?;
+            let reason = match forced {
+                IntentDraftKind::DraftAutopilot => {
+                    "Switched to recurring. Terminus prepared an Autopilot setup.".to_string()
+                }
+                IntentDraftKind::OneOffRun => {
+                    "Switched to one-time. Terminus prepared a one-off Run.".to_string()
+                }
+            };
+            (forced, reason)
+        }
+        None => (auto_kind, auto_reason),
+    };
+    let connection = open_connection(&state)?;
+    let (plan, degraded) =
+        draft_plan_for_intent(&connection, cleaned, provider_id, forced_recipe.as_deref())?;
+    let classification_reason = if degraded {
+        "Terminus couldn't reach your AI provider, so this is a minimal safe draft: read, then notify, with every step waiting for approval. Retry for a full plan.".to_string()
+    } else {
+        classification_reason
+    };
+    let preview = preview_for_plan(&kind, &plan);
+    let own_domains = open_connection(&state)
+        .map(|conn| connected_mailbox_domains(&conn))
+        .unwrap_or_default();
+    let warnings = collect_plan_warnings(&plan, &own_domains);
+
+    Ok(IntentDraftResponse {
+        kind,
+        classification_reason,
+        plan,
+        preview,
+        warnings,
+        degraded,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_recipe_preserves_existing_signals_and_detects_custom() {
+        assert_eq!(
+            classify_recipe("Monitor https://example.com for changes"),
+            RecipeKind::WebsiteMonitor
+        );
+        assert_eq!(classify_recipe("Handle my inbox"), RecipeKind::InboxTriage);
+        assert_eq!(
+            classify_recipe("Prepare a daily digest from these links"),
+            RecipeKind::DailyBrief
+        );
+        assert_eq!(
+            classify_recipe("Parse this invoice and categorize expenses every Friday"),
+            RecipeKind::Custom
+        );
+    }
+
+    #[test]
+    fn webhook_delivery_over_per_minute_limit_is_rate_limited() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        conn.execute(
+            "INSERT INTO autopilots (id, name, created_at) VALUES ('auto_hooks', 'Hooks', 1)",
+            [],
+        )
+        .expect("autopilot");
+        let plan = AutopilotPlan::from_intent(
+            RecipeKind::DailyBrief,
+            "Summarize webhook events".to_string(),
+            ProviderId::OpenAi,
+        );
+        conn.execute(
+            "INSERT INTO webhook_triggers (
+               id, autopilot_id, status, endpoint_path, plan_json, max_deliveries_per_minute,
+               created_at_ms, updated_at_ms
+             ) VALUES ('wh_flood', 'auto_hooks', 'active', 'hooks/flood', ?1, 2, 1, 1)",
+            rusqlite::params![serde_json::to_string(&plan).expect("plan json")],
+        )
+        .expect("trigger");
+        let deliver = |conn: &mut rusqlite::Connection, delivery_id: &str| {
+            ingest_webhook_event_internal(
+                conn,
+                WebhookIngestInput {
+                    relay_request_id: None,
+                    relay_callback_secret: None,
+                    relay_issued_at_ms: None,
+                    trigger_id: "wh_flood".to_string(),
+                    delivery_id: delivery_id.to_string(),
+                    content_type: "application/json".to_string(),
+                    body_json: format!("{{\"delivery\":\"{delivery_id}\"}}"),
+                    signature: None,
+                    signature_ts_ms: None,
+                    headers_redacted_json: None,
+                    relay_channel: Some("local_debug".to_string()),
+                    require_relay_callback_auth: false,
+                    require_webhook_signature: false,
+                },
+            )
+            .expect("ingest")
+        };
+
+        conn.execute(
+            "UPDATE webhook_triggers SET status = 'paused' WHERE id = 'wh_flood'",
+            [],
+        )
+        .expect("pause");
+        for rejected in ["r1", "r2", "r3"] {
+            assert_eq!(deliver(&mut conn, rejected).status, "rejected");
+        }
+        conn.execute(
+            "UPDATE webhook_triggers SET status = 'active' WHERE id = 'wh_flood'",
+            [],
+        )
+        .expect("resume");
+
+        assert_eq!(deliver(&mut conn, "d1").status, "queued");
+        assert_eq!(deliver(&mut conn, "d2").status, "queued");
+        let limited = deliver(&mut conn, "d3");
+        assert_eq!(limited.status, "rate_limited");
+        assert!(limited.run_id.is_none());
+
+        let events =
+            webhook_triggers::list_webhook_trigger_events(&conn, "wh_flood", 10).expect("events");
+        let d3 = events
+            .iter()
+            .find(|e| e.delivery_id == "d3")
+            .expect("d3 event");
+        assert_eq!(d3.status, "rate_limited");
+        assert_eq!(d3.http_status, Some(429));
+        let runs: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM runs WHERE autopilot_id = 'auto_hooks'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("runs");
+        assert_eq!(runs, 2);
+    }
+
+    #[test]
+    fn webhook_payload_schema_rejects_nonconforming_delivery_with_path() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        conn.execute(
+            "INSERT INTO autopilots (id, name, created_at) VALUES ('auto_hooks', 'Hooks', 1)",
+            [],
+        )
+        .expect("autopilot");
+        let plan = AutopilotPlan::from_intent(
+            RecipeKind::DailyBrief,
+            "Summarize webhook events".to_string(),
+            ProviderId::OpenAi,
+        );
+        conn.execute(
+            "INSERT INTO webhook_triggers (
+               id, autopilot_id, status, endpoint_path, plan_json, created_at_ms, updated_at_ms
+             ) VALUES ('wh_shape', 'auto_hooks', 'active', 'hooks/shape', ?1, 1, 1)",
+            rusqlite::params![serde_json::to_string(&plan).expect("plan json")],
+        )
+        .expect("trigger");
+        let saved = update_webhook_trigger_payload_schema_internal(
+            &conn,
+            "wh_shape",
+            Some(webhook_triggers::WebhookPayloadSchema {
+                required: vec![
+                    webhook_triggers::WebhookPayloadField {
+                        path: "event".to_string(),
+                        value_type: Some("string".to_string()),
+                    },
+                    webhook_triggers::WebhookPayloadField {
+                        path: "data".to_string(),
+                        value_type: Some("object".to_string()),
+                    },
+                ],
+            }),
+        )
+        .expect("save schema");
+        assert_eq!(saved.expect("schema").required.len(), 2);
+        let deliver = |conn: &mut rusqlite::Connection, delivery_id: &str, body: &str| {
+            ingest_webhook_event_internal(
+                conn,
+                WebhookIngestInput {
+                    relay_request_id: None,
+                    relay_callback_secret: None,
+                    relay_issued_at_ms: None,
+                    trigger_id: "wh_shape".to_string(),
+                    delivery_id: delivery_id.to_string(),
+                    content_type: "application/json".to_string(),
+                    body_json: body.to_string(),
+                    signature: None,
+                    signature_ts_ms: None,
+                    headers_redacted_json: None,
+                    relay_channel: Some("local_debug".to_string()),
+                    require_relay_callback_auth: false,
+                    require_webhook_signature: false,
+                },
+            )
+            .expect("ingest")
+        };
+
+        let accepted = deliver(
+            &mut conn,
+            "d_ok",
+            r#"{"event":"order.created","data":{"id":1}}"#,
+        );
+        assert_eq!(accepted.status, "queued");
+        assert!(accepted.run_id.is_some());
+
+        let rejected = deliver(&mut conn, "d_bad", r#"{"event":"order.created","data":[]}"#);
+        assert_eq!(rejected.status, "failed_validation");
+        assert!(rejected.message.contains("`data`"));
+        assert!(rejected.run_id.is_none());
+        let events =
+            webhook_triggers::list_webhook_trigger_events(&conn, "wh_shape", 10).expect("events");
+        let bad = events
+            .iter()
+            .find(|e| e.delivery_id == "d_bad")
+            .expect("bad event");
+        assert_eq!(bad.http_status, Some(422));
+
+        update_webhook_trigger_payload_schema_internal(&conn, "wh_shape", None).expect("clear");
+        assert_eq!(
+            deliver(&mut conn, "d_any", r#"{"anything":true}"#).status,
+            "queued"
+        );
+    }
+
+    #[test]
+    fn webhook_run_creation_failure_is_dead_lettered_and_retryable() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        conn.execute(
+            "INSERT INTO autopilots (id, name, created_at) VALUES ('auto_hooks', 'Hooks', 1)",
+            [],
+        )
+        .expect("autopilot");
+        let plan = AutopilotPlan::from_intent(
+            RecipeKind::DailyBrief,
+            "Summarize webhook events".to_string(),
+            ProviderId::OpenAi,
+        );
+        conn.execute(
+            "INSERT INTO webhook_triggers (
+               id, autopilot_id, status, endpoint_path, plan_json, created_at_ms, updated_at_ms
+             ) VALUES ('wh_dlq', 'auto_hooks', 'active', 'hooks/dlq', ?1, 1, 1)",
+            rusqlite::params![serde_json::to_string(&plan).expect("plan json")],
+        )
+        .expect("trigger");
+        conn.execute_batch(
+            "CREATE TRIGGER block_runs BEFORE INSERT ON runs
+             BEGIN SELECT RAISE(ABORT, 'database is locked'); END;",
+        )
+        .expect("block runs");
+
+        let result = ingest_webhook_event_internal(
+            &mut conn,
+            WebhookIngestInput {
+                relay_request_id: None,
+                relay_callback_secret: None,
+                relay_issued_at_ms: None,
+                trigger_id: "wh_dlq".to_string(),
+                delivery_id: "d1".to_string(),
+                content_type: "application/json".to_string(),
+                body_json: "{\"order\":42}".to_string(),
+                signature: None,
+                signature_ts_ms: None,
+                headers_redacted_json: None,
+                relay_channel: Some("local_debug".to_string()),
+                require_relay_callback_auth: false,
+                require_webhook_signature: false,
+            },
+        )
+        .expect("ingest");
+        assert_eq!(result.status, "dead_lettered");
+        let dead_letters =
+            webhook_triggers::list_webhook_dead_letters(&conn, 10).expect("dead letters");
+        assert_eq!(dead_letters.len(), 1);
+        let dead_letter = &dead_letters[0];
+        assert_eq!(dead_letter.status, "pending");
+        assert_eq!(dead_letter.body_json, "{\"order\":42}");
+        assert!(dead_letter.reason.contains("locked"));
+
+        let err = retry_webhook_dead_letter_internal(&mut conn, &dead_letter.id)
+            .expect_err("still blocked");
+        assert!(err.contains("locked"));
+        conn.execute_batch("DROP TRIGGER block_runs;")
+            .expect("unblock runs");
+        let retried =
+            retry_webhook_dead_letter_internal(&mut conn, &dead_letter.id).expect("retry");
+        assert_eq!(retried.status, "queued");
+        let run_id = retried.run_id.expect("run id");
+
+        let dead_letter = webhook_triggers::get_webhook_dead_letter(&conn, &dead_letter.id)
+            .expect("load")
+            .expect("dead letter");
+        assert_eq!(dead_letter.status, "retried");
+        assert_eq!(dead_letter.retry_count, 2);
+        assert_eq!(dead_letter.run_id.as_deref(), Some(run_id.as_str()));
+        let events =
+            webhook_triggers::list_webhook_trigger_events(&conn, "wh_dlq", 10).expect("events");
+        assert_eq!(events[0].status, "queued");
+        assert_eq!(events[0].run_id.as_deref(), Some(run_id.as_str()));
+        assert!(retry_webhook_dead_letter_internal(&mut conn, &dead_letter.id).is_err());
+    }
+
+    #[test]
+    fn relay_ping_history_is_bounded_and_reports_uptime() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        for i in 0..db::RELAY_PING_LOG_MAX_ROWS {
+            db::insert_relay_ping(
+                &conn,
+                &db::RelayPingRecord {
+                    id: format!("ping_{i}"),
+                    reachable: true,
+                    status: "ok".to_string(),
+                    latency_ms: 20,
+                    http_status: Some(200),
+                    message: "ok".to_string(),
+                    created_at_ms: i,
+                },
+            )
+            .expect("seed ping");
+        }
+        let closed_port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+            listener.local_addr().expect("addr").port()
+        };
+        let relay = RelayTransport::new(format!("http://127.0.0.1:{closed_port}/dispatch"));
+
+        let response = ping_relay_internal(&conn, &relay).expect("ping");
+        assert!(!response.latest.reachable);
+        assert_eq!(response.latest.status, "refused");
+        assert_eq!(response.recent.len() as i64, db::RELAY_PING_LOG_MAX_ROWS);
+        assert_eq!(response.recent[0].id, response.latest.id);
+        assert_eq!(response.uptime_percent, 95);
+    }
+
+    #[test]
+    fn send_policy_change_is_audited_without_secret_values() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        conn.execute(
+            "INSERT INTO autopilots (id, name, created_at) VALUES ('auto_send', 'Send', 1)",
+            [],
+        )
+        .expect("autopilot");
+
+        update_autopilot_send_policy_internal(
+            &conn,
+            AutopilotSendPolicyInput {
+                autopilot_id: "auto_send".to_string(),
+                allow_sending: true,
+                recipient_allowlist: vec!["Ops@Example.com".to_string()],
+                max_sends_per_day: 5,
+                quiet_hours_start_local: 22,
+                quiet_hours_end_local: 7,
+                allow_outside_quiet_hours: false,
+                recipient_cooldown_minutes: Some(30),
+                self_send_outcomes: None,
+            },
+        )
+        .expect("update policy");
+
+        let entries = db::list_audit_log(&conn, 10).expect("audit log");
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.actor, "user");
+        assert_eq!(entry.action, "update_autopilot_send_policy");
+        assert_eq!(entry.target, "autopilot:auto_send");
+        let before = entry.before_excerpt.as_deref().expect("before");
+        let after = entry.after_excerpt.as_deref().expect("after");
+        assert!(before.contains("\"allowSending\":false"));
+        assert!(after.contains("\"allowSending\":true"));
+        assert!(after.contains("ops@example.com"));
+
+        // When the audit entry can't be written, the policy change is rolled back with it.
+        conn.execute_batch(
+            "CREATE TEMP TRIGGER fail_audit BEFORE INSERT ON audit_log
+             BEGIN SELECT RAISE(ABORT, 'audit unavailable'); END;",
+        )
+        .expect("trigger");
+        update_autopilot_send_policy_internal(
+            &conn,
+            AutopilotSendPolicyInput {
+                autopilot_id: "auto_send".to_string(),
+                allow_sending: false,
+                recipient_allowlist: Vec::new(),
+                max_sends_per_day: 1,
+                quiet_hours_start_local: 22,
+                quiet_hours_end_local: 7,
+                allow_outside_quiet_hours: false,
+                recipient_cooldown_minutes: None,
+                self_send_outcomes: None,
+            },
+        )
+        .expect_err("audit failure");
+        let kept = db::get_autopilot_send_policy(&conn, "auto_send").expect("policy");
+        assert!(kept.allow_sending);
+        assert_eq!(kept.max_sends_per_day, 5);
+        conn.execute_batch("DROP TRIGGER fail_audit;")
+            .expect("drop trigger");
+
+        let excerpt = db::audit_excerpt(&serde_json::json!({
+            "relayUrl": "https://relay.terminus.run",
+            "callbackSecret": "whsec_live_123",
+            "nested": { "subscriber_token": "tok_456", "apiKeyRef": "crm_prod" },
+        }));
+        assert!(!excerpt.contains("whsec_live_123"));
+        assert!(!excerpt.contains("tok_456"));
+        assert!(excerpt.contains("[redacted]"));
+        assert!(excerpt.contains("relay.terminus.run"));
+    }
+
+    #[test]
+    fn recipe_model_override_accepts_only_known_models() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("db");
+        db::bootstrap_schema(&mut conn).expect("schema");
+        let input = |model: Option<&str>| RecipeModelOverrideInput {
+            provider_kind: "openai".to_string(),
+            recipe_kind: "daily_brief".to_string(),
+            model: model.map(str::to_string),
+        };
+
+        let err = update_recipe_model_override_internal(&conn, input(Some("gpt-4.1-nano")))
+            .expect_err("unpriced model is unknown");
+        assert!(err.contains("Unknown model"));
+
+        db::upsert_provider_pricing(
+            &conn,
+            &db::ProviderPricingRecord {
+                provider_kind: "openai".to_string(),
+                model: "gpt-4.1-nano".to_string(),
+                input_cents_per_1k: 0.01,
+                output_cents_per_1k: 0.04,
+                is_fallback: false,
+                updated_at_ms: 1,
+            },
+        )
+        .expect("pricing");
+        let saved = update_recipe_model_override_internal(&conn, input(Some("gpt-4.1-nano")))
+            .expect("known model");
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].model, "gpt-4.1-nano");
+        assert_eq!(
+            db::get_recipe_model_override(&conn, "openai", "daily_brief").expect("read"),
+            Some("gpt-4.1-nano".to_string())
+        );
+
+        let cleared = update_recipe_model_override_internal(&conn, input(None)).expect("clear");
+        assert!(cleared.is_empty());
+        let audit = db::list_audit_log(&conn, 10).expect("audit");
+        assert_eq!(audit.len(), 2);
+        assert!(audit
+            .iter()
+            .all(|entry| entry.target == "openai:daily_brief"));
+    }
+
+    #[test]
+    fn webhook_endpoint_setup_composes_relay_base_and_path() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        conn.execute(
+            "INSERT INTO autopilots (id, name, created_at) VALUES ('auto_setup', 'Setup', 1)",
+            [],
+        )
+        .expect("autopilot");
+        for (id, path, mode) in [
+            ("wh_setup_terminus", "/hooks/abc123", "terminus_hmac_sha256"),
+            ("wh_setup_github", "hooks/def456", "github_sha256"),
+        ] {
+            conn.execute(
+                "INSERT INTO webhook_triggers (
+                   id, autopilot_id, status, endpoint_path, signature_mode, description,
+                   max_payload_bytes, allowed_content_types_json, plan_json, provider_kind,
+                   created_at_ms, updated_at_ms
+                 ) VALUES (?1, 'auto_setup', 'active', ?2, ?3, '', 65536,
+                           '[\"application/json\"]', '{}', 'openai', 1, 1)",
+                rusqlite::params![id, path, mode],
+            )
+            .expect("trigger");
+        }
+
+        let terminus = webhook_endpoint_setup_internal(
+            &conn,
+            "wh_setup_terminus",
+            "https://relay.example.com/webhooks/",
+        )
+        .expect("setup");
+        assert_eq!(
+            terminus.endpoint_url,
+            "https://relay.example.com/webhooks/hooks/abc123"
+        );
+        assert_eq!(terminus.signature_header, "X-Terminus-Signature");
+        assert_eq!(
+            terminus.timestamp_header.as_deref(),
+            Some("X-Terminus-Timestamp")
+        );
+        assert!(terminus
+            .sample_curl
+            .contains("'https://relay.example.com/webhooks/hooks/abc123'"));
+
+        let github = webhook_endpoint_setup_internal(
+            &conn,
+            "wh_setup_github",
+            "https://relay.example.com/webhooks",
+        )
+        .expect("setup");
+        assert_eq!(
+            github.endpoint_url,
+            "https://relay.example.com/webhooks/hooks/def456"
+        );
+        assert_eq!(github.signature_header, "X-Hub-Signature-256");
+        assert!(github.timestamp_header.is_none());
+        assert!(webhook_endpoint_setup_internal(&conn, "wh_missing", "https://x").is_err());
+    }
+
+    #[test]
+    fn simulated_webhook_signature_validates_without_exposing_secret() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("db");
+        db::bootstrap_schema(&mut conn).expect("schema");
+        conn.execute(
+            "INSERT INTO autopilots (id, name, created_at) VALUES ('auto_sim', 'Sim', 1)",
+            [],
+        )
+        .expect("autopilot");
+        let plan_json = serde_json::to_string(&AutopilotPlan::from_intent(
+            RecipeKind::DailyBrief,
+            "Summarize webhook".to_string(),
+            ProviderId::OpenAi,
+        ))
+        .expect("plan");
+        for (id, mode) in [
+            ("wh_sim_terminus", "terminus_hmac_sha256"),
+            ("wh_sim_stripe", "stripe"),
+            ("wh_sim_github", "github_sha256"),
+        ] {
+            conn.execute(
+                "INSERT INTO webhook_triggers (
+                   id, autopilot_id, status, endpoint_path, signature_mode, description,
+                   max_payload_bytes, allowed_content_types_json, plan_json, provider_kind,
+                   created_at_ms, updated_at_ms
+                 ) VALUES (?1, 'auto_sim', 'active', ?1, ?2, '', 65536, '[\"application/json\"]',
+                           ?3, 'openai', 1, 1)",
+                rusqlite::params![id, mode, plan_json],
+            )
+            .expect("trigger");
+        }
+        let secret = "whsec_simulated_secret";
+        let lookup = |_: &str| Some(secret.to_string());
+        let body = "{\"event\":\"ping\"}";
+        let ts = now_ms();
+
+        let terminus =
+            simulate_webhook_signature_internal(&conn, "wh_sim_terminus", body, ts, &lookup)
+                .expect("terminus");
+        assert_eq!(terminus.signature_ts_ms, Some(ts));
+        validate_webhook_signature(secret, body, &terminus.signature, ts)
+            .expect("terminus signature validates");
+
+        for id in ["wh_sim_stripe", "wh_sim_github"] {
+            let simulated = simulate_webhook_signature_internal(&conn, id, body, ts, &lookup)
+                .expect("simulate");
+            verify_webhook_signature(
+                &simulated.signature_mode,
+                secret,
+                body,
+                &simulated.signature,
+                0,
+            )
+            .expect("signature validates");
+            assert!(!simulated.signature.contains(secret));
+        }
+        assert!(!serde_json::to_string(&terminus)
+            .expect("json")
+            .contains(secret));
+
+        let missing = simulate_webhook_signature_internal(&conn, "wh_unknown", body, ts, &lookup)
+            .expect_err("unknown trigger");
+        assert!(missing.contains("not found"));
+        let no_secret =
+            simulate_webhook_signature_internal(&conn, "wh_sim_github", body, ts, &|_| None)
+                .expect_err("no secret");
+        assert!(no_secret.contains("secret is missing"));
+    }
+
+    #[test]
+    fn webhook_run_preview_uses_trigger_snapshot_without_starting_a_run() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("db");
+        db::bootstrap_schema(&mut conn).expect("schema");
+        conn.execute(
+            "INSERT INTO autopilots (id, name, created_at) VALUES ('auto_preview', 'Preview', 1)",
+            [],
+        )
+        .expect("autopilot");
+        let plan_json = serde_json::to_string(&AutopilotPlan::from_intent(
+            RecipeKind::DailyBrief,
+            "Summarize webhook".to_string(),
+            ProviderId::OpenAi,
+        ))
+        .expect("plan");
+        conn.execute(
+            "INSERT INTO webhook_triggers (
+               id, autopilot_id, status, endpoint_path, signature_mode, description,
+               max_payload_bytes, allowed_content_types_json, plan_json, provider_kind,
+               created_at_ms, updated_at_ms
+             ) VALUES ('wh_preview', 'auto_preview', 'active', 'wh_preview',
+                       'terminus_hmac_sha256', '', 64, '[\"application/json\"]', ?1, 'openai', 1, 1)",
+            rusqlite::params![plan_json],
+        )
+        .expect("trigger");
+
+        let body = "{\"event\":\"order.created\"}";
+        let preview =
+            preview_webhook_run_internal(&conn, "wh_preview", body, 1_000).expect("preview");
+        assert_eq!(preview.trigger_id, "wh_preview");
+        assert_eq!(preview.plan.recipe, RecipeKind::DailyBrief);
+        assert!(preview.plan.intent.contains("[Webhook trigger wh_preview]"));
+        let source = preview
+            .plan
+            .inbox_source_text
+            .as_deref()
+            .unwrap_or_default();
+        assert!(source.contains("order.created"));
+        assert_eq!(preview.plan.event_context, None);
+        assert!(!preview.preview.reads.is_empty());
+        let runs: i64 = conn
+            .query_row("SELECT COUNT(*) FROM runs", [], |row| row.get(0))
+            .expect("runs");
+        assert_eq!(runs, 0);
+
+        let oversized = format!("{{\"event\":\"{}\"}}", "x".repeat(80));
+        assert!(
+            preview_webhook_run_internal(&conn, "wh_preview", &oversized, 1_000)
+                .expect_err("too large")
+                .contains("64 bytes")
+        );
+        assert!(
+            preview_webhook_run_internal(&conn, "wh_preview", "not json", 1_000)
+                .expect_err("invalid json")
+                .contains("valid JSON")
+        );
+        assert!(
+            preview_webhook_run_internal(&conn, "wh_missing", body, 1_000)
+                .expect_err("missing")
+                .contains("not found")
+        );
+    }
+
+    #[test]
+    fn autopilot_context_limits_are_bounded_and_audited() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("db");
+        db::bootstrap_schema(&mut conn).expect("schema");
+        conn.execute(
+            "INSERT INTO autopilots (id, name, created_at) VALUES ('auto_limits', 'Limits', 1)",
+            [],
+        )
+        .expect("autopilot");
+        let input = |chars: Option<i64>, items: Option<i64>| AutopilotContextLimitsInput {
+            autopilot_id: "auto_limits".to_string(),
+            inbox_text_max_chars: chars,
+            daily_source_max_items: items,
+            summarize_overflow: false,
+        };
+
+        let err = update_autopilot_context_limits_internal(&conn, input(Some(500_000), None))
+            .expect_err("above ceiling");
+        assert!(err.contains("Inbox text limit"));
+        let err = update_autopilot_context_limits_internal(&conn, input(None, Some(0)))
+            .expect_err("zero sources");
+        assert!(err.contains("Daily source limit"));
+
+        let saved = update_autopilot_context_limits_internal(&conn, input(Some(50_000), Some(15)))
+            .expect("within bounds");
+        assert_eq!(saved.inbox_text_max_chars, Some(50_000));
+        assert_eq!(saved.daily_source_max_items, Some(15));
+        let audited: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM audit_log WHERE action = 'update_autopilot_context_limits'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("audit");
+        assert_eq!(audited, 1);
+    }
+
+    #[test]
+    fn autopilot_web_tracking_is_validated_and_audited() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("db");
+        db::bootstrap_schema(&mut conn).expect("schema");
+        conn.execute(
+            "INSERT INTO autopilots (id, name, created_at) VALUES ('auto_tracking', 'Tracking', 1)",
+            [],
+        )
+        .expect("autopilot");
+        let input = |params: Option<Vec<&str>>| AutopilotWebTrackingInput {
+            autopilot_id: "auto_tracking".to_string(),
+            tracking_params: params.map(|list| list.into_iter().map(str::to_string).collect()),
+        };
+
+        let defaults = db::get_autopilot_web_tracking(&conn, "auto_tracking").expect("defaults");
+        assert_eq!(defaults.tracking_params, None);
+        let err = update_autopilot_web_tracking_internal(&conn, input(Some(vec!["utm source"])))
+            .expect_err("space");
+        assert!(err.contains("query parameter name"));
+        let err = update_autopilot_web_tracking_internal(&conn, input(Some(vec!["*"])))
+            .expect_err("bare wildcard");
+        assert!(err.contains("query parameter name"));
+
+        let saved = update_autopilot_web_tracking_internal(
+            &conn,
+            input(Some(vec![" Session_Tag ", "ref_*", "session_tag"])),
+        )
+        .expect("valid list");
+        assert_eq!(
+            saved.tracking_params,
+            Some(vec!["session_tag".to_string(), "ref_*".to_string()])
+        );
+        let cleared = update_autopilot_web_tracking_internal(&conn, input(None)).expect("reset");
+        assert_eq!(cleared.tracking_params, None);
+        let audited: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM audit_log WHERE action = 'update_autopilot_web_tracking'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("audit");
+        assert_eq!(audited, 2);
+    }
+
+    #[test]
+    fn webhook_recipe_parses_and_each_run_starts_from_the_trigger_snapshot() {
+        assert_eq!(parse_recipe("webhook"), Ok(RecipeKind::Webhook));
+        let mut conn = rusqlite::Connection::open_in_memory().expect("db");
+        db::bootstrap_schema(&mut conn).expect("schema");
+        let webhook_plan = AutopilotPlan::from_intent(
+            RecipeKind::Webhook,
+            "Summarize each new order".to_string(),
+            ProviderId::OpenAi,
+        );
+        let route = webhook_triggers::WebhookTriggerRouteConfig {
+            trigger_id: "whtrig_recipe".to_string(),
+            autopilot_id: "auto_webhook_recipe".to_string(),
+            status: "active".to_string(),
+            signature_mode: "terminus_hmac_sha256".to_string(),
+            max_payload_bytes: 32_768,
+            max_deliveries_per_minute: 30,
+            allowed_content_types: vec!["application/json".to_string()],
+            plan_json: serde_json::to_string(&webhook_plan).expect("plan json"),
+            provider_kind: "openai".to_string(),
+            payload_schema: None,
+        };
+        let body = r#"{"order_id":"ord_42","total":"19.00"}"#;
+
+        let first =
+            build_webhook_run_plan(&conn, &route, body, &payload_hash(body), 1).expect("plan");
+        assert_eq!(first.recipe, RecipeKind::Webhook);
+        assert_eq!(first.steps.len(), 2);
+        assert_eq!(
+            first.event_context.as_ref().and_then(|v| v.get("order_id")),
+            Some(&serde_json::json!("ord_42"))
+        );
+        assert!(first
+            .inbox_source_text
+            .as_deref()
+            .unwrap_or_default()
+            .contains("whtrig_recipe"));
+        assert!(first.steps[1].requires_approval);
+        RunnerEngine::start_run(
+            &mut conn,
+            "auto_webhook_recipe",
+            first.clone(),
+            "webhook-recipe-first",
+            0,
+        )
+        .expect("first webhook run");
+
+        let second =
+            build_webhook_run_plan(&conn, &route, body, &payload_hash(body), 1).expect("plan");
+        assert_eq!(second.intent, first.intent);
+        assert_eq!(second.inbox_source_text, first.inbox_source_text);
+
+        // A later run of another recipe does not replace the webhook plan a new trigger snapshots.
+        let custom_plan = AutopilotPlan::from_intent(
+            RecipeKind::Custom,
+            "Draft a weekly recap".to_string(),
+            ProviderId::OpenAi,
+        );
+        let custom_run = RunnerEngine::start_run(
+            &mut conn,
+            "auto_webhook_recipe",
+            custom_plan,
+            "webhook-recipe-custom",
+            0,
+        )
+        .expect("later custom run");
+        conn.execute(
+            "UPDATE runs SET updated_at = updated_at + 60000, created_at = created_at + 60000
+             WHERE id = ?1",
+            rusqlite::params![custom_run.id],
+        )
+        .expect("make the custom run the latest");
+        let (snapshot_json, _) =
+            latest_run_plan_snapshot(&conn, "auto_webhook_recipe").expect("snapshot");
+        let snapshot: AutopilotPlan = serde_json::from_str(&snapshot_json).expect("snapshot plan");
+        assert_eq!(snapshot.recipe, RecipeKind::Webhook);
+        assert_eq!(snapshot.intent, first.intent);
+
+        // Without any webhook run the latest plan of any recipe is used.
+        RunnerEngine::start_run(
+            &mut conn,
+            "auto_custom_only",
+            AutopilotPlan::from_intent(
+                RecipeKind::Custom,
+                "Draft a weekly recap".to_string(),
+                ProviderId::OpenAi,
+            ),
+            "custom-only",
+            0,
+        )
+        .expect("custom only run");
+        let (fallback_json, _) =
+            latest_run_plan_snapshot(&conn, "auto_custom_only").expect("fallback");
+        let fallback: AutopilotPlan = serde_json::from_str(&fallback_json).expect("fallback plan");
+        assert_eq!(fallback.recipe, RecipeKind::Custom);
+    }
+
+    #[test]
+    fn approval_channel_policy_rejects_relay_resolution_for_local_only_autopilot() {
+        std::env::set_var("TERMINUS_TRANSPORT", "mock");
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+
+        let mut plan =
+            AutopilotPlan::from_intent(RecipeKind::Custom, "x".to_string(), ProviderId::OpenAi);
+        plan.allowed_primitives = vec![PrimitiveId::WriteOutcomeDraft];
+        plan.steps = vec![PlanStep {
+            id: "step_1".to_string(),
+            label: "Prepare outcome".to_string(),
+            primitive: PrimitiveId::WriteOutcomeDraft,
+            requires_approval: true,
+            risk_tier: RiskTier::Medium,
+        }];
+        let run = RunnerEngine::start_run(&mut conn, "auto_local_only", plan, "idem_channel", 1)
+            .expect("start");
+        RunnerEngine::run_tick(&mut conn, &run.id).expect("tick");
+        let approval_id = RunnerEngine::list_pending_approvals(&conn)
+            .expect("approvals")
+            .into_iter()
+            .find(|a| a.run_id == run.id)
+            .expect("pending approval")
+            .id;
+
+        assert!(update_approval_channel_policy_internal(
+            &conn,
+            ApprovalChannelPolicyInput {
+                autopilot_id: "auto_local_only".to_string(),
+                allowed_channels: vec!["sms".to_string()],
+            },
+        )
+        .expect_err("unknown channel")
+        .contains("Unknown approval channel"));
+        let policy = update_approval_channel_policy_internal(
+            &conn,
+            ApprovalChannelPolicyInput {
+                autopilot_id: "auto_local_only".to_string(),
+                allowed_channels: vec!["local_ui".to_string(), "LOCAL_UI".to_string()],
+            },
+        )
+        .expect("policy");
+        assert_eq!(policy.allowed_channels, vec!["local_ui".to_string()]);
+
+        let err = approve_run_approval_with_context(
+            &mut conn,
+            &approval_id,
+            ApprovalEntryPoint::Relay,
+            Some("relay"),
+            Some("Phone"),
+        )
+        .expect_err("relay rejected");
+        assert!(err.contains("relay"));
+        let err = approve_run_approval_with_context(
+            &mut conn,
+            &approval_id,
+            ApprovalEntryPoint::Relay,
+            Some("local_ui"),
+            Some("Phone"),
+        )
+        .expect_err("a relay caller cannot claim the local channel");
+        assert!(err.contains("relay"));
+        let (status, channel): (String, Option<String>) = conn
+            .query_row(
+                "SELECT status, decided_channel FROM approvals WHERE id = ?1",
+                rusqlite::params![approval_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("approval");
+        assert_eq!(status, "pending");
+        assert_eq!(channel, None);
+
+        approve_run_approval_with_context(
+            &mut conn,
+            &approval_id,
+            ApprovalEntryPoint::LocalUi,
+            Some("local_ui"),
+            Some("User"),
+        )
+        .expect("local approval");
+        let status: String = conn
+            .query_row(
+                "SELECT status FROM approvals WHERE id = ?1",
+                rusqlite::params![approval_id],
+                |row| row.get(0),
+            )
+            .expect("status");
+        assert_eq!(status, "approved");
+        let changes: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM audit_log WHERE action = 'update_approval_channel_policy'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("audit");
+        assert_eq!(changes, 1);
+    }
+
+    #[test]
+    fn email_reply_from_account_owner_resolves_matching_approval() {
+        std::env::set_var("TERMINUS_TRANSPORT", "mock");
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+
+        let mut plan =
+            AutopilotPlan::from_intent(RecipeKind::Custom, "x".to_string(), ProviderId::OpenAi);
+        plan.allowed_primitives = vec![PrimitiveId::WriteOutcomeDraft];
+        plan.steps = vec![PlanStep {
+            id: "step_1".to_string(),
+            label: "Prepare outcome".to_string(),
+            primitive: PrimitiveId::WriteOutcomeDraft,
+            requires_approval: true,
+            risk_tier: RiskTier::Medium,
+        }];
+        let run = RunnerEngine::start_run(&mut conn, "auto_email_reply", plan, "idem_reply", 1)
+            .expect("start");
+        RunnerEngine::run_tick(&mut conn, &run.id).expect("tick");
+        let approval_id = RunnerEngine::list_pending_approvals(&conn)
+            .expect("approvals")
+            .into_iter()
+            .find(|a| a.run_id == run.id)
+            .expect("pending approval")
+            .id;
+
+        let subject = inbox_watcher::approval_reply_subject(&conn, &approval_id).expect("subject");
+        assert_eq!(
+            inbox_watcher::approval_reply_subject(&conn, &approval_id).expect("again"),
+            subject
         );
-        if input.require_relay_callback_auth {
-            let _ = update_relay_webhook_callback_event_status(
-                connection,
-                input.relay_request_id.as_deref().unwrap_or(""),
-                status,
-            );
-        }
-        if let Some(code) = http_status {
-            let _ = connection.execute(
-                "UPDATE webhook_trigger_events SET http_status = ?1 WHERE trigger_id = ?2 AND event_idempotency_key = ?3",
-                rusqlite::params![code, &trigger_id, &event_key],
-            );
-        }
-        Ok(WebhookIngestResult {
-            status: status.to_string(),
-            trigger_id: trigger_id.clone(),
-            delivery_id: delivery_id.clone(),
-            run_id: None,
-            message: reason.to_string(),
-        })
-    };
+        let reply_subject = format!("Re: {subject}");
+        let owner = Some("Owner <owner@example.com>");
 
-    if route.status != "active" {
-        return fail(
-            "rejected",
-            "Webhook trigger is paused. Enable it to accept events.",
-            Some(409),
-        );
+        let spoofed = inbox_watcher::resolve_approval_reply(
+            &mut conn,
+            owner,
+            Some("attacker@example.com"),
+            &reply_subject,
+            "Approve",
+            &resolve_email_reply_approval,
+        )
+        .expect("spoofed");
+        assert_eq!(spoofed, Some("approval_spoofed"));
+        let ordinary = inbox_watcher::resolve_approval_reply(
+            &mut conn,
+            owner,
+            Some("owner@example.com"),
+            "Lunch on Friday?",
+            "Approve",
+            &resolve_email_reply_approval,
+        )
+        .expect("ordinary");
+        assert_eq!(ordinary, None);
+
+        let resolved = inbox_watcher::resolve_approval_reply(
+            &mut conn,
+            owner,
+            Some("OWNER@example.com"),
+            &reply_subject,
+            "Approve.\n\nOn Mon, Terminus wrote: ...",
+            &resolve_email_reply_approval,
+        )
+        .expect("resolved");
+        assert_eq!(resolved, Some("approval_resolved"));
+        let (status, channel, actor): (String, Option<String>, Option<String>) = conn
+            .query_row(
+                "SELECT status, decided_channel, decided_by FROM approvals WHERE id = ?1",
+                rusqlite::params![approval_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .expect("approval");
+        assert_eq!(status, "approved");
+        assert_eq!(channel.as_deref(), Some("email_reply"));
+        assert_eq!(actor.as_deref(), Some("owner@example.com"));
+
+        let replay = inbox_watcher::resolve_approval_reply(
+            &mut conn,
+            owner,
+            Some("owner@example.com"),
+            &reply_subject,
+            "Approve",
+            &resolve_email_reply_approval,
+        )
+        .expect("replay");
+        assert_eq!(replay, Some("approval_unmatched"));
     }
-    if content_type != "application/json"
-        || !route
-            .allowed_content_types
+
+    #[test]
+    fn batch_runs_report_per_item_results_and_stop_at_daily_cap() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let item = |recipe: &str, intent: &str| BatchRunItemInput {
+            autopilot_id: "auto_batch".to_string(),
+            recipe: recipe.to_string(),
+            intent: intent.to_string(),
+            provider: "openai".to_string(),
+        };
+        let mut items = vec![
+            item("inbox_triage", "Sort the vendor thread"),
+            item("not_a_recipe", "Anything"),
+            item("custom", "Needs a plan"),
+        ];
+        for n in 0..6 {
+            items.push(item(
+                "inbox_triage",
+                &format!("simulate_cap_hard batch {n}"),
+            ));
+        }
+
+        let response = start_batch_runs_internal(&mut conn, items.clone()).expect("batch");
+        let statuses = response
+            .items
             .iter()
-            .any(|v| normalize_content_type(v) == content_type)
-    {
-        return fail(
-            "failed_validation",
-            "Unsupported webhook content type. Terminus currently accepts JSON only.",
-            Some(415),
-        );
-    }
-    if body_len <= 0 || body_len > route.max_payload_bytes {
-        return fail(
-            "failed_validation",
-            "Webhook payload is too large for this trigger. Reduce payload size or raise the trigger limit.",
-            Some(413),
+            .map(|r| r.status.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(&statuses[..3], &["started", "failed", "failed"]);
+        assert!(response.items[1]
+            .error
+            .as_deref()
+            .is_some_and(|e| e.contains("Unknown recipe")));
+        let first_skipped = statuses
+            .iter()
+            .position(|s| *s == "skipped_cap")
+            .expect("cap stops the batch");
+        assert!(statuses[first_skipped..]
+            .iter()
+            .all(|s| *s == "skipped_cap"));
+        let started_cost: i64 = response
+            .items
+            .iter()
+            .filter(|r| r.status == "started")
+            .filter_map(|r| r.estimated_cost_usd_cents)
+            .sum();
+        assert!(started_cost <= 500);
+        assert!(
+            started_cost
+                + response.items[first_skipped]
+                    .estimated_cost_usd_cents
+                    .unwrap_or(0)
+                > 500
         );
-    }
-    if serde_json::from_str::<Value>(&body_json).is_err() {
-        return fail(
-            "failed_validation",
-            "Webhook payload must be valid JSON.",
-            Some(400),
+        assert_eq!(
+            response.started_count + response.failed_count + response.skipped_count,
+            items.len()
         );
-    }
-    if input.require_webhook_signature {
-        let secret = providers::keychain::get_webhook_trigger_secret(&trigger_id)
-            .map_err(|e| e.to_string())?
-            .ok_or_else(|| {
-                "Webhook trigger signing secret is missing. Rotate the secret and retry."
-                    .to_string()
-            })?;
-        if let Err(err) = validate_webhook_signature(
-            &secret,
-            &body_json,
-            input.signature.as_deref().unwrap_or(""),
-            input.signature_ts_ms.unwrap_or_default(),
-        ) {
-            return fail("rejected", &err, Some(401));
-        }
-    }
+        let run_count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM runs", [], |row| row.get(0))
+            .expect("count runs");
+        assert_eq!(run_count as usize, response.started_count);
 
-    let plan = build_webhook_run_plan(&route, &body_json, &hash, now)?;
-    let run_idempotency_key = format!("webhook:{}:{}", trigger_id, event_key);
-    let run = RunnerEngine::start_run(
-        connection,
-        &route.autopilot_id,
-        plan,
-        &run_idempotency_key,
-        2,
-    )
-    .map_err(|e| e.to_string())?;
-    insert_webhook_run_activity(
-        connection,
-        &run.id,
-        &trigger_id,
-        &delivery_id,
-        input.relay_channel.as_deref(),
-    );
-    webhook_triggers::update_webhook_trigger_event_status(
-        connection,
-        &trigger_id,
-        &event_key,
-        "queued",
-        None,
-        Some(&run.id),
-    )?;
-    webhook_triggers::touch_webhook_trigger_delivery(connection, &trigger_id, now, None)?;
-    if input.require_relay_callback_auth {
-        update_relay_webhook_callback_event_status(
-            connection,
-            input.relay_request_id.as_deref().unwrap_or(""),
-            "applied",
-        )?;
-    }
-    Ok(WebhookIngestResult {
-        status: "queued".to_string(),
-        trigger_id,
-        delivery_id,
-        run_id: Some(run.id),
-        message: "Webhook accepted and run queued.".to_string(),
-    })
-}
+        let again = start_batch_runs_internal(&mut conn, items).expect("resubmit");
+        assert_eq!(again.items[0].run_id, response.items[0].run_id);
+        let run_count_after: i64 = conn
+            .query_row("SELECT COUNT(*) FROM runs", [], |row| row.get(0))
+            .expect("count runs");
+        assert_eq!(run_count_after, run_count);
 
-fn validate_voice_tone(input: &str) -> Result<String, String> {
-    let value = input.trim().to_ascii_lowercase();
-    match value.as_str() {
-        "professional" | "neutral" | "warm" => Ok(value),
-        _ => Err("Voice tone must be Professional, Neutral, or Warm.".to_string()),
+        assert!(start_batch_runs_internal(&mut conn, Vec::new()).is_err());
+        let oversized = vec![item("inbox_triage", "x"); MAX_BATCH_RUN_ITEMS + 1];
+        assert!(start_batch_runs_internal(&mut conn, oversized).is_err());
     }
-}
 
-fn validate_voice_length(input: &str) -> Result<String, String> {
-    let value = input.trim().to_ascii_lowercase();
-    match value.as_str() {
-        "concise" | "normal" | "detailed" => Ok(value),
-        _ => Err("Voice length must be Concise, Normal, or Detailed.".to_string()),
-    }
-}
+    #[test]
+    fn guidance_preview_matches_what_submit_stores() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let instruction = "  From now on, keep replies under three sentences.  ";
 
-fn validate_voice_humor(input: &str) -> Result<String, String> {
-    let value = input.trim().to_ascii_lowercase();
-    match value.as_str() {
-        "off" | "light" => Ok(value),
-        _ => Err("Voice humor must be Off or Light.".to_string()),
-    }
-}
+        let preview = preview_guidance_internal(instruction).expect("preview");
+        assert_eq!(
+            preview.normalized_instruction,
+            "From now on, keep replies under three sentences."
+        );
+        assert_eq!(preview.mode, GuidanceMode::ProposedRule);
+        let before: i64 = conn
+            .query_row("SELECT COUNT(*) FROM guidance_events", [], |row| row.get(0))
+            .expect("count");
+        assert_eq!(before, 0);
 
-fn sanitize_voice_notes(input: &str) -> String {
-    input
-        .trim()
-        .chars()
-        .take(800)
-        .collect::<String>()
-        .replace('\u{0000}', "")
-}
+        let submitted = submit_guidance_internal(
+            &conn,
+            GuidanceInput {
+                scope_type: "autopilot".to_string(),
+                scope_id: "auto_1".to_string(),
+                instruction: instruction.to_string(),
+            },
+        )
+        .expect("submit");
+        let (mode, stored_instruction, result_json): (String, String, String) = conn
+            .query_row(
+                "SELECT mode, instruction, result_json FROM guidance_events",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .expect("stored guidance");
+        assert_eq!(mode, "proposed_rule");
+        assert_eq!(stored_instruction, preview.normalized_instruction);
+        assert_eq!(submitted.message, preview.message);
+        let stored: serde_json::Value = serde_json::from_str(&result_json).expect("result json");
+        assert_eq!(
+            stored["proposedRule"].as_str(),
+            preview.proposed_rule.as_deref()
+        );
+        assert_eq!(stored["message"].as_str(), Some(preview.message.as_str()));
 
-fn annotate_approval_resolution(
-    connection: &rusqlite::Connection,
-    approval_id: &str,
-    channel: Option<&str>,
-    actor_label: Option<&str>,
-) -> Result<(), String> {
-    let channel = sanitize_approval_resolution_field(channel, 32);
-    let actor = sanitize_approval_resolution_field(actor_label, 80);
-    if channel.is_none() && actor.is_none() {
-        return Ok(());
+        assert!(preview_guidance_internal("   ").is_err());
     }
-    connection
-        .execute(
-            "UPDATE approvals
-             SET decided_channel = COALESCE(?1, decided_channel),
-                 decided_by = COALESCE(?2, decided_by)
-             WHERE id = ?3",
-            rusqlite::params![channel, actor, approval_id],
+
+    #[test]
+    fn custom_triage_label_builds_provider_mutation_and_rejects_unknown_labels() {
+        std::env::set_var("TERMINUS_EMAIL_EFFECTOR", "mock");
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        conn.execute(
+            "INSERT INTO autopilots (id, name, created_at) VALUES ('auto_triage', 'Triage', 1)",
+            [],
         )
-        .map_err(|e| format!("Could not record approval source metadata: {e}"))?;
-    Ok(())
-}
+        .expect("autopilot");
 
-fn approve_run_approval_with_context(
-    connection: &mut rusqlite::Connection,
-    approval_id: &str,
-    channel: Option<&str>,
-    actor_label: Option<&str>,
-) -> Result<RunRecord, String> {
-    annotate_approval_resolution(connection, approval_id, channel, actor_label)?;
-    RunnerEngine::approve(connection, approval_id).map_err(|e| e.to_string())
-}
+        let saved =
+            set_autopilot_triage_label_internal(&conn, "auto_triage", "gmail", Some("receipts"))
+                .expect("save label");
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].label_id, "Label_receipts");
+        assert_eq!(saved[0].label_name, "Receipts");
 
-fn reject_run_approval_with_context(
-    connection: &mut rusqlite::Connection,
-    approval_id: &str,
-    reason: Option<String>,
-    channel: Option<&str>,
-    actor_label: Option<&str>,
-) -> Result<RunRecord, String> {
-    annotate_approval_resolution(connection, approval_id, channel, actor_label)?;
-    RunnerEngine::reject(connection, approval_id, reason).map_err(|e| e.to_string())
-}
+        let action = email_connections::TriageAction::MoveToLabel {
+            label_id: saved[0].label_id.clone(),
+        };
+        let (endpoint, body) = email_connections::build_triage_mutation(
+            email_connections::EmailProvider::Gmail,
+            "msg_1",
+            &action,
+        );
+        assert_eq!(
+            endpoint,
+            "https://gmail.googleapis.com/gmail/v1/users/me/messages/msg_1/modify"
+        );
+        assert_eq!(
+            body,
+            serde_json::json!({ "addLabelIds": ["Label_receipts"], "removeLabelIds": ["INBOX"] })
+        );
+        let (endpoint, body) = email_connections::build_triage_mutation(
+            email_connections::EmailProvider::Microsoft365,
+            "msg_2",
+            &email_connections::TriageAction::MoveToLabel {
+                label_id: "folder_abc".to_string(),
+            },
+        );
+        assert!(endpoint.ends_with("/me/messages/msg_2/move"));
+        assert_eq!(body, serde_json::json!({ "destinationId": "folder_abc" }));
 
-fn parse_recipe(value: &str) -> Result<RecipeKind, String> {
-    match value {
-        "website_monitor" => Ok(RecipeKind::WebsiteMonitor),
-        "inbox_triage" => Ok(RecipeKind::InboxTriage),
-        "daily_brief" => Ok(RecipeKind::DailyBrief),
-        "custom" => Ok(RecipeKind::Custom),
-        _ => Err(format!("Unknown recipe: {value}")),
+        let err =
+            set_autopilot_triage_label_internal(&conn, "auto_triage", "gmail", Some("Travel"))
+                .expect_err("unknown label");
+        assert!(err.contains("not a label or folder"), "{err}");
+        let cleared = set_autopilot_triage_label_internal(&conn, "auto_triage", "gmail", None)
+            .expect("clear");
+        assert!(cleared.is_empty());
     }
-}
 
-fn parse_provider(value: &str) -> Result<ProviderId, String> {
-    match value {
-        "openai" => Ok(ProviderId::OpenAi),
-        "anthropic" => Ok(ProviderId::Anthropic),
-        "gemini" => Ok(ProviderId::Gemini),
-        _ => Err(format!("Unknown provider: {value}")),
+    #[test]
+    fn forced_recipe_overrides_auto_classification() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("db");
+        db::bootstrap_schema(&mut conn).expect("schema");
+        let intent = "Prepare a daily digest from these links";
+        assert_eq!(classify_recipe(intent), RecipeKind::DailyBrief);
+
+        let (auto, _) =
+            draft_plan_for_intent(&conn, intent, ProviderId::OpenAi, None).expect("auto");
+        assert_eq!(auto.recipe, RecipeKind::DailyBrief);
+        let (forced, _) =
+            draft_plan_for_intent(&conn, intent, ProviderId::OpenAi, Some(" website_monitor "))
+                .expect("forced");
+        assert_eq!(forced.recipe, RecipeKind::WebsiteMonitor);
+
+        let err = draft_plan_for_intent(&conn, intent, ProviderId::OpenAi, Some("newsletter"))
+            .expect_err("unknown recipe");
+        assert!(err.contains("Unknown recipe"), "{err}");
     }
-}
 
-fn parse_intent_kind(value: &str) -> Result<IntentDraftKind, String> {
-    match value {
-        "one_off_run" => Ok(IntentDraftKind::OneOffRun),
-        "draft_autopilot" => Ok(IntentDraftKind::DraftAutopilot),
-        _ => Err(format!("Unknown intent kind: {value}")),
+    #[test]
+    fn intent_kind_override_flips_classification_for_matching_phrase() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let intent = "Reconcile ledger against the bank export";
+        let (kind, _) = classify_intent_kind(intent, &[]);
+        assert!(matches!(kind, IntentDraftKind::OneOffRun));
+
+        let overrides =
+            upsert_intent_kind_override_internal(&conn, "  Reconcile Ledger ", "draft_autopilot")
+                .expect("save override");
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides[0].phrase, "reconcile ledger");
+        let (kind, reason) = classify_intent_kind(intent, &overrides);
+        assert!(matches!(kind, IntentDraftKind::DraftAutopilot));
+        assert!(reason.contains("reconcile ledger"));
+
+        let (kind, _) = classify_intent_kind("Check the weekly report", &overrides);
+        assert!(matches!(kind, IntentDraftKind::DraftAutopilot));
+        let (kind, _) = classify_intent_kind("Summarize this article", &overrides);
+        assert!(matches!(kind, IntentDraftKind::OneOffRun));
+        assert!(upsert_intent_kind_override_internal(&conn, "ledger", "sometimes").is_err());
     }
-}
 
-fn classify_intent_kind(intent: &str) -> (IntentDraftKind, String) {
-    let normalized = intent.to_ascii_lowercase();
-    let recurring_hints = [
-        "every",
-        "daily",
-        "weekly",
-        "monitor",
-        "watch",
-        "always",
-        "whenever",
-        "keep an eye",
-    ];
-    let should_recur = recurring_hints.iter().any(|hint| normalized.contains(hint))
-        || normalized.contains("inbox");
+    #[test]
+    fn secret_rotation_warnings_flag_only_stale_secrets() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let day = db::MS_PER_DAY;
+        let now = 400 * day;
+        db::record_api_key_ref_saved(&conn, "crm_old", now - 120 * day).expect("old ref");
+        db::record_api_key_ref_saved(&conn, "crm_fresh", now - 5 * day).expect("fresh ref");
+        conn.execute(
+            "INSERT INTO autopilots (id, name, created_at) VALUES ('auto_hooks', 'Hooks', 1)",
+            [],
+        )
+        .expect("autopilot");
+        for (id, created_at) in [
+            ("wh_ancient", now - 300 * day),
+            ("wh_rotated", now - 300 * day),
+        ] {
+            conn.execute(
+                "INSERT INTO webhook_triggers (id, autopilot_id, status, endpoint_path, created_at_ms, updated_at_ms)
+                 VALUES (?1, 'auto_hooks', 'active', ?1, ?2, ?2)",
+                rusqlite::params![id, created_at],
+            )
+            .expect("trigger");
+        }
+        webhook_triggers::record_webhook_trigger_secret_rotated(&conn, "wh_rotated", now - day)
+            .expect("rotate");
 
-    if should_recur {
-        (
-            IntentDraftKind::DraftAutopilot,
-            "Looks recurring, so Terminus prepared an Autopilot setup.".to_string(),
+        let warnings = collect_secret_rotation_warnings(&conn, None, now).expect("warnings");
+        let names = warnings.iter().map(|w| w.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["wh_ancient", "crm_old"]);
+        assert_eq!(warnings[0].severity, "critical");
+        assert_eq!(warnings[0].autopilot_id.as_deref(), Some("auto_hooks"));
+        assert_eq!(warnings[1].severity, "warning");
+        assert_eq!(warnings[1].age_days, 120);
+
+        let strict = collect_secret_rotation_warnings(&conn, Some(3), now).expect("strict");
+        assert!(strict.iter().any(|w| w.name == "crm_fresh"));
+    }
+
+    #[test]
+    fn app_healthcheck_degrades_when_email_connection_is_unhealthy() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let transport = || TransportStatus {
+            mode: TransportMode::Mock,
+            relay_configured: false,
+            relay_url: String::new(),
+            local_base_url: None,
+        };
+        let now = 1_000_000;
+
+        let healthy = build_app_healthcheck(&conn, transport(), false, now).expect("healthy");
+        assert_eq!(healthy.overall, "ok");
+        assert!(healthy.issues.is_empty());
+
+        conn.execute(
+            "INSERT INTO email_connections (provider, status, account_email, scopes_json, connected_at_ms, updated_at_ms, last_error)
+             VALUES ('gmail', 'connected', 'ops@example.com', '[]', 1, 1, NULL)",
+            [],
         )
-    } else {
-        (
-            IntentDraftKind::OneOffRun,
-            "Looks one-time, so Terminus prepared a one-off Run.".to_string(),
+        .expect("connection");
+        conn.execute(
+            "INSERT INTO inbox_watcher_state (provider, backoff_until_ms, consecutive_failures, last_error, updated_at_ms)
+             VALUES ('gmail', ?1, 3, 'Token expired', 1)",
+            rusqlite::params![now + 60_000],
         )
-    }
-}
+        .expect("watcher state");
 
-fn classify_recipe(intent: &str) -> RecipeKind {
-    let normalized = intent.to_ascii_lowercase();
-    if normalized.contains("inbox")
-        || normalized.contains("email")
-        || normalized.contains("reply")
-        || normalized.contains("triage")
-    {
-        return RecipeKind::InboxTriage;
+        let degraded = build_app_healthcheck(&conn, transport(), false, now).expect("degraded");
+        assert_eq!(degraded.overall, "degraded");
+        assert!(degraded.issues.iter().any(|issue| issue.contains("gmail")));
+
+        let broken = build_app_healthcheck(
+            &conn,
+            TransportStatus {
+                mode: TransportMode::Relay,
+                ..transport()
+            },
+            false,
+            now,
+        )
+        .expect("error");
+        assert_eq!(broken.overall, "error");
     }
-    if normalized.contains("monitor")
-        || normalized.contains("website")
-        || normalized.contains("web page")
-        || normalized.contains("url")
-        || ((normalized.contains("http://") || normalized.contains("https://"))
-            && !normalized.contains("email"))
-    {
-        return RecipeKind::WebsiteMonitor;
+
+    #[test]
+    fn manual_runner_cycle_writes_cycle_log_row() {
+        std::env::set_var("TERMINUS_TRANSPORT", "mock");
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let mut control = db::get_runner_control(&conn).expect("control");
+        control.watcher_enabled = false;
+        db::upsert_runner_control(&conn, &control).expect("pause watcher");
+
+        tick_runner_cycle_internal(&mut conn, false, &runner::NoopProgressSink).expect("cycle");
+
+        let cycles = db::list_runner_cycle_log(&conn, 10).expect("cycles");
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].trigger, "manual");
+        assert_eq!(cycles[0].watcher_status, "paused");
+        assert!(cycles[0].created_at_ms > 0);
     }
-    if normalized.contains("brief")
-        || normalized.contains("summary")
-        || normalized.contains("digest")
-    {
-        return RecipeKind::DailyBrief;
+
+    /// Starts a one-step run paused on approval and returns it with a relayed approve decision.
+    fn paused_run_with_relay_decision(
+        conn: &mut rusqlite::Connection,
+        request_id: &str,
+    ) -> (RunRecord, RelayApprovalDecision) {
+        let mut plan =
+            AutopilotPlan::from_intent(RecipeKind::Custom, "x".to_string(), ProviderId::OpenAi);
+        plan.allowed_primitives = vec![PrimitiveId::WriteOutcomeDraft];
+        plan.steps = vec![PlanStep {
+            id: "step_1".to_string(),
+            label: "Prepare outcome".to_string(),
+            primitive: PrimitiveId::WriteOutcomeDraft,
+            requires_approval: true,
+            risk_tier: RiskTier::Medium,
+        }];
+        let run = RunnerEngine::start_run(
+            conn,
+            "auto_relay_race",
+            plan,
+            &format!("idem_{request_id}"),
+            1,
+        )
+        .expect("start");
+        let paused = RunnerEngine::run_tick(conn, &run.id).expect("tick");
+        assert_eq!(paused.state, runner::RunState::NeedsApproval);
+        let approval_id = RunnerEngine::list_pending_approvals(conn)
+            .expect("approvals")
+            .into_iter()
+            .find(|a| a.run_id == run.id)
+            .expect("pending approval")
+            .id;
+
+        let decision = RelayApprovalDecision {
+            request_id: request_id.to_string(),
+            approval_id,
+            decision: "approve".to_string(),
+            actor_label: Some("Phone".to_string()),
+            channel: Some("relay_callback".to_string()),
+            reason: None,
+            issued_at_ms: now_ms(),
+        };
+        (run, decision)
     }
-    let custom_signals = [
-        "chase",
-        "follow up",
-        "follow-up",
-        "remind",
-        "coordinate",
-        "parse",
-        "categorize",
-        "extract",
-        "prepare",
-        "compile",
-        "collect updates",
-        "generate report",
-        "proposal",
-        "contract",
-        "invoice",
-        "receipt",
-        "every friday",
-        "every monday",
-        "every week",
-        "spreadsheet",
-        "excel",
-        "automate",
-    ];
-    if custom_signals
-        .iter()
-        .any(|signal| normalized.contains(signal))
-    {
-        return RecipeKind::Custom;
+
+    #[derive(Default)]
+    struct CapturingProgressSink {
+        events: std::cell::RefCell<Vec<RunProgressEvent>>,
     }
-    RecipeKind::DailyBrief
-}
 
-#[derive(Debug, Deserialize)]
-struct GeneratedCustomPlan {
-    steps: Vec<GeneratedCustomStep>,
-    #[serde(default)]
-    web_allowed_domains: Vec<String>,
-    #[serde(default)]
-    recipient_hints: Vec<String>,
-    #[serde(default)]
-    allowed_primitives: Vec<String>,
-    #[serde(default)]
-    api_call_request: Option<GeneratedApiCallRequest>,
-}
+    impl RunProgressSink for CapturingProgressSink {
+        fn emit(&self, event: &RunProgressEvent) {
+            self.events.borrow_mut().push(event.clone());
+        }
+    }
 
-#[derive(Debug, Deserialize)]
-struct GeneratedCustomStep {
-    id: String,
-    label: String,
-    primitive: String,
-    requires_approval: bool,
-    risk_tier: String,
-}
+    #[test]
+    fn applied_relay_decision_emits_one_progress_event() {
+        std::env::set_var("TERMINUS_TRANSPORT", "mock");
+        let _keychain = providers::keychain::scoped_test_keychain();
+        providers::keychain::set_relay_callback_secret("cb_secret").expect("callback secret");
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let (run, decision) = paused_run_with_relay_decision(&mut conn, "req_event");
+        let sink = CapturingProgressSink::default();
 
-#[derive(Debug, Deserialize)]
-struct GeneratedApiCallRequest {
-    url: String,
-    #[serde(default)]
-    method: Option<String>,
-    header_key_ref: String,
-    #[serde(default)]
-    auth_header_name: Option<String>,
-    #[serde(default)]
-    auth_scheme: Option<String>,
-    #[serde(default)]
-    body_json: Option<String>,
-}
+        for channel in [
+            RelayDecisionSyncChannel::Push,
+            RelayDecisionSyncChannel::Poll,
+        ] {
+            apply_relay_polled_decision(&mut conn, &decision, "cb_secret", channel, &sink)
+                .expect("apply");
+        }
 
-fn provider_kind_for_schema(provider_id: ProviderId) -> ApiProviderKind {
-    match provider_id {
-        ProviderId::OpenAi => ApiProviderKind::OpenAi,
-        ProviderId::Anthropic => ApiProviderKind::Anthropic,
-        ProviderId::Gemini => ApiProviderKind::Gemini,
+        let events = sink.events.borrow();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].run_id, run.id);
+        let stored = RunnerEngine::get_run(&conn, &run.id).expect("run");
+        assert_eq!(events[0].state, stored.state);
+        assert_ne!(events[0].state, runner::RunState::NeedsApproval);
     }
-}
 
-fn provider_tier_for_schema(provider_id: ProviderId) -> ApiProviderTier {
-    match provider_id {
-        ProviderId::OpenAi | ProviderId::Anthropic => ApiProviderTier::Supported,
-        ProviderId::Gemini => ApiProviderTier::Experimental,
+    #[test]
+    fn relay_poll_and_push_apply_the_same_decision_once() {
+        std::env::set_var("TERMINUS_TRANSPORT", "mock");
+        let _keychain = providers::keychain::scoped_test_keychain();
+        providers::keychain::set_relay_callback_secret("cb_secret").expect("callback secret");
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+
+        let mut plan =
+            AutopilotPlan::from_intent(RecipeKind::Custom, "x".to_string(), ProviderId::OpenAi);
+        plan.allowed_primitives = vec![PrimitiveId::WriteOutcomeDraft];
+        plan.steps = vec![PlanStep {
+            id: "step_1".to_string(),
+            label: "Prepare outcome".to_string(),
+            primitive: PrimitiveId::WriteOutcomeDraft,
+            requires_approval: true,
+            risk_tier: RiskTier::Medium,
+        }];
+        let run = RunnerEngine::start_run(&mut conn, "auto_relay_race", plan, "idem_race", 1)
+            .expect("start");
+        let paused = RunnerEngine::run_tick(&mut conn, &run.id).expect("tick");
+        assert_eq!(paused.state, runner::RunState::NeedsApproval);
+        let approval_id = RunnerEngine::list_pending_approvals(&conn)
+            .expect("approvals")
+            .into_iter()
+            .find(|a| a.run_id == run.id)
+            .expect("pending approval")
+            .id;
+
+        let decision = RelayApprovalDecision {
+            request_id: "req_race".to_string(),
+            approval_id,
+            decision: "approve".to_string(),
+            actor_label: Some("Phone".to_string()),
+            channel: Some("relay_callback".to_string()),
+            reason: None,
+            issued_at_ms: now_ms(),
+        };
+        let push = apply_relay_polled_decision(
+            &mut conn,
+            &decision,
+            "cb_secret",
+            RelayDecisionSyncChannel::Push,
+            &runner::NoopProgressSink,
+        )
+        .expect("push");
+        let poll = apply_relay_polled_decision(
+            &mut conn,
+            &decision,
+            "cb_secret",
+            RelayDecisionSyncChannel::Poll,
+            &runner::NoopProgressSink,
+        )
+        .expect("poll");
+        let push_again = apply_relay_polled_decision(
+            &mut conn,
+            &decision,
+            "cb_secret",
+            RelayDecisionSyncChannel::Push,
+            &runner::NoopProgressSink,
+        )
+        .expect("push again");
+
+        assert!(matches!(push, RelayDecisionApplyOutcome::Applied));
+        assert!(matches!(
+            poll,
+            RelayDecisionApplyOutcome::DedupedByOtherChannel
+        ));
+        assert!(matches!(push_again, RelayDecisionApplyOutcome::NothingToDo));
+        let events = db::list_relay_callback_events(&conn, Some("applied"), 10).expect("events");
+        assert_eq!(events.len(), 1);
+        let approved: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM approvals WHERE run_id = ?1 AND status = 'approved'",
+                rusqlite::params![run.id],
+                |row| row.get(0),
+            )
+            .expect("approved count");
+        assert_eq!(approved, 1);
+    }
+
+    /// Records the batch limit each pull asks for and returns no decisions.
+    #[derive(Default)]
+    struct CapturingDecisionSource {
+        limits: std::sync::Mutex<Vec<usize>>,
+    }
+
+    impl RelayApprovalDecisionSource for CapturingDecisionSource {
+        fn poll_approval_decisions(
+            &self,
+            _token: &str,
+            _device_id: &str,
+            limit: usize,
+        ) -> Result<transport::RelayApprovalPollResponse, providers::types::ProviderError> {
+            self.limits.lock().expect("limits").push(limit);
+            Ok(transport::RelayApprovalPollResponse {
+                decisions: Vec::new(),
+            })
+        }
+
+        fn stream_approval_decisions(
+            &self,
+            token: &str,
+            device_id: &str,
+            limit: usize,
+            _wait_seconds: i64,
+        ) -> Result<transport::RelayApprovalPollResponse, providers::types::ProviderError> {
+            self.poll_approval_decisions(token, device_id, limit)
+        }
     }
-}
 
-fn parse_generated_primitive_id(raw: &str) -> Result<PrimitiveId, String> {
-    let normalized = raw.trim().to_ascii_lowercase().replace(['.', '-'], "_");
-    match normalized.as_str() {
-        "readweb" | "read_web" => Ok(PrimitiveId::ReadWeb),
-        "readsources" | "read_sources" => Ok(PrimitiveId::ReadSources),
-        "readforwardedemail" | "read_forwarded_email" => Ok(PrimitiveId::ReadForwardedEmail),
-        "callapi" | "call_api" => Ok(PrimitiveId::CallApi),
-        "triageemail" | "triage_email" => Ok(PrimitiveId::TriageEmail),
-        "aggregatedailysummary" | "aggregate_daily_summary" => {
-            Ok(PrimitiveId::AggregateDailySummary)
+    /// A relay whose stream endpoint rejects requests; keeps its own capability flag.
+    #[derive(Default)]
+    struct StreamlessDecisionSource {
+        stream_calls: std::sync::Mutex<usize>,
+        poll_calls: std::sync::Mutex<usize>,
+        support: std::sync::Mutex<Option<transport::RelayStreamSupport>>,
+    }
+
+    impl RelayApprovalDecisionSource for StreamlessDecisionSource {
+        fn poll_approval_decisions(
+            &self,
+            _token: &str,
+            _device_id: &str,
+            _limit: usize,
+        ) -> Result<transport::RelayApprovalPollResponse, providers::types::ProviderError> {
+            *self.poll_calls.lock().expect("poll calls") += 1;
+            Ok(transport::RelayApprovalPollResponse {
+                decisions: Vec::new(),
+            })
+        }
+
+        fn stream_approval_decisions(
+            &self,
+            _token: &str,
+            _device_id: &str,
+            _limit: usize,
+            _wait_seconds: i64,
+        ) -> Result<transport::RelayApprovalPollResponse, providers::types::ProviderError> {
+            *self.stream_calls.lock().expect("stream calls") += 1;
+            Err(providers::types::ProviderError::non_retryable(
+                "Terminus relay rejected this request.",
+            ))
+        }
+
+        fn stream_support(&self) -> Option<transport::RelayStreamSupport> {
+            *self.support.lock().expect("support")
+        }
+
+        fn record_stream_support(&self, supported: bool) {
+            *self.support.lock().expect("support") = Some(transport::RelayStreamSupport {
+                supported,
+                probed_at_ms: now_ms(),
+            });
         }
-        "readvaultfile" | "read_vault_file" => Ok(PrimitiveId::ReadVaultFile),
-        "writeoutcomedraft" | "write_outcome_draft" => Ok(PrimitiveId::WriteOutcomeDraft),
-        "writeemaildraft" | "write_email_draft" => Ok(PrimitiveId::WriteEmailDraft),
-        "sendemail" | "send_email" => Ok(PrimitiveId::SendEmail),
-        "schedulerun" | "schedule_run" => Ok(PrimitiveId::ScheduleRun),
-        "notifyuser" | "notify_user" => Ok(PrimitiveId::NotifyUser),
-        _ => Err(format!("Unknown primitive in generated plan: {raw}")),
     }
-}
 
-fn parse_generated_risk_tier(raw: &str) -> Result<RiskTier, String> {
-    match raw.trim().to_ascii_lowercase().as_str() {
-        "low" => Ok(RiskTier::Low),
-        "medium" => Ok(RiskTier::Medium),
-        "high" => Ok(RiskTier::High),
-        _ => Err(format!("Unknown risk tier in generated plan: {raw}")),
-    }
-}
+    #[test]
+    fn push_sync_caches_unsupported_stream_and_falls_back_to_poll() {
+        let _keychain = providers::keychain::scoped_test_keychain();
+        providers::keychain::set_relay_subscriber_token("relay_token").expect("relay token");
+        providers::keychain::set_relay_callback_secret("cb_secret").expect("callback secret");
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let source = StreamlessDecisionSource::default();
+
+        let first = tick_relay_approval_sync_with_source(
+            &mut conn,
+            true,
+            RelayDecisionSyncChannel::Push,
+            &source,
+            &runner::NoopProgressSink,
+        )
+        .expect("first push tick");
+        assert!(first.status.last_error.is_none());
+        assert_eq!(*source.stream_calls.lock().expect("stream calls"), 1);
+        assert_eq!(*source.poll_calls.lock().expect("poll calls"), 1);
+        assert!(source
+            .stream_support()
+            .is_some_and(|support| !support.supported));
+
+        tick_relay_approval_sync_with_source(
+            &mut conn,
+            true,
+            RelayDecisionSyncChannel::Push,
+            &source,
+            &runner::NoopProgressSink,
+        )
+        .expect("second push tick");
+        assert_eq!(*source.stream_calls.lock().expect("stream calls"), 1);
+        assert_eq!(*source.poll_calls.lock().expect("poll calls"), 2);
+
+        *source.support.lock().expect("support") = Some(transport::RelayStreamSupport {
+            supported: false,
+            probed_at_ms: now_ms() - RELAY_STREAM_REPROBE_MS - 1,
+        });
+        tick_relay_approval_sync_with_source(
+            &mut conn,
+            true,
+            RelayDecisionSyncChannel::Push,
+            &source,
+            &runner::NoopProgressSink,
+        )
+        .expect("re-probe tick");
+        assert_eq!(*source.stream_calls.lock().expect("stream calls"), 2);
+        assert_eq!(*source.poll_calls.lock().expect("poll calls"), 3);
+    }
+
+    #[test]
+    fn relay_sync_passes_configured_batch_size_to_transport() {
+        let _keychain = providers::keychain::scoped_test_keychain();
+        providers::keychain::set_relay_subscriber_token("relay_token").expect("relay token");
+        providers::keychain::set_relay_callback_secret("cb_secret").expect("callback secret");
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        assert_eq!(
+            db::get_runner_control(&conn)
+                .expect("control")
+                .relay_sync_batch_size,
+            20
+        );
+
+        let source = CapturingDecisionSource::default();
+        tick_relay_approval_sync_with_source(
+            &mut conn,
+            true,
+            RelayDecisionSyncChannel::Poll,
+            &source,
+            &runner::NoopProgressSink,
+        )
+        .expect("default tick");
+
+        let mut control = db::get_runner_control(&conn).expect("control");
+        control.relay_sync_batch_size = 75;
+        db::upsert_runner_control(&conn, &control).expect("update control");
+        tick_relay_approval_sync_with_source(
+            &mut conn,
+            true,
+            RelayDecisionSyncChannel::Push,
+            &source,
+            &runner::NoopProgressSink,
+        )
+        .expect("configured tick");
 
-fn sanitize_api_key_ref_name(raw: &str) -> Result<String, String> {
-    let cleaned = raw
-        .trim()
-        .chars()
-        .take(64)
-        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
-        .collect::<String>();
-    if cleaned.is_empty() {
-        return Err("API key reference name is required.".to_string());
-    }
-    Ok(cleaned)
-}
+        control.relay_sync_batch_size = 500;
+        db::upsert_runner_control(&conn, &control).expect("clamped control");
+        tick_relay_approval_sync_with_source(
+            &mut conn,
+            true,
+            RelayDecisionSyncChannel::Poll,
+            &source,
+            &runner::NoopProgressSink,
+        )
+        .expect("clamped tick");
 
-fn normalize_api_call_method(raw: &str) -> Result<String, String> {
-    let method = raw.trim().to_ascii_uppercase();
-    match method.as_str() {
-        "GET" | "POST" => Ok(method),
-        _ => Err("CallApi method must be GET or POST in MVP.".to_string()),
+        assert_eq!(*source.limits.lock().expect("limits"), vec![20, 75, 100]);
     }
-}
 
-fn normalize_auth_header_name(raw: &str) -> Result<String, String> {
-    let cleaned = raw
-        .trim()
-        .chars()
-        .take(48)
-        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
-        .collect::<String>();
-    if cleaned.is_empty() {
-        return Err("CallApi auth header name is invalid.".to_string());
-    }
-    Ok(cleaned)
-}
+    #[test]
+    fn relay_timeout_settings_feed_transport_policy() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let mut control = db::get_runner_control(&conn).expect("control");
+        assert_eq!(
+            relay_timeout_policy(&control),
+            RelayTimeoutPolicy::default()
+        );
 
-fn normalize_auth_scheme(raw: &str) -> Result<String, String> {
-    let value = raw.trim().to_ascii_lowercase();
-    match value.as_str() {
-        "bearer" | "raw" => Ok(value),
-        _ => Err("CallApi auth scheme must be bearer or raw in MVP.".to_string()),
+        control.relay_connect_timeout_seconds = 4;
+        control.relay_read_timeout_seconds = 500;
+        control.relay_max_retries = 9;
+        db::upsert_runner_control(&conn, &control).expect("update control");
+        let policy = relay_timeout_policy(&db::get_runner_control(&conn).expect("control"));
+        assert_eq!(policy.connect_timeout_seconds, 4);
+        assert_eq!(
+            policy.read_timeout_seconds,
+            db::RELAY_READ_TIMEOUT_SECONDS_MAX
+        );
+        assert_eq!(policy.max_retries, db::RELAY_MAX_RETRIES_LIMIT as u32);
     }
-}
 
-fn validate_api_call_request_config(
-    config: ApiCallRequest,
-    allowlisted_domains: &mut Vec<String>,
-) -> Result<ApiCallRequest, String> {
-    let url = config.url.trim().to_string();
-    let (scheme, host) = crate::web::parse_scheme_host(&url)
-        .ok_or_else(|| "CallApi URL must be a valid HTTP/HTTPS URL.".to_string())?;
-    if scheme != "http" && scheme != "https" {
-        return Err("CallApi URL must use HTTP or HTTPS.".to_string());
-    }
-    if !allowlisted_domains
-        .iter()
-        .any(|d| d.eq_ignore_ascii_case(&host))
-    {
-        allowlisted_domains.push(host.to_ascii_lowercase());
-    }
-    let method = normalize_api_call_method(&config.method)?;
-    let header_key_ref = sanitize_api_key_ref_name(&config.header_key_ref)?;
-    let auth_header_name = normalize_auth_header_name(&config.auth_header_name)?;
-    let auth_scheme = normalize_auth_scheme(&config.auth_scheme)?;
-    let body_json = config
-        .body_json
-        .as_deref()
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .map(|s| {
-            if s.len() > 8_000 {
-                return Err("CallApi request body is too large for MVP.".to_string());
-            }
-            serde_json::from_str::<serde_json::Value>(s)
-                .map_err(|_| "CallApi request body must be valid JSON.".to_string())?;
-            Ok(s.to_string())
-        })
-        .transpose()?;
-    if method == "GET" && body_json.is_some() {
-        return Err("CallApi GET requests cannot include a JSON body in MVP.".to_string());
-    }
-    Ok(ApiCallRequest {
-        url,
-        method,
-        header_key_ref,
-        auth_header_name,
-        auth_scheme,
-        body_json,
-    })
-}
+    #[test]
+    fn reset_relay_sync_state_lets_next_background_tick_run_despite_backoff() {
+        let _keychain = providers::keychain::scoped_test_keychain();
+        providers::keychain::set_relay_subscriber_token("relay_token").expect("relay token");
+        providers::keychain::set_relay_callback_secret("cb_secret").expect("callback secret");
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let channel = RelayDecisionSyncChannel::parse(" Push ").expect("channel");
+        assert!(RelayDecisionSyncChannel::parse("sms").is_err());
+
+        let stuck = RelaySyncStateRow {
+            consecutive_failures: 4,
+            backoff_until_ms: Some(now_ms() + 300_000),
+            last_error: Some("Relay is temporarily unavailable.".to_string()),
+            total_processed_count: 7,
+            ..RelaySyncStateRow::default()
+        };
+        persist_relay_sync_state(&conn, channel, &stuck, now_ms()).expect("seed backoff");
 
-fn generated_api_call_to_schema(
-    generated: GeneratedApiCallRequest,
-    allowlisted_domains: &mut Vec<String>,
-) -> Result<ApiCallRequest, String> {
-    validate_api_call_request_config(
-        ApiCallRequest {
-            url: generated.url,
-            method: generated.method.unwrap_or_else(|| "GET".to_string()),
-            header_key_ref: generated.header_key_ref,
-            auth_header_name: generated
-                .auth_header_name
-                .unwrap_or_else(|| "Authorization".to_string()),
-            auth_scheme: generated
-                .auth_scheme
-                .unwrap_or_else(|| "bearer".to_string()),
-            body_json: generated.body_json,
-        },
-        allowlisted_domains,
-    )
-}
+        let source = CapturingDecisionSource::default();
+        tick_relay_approval_sync_with_source(
+            &mut conn,
+            false,
+            channel,
+            &source,
+            &runner::NoopProgressSink,
+        )
+        .expect("backoff tick");
+        assert!(source.limits.lock().expect("limits").is_empty());
 
-fn validate_custom_execution_plan(
-    mut plan: AutopilotPlan,
-    provider_id: ProviderId,
-) -> Result<AutopilotPlan, String> {
-    if plan.recipe != RecipeKind::Custom {
-        return Err("Custom plan payload must use recipe=custom.".to_string());
-    }
-    if plan.steps.is_empty() {
-        return Err("Custom plan must include at least one step.".to_string());
-    }
-    if plan.steps.len() > 10 {
-        return Err("Custom plan exceeds the maximum of 10 steps.".to_string());
-    }
-    if plan
-        .steps
-        .iter()
-        .any(|s| s.id.trim().is_empty() || s.label.trim().is_empty())
-    {
-        return Err("Every custom plan step needs an id and label.".to_string());
+        reset_relay_sync_state_internal(&conn, channel).expect("reset");
+        let cleared = load_relay_sync_state(&conn, channel).expect("state");
+        assert_eq!(cleared.consecutive_failures, 0);
+        assert!(cleared.backoff_until_ms.is_none());
+        assert!(cleared.last_error.is_none());
+        assert_eq!(cleared.total_processed_count, 7);
+
+        let ticked = tick_relay_approval_sync_with_source(
+            &mut conn,
+            false,
+            channel,
+            &source,
+            &runner::NoopProgressSink,
+        )
+        .expect("tick after reset");
+        assert_eq!(source.limits.lock().expect("limits").len(), 1);
+        assert_eq!(ticked.status.status, "ready");
     }
 
-    let mut used = Vec::<PrimitiveId>::new();
-    for step in &mut plan.steps {
-        match step.primitive {
-            PrimitiveId::CallApi => {
-                step.requires_approval = true;
-                step.risk_tier = RiskTier::High;
-            }
-            PrimitiveId::SendEmail => {
-                step.requires_approval = true;
-                step.risk_tier = RiskTier::High;
-            }
-            PrimitiveId::WriteOutcomeDraft
-            | PrimitiveId::WriteEmailDraft
-            | PrimitiveId::TriageEmail => {
-                step.requires_approval = true;
-                if step.risk_tier == RiskTier::Low {
-                    step.risk_tier = RiskTier::Medium;
-                }
-            }
-            PrimitiveId::ScheduleRun | PrimitiveId::ReadVaultFile => {
-                return Err(format!(
-                    "This action isn't allowed in Terminus yet: {}.",
-                    step.label
-                ));
-            }
-            _ => {}
-        }
-        if !used.contains(&step.primitive) {
-            used.push(step.primitive);
-        }
+    #[test]
+    fn relay_callback_events_filter_by_status() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        reserve_relay_callback_event(
+            &conn,
+            "req_applied",
+            "approval_1",
+            "Approve",
+            Some("relay_callback"),
+            Some("Phone"),
+            "callback",
+        )
+        .expect("first event");
+        update_relay_callback_event_status(&conn, "req_applied", "applied").expect("applied");
+        reserve_relay_callback_event(
+            &conn,
+            "req_stuck",
+            "approval_2",
+            "reject",
+            None,
+            None,
+            "poll",
+        )
+        .expect("second event");
+
+        let all = db::list_relay_callback_events(&conn, None, 10).expect("all");
+        assert_eq!(all.len(), 2);
+
+        let applied = db::list_relay_callback_events(&conn, Some("applied"), 10).expect("applied");
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].request_id, "req_applied");
+        assert_eq!(applied[0].approval_id, "approval_1");
+        assert_eq!(applied[0].decision, "approve");
+        assert_eq!(applied[0].channel.as_deref(), Some("relay_callback"));
+        assert_eq!(applied[0].actor_label.as_deref(), Some("Phone"));
+
+        let received =
+            db::list_relay_callback_events(&conn, Some(" Received "), 10).expect("received");
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].request_id, "req_stuck");
+
+        let err = db::list_relay_callback_events(&conn, Some("done"), 10).expect_err("invalid");
+        assert!(err.contains("Unknown relay callback status"));
     }
-    plan.allowed_primitives = used;
 
-    plan.provider = schema::ProviderMetadata::from_provider_id(provider_id);
-    plan.web_allowed_domains = plan
-        .web_allowed_domains
-        .into_iter()
-        .map(|d| d.trim().trim_matches('.').to_ascii_lowercase())
-        .filter(|d| !d.is_empty())
-        .collect();
-    if let Some(url) = plan.web_source_url.clone() {
-        if let Some((_, host)) = crate::web::parse_scheme_host(&url) {
-            if !plan
-                .web_allowed_domains
-                .iter()
-                .any(|existing| existing.eq_ignore_ascii_case(&host))
-            {
-                plan.web_allowed_domains.push(host);
-            }
-        }
+    #[test]
+    fn microsoft_watcher_is_skipped_within_its_poll_interval() {
+        std::env::set_var("TERMINUS_TRANSPORT", "mock");
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        conn.execute(
+            "INSERT INTO email_connections (provider, status, account_email, scopes_json, connected_at_ms, updated_at_ms, last_error)
+             VALUES ('microsoft365', 'connected', 'ops@example.com', '[]', 1, 1, NULL)",
+            [],
+        )
+        .expect("connection");
+        let control = db::get_runner_control(&conn).expect("control");
+        let interval_ms = control.watcher_poll_seconds * 1000;
+        let recent = now_ms() - interval_ms / 2;
+        inbox_watcher::record_provider_poll(
+            &conn,
+            email_connections::EmailProvider::Microsoft365,
+            recent,
+        )
+        .expect("record poll");
+
+        let mut summary = RunnerCycleSummary::skipped("idle");
+        run_watchers(&mut conn, &control, &mut summary, true).expect("watchers");
+        assert_eq!(summary.providers_polled, 1);
+        assert_eq!(summary.fetched, 0);
+        let last_poll: Option<i64> = conn
+            .query_row(
+                "SELECT last_poll_at_ms FROM inbox_watcher_state WHERE provider = 'microsoft365'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("state");
+        assert_eq!(last_poll, Some(recent));
+
+        inbox_watcher::record_provider_poll(
+            &conn,
+            email_connections::EmailProvider::Microsoft365,
+            recent - interval_ms,
+        )
+        .expect("age poll");
+        let cycle_started = now_ms();
+        run_watchers(&mut conn, &control, &mut summary, true).expect("watchers");
+        assert_eq!(summary.providers_polled, 2);
+        let last_poll: i64 = conn
+            .query_row(
+                "SELECT last_poll_at_ms FROM inbox_watcher_state WHERE provider = 'microsoft365'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("state");
+        assert!(last_poll >= cycle_started && last_poll <= now_ms());
     }
-    for source in &plan.daily_sources {
-        if let Some((_, host)) = crate::web::parse_scheme_host(source) {
-            if !plan
-                .web_allowed_domains
-                .iter()
-                .any(|existing| existing.eq_ignore_ascii_case(&host))
-            {
-                plan.web_allowed_domains.push(host);
-            }
+
+    #[test]
+    fn configured_catch_up_cap_bounds_catch_up_cycles() {
+        std::env::set_var("TERMINUS_TRANSPORT", "mock");
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+
+        for (cap, expected) in [(2, 2), (0, 0), (10, 7)] {
+            let mut control = db::get_runner_control(&conn).expect("control");
+            control.max_catch_up_cycles = cap;
+            control.watcher_last_tick_ms =
+                Some(now_ms() - 8 * control.watcher_poll_seconds * 1000 - 500);
+            db::upsert_runner_control(&conn, &control).expect("control");
+
+            let summary = tick_runner_cycle_internal(&mut conn, false, &runner::NoopProgressSink)
+                .expect("cycle");
+            assert_eq!(summary.watcher_status, "ran");
+            assert_eq!(summary.missed_runs_detected, 7, "cap {cap}");
+            assert_eq!(summary.catch_up_cycles_run, expected, "cap {cap}");
         }
     }
-    if let Some(config) = plan.api_call_request.clone() {
-        plan.api_call_request = Some(validate_api_call_request_config(
-            config,
-            &mut plan.web_allowed_domains,
-        )?);
-    }
-    plan.recipient_hints = plan
-        .recipient_hints
-        .into_iter()
-        .map(|e| e.trim().to_ascii_lowercase())
-        .filter(|e| e.contains('@'))
-        .collect();
 
-    if plan
-        .steps
-        .iter()
-        .any(|s| s.primitive == PrimitiveId::ReadWeb)
-        && (plan.web_source_url.as_deref().unwrap_or("").is_empty()
-            || plan.web_allowed_domains.is_empty())
-    {
-        return Err(
-            "Custom plan reads a website but has no allowed domains. Add a website domain and retry."
-                .to_string(),
-        );
+    #[test]
+    fn globally_paused_background_cycle_does_nothing() {
+        std::env::set_var("TERMINUS_TRANSPORT", "mock");
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let mut control = db::get_runner_control(&conn).expect("control");
+        control.background_enabled = true;
+        db::upsert_runner_control(&conn, &control).expect("enable background");
+        db::set_global_pause(&conn, true, 1).expect("pause");
+
+        let summary =
+            tick_runner_cycle_internal(&mut conn, true, &runner::NoopProgressSink).expect("cycle");
+        assert_eq!(summary.watcher_status, "globally_paused");
+        assert_eq!(summary.relay_sync_status, "globally_paused");
+        let after = db::get_runner_control(&conn).expect("control");
+        assert_eq!(after.watcher_last_tick_ms, None);
+        assert!(db::list_runner_cycle_log(&conn, 10)
+            .expect("log")
+            .is_empty());
+
+        let manual =
+            tick_runner_cycle_internal(&mut conn, false, &runner::NoopProgressSink).expect("cycle");
+        assert_eq!(manual.watcher_status, "ran");
     }
-    if plan
-        .steps
-        .iter()
-        .any(|s| s.primitive == PrimitiveId::ReadSources)
-        && plan.daily_sources.is_empty()
-    {
-        return Err(
-            "Custom plan reads sources but no source URLs were detected. Add source URLs and retry."
-                .to_string(),
+
+    #[test]
+    fn repeated_custom_plan_draft_within_ttl_reuses_cached_plan() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("db");
+        db::bootstrap_schema(&mut conn).expect("schema");
+        std::env::set_var("TERMINUS_TRANSPORT", "mock");
+        let mock = std::sync::Arc::new(
+            transport::MockTransport::builder()
+                .respond(r#"{"steps":[{"id":"step_1","label":"Summarize receipts","primitive":"write_outcome_draft","requires_approval":true,"risk_tier":"medium"}],"web_allowed_domains":[],"recipient_hints":[],"allowed_primitives":["write_outcome_draft"]}"#)
+                .build(),
         );
+        let _scope = providers::runtime::scoped_mock_transport(mock.clone());
+        let intent = "Categorize forwarded receipts for the plan cache test";
+
+        let first = generate_custom_plan(&conn, intent, ProviderId::OpenAi).expect("first draft");
+        let second = generate_custom_plan(&conn, intent, ProviderId::OpenAi).expect("second draft");
+        assert_eq!(first, second);
+        assert_eq!(mock.received_requests().len(), 1);
+
+        let key = custom_plan_cache_key(intent, ProviderId::OpenAi);
+        assert!(cached_custom_plan(&key, now_ms() + CUSTOM_PLAN_CACHE_TTL_MS + 1).is_none());
+        generate_custom_plan(&conn, intent, ProviderId::OpenAi).expect("regenerated draft");
+        assert_eq!(mock.received_requests().len(), 2);
     }
-    if plan
-        .steps
-        .iter()
-        .any(|s| s.primitive == PrimitiveId::CallApi)
-        && plan.api_call_request.is_none()
-    {
-        return Err(
-            "Custom plan calls an API but has no API request configuration. Add URL and key ref and retry."
-                .to_string(),
+
+    #[test]
+    fn provider_error_drafts_degraded_template_plan() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("db");
+        db::bootstrap_schema(&mut conn).expect("schema");
+        std::env::set_var("TERMINUS_TRANSPORT", "mock");
+        let mock = std::sync::Arc::new(
+            transport::MockTransport::builder()
+                .fail_non_retryable("Provider is unavailable.")
+                .respond("not json")
+                .build(),
         );
-    }
-    if plan
-        .steps
-        .iter()
-        .any(|s| s.primitive == PrimitiveId::SendEmail)
-        && plan.recipient_hints.is_empty()
-    {
-        return Err(
-            "Custom plan sends email but has no recipient hints. Add at least one email recipient and retry."
-                .to_string(),
+        let _scope = providers::runtime::scoped_mock_transport(mock.clone());
+
+        let (plan, degraded) = draft_plan_for_intent(
+            &conn,
+            "Reconcile vendor statements for the degraded draft test",
+            ProviderId::OpenAi,
+            Some("custom"),
+        )
+        .expect("degraded draft instead of an error");
+        assert!(degraded);
+        assert_eq!(plan.recipe, RecipeKind::Custom);
+        assert_eq!(
+            plan.steps.iter().map(|s| s.primitive).collect::<Vec<_>>(),
+            vec![PrimitiveId::ReadForwardedEmail, PrimitiveId::NotifyUser]
         );
-    }
-    Ok(plan)
-}
+        let with_url = template_custom_plan(
+            "Summarize https://status.example.com each morning",
+            ProviderId::OpenAi,
+        )
+        .expect("template");
+        assert_eq!(with_url.steps[0].primitive, PrimitiveId::ReadSources);
+        assert!(plan.steps.iter().all(|s| s.requires_approval));
 
-fn validate_and_build_custom_plan(
-    intent: &str,
-    provider_id: ProviderId,
-    generated: GeneratedCustomPlan,
-) -> Result<AutopilotPlan, String> {
-    if generated.steps.is_empty() {
-        return Err("Generated plan had no steps. Try a more specific request.".to_string());
+        let err = draft_plan_for_intent(
+            &conn,
+            "Reconcile vendor statements for the invalid reply test",
+            ProviderId::OpenAi,
+            Some("custom"),
+        )
+        .expect_err("an invalid reply is not masked");
+        assert!(err.contains("invalid JSON"), "{err}");
     }
-    if generated.steps.len() > 10 {
-        return Err("Generated plan exceeded the maximum of 10 steps.".to_string());
+
+    #[test]
+    fn custom_plan_request_carries_instructions_as_system_prompt() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("db");
+        db::bootstrap_schema(&mut conn).expect("schema");
+        std::env::set_var("TERMINUS_TRANSPORT", "mock");
+        let mock = std::sync::Arc::new(transport::MockTransport::new());
+        let _scope = providers::runtime::scoped_mock_transport(mock.clone());
+        let intent = "Parse this invoice for the system prompt test";
+
+        generate_custom_plan_uncached(&conn, intent, ProviderId::OpenAi).expect("plan");
+
+        let received = mock.received_requests();
+        assert_eq!(received.len(), 1);
+        let system_prompt = received[0].system_prompt.as_deref().expect("system prompt");
+        assert!(system_prompt.starts_with("Generate a Terminus execution plan"));
+        assert!(system_prompt.contains(r#"{"steps":[{"id""#));
+        assert!(system_prompt.contains(r#"api_call_requests keyed by step id: {"step_1":{...}"#));
+        assert!(!system_prompt.contains("{{"));
+        assert!(!system_prompt.contains(intent));
+        assert_eq!(received[0].input, format!("Intent: {intent}"));
     }
 
-    let mut used_primitives = Vec::<PrimitiveId>::new();
-    let mut steps = Vec::<PlanStep>::new();
-    for (index, generated_step) in generated.steps.iter().enumerate() {
-        let primitive = parse_generated_primitive_id(&generated_step.primitive)?;
-        let mut risk_tier = parse_generated_risk_tier(&generated_step.risk_tier)?;
-        let mut requires_approval = generated_step.requires_approval;
-        match primitive {
-            PrimitiveId::CallApi => {
-                requires_approval = true;
-                risk_tier = RiskTier::High;
-            }
-            PrimitiveId::SendEmail => {
-                requires_approval = true;
-                risk_tier = RiskTier::High;
-            }
-            PrimitiveId::WriteOutcomeDraft
-            | PrimitiveId::WriteEmailDraft
-            | PrimitiveId::TriageEmail => {
-                requires_approval = true;
-                if risk_tier == RiskTier::Low {
-                    risk_tier = RiskTier::Medium;
-                }
-            }
-            PrimitiveId::ScheduleRun | PrimitiveId::ReadVaultFile => {
-                return Err("This action isn't allowed in Terminus yet.".to_string())
-            }
-            _ => {}
-        }
-        if !used_primitives.contains(&primitive) {
-            used_primitives.push(primitive);
+    #[test]
+    fn onboarding_recommendation_maps_role_and_pain_to_recipe() {
+        let cases = [
+            (
+                "Founder",
+                "Sales",
+                "My inbox is out of control",
+                RecipeKind::InboxTriage,
+            ),
+            (
+                "Product marketer",
+                "Competitor pricing",
+                "I miss when rivals change plans",
+                RecipeKind::WebsiteMonitor,
+            ),
+            (
+                "Analyst",
+                "Market research",
+                "Too many tabs to read",
+                RecipeKind::DailyBrief,
+            ),
+            (
+                "Finance lead",
+                "Month-end close",
+                "Chasing every invoice by hand",
+                RecipeKind::Custom,
+            ),
+            ("Ops manager", "", "", RecipeKind::DailyBrief),
+        ];
+        for (role, focus, pain, expected) in cases {
+            let recommendation = recommend_onboarding_intent(role, focus, pain);
+            assert_eq!(recommendation.recipe, expected, "{role} / {focus} / {pain}");
+            assert!(recommendation.intent.contains(pain.trim()));
         }
-        steps.push(PlanStep {
-            id: if generated_step.id.trim().is_empty() {
-                format!("step_{}", index + 1)
-            } else {
-                generated_step.id.trim().to_string()
-            },
-            label: generated_step.label.trim().to_string(),
-            primitive,
-            requires_approval,
-            risk_tier,
-        });
+        assert!(recommend_onboarding_intent("Ops manager", "", "")
+            .intent
+            .starts_with("Every weekday morning"));
     }
 
-    let inferred_urls = intent
-        .split_whitespace()
-        .filter_map(|token| {
-            let normalized = token.trim_matches(|c: char| ",.;:!?()[]{}<>\"'".contains(c));
-            if normalized.starts_with("http://") || normalized.starts_with("https://") {
-                Some(normalized.to_string())
-            } else {
-                None
-            }
-        })
-        .collect::<Vec<String>>();
-    let web_source_url = inferred_urls.first().cloned();
-    let daily_sources = inferred_urls
-        .iter()
-        .take(5)
-        .cloned()
-        .collect::<Vec<String>>();
-
-    let mut web_allowed_domains = generated.web_allowed_domains;
-    let api_call_request = generated
-        .api_call_request
-        .map(|cfg| generated_api_call_to_schema(cfg, &mut web_allowed_domains))
-        .transpose()?;
+    #[test]
+    fn onboarding_recommendation_is_stored_in_onboarding_state() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open memory db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let mut current = db::get_onboarding_state(&conn).expect("state");
+        current.role_text = "Support lead".to_string();
+        current.biggest_pain_text = "Customer emails pile up".to_string();
+        db::upsert_onboarding_state(&conn, &current).expect("save");
 
-    let plan = AutopilotPlan {
-        schema_version: "1.0".to_string(),
-        recipe: RecipeKind::Custom,
-        intent: intent.to_string(),
-        provider: schema::ProviderMetadata::from_provider_id(provider_id),
-        web_source_url,
-        web_allowed_domains,
-        inbox_source_text: None,
-        daily_sources,
-        api_call_request,
-        recipient_hints: generated.recipient_hints,
-        allowed_primitives: if generated.allowed_primitives.is_empty() {
-            used_primitives
-        } else {
-            used_primitives
-        },
-        steps,
-    };
-    validate_custom_execution_plan(plan, provider_id)
-}
-
-fn generate_custom_plan(intent: &str, provider_id: ProviderId) -> Result<AutopilotPlan, String> {
-    let prompt = format!(
-        concat!(
-            "Generate a Terminus execution plan as JSON only.\n",
-            "Intent: {intent}\n\n",
-            "Use only these primitive ids (snake_case): read_web, read_sources, read_forwarded_email, triage_email, aggregate_daily_summary, write_outcome_draft, write_email_draft, send_email, notify_user.\n",
-            "You may also use: call_api (approval-gated, bounded HTTP GET/POST to allowlisted domain with Keychain ref).\n",
-            "Do not use schedule_run or read_vault_file.\n",
-            "Required JSON shape:\n",
-            "{{\"steps\":[{{\"id\":\"step_1\",\"label\":\"...\",\"primitive\":\"read_web\",\"requires_approval\":false,\"risk_tier\":\"low\"}}],\"web_allowed_domains\":[\"example.com\"],\"recipient_hints\":[\"person@example.com\"],\"allowed_primitives\":[\"read_web\"],\"api_call_request\":null}}\n",
-            "If using call_api include api_call_request: {{\"url\":\"https://api.example.com/v1/items\",\"method\":\"GET|POST\",\"header_key_ref\":\"crm_prod\",\"auth_header_name\":\"Authorization\",\"auth_scheme\":\"bearer|raw\",\"body_json\":\"{{...}}\"}}\n",
-            "Rules:\n",
-            "- call_api must be approval-gated and high risk\n",
-            "- send_email must be high risk and approval-gated\n",
-            "- write_outcome_draft and write_email_draft should be approval-gated\n",
-            "- Keep step count between 1 and 10\n",
-            "- Output JSON only, no markdown"
-        ),
-        intent = intent
-    );
-    let request = ProviderRequest {
-        provider_kind: provider_kind_for_schema(provider_id),
-        provider_tier: provider_tier_for_schema(provider_id),
-        model: schema::ProviderMetadata::from_provider_id(provider_id).default_model,
-        input: prompt,
-        max_output_tokens: Some(900),
-        correlation_id: Some(format!("plan_gen:{}", make_main_id("req"))),
-    };
-    let response = ProviderRuntime::default()
-        .dispatch(&request)
-        .map_err(|e| format!("Could not generate a custom plan yet: {e}"))?;
-    let generated: GeneratedCustomPlan = serde_json::from_str(response.text.trim())
-        .map_err(|e| format!("Plan generation returned invalid JSON: {e}"))?;
-    validate_and_build_custom_plan(intent, provider_id, generated)
-}
+        let recommendation = refresh_onboarding_recommendation(&conn).expect("recommend");
+        assert_eq!(recommendation.recipe, RecipeKind::InboxTriage);
+        let stored = db::get_onboarding_state(&conn).expect("state");
+        assert_eq!(stored.recommended_intent, Some(recommendation.intent));
+        assert_eq!(stored.role_text, "Support lead");
+    }
 
-fn describe_primitive_read(primitive: PrimitiveId) -> Option<String> {
-    match primitive {
-        PrimitiveId::ReadWeb => Some("Read website content from allowlisted domains".to_string()),
-        PrimitiveId::ReadSources => Some("Read configured sources for this brief".to_string()),
-        PrimitiveId::ReadForwardedEmail => {
-            Some("Read forwarded or pasted inbox content".to_string())
+    #[test]
+    fn maintenance_summary_aggregates_every_prune_path() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open memory db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        for i in 0..db::RELAY_PING_LOG_MAX_ROWS + 5 {
+            conn.execute(
+                "INSERT INTO relay_ping_log (id, reachable, status, latency_ms, http_status, message, created_at_ms)
+                 VALUES (?1, 1, 'ok', 10, 200, 'ok', ?2)",
+                rusqlite::params![format!("ping_{i}"), i],
+            )
+            .expect("ping");
         }
-        PrimitiveId::CallApi => Some("Read or write a bounded external API endpoint".to_string()),
-        PrimitiveId::ReadVaultFile => Some("Read connected vault files".to_string()),
-        _ => None,
-    }
-}
+        for i in 0..db::PROVIDER_TRACE_MAX_ROWS + 3 {
+            conn.execute(
+                "INSERT INTO provider_traces (id, provider_kind, model, status, request_input, latency_ms, created_at_ms)
+                 VALUES (?1, 'openai', 'gpt-4o-mini', 'ok', 'hi', 5, ?2)",
+                rusqlite::params![format!("trace_{i}"), i],
+            )
+            .expect("trace");
+        }
+        let count = |table: &str| -> i64 {
+            conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+                row.get(0)
+            })
+            .expect("count")
+        };
 
-fn describe_primitive_write(primitive: PrimitiveId) -> Option<String> {
-    match primitive {
-        PrimitiveId::TriageEmail => Some("Apply inbox filing action".to_string()),
-        PrimitiveId::WriteOutcomeDraft => Some("Prepare a completed outcome".to_string()),
-        PrimitiveId::WriteEmailDraft => Some("Prepare an approval-ready message".to_string()),
-        PrimitiveId::SendEmail => Some("Send an email".to_string()),
-        PrimitiveId::ScheduleRun => Some("Schedule this autopilot".to_string()),
-        PrimitiveId::NotifyUser => Some("Send a notification".to_string()),
-        PrimitiveId::CallApi => Some("Call an external API (approval-gated)".to_string()),
-        _ => None,
+        let preview = run_maintenance_internal(&conn, true, 1_000_000).expect("dry run");
+        assert!(preview.dry_run && preview.learning.dry_run);
+        assert_eq!(preview.relay_ping_log_deleted, 5);
+        assert_eq!(preview.provider_traces_deleted, 3);
+        assert_eq!(preview.total_deleted, 8);
+        assert_eq!(count("relay_ping_log"), db::RELAY_PING_LOG_MAX_ROWS + 5);
+
+        let applied = run_maintenance_internal(&conn, false, 1_000_000).expect("run");
+        assert_eq!(applied.total_deleted, preview.total_deleted);
+        assert_eq!(count("relay_ping_log"), db::RELAY_PING_LOG_MAX_ROWS);
+        assert_eq!(count("provider_traces"), db::PROVIDER_TRACE_MAX_ROWS);
+        let oldest_ping: String = conn
+            .query_row(
+                "SELECT id FROM relay_ping_log ORDER BY created_at_ms ASC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("oldest");
+        assert_eq!(oldest_ping, "ping_5");
+
+        let again = run_maintenance_internal(&conn, false, 1_000_000).expect("rerun");
+        assert_eq!(again.total_deleted, 0);
     }
-}
 
-fn preview_for_plan(kind: &IntentDraftKind, plan: &AutopilotPlan) -> IntentDraftPreview {
-    let mut reads = Vec::new();
-    let mut writes = Vec::new();
-    let mut approvals_required = Vec::new();
+    #[test]
+    fn schema_status_reports_current_version_and_boot_migration() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open memory db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let fresh = db::get_schema_status(&conn).expect("status");
+        assert_eq!(fresh.current_version, db::SCHEMA_VERSION);
+        assert_eq!(fresh.stored_version.as_deref(), Some(db::SCHEMA_VERSION));
+        assert!(fresh.up_to_date);
+        assert_eq!(fresh.migrated_from_version, None);
+        assert!(!fresh.migrated_on_last_boot);
 
-    for step in &plan.steps {
-        if let Some(read) = describe_primitive_read(step.primitive) {
-            if !reads.contains(&read) {
-                reads.push(read);
-            }
-        }
-        if let Some(write) = describe_primitive_write(step.primitive) {
-            if !writes.contains(&write) {
-                writes.push(write);
-            }
+        conn.execute(
+            "UPDATE schema_meta SET value = '2026-02-22-hardening' WHERE key = 'schema_version'",
+            [],
+        )
+        .expect("downgrade");
+        assert!(!db::get_schema_status(&conn).expect("status").up_to_date);
+        db::record_boot(&conn, 1).expect("boot");
+        db::bootstrap_schema(&mut conn).expect("rebootstrap");
+        let upgraded = db::get_schema_status(&conn).expect("status");
+        assert!(upgraded.up_to_date);
+        assert_eq!(
+            upgraded.migrated_from_version.as_deref(),
+            Some("2026-02-22-hardening")
+        );
+        assert!(upgraded.migrated_on_last_boot);
+
+        db::record_boot(&conn, i64::MAX).expect("later boot");
+        assert!(
+            !db::get_schema_status(&conn)
+                .expect("status")
+                .migrated_on_last_boot
+        );
+    }
+
+    #[test]
+    fn activity_feed_interleaves_run_and_autopilot_events_by_time() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open memory db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        conn.execute(
+            "INSERT INTO autopilots (id, name, created_at) VALUES ('auto_feed', 'Feed', 1)",
+            [],
+        )
+        .expect("autopilot");
+        conn.execute(
+            "INSERT INTO runs (id, autopilot_id, idempotency_key, plan_json, state, created_at, updated_at)
+             VALUES ('run_feed', 'auto_feed', 'idem_feed', '{}', 'succeeded', 1, 1)",
+            [],
+        )
+        .expect("run");
+        for (id, at) in [("act_1", 1_000), ("act_3", 3_000)] {
+            conn.execute(
+                "INSERT INTO activities (id, run_id, activity_type, from_state, to_state, user_message, created_at)
+                 VALUES (?1, 'run_feed', 'step_completed', 'running', 'ready', 'Step done.', ?2)",
+                rusqlite::params![id, at],
+            )
+            .expect("run activity");
         }
-        if step.requires_approval {
-            approvals_required.push(step.label.clone());
+        for (id, at) in [("ap_2", 2_000), ("ap_4", 4_000)] {
+            conn.execute(
+                "INSERT INTO activity (id, autopilot_id, event, created_at) VALUES (?1, 'auto_feed', 'Learning compacted.', ?2)",
+                rusqlite::params![id, at],
+            )
+            .expect("autopilot activity");
         }
+
+        let feed = db::get_activity_feed(&conn, 10, None).expect("feed");
+        let order = feed
+            .iter()
+            .map(|item| (item.id.as_str(), item.source.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            order,
+            vec![
+                ("ap_4", "autopilot"),
+                ("act_3", "run"),
+                ("ap_2", "autopilot"),
+                ("act_1", "run"),
+            ]
+        );
+        assert_eq!(feed[1].autopilot_id.as_deref(), Some("auto_feed"));
+        assert_eq!(feed[1].run_id.as_deref(), Some("run_feed"));
+        assert_eq!(feed[0].run_id, None);
+
+        let page = db::get_activity_feed(&conn, 2, Some(3_000)).expect("page");
+        assert_eq!(
+            page.iter().map(|item| item.id.as_str()).collect::<Vec<_>>(),
+            vec!["ap_2", "act_1"]
+        );
     }
 
-    IntentDraftPreview {
-        reads,
-        writes,
-        approvals_required,
-        estimated_spend: "About S$0.10–S$0.60 per run".to_string(),
-        primary_cta: match kind {
-            IntentDraftKind::OneOffRun => "Run now".to_string(),
-            IntentDraftKind::DraftAutopilot => "Run test".to_string(),
-        },
+    #[test]
+    fn reset_onboarding_clears_completion_but_keeps_history() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open memory db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        conn.execute(
+            "INSERT INTO autopilots (id, name, created_at) VALUES ('auto_onboard', 'Onboard', 1)",
+            [],
+        )
+        .expect("autopilot");
+        // Run timestamps are epoch ms, as `RunnerEngine::start_run` stores them.
+        let first_run_ms = 1_760_000_000_000_i64;
+        conn.execute(
+            "INSERT INTO runs (id, autopilot_id, idempotency_key, plan_json, state, created_at, updated_at)
+             VALUES ('run_done', 'auto_onboard', 'idem_onboard', '{}', 'succeeded', ?1, ?1)",
+            rusqlite::params![first_run_ms],
+        )
+        .expect("run");
+        db::record_onboarding_milestone(
+            &conn,
+            db::OnboardingMilestone::AutopilotCreated,
+            first_run_ms - 10_000,
+        )
+        .expect("milestone");
+        let mut current = db::get_onboarding_state(&conn).expect("state");
+        assert!(current.onboarding_complete);
+        assert_eq!(current.completed_at_ms, Some(first_run_ms));
+        current.role_text = "Ops manager".to_string();
+        current.biggest_pain_text = "Vendor follow-ups".to_string();
+        current.dismissed = true;
+        current.dismissed_at_ms = Some(first_run_ms + 20_000);
+        db::upsert_onboarding_state(&conn, &current).expect("save");
+
+        let reset_ms = first_run_ms + 100_000;
+        let reset = db::reset_onboarding_state(&conn, reset_ms).expect("reset");
+        assert!(!reset.onboarding_complete);
+        assert!(!reset.dismissed);
+        assert_eq!(reset.completed_at_ms, None);
+        assert_eq!(reset.dismissed_at_ms, None);
+        assert_eq!(reset.started_at_ms, reset_ms);
+        assert_eq!(reset.role_text, "Ops manager");
+        assert_eq!(reset.biggest_pain_text, "Vendor follow-ups");
+        assert_eq!(reset.first_successful_run_at_ms, Some(first_run_ms));
+        assert_eq!(
+            reset.first_autopilot_created_at_ms,
+            Some(first_run_ms - 10_000)
+        );
+
+        // The earlier success does not immediately re-complete the replayed onboarding.
+        let reread = db::get_onboarding_state(&conn).expect("reread");
+        assert!(!reread.onboarding_complete);
+
+        let next_run_ms = reset_ms + 50_000;
+        conn.execute(
+            "INSERT INTO runs (id, autopilot_id, idempotency_key, plan_json, state, created_at, updated_at)
+             VALUES ('run_again', 'auto_onboard', 'idem_again', '{}', 'succeeded', ?1, ?1)",
+            rusqlite::params![next_run_ms],
+        )
+        .expect("second run");
+        let completed = db::get_onboarding_state(&conn).expect("completed");
+        assert!(completed.onboarding_complete);
+        assert_eq!(completed.completed_at_ms, Some(next_run_ms));
+        assert_eq!(completed.first_successful_run_at_ms, Some(first_run_ms));
     }
-}
 
-#[tauri::command]
-fn draft_intent(
-    intent: String,
-    provider: Option<String>,
-    forced_kind: Option<String>,
-) -> Result<IntentDraftResponse, String> {
-    let cleaned = intent.trim();
-    if cleaned.is_empty() {
-        return Err("Add a one-line intent to continue.".to_string());
+    #[test]
+    fn voice_preview_differs_by_tone_and_validates_config() {
+        let voice = |tone: &str| db::VoiceConfigRecord {
+            tone: tone.to_string(),
+            length: "normal".to_string(),
+            humor: "off".to_string(),
+            notes: String::new(),
+            updated_at_ms: 0,
+        };
+        let professional = render_voice_preview(&voice("professional"), "the launch plan");
+        let warm = render_voice_preview(&voice("warm"), "the launch plan");
+        let neutral = render_voice_preview(&voice("neutral"), "the launch plan");
+        assert_ne!(professional, warm);
+        assert_ne!(professional, neutral);
+        assert_ne!(warm, neutral);
+        assert!(warm.contains("the launch plan"));
+
+        let mut light = voice("neutral");
+        light.humor = "light".to_string();
+        assert_ne!(render_voice_preview(&light, "the launch plan"), neutral);
+
+        let invalid = preview_voice(
+            VoiceConfigInput {
+                tone: "sarcastic".to_string(),
+                length: "normal".to_string(),
+                humor: "off".to_string(),
+                notes: String::new(),
+            },
+            None,
+        );
+        assert!(invalid.is_err());
+        let preview = preview_voice(
+            VoiceConfigInput {
+                tone: " Warm ".to_string(),
+                length: "Concise".to_string(),
+                humor: "off".to_string(),
+                notes: String::new(),
+            },
+            None,
+        )
+        .expect("preview");
+        assert_eq!(preview.tone, "warm");
+        assert!(preview.sample_text.contains("this week's project update"));
     }
-    let provider_id = match provider {
-        Some(raw) => parse_provider(&raw)?,
-        None => ProviderId::OpenAi,
-    };
 
-    let (auto_kind, auto_reason) = classify_intent_kind(cleaned);
-    let (kind, classification_reason) = match forced_kind {
-        Some(raw) => {
-            let forced = parse_intent_kind(raw.trim())?;
-            let reason = match forced {
-                IntentDraftKind::DraftAutopilot => {
-                    "Switched to recurring. Terminus prepared an Autopilot setup.".to_string()
-                }
-                IntentDraftKind::OneOffRun => {
-                    "Switched to one-time. Terminus prepared a one-off Run.".to_string()
-                }
-            };
-            (forced, reason)
+    #[test]
+    fn home_snapshot_range_scopes_counts_to_window() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open memory db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let day = 24 * 60 * 60 * 1000_i64;
+        let now = 1_700_000_000_000_i64 - 1_700_000_000_000_i64 % day + 12 * 60 * 60 * 1000;
+        let seed = |id: &str, created_at: i64| {
+            conn.execute(
+                "INSERT INTO autopilots (id, name, created_at) VALUES (?1, 'Home', ?2)",
+                rusqlite::params![format!("auto_{id}"), created_at],
+            )
+            .expect("autopilot");
+            conn.execute(
+                "INSERT INTO runs (id, autopilot_id, idempotency_key, plan_json, state, created_at, updated_at)
+                 VALUES (?1, ?2, ?1, '{}', 'succeeded', ?3, ?3)",
+                rusqlite::params![format!("run_{id}"), format!("auto_{id}"), created_at],
+            )
+            .expect("run");
+            conn.execute(
+                "INSERT INTO activities (id, run_id, activity_type, user_message, created_at)
+                 VALUES (?1, ?2, 'run_succeeded', 'Done', ?3)",
+                rusqlite::params![format!("act_{id}"), format!("run_{id}"), created_at],
+            )
+            .expect("activity");
+        };
+        seed("today", now - 60_000);
+        seed("three_days", now - 3 * day);
+        seed("last_month", now - 30 * day);
+
+        let surface_count = |range: db::HomeSnapshotRange, title: &str| {
+            db::build_home_snapshot(&conn, range, now)
+                .expect("snapshot")
+                .surfaces
+                .into_iter()
+                .find(|surface| surface.title == title)
+                .expect("surface")
+                .count
+        };
+        for title in ["Autopilots", "Outcomes", "Activity"] {
+            assert_eq!(
+                surface_count(db::HomeSnapshotRange::Today, title),
+                1,
+                "{title}"
+            );
+            assert_eq!(
+                surface_count(db::HomeSnapshotRange::Week, title),
+                2,
+                "{title}"
+            );
+            assert_eq!(
+                surface_count(db::HomeSnapshotRange::AllTime, title),
+                3,
+                "{title}"
+            );
         }
-        None => (auto_kind, auto_reason),
-    };
-    let recipe = classify_recipe(cleaned);
-    let plan = if recipe == RecipeKind::Custom {
-        generate_custom_plan(cleaned, provider_id)?
-    } else {
-        AutopilotPlan::from_intent(recipe, cleaned.to_string(), provider_id)
-    };
-    let preview = preview_for_plan(&kind, &plan);
-
-    Ok(IntentDraftResponse {
-        kind,
-        classification_reason,
-        plan,
-        preview,
-    })
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
+        assert_eq!(
+            db::build_home_snapshot(&conn, db::HomeSnapshotRange::default(), now)
+                .expect("snapshot")
+                .range,
+            "all_time"
+        );
+        assert_eq!(db::HomeSnapshotRange::parse("fortnight"), None);
+    }
 
     #[test]
-    fn classify_recipe_preserves_existing_signals_and_detects_custom() {
+    fn primary_outcomes_filter_by_autopilot_and_kind() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open memory db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let seed = |run_id: &str, autopilot_id: &str, kind: &str, at: i64| {
+            conn.execute(
+                "INSERT OR IGNORE INTO autopilots (id, name, created_at) VALUES (?1, 'Filter', 0)",
+                rusqlite::params![autopilot_id],
+            )
+            .expect("autopilot");
+            conn.execute(
+                "INSERT INTO runs (id, autopilot_id, idempotency_key, plan_json, state, created_at, updated_at)
+                 VALUES (?1, ?2, ?1, '{}', 'succeeded', ?3, ?3)",
+                rusqlite::params![run_id, autopilot_id, at],
+            )
+            .expect("run");
+            conn.execute(
+                "INSERT INTO outcomes (id, run_id, step_id, kind, status, content, created_at, updated_at)
+                 VALUES (?1, ?2, 'step_1', ?3, 'captured', 'draft', ?4, ?4)",
+                rusqlite::params![format!("outcome_{run_id}"), run_id, kind, at],
+            )
+            .expect("outcome");
+        };
+        seed("run_email_a", "auto_a", "email_draft", 1);
+        seed("run_note_a", "auto_a", "outcome_draft", 2);
+        seed("run_email_b", "auto_b", "email_draft", 3);
+
+        let run_ids = |autopilot_id: Option<&str>, kind: Option<&str>| {
+            db::list_primary_outcomes(&conn, 50, autopilot_id, kind)
+                .expect("list")
+                .into_iter()
+                .map(|row| row.run_id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(run_ids(None, None).len(), 3);
         assert_eq!(
-            classify_recipe("Monitor https://example.com for changes"),
-            RecipeKind::WebsiteMonitor
+            run_ids(Some("auto_a"), None),
+            vec!["run_note_a", "run_email_a"]
         );
-        assert_eq!(classify_recipe("Handle my inbox"), RecipeKind::InboxTriage);
         assert_eq!(
-            classify_recipe("Prepare a daily digest from these links"),
-            RecipeKind::DailyBrief
+            run_ids(None, Some("email_draft")),
+            vec!["run_email_b", "run_email_a"]
         );
         assert_eq!(
-            classify_recipe("Parse this invoice and categorize expenses every Friday"),
-            RecipeKind::Custom
+            run_ids(Some("auto_a"), Some("email_draft")),
+            vec!["run_email_a"]
         );
+        assert_eq!(run_ids(None, Some("not_a_kind")).len(), 3);
     }
 
     #[test]
@@ -4281,6 +10437,7 @@ mod tests {
             recipient_hints: vec!["team@example.com".to_string()],
             allowed_primitives: vec!["send_email".to_string()],
             api_call_request: None,
+            api_call_requests: Default::default(),
         };
         let plan = validate_and_build_custom_plan(
             "Send updates for https://example.com",
@@ -4309,6 +10466,7 @@ mod tests {
             recipient_hints: vec![],
             allowed_primitives: vec![],
             api_call_request: None,
+            api_call_requests: Default::default(),
         };
         let err = validate_and_build_custom_plan("Schedule this", ProviderId::OpenAi, disallowed)
             .expect_err("schedule_run must be rejected");
@@ -4319,7 +10477,12 @@ mod tests {
     fn validate_custom_execution_plan_enforces_bounds_and_required_metadata() {
         let mut plan =
             AutopilotPlan::from_intent(RecipeKind::Custom, "x".to_string(), ProviderId::OpenAi);
-        assert!(validate_custom_execution_plan(plan.clone(), ProviderId::OpenAi).is_err());
+        assert!(validate_custom_execution_plan(
+            plan.clone(),
+            ProviderId::OpenAi,
+            &db::UNSUPPORTED_PRIMITIVES
+        )
+        .is_err());
 
         plan.steps = vec![PlanStep {
             id: "step_1".to_string(),
@@ -4328,16 +10491,110 @@ mod tests {
             requires_approval: false,
             risk_tier: RiskTier::Low,
         }];
-        let err = validate_custom_execution_plan(plan.clone(), ProviderId::OpenAi)
-            .expect_err("read_web requires allowlist");
+        let err = validate_custom_execution_plan(
+            plan.clone(),
+            ProviderId::OpenAi,
+            &db::UNSUPPORTED_PRIMITIVES,
+        )
+        .expect_err("read_web requires allowlist");
         assert!(err.contains("allowed domains"));
 
         plan.web_source_url = Some("https://example.com".to_string());
         plan.web_allowed_domains = vec!["example.com".to_string()];
-        let ok = validate_custom_execution_plan(plan, ProviderId::OpenAi).expect("valid");
+        let ok =
+            validate_custom_execution_plan(plan, ProviderId::OpenAi, &db::UNSUPPORTED_PRIMITIVES)
+                .expect("valid");
         assert_eq!(ok.provider.id, ProviderId::OpenAi);
     }
 
+    #[test]
+    fn validate_plan_json_returns_normalized_plan_or_structured_error() {
+        let mut plan =
+            AutopilotPlan::from_intent(RecipeKind::Custom, "x".to_string(), ProviderId::OpenAi);
+        plan.steps = vec![
+            PlanStep {
+                id: "step_1".to_string(),
+                label: "Read".to_string(),
+                primitive: PrimitiveId::ReadWeb,
+                requires_approval: false,
+                risk_tier: RiskTier::Low,
+            },
+            PlanStep {
+                id: "step_2".to_string(),
+                label: "Draft summary".to_string(),
+                primitive: PrimitiveId::WriteOutcomeDraft,
+                requires_approval: false,
+                risk_tier: RiskTier::Low,
+            },
+        ];
+        plan.web_source_url = Some("https://status.example.com/feed".to_string());
+        plan.web_allowed_domains = vec!["example.com".to_string()];
+        let json = serde_json::to_string(&plan).expect("plan json");
+
+        let ok = validate_plan_json_internal(&json, "openai", &[], &db::UNSUPPORTED_PRIMITIVES);
+        assert!(ok.valid);
+        assert!(ok.error.is_none());
+        let normalized = ok.plan.expect("normalized plan");
+        assert!(normalized.steps[1].requires_approval);
+        assert_eq!(normalized.steps[1].risk_tier, RiskTier::Medium);
+        assert!(normalized
+            .web_allowed_domains
+            .contains(&"status.example.com".to_string()));
+        let codes = ok
+            .warnings
+            .iter()
+            .map(|w| w.code.as_str())
+            .collect::<Vec<&str>>();
+        assert!(codes.contains(&"approval_enforced"));
+        assert!(codes.contains(&"risk_raised"));
+        assert!(codes.contains(&"domains_added"));
+
+        plan.web_source_url = None;
+        plan.web_allowed_domains.clear();
+        let json = serde_json::to_string(&plan).expect("plan json");
+        let missing =
+            validate_plan_json_internal(&json, "openai", &[], &db::UNSUPPORTED_PRIMITIVES);
+        assert!(!missing.valid);
+        assert!(missing.plan.is_none());
+        assert!(missing
+            .error
+            .expect("allowlist error")
+            .contains("no allowed domains"));
+
+        let garbled =
+            validate_plan_json_internal("{not json", "openai", &[], &db::UNSUPPORTED_PRIMITIVES);
+        assert!(garbled.error.expect("json error").contains("invalid JSON"));
+    }
+
+    #[test]
+    fn validate_custom_plan_accepts_subdomain_wildcard_and_rejects_bare_star() {
+        let mut plan =
+            AutopilotPlan::from_intent(RecipeKind::Custom, "x".to_string(), ProviderId::OpenAi);
+        plan.steps = vec![PlanStep {
+            id: "step_1".to_string(),
+            label: "Read".to_string(),
+            primitive: PrimitiveId::ReadWeb,
+            requires_approval: false,
+            risk_tier: RiskTier::Low,
+        }];
+        plan.web_source_url = Some("https://v2.api.example.com/status".to_string());
+        plan.web_allowed_domains = vec!["*.Example.com".to_string()];
+        let ok = validate_custom_execution_plan(
+            plan.clone(),
+            ProviderId::OpenAi,
+            &db::UNSUPPORTED_PRIMITIVES,
+        )
+        .expect("wildcard plan");
+        // The wildcard already covers the source host, so no exact entry is appended.
+        assert_eq!(ok.web_allowed_domains, vec!["*.example.com".to_string()]);
+
+        plan.web_allowed_domains = vec!["*".to_string()];
+        let err =
+            validate_custom_execution_plan(plan, ProviderId::OpenAi, &db::UNSUPPORTED_PRIMITIVES)
+                .expect_err("bare wildcard rejected");
+        assert!(err.contains("invalid"));
+    }
+
     #[test]
     fn validate_custom_plan_call_api_requires_config_and_forces_approval() {
         let generated = GeneratedCustomPlan {
@@ -4358,7 +10615,9 @@ mod tests {
                 auth_header_name: Some("Authorization".to_string()),
                 auth_scheme: Some("bearer".to_string()),
                 body_json: None,
+                response_fields: Vec::new(),
             }),
+            api_call_requests: Default::default(),
         };
         let plan = validate_and_build_custom_plan(
             "Call the CRM API and summarize results",
@@ -4378,9 +10637,201 @@ mod tests {
 
         let mut missing_cfg = plan.clone();
         missing_cfg.api_call_request = None;
-        let err = validate_custom_execution_plan(missing_cfg, ProviderId::OpenAi)
-            .expect_err("call_api should require config");
+        let err = validate_custom_execution_plan(
+            missing_cfg,
+            ProviderId::OpenAi,
+            &db::UNSUPPORTED_PRIMITIVES,
+        )
+        .expect_err("call_api should require config");
+        assert!(err.contains("API request configuration"));
+    }
+
+    #[test]
+    fn disallowing_send_email_rejects_send_plans() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let mut plan =
+            AutopilotPlan::from_intent(RecipeKind::Custom, "x".to_string(), ProviderId::OpenAi);
+        plan.steps = vec![PlanStep {
+            id: "step_1".to_string(),
+            label: "Send update".to_string(),
+            primitive: PrimitiveId::SendEmail,
+            requires_approval: true,
+            risk_tier: RiskTier::High,
+        }];
+        plan.recipient_hints = vec!["teammate@acme.com".to_string()];
+
+        let defaults = db::get_app_settings(&conn).expect("settings");
+        assert_eq!(
+            defaults.disallowed_primitives,
+            db::UNSUPPORTED_PRIMITIVES.to_vec()
+        );
+        validate_custom_execution_plan(
+            plan.clone(),
+            ProviderId::OpenAi,
+            &defaults.disallowed_primitives,
+        )
+        .expect("send allowed by default");
+
+        assert!(set_disallowed_primitives_internal(&conn, &["send_mail".to_string()]).is_err());
+        let locked =
+            set_disallowed_primitives_internal(&conn, &["send_email".to_string()]).expect("lock");
+        assert!(locked
+            .disallowed_primitives
+            .contains(&PrimitiveId::SendEmail));
+        assert!(locked
+            .disallowed_primitives
+            .contains(&PrimitiveId::ScheduleRun));
+        let err =
+            validate_custom_execution_plan(plan, ProviderId::OpenAi, &locked.disallowed_primitives)
+                .expect_err("send disallowed");
+        assert!(err.contains("turned off"));
+        assert!(err.contains("Send update"));
+    }
+
+    #[test]
+    fn plan_warnings_flag_external_recipients_and_stay_empty_for_safe_plans() {
+        let mut plan =
+            AutopilotPlan::from_intent(RecipeKind::Custom, "x".to_string(), ProviderId::OpenAi);
+        plan.steps = vec![
+            PlanStep {
+                id: "step_1".to_string(),
+                label: "Prepare outcome".to_string(),
+                primitive: PrimitiveId::WriteOutcomeDraft,
+                requires_approval: false,
+                risk_tier: RiskTier::Low,
+            },
+            PlanStep {
+                id: "step_2".to_string(),
+                label: "Send update".to_string(),
+                primitive: PrimitiveId::SendEmail,
+                requires_approval: true,
+                risk_tier: RiskTier::High,
+            },
+        ];
+        plan.recipient_hints = vec![
+            "Teammate@Acme.com".to_string(),
+            "partner@vendor.io".to_string(),
+        ];
+        let plan =
+            validate_custom_execution_plan(plan, ProviderId::OpenAi, &db::UNSUPPORTED_PRIMITIVES)
+                .expect("valid");
+        let own = vec!["acme.com".to_string()];
+
+        let warnings = collect_plan_warnings(&plan, &own);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, "external_recipient");
+        assert!(warnings[0].message.contains("vendor.io"));
+        assert!(!warnings[0].message.contains("acme.com"));
+
+        let mut internal_only = plan.clone();
+        internal_only.recipient_hints = vec!["teammate@acme.com".to_string()];
+        assert!(collect_plan_warnings(&internal_only, &own).is_empty());
+
+        let mut unapproved = internal_only;
+        unapproved.steps.push(PlanStep {
+            id: "step_3".to_string(),
+            label: "Aggregate summary".to_string(),
+            primitive: PrimitiveId::AggregateDailySummary,
+            requires_approval: false,
+            risk_tier: RiskTier::Medium,
+        });
+        let warnings = collect_plan_warnings(&unapproved, &own);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, "unapproved_risky_step");
+    }
+
+    #[test]
+    fn validate_custom_plan_accepts_two_call_api_steps_with_separate_hosts() {
+        let call = |url: &str, method: &str, body_json: Option<&str>| GeneratedApiCallRequest {
+            url: url.to_string(),
+            method: Some(method.to_string()),
+            header_key_ref: "crm_prod".to_string(),
+            auth_header_name: None,
+            auth_scheme: None,
+            body_json: body_json.map(str::to_string),
+            response_fields: Vec::new(),
+        };
+        let step = |id: &str, label: &str| GeneratedCustomStep {
+            id: id.to_string(),
+            label: label.to_string(),
+            primitive: "call_api".to_string(),
+            requires_approval: false,
+            risk_tier: "low".to_string(),
+        };
+        let generated = GeneratedCustomPlan {
+            steps: vec![
+                step("step_1", "Read CRM"),
+                step("step_2", "Post to tracker"),
+            ],
+            web_allowed_domains: vec![],
+            recipient_hints: vec![],
+            allowed_primitives: vec![],
+            api_call_request: None,
+            api_call_requests: [
+                (
+                    "step_1".to_string(),
+                    call("https://crm.example.com/v1/items", "get", None),
+                ),
+                (
+                    "step_2".to_string(),
+                    call(
+                        "https://tracker.example.org/v1/issues",
+                        "post",
+                        Some(r#"{"title":"sync"}"#),
+                    ),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let plan = validate_and_build_custom_plan(
+            "Read from the CRM API and post to the tracker API",
+            ProviderId::OpenAi,
+            generated,
+        )
+        .expect("two call api plan");
+        assert!(plan
+            .steps
+            .iter()
+            .all(|s| s.requires_approval && s.risk_tier == RiskTier::High));
+        assert!(plan
+            .web_allowed_domains
+            .contains(&"crm.example.com".to_string()));
+        assert!(plan
+            .web_allowed_domains
+            .contains(&"tracker.example.org".to_string()));
+        assert_eq!(
+            plan.api_call_for_step("step_1").map(|c| c.method.as_str()),
+            Some("GET")
+        );
+        assert_eq!(
+            plan.api_call_for_step("step_2").map(|c| c.method.as_str()),
+            Some("POST")
+        );
+
+        let mut missing = plan.clone();
+        missing.api_call_requests.remove("step_2");
+        let err = validate_custom_execution_plan(
+            missing,
+            ProviderId::OpenAi,
+            &db::UNSUPPORTED_PRIMITIVES,
+        )
+        .expect_err("each call api step needs a config");
         assert!(err.contains("API request configuration"));
+
+        let mut orphaned = plan;
+        let config = orphaned.api_call_requests["step_1"].clone();
+        orphaned
+            .api_call_requests
+            .insert("step_9".to_string(), config);
+        let err = validate_custom_execution_plan(
+            orphaned,
+            ProviderId::OpenAi,
+            &db::UNSUPPORTED_PRIMITIVES,
+        )
+        .expect_err("config must target a call api step");
+        assert!(err.contains("step_9"));
     }
 
     #[test]
@@ -4398,6 +10849,71 @@ mod tests {
         assert!(err.to_ascii_lowercase().contains("signature"));
     }
 
+    #[test]
+    fn webhook_signature_modes_verify_their_own_header_formats() {
+        let secret = "whsec_test";
+        let body = "{\"event\":\"ok\"}";
+        let tampered = "{\"event\":\"forged\"}";
+        let now = now_ms();
+
+        let ts = now;
+        let terminus = format!(
+            "sha256={}",
+            webhook_hmac_sha256_hex(secret, &format!("{ts}.{body}")).expect("hmac")
+        );
+        verify_webhook_signature("terminus_hmac_sha256", secret, body, &terminus, ts)
+            .expect("terminus valid");
+        verify_webhook_signature("terminus_hmac_sha256", secret, tampered, &terminus, ts)
+            .expect_err("terminus tampered");
+
+        let t = now / 1000;
+        let stripe = format!(
+            "t={t},v1={},v0=ignored",
+            webhook_hmac_sha256_hex(secret, &format!("{t}.{body}")).expect("hmac")
+        );
+        verify_webhook_signature("stripe", secret, body, &stripe, 0).expect("stripe valid");
+        verify_webhook_signature("stripe", secret, tampered, &stripe, 0)
+            .expect_err("stripe tampered");
+        let stale_t = t - STRIPE_SIGNATURE_TOLERANCE_SECONDS - 60;
+        let stale = format!(
+            "t={stale_t},v1={}",
+            webhook_hmac_sha256_hex(secret, &format!("{stale_t}.{body}")).expect("hmac")
+        );
+        let err =
+            verify_webhook_signature("stripe", secret, body, &stale, 0).expect_err("stripe stale");
+        assert!(err.contains("expired"));
+
+        let github = format!(
+            "sha256={}",
+            webhook_hmac_sha256_hex(secret, body).expect("hmac")
+        );
+        verify_webhook_signature("github_sha256", secret, body, &github, 0).expect("github valid");
+        verify_webhook_signature("github_sha256", secret, tampered, &github, 0)
+            .expect_err("github tampered");
+        verify_webhook_signature("github_sha256", secret, body, &terminus, 0)
+            .expect_err("github rejects terminus signature");
+
+        assert_eq!(
+            validate_webhook_signature_mode(" Stripe ").expect("mode"),
+            "stripe"
+        );
+        assert!(validate_webhook_signature_mode("sha1").is_err());
+    }
+
+    #[test]
+    fn stripe_triggers_take_the_secret_stripe_issued() {
+        assert_eq!(
+            webhook_signing_secret("stripe", Some(" whsec_from_stripe ")).expect("stripe secret"),
+            "whsec_from_stripe"
+        );
+        assert!(webhook_signing_secret("stripe", None).is_err());
+        assert!(webhook_signing_secret("stripe", Some("sk_live_123")).is_err());
+        assert!(webhook_signing_secret("github_sha256", Some("whsec_x")).is_err());
+        assert!(webhook_signing_secret("terminus_hmac_sha256", None)
+            .expect("generated")
+            .starts_with("whsec"));
+    }
+
     #[test]
     fn webhook_content_type_normalization_strips_charset() {
         assert_eq!(
@@ -4444,6 +10960,7 @@ mod tests {
                     deduped: 0,
                     started_runs: 1,
                     failed: 0,
+                    approvals_resolved: 0,
                 })
             },
         )
@@ -4464,6 +10981,77 @@ mod tests {
         assert_eq!(second.created_run_count, 0);
     }
 
+    #[test]
+    fn relay_gmail_pubsub_redelivery_under_new_request_id_is_duplicate() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let body = r#"{"message":{"messageId":"m7","publishTime":"2026-02-25T12:00:00Z","data":"eyJoaXN0b3J5SWQiOiI3In0="}}"#;
+        let fetches = std::cell::Cell::new(0);
+        let fetch = |_conn: &mut rusqlite::Connection| {
+            fetches.set(fetches.get() + 1);
+            Ok(inbox_watcher::InboxWatcherTickSummary {
+                provider: "gmail".to_string(),
+                autopilot_id: "auto_inbox_watch_gmail".to_string(),
+                fetched: 1,
+                deduped: 0,
+                started_runs: 1,
+                failed: 0,
+                approvals_resolved: 0,
+            })
+        };
+
+        let first = ingest_gmail_pubsub_event_internal(
+            &mut conn,
+            Some("relay_req_a".to_string()),
+            Some("relay"),
+            body,
+            true,
+            fetch,
+        )
+        .expect("first delivery");
+        assert_eq!(first.status, "accepted");
+
+        let second = ingest_gmail_pubsub_event_internal(
+            &mut conn,
+            Some("relay_req_b".to_string()),
+            Some("relay"),
+            body,
+            true,
+            fetch,
+        )
+        .expect("redelivery");
+        assert_eq!(second.status, "duplicate");
+        assert_eq!(second.event_dedupe_key, first.event_dedupe_key);
+        assert_eq!(second.created_run_count, 0);
+        assert_eq!(fetches.get(), 1);
+
+        let rows = conn
+            .prepare(
+                "SELECT request_id, status, event_dedupe_key
+                 FROM relay_gmail_pubsub_callback_events ORDER BY request_id",
+            )
+            .expect("prepare")
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                ))
+            })
+            .expect("query")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("rows");
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].1, "applied");
+        assert_eq!(rows[1].1, "duplicate");
+        assert_eq!(rows[0].2.as_deref(), Some(first.event_dedupe_key.as_str()));
+        assert_eq!(rows[1].2, rows[0].2);
+        assert_eq!(
+            gmail_pubsub::list_events(&conn, 5).expect("events").len(),
+            1
+        );
+    }
+
     #[test]
     fn gmail_pubsub_ingest_records_fetch_failure_without_crashing() {
         let mut conn = rusqlite::Connection::open_in_memory().expect("db");
@@ -4505,6 +11093,24 @@ mod tests {
             .contains("gmail"));
     }
 
+    #[test]
+    fn autopilot_transport_mode_requires_relay_token_for_relay() {
+        assert_eq!(
+            validate_autopilot_transport_mode("Inherit", false).expect("inherit"),
+            "inherit"
+        );
+        assert_eq!(
+            validate_autopilot_transport_mode("byok_local", false).expect("local"),
+            "local_http"
+        );
+        assert!(validate_autopilot_transport_mode("relay", false).is_err());
+        assert_eq!(
+            validate_autopilot_transport_mode("relay", true).expect("relay"),
+            "relay"
+        );
+        assert!(validate_autopilot_transport_mode("carrier_pigeon", true).is_err());
+    }
+
     #[test]
     fn relay_routing_blocks_standby_when_preferred_active() {
         let mut conn = rusqlite::Connection::open_in_memory().expect("db");
@@ -4545,6 +11151,49 @@ mod tests {
             .expect("manual target should block");
         assert!(reason.to_ascii_lowercase().contains("manual target"));
     }
+
+    #[test]
+    fn routing_preview_marks_no_targets_under_manual_target_only() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let now = now_ms();
+        conn.execute(
+            "INSERT INTO relay_devices (device_id, device_label, status, last_seen_at_ms, capabilities_json, is_preferred_target, updated_at_ms)
+             VALUES ('dev_a','Mac A','active',?1,'{}',1,?1), ('dev_b','Mac B','standby',?1,'{}',0,?1)",
+            rusqlite::params![now],
+        )
+        .expect("insert devices");
+        let proposal = |mode: &str| RelayRoutingPolicyInput {
+            approval_target_mode: mode.to_string(),
+            trigger_target_mode: "preferred_only".to_string(),
+            fallback_policy: "queue_until_online".to_string(),
+        };
+
+        let current =
+            evaluate_routing_policy_internal(&conn, &proposal("preferred_only")).expect("preview");
+        assert_eq!(current.len(), 2);
+        assert!(current
+            .iter()
+            .any(|d| d.device_id == "dev_a" && d.would_receive_decisions));
+        let standby = current
+            .iter()
+            .find(|d| d.device_id == "dev_b")
+            .expect("standby device");
+        assert_eq!(
+            standby.reason,
+            "Mac B is standby. Relay decisions are routed to preferred device Mac A."
+        );
+
+        let manual = evaluate_routing_policy_internal(&conn, &proposal("manual_target_only"))
+            .expect("preview");
+        assert_eq!(manual.len(), 2);
+        assert!(manual.iter().all(|d| !d.would_receive_decisions));
+        assert!(manual
+            .iter()
+            .all(|d| d.reason.contains(&d.device_label) && !d.reason.contains("This device")));
+        let stored = get_relay_routing_policy_internal(&conn).expect("policy");
+        assert_eq!(stored.approval_target_mode, "preferred_only");
+    }
 }
 
 fn main() {
@@ -4586,9 +11235,16 @@ fn main() {
             get_home_snapshot,
             list_primary_outcomes,
             get_transport_status,
+            get_provider_health,
+            list_provider_pricing,
+            update_provider_pricing,
+            get_recipe_model_overrides,
+            update_recipe_model_override,
+            estimate_provider_cost,
             get_remote_approval_readiness,
             list_relay_devices,
             get_relay_routing_policy,
+            evaluate_routing_policy,
             set_relay_device_status,
             set_preferred_relay_device,
             update_relay_routing_policy,
@@ -4596,13 +11252,38 @@ fn main() {
             get_relay_push_status,
             tick_relay_approval_sync,
             tick_relay_approval_push,
+            reset_relay_sync_state,
             issue_relay_callback_secret,
             clear_relay_callback_secret,
             set_subscriber_token,
             remove_subscriber_token,
+            set_local_transport_base_url,
+            clear_local_transport_base_url,
+            probe_local_transport,
+            list_audit_log,
+            ping_relay,
+            list_relay_pings,
+            get_autopilot_transport_config,
+            get_autopilot_context_limits,
+            update_autopilot_context_limits,
+            get_autopilot_web_tracking,
+            update_autopilot_web_tracking,
+            get_approval_ttls,
+            update_approval_ttls,
+            get_approval_channel_policy,
+            update_approval_channel_policy,
+            get_approval_reply_subject,
+            get_approval_context,
+            get_run_queue,
+            get_recent_logs,
+            update_autopilot_transport_config,
             set_api_key_ref,
             remove_api_key_ref,
             get_api_key_ref_status,
+            list_api_key_refs,
+            list_secret_rotation_warnings,
+            export_keychain_bundle,
+            import_keychain_bundle,
             probe_vault_extraction,
             get_codex_oauth_status,
             import_codex_oauth_from_local_auth,
@@ -4619,11 +11300,22 @@ fn main() {
             rotate_webhook_trigger_secret,
             disable_webhook_trigger,
             enable_webhook_trigger,
+            disable_all_webhook_triggers,
+            enable_all_webhook_triggers,
+            list_webhook_dead_letters,
+            retry_dead_letter,
+            simulate_webhook_signature,
+            get_webhook_endpoint_url,
+            preview_webhook_run,
+            update_webhook_trigger_payload_schema,
             get_webhook_trigger_events,
             ingest_webhook_event_local_debug,
             resolve_relay_webhook_callback,
             draft_intent,
+            reclassify_recipe,
             start_recipe_run,
+            start_batch_runs,
+            validate_plan_json,
             run_tick,
             resume_due_runs,
             create_mission_draft,
@@ -4631,7 +11323,11 @@ fn main() {
             get_mission,
             list_missions,
             run_mission_tick,
+            save_mission_template,
+            list_mission_templates,
+            instantiate_mission_template,
             approve_run_approval,
+            regenerate_step_with_provider,
             approve_run_approval_remote,
             reject_run_approval,
             reject_run_approval_remote,
@@ -4639,32 +11335,66 @@ fn main() {
             list_pending_approvals,
             list_pending_clarifications,
             list_run_diagnostics,
+            explain_run,
             apply_intervention,
             submit_clarification_answer,
             get_run,
+            get_run_with_plan,
+            get_run_step_details,
+            get_run_cost_breakdown,
             get_terminal_receipt,
+            compare_run_receipts,
+            list_primitive_guard_events,
             list_email_connections,
             save_email_oauth_config,
             start_email_oauth,
             complete_email_oauth,
             disconnect_email_provider,
+            list_email_labels,
+            list_autopilot_triage_labels,
+            set_autopilot_triage_label,
             run_inbox_watcher_tick,
             get_runner_control,
+            get_app_settings,
+            set_disallowed_primitives,
+            set_global_pause,
+            get_app_healthcheck,
             update_runner_control,
             get_onboarding_state,
             save_onboarding_state,
+            get_onboarding_recommendation,
             dismiss_onboarding,
+            reset_onboarding,
+            get_activity_feed,
+            get_inbox_watcher_stats,
+            get_schema_status,
             get_global_voice_config,
             update_global_voice_config,
             get_autopilot_voice_config,
             update_autopilot_voice_config,
             clear_autopilot_voice_config,
+            preview_voice,
             tick_runner_cycle,
+            list_runner_cycles,
+            list_relay_callback_events,
+            list_provider_traces,
             get_autopilot_send_policy,
+            get_autopilot_send_stats,
+            list_intent_kind_overrides,
+            upsert_intent_kind_override,
+            delete_intent_kind_override,
+            list_recipient_cooldowns,
+            clear_recipient_cooldowns,
             update_autopilot_send_policy,
             submit_guidance,
+            preview_guidance,
             record_decision_event,
-            compact_learning_data
+            mark_outcome_reviewed,
+            compact_learning_data,
+            run_maintenance,
+            list_memory_cards,
+            upsert_memory_card,
+            delete_memory_card
         ])
         .run(tauri::generate_context!())
         .expect("failed to run Terminus app");
@@ -3,28 +3,36 @@ use crate::schema::{AutopilotPlan, PlanStep, PrimitiveId, ProviderId, RecipeKind
 use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::BTreeMap;
 use std::str::FromStr;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 static MISSION_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
+const MAX_MISSION_STAGES: usize = 5;
+const STAGE_CONTEXT_MAX_OUTCOMES: usize = 4;
+const STAGE_CONTEXT_MAX_CHARS: usize = 1_200;
+const MAX_TEMPLATE_PARAMETERS: usize = 10;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum MissionTemplateKind {
     DailyBriefMultiSource,
+    MultiRecipe,
 }
 
 impl MissionTemplateKind {
     fn as_str(&self) -> &'static str {
         match self {
             Self::DailyBriefMultiSource => "daily_brief_multi_source",
+            Self::MultiRecipe => "multi_recipe",
         }
     }
 
     fn parse(value: &str) -> Result<Self, String> {
         match value {
             "daily_brief_multi_source" => Ok(Self::DailyBriefMultiSource),
+            "multi_recipe" => Ok(Self::MultiRecipe),
             _ => Err("Unsupported mission template.".to_string()),
         }
     }
@@ -104,11 +112,96 @@ pub struct CreateMissionDraftInput {
     pub sources: Vec<String>,
 }
 
+/// Optional `list_missions` filters. The creation window is `[created_after_ms, created_before_ms)`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissionListFilter {
+    pub status: Option<String>,
+    pub created_after_ms: Option<i64>,
+    pub created_before_ms: Option<i64>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StartMissionInput {
     pub draft: MissionDraft,
     pub idempotency_key: Option<String>,
+    /// Ordered recipe stages. When present the mission runs them one after another instead of
+    /// fanning out over the draft's source groups.
+    #[serde(default)]
+    pub stages: Vec<MissionStageInput>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissionStageInput {
+    pub recipe: String,
+    pub intent: String,
+    #[serde(default)]
+    pub sources: Vec<String>,
+    pub input_text: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StagedMissionConfig {
+    intent: String,
+    provider: String,
+    stages: Vec<MissionStageInput>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissionStageProgress {
+    pub stage_index: usize,
+    pub stage_key: String,
+    pub recipe: String,
+    pub intent: String,
+    pub run_id: Option<String>,
+    /// `pending` until the stage's run exists, then the run's state.
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveMissionTemplateInput {
+    pub name: String,
+    pub draft: MissionDraft,
+    #[serde(default)]
+    pub stages: Vec<MissionStageInput>,
+    /// Placeholder names used as `{{name}}` in the draft or stage text.
+    #[serde(default)]
+    pub parameters: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissionTemplateRecord {
+    pub id: String,
+    pub name: String,
+    pub template_kind: MissionTemplateKind,
+    pub parameters: Vec<String>,
+    pub draft: MissionDraft,
+    pub stages: Vec<MissionStageInput>,
+    pub created_at_ms: i64,
+    pub updated_at_ms: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateMissionTemplateInput {
+    pub template_id: String,
+    #[serde(default)]
+    pub parameters: BTreeMap<String, String>,
+}
+
+/// A template with its parameters filled in, ready to pass to `start_mission`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissionTemplateInstance {
+    pub template_id: String,
+    pub draft: MissionDraft,
+    pub stages: Vec<MissionStageInput>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -165,6 +258,8 @@ pub struct MissionDetail {
     pub child_runs: Vec<MissionRunLink>,
     pub events: Vec<MissionEventRecord>,
     pub contract: MissionContractStatus,
+    #[serde(default)]
+    pub stages: Vec<MissionStageProgress>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -192,7 +287,7 @@ pub fn create_mission_draft(input: CreateMissionDraftInput) -> Result<MissionDra
         .map(|s| s.trim().to_string())
         .filter(|s| !s.is_empty())
         .collect::<Vec<String>>();
-    if cleaned_sources.is_empty() {
+    if cleaned_sources.is_empty() && template_kind == MissionTemplateKind::DailyBriefMultiSource {
         return Err("Add at least one source for this mission.".to_string());
     }
     if cleaned_sources.len() > 10 {
@@ -209,17 +304,26 @@ pub fn create_mission_draft(input: CreateMissionDraftInput) -> Result<MissionDra
                 sources: vec![source],
             })
             .collect::<Vec<_>>(),
+        MissionTemplateKind::MultiRecipe => Vec::new(),
+    };
+    let preview = match template_kind {
+        MissionTemplateKind::DailyBriefMultiSource => MissionDraftPreview {
+            child_runs: source_groups.len(),
+            contract: "All child runs must finish without blocked/pending states before aggregation completes.".to_string(),
+            note: "This MVP mission fans out into child runs, then aggregates a deterministic summary.".to_string(),
+        },
+        MissionTemplateKind::MultiRecipe => MissionDraftPreview {
+            child_runs: 0,
+            contract: "Each stage must succeed before the next one starts.".to_string(),
+            note: "Stages run in order. Each stage receives the previous stage's outcomes as context.".to_string(),
+        },
     };
 
     Ok(MissionDraft {
         template_kind,
         provider,
         intent,
-        preview: MissionDraftPreview {
-            child_runs: source_groups.len(),
-            contract: "All child runs must finish without blocked/pending states before aggregation completes.".to_string(),
-            note: "This MVP mission fans out into child runs, then aggregates a deterministic summary.".to_string(),
-        },
+        preview,
         source_groups,
     })
 }
@@ -228,6 +332,9 @@ pub fn start_mission(
     connection: &mut Connection,
     input: StartMissionInput,
 ) -> Result<MissionDetail, String> {
+    if !input.stages.is_empty() || input.draft.template_kind == MissionTemplateKind::MultiRecipe {
+        return start_staged_mission(connection, input);
+    }
     validate_mission_draft(&input.draft)?;
     let mission_id = make_id("mission");
     let mission_key = input.idempotency_key.unwrap_or_else(|| {
@@ -269,37 +376,14 @@ pub fn start_mission(
     tx.commit()
         .map_err(|e| format!("Failed to commit mission creation: {e}"))?;
 
-    let mut created_children = 0usize;
-    for group in &input.draft.source_groups {
-        let child_autopilot_id = format!("{}_{}", mission_id, group.child_key);
-        let child_idempotency_key = format!("mission:{}:{}", mission_id, group.child_key);
-        let plan = build_daily_brief_child_plan(&input.draft.intent, provider_id, &group.sources);
-        let run = RunnerEngine::start_run(
-            connection,
-            &child_autopilot_id,
-            plan,
-            &child_idempotency_key,
-            2,
-        )
-        .map_err(|e| e.to_string())?;
-
-        connection
-            .execute(
-                "INSERT INTO mission_runs (id, mission_id, child_key, run_id, run_role, source_label, status, created_at_ms, updated_at_ms)
-                 VALUES (?1, ?2, ?3, ?4, 'child', ?5, ?6, ?7, ?7)",
-                params![
-                    make_id("mission_run"),
-                    mission_id,
-                    group.child_key,
-                    run.id,
-                    group.label,
-                    run.state.as_str(),
-                    now_ms()
-                ],
-            )
-            .map_err(|e| format!("Failed to link child run to mission: {e}"))?;
-        created_children += 1;
-    }
+    let created_children =
+        match start_daily_brief_children(connection, &mission_id, &input.draft, provider_id) {
+            Ok(count) => count,
+            Err(err) => {
+                mark_mission_start_failed(connection, &mission_id, &err)?;
+                return Err(err);
+            }
+        };
 
     update_mission_status(
         connection,
@@ -314,7 +398,23 @@ pub fn start_mission(
     get_mission(connection, &mission_id)
 }
 
-pub fn list_missions(connection: &Connection, limit: usize) -> Result<Vec<MissionRecord>, String> {
+pub fn list_missions(
+    connection: &Connection,
+    limit: usize,
+    filter: &MissionListFilter,
+) -> Result<Vec<MissionRecord>, String> {
+    let status = filter
+        .status
+        .as_deref()
+        .map(|s| s.trim().to_ascii_lowercase())
+        .filter(|s| !s.is_empty())
+        .map(|s| MissionStatus::parse(&s).map(|status| status.as_str()))
+        .transpose()?;
+    if let (Some(after), Some(before)) = (filter.created_after_ms, filter.created_before_ms) {
+        if after >= before {
+            return Err("Mission date range start must be before its end.".to_string());
+        }
+    }
     let mut stmt = connection
         .prepare(
             "
@@ -325,6 +425,9 @@ pub fn list_missions(connection: &Connection, limit: usize) -> Result<Vec<Missio
                              JOIN runs r ON r.id = mr.run_id
                              WHERE mr.mission_id = m.id AND r.state IN ('succeeded','failed','blocked','canceled')), 0) AS terminal_count
             FROM missions m
+            WHERE (?2 IS NULL OR m.status = ?2)
+              AND (?3 IS NULL OR m.created_at_ms >= ?3)
+              AND (?4 IS NULL OR m.created_at_ms < ?4)
             ORDER BY m.updated_at_ms DESC
             LIMIT ?1
             ",
@@ -332,7 +435,15 @@ pub fn list_missions(connection: &Connection, limit: usize) -> Result<Vec<Missio
         .map_err(|e| format!("Failed to prepare missions list: {e}"))?;
 
     let rows = stmt
-        .query_map(params![limit as i64], map_mission_row)
+        .query_map(
+            params![
+                limit as i64,
+                status,
+                filter.created_after_ms,
+                filter.created_before_ms
+            ],
+            map_mission_row,
+        )
         .map_err(|e| format!("Failed to query missions: {e}"))?;
     let mut out = Vec::new();
     for row in rows {
@@ -418,12 +529,19 @@ pub fn get_mission(connection: &Connection, mission_id: &str) -> Result<MissionD
     }
 
     let contract = build_contract_status(&mission, &child_runs);
+    let stages = if mission.template_kind == MissionTemplateKind::MultiRecipe {
+        let config = load_staged_mission_config(connection, mission_id)?;
+        build_stage_progress(&config, &child_runs)
+    } else {
+        Vec::new()
+    };
 
     Ok(MissionDetail {
         mission,
         child_runs,
         events,
         contract,
+        stages,
     })
 }
 
@@ -443,6 +561,9 @@ pub fn run_mission_tick(
             child_runs_ticked,
         });
     }
+    if mission.mission.template_kind == MissionTemplateKind::MultiRecipe {
+        return run_staged_mission_tick(connection, mission);
+    }
 
     for child in &mission.child_runs {
         let Some(state_text) = child.run_state.as_deref() else {
@@ -450,7 +571,7 @@ pub fn run_mission_tick(
         };
         let run_state = RunState::from_str(state_text).map_err(|e| e.to_string())?;
         match run_state {
-            RunState::Ready | RunState::Running | RunState::Retrying => {
+            RunState::Ready | RunState::Running | RunState::Retrying | RunState::Paused => {
                 let _ =
                     RunnerEngine::run_tick(connection, &child.run_id).map_err(|e| e.to_string())?;
                 child_runs_ticked += 1;
@@ -567,6 +688,727 @@ pub fn run_mission_tick(
     })
 }
 
+/// Starts one Daily Brief child run per source group and links it to the mission.
+fn start_daily_brief_children(
+    connection: &mut Connection,
+    mission_id: &str,
+    draft: &MissionDraft,
+    provider_id: ProviderId,
+) -> Result<usize, String> {
+    let mut created_children = 0usize;
+    for group in &draft.source_groups {
+        let child_autopilot_id = format!("{}_{}", mission_id, group.child_key);
+        let child_idempotency_key = format!("mission:{}:{}", mission_id, group.child_key);
+        let plan = build_daily_brief_child_plan(&draft.intent, provider_id, &group.sources);
+        let run = RunnerEngine::start_run(
+            connection,
+            &child_autopilot_id,
+            plan,
+            &child_idempotency_key,
+            2,
+        )
+        .map_err(|e| e.to_string())?;
+
+        connection
+            .execute(
+                "INSERT INTO mission_runs (id, mission_id, child_key, run_id, run_role, source_label, status, created_at_ms, updated_at_ms)
+                 VALUES (?1, ?2, ?3, ?4, 'child', ?5, ?6, ?7, ?7)",
+                params![
+                    make_id("mission_run"),
+                    mission_id,
+                    group.child_key,
+                    run.id,
+                    group.label,
+                    run.state.as_str(),
+                    now_ms()
+                ],
+            )
+            .map_err(|e| format!("Failed to link child run to mission: {e}"))?;
+        created_children += 1;
+    }
+    Ok(created_children)
+}
+
+/// Runs start outside the mission's creation transaction, so a mission whose first runs could
+/// not be created is marked failed instead of waiting on runs that never existed.
+fn mark_mission_start_failed(
+    connection: &Connection,
+    mission_id: &str,
+    reason: &str,
+) -> Result<(), String> {
+    update_mission_status(
+        connection,
+        mission_id,
+        MissionStatus::Failed,
+        Some(reason),
+        None,
+        "Mission could not start its runs.",
+        json!({}),
+    )
+}
+
+fn start_staged_mission(
+    connection: &mut Connection,
+    input: StartMissionInput,
+) -> Result<MissionDetail, String> {
+    let provider_id = parse_provider(&input.draft.provider)?;
+    validate_mission_stages(&input.stages)?;
+    let mission_id = make_id("mission");
+    let mission_key = input.idempotency_key.unwrap_or_else(|| {
+        format!(
+            "mission:{}:{}",
+            MissionTemplateKind::MultiRecipe.as_str(),
+            mission_id
+        )
+    });
+    let now = now_ms();
+    let config = StagedMissionConfig {
+        intent: input.draft.intent.clone(),
+        provider: input.draft.provider.clone(),
+        stages: input.stages,
+    };
+    let config_json = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+
+    let tx = connection
+        .transaction()
+        .map_err(|e| format!("Failed to start mission transaction: {e}"))?;
+    tx.execute(
+        "INSERT INTO missions (id, template_kind, idempotency_key, status, provider_kind, config_json, summary_json, failure_reason, created_at_ms, updated_at_ms)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, NULL, NULL, ?7, ?7)",
+        params![
+            mission_id,
+            MissionTemplateKind::MultiRecipe.as_str(),
+            mission_key,
+            MissionStatus::Running.as_str(),
+            config.provider,
+            config_json,
+            now
+        ],
+    )
+    .map_err(|e| format!("Failed to create mission: {e}"))?;
+    insert_mission_event_tx(
+        &tx,
+        &mission_id,
+        "mission_started",
+        "Mission created. Starting the first stage.",
+        json!({"stageCount": config.stages.len()}),
+        now,
+    )?;
+    tx.commit()
+        .map_err(|e| format!("Failed to commit mission creation: {e}"))?;
+
+    if let Err(err) = start_mission_stage(connection, &mission_id, &config, provider_id, 0, &[]) {
+        mark_mission_start_failed(connection, &mission_id, &err)?;
+        return Err(err);
+    }
+    update_mission_status(
+        connection,
+        &mission_id,
+        MissionStatus::WaitingChildren,
+        None,
+        None,
+        "Stage 1 started. Waiting for it to finish.",
+        json!({"stageIndex": 0}),
+    )?;
+    get_mission(connection, &mission_id)
+}
+
+/// Advances a staged mission: ticks the current stage, and once it succeeds starts the next
+/// stage with the finished stage's outcomes as context.
+fn run_staged_mission_tick(
+    connection: &mut Connection,
+    mission: MissionDetail,
+) -> Result<MissionTickResult, String> {
+    let mission_id = mission.mission.id.clone();
+    let config = load_staged_mission_config(connection, &mission_id)?;
+    let provider_id = parse_provider(&config.provider)?;
+    let Some(current) = mission.child_runs.last() else {
+        return Err("Mission has no stage runs yet.".to_string());
+    };
+    let stage_index = mission.child_runs.len() - 1;
+    let stage_number = stage_index + 1;
+    let mut child_runs_ticked = 0usize;
+
+    let mut run = RunnerEngine::get_run(connection, &current.run_id).map_err(|e| e.to_string())?;
+    if matches!(
+        run.state,
+        RunState::Ready | RunState::Running | RunState::Retrying | RunState::Paused
+    ) {
+        run = RunnerEngine::run_tick(connection, &current.run_id).map_err(|e| e.to_string())?;
+        child_runs_ticked += 1;
+    }
+
+    match run.state {
+        RunState::NeedsApproval | RunState::NeedsClarification | RunState::Blocked => {
+            let detail =
+                format!("Stage {stage_number} requires attention before the mission can continue.");
+            update_mission_status(
+                connection,
+                &mission_id,
+                MissionStatus::Blocked,
+                Some(&detail),
+                None,
+                &detail,
+                json!({"stageIndex": stage_index}),
+            )?;
+        }
+        RunState::Failed | RunState::Canceled => {
+            update_mission_status(
+                connection,
+                &mission_id,
+                MissionStatus::Failed,
+                Some(
+                    "A mission stage failed. Review the stage receipt and retry the mission later.",
+                ),
+                None,
+                &format!("Mission failed because stage {stage_number} failed."),
+                json!({"stageIndex": stage_index}),
+            )?;
+        }
+        RunState::Succeeded if stage_number < config.stages.len() => {
+            let context =
+                stage_outcome_context(connection, &current.run_id, stage_number, &config)?;
+            start_mission_stage(
+                connection,
+                &mission_id,
+                &config,
+                provider_id,
+                stage_index + 1,
+                &context,
+            )?;
+            update_mission_status(
+                connection,
+                &mission_id,
+                MissionStatus::WaitingChildren,
+                None,
+                None,
+                &format!(
+                    "Stage {stage_number} finished. Stage {} started with its outcomes as context.",
+                    stage_number + 1
+                ),
+                json!({"stageIndex": stage_index + 1, "contextItems": context.len()}),
+            )?;
+        }
+        RunState::Succeeded => {
+            update_mission_status(
+                connection,
+                &mission_id,
+                MissionStatus::Aggregating,
+                None,
+                None,
+                "All stages completed. Building mission summary.",
+                json!({}),
+            )?;
+            let refreshed = get_mission(connection, &mission_id)?;
+            let summary = build_staged_mission_summary(connection, &refreshed, &config)?;
+            let summary_json = serde_json::to_string(&summary).map_err(|e| e.to_string())?;
+            update_mission_status(
+                connection,
+                &mission_id,
+                MissionStatus::Succeeded,
+                None,
+                Some(&summary_json),
+                "Mission aggregation complete.",
+                json!({"stages": config.stages.len()}),
+            )?;
+        }
+        RunState::Ready | RunState::Running | RunState::Retrying | RunState::Paused => {
+            update_mission_status(
+                connection,
+                &mission_id,
+                MissionStatus::WaitingChildren,
+                None,
+                None,
+                &format!("Mission tick complete. Waiting for stage {stage_number}."),
+                json!({"childRunsTicked": child_runs_ticked}),
+            )?;
+        }
+    }
+
+    let mission = get_mission(connection, &mission_id)?;
+    Ok(MissionTickResult {
+        mission,
+        child_runs_ticked,
+    })
+}
+
+fn start_mission_stage(
+    connection: &mut Connection,
+    mission_id: &str,
+    config: &StagedMissionConfig,
+    provider_id: ProviderId,
+    stage_index: usize,
+    context: &[String],
+) -> Result<(), String> {
+    let stage = config
+        .stages
+        .get(stage_index)
+        .ok_or_else(|| "Mission stage is out of range.".to_string())?;
+    let stage_key = format!("stage_{}", stage_index + 1);
+    let plan = build_stage_plan(stage, provider_id, context)?;
+    let run = RunnerEngine::start_run(
+        connection,
+        &format!("{mission_id}_{stage_key}"),
+        plan,
+        &format!("mission:{mission_id}:{stage_key}"),
+        2,
+    )
+    .map_err(|e| e.to_string())?;
+    connection
+        .execute(
+            "INSERT INTO mission_runs (id, mission_id, child_key, run_id, run_role, source_label, status, created_at_ms, updated_at_ms)
+             VALUES (?1, ?2, ?3, ?4, 'stage', ?5, ?6, ?7, ?7)",
+            params![
+                make_id("mission_run"),
+                mission_id,
+                stage_key,
+                run.id,
+                stage.recipe,
+                run.state.as_str(),
+                now_ms()
+            ],
+        )
+        .map_err(|e| format!("Failed to link stage run to mission: {e}"))?;
+    Ok(())
+}
+
+fn validate_mission_stages(stages: &[MissionStageInput]) -> Result<(), String> {
+    if stages.is_empty() {
+        return Err("Add at least one stage to this mission.".to_string());
+    }
+    if stages.len() > MAX_MISSION_STAGES {
+        return Err(format!(
+            "Keep missions to {MAX_MISSION_STAGES} stages or fewer."
+        ));
+    }
+    for (idx, stage) in stages.iter().enumerate() {
+        let recipe = parse_stage_recipe(&stage.recipe)?;
+        if stage.intent.trim().is_empty() {
+            return Err(format!("Stage {} needs an intent.", idx + 1));
+        }
+        if idx == 0 && recipe == RecipeKind::DailyBrief && stage.sources.is_empty() {
+            return Err("A daily brief first stage needs at least one source.".to_string());
+        }
+    }
+    Ok(())
+}
+
+fn parse_stage_recipe(value: &str) -> Result<RecipeKind, String> {
+    match value.trim() {
+        "inbox_triage" => Ok(RecipeKind::InboxTriage),
+        "daily_brief" => Ok(RecipeKind::DailyBrief),
+        other => Err(format!(
+            "Mission stages support inbox_triage and daily_brief, not '{other}'."
+        )),
+    }
+}
+
+/// Stage plans keep only their read and summarize steps, like fan-out children, so a stage
+/// never stalls on an approval gate. Earlier stages' outcomes are appended as input text.
+fn build_stage_plan(
+    stage: &MissionStageInput,
+    provider: ProviderId,
+    context: &[String],
+) -> Result<AutopilotPlan, String> {
+    let recipe = parse_stage_recipe(&stage.recipe)?;
+    let mut plan = AutopilotPlan::from_intent(
+        recipe,
+        format!("{} (mission stage)", stage.intent.trim()),
+        provider,
+    );
+    let keep: &[PrimitiveId] = match recipe {
+        RecipeKind::InboxTriage => {
+            let mut text = stage
+                .input_text
+                .as_deref()
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .unwrap_or(stage.intent.trim())
+                .to_string();
+            for item in context {
+                text.push_str("\n\n");
+                text.push_str(item);
+            }
+            plan.inbox_source_text = Some(text);
+            &[
+                PrimitiveId::ReadForwardedEmail,
+                PrimitiveId::WriteOutcomeDraft,
+            ]
+        }
+        _ => {
+            plan.daily_sources = stage
+                .sources
+                .iter()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .chain(context.iter().cloned())
+                .collect();
+            &[PrimitiveId::ReadSources, PrimitiveId::AggregateDailySummary]
+        }
+    };
+    plan.steps = plan
+        .steps
+        .into_iter()
+        .filter(|step| keep.contains(&step.primitive))
+        .map(strip_step_approval)
+        .collect::<Vec<PlanStep>>();
+    Ok(plan)
+}
+
+/// The finished stage's outcomes, oldest first, as inline text items for the next stage.
+fn stage_outcome_context(
+    connection: &Connection,
+    run_id: &str,
+    stage_number: usize,
+    config: &StagedMissionConfig,
+) -> Result<Vec<String>, String> {
+    let recipe = config
+        .stages
+        .get(stage_number - 1)
+        .map(|s| s.recipe.as_str())
+        .unwrap_or("stage");
+    let mut stmt = connection
+        .prepare(
+            "SELECT kind, content FROM outcomes
+             WHERE run_id = ?1
+             ORDER BY created_at ASC, rowid ASC
+             LIMIT ?2",
+        )
+        .map_err(|e| format!("Failed to prepare stage outcome query: {e}"))?;
+    let rows = stmt
+        .query_map(params![run_id, STAGE_CONTEXT_MAX_OUTCOMES as i64], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| format!("Failed to query stage outcomes: {e}"))?;
+    let mut context = Vec::new();
+    for row in rows {
+        let (kind, content) = row.map_err(|e| format!("Failed to parse stage outcome: {e}"))?;
+        let prefix = format!("Stage {stage_number} ({recipe}) {kind}: ");
+        let budget = STAGE_CONTEXT_MAX_CHARS.saturating_sub(prefix.chars().count());
+        context.push(format!("{prefix}{}", truncate(content.trim(), budget)));
+    }
+    Ok(context)
+}
+
+fn load_staged_mission_config(
+    connection: &Connection,
+    mission_id: &str,
+) -> Result<StagedMissionConfig, String> {
+    let config_json: String = connection
+        .query_row(
+            "SELECT config_json FROM missions WHERE id = ?1",
+            params![mission_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Mission not found: {e}"))?;
+    serde_json::from_str(&config_json).map_err(|e| format!("Mission stage config is invalid: {e}"))
+}
+
+fn build_stage_progress(
+    config: &StagedMissionConfig,
+    child_runs: &[MissionRunLink],
+) -> Vec<MissionStageProgress> {
+    config
+        .stages
+        .iter()
+        .enumerate()
+        .map(|(idx, stage)| {
+            let stage_key = format!("stage_{}", idx + 1);
+            let link = child_runs.iter().find(|c| c.child_key == stage_key);
+            MissionStageProgress {
+                stage_index: idx,
+                recipe: stage.recipe.clone(),
+                intent: stage.intent.clone(),
+                run_id: link.map(|c| c.run_id.clone()),
+                status: link
+                    .and_then(|c| c.run_state.clone())
+                    .unwrap_or_else(|| "pending".to_string()),
+                stage_key,
+            }
+        })
+        .collect()
+}
+
+fn build_staged_mission_summary(
+    connection: &Connection,
+    mission: &MissionDetail,
+    config: &StagedMissionConfig,
+) -> Result<Value, String> {
+    let mut stages = Vec::new();
+    for (idx, child) in mission.child_runs.iter().enumerate() {
+        let outcomes = stage_outcome_context(connection, &child.run_id, idx + 1, config)?;
+        stages.push(json!({
+            "stageKey": child.child_key,
+            "recipe": child.source_label,
+            "runId": child.run_id,
+            "outcomes": outcomes,
+        }));
+    }
+    Ok(json!({
+        "templateKind": mission.mission.template_kind,
+        "title": format!("Mission: {} stages completed", stages.len()),
+        "intent": config.intent,
+        "stages": stages,
+        "generatedAtMs": now_ms()
+    }))
+}
+
+/// Saves a draft (and optional stages) under a name. Saving an existing name replaces it.
+pub fn save_mission_template(
+    connection: &Connection,
+    input: SaveMissionTemplateInput,
+) -> Result<MissionTemplateRecord, String> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Give this mission template a name.".to_string());
+    }
+    if input.parameters.len() > MAX_TEMPLATE_PARAMETERS {
+        return Err(format!(
+            "Keep mission templates to {MAX_TEMPLATE_PARAMETERS} parameters or fewer."
+        ));
+    }
+    let mut parameters = Vec::new();
+    for raw in &input.parameters {
+        let parameter = raw.trim().to_string();
+        if !is_valid_parameter_name(&parameter) {
+            return Err(format!(
+                "Parameter '{parameter}' must use lowercase letters, digits, or underscores."
+            ));
+        }
+        if parameters.contains(&parameter) {
+            return Err(format!("Parameter '{parameter}' is declared twice."));
+        }
+        parameters.push(parameter);
+    }
+    for placeholder in template_placeholders(&input.draft, &input.stages) {
+        if !parameters.contains(&placeholder) {
+            return Err(format!(
+                "Placeholder '{{{{{placeholder}}}}}' is not a declared parameter."
+            ));
+        }
+    }
+    if input.draft.template_kind == MissionTemplateKind::MultiRecipe && input.stages.is_empty() {
+        return Err("Add at least one stage to this mission template.".to_string());
+    }
+
+    let now = now_ms();
+    let draft_json = serde_json::to_string(&input.draft).map_err(|e| e.to_string())?;
+    let stages_json = serde_json::to_string(&input.stages).map_err(|e| e.to_string())?;
+    let parameters_json = serde_json::to_string(&parameters).map_err(|e| e.to_string())?;
+    connection
+        .execute(
+            "INSERT INTO mission_templates (id, name, template_kind, draft_json, stages_json, parameters_json, created_at_ms, updated_at_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+             ON CONFLICT(name) DO UPDATE SET
+               template_kind = excluded.template_kind,
+               draft_json = excluded.draft_json,
+               stages_json = excluded.stages_json,
+               parameters_json = excluded.parameters_json,
+               updated_at_ms = excluded.updated_at_ms",
+            params![
+                make_id("mission_template"),
+                name,
+                input.draft.template_kind.as_str(),
+                draft_json,
+                stages_json,
+                parameters_json,
+                now
+            ],
+        )
+        .map_err(|e| format!("Failed to save mission template: {e}"))?;
+    let id: String = connection
+        .query_row(
+            "SELECT id FROM mission_templates WHERE name = ?1",
+            params![name],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to load saved mission template: {e}"))?;
+    get_mission_template(connection, &id)
+}
+
+pub fn list_mission_templates(
+    connection: &Connection,
+) -> Result<Vec<MissionTemplateRecord>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT id, name, template_kind, draft_json, stages_json, parameters_json, created_at_ms, updated_at_ms
+             FROM mission_templates
+             ORDER BY name ASC",
+        )
+        .map_err(|e| format!("Failed to prepare mission templates list: {e}"))?;
+    let rows = stmt
+        .query_map([], map_mission_template_row)
+        .map_err(|e| format!("Failed to query mission templates: {e}"))?;
+    let mut out = Vec::new();
+    for row in rows {
+        out.push(row.map_err(|e| format!("Failed to parse mission template row: {e}"))?);
+    }
+    Ok(out)
+}
+
+pub fn get_mission_template(
+    connection: &Connection,
+    template_id: &str,
+) -> Result<MissionTemplateRecord, String> {
+    connection
+        .query_row(
+            "SELECT id, name, template_kind, draft_json, stages_json, parameters_json, created_at_ms, updated_at_ms
+             FROM mission_templates
+             WHERE id = ?1",
+            params![template_id],
+            map_mission_template_row,
+        )
+        .optional()
+        .map_err(|e| format!("Failed to load mission template: {e}"))?
+        .ok_or_else(|| "Mission template not found.".to_string())
+}
+
+/// Fills a template's `{{name}}` placeholders. Every declared parameter needs a non-empty value
+/// and undeclared values are rejected, so a typo can't silently leave a placeholder behind.
+pub fn instantiate_mission_template(
+    connection: &Connection,
+    input: InstantiateMissionTemplateInput,
+) -> Result<MissionTemplateInstance, String> {
+    let template = get_mission_template(connection, &input.template_id)?;
+    if let Some(unknown) = input
+        .parameters
+        .keys()
+        .find(|key| !template.parameters.contains(key))
+    {
+        return Err(format!(
+            "'{unknown}' is not a parameter of the '{}' template.",
+            template.name
+        ));
+    }
+    for parameter in &template.parameters {
+        let provided = input
+            .parameters
+            .get(parameter)
+            .map(|value| value.trim())
+            .unwrap_or_default();
+        if provided.is_empty() {
+            return Err(format!("Provide a value for '{parameter}'."));
+        }
+    }
+
+    let fill = |text: &str| substitute_placeholders(text, &input.parameters);
+    let mut draft = template.draft;
+    draft.intent = fill(&draft.intent);
+    for group in &mut draft.source_groups {
+        group.label = fill(&group.label);
+        group.sources = group.sources.iter().map(|s| fill(s)).collect();
+    }
+    let stages = template
+        .stages
+        .into_iter()
+        .map(|stage| MissionStageInput {
+            recipe: stage.recipe,
+            intent: fill(&stage.intent),
+            sources: stage.sources.iter().map(|s| fill(s)).collect(),
+            input_text: stage.input_text.as_deref().map(fill),
+        })
+        .collect();
+
+    Ok(MissionTemplateInstance {
+        template_id: template.id,
+        draft,
+        stages,
+    })
+}
+
+fn map_mission_template_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<MissionTemplateRecord> {
+    fn json_column<T: serde::de::DeserializeOwned>(
+        row: &rusqlite::Row<'_>,
+        idx: usize,
+    ) -> rusqlite::Result<T> {
+        let raw: String = row.get(idx)?;
+        serde_json::from_str(&raw).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(idx, rusqlite::types::Type::Text, Box::new(e))
+        })
+    }
+    let template_kind: String = row.get(2)?;
+    Ok(MissionTemplateRecord {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        template_kind: MissionTemplateKind::parse(&template_kind).map_err(|_| {
+            rusqlite::Error::InvalidColumnType(
+                2,
+                "template_kind".to_string(),
+                rusqlite::types::Type::Text,
+            )
+        })?,
+        draft: json_column(row, 3)?,
+        stages: json_column(row, 4)?,
+        parameters: json_column(row, 5)?,
+        created_at_ms: row.get(6)?,
+        updated_at_ms: row.get(7)?,
+    })
+}
+
+fn is_valid_parameter_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn template_placeholders(draft: &MissionDraft, stages: &[MissionStageInput]) -> Vec<String> {
+    let mut texts: Vec<&str> = vec![draft.intent.as_str()];
+    for group in &draft.source_groups {
+        texts.push(&group.label);
+        texts.extend(group.sources.iter().map(String::as_str));
+    }
+    for stage in stages {
+        texts.push(&stage.intent);
+        texts.extend(stage.sources.iter().map(String::as_str));
+        if let Some(input_text) = &stage.input_text {
+            texts.push(input_text);
+        }
+    }
+    let mut found = Vec::new();
+    for text in texts {
+        for name in placeholders_in(text) {
+            if !found.contains(&name) {
+                found.push(name);
+            }
+        }
+    }
+    found
+}
+
+fn placeholders_in(text: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        out.push(after[..end].trim().to_string());
+        rest = &after[end + 2..];
+    }
+    out
+}
+
+fn substitute_placeholders(text: &str, values: &BTreeMap<String, String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        match values.get(after[..end].trim()) {
+            Some(value) => out.push_str(value.trim()),
+            None => out.push_str(&rest[start..start + end + 4]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
 fn map_mission_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<MissionRecord> {
     let template_kind: String = row.get(1)?;
     let status: String = row.get(2)?;
@@ -924,6 +1766,7 @@ mod tests {
             StartMissionInput {
                 draft: sample_draft(),
                 idempotency_key: Some("mission-idem-1".to_string()),
+                stages: Vec::new(),
             },
         )
         .expect("start");
@@ -946,6 +1789,36 @@ mod tests {
         assert_eq!(unique_keys, 2);
     }
 
+    #[test]
+    fn mission_that_cannot_start_its_runs_is_marked_failed() {
+        std::env::set_var("TERMINUS_TRANSPORT", "mock");
+        let mut conn = test_conn();
+        conn.execute_batch(
+            "CREATE TEMP TRIGGER reject_mission_links BEFORE INSERT ON mission_runs
+             BEGIN SELECT RAISE(ABORT, 'link rejected'); END;",
+        )
+        .expect("trigger");
+        let err = start_mission(
+            &mut conn,
+            StartMissionInput {
+                draft: sample_draft(),
+                idempotency_key: Some("mission-idem-fail".to_string()),
+                stages: Vec::new(),
+            },
+        )
+        .expect_err("link fails");
+        assert!(err.contains("link rejected"));
+        let (status, failure_reason): (String, Option<String>) = conn
+            .query_row(
+                "SELECT status, failure_reason FROM missions WHERE idempotency_key = 'mission-idem-fail'",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .expect("mission row");
+        assert_eq!(status, MissionStatus::Failed.as_str());
+        assert!(failure_reason.is_some_and(|reason| reason.contains("link rejected")));
+    }
+
     #[test]
     fn mission_waits_until_children_terminal_then_aggregates() {
         std::env::set_var("TERMINUS_TRANSPORT", "mock");
@@ -955,6 +1828,7 @@ mod tests {
             StartMissionInput {
                 draft: sample_draft(),
                 idempotency_key: None,
+                stages: Vec::new(),
             },
         )
         .expect("start");
@@ -995,6 +1869,7 @@ mod tests {
             StartMissionInput {
                 draft: sample_draft(),
                 idempotency_key: None,
+                stages: Vec::new(),
             },
         )
         .expect("start");
@@ -1008,4 +1883,228 @@ mod tests {
         assert_eq!(tick.mission.mission.status, MissionStatus::Blocked);
         assert!(tick.mission.contract.has_blocked_or_pending_child);
     }
+
+    #[test]
+    fn staged_mission_passes_first_stage_outcomes_to_second_stage() {
+        std::env::set_var("TERMINUS_TRANSPORT", "mock");
+        let mut conn = test_conn();
+        let mut draft = sample_draft();
+        draft.template_kind = MissionTemplateKind::MultiRecipe;
+        let started = start_mission(
+            &mut conn,
+            StartMissionInput {
+                draft,
+                idempotency_key: None,
+                stages: vec![
+                    MissionStageInput {
+                        recipe: "inbox_triage".to_string(),
+                        intent: "Triage the vendor thread".to_string(),
+                        sources: Vec::new(),
+                        input_text: Some(
+                            "From: vendor@example.com\nSubject: Renewal quote due Friday"
+                                .to_string(),
+                        ),
+                    },
+                    MissionStageInput {
+                        recipe: "daily_brief".to_string(),
+                        intent: "Brief me on the triaged items".to_string(),
+                        sources: Vec::new(),
+                        input_text: None,
+                    },
+                ],
+            },
+        )
+        .expect("start");
+        assert_eq!(
+            started.mission.template_kind,
+            MissionTemplateKind::MultiRecipe
+        );
+        assert_eq!(started.stages.len(), 2);
+        assert_eq!(started.stages[1].status, "pending");
+        assert!(started.stages[1].run_id.is_none());
+
+        let mut detail = started;
+        for _ in 0..10 {
+            if detail.child_runs.len() == 2 {
+                break;
+            }
+            detail = run_mission_tick(&mut conn, &detail.mission.id)
+                .expect("tick")
+                .mission;
+        }
+        assert_eq!(detail.child_runs.len(), 2, "second stage should start");
+        assert_eq!(detail.stages[0].status, "succeeded");
+
+        let stage_one_outcome: String = conn
+            .query_row(
+                "SELECT content FROM outcomes WHERE run_id = ?1 ORDER BY created_at ASC, rowid ASC LIMIT 1",
+                params![detail.child_runs[0].run_id],
+                |r| r.get(0),
+            )
+            .expect("stage one outcome");
+        let second = RunnerEngine::get_run(&conn, &detail.child_runs[1].run_id).expect("run");
+        let context = second
+            .plan
+            .daily_sources
+            .iter()
+            .find(|s| s.starts_with("Stage 1 (inbox_triage)"))
+            .expect("stage one context");
+        let excerpt: String = stage_one_outcome.trim().chars().take(40).collect();
+        assert!(context.contains(&excerpt));
+
+        for _ in 0..10 {
+            if detail.mission.status == MissionStatus::Succeeded {
+                break;
+            }
+            detail = run_mission_tick(&mut conn, &detail.mission.id)
+                .expect("tick")
+                .mission;
+        }
+        assert_eq!(detail.mission.status, MissionStatus::Succeeded);
+        assert!(detail
+            .stages
+            .iter()
+            .all(|stage| stage.status == "succeeded"));
+        assert!(detail.contract.aggregation_summary_exists);
+    }
+
+    #[test]
+    fn mission_template_saves_lists_and_instantiates_with_parameters() {
+        let conn = test_conn();
+        let mut draft = sample_draft();
+        draft.intent = "Brief me for {{target_date}}".to_string();
+        draft.source_groups[0].sources =
+            vec!["Inline note: updates for {{ recipient }}".to_string()];
+        let saved = save_mission_template(
+            &conn,
+            SaveMissionTemplateInput {
+                name: "Weekly brief".to_string(),
+                draft: draft.clone(),
+                stages: Vec::new(),
+                parameters: vec!["target_date".to_string(), "recipient".to_string()],
+            },
+        )
+        .expect("save");
+        assert_eq!(saved.parameters, vec!["target_date", "recipient"]);
+
+        let undeclared = save_mission_template(
+            &conn,
+            SaveMissionTemplateInput {
+                name: "Broken".to_string(),
+                draft,
+                stages: Vec::new(),
+                parameters: vec!["target_date".to_string()],
+            },
+        )
+        .expect_err("undeclared placeholder rejected");
+        assert!(undeclared.contains("recipient"));
+
+        let listed = list_mission_templates(&conn).expect("list");
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, saved.id);
+        assert_eq!(
+            listed[0].template_kind,
+            MissionTemplateKind::DailyBriefMultiSource
+        );
+
+        let missing = instantiate_mission_template(
+            &conn,
+            InstantiateMissionTemplateInput {
+                template_id: saved.id.clone(),
+                parameters: BTreeMap::from([("target_date".to_string(), "2026-10-23".to_string())]),
+            },
+        )
+        .expect_err("missing parameter rejected");
+        assert!(missing.contains("recipient"));
+
+        let unknown = instantiate_mission_template(
+            &conn,
+            InstantiateMissionTemplateInput {
+                template_id: saved.id.clone(),
+                parameters: BTreeMap::from([
+                    ("target_date".to_string(), "2026-10-23".to_string()),
+                    ("recipient".to_string(), "Dana".to_string()),
+                    ("extra".to_string(), "x".to_string()),
+                ]),
+            },
+        )
+        .expect_err("unknown parameter rejected");
+        assert!(unknown.contains("extra"));
+
+        let instance = instantiate_mission_template(
+            &conn,
+            InstantiateMissionTemplateInput {
+                template_id: saved.id,
+                parameters: BTreeMap::from([
+                    ("target_date".to_string(), "2026-10-23".to_string()),
+                    ("recipient".to_string(), "Dana".to_string()),
+                ]),
+            },
+        )
+        .expect("instantiate");
+        assert_eq!(instance.draft.intent, "Brief me for 2026-10-23");
+        assert_eq!(
+            instance.draft.source_groups[0].sources,
+            vec!["Inline note: updates for Dana".to_string()]
+        );
+        assert_eq!(
+            instance.draft.source_groups[1].sources,
+            sample_draft().source_groups[1].sources
+        );
+    }
+
+    #[test]
+    fn list_missions_filters_by_state_and_creation_window() {
+        let conn = test_conn();
+        for (id, status, created_at_ms) in [
+            ("mission_a", "succeeded", 1_000),
+            ("mission_b", "failed", 2_000),
+            ("mission_c", "succeeded", 3_000),
+        ] {
+            conn.execute(
+                "INSERT INTO missions (id, template_kind, idempotency_key, status, created_at_ms, updated_at_ms)
+                 VALUES (?1, 'daily_brief_multi_source', ?1, ?2, ?3, ?3)",
+                params![id, status, created_at_ms],
+            )
+            .expect("seed mission");
+        }
+
+        let all = list_missions(&conn, 20, &MissionListFilter::default()).expect("all");
+        assert_eq!(all.len(), 3);
+
+        let succeeded = MissionListFilter {
+            status: Some(" Succeeded ".to_string()),
+            ..Default::default()
+        };
+        let ids = list_missions(&conn, 20, &succeeded)
+            .expect("succeeded")
+            .into_iter()
+            .map(|m| m.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["mission_c", "mission_a"]);
+
+        let windowed = MissionListFilter {
+            status: Some("succeeded".to_string()),
+            created_after_ms: Some(2_000),
+            created_before_ms: Some(4_000),
+        };
+        let ids = list_missions(&conn, 20, &windowed)
+            .expect("windowed")
+            .into_iter()
+            .map(|m| m.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["mission_c"]);
+
+        let unknown = MissionListFilter {
+            status: Some("paused".to_string()),
+            ..Default::default()
+        };
+        assert!(list_missions(&conn, 20, &unknown).is_err());
+        let inverted = MissionListFilter {
+            created_after_ms: Some(5_000),
+            created_before_ms: Some(1_000),
+            ..Default::default()
+        };
+        assert!(list_missions(&conn, 20, &inverted).is_err());
+    }
 }
@@ -1,10 +1,33 @@
 use crate::schema::PrimitiveId;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
+const PRIMITIVE_GUARD_LOG_MAX_ROWS: i64 = 5_000;
+const PRIMITIVE_GUARD_LOG_LIST_LIMIT: i64 = 200;
+const ALLOW_REASON: &str = "Action is on this Autopilot's allowlist.";
+
+static GUARD_LOG_COUNTER: AtomicU64 = AtomicU64::new(1);
+
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum PrimitiveGuardError {
     #[error("This action isn't allowed in Terminus yet.")]
     NotAllowed,
+    #[error("Terminus couldn't record the action check: {0}")]
+    Audit(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PrimitiveGuardEvent {
+    pub id: String,
+    pub run_id: String,
+    pub step_id: String,
+    pub primitive: PrimitiveId,
+    pub decision: String,
+    pub reason: String,
+    pub created_at_ms: i64,
 }
 
 #[derive(Debug, Clone)]
@@ -24,12 +47,120 @@ impl PrimitiveGuard {
             Err(PrimitiveGuardError::NotAllowed)
         }
     }
+
+    /// Same as `validate`, but records the allow/deny decision in `primitive_guard_log`.
+    pub fn validate_logged(
+        &self,
+        connection: &Connection,
+        run_id: &str,
+        step_id: &str,
+        primitive: PrimitiveId,
+    ) -> Result<(), PrimitiveGuardError> {
+        let result = self.validate(primitive);
+        let (decision, reason) = match &result {
+            Ok(()) => ("allow", ALLOW_REASON.to_string()),
+            Err(error) => ("deny", error.to_string()),
+        };
+        let primitive_key = serde_json::to_value(primitive)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        connection
+            .execute(
+                "INSERT INTO primitive_guard_log (id, run_id, step_id, primitive, decision, reason, created_at_ms)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    make_guard_log_id(),
+                    run_id,
+                    step_id,
+                    primitive_key,
+                    decision,
+                    reason,
+                    now_ms()
+                ],
+            )
+            .map_err(|e| PrimitiveGuardError::Audit(e.to_string()))?;
+        result
+    }
+}
+
+pub fn list_primitive_guard_events(
+    connection: &Connection,
+    run_id: &str,
+) -> Result<Vec<PrimitiveGuardEvent>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT id, run_id, step_id, primitive, decision, reason, created_at_ms
+             FROM primitive_guard_log
+             WHERE run_id = ?1
+             ORDER BY created_at_ms ASC, id ASC
+             LIMIT ?2",
+        )
+        .map_err(|e| format!("Failed to prepare primitive guard log query: {e}"))?;
+    let rows = stmt
+        .query_map(params![run_id, PRIMITIVE_GUARD_LOG_LIST_LIMIT], |row| {
+            let primitive_key: String = row.get(3)?;
+            let primitive = serde_json::from_value(serde_json::Value::String(primitive_key))
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+            Ok(PrimitiveGuardEvent {
+                id: row.get(0)?,
+                run_id: row.get(1)?,
+                step_id: row.get(2)?,
+                primitive,
+                decision: row.get(4)?,
+                reason: row.get(5)?,
+                created_at_ms: row.get(6)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query primitive guard log: {e}"))?;
+    let mut out = Vec::new();
+    for row in rows {
+        out.push(row.map_err(|e| format!("Failed to parse primitive guard log row: {e}"))?);
+    }
+    Ok(out)
+}
+
+/// Keeps only the newest `PRIMITIVE_GUARD_LOG_MAX_ROWS` guard decisions.
+/// Returns how many rows were (or, on a dry run, would be) removed.
+pub fn prune_primitive_guard_log(connection: &Connection, dry_run: bool) -> Result<i64, String> {
+    let total: i64 = connection
+        .query_row("SELECT COUNT(*) FROM primitive_guard_log", [], |row| {
+            row.get(0)
+        })
+        .map_err(|e| format!("Failed to count primitive guard log: {e}"))?;
+    let excess = (total - PRIMITIVE_GUARD_LOG_MAX_ROWS).max(0);
+    if dry_run || excess == 0 {
+        return Ok(excess);
+    }
+    connection
+        .execute(
+            "DELETE FROM primitive_guard_log WHERE id IN (
+               SELECT id FROM primitive_guard_log ORDER BY created_at_ms ASC, id ASC LIMIT ?1
+             )",
+            params![excess],
+        )
+        .map_err(|e| format!("Failed to prune primitive guard log: {e}"))?;
+    Ok(excess)
+}
+
+fn make_guard_log_id() -> String {
+    let n = GUARD_LOG_COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("pguard_{}_{}", now_ms(), n)
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{PrimitiveGuard, PrimitiveGuardError};
+    use super::{list_primitive_guard_events, PrimitiveGuard, PrimitiveGuardError};
+    use crate::db::bootstrap_schema;
     use crate::schema::PrimitiveId;
+    use rusqlite::Connection;
 
     #[test]
     fn allows_known_primitive_when_in_allowlist() {
@@ -47,4 +178,29 @@ mod tests {
             "This action isn't allowed in Terminus yet."
         );
     }
+
+    #[test]
+    fn denied_read_vault_file_is_logged() {
+        let mut conn = Connection::open_in_memory().expect("open memory db");
+        bootstrap_schema(&mut conn).expect("bootstrap schema");
+        let guard = PrimitiveGuard::new(vec![PrimitiveId::WriteOutcomeDraft]);
+
+        assert_eq!(
+            guard.validate_logged(&conn, "run_guard", "step_1", PrimitiveId::WriteOutcomeDraft),
+            Ok(())
+        );
+        assert_eq!(
+            guard.validate_logged(&conn, "run_guard", "step_2", PrimitiveId::ReadVaultFile),
+            Err(PrimitiveGuardError::NotAllowed)
+        );
+
+        let events = list_primitive_guard_events(&conn, "run_guard").expect("list");
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].decision, "allow");
+        let denied = &events[1];
+        assert_eq!(denied.step_id, "step_2");
+        assert_eq!(denied.primitive, PrimitiveId::ReadVaultFile);
+        assert_eq!(denied.decision, "deny");
+        assert_eq!(denied.reason, "This action isn't allowed in Terminus yet.");
+    }
 }
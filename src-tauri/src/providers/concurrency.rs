@@ -0,0 +1,184 @@
+use crate::providers::types::ProviderKind;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Condvar, Mutex, OnceLock};
+
+/// Default number of simultaneous dispatches allowed per provider.
+pub const DEFAULT_MAX_CONCURRENT_DISPATCHES: usize = 2;
+const MAX_CONCURRENT_DISPATCHES_LIMIT: usize = 16;
+
+/// Point-in-time view of one provider's dispatch slots.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderConcurrencySnapshot {
+    pub provider: String,
+    pub in_flight: usize,
+    pub max_concurrent: usize,
+}
+
+/// Counting semaphore keyed by provider. Dispatches beyond the cap wait for a slot instead
+/// of flooding the provider when many runs resume at once.
+pub struct ProviderConcurrencyLimiter {
+    max_concurrent: usize,
+    in_flight: Mutex<HashMap<ProviderKind, usize>>,
+    released: Condvar,
+}
+
+impl ProviderConcurrencyLimiter {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            max_concurrent: max_concurrent.clamp(1, MAX_CONCURRENT_DISPATCHES_LIMIT),
+            in_flight: Mutex::new(HashMap::new()),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Process-wide limiter. `TERMINUS_PROVIDER_MAX_CONCURRENCY` overrides the default cap.
+    pub fn global() -> &'static Self {
+        static LIMITER: OnceLock<ProviderConcurrencyLimiter> = OnceLock::new();
+        LIMITER.get_or_init(|| {
+            let max_concurrent = std::env::var("TERMINUS_PROVIDER_MAX_CONCURRENCY")
+                .ok()
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .unwrap_or(DEFAULT_MAX_CONCURRENT_DISPATCHES);
+            Self::new(max_concurrent)
+        })
+    }
+
+    /// Runs `f` once a slot for `provider` is free, releasing the slot afterwards even if
+    /// `f` panics.
+    pub fn run<T>(&self, provider: ProviderKind, f: impl FnOnce() -> T) -> T {
+        let _permit = self.acquire(provider);
+        f()
+    }
+
+    pub fn in_flight(&self, provider: ProviderKind) -> usize {
+        self.in_flight
+            .lock()
+            .map(|slots| slots.get(&provider).copied().unwrap_or(0))
+            .unwrap_or(0)
+    }
+
+    pub fn snapshot(&self) -> Vec<ProviderConcurrencySnapshot> {
+        [
+            ProviderKind::OpenAi,
+            ProviderKind::Anthropic,
+            ProviderKind::Gemini,
+        ]
+        .into_iter()
+        .map(|provider| ProviderConcurrencySnapshot {
+            provider: provider.as_str().to_string(),
+            in_flight: self.in_flight(provider),
+            max_concurrent: self.max_concurrent,
+        })
+        .collect()
+    }
+
+    fn acquire(&self, provider: ProviderKind) -> Permit<'_> {
+        let mut slots = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        while slots.get(&provider).copied().unwrap_or(0) >= self.max_concurrent {
+            slots = self
+                .released
+                .wait(slots)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        *slots.entry(provider).or_insert(0) += 1;
+        Permit {
+            limiter: self,
+            provider,
+        }
+    }
+}
+
+struct Permit<'a> {
+    limiter: &'a ProviderConcurrencyLimiter,
+    provider: ProviderKind,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        let mut slots = self
+            .limiter
+            .in_flight
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(count) = slots.get_mut(&self.provider) {
+            *count = count.saturating_sub(1);
+        }
+        drop(slots);
+        self.limiter.released.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProviderConcurrencyLimiter;
+    use crate::providers::types::ProviderKind;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Stands in for a transport that holds the connection open for a while.
+    struct BlockingMock {
+        current: AtomicUsize,
+        peak: AtomicUsize,
+        calls: AtomicUsize,
+    }
+
+    impl BlockingMock {
+        fn dispatch(&self) {
+            let now = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(30));
+            self.current.fetch_sub(1, Ordering::SeqCst);
+            self.calls.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn cap_bounds_simultaneous_dispatches_per_provider() {
+        let limiter = Arc::new(ProviderConcurrencyLimiter::new(2));
+        let openai = Arc::new(BlockingMock {
+            current: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            calls: AtomicUsize::new(0),
+        });
+        let anthropic = Arc::new(BlockingMock {
+            current: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            calls: AtomicUsize::new(0),
+        });
+
+        let mut handles = Vec::new();
+        for i in 0..8 {
+            let limiter = limiter.clone();
+            let (provider, mock) = if i % 4 == 3 {
+                (ProviderKind::Anthropic, anthropic.clone())
+            } else {
+                (ProviderKind::OpenAi, openai.clone())
+            };
+            handles.push(thread::spawn(move || {
+                limiter.run(provider, || mock.dispatch());
+            }));
+        }
+        thread::sleep(Duration::from_millis(10));
+        assert!(limiter.in_flight(ProviderKind::OpenAi) <= 2);
+        for handle in handles {
+            handle.join().expect("dispatch thread");
+        }
+
+        assert_eq!(openai.calls.load(Ordering::SeqCst), 6);
+        assert_eq!(openai.peak.load(Ordering::SeqCst), 2);
+        assert_eq!(anthropic.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(limiter.in_flight(ProviderKind::OpenAi), 0);
+        let snapshot = limiter.snapshot();
+        assert_eq!(snapshot.len(), 3);
+        assert!(snapshot
+            .iter()
+            .all(|s| s.in_flight == 0 && s.max_concurrent == 2));
+    }
+}
@@ -1,5 +1,13 @@
 use crate::providers::types::{ProviderError, ProviderKind};
+use base64::Engine as _;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
+#[cfg(test)]
+use std::cell::RefCell;
+#[cfg(test)]
+use std::collections::HashMap;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -11,11 +19,21 @@ pub const RELAY_CALLBACK_SECRET_SERVICE: &str = "terminus.relay.callback_secret"
 pub const RELAY_CALLBACK_SECRET_ACCOUNT: &str = "TerminusRelayCallback";
 pub const RELAY_DEVICE_ID_SERVICE: &str = "terminus.relay.device_id";
 pub const RELAY_DEVICE_ID_ACCOUNT: &str = "TerminusRelayDevice";
+pub const LOCAL_TRANSPORT_BASE_URL_SERVICE: &str = "terminus.local_transport.base_url";
+pub const LOCAL_TRANSPORT_BASE_URL_ACCOUNT: &str = "TerminusLocalTransport";
 pub const API_KEY_REF_SERVICE_PREFIX: &str = "terminus.api_key_ref.";
 pub const API_KEY_REF_ACCOUNT: &str = "TerminusApiKeyRef";
 pub const WEBHOOK_TRIGGER_SECRET_SERVICE_PREFIX: &str = "terminus.webhook_trigger_secret";
 pub const CODEX_OAUTH_BUNDLE_SERVICE: &str = "terminus.openai.codex_oauth_bundle";
 pub const CODEX_OAUTH_BUNDLE_ACCOUNT: &str = "TerminusOpenAiCodexOAuth";
+const WEBHOOK_TRIGGER_SECRET_ACCOUNT: &str = "TerminusWebhookTrigger";
+
+const KEYCHAIN_BUNDLE_VERSION: u32 = 1;
+const KEYCHAIN_BUNDLE_KDF: &str = "pbkdf2-hmac-sha256";
+// Debug builds hash slowly; tests only need the format, not the work factor.
+const KEYCHAIN_BUNDLE_KDF_ROUNDS: u32 = if cfg!(test) { 1_000 } else { 600_000 };
+const KEYCHAIN_BUNDLE_MAX_KDF_ROUNDS: u32 = 5_000_000;
+pub const KEYCHAIN_BUNDLE_MIN_PASSPHRASE_CHARS: usize = 12;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CodexOauthBundle {
@@ -178,6 +196,17 @@ pub fn set_secret(service: &str, account: &str, secret: &str) -> Result<(), Prov
             "Secret cannot be empty for Keychain storage.",
         ));
     }
+    #[cfg(test)]
+    if with_scoped_keychain(|store| {
+        store.insert(
+            (service.to_string(), account.to_string()),
+            secret.to_string(),
+        )
+    })
+    .is_some()
+    {
+        return Ok(());
+    }
 
     let mut child = Command::new("security")
         .arg("add-generic-password")
@@ -216,7 +245,46 @@ pub fn set_secret(service: &str, account: &str, secret: &str) -> Result<(), Prov
     ))
 }
 
+#[cfg(test)]
+thread_local! {
+    static SCOPED_KEYCHAIN: RefCell<Option<HashMap<(String, String), String>>> =
+        const { RefCell::new(None) };
+}
+
+/// Backs Keychain reads and writes on the current thread with an in-memory store until the
+/// guard is dropped, so tests can exercise code paths that need a stored secret.
+#[cfg(test)]
+pub fn scoped_test_keychain() -> ScopedTestKeychain {
+    SCOPED_KEYCHAIN.with(|slot| *slot.borrow_mut() = Some(HashMap::new()));
+    ScopedTestKeychain
+}
+
+#[cfg(test)]
+pub struct ScopedTestKeychain;
+
+#[cfg(test)]
+impl Drop for ScopedTestKeychain {
+    fn drop(&mut self) {
+        SCOPED_KEYCHAIN.with(|slot| *slot.borrow_mut() = None);
+    }
+}
+
+#[cfg(test)]
+fn with_scoped_keychain<T>(
+    f: impl FnOnce(&mut HashMap<(String, String), String>) -> T,
+) -> Option<T> {
+    SCOPED_KEYCHAIN.with(|slot| slot.borrow_mut().as_mut().map(f))
+}
+
 pub fn get_secret(service: &str, account: &str) -> Result<Option<String>, ProviderError> {
+    #[cfg(test)]
+    if let Some(value) = with_scoped_keychain(|store| {
+        store
+            .get(&(service.to_string(), account.to_string()))
+            .cloned()
+    }) {
+        return Ok(value);
+    }
     let output = Command::new("security")
         .arg("find-generic-password")
         .arg("-a")
@@ -252,6 +320,12 @@ pub fn get_secret(service: &str, account: &str) -> Result<Option<String>, Provid
 }
 
 pub fn delete_secret(service: &str, account: &str) -> Result<(), ProviderError> {
+    #[cfg(test)]
+    if with_scoped_keychain(|store| store.remove(&(service.to_string(), account.to_string())))
+        .is_some()
+    {
+        return Ok(());
+    }
     let output = Command::new("security")
         .arg("delete-generic-password")
         .arg("-a")
@@ -349,6 +423,28 @@ pub fn delete_relay_subscriber_token() -> Result<(), ProviderError> {
     )
 }
 
+pub fn get_local_transport_base_url() -> Result<Option<String>, ProviderError> {
+    get_secret(
+        LOCAL_TRANSPORT_BASE_URL_SERVICE,
+        LOCAL_TRANSPORT_BASE_URL_ACCOUNT,
+    )
+}
+
+pub fn set_local_transport_base_url(base_url: &str) -> Result<(), ProviderError> {
+    set_secret(
+        LOCAL_TRANSPORT_BASE_URL_SERVICE,
+        LOCAL_TRANSPORT_BASE_URL_ACCOUNT,
+        base_url,
+    )
+}
+
+pub fn delete_local_transport_base_url() -> Result<(), ProviderError> {
+    delete_secret(
+        LOCAL_TRANSPORT_BASE_URL_SERVICE,
+        LOCAL_TRANSPORT_BASE_URL_ACCOUNT,
+    )
+}
+
 pub fn get_relay_callback_secret() -> Result<Option<String>, ProviderError> {
     get_secret(RELAY_CALLBACK_SECRET_SERVICE, RELAY_CALLBACK_SECRET_ACCOUNT)
 }
@@ -396,14 +492,14 @@ fn webhook_trigger_secret_service(trigger_id: &str) -> String {
 pub fn get_webhook_trigger_secret(trigger_id: &str) -> Result<Option<String>, ProviderError> {
     get_secret(
         &webhook_trigger_secret_service(trigger_id),
-        "TerminusWebhookTrigger",
+        WEBHOOK_TRIGGER_SECRET_ACCOUNT,
     )
 }
 
 pub fn set_webhook_trigger_secret(trigger_id: &str, secret: &str) -> Result<(), ProviderError> {
     set_secret(
         &webhook_trigger_secret_service(trigger_id),
-        "TerminusWebhookTrigger",
+        WEBHOOK_TRIGGER_SECRET_ACCOUNT,
         secret,
     )
 }
@@ -411,15 +507,266 @@ pub fn set_webhook_trigger_secret(trigger_id: &str, secret: &str) -> Result<(),
 pub fn delete_webhook_trigger_secret(trigger_id: &str) -> Result<(), ProviderError> {
     delete_secret(
         &webhook_trigger_secret_service(trigger_id),
-        "TerminusWebhookTrigger",
+        WEBHOOK_TRIGGER_SECRET_ACCOUNT,
     )
 }
 
+/// One Keychain item carried in a backup bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeychainBundleEntry {
+    pub service: String,
+    pub account: String,
+    pub secret: String,
+}
+
+/// On-disk backup format. Only the KDF parameters are in the clear; every secret lives
+/// inside `ciphertext`.
+#[derive(Debug, Serialize, Deserialize)]
+struct SealedKeychainBundle {
+    version: u32,
+    kdf: String,
+    rounds: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Reads every Terminus-managed Keychain item that is currently set. API key refs and
+/// webhook triggers can't be enumerated from the Keychain, so callers pass the known names.
+pub fn collect_managed_keychain_entries(
+    api_key_refs: &[String],
+    webhook_trigger_ids: &[String],
+) -> Result<Vec<KeychainBundleEntry>, ProviderError> {
+    let mut items = vec![
+        (
+            RELAY_SUBSCRIBER_TOKEN_SERVICE.to_string(),
+            RELAY_SUBSCRIBER_TOKEN_ACCOUNT,
+        ),
+        (
+            RELAY_CALLBACK_SECRET_SERVICE.to_string(),
+            RELAY_CALLBACK_SECRET_ACCOUNT,
+        ),
+        (RELAY_DEVICE_ID_SERVICE.to_string(), RELAY_DEVICE_ID_ACCOUNT),
+        (
+            LOCAL_TRANSPORT_BASE_URL_SERVICE.to_string(),
+            LOCAL_TRANSPORT_BASE_URL_ACCOUNT,
+        ),
+        (
+            CODEX_OAUTH_BUNDLE_SERVICE.to_string(),
+            CODEX_OAUTH_BUNDLE_ACCOUNT,
+        ),
+    ];
+    items.extend(
+        api_key_refs
+            .iter()
+            .map(|name| (api_key_ref_service(name), API_KEY_REF_ACCOUNT)),
+    );
+    items.extend(webhook_trigger_ids.iter().map(|id| {
+        (
+            webhook_trigger_secret_service(id),
+            WEBHOOK_TRIGGER_SECRET_ACCOUNT,
+        )
+    }));
+
+    let mut entries = Vec::new();
+    for (service, account) in items {
+        if entries
+            .iter()
+            .any(|e: &KeychainBundleEntry| e.service == service)
+        {
+            continue;
+        }
+        if let Some(secret) = get_secret(&service, account)? {
+            entries.push(KeychainBundleEntry {
+                service,
+                account: account.to_string(),
+                secret,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// Writes bundle entries back to the Keychain. Entries outside Terminus' managed services are
+/// rejected up front so a crafted bundle can't overwrite unrelated Keychain items.
+pub fn restore_keychain_entries(entries: &[KeychainBundleEntry]) -> Result<usize, ProviderError> {
+    if let Some(entry) = entries.iter().find(|e| !is_managed_keychain_item(e)) {
+        return Err(ProviderError::non_retryable(format!(
+            "Backup contains an item Terminus does not manage: {}",
+            entry.service
+        )));
+    }
+    for entry in entries {
+        set_secret(&entry.service, &entry.account, &entry.secret)?;
+    }
+    Ok(entries.len())
+}
+
+fn is_managed_keychain_item(entry: &KeychainBundleEntry) -> bool {
+    let fixed = [
+        (
+            RELAY_SUBSCRIBER_TOKEN_SERVICE,
+            RELAY_SUBSCRIBER_TOKEN_ACCOUNT,
+        ),
+        (RELAY_CALLBACK_SECRET_SERVICE, RELAY_CALLBACK_SECRET_ACCOUNT),
+        (RELAY_DEVICE_ID_SERVICE, RELAY_DEVICE_ID_ACCOUNT),
+        (
+            LOCAL_TRANSPORT_BASE_URL_SERVICE,
+            LOCAL_TRANSPORT_BASE_URL_ACCOUNT,
+        ),
+        (CODEX_OAUTH_BUNDLE_SERVICE, CODEX_OAUTH_BUNDLE_ACCOUNT),
+    ];
+    let service = entry.service.as_str();
+    let account = entry.account.as_str();
+    fixed.contains(&(service, account))
+        || (account == API_KEY_REF_ACCOUNT
+            && service
+                .strip_prefix(API_KEY_REF_SERVICE_PREFIX)
+                .is_some_and(|name| !name.is_empty()))
+        || (account == WEBHOOK_TRIGGER_SECRET_ACCOUNT
+            && service
+                .strip_prefix(WEBHOOK_TRIGGER_SECRET_SERVICE_PREFIX)
+                .and_then(|rest| rest.strip_prefix('.'))
+                .is_some_and(|id| !id.is_empty()))
+}
+
+/// Encrypts entries with a key derived from `passphrase` (PBKDF2-HMAC-SHA256, random salt)
+/// and XChaCha20-Poly1305. Returns a JSON string safe to store anywhere.
+pub fn seal_keychain_bundle(
+    entries: &[KeychainBundleEntry],
+    passphrase: &str,
+) -> Result<String, ProviderError> {
+    validate_bundle_passphrase(passphrase)?;
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 24];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let cipher = bundle_cipher(passphrase, &salt, KEYCHAIN_BUNDLE_KDF_ROUNDS);
+    let plaintext = serde_json::to_vec(entries)
+        .map_err(|_| ProviderError::non_retryable("Could not encode Keychain backup."))?;
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce), plaintext.as_slice())
+        .map_err(|_| ProviderError::non_retryable("Could not encrypt Keychain backup."))?;
+    let b64 = base64::engine::general_purpose::STANDARD;
+    serde_json::to_string(&SealedKeychainBundle {
+        version: KEYCHAIN_BUNDLE_VERSION,
+        kdf: KEYCHAIN_BUNDLE_KDF.to_string(),
+        rounds: KEYCHAIN_BUNDLE_KDF_ROUNDS,
+        salt: b64.encode(salt),
+        nonce: b64.encode(nonce),
+        ciphertext: b64.encode(ciphertext),
+    })
+    .map_err(|_| ProviderError::non_retryable("Could not encode Keychain backup."))
+}
+
+/// Decrypts a bundle produced by `seal_keychain_bundle`. A wrong passphrase and a tampered
+/// bundle fail the same way.
+pub fn open_keychain_bundle(
+    bundle: &str,
+    passphrase: &str,
+) -> Result<Vec<KeychainBundleEntry>, ProviderError> {
+    let invalid =
+        || ProviderError::non_retryable("Keychain backup is not a valid Terminus backup.");
+    let sealed: SealedKeychainBundle =
+        serde_json::from_str(bundle.trim()).map_err(|_| invalid())?;
+    if sealed.version != KEYCHAIN_BUNDLE_VERSION
+        || sealed.kdf != KEYCHAIN_BUNDLE_KDF
+        || sealed.rounds == 0
+        || sealed.rounds > KEYCHAIN_BUNDLE_MAX_KDF_ROUNDS
+    {
+        return Err(invalid());
+    }
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let salt = b64.decode(&sealed.salt).map_err(|_| invalid())?;
+    let nonce = b64.decode(&sealed.nonce).map_err(|_| invalid())?;
+    let ciphertext = b64.decode(&sealed.ciphertext).map_err(|_| invalid())?;
+    if nonce.len() != 24 || salt.is_empty() {
+        return Err(invalid());
+    }
+    let cipher = bundle_cipher(passphrase, &salt, sealed.rounds);
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| {
+            ProviderError::non_retryable(
+                "Could not unlock Keychain backup. Check the passphrase and try again.",
+            )
+        })?;
+    serde_json::from_slice(&plaintext).map_err(|_| invalid())
+}
+
+fn validate_bundle_passphrase(passphrase: &str) -> Result<(), ProviderError> {
+    if passphrase.chars().count() < KEYCHAIN_BUNDLE_MIN_PASSPHRASE_CHARS {
+        return Err(ProviderError::non_retryable(format!(
+            "Backup passphrase must be at least {KEYCHAIN_BUNDLE_MIN_PASSPHRASE_CHARS} characters."
+        )));
+    }
+    Ok(())
+}
+
+fn bundle_cipher(passphrase: &str, salt: &[u8], rounds: u32) -> XChaCha20Poly1305 {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(passphrase.as_bytes(), salt, rounds, &mut key);
+    let cipher = XChaCha20Poly1305::new(&key.into());
+    key.fill(0);
+    cipher
+}
+
 #[cfg(test)]
 mod tests {
-    use super::read_codex_cli_auth_snapshot_from_path;
+    use super::{
+        open_keychain_bundle, read_codex_cli_auth_snapshot_from_path, restore_keychain_entries,
+        seal_keychain_bundle, KeychainBundleEntry, API_KEY_REF_ACCOUNT,
+    };
     use std::fs;
 
+    fn sample_entries() -> Vec<KeychainBundleEntry> {
+        vec![
+            KeychainBundleEntry {
+                service: "terminus.api_key_ref.billing".to_string(),
+                account: API_KEY_REF_ACCOUNT.to_string(),
+                secret: "sk_live_backup".to_string(),
+            },
+            KeychainBundleEntry {
+                service: "terminus.webhook_trigger_secret.whtrig_1".to_string(),
+                account: "TerminusWebhookTrigger".to_string(),
+                secret: "whsec_backup".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn keychain_bundle_round_trips_without_plaintext() {
+        let entries = sample_entries();
+        let sealed = seal_keychain_bundle(&entries, "correct horse battery").expect("seal bundle");
+        assert!(!sealed.contains("sk_live_backup"));
+        assert!(!sealed.contains("whsec_backup"));
+        assert!(!sealed.contains("billing"));
+
+        let opened = open_keychain_bundle(&sealed, "correct horse battery").expect("open bundle");
+        assert_eq!(opened, entries);
+    }
+
+    #[test]
+    fn keychain_bundle_rejects_wrong_passphrase_and_short_passphrase() {
+        let sealed =
+            seal_keychain_bundle(&sample_entries(), "correct horse battery").expect("seal bundle");
+        let err = open_keychain_bundle(&sealed, "incorrect horse battery").expect_err("wrong");
+        assert!(err.to_string().contains("passphrase"));
+        assert!(seal_keychain_bundle(&sample_entries(), "short").is_err());
+        assert!(open_keychain_bundle("{}", "correct horse battery").is_err());
+    }
+
+    #[test]
+    fn restore_rejects_items_outside_managed_services() {
+        let err = restore_keychain_entries(&[KeychainBundleEntry {
+            service: "com.apple.account".to_string(),
+            account: "someone".to_string(),
+            secret: "x".to_string(),
+        }])
+        .expect_err("unmanaged item");
+        assert!(err.to_string().contains("does not manage"));
+    }
+
     #[test]
     fn parses_codex_cli_auth_snapshot_and_ignores_empty_openai_key() {
         let tmp = std::env::temp_dir().join(format!(
@@ -1,6 +1,10 @@
+pub mod concurrency;
 pub mod keychain;
 pub mod runtime;
+pub mod tokens;
+pub mod trace;
 pub mod types;
 
 pub use runtime::ProviderRuntime;
+pub use tokens::estimate_tokens;
 pub use types::{ProviderError, ProviderKind, ProviderRequest, ProviderResponse, ProviderTier};
@@ -1,9 +1,41 @@
+use crate::providers::concurrency::ProviderConcurrencyLimiter;
 use crate::providers::keychain;
+use crate::providers::trace;
 use crate::providers::types::{ProviderError, ProviderRequest, ProviderResponse};
 use crate::transport::{ExecutionTransport, LocalHttpTransport, MockTransport, RelayTransport};
+#[cfg(test)]
+use std::cell::RefCell;
+#[cfg(test)]
+use std::sync::Arc;
 use std::sync::OnceLock;
+use std::time::Instant;
 
-pub struct ProviderRuntime;
+#[cfg(test)]
+thread_local! {
+    static SCOPED_MOCK: RefCell<Option<Arc<MockTransport>>> = const { RefCell::new(None) };
+}
+
+/// Routes every dispatch on the current thread to `mock` until the guard is dropped,
+/// so runner tests can script replies and assert the dispatch sequence.
+#[cfg(test)]
+pub fn scoped_mock_transport(mock: Arc<MockTransport>) -> ScopedMockTransport {
+    SCOPED_MOCK.with(|slot| *slot.borrow_mut() = Some(mock));
+    ScopedMockTransport
+}
+
+#[cfg(test)]
+pub struct ScopedMockTransport;
+
+#[cfg(test)]
+impl Drop for ScopedMockTransport {
+    fn drop(&mut self) {
+        SCOPED_MOCK.with(|slot| *slot.borrow_mut() = None);
+    }
+}
+
+pub struct ProviderRuntime {
+    mode_override: Option<TransportMode>,
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TransportMode {
@@ -17,11 +49,26 @@ pub struct TransportStatus {
     pub mode: TransportMode,
     pub relay_configured: bool,
     pub relay_url: String,
+    pub local_base_url: Option<String>,
 }
 
 impl ProviderRuntime {
     pub fn default() -> Self {
-        Self
+        Self {
+            mode_override: None,
+        }
+    }
+
+    /// Pins dispatch to one transport regardless of global configuration
+    /// (used for per-Autopilot transport settings).
+    pub fn with_mode(mode: TransportMode) -> Self {
+        Self {
+            mode_override: Some(mode),
+        }
+    }
+
+    pub fn mode_override(&self) -> Option<TransportMode> {
+        self.mode_override
     }
 
     pub fn transport_status(&self) -> TransportStatus {
@@ -31,34 +78,77 @@ impl ProviderRuntime {
             .map(|t| !t.trim().is_empty())
             .unwrap_or(false);
         let relay_url = RelayTransport::default_url();
-        let mode = Self::resolve_mode(relay_configured);
+        let local_base_url = LocalHttpTransport::configured_base_url();
+        let mode = self
+            .mode_override()
+            .unwrap_or_else(|| Self::resolve_mode(relay_configured, local_base_url.is_some()));
         TransportStatus {
             mode,
             relay_configured,
             relay_url,
+            local_base_url,
         }
     }
 
-    fn resolve_mode(relay_configured: bool) -> TransportMode {
+    /// An explicit `TERMINUS_TRANSPORT` wins; otherwise a configured local endpoint is
+    /// preferred over the hosted relay, and mock is the unconfigured default.
+    fn resolve_mode(relay_configured: bool, local_configured: bool) -> TransportMode {
         match std::env::var("TERMINUS_TRANSPORT") {
             Ok(mode) if mode.eq_ignore_ascii_case("relay") => TransportMode::Relay,
             Ok(mode) if mode.eq_ignore_ascii_case("local_http") => TransportMode::LocalHttp,
             Ok(mode) if mode.eq_ignore_ascii_case("mock") => TransportMode::Mock,
+            _ if local_configured => TransportMode::LocalHttp,
             _ if relay_configured => TransportMode::Relay,
             _ => TransportMode::Mock,
         }
     }
 
     pub fn dispatch(&self, request: &ProviderRequest) -> Result<ProviderResponse, ProviderError> {
+        #[cfg(test)]
+        if let Some(mock) = SCOPED_MOCK.with(|slot| slot.borrow().clone()) {
+            return mock.dispatch(request, None);
+        }
+        ProviderConcurrencyLimiter::global()
+            .run(request.provider_kind, || self.dispatch_unthrottled(request))
+    }
+
+    /// Same as `dispatch`, but when `trace` is set the redacted round-trip is stored in
+    /// `provider_traces` for debugging.
+    pub fn dispatch_traced(
+        &self,
+        connection: &rusqlite::Connection,
+        request: &ProviderRequest,
+        trace: bool,
+    ) -> Result<ProviderResponse, ProviderError> {
+        if !trace {
+            return self.dispatch(request);
+        }
+        let started = Instant::now();
+        let result = self.dispatch(request);
+        let latency_ms = started.elapsed().as_millis() as i64;
+        trace::record_provider_trace(connection, request, &result, latency_ms);
+        result
+    }
+
+    fn dispatch_unthrottled(
+        &self,
+        request: &ProviderRequest,
+    ) -> Result<ProviderResponse, ProviderError> {
         let relay_token = keychain::get_relay_subscriber_token()?;
-        let mode = Self::resolve_mode(relay_token.as_ref().is_some_and(|t| !t.trim().is_empty()));
+        let local_transport = LocalHttpTransport::from_config();
+        let mode = self.mode_override.unwrap_or_else(|| {
+            Self::resolve_mode(
+                relay_token.as_ref().is_some_and(|t| !t.trim().is_empty()),
+                local_transport.base_url().is_some(),
+            )
+        });
         match mode {
             TransportMode::Relay => {
                 let transport = Self::relay_transport();
                 transport.dispatch(request, relay_token.as_deref())
             }
             TransportMode::LocalHttp => {
-                let transport = Self::local_http_transport();
+                let transport = &local_transport;
                 let key = if transport.requires_keychain_key() {
                     keychain::get_api_key(request.provider_kind)?
                 } else {
@@ -73,11 +163,6 @@ impl ProviderRuntime {
         }
     }
 
-    fn local_http_transport() -> &'static LocalHttpTransport {
-        static LOCAL: OnceLock<LocalHttpTransport> = OnceLock::new();
-        LOCAL.get_or_init(LocalHttpTransport::new)
-    }
-
     fn mock_transport() -> &'static MockTransport {
         static MOCK: OnceLock<MockTransport> = OnceLock::new();
         MOCK.get_or_init(MockTransport::new)
@@ -90,6 +175,24 @@ impl ProviderRuntime {
 }
 
 impl TransportMode {
+    /// Parses a stored config value; accepts both config keys and display labels.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "mock" => Some(Self::Mock),
+            "local_http" | "byok_local" => Some(Self::LocalHttp),
+            "relay" | "hosted_relay" => Some(Self::Relay),
+            _ => None,
+        }
+    }
+
+    pub fn config_key(&self) -> &'static str {
+        match self {
+            Self::Mock => "mock",
+            Self::LocalHttp => "local_http",
+            Self::Relay => "relay",
+        }
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Mock => "mock",
@@ -0,0 +1,58 @@
+/// Rough token count for `input` under `model`, for budgeting before a dispatch. ASCII text is
+/// divided by the family's typical characters-per-token; every other character counts as one
+/// token, which keeps CJK and emoji-heavy input from being underestimated.
+pub fn estimate_tokens(model: &str, input: &str) -> u32 {
+    if input.is_empty() {
+        return 0;
+    }
+    let chars_per_token = chars_per_token(model);
+    let (ascii, other) = input.chars().fold((0u64, 0u64), |(ascii, other), c| {
+        if c.is_ascii() {
+            (ascii + 1, other)
+        } else {
+            (ascii, other + 1)
+        }
+    });
+    let tokens = (ascii as f64 / chars_per_token).ceil() as u64 + other;
+    tokens.min(u32::MAX as u64) as u32
+}
+
+fn chars_per_token(model: &str) -> f64 {
+    let model = model.trim().to_ascii_lowercase();
+    if model.starts_with("claude") {
+        3.5
+    } else if model.starts_with("gemini") {
+        4.2
+    } else {
+        // OpenAI's GPT and o-series tokenizers, and the fallback for unknown models.
+        4.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::estimate_tokens;
+
+    #[test]
+    fn token_estimate_grows_with_input_length() {
+        let mut previous = estimate_tokens("gpt-4o-mini", "");
+        assert_eq!(previous, 0);
+        for len in [1, 10, 100, 1_000, 10_000] {
+            let estimate = estimate_tokens("gpt-4o-mini", &"word ".repeat(len));
+            assert!(estimate > previous, "{len} words: {estimate} <= {previous}");
+            previous = estimate;
+        }
+        assert!(estimate_tokens("gpt-4o-mini", "你好世界") >= 4);
+    }
+
+    #[test]
+    fn token_estimate_differs_by_model_family() {
+        let input = "Summarize the quarterly vendor updates for the finance team. ".repeat(40);
+        let openai = estimate_tokens("gpt-4.1-mini", &input);
+        let claude = estimate_tokens("claude-3-5-haiku-latest", &input);
+        let gemini = estimate_tokens("gemini-1.5-flash", &input);
+        assert!(claude > openai, "claude {claude} vs openai {openai}");
+        assert!(openai > gemini, "openai {openai} vs gemini {gemini}");
+        assert_eq!(estimate_tokens("unknown-model", &input), openai);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::db::{self, ProviderTraceRecord};
+use crate::learning::REDACTION_FORBIDDEN_SUBSTRINGS;
+use crate::providers::types::{ProviderError, ProviderRequest, ProviderResponse};
+use rusqlite::Connection;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Longest request or response text kept per trace.
+const TRACE_TEXT_MAX_CHARS: usize = 8_000;
+const REDACTED: &str = "[REDACTED]";
+static TRACE_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
+
+/// Tracing is off unless `TERMINUS_PROVIDER_TRACE` is `1`, `true` or `on`.
+pub fn trace_enabled() -> bool {
+    std::env::var("TERMINUS_PROVIDER_TRACE")
+        .is_ok_and(|value| matches!(value.trim(), "1" | "true" | "on"))
+}
+
+/// Replaces every whitespace-separated token containing a forbidden substring. When that token
+/// is a label (`Authorization:`, `Bearer`), the value after it is replaced too.
+pub fn redact_trace_text(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut redact_next = false;
+    for piece in input.split_inclusive(char::is_whitespace) {
+        let token = piece.trim_end();
+        if token.is_empty() {
+            out.push_str(piece);
+            continue;
+        }
+        let lower = token.to_ascii_lowercase();
+        let forbidden = REDACTION_FORBIDDEN_SUBSTRINGS
+            .iter()
+            .any(|needle| lower.contains(needle.trim()));
+        if forbidden || redact_next {
+            out.push_str(REDACTED);
+            out.push_str(&piece[token.len()..]);
+        } else {
+            out.push_str(piece);
+        }
+        redact_next = forbidden && (token.ends_with(':') || lower == "bearer");
+    }
+    out.chars().take(TRACE_TEXT_MAX_CHARS).collect()
+}
+
+/// Stores one redacted round-trip. Failures are swallowed: tracing must never break dispatch.
+pub fn record_provider_trace(
+    connection: &Connection,
+    request: &ProviderRequest,
+    result: &Result<ProviderResponse, ProviderError>,
+    latency_ms: i64,
+) {
+    let (status, response_text, error_message) = match result {
+        Ok(response) => ("ok", Some(redact_trace_text(&response.text)), None),
+        Err(error) => ("error", None, Some(redact_trace_text(&error.message))),
+    };
+    let created_at_ms = now_ms();
+    let record = ProviderTraceRecord {
+        id: format!(
+            "trace_{}_{}",
+            created_at_ms,
+            TRACE_ID_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ),
+        correlation_id: request.correlation_id.clone(),
+        provider_kind: request.provider_kind.as_str().to_string(),
+        model: request.model.clone(),
+        status: status.to_string(),
+        request_input: redact_trace_text(&request.full_prompt()),
+        response_text,
+        error_message,
+        latency_ms,
+        created_at_ms,
+    };
+    let _ = db::insert_provider_trace(connection, &record);
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::redact_trace_text;
+    use crate::db;
+    use crate::providers::runtime::{scoped_mock_transport, ProviderRuntime};
+    use crate::providers::types::{ProviderKind, ProviderRequest, ProviderTier};
+    use crate::transport::MockTransport;
+    use std::sync::Arc;
+
+    #[test]
+    fn redaction_covers_forbidden_tokens_and_their_values() {
+        let redacted = redact_trace_text(
+            "Use Authorization: Bearer abc123 and api_key=xyz\nthen sk-live-0001 done",
+        );
+        assert_eq!(
+            redacted,
+            "Use [REDACTED] [REDACTED] [REDACTED] and [REDACTED]\nthen [REDACTED] done"
+        );
+    }
+
+    #[test]
+    fn traced_dispatch_stores_redacted_record() {
+        let mut conn = rusqlite::Connection::open_in_memory().expect("open db");
+        db::bootstrap_schema(&mut conn).expect("bootstrap");
+        let mock = Arc::new(MockTransport::new());
+        let _guard = scoped_mock_transport(mock);
+        let request = ProviderRequest {
+            provider_kind: ProviderKind::OpenAi,
+            provider_tier: ProviderTier::Supported,
+            model: "gpt-4.1-mini".to_string(),
+            system_prompt: Some("Answer in one line.".to_string()),
+            input: "Summarize this. x-api-key: secret-value-123 thanks".to_string(),
+            max_output_tokens: Some(64),
+            correlation_id: Some("trace_test".to_string()),
+        };
+
+        ProviderRuntime::default()
+            .dispatch_traced(&conn, &request, true)
+            .expect("dispatch");
+
+        let traces = db::list_provider_traces(&conn, 10).expect("traces");
+        assert_eq!(traces.len(), 1);
+        let trace = &traces[0];
+        assert_eq!(trace.status, "ok");
+        assert_eq!(trace.correlation_id.as_deref(), Some("trace_test"));
+        assert_eq!(trace.provider_kind, "openai");
+        assert!(trace
+            .request_input
+            .starts_with("Answer in one line.\n\nSummarize this."));
+        assert!(!trace.request_input.contains("secret-value-123"));
+        assert!(trace.response_text.is_some());
+
+        ProviderRuntime::default()
+            .dispatch_traced(&conn, &request, false)
+            .expect("untraced dispatch");
+        assert_eq!(
+            db::list_provider_traces(&conn, 10).expect("traces").len(),
+            1
+        );
+    }
+}
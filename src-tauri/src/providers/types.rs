@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ProviderKind {
     OpenAi,
@@ -48,11 +48,27 @@ pub struct ProviderRequest {
     pub provider_kind: ProviderKind,
     pub provider_tier: ProviderTier,
     pub model: String,
+    /// Instructions for the provider's system slot; `input` stays the user content.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
     pub input: String,
     pub max_output_tokens: Option<u32>,
     pub correlation_id: Option<String>,
 }
 
+impl ProviderRequest {
+    /// The system prompt followed by `input`: everything the provider reads, for traces, token
+    /// estimates and transports without a system slot.
+    pub fn full_prompt(&self) -> String {
+        match self.system_prompt.as_deref().map(str::trim) {
+            Some(system_prompt) if !system_prompt.is_empty() => {
+                format!("{system_prompt}\n\n{}", self.input)
+            }
+            _ => self.input.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderUsage {
     pub input_tokens: u32,
@@ -73,6 +89,11 @@ pub struct ProviderResponse {
 pub enum ProviderErrorKind {
     Retryable,
     NonRetryable,
+    /// The provider asked us to slow down. `retry_after_ms` comes from its `Retry-After`
+    /// header when one was sent.
+    RateLimited {
+        retry_after_ms: Option<u64>,
+    },
 }
 
 #[derive(Debug, Error)]
@@ -97,7 +118,25 @@ impl ProviderError {
         }
     }
 
+    pub fn rate_limited(message: impl Into<String>, retry_after_ms: Option<u64>) -> Self {
+        Self {
+            kind: ProviderErrorKind::RateLimited { retry_after_ms },
+            message: message.into(),
+        }
+    }
+
     pub fn is_retryable(&self) -> bool {
-        matches!(self.kind, ProviderErrorKind::Retryable)
+        matches!(
+            self.kind,
+            ProviderErrorKind::Retryable | ProviderErrorKind::RateLimited { .. }
+        )
+    }
+
+    /// Provider-requested delay before the next attempt, if any.
+    pub fn retry_after_ms(&self) -> Option<u64> {
+        match self.kind {
+            ProviderErrorKind::RateLimited { retry_after_ms } => retry_after_ms,
+            _ => None,
+        }
     }
 }
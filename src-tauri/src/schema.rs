@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Version stamped on every plan this build writes. `migrate_plan` upgrades older ones.
+pub const PLAN_SCHEMA_VERSION: &str = "1.0";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -7,6 +11,20 @@ pub enum RecipeKind {
     InboxTriage,
     DailyBrief,
     Custom,
+    /// Event-driven plan that reads a webhook payload and drafts from its fields.
+    Webhook,
+}
+
+impl RecipeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::WebsiteMonitor => "website_monitor",
+            Self::InboxTriage => "inbox_triage",
+            Self::DailyBrief => "daily_brief",
+            Self::Custom => "custom",
+            Self::Webhook => "webhook",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -57,6 +75,10 @@ pub struct ApiCallRequest {
     pub auth_header_name: String,
     pub auth_scheme: String,
     pub body_json: Option<String>,
+    /// Dot paths into a JSON response (e.g. `data.items.0.name`) that later steps may see.
+    /// Anything not listed stays in the stored excerpt only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub response_fields: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -83,9 +105,20 @@ pub struct AutopilotPlan {
     pub provider: ProviderMetadata,
     pub web_source_url: Option<String>,
     pub web_allowed_domains: Vec<String>,
+    /// Website monitors diff the full HTML instead of extracted readable text.
+    #[serde(default)]
+    pub web_raw_html: bool,
     pub inbox_source_text: Option<String>,
+    /// Parsed payload of the event that started this run (e.g. a webhook body). CallApi body
+    /// templates resolve `{{event.*}}` placeholders against it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_context: Option<serde_json::Value>,
     pub daily_sources: Vec<String>,
     pub api_call_request: Option<ApiCallRequest>,
+    /// Per-step CallApi configs keyed by step id. Steps without an entry fall back to
+    /// `api_call_request`, which keeps single-call plans stored before this field valid.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub api_call_requests: BTreeMap<String, ApiCallRequest>,
     pub recipient_hints: Vec<String>,
     pub allowed_primitives: Vec<PrimitiveId>,
     pub steps: Vec<PlanStep>,
@@ -114,6 +147,13 @@ impl ProviderMetadata {
 }
 
 impl AutopilotPlan {
+    /// CallApi config for `step_id`, preferring a step-specific entry over the shared one.
+    pub fn api_call_for_step(&self, step_id: &str) -> Option<&ApiCallRequest> {
+        self.api_call_requests
+            .get(step_id)
+            .or(self.api_call_request.as_ref())
+    }
+
     pub fn from_intent(recipe: RecipeKind, intent: String, provider_id: ProviderId) -> Self {
         let provider = ProviderMetadata::from_provider_id(provider_id);
         let web_source_url = extract_first_url(&intent);
@@ -266,18 +306,37 @@ impl AutopilotPlan {
                 },
             ],
             RecipeKind::Custom => Vec::new(),
+            RecipeKind::Webhook => vec![
+                PlanStep {
+                    id: "step_1".to_string(),
+                    label: "Read the webhook event payload".to_string(),
+                    primitive: PrimitiveId::ReadForwardedEmail,
+                    requires_approval: false,
+                    risk_tier: RiskTier::Low,
+                },
+                PlanStep {
+                    id: "step_2".to_string(),
+                    label: "Draft an outcome from the event fields".to_string(),
+                    primitive: PrimitiveId::WriteOutcomeDraft,
+                    requires_approval: false,
+                    risk_tier: RiskTier::Medium,
+                },
+            ],
         };
 
         Self {
-            schema_version: "1.0".to_string(),
+            schema_version: PLAN_SCHEMA_VERSION.to_string(),
             recipe,
             intent,
             provider,
             web_source_url,
             web_allowed_domains,
+            web_raw_html: false,
             inbox_source_text,
+            event_context: None,
             daily_sources,
             api_call_request: None,
+            api_call_requests: BTreeMap::new(),
             recipient_hints,
             allowed_primitives,
             steps,
@@ -354,9 +413,49 @@ fn extract_emails(input: &str) -> Vec<String> {
         .collect::<Vec<String>>()
 }
 
+/// Parses a stored plan, upgrading older shapes to `PLAN_SCHEMA_VERSION`. Plans written
+/// before versioning carry no `schema_version`; plans from a newer major version are refused
+/// rather than guessed at.
+pub fn migrate_plan(raw: &str) -> Result<AutopilotPlan, String> {
+    let mut value: serde_json::Value =
+        serde_json::from_str(raw).map_err(|e| format!("Stored plan is not valid JSON: {e}"))?;
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| "Stored plan must be a JSON object.".to_string())?;
+    let stored_version = object
+        .get("schema_version")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    if let Some(version) = stored_version.as_deref() {
+        let major = plan_schema_major(version)
+            .ok_or_else(|| format!("Plan schema {version} is not a recognized version."))?;
+        let current_major = plan_schema_major(PLAN_SCHEMA_VERSION).unwrap_or_default();
+        if major > current_major {
+            return Err(format!(
+                "Plan schema {version} is newer than this app supports ({PLAN_SCHEMA_VERSION})."
+            ));
+        }
+    }
+    object.insert(
+        "schema_version".to_string(),
+        serde_json::Value::String(PLAN_SCHEMA_VERSION.to_string()),
+    );
+    serde_json::from_value(value).map_err(|e| format!("Stored plan is invalid: {e}"))
+}
+
+/// Major component of a `major.minor` schema version; `None` unless both parts are numeric.
+fn plan_schema_major(version: &str) -> Option<u32> {
+    let (major, minor) = version.trim().split_once('.')?;
+    minor.parse::<u32>().ok()?;
+    major.parse::<u32>().ok()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{AutopilotPlan, ProviderId, ProviderTier, RecipeKind};
+    use super::{
+        migrate_plan, ApiCallRequest, AutopilotPlan, ProviderId, ProviderTier, RecipeKind,
+        PLAN_SCHEMA_VERSION,
+    };
 
     #[test]
     fn builds_shared_plan_schema_for_all_three_recipes() {
@@ -393,8 +492,78 @@ mod tests {
             .contains(&super::PrimitiveId::ReadVaultFile));
         assert_eq!(brief.provider.tier, ProviderTier::Experimental);
         assert_eq!(custom.steps.len(), 0);
+        let webhook = AutopilotPlan::from_intent(
+            RecipeKind::Webhook,
+            "When a new order arrives, summarize it".to_string(),
+            ProviderId::OpenAi,
+        );
+        assert_eq!(webhook.recipe.as_str(), "webhook");
+        assert_eq!(webhook.steps.len(), 2);
+        assert_eq!(
+            webhook.steps[0].primitive,
+            super::PrimitiveId::ReadForwardedEmail
+        );
+        assert!(webhook.inbox_source_text.is_none());
         assert_eq!(website.steps.len(), 3);
         assert_eq!(triage.steps.len(), 4);
         assert_eq!(brief.steps.len(), 3);
     }
+
+    #[test]
+    fn migrate_plan_stamps_unversioned_plans_and_refuses_newer_ones() {
+        let plan = AutopilotPlan::from_intent(
+            RecipeKind::DailyBrief,
+            "Morning brief".to_string(),
+            ProviderId::OpenAi,
+        );
+        let mut legacy = serde_json::to_value(&plan).expect("serialize");
+        legacy
+            .as_object_mut()
+            .expect("object")
+            .remove("schema_version");
+
+        let migrated = migrate_plan(&legacy.to_string()).expect("legacy plan migrates");
+        assert_eq!(migrated.schema_version, PLAN_SCHEMA_VERSION);
+        assert_eq!(migrated, plan);
+
+        legacy["schema_version"] = serde_json::json!("9.0");
+        let err = migrate_plan(&legacy.to_string()).expect_err("newer schema");
+        assert!(err.contains("newer than this app supports"));
+
+        for unrecognized in ["v2", "beta.1", "", "1"] {
+            legacy["schema_version"] = serde_json::json!(unrecognized);
+            let err = migrate_plan(&legacy.to_string()).expect_err("unrecognized schema");
+            assert!(
+                err.contains("not a recognized version"),
+                "{unrecognized}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn single_api_call_request_plans_still_deserialize() {
+        let mut plan = AutopilotPlan::from_intent(
+            RecipeKind::Custom,
+            "Call the CRM API".to_string(),
+            ProviderId::OpenAi,
+        );
+        plan.api_call_request = Some(ApiCallRequest {
+            url: "https://api.example.com/v1/items".to_string(),
+            method: "GET".to_string(),
+            header_key_ref: "crm_prod".to_string(),
+            auth_header_name: "Authorization".to_string(),
+            auth_scheme: "bearer".to_string(),
+            body_json: None,
+            response_fields: Vec::new(),
+        });
+        let legacy = serde_json::to_value(&plan).expect("serialize");
+        assert!(legacy.get("api_call_requests").is_none());
+
+        let parsed: AutopilotPlan = serde_json::from_value(legacy).expect("legacy plan");
+        assert!(parsed.api_call_requests.is_empty());
+        assert_eq!(
+            parsed.api_call_for_step("step_1").map(|c| c.url.as_str()),
+            Some("https://api.example.com/v1/items")
+        );
+    }
 }
@@ -6,12 +6,112 @@ use crate::transport::ExecutionTransport;
 use serde_json::Value;
 use std::io::Write;
 use std::process::{Command, Stdio};
+use std::time::Instant;
 
-pub struct LocalHttpTransport;
+pub const LOCAL_HTTP_BASE_URL_ENV: &str = "TERMINUS_LOCAL_HTTP_BASE_URL";
+
+pub struct LocalHttpTransport {
+    base_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalTransportProbe {
+    pub base_url: String,
+    pub reachable: bool,
+    pub latency_ms: i64,
+    pub http_status: Option<u16>,
+    pub message: String,
+}
 
 impl LocalHttpTransport {
     pub fn new() -> Self {
-        Self
+        Self { base_url: None }
+    }
+
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self {
+            base_url: Some(base_url.trim().trim_end_matches('/').to_string()),
+        }
+    }
+
+    /// Builds the transport from the env override or the Keychain-stored base URL.
+    /// Without either, requests go straight to the public provider endpoints.
+    pub fn from_config() -> Self {
+        match Self::configured_base_url() {
+            Some(base_url) => Self::with_base_url(&base_url),
+            None => Self::new(),
+        }
+    }
+
+    pub fn configured_base_url() -> Option<String> {
+        std::env::var(LOCAL_HTTP_BASE_URL_ENV)
+            .ok()
+            .or_else(|| keychain::get_local_transport_base_url().ok().flatten())
+            .map(|v| v.trim().trim_end_matches('/').to_string())
+            .filter(|v| !v.is_empty())
+    }
+
+    pub fn base_url(&self) -> Option<&str> {
+        self.base_url.as_deref()
+    }
+
+    fn endpoint(&self, default_url: &str, path: &str) -> String {
+        match self.base_url.as_deref() {
+            Some(base) => format!("{base}{path}"),
+            None => default_url.to_string(),
+        }
+    }
+
+    /// Issues a cheap GET against the configured base URL and reports reachability and latency.
+    pub fn probe(&self) -> Result<LocalTransportProbe, ProviderError> {
+        let base_url = self.base_url.as_deref().ok_or_else(|| {
+            ProviderError::non_retryable(
+                "Local endpoint is not configured. Add a base URL in BYOK settings first.",
+            )
+        })?;
+        let sentinel = "__TERMINUS_HTTP_STATUS__:";
+        let mut config = String::new();
+        config.push_str("silent\n");
+        config.push_str("connect-timeout = 2\n");
+        config.push_str("max-time = 3\n");
+        config.push_str("output = \"/dev/null\"\n");
+        config.push_str(&format!("url = \"{base_url}/v1/models\"\n"));
+        config.push_str(&format!("write-out = \"{sentinel}%{{http_code}}\"\n"));
+
+        let started = Instant::now();
+        let mut child = Command::new("curl")
+            .arg("--config")
+            .arg("-")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|_| ProviderError::retryable("Network transport is unavailable."))?;
+        {
+            let stdin = child
+                .stdin
+                .as_mut()
+                .ok_or_else(|| ProviderError::non_retryable("Network transport is unavailable."))?;
+            stdin
+                .write_all(config.as_bytes())
+                .map_err(|_| ProviderError::retryable("Network transport is unavailable."))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|_| ProviderError::retryable("Network transport is unavailable."))?;
+        let latency_ms = started.elapsed().as_millis() as i64;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let http_status = stdout
+            .rsplit_once(sentinel)
+            .and_then(|(_, code)| code.trim().parse::<u16>().ok())
+            .unwrap_or(0);
+        Ok(map_probe_outcome(
+            base_url,
+            output.status.code().unwrap_or(1),
+            http_status,
+            latency_ms,
+        ))
     }
 
     fn require_key(keychain_api_key: Option<&str>) -> Result<&str, ProviderError> {
@@ -57,12 +157,20 @@ impl LocalHttpTransport {
         }
     }
 
-    fn classify_http_status(provider: &str, http_status: u16) -> ProviderError {
+    fn classify_http_status(
+        provider: &str,
+        http_status: u16,
+        retry_after: Option<&str>,
+    ) -> ProviderError {
         match http_status {
             401 | 403 => ProviderError::non_retryable(format!(
                 "{provider} rejected the request. Check your API key or reconnect Codex OAuth and try again."
             )),
-            408 | 429 => ProviderError::retryable(format!(
+            429 => ProviderError::rate_limited(
+                format!("{provider} is rate limiting requests. Try again shortly."),
+                retry_after.and_then(|v| super::parse_retry_after_ms(v, super::now_ms())),
+            ),
+            408 => ProviderError::retryable(format!(
                 "{provider} is rate limiting or temporarily unavailable. Try again shortly."
             )),
             500..=599 => ProviderError::retryable(format!(
@@ -92,6 +200,10 @@ impl LocalHttpTransport {
         config.push_str("show-error\n");
         config.push_str("location\n");
         config.push_str("max-time = 30\n");
+        if self.base_url.is_some() {
+            // A configured local endpoint should answer immediately; don't wait out max-time.
+            config.push_str("connect-timeout = 3\n");
+        }
         config.push_str("request = \"POST\"\n");
         config.push_str(&format!("url = \"{url}\"\n"));
         config.push_str("header = \"Content-Type: application/json\"\n");
@@ -105,8 +217,11 @@ impl LocalHttpTransport {
             .map_err(|_| ProviderError::non_retryable("Request could not be encoded."))?;
         config.push_str(&format!("data = {body}\n"));
 
-        // Write out status code as a final line (stdout), separate from JSON.
-        config.push_str(&format!("write-out = \"\\n{sentinel}%{{http_code}}\"\n"));
+        // Write out status code (and any Retry-After) as a final line, separate from JSON.
+        config.push_str(&format!(
+            "write-out = \"\\n{sentinel}%{{http_code}}{}\"\n",
+            super::CURL_RETRY_AFTER_WRITE_OUT
+        ));
 
         let mut child = Command::new("curl")
             .arg("--config")
@@ -133,6 +248,11 @@ impl LocalHttpTransport {
 
         if !output.status.success() {
             let code = output.status.code().unwrap_or(1);
+            if let Some(base_url) = self.base_url.as_deref() {
+                if let Some(err) = classify_local_endpoint_failure(base_url, code) {
+                    return Err(err);
+                }
+            }
             let stderr = String::from_utf8_lossy(&output.stderr);
             return Err(Self::classify_curl_failure(provider, code, &stderr));
         }
@@ -142,9 +262,13 @@ impl LocalHttpTransport {
             .rsplit_once(sentinel)
             .ok_or_else(|| ProviderError::retryable("Provider response could not be parsed."))?;
 
-        let http_status: u16 = status_str.trim().parse().unwrap_or(0);
+        let (http_status, retry_after) = super::split_status_trailer(status_str);
         if !(200..=299).contains(&http_status) {
-            return Err(Self::classify_http_status(provider, http_status));
+            return Err(Self::classify_http_status(
+                provider,
+                http_status,
+                retry_after,
+            ));
         }
 
         serde_json::from_str(json_str.trim())
@@ -158,15 +282,14 @@ impl LocalHttpTransport {
     ) -> Result<ProviderResponse, ProviderError> {
         let key = Self::require_openai_auth(keychain_api_key)?;
 
-        let body = serde_json::json!({
-          "model": request.model,
-          "messages": [{"role": "user", "content": request.input}],
-          "max_tokens": request.max_output_tokens
-        });
+        let body = openai_request_body(request);
 
         let json = self.curl_json_request(
             "OpenAI",
-            "https://api.openai.com/v1/chat/completions",
+            &self.endpoint(
+                "https://api.openai.com/v1/chat/completions",
+                "/v1/chat/completions",
+            ),
             &[("Authorization", format!("Bearer {key}"))],
             &body,
         )?;
@@ -191,11 +314,8 @@ impl LocalHttpTransport {
             .and_then(|v| v.as_u64())
             .unwrap_or(0) as u32;
 
-        let estimated_cost_usd_cents = estimate_openai_cost_usd_cents(
-            &request.model,
-            input_tokens as i64,
-            output_tokens as i64,
-        );
+        // Priced by the runner from the provider_pricing table; transports only report tokens.
+        let estimated_cost_usd_cents = 0;
 
         Ok(ProviderResponse {
             provider_kind: request.provider_kind,
@@ -217,16 +337,11 @@ impl LocalHttpTransport {
     ) -> Result<ProviderResponse, ProviderError> {
         let key = Self::require_key(keychain_api_key)?;
 
-        let max_tokens = request.max_output_tokens.unwrap_or(512).max(1);
-        let body = serde_json::json!({
-          "model": request.model,
-          "max_tokens": max_tokens,
-          "messages": [{"role": "user", "content": request.input}]
-        });
+        let body = anthropic_request_body(request);
 
         let json = self.curl_json_request(
             "Anthropic",
-            "https://api.anthropic.com/v1/messages",
+            &self.endpoint("https://api.anthropic.com/v1/messages", "/v1/messages"),
             &[
                 ("x-api-key", key.to_string()),
                 ("anthropic-version", "2023-06-01".to_string()),
@@ -265,7 +380,7 @@ impl LocalHttpTransport {
             .and_then(|v| v.as_u64())
             .unwrap_or(0) as u32;
 
-        // Pricing intentionally not hard-coded for Anthropic yet; runner ledger will fall back to per-step estimates if this is 0.
+        // Priced by the runner from the provider_pricing table; transports only report tokens.
         let estimated_cost_usd_cents = 0;
 
         Ok(ProviderResponse {
@@ -302,25 +417,210 @@ impl ExecutionTransport for LocalHttpTransport {
     }
 }
 
-fn estimate_openai_cost_usd_cents(model: &str, input_tokens: i64, output_tokens: i64) -> i64 {
-    // Best-effort local estimate for caps/ledger. Authoritative billing is always the provider for BYOK.
-    // Rates are USD per 1M tokens.
-    let (usd_per_m_input, usd_per_m_output) = match model {
-        "gpt-4o-mini" => (0.15, 0.60),
-        // Unknown model: do not guess.
-        _ => return 0,
-    };
+/// Chat Completions takes the system prompt as a leading `system` message.
+fn openai_request_body(request: &ProviderRequest) -> serde_json::Value {
+    let mut messages = Vec::new();
+    if let Some(system_prompt) = request.system_prompt.as_deref() {
+        messages.push(serde_json::json!({"role": "system", "content": system_prompt}));
+    }
+    messages.push(serde_json::json!({"role": "user", "content": request.input}));
+    serde_json::json!({
+      "model": request.model,
+      "messages": messages,
+      "max_tokens": request.max_output_tokens
+    })
+}
+
+/// The Messages API has no system role; the prompt goes in the top-level `system` field.
+fn anthropic_request_body(request: &ProviderRequest) -> serde_json::Value {
+    let max_tokens = request.max_output_tokens.unwrap_or(512).max(1);
+    let mut body = serde_json::json!({
+      "model": request.model,
+      "max_tokens": max_tokens,
+      "messages": [{"role": "user", "content": request.input}]
+    });
+    if let Some(system_prompt) = request.system_prompt.as_deref() {
+        body["system"] = serde_json::json!(system_prompt);
+    }
+    body
+}
 
-    let cost_usd = (input_tokens as f64 * usd_per_m_input / 1_000_000.0)
-        + (output_tokens as f64 * usd_per_m_output / 1_000_000.0);
-    (cost_usd * 100.0).round() as i64
+/// Connection-level failures against a configured local endpoint are not worth retrying:
+/// the server is down, so the run should stop with a clear reason instead of backing off.
+fn classify_local_endpoint_failure(base_url: &str, curl_status: i32) -> Option<ProviderError> {
+    match curl_status {
+        6 | 7 | 28 => Some(ProviderError::non_retryable(format!(
+            "Local model endpoint at {base_url} is not reachable. Start it or update the base URL in BYOK settings."
+        ))),
+        _ => None,
+    }
+}
+
+fn map_probe_outcome(
+    base_url: &str,
+    curl_status: i32,
+    http_status: u16,
+    latency_ms: i64,
+) -> LocalTransportProbe {
+    let (reachable, http_status, message) = match (curl_status, http_status) {
+        (0, 200..=499) => (
+            true,
+            Some(http_status),
+            format!("Local endpoint responded in {latency_ms} ms."),
+        ),
+        (0, 500..=599) => (
+            false,
+            Some(http_status),
+            format!("Local endpoint is up but returned a server error (HTTP {http_status})."),
+        ),
+        (6, _) => (
+            false,
+            None,
+            "Local endpoint host could not be resolved. Check the base URL.".to_string(),
+        ),
+        (7, _) => (
+            false,
+            None,
+            "Nothing is listening at the local endpoint. Start your local model server and try again."
+                .to_string(),
+        ),
+        (28, _) => (
+            false,
+            None,
+            "Local endpoint did not respond in time.".to_string(),
+        ),
+        _ => (
+            false,
+            None,
+            "Local endpoint could not be reached.".to_string(),
+        ),
+    };
+    LocalTransportProbe {
+        base_url: base_url.to_string(),
+        reachable,
+        latency_ms,
+        http_status,
+        message,
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::LocalHttpTransport;
+    use super::{
+        anthropic_request_body, classify_local_endpoint_failure, map_probe_outcome,
+        openai_request_body, LocalHttpTransport,
+    };
     use crate::providers::types::{ProviderKind, ProviderRequest, ProviderTier};
-    use crate::transport::ExecutionTransport;
+    use crate::transport::{parse_retry_after_ms, split_status_trailer, ExecutionTransport};
+
+    #[test]
+    fn system_prompt_maps_to_each_providers_system_slot() {
+        let request = ProviderRequest {
+            provider_kind: ProviderKind::OpenAi,
+            provider_tier: ProviderTier::Supported,
+            model: "gpt-4o-mini".to_string(),
+            system_prompt: Some("Output JSON only.".to_string()),
+            input: "Intent: watch the pricing page".to_string(),
+            max_output_tokens: Some(64),
+            correlation_id: None,
+        };
+
+        let openai = openai_request_body(&request);
+        assert_eq!(
+            openai["messages"],
+            serde_json::json!([
+                {"role": "system", "content": "Output JSON only."},
+                {"role": "user", "content": "Intent: watch the pricing page"}
+            ])
+        );
+
+        let anthropic = anthropic_request_body(&request);
+        assert_eq!(anthropic["system"], "Output JSON only.");
+        assert_eq!(
+            anthropic["messages"],
+            serde_json::json!([{"role": "user", "content": "Intent: watch the pricing page"}])
+        );
+
+        let bare = ProviderRequest {
+            system_prompt: None,
+            ..request
+        };
+        assert_eq!(
+            openai_request_body(&bare)["messages"]
+                .as_array()
+                .map(Vec::len),
+            Some(1)
+        );
+        assert!(anthropic_request_body(&bare).get("system").is_none());
+    }
+
+    #[test]
+    fn retry_after_parses_delta_seconds_and_http_dates() {
+        // 2015-10-21T07:28:00Z
+        let now = 1_445_412_480_000;
+        assert_eq!(parse_retry_after_ms("120", now), Some(120_000));
+        assert_eq!(parse_retry_after_ms(" 0 ", now), Some(0));
+        assert_eq!(
+            parse_retry_after_ms("Wed, 21 Oct 2015 07:28:45 GMT", now),
+            Some(45_000)
+        );
+        assert_eq!(
+            parse_retry_after_ms("Wed, 21 Oct 2015 07:20:00 GMT", now),
+            Some(0)
+        );
+        assert_eq!(parse_retry_after_ms("999999", now), Some(60 * 60 * 1000));
+        assert_eq!(parse_retry_after_ms("soon", now), None);
+        assert_eq!(parse_retry_after_ms("", now), None);
+    }
+
+    #[test]
+    fn rate_limited_status_carries_retry_after() {
+        assert_eq!(split_status_trailer("429 30\n"), (429, Some("30")));
+        assert_eq!(split_status_trailer("200 "), (200, None));
+
+        let err = LocalHttpTransport::classify_http_status("OpenAI", 429, Some("30"));
+        assert!(err.is_retryable());
+        assert_eq!(err.retry_after_ms(), Some(30_000));
+        let timeout = LocalHttpTransport::classify_http_status("OpenAI", 408, None);
+        assert!(timeout.is_retryable());
+        assert_eq!(timeout.retry_after_ms(), None);
+    }
+
+    #[test]
+    fn probe_outcome_maps_success_and_failure() {
+        let ok = map_probe_outcome("http://127.0.0.1:11434", 0, 200, 12);
+        assert!(ok.reachable);
+        assert_eq!(ok.http_status, Some(200));
+        assert_eq!(ok.latency_ms, 12);
+
+        // An auth challenge still proves the server is up.
+        assert!(map_probe_outcome("http://127.0.0.1:11434", 0, 401, 5).reachable);
+
+        let server_error = map_probe_outcome("http://127.0.0.1:11434", 0, 503, 5);
+        assert!(!server_error.reachable);
+        assert_eq!(server_error.http_status, Some(503));
+
+        let refused = map_probe_outcome("http://127.0.0.1:11434", 7, 0, 1);
+        assert!(!refused.reachable);
+        assert_eq!(refused.http_status, None);
+        assert!(refused.message.contains("Nothing is listening"));
+
+        let timed_out = map_probe_outcome("http://127.0.0.1:11434", 28, 0, 3000);
+        assert!(!timed_out.reachable);
+    }
+
+    #[test]
+    fn local_endpoint_down_fails_fast_without_retry() {
+        let err = classify_local_endpoint_failure("http://127.0.0.1:11434", 7)
+            .expect("connection refused is classified");
+        assert!(!err.is_retryable());
+        assert!(err.message.contains("http://127.0.0.1:11434"));
+        assert!(classify_local_endpoint_failure("http://127.0.0.1:11434", 22).is_none());
+
+        let transport = LocalHttpTransport::with_base_url("http://127.0.0.1:11434/");
+        assert_eq!(transport.base_url(), Some("http://127.0.0.1:11434"));
+        assert!(LocalHttpTransport::new().probe().is_err());
+    }
 
     // Env-gated integration tests. These require local Keychain keys and real network access.
     #[test]
@@ -336,6 +636,7 @@ mod tests {
             provider_kind: ProviderKind::OpenAi,
             provider_tier: ProviderTier::Supported,
             model: "gpt-4o-mini".to_string(),
+            system_prompt: None,
             input: "Reply with the single word: ok".to_string(),
             max_output_tokens: Some(16),
             correlation_id: Some("live_openai_test".to_string()),
@@ -360,6 +661,7 @@ mod tests {
             provider_kind: ProviderKind::Anthropic,
             provider_tier: ProviderTier::Supported,
             model: "claude-3-5-sonnet-latest".to_string(),
+            system_prompt: None,
             input: "Reply with the single word: ok".to_string(),
             max_output_tokens: Some(16),
             correlation_id: Some("live_anthropic_test".to_string()),
@@ -1,19 +1,169 @@
 use crate::providers::types::{ProviderError, ProviderRequest, ProviderResponse, ProviderUsage};
 use crate::transport::ExecutionTransport;
 use std::collections::HashMap;
+#[cfg(test)]
+use std::collections::VecDeque;
 use std::sync::Mutex;
 
 pub struct MockTransport {
     attempts: Mutex<HashMap<String, u32>>,
+    #[cfg(test)]
+    script: Mutex<MockScript>,
+    #[cfg(test)]
+    received: Mutex<Vec<ProviderRequest>>,
+}
+
+/// A scripted reply consumed by `MockTransport::dispatch` before the keyword simulations.
+#[cfg(test)]
+#[derive(Debug, Clone)]
+pub enum MockReply {
+    Text(String),
+    Retryable(String),
+    NonRetryable(String),
+    RateLimited(String, u64),
+}
+
+#[cfg(test)]
+#[derive(Debug, Default)]
+struct MockScript {
+    by_correlation: HashMap<String, VecDeque<MockReply>>,
+    sequence: VecDeque<MockReply>,
+}
+
+/// Builds a `MockTransport` whose replies are scripted per correlation id or in call order.
+/// Correlation-scoped replies win over the shared sequence; once both are drained the mock
+/// falls back to its keyword-driven defaults.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct MockTransportBuilder {
+    script: MockScript,
+}
+
+#[cfg(test)]
+impl MockTransportBuilder {
+    pub fn respond(mut self, text: impl Into<String>) -> Self {
+        self.script.sequence.push_back(MockReply::Text(text.into()));
+        self
+    }
+
+    pub fn fail_retryable(mut self, message: impl Into<String>) -> Self {
+        self.script
+            .sequence
+            .push_back(MockReply::Retryable(message.into()));
+        self
+    }
+
+    pub fn fail_rate_limited(mut self, message: impl Into<String>, retry_after_ms: u64) -> Self {
+        self.script
+            .sequence
+            .push_back(MockReply::RateLimited(message.into(), retry_after_ms));
+        self
+    }
+
+    pub fn fail_non_retryable(mut self, message: impl Into<String>) -> Self {
+        self.script
+            .sequence
+            .push_back(MockReply::NonRetryable(message.into()));
+        self
+    }
+
+    pub fn for_correlation(mut self, correlation_id: impl Into<String>, reply: MockReply) -> Self {
+        self.script
+            .by_correlation
+            .entry(correlation_id.into())
+            .or_default()
+            .push_back(reply);
+        self
+    }
+
+    pub fn build(self) -> MockTransport {
+        MockTransport {
+            attempts: Mutex::new(HashMap::new()),
+            script: Mutex::new(self.script),
+            received: Mutex::new(Vec::new()),
+        }
+    }
 }
 
 impl MockTransport {
     pub fn new() -> Self {
         Self {
             attempts: Mutex::new(HashMap::new()),
+            #[cfg(test)]
+            script: Mutex::new(MockScript::default()),
+            #[cfg(test)]
+            received: Mutex::new(Vec::new()),
         }
     }
 
+    #[cfg(test)]
+    pub fn builder() -> MockTransportBuilder {
+        MockTransportBuilder::default()
+    }
+
+    /// Every request seen by this mock, in dispatch order.
+    #[cfg(test)]
+    pub fn received_requests(&self) -> Vec<ProviderRequest> {
+        self.received
+            .lock()
+            .map(|received| received.clone())
+            .unwrap_or_default()
+    }
+
+    #[cfg(test)]
+    pub fn dispatched_correlation_ids(&self) -> Vec<String> {
+        self.received_requests()
+            .into_iter()
+            .map(|r| r.correlation_id.unwrap_or_default())
+            .collect()
+    }
+
+    #[cfg(test)]
+    pub fn remaining_scripted_replies(&self) -> usize {
+        self.script
+            .lock()
+            .map(|script| {
+                script.sequence.len()
+                    + script
+                        .by_correlation
+                        .values()
+                        .map(|queue| queue.len())
+                        .sum::<usize>()
+            })
+            .unwrap_or(0)
+    }
+
+    #[cfg(test)]
+    fn take_scripted_reply(
+        &self,
+        request: &ProviderRequest,
+    ) -> Option<Result<ProviderResponse, ProviderError>> {
+        if let Ok(mut received) = self.received.lock() {
+            received.push(request.clone());
+        }
+        let mut script = self.script.lock().ok()?;
+        let scoped = request
+            .correlation_id
+            .as_deref()
+            .and_then(|id| script.by_correlation.get_mut(id))
+            .and_then(|queue| queue.pop_front());
+        let reply = scoped.or_else(|| script.sequence.pop_front())?;
+        Some(match reply {
+            MockReply::Text(text) => Ok(ProviderResponse {
+                provider_kind: request.provider_kind,
+                provider_tier: request.provider_tier,
+                model: request.model.clone(),
+                text,
+                usage: Self::usage_for(request),
+            }),
+            MockReply::Retryable(message) => Err(ProviderError::retryable(message)),
+            MockReply::NonRetryable(message) => Err(ProviderError::non_retryable(message)),
+            MockReply::RateLimited(message, retry_after_ms) => {
+                Err(ProviderError::rate_limited(message, Some(retry_after_ms)))
+            }
+        })
+    }
+
     fn key_for(request: &ProviderRequest) -> String {
         request
             .correlation_id
@@ -46,6 +196,11 @@ impl ExecutionTransport for MockTransport {
         request: &ProviderRequest,
         _keychain_api_key: Option<&str>,
     ) -> Result<ProviderResponse, ProviderError> {
+        #[cfg(test)]
+        if let Some(reply) = self.take_scripted_reply(request) {
+            return reply;
+        }
+
         if request
             .correlation_id
             .as_deref()
@@ -115,3 +270,69 @@ impl ExecutionTransport for MockTransport {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{MockReply, MockTransport};
+    use crate::providers::types::{ProviderKind, ProviderRequest, ProviderTier};
+    use crate::transport::ExecutionTransport;
+
+    fn request(correlation_id: &str) -> ProviderRequest {
+        ProviderRequest {
+            provider_kind: ProviderKind::OpenAi,
+            provider_tier: ProviderTier::Supported,
+            model: "gpt-4o-mini".to_string(),
+            system_prompt: None,
+            input: "scripted mock input".to_string(),
+            max_output_tokens: Some(64),
+            correlation_id: Some(correlation_id.to_string()),
+        }
+    }
+
+    #[test]
+    fn scripted_sequence_replays_in_order_then_falls_back() {
+        let mock = MockTransport::builder()
+            .fail_retryable("busy")
+            .respond("second")
+            .build();
+
+        let first = mock
+            .dispatch(&request("a"), None)
+            .expect_err("scripted error");
+        assert!(first.is_retryable());
+        assert_eq!(first.message, "busy");
+        let second = mock.dispatch(&request("b"), None).expect("scripted text");
+        assert_eq!(second.text, "second");
+        let third = mock.dispatch(&request("c"), None).expect("default reply");
+        assert!(third.text.starts_with("Draft generated by openai"));
+
+        assert_eq!(mock.remaining_scripted_replies(), 0);
+        assert_eq!(mock.dispatched_correlation_ids(), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn correlation_replies_take_precedence_over_sequence() {
+        let mock = MockTransport::builder()
+            .respond("shared")
+            .fail_non_retryable("shared rejection")
+            .for_correlation(
+                "run_1:step_1",
+                MockReply::NonRetryable("rejected".to_string()),
+            )
+            .build();
+
+        let scoped = mock
+            .dispatch(&request("run_1:step_1"), None)
+            .expect_err("scoped error");
+        assert!(!scoped.is_retryable());
+        let shared = mock
+            .dispatch(&request("run_1:step_1"), None)
+            .expect("shared text");
+        assert_eq!(shared.text, "shared");
+        let rejected = mock
+            .dispatch(&request("run_2:step_1"), None)
+            .expect_err("shared error");
+        assert!(!rejected.is_retryable());
+        assert_eq!(mock.received_requests().len(), 3);
+    }
+}
@@ -4,10 +4,52 @@ mod relay;
 
 pub use local_http::LocalHttpTransport;
 pub use mock::MockTransport;
-pub use relay::{RelayApprovalDecision, RelayTransport};
+pub use relay::{
+    RelayApprovalDecision, RelayApprovalDecisionSource, RelayTimeoutPolicy, RelayTransport,
+};
+#[cfg(test)]
+pub use relay::{RelayApprovalPollResponse, RelayStreamSupport};
 
 use crate::providers::types::{ProviderError, ProviderRequest, ProviderResponse};
 
+/// Longest provider-requested delay we honor; anything beyond is clamped.
+const RETRY_AFTER_MAX_MS: u64 = 60 * 60 * 1000;
+
+/// Curl `write-out` fragment appended after the status code: a space, then the raw
+/// `Retry-After` header (empty when absent).
+pub(crate) const CURL_RETRY_AFTER_WRITE_OUT: &str = " %header{retry-after}";
+
+/// Parses a `Retry-After` value (delta-seconds or an HTTP-date) into a delay from `now_ms`.
+/// Dates in the past yield zero; unparseable values yield `None`.
+pub(crate) fn parse_retry_after_ms(value: &str, now_ms: i64) -> Option<u64> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    let delay_ms = if let Ok(seconds) = value.parse::<u64>() {
+        seconds.saturating_mul(1000)
+    } else {
+        let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+        at.timestamp_millis().saturating_sub(now_ms).max(0) as u64
+    };
+    Some(delay_ms.min(RETRY_AFTER_MAX_MS))
+}
+
+/// Splits the curl status trailer (`"429 120"`) into the HTTP status and `Retry-After` value.
+pub(crate) fn split_status_trailer(trailer: &str) -> (u16, Option<&str>) {
+    let trailer = trailer.trim();
+    let (code, retry_after) = trailer.split_once(' ').unwrap_or((trailer, ""));
+    let retry_after = Some(retry_after.trim()).filter(|v| !v.is_empty());
+    (code.trim().parse().unwrap_or(0), retry_after)
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
 pub trait ExecutionTransport: Send + Sync {
     fn dispatch(
         &self,
@@ -1,12 +1,110 @@
-use crate::providers::types::{ProviderError, ProviderRequest, ProviderResponse};
+use crate::providers::types::{
+    ProviderError, ProviderErrorKind, ProviderRequest, ProviderResponse,
+};
 use crate::transport::ExecutionTransport;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::io::Write;
 use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Largest decision batch the relay will hand out per pull.
+pub const MAX_APPROVAL_BATCH_SIZE: usize = 100;
+
+/// Where approval decisions come from. `RelayTransport` is the only production source; tests
+/// substitute a capturing fake.
+pub trait RelayApprovalDecisionSource {
+    fn poll_approval_decisions(
+        &self,
+        token: &str,
+        device_id: &str,
+        limit: usize,
+    ) -> Result<RelayApprovalPollResponse, ProviderError>;
+
+    fn stream_approval_decisions(
+        &self,
+        token: &str,
+        device_id: &str,
+        limit: usize,
+        wait_seconds: i64,
+    ) -> Result<RelayApprovalPollResponse, ProviderError>;
+
+    /// Last known answer to "does this relay stream decisions?", if it was ever probed.
+    fn stream_support(&self) -> Option<RelayStreamSupport> {
+        None
+    }
+
+    fn record_stream_support(&self, _supported: bool) {}
+}
+
+/// Outcome of the most recent stream probe against one relay stream URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelayStreamSupport {
+    pub supported: bool,
+    pub probed_at_ms: i64,
+}
+
+/// Process-wide stream capability per relay stream URL. Transports are built per tick, so the
+/// answer has to outlive any one of them.
+#[derive(Default)]
+pub struct RelayStreamSupportCache {
+    entries: Mutex<HashMap<String, RelayStreamSupport>>,
+}
+
+impl RelayStreamSupportCache {
+    pub fn global() -> &'static Self {
+        static CACHE: OnceLock<RelayStreamSupportCache> = OnceLock::new();
+        CACHE.get_or_init(Self::default)
+    }
+
+    pub fn get(&self, stream_url: &str) -> Option<RelayStreamSupport> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(stream_url)
+            .copied()
+    }
+
+    pub fn record(&self, stream_url: &str, supported: bool, now_ms: i64) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(
+                stream_url.to_string(),
+                RelayStreamSupport {
+                    supported,
+                    probed_at_ms: now_ms,
+                },
+            );
+    }
+}
+
+/// Connect/read timeouts and retry budget for relay calls. Bounds live in `db` next to the
+/// runner-control settings that feed this.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelayTimeoutPolicy {
+    pub connect_timeout_seconds: i64,
+    pub read_timeout_seconds: i64,
+    pub max_retries: u32,
+    pub retry_backoff_ms: u64,
+}
+
+impl Default for RelayTimeoutPolicy {
+    fn default() -> Self {
+        Self {
+            connect_timeout_seconds: 10,
+            read_timeout_seconds: 30,
+            max_retries: 2,
+            retry_backoff_ms: 250,
+        }
+    }
+}
 
 pub struct RelayTransport {
     relay_url: String,
+    policy: RelayTimeoutPolicy,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,13 +126,32 @@ pub struct RelayApprovalPollResponse {
     pub decisions: Vec<RelayApprovalDecision>,
 }
 
+/// Result of one relay health round-trip. `status` is a short machine-readable label;
+/// `message` is safe to show as-is.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayPing {
+    pub relay_url: String,
+    pub reachable: bool,
+    pub status: String,
+    pub latency_ms: i64,
+    pub http_status: Option<u16>,
+    pub message: String,
+}
+
 impl RelayTransport {
     pub fn new(relay_url: impl Into<String>) -> Self {
         Self {
             relay_url: relay_url.into(),
+            policy: RelayTimeoutPolicy::default(),
         }
     }
 
+    pub fn with_policy(mut self, policy: RelayTimeoutPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
     pub fn default_url() -> String {
         std::env::var("TERMINUS_RELAY_URL")
             .ok()
@@ -70,6 +187,59 @@ impl RelayTransport {
         }
     }
 
+    /// Health endpoint next to the dispatch URL (`.../dispatch` -> `.../health`).
+    pub fn health_url(&self) -> String {
+        match self.relay_url.rsplit_once('/') {
+            Some((prefix, _)) if prefix.contains("://") && !prefix.ends_with('/') => {
+                format!("{prefix}/health")
+            }
+            _ => format!("{}/health", self.relay_url.trim_end_matches('/')),
+        }
+    }
+
+    /// Issues a short unauthenticated GET against the relay health endpoint and reports
+    /// reachability and latency. Never fails: transport errors map to an unreachable ping.
+    pub fn ping(&self) -> RelayPing {
+        let url = self.health_url();
+        let sentinel = "__TERMINUS_HTTP_STATUS__:";
+        let mut config = String::new();
+        config.push_str("silent\n");
+        config.push_str("connect-timeout = 3\n");
+        config.push_str("max-time = 5\n");
+        config.push_str("output = \"/dev/null\"\n");
+        config.push_str(&format!("url = \"{url}\"\n"));
+        config.push_str(&format!("write-out = \"{sentinel}%{{http_code}}\"\n"));
+
+        let started = Instant::now();
+        let output = Command::new("curl")
+            .arg("--config")
+            .arg("-")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .and_then(|mut child| {
+                if let Some(stdin) = child.stdin.as_mut() {
+                    stdin.write_all(config.as_bytes())?;
+                }
+                child.wait_with_output()
+            });
+        let latency_ms = started.elapsed().as_millis() as i64;
+        let Ok(output) = output else {
+            return map_relay_ping_outcome(&url, -1, 0, latency_ms);
+        };
+        let http_status = String::from_utf8_lossy(&output.stdout)
+            .rsplit_once(sentinel)
+            .and_then(|(_, code)| code.trim().parse::<u16>().ok())
+            .unwrap_or(0);
+        map_relay_ping_outcome(
+            &url,
+            output.status.code().unwrap_or(1),
+            http_status,
+            latency_ms,
+        )
+    }
+
     fn require_token(keychain_token: Option<&str>) -> Result<&str, ProviderError> {
         keychain_token
             .filter(|v| !v.trim().is_empty())
@@ -81,6 +251,9 @@ impl RelayTransport {
     }
 
     fn classify_curl_failure(status: i32, stderr: &str) -> ProviderError {
+        if status == 28 {
+            return ProviderError::retryable("Terminus relay timed out. Try again shortly.");
+        }
         let retryable = matches!(status, 5 | 6 | 7 | 28 | 52 | 56);
         if retryable || stderr.to_ascii_lowercase().contains("could not resolve") {
             ProviderError::retryable(
@@ -91,12 +264,16 @@ impl RelayTransport {
         }
     }
 
-    fn classify_http_status(http_status: u16) -> ProviderError {
+    fn classify_http_status(http_status: u16, retry_after: Option<&str>) -> ProviderError {
         match http_status {
             401 | 403 => ProviderError::non_retryable(
                 "Your Terminus session needs attention. Sign in again and retry.",
             ),
-            408 | 429 => ProviderError::retryable(
+            429 => ProviderError::rate_limited(
+                "Terminus relay is rate limiting requests. Try again shortly.",
+                retry_after.and_then(|v| super::parse_retry_after_ms(v, super::now_ms())),
+            ),
+            408 => ProviderError::retryable(
                 "Terminus relay is rate limiting or temporarily unavailable. Try again shortly.",
             ),
             500..=599 => ProviderError::retryable(
@@ -112,7 +289,12 @@ impl RelayTransport {
         token: &str,
         body_json: &Value,
     ) -> Result<Value, ProviderError> {
-        self.curl_json_request_to_url_with_timeout(url, token, body_json, 30)
+        self.curl_json_request_to_url_with_timeout(
+            url,
+            token,
+            body_json,
+            self.policy.read_timeout_seconds,
+        )
     }
 
     fn curl_json_request_to_url_with_timeout(
@@ -127,7 +309,11 @@ impl RelayTransport {
         config.push_str("silent\n");
         config.push_str("show-error\n");
         config.push_str("location\n");
-        config.push_str(&format!("max-time = {}\n", max_time_seconds.clamp(5, 60)));
+        config.push_str(&format!(
+            "connect-timeout = {}\n",
+            self.policy.connect_timeout_seconds.clamp(1, 60)
+        ));
+        config.push_str(&format!("max-time = {}\n", max_time_seconds.clamp(5, 120)));
         config.push_str("request = \"POST\"\n");
         config.push_str(&format!("url = \"{url}\"\n"));
         config.push_str("header = \"Content-Type: application/json\"\n");
@@ -135,7 +321,10 @@ impl RelayTransport {
         let body = serde_json::to_string(body_json)
             .map_err(|_| ProviderError::non_retryable("Relay request could not be encoded."))?;
         config.push_str(&format!("data = {body}\n"));
-        config.push_str(&format!("write-out = \"\\n{sentinel}%{{http_code}}\"\n"));
+        config.push_str(&format!(
+            "write-out = \"\\n{sentinel}%{{http_code}}{}\"\n",
+            super::CURL_RETRY_AFTER_WRITE_OUT
+        ));
 
         let mut child = Command::new("curl")
             .arg("--config")
@@ -169,9 +358,9 @@ impl RelayTransport {
         let (json_str, status_str) = stdout
             .rsplit_once(sentinel)
             .ok_or_else(|| ProviderError::retryable("Relay response could not be parsed."))?;
-        let http_status: u16 = status_str.trim().parse().unwrap_or(0);
+        let (http_status, retry_after) = super::split_status_trailer(status_str);
         if !(200..=299).contains(&http_status) {
-            return Err(Self::classify_http_status(http_status));
+            return Err(Self::classify_http_status(http_status, retry_after));
         }
 
         serde_json::from_str(json_str.trim())
@@ -181,8 +370,10 @@ impl RelayTransport {
     fn curl_json_request(&self, token: &str, body_json: &Value) -> Result<Value, ProviderError> {
         self.curl_json_request_to_url(&self.relay_url, token, body_json)
     }
+}
 
-    pub fn poll_approval_decisions(
+impl RelayApprovalDecisionSource for RelayTransport {
+    fn poll_approval_decisions(
         &self,
         token: &str,
         device_id: &str,
@@ -190,10 +381,12 @@ impl RelayTransport {
     ) -> Result<RelayApprovalPollResponse, ProviderError> {
         let payload = serde_json::json!({
             "deviceId": device_id,
-            "limit": limit.clamp(1, 50),
+            "limit": limit.clamp(1, MAX_APPROVAL_BATCH_SIZE),
         });
-        let json =
-            self.curl_json_request_to_url(&Self::default_approval_poll_url(), token, &payload)?;
+        let url = Self::default_approval_poll_url();
+        let json = retry_relay_call(self.policy, || {
+            self.curl_json_request_to_url(&url, token, &payload)
+        })?;
         serde_json::from_value::<RelayApprovalPollResponse>(json.clone())
             .or_else(|_| {
                 json.get("decisions")
@@ -209,7 +402,7 @@ impl RelayTransport {
             })
     }
 
-    pub fn stream_approval_decisions(
+    fn stream_approval_decisions(
         &self,
         token: &str,
         device_id: &str,
@@ -218,15 +411,16 @@ impl RelayTransport {
     ) -> Result<RelayApprovalPollResponse, ProviderError> {
         let payload = serde_json::json!({
             "deviceId": device_id,
-            "limit": limit.clamp(1, 50),
+            "limit": limit.clamp(1, MAX_APPROVAL_BATCH_SIZE),
             "waitSeconds": wait_seconds.clamp(1, 25),
         });
-        let json = self.curl_json_request_to_url_with_timeout(
-            &Self::default_approval_stream_url(),
-            token,
-            &payload,
-            wait_seconds.saturating_add(5),
-        )?;
+        let url = Self::default_approval_stream_url();
+        let max_time = wait_seconds
+            .saturating_add(5)
+            .max(self.policy.read_timeout_seconds);
+        let json = retry_relay_call(self.policy, || {
+            self.curl_json_request_to_url_with_timeout(&url, token, &payload, max_time)
+        })?;
         serde_json::from_value::<RelayApprovalPollResponse>(json.clone())
             .or_else(|_| {
                 json.get("decisions")
@@ -241,6 +435,18 @@ impl RelayTransport {
                 ProviderError::retryable("Relay approval stream response could not be parsed.")
             })
     }
+
+    fn stream_support(&self) -> Option<RelayStreamSupport> {
+        RelayStreamSupportCache::global().get(&Self::default_approval_stream_url())
+    }
+
+    fn record_stream_support(&self, supported: bool) {
+        RelayStreamSupportCache::global().record(
+            &Self::default_approval_stream_url(),
+            supported,
+            super::now_ms(),
+        );
+    }
 }
 
 impl ExecutionTransport for RelayTransport {
@@ -251,7 +457,7 @@ impl ExecutionTransport for RelayTransport {
     ) -> Result<ProviderResponse, ProviderError> {
         let token = Self::require_token(keychain_api_key)?;
         let payload = serde_json::json!({
-            "providerRequest": request
+            "providerRequest": relay_provider_request(request)
         });
         let json = self.curl_json_request(token, &payload)?;
 
@@ -269,9 +475,244 @@ impl ExecutionTransport for RelayTransport {
     }
 }
 
+/// The relay forwards `input` as the prompt and knows no system slot, so the system prompt is
+/// folded into `input` before the request leaves.
+fn relay_provider_request(request: &ProviderRequest) -> ProviderRequest {
+    ProviderRequest {
+        system_prompt: None,
+        input: request.full_prompt(),
+        ..request.clone()
+    }
+}
+
+/// Runs `call`, retrying retryable failures up to `policy.max_retries` more times with doubling
+/// backoff. Rate limits and non-retryable errors return immediately so the sync backoff decides.
+pub fn retry_relay_call<T>(
+    policy: RelayTimeoutPolicy,
+    mut call: impl FnMut() -> Result<T, ProviderError>,
+) -> Result<T, ProviderError> {
+    let mut attempt: u32 = 0;
+    loop {
+        match call() {
+            Err(error)
+                if matches!(error.kind, ProviderErrorKind::Retryable)
+                    && attempt < policy.max_retries =>
+            {
+                let delay = policy
+                    .retry_backoff_ms
+                    .saturating_mul(1u64 << attempt.min(6))
+                    .min(5_000);
+                if delay > 0 {
+                    std::thread::sleep(Duration::from_millis(delay));
+                }
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn map_relay_ping_outcome(
+    relay_url: &str,
+    curl_status: i32,
+    http_status: u16,
+    latency_ms: i64,
+) -> RelayPing {
+    let (reachable, status, http_status, message) = match (curl_status, http_status) {
+        (0, 200..=299) => (
+            true,
+            "ok",
+            Some(http_status),
+            format!("Terminus relay responded in {latency_ms} ms."),
+        ),
+        (0, 401 | 403) => (
+            true,
+            "auth_required",
+            Some(http_status),
+            "Terminus relay is reachable but needs you to sign in again.".to_string(),
+        ),
+        (0, 300..=499) => (
+            true,
+            "ok",
+            Some(http_status),
+            format!("Terminus relay responded in {latency_ms} ms."),
+        ),
+        (0, 500..=599) => (
+            false,
+            "server_error",
+            Some(http_status),
+            format!("Terminus relay is up but returned a server error (HTTP {http_status})."),
+        ),
+        (6, _) => (
+            false,
+            "dns_failed",
+            None,
+            "Terminus relay host could not be resolved. Check your network connection.".to_string(),
+        ),
+        (7, _) => (
+            false,
+            "refused",
+            None,
+            "Terminus relay refused the connection. Try again shortly.".to_string(),
+        ),
+        (28, _) => (
+            false,
+            "timeout",
+            None,
+            "Terminus relay did not respond in time.".to_string(),
+        ),
+        _ => (
+            false,
+            "unreachable",
+            None,
+            "Terminus relay could not be reached.".to_string(),
+        ),
+    };
+    RelayPing {
+        relay_url: relay_url.to_string(),
+        reachable,
+        status: status.to_string(),
+        latency_ms,
+        http_status,
+        message,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::RelayTransport;
+    use super::{
+        relay_provider_request, retry_relay_call, RelayStreamSupport, RelayStreamSupportCache,
+        RelayTimeoutPolicy, RelayTransport,
+    };
+    use crate::providers::types::{
+        ProviderError, ProviderErrorKind, ProviderKind, ProviderRequest, ProviderTier,
+    };
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    /// Answers a single HTTP request on a loopback port with `status_line`.
+    fn serve_once(status_line: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock relay");
+        let addr = listener.local_addr().expect("mock relay addr");
+        std::thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status_line}\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok"
+                );
+            }
+        });
+        format!("http://{addr}/dispatch")
+    }
+
+    #[test]
+    fn relay_request_folds_the_system_prompt_into_input() {
+        let request = ProviderRequest {
+            provider_kind: ProviderKind::Anthropic,
+            provider_tier: ProviderTier::Supported,
+            model: "claude-3-5-haiku-latest".to_string(),
+            system_prompt: Some("Output JSON only.".to_string()),
+            input: "Intent: track vendor updates".to_string(),
+            max_output_tokens: Some(900),
+            correlation_id: Some("relay_fold".to_string()),
+        };
+        let payload = serde_json::to_value(relay_provider_request(&request)).expect("json");
+        assert!(payload.get("system_prompt").is_none());
+        assert_eq!(
+            payload["input"],
+            "Output JSON only.\n\nIntent: track vendor updates"
+        );
+        assert_eq!(payload["correlation_id"], "relay_fold");
+    }
+
+    #[test]
+    fn ping_maps_success_and_unreachable_relay() {
+        let relay = RelayTransport::new(serve_once("200 OK"));
+        assert!(relay.health_url().ends_with("/health"));
+        let ping = relay.ping();
+        assert!(ping.reachable);
+        assert_eq!(ping.status, "ok");
+        assert_eq!(ping.http_status, Some(200));
+
+        let failing = RelayTransport::new(serve_once("503 Service Unavailable")).ping();
+        assert!(!failing.reachable);
+        assert_eq!(failing.status, "server_error");
+
+        let closed_port = {
+            let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+            listener.local_addr().expect("addr").port()
+        };
+        let down = RelayTransport::new(format!("http://127.0.0.1:{closed_port}/dispatch")).ping();
+        assert!(!down.reachable);
+        assert_eq!(down.status, "refused");
+        assert_eq!(down.http_status, None);
+    }
+
+    #[test]
+    fn relay_timeout_is_retryable_and_retry_budget_is_honored() {
+        let timeout = RelayTransport::classify_curl_failure(28, "Operation timed out");
+        assert!(matches!(timeout.kind, ProviderErrorKind::Retryable));
+        assert!(timeout.message.contains("timed out"));
+
+        let policy = RelayTimeoutPolicy {
+            max_retries: 3,
+            retry_backoff_ms: 0,
+            ..RelayTimeoutPolicy::default()
+        };
+        let mut attempts = 0;
+        let result: Result<(), ProviderError> = retry_relay_call(policy, || {
+            attempts += 1;
+            Err(RelayTransport::classify_curl_failure(28, ""))
+        });
+        assert!(matches!(
+            result.expect_err("timeouts exhaust retries").kind,
+            ProviderErrorKind::Retryable
+        ));
+        assert_eq!(attempts, 4);
+
+        let mut attempts = 0;
+        let recovered = retry_relay_call(policy, || {
+            attempts += 1;
+            if attempts < 2 {
+                Err(ProviderError::retryable("flaky"))
+            } else {
+                Ok(attempts)
+            }
+        });
+        assert_eq!(recovered.expect("second attempt succeeds"), 2);
+
+        let mut attempts = 0;
+        let rejected: Result<(), ProviderError> = retry_relay_call(policy, || {
+            attempts += 1;
+            Err(ProviderError::non_retryable("rejected"))
+        });
+        assert!(rejected.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn stream_support_cache_is_keyed_by_stream_url() {
+        let cache = RelayStreamSupportCache::default();
+        assert_eq!(cache.get("https://a.example/approvals/stream"), None);
+        cache.record("https://a.example/approvals/stream", false, 1_000);
+        cache.record("https://b.example/approvals/stream", true, 2_000);
+        assert_eq!(
+            cache.get("https://a.example/approvals/stream"),
+            Some(RelayStreamSupport {
+                supported: false,
+                probed_at_ms: 1_000
+            })
+        );
+        cache.record("https://a.example/approvals/stream", true, 3_000);
+        assert!(cache
+            .get("https://a.example/approvals/stream")
+            .is_some_and(|support| support.supported && support.probed_at_ms == 3_000));
+        assert!(cache
+            .get("https://b.example/approvals/stream")
+            .is_some_and(|support| support.supported));
+    }
 
     #[test]
     fn default_url_uses_hosted_default_when_env_missing() {
@@ -1,13 +1,21 @@
 use serde::{Deserialize, Serialize};
+use std::io::Read;
 use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
-use std::process::Command;
+use std::process::{Command, Stdio};
 use thiserror::Error;
 use url::Url;
 
-const FETCH_TIMEOUT_SECS: u32 = 15;
+const FETCH_TIMEOUT_MS: u64 = 15_000;
 const MAX_REDIRECTS: usize = 3;
 const MAX_RESPONSE_BYTES: usize = 200_000;
+const RESPONSE_HEADER_ALLOWANCE_BYTES: usize = 16_384;
 const EXCERPT_MAX_CHARS: usize = 2_000;
+/// Query parameters that vary per visit without changing the page. A trailing `*` matches
+/// any parameter with that prefix.
+pub const DEFAULT_TRACKING_PARAMS: &[&str] = &[
+    "utm_*", "fbclid", "gclid", "dclid", "msclkid", "mc_cid", "mc_eid", "_ga", "_gl", "igshid",
+    "yclid",
+];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebFetchResult {
@@ -29,29 +37,75 @@ pub enum WebFetchError {
         "This website points to a private or local network address, which Terminus does not allow."
     )]
     PrivateNetworkHost,
-    #[error("Website fetch timed out. Try again.")]
+    #[error("Website did not respond within the fetch time limit. Check the URL or pick a lighter page.")]
     Timeout,
     #[error("Website is temporarily unavailable. Try again.")]
     RetryableNetwork,
-    #[error("Website response was too large. Reduce scope.")]
+    #[error(
+        "Website response was too large for the fetch size limit. Point the monitor at a smaller page."
+    )]
     TooLarge,
-    #[error("Website content type is not supported yet.")]
-    UnsupportedContentType,
+    #[error("Website returned {0}, which Terminus can't read as text yet.")]
+    UnsupportedContentType(String),
     #[error("Could not read website content.")]
     FetchFailed,
     #[error("Website redirected to an unsupported location.")]
     InvalidRedirect,
+    #[error("Website redirected to {0}, which is outside this Autopilot's allowlist.")]
+    RedirectOffAllowlist(String),
+    #[error("Website redirected too many times.")]
+    TooManyRedirects,
+}
+
+/// Per-fetch bounds and extraction mode. `Default` carries the production limits
+/// and readable-text extraction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebFetchOptions {
+    pub max_redirects: usize,
+    pub max_bytes: usize,
+    pub timeout_ms: u64,
+    /// Keep the full HTML instead of extracting readable main-content text.
+    pub raw_html: bool,
+    /// Stripped from every URL in the page before hashing so per-visit tracking links don't
+    /// register as changes.
+    pub tracking_params: Vec<String>,
+}
+
+impl Default for WebFetchOptions {
+    fn default() -> Self {
+        Self {
+            max_redirects: MAX_REDIRECTS,
+            max_bytes: MAX_RESPONSE_BYTES,
+            timeout_ms: FETCH_TIMEOUT_MS,
+            raw_html: false,
+            tracking_params: DEFAULT_TRACKING_PARAMS
+                .iter()
+                .map(|param| param.to_string())
+                .collect(),
+        }
+    }
 }
 
 impl WebFetchError {
     pub fn is_retryable(&self) -> bool {
-        matches!(self, Self::Timeout | Self::RetryableNetwork)
+        // Size and time caps are plan errors: retrying the same page hits the same cap.
+        matches!(self, Self::RetryableNetwork)
     }
 }
 
 pub fn fetch_allowlisted_text(
     url: &str,
     allowlisted_hosts: &[String],
+) -> Result<WebFetchResult, WebFetchError> {
+    fetch_allowlisted_text_with_options(url, allowlisted_hosts, WebFetchOptions::default())
+}
+
+/// Fetches `url`, following at most `options.max_redirects` redirects. Every hop is
+/// revalidated against the allowlist so a redirect cannot move the fetch off-domain.
+pub fn fetch_allowlisted_text_with_options(
+    url: &str,
+    allowlisted_hosts: &[String],
+    options: WebFetchOptions,
 ) -> Result<WebFetchResult, WebFetchError> {
     let (scheme, host) = parse_scheme_host(url).ok_or(WebFetchError::InvalidScheme)?;
     validate_scheme(&scheme)?;
@@ -59,18 +113,29 @@ pub fn fetch_allowlisted_text(
     reject_private_host_resolution(&host)?;
 
     let mut current_url = url.to_string();
-    for _ in 0..=MAX_REDIRECTS {
+    for hop in 0..=options.max_redirects {
         let parsed = ParsedFetchUrl::parse(&current_url)?;
         let pinned_addr = resolve_public_addr(&parsed.host, parsed.port)?;
-        let response = fetch_once(&current_url, &parsed.host, parsed.port, pinned_addr.ip())?;
+        let response = fetch_once(
+            &current_url,
+            &parsed.host,
+            parsed.port,
+            pinned_addr.ip(),
+            &options,
+        )?;
         if (300..400).contains(&response.status_code) {
+            if hop == options.max_redirects {
+                return Err(WebFetchError::TooManyRedirects);
+            }
             let location = response.location.ok_or(WebFetchError::InvalidRedirect)?;
             let next_url = resolve_redirect_url(&current_url, &location)
                 .ok_or(WebFetchError::InvalidRedirect)?;
             let (next_scheme, next_host) =
                 parse_scheme_host(&next_url).ok_or(WebFetchError::InvalidRedirect)?;
             validate_scheme(&next_scheme)?;
-            validate_allowlist(&next_host, allowlisted_hosts)?;
+            if !web_fetch_host_allowed(&next_host, allowlisted_hosts) {
+                return Err(WebFetchError::RedirectOffAllowlist(next_host));
+            }
             reject_private_host_resolution(&next_host)?;
             current_url = next_url;
             continue;
@@ -79,10 +144,9 @@ pub fn fetch_allowlisted_text(
         if !(200..300).contains(&response.status_code) {
             return Err(WebFetchError::FetchFailed);
         }
-        if response.body.len() > MAX_RESPONSE_BYTES {
-            return Err(WebFetchError::TooLarge);
-        }
 
+        // Only HTML and plain text are diffed. `fetch_once` already aborted oversized bodies; the
+        // length check below only catches one that fit within its header allowance.
         let normalized_content_type = response
             .content_type
             .to_ascii_lowercase()
@@ -92,13 +156,25 @@ pub fn fetch_allowlisted_text(
             .trim()
             .to_string();
         if normalized_content_type != "text/html" && normalized_content_type != "text/plain" {
-            return Err(WebFetchError::UnsupportedContentType);
+            return Err(WebFetchError::UnsupportedContentType(
+                if normalized_content_type.is_empty() {
+                    "an unlabeled response".to_string()
+                } else {
+                    normalized_content_type
+                },
+            ));
+        }
+        if response.body.len() > options.max_bytes {
+            return Err(WebFetchError::TooLarge);
         }
 
-        let extracted = if normalized_content_type == "text/html" {
-            html_to_text(&response.body)
+        let body = sanitize_tracking(&response.body, &options.tracking_params);
+        let extracted = if normalized_content_type == "text/html" && options.raw_html {
+            body.trim().to_string()
+        } else if normalized_content_type == "text/html" {
+            html_to_text(&body)
         } else {
-            collapse_whitespace(&response.body)
+            collapse_whitespace(&body)
         };
         let excerpt = truncate_chars(&extracted, EXCERPT_MAX_CHARS);
         let content_hash = fnv1a_64_hex(&extracted);
@@ -113,7 +189,7 @@ pub fn fetch_allowlisted_text(
         });
     }
 
-    Err(WebFetchError::InvalidRedirect)
+    Err(WebFetchError::TooManyRedirects)
 }
 
 #[derive(Debug)]
@@ -124,25 +200,27 @@ struct SingleFetchResponse {
     body: String,
 }
 
+/// Streams curl's output and aborts as soon as it exceeds `options.max_bytes`
+/// (plus a small header allowance), so an oversized page is never fully buffered.
 fn fetch_once(
     url: &str,
     host: &str,
     port: u16,
     ip: IpAddr,
+    options: &WebFetchOptions,
 ) -> Result<SingleFetchResponse, WebFetchError> {
     let resolve_arg = format!("{host}:{port}:{ip}");
-    let output = Command::new("curl")
+    let mut child = Command::new("curl")
         .args([
             "--silent",
-            "--show-error",
             "--max-time",
-            &FETCH_TIMEOUT_SECS.to_string(),
+            &format!("{:.3}", options.timeout_ms as f64 / 1000.0),
             "--proto",
             "=http,https",
             "--proto-redir",
             "=http,https",
             "--max-filesize",
-            &MAX_RESPONSE_BYTES.to_string(),
+            &options.max_bytes.to_string(),
             "--resolve",
             &resolve_arg,
             "--dump-header",
@@ -151,11 +229,33 @@ fn fetch_once(
             "-",
             url,
         ])
-        .output()
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
         .map_err(|_| WebFetchError::RetryableNetwork)?;
 
-    if !output.status.success() {
-        let code = output.status.code().unwrap_or(1);
+    let mut stdout = child.stdout.take().ok_or(WebFetchError::FetchFailed)?;
+    let cap = options.max_bytes + RESPONSE_HEADER_ALLOWANCE_BYTES;
+    let mut raw = Vec::new();
+    let mut chunk = [0_u8; 8192];
+    loop {
+        let read = stdout
+            .read(&mut chunk)
+            .map_err(|_| WebFetchError::FetchFailed)?;
+        if read == 0 {
+            break;
+        }
+        raw.extend_from_slice(&chunk[..read]);
+        if raw.len() > cap {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(WebFetchError::TooLarge);
+        }
+    }
+    let status = child.wait().map_err(|_| WebFetchError::FetchFailed)?;
+
+    if !status.success() {
+        let code = status.code().unwrap_or(1);
         return Err(match code {
             28 => WebFetchError::Timeout,
             63 => WebFetchError::TooLarge,
@@ -164,7 +264,7 @@ fn fetch_once(
         });
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stdout = String::from_utf8_lossy(&raw).to_string();
     let (raw_headers, body) = split_headers_and_body(&stdout).ok_or(WebFetchError::FetchFailed)?;
     let status_code = parse_status_code(raw_headers).ok_or(WebFetchError::FetchFailed)?;
     let content_type = parse_header(raw_headers, "content-type").unwrap_or_default();
@@ -212,18 +312,67 @@ fn validate_scheme(scheme: &str) -> Result<(), WebFetchError> {
 }
 
 fn validate_allowlist(host: &str, allowlisted_hosts: &[String]) -> Result<(), WebFetchError> {
-    let host_lc = host.to_ascii_lowercase();
-    let allowed = allowlisted_hosts.iter().any(|entry| {
-        let entry = entry.to_ascii_lowercase();
-        host_lc == entry || host_lc.ends_with(&format!(".{entry}"))
-    });
-    if allowed {
+    if web_fetch_host_allowed(host, allowlisted_hosts) {
         Ok(())
     } else {
         Err(WebFetchError::HostNotAllowlisted)
     }
 }
 
+/// Web fetches have always let a plain entry cover its subdomains, and stored monitors rely on
+/// `example.com` reaching `www.example.com`, so that rule stays on top of `host_matches_allowlist`.
+fn web_fetch_host_allowed(host: &str, allowlisted_hosts: &[String]) -> bool {
+    if host_matches_allowlist(host, allowlisted_hosts) {
+        return true;
+    }
+    let host_lc = host.trim().trim_end_matches('.').to_ascii_lowercase();
+    allowlisted_hosts.iter().any(|entry| {
+        let entry = entry.trim().to_ascii_lowercase();
+        !entry.starts_with("*.") && !entry.is_empty() && host_lc.ends_with(&format!(".{entry}"))
+    })
+}
+
+/// Plain entries match the host exactly. A `*.example.com` entry opts in to any
+/// subdomain of `example.com` (at any depth), but not `example.com` itself.
+pub fn host_matches_allowlist(host: &str, allowlisted_hosts: &[String]) -> bool {
+    let host_lc = host.trim().trim_end_matches('.').to_ascii_lowercase();
+    allowlisted_hosts.iter().any(|entry| {
+        let entry = entry.trim().to_ascii_lowercase();
+        match entry.strip_prefix("*.") {
+            Some(parent) => host_lc.ends_with(&format!(".{parent}")),
+            None => host_lc == entry,
+        }
+    })
+}
+
+/// Normalizes an allowlist entry: an exact host, or a single leading `*.` wildcard
+/// over a domain with at least two labels. Bare `*` and `*.com` are rejected.
+pub fn normalize_allowlist_entry(entry: &str) -> Result<String, String> {
+    let entry = entry.trim().trim_matches('.').to_ascii_lowercase();
+    let (wildcard, domain) = match entry.strip_prefix("*.") {
+        Some(rest) => (true, rest),
+        None => (false, entry.as_str()),
+    };
+    if domain.is_empty() || domain.contains('*') {
+        return Err(format!(
+            "Allowed domain \"{entry}\" is invalid. Use a host like example.com or *.example.com."
+        ));
+    }
+    if !domain
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+        || domain.split('.').any(str::is_empty)
+    {
+        return Err(format!("Allowed domain \"{entry}\" is not a valid host."));
+    }
+    if wildcard && !domain.contains('.') {
+        return Err(format!(
+            "Wildcard domain \"{entry}\" is too broad. Use *.example.com or narrower."
+        ));
+    }
+    Ok(entry)
+}
+
 fn reject_private_host_resolution(host: &str) -> Result<(), WebFetchError> {
     let host_lc = host.trim().to_ascii_lowercase();
     #[cfg(test)]
@@ -340,13 +489,26 @@ fn resolve_redirect_url(current_url: &str, location: &str) -> Option<String> {
     Some(format!("{base}/{location}"))
 }
 
+/// Blocks that never carry page content, plus common site chrome whose churn
+/// (menus, cookie banners, footers) would otherwise register as a page change. `<form>` is
+/// kept: some sites (ASP.NET WebForms and similar) wrap the whole body in one.
+const BOILERPLATE_TAGS: [&str; 10] = [
+    "script", "style", "noscript", "template", "svg", "iframe", "nav", "header", "footer", "aside",
+];
+
+/// Converts HTML to readable text: drops boilerplate blocks, prefers the `<main>`
+/// (or first `<article>`) region when present, strips tags and decodes common entities.
 fn html_to_text(input: &str) -> String {
-    let without_scripts = remove_tag_blocks(input, "script");
-    let without_styles = remove_tag_blocks(&without_scripts, "style");
-    let without_nav = remove_tag_blocks(&without_styles, "nav");
-    let mut out = String::with_capacity(without_nav.len());
+    let mut cleaned = input.to_string();
+    for tag in BOILERPLATE_TAGS {
+        cleaned = remove_tag_blocks(&cleaned, tag);
+    }
+    let content = inner_tag_block(&cleaned, "main")
+        .or_else(|| inner_tag_block(&cleaned, "article"))
+        .unwrap_or(&cleaned);
+    let mut out = String::with_capacity(content.len());
     let mut in_tag = false;
-    for ch in without_nav.chars() {
+    for ch in content.chars() {
         if ch == '<' {
             in_tag = true;
             out.push(' ');
@@ -361,18 +523,161 @@ fn html_to_text(input: &str) -> String {
             out.push(ch);
         }
     }
-    collapse_whitespace(&out)
+    collapse_whitespace(&decode_basic_entities(&out))
 }
 
-fn remove_tag_blocks(input: &str, tag: &str) -> String {
+/// Drops 1x1 tracking-pixel `<img>` tags and removes `tracking_params` from every http(s) URL,
+/// so two fetches of an unchanged page hash the same.
+pub fn sanitize_tracking(input: &str, tracking_params: &[String]) -> String {
+    strip_tracking_query_params(&remove_tracking_pixels(input), tracking_params)
+}
+
+fn remove_tracking_pixels(input: &str) -> String {
+    let lower = input.to_ascii_lowercase();
+    let mut out = String::with_capacity(input.len());
+    let mut from = 0;
+    while let Some(rel) = find_tag_open(&lower[from..], "img") {
+        let start = from + rel;
+        let Some(end_rel) = lower[start..].find('>') else {
+            break;
+        };
+        let end = start + end_rel + 1;
+        out.push_str(&input[from..start]);
+        let tag = &lower[start..end];
+        let is_pixel = ["width", "height"]
+            .iter()
+            .all(|attr| matches!(tag_attr_value(tag, attr), Some("0" | "1" | "1px")));
+        if !is_pixel {
+            out.push_str(&input[start..end]);
+        }
+        from = end;
+    }
+    out.push_str(&input[from..]);
+    out
+}
+
+fn tag_attr_value<'a>(tag: &'a str, attr: &str) -> Option<&'a str> {
+    let needle = format!("{attr}=");
+    let mut from = 0;
+    while let Some(rel) = tag[from..].find(&needle) {
+        let start = from + rel;
+        from = start + needle.len();
+        if !tag[..start].ends_with(|c: char| c.is_ascii_whitespace()) {
+            continue;
+        }
+        let rest = &tag[from..];
+        let value = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => rest[1..].split(quote).next().unwrap_or(""),
+            _ => rest
+                .split(|c: char| c.is_ascii_whitespace() || c == '>' || c == '/')
+                .next()
+                .unwrap_or(""),
+        };
+        return Some(value.trim());
+    }
+    None
+}
+
+fn strip_tracking_query_params(input: &str, tracking_params: &[String]) -> String {
+    if tracking_params.is_empty() {
+        return input.to_string();
+    }
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest
+        .find("http://")
+        .into_iter()
+        .chain(rest.find("https://"))
+        .min()
+    {
+        out.push_str(&rest[..start]);
+        let candidate = &rest[start..];
+        let end = candidate
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>' | ')'))
+            .unwrap_or(candidate.len());
+        out.push_str(&strip_url_tracking(&candidate[..end], tracking_params));
+        rest = &candidate[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn strip_url_tracking(raw: &str, tracking_params: &[String]) -> String {
+    let Ok(mut url) = Url::parse(&raw.replace("&amp;", "&")) else {
+        return raw.to_string();
+    };
+    if url.query().is_none() {
+        return raw.to_string();
+    }
+    let pairs = url.query_pairs().into_owned().collect::<Vec<_>>();
+    let kept = pairs
+        .iter()
+        .filter(|(name, _)| !is_tracking_param(name, tracking_params))
+        .collect::<Vec<_>>();
+    if kept.len() == pairs.len() {
+        return raw.to_string();
+    }
+    if kept.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(kept);
+    }
+    url.to_string()
+}
+
+fn is_tracking_param(name: &str, tracking_params: &[String]) -> bool {
+    let name = name.to_ascii_lowercase();
+    tracking_params.iter().any(|param| {
+        let param = param.trim().to_ascii_lowercase();
+        match param.strip_suffix('*') {
+            Some(prefix) => !prefix.is_empty() && name.starts_with(prefix),
+            None => name == param,
+        }
+    })
+}
+
+fn decode_basic_entities(input: &str) -> String {
+    input
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Finds `<tag` followed by a tag-name boundary, so `<nav` doesn't match `<navbar`.
+fn find_tag_open(lower: &str, tag: &str) -> Option<usize> {
     let open = format!("<{tag}");
+    let mut from = 0;
+    while let Some(rel) = lower[from..].find(&open) {
+        let start = from + rel;
+        match lower[start + open.len()..].chars().next() {
+            Some(c) if c == '>' || c == '/' || c.is_ascii_whitespace() => return Some(start),
+            None => return Some(start),
+            _ => from = start + open.len(),
+        }
+    }
+    None
+}
+
+fn inner_tag_block<'a>(input: &'a str, tag: &str) -> Option<&'a str> {
+    let lower = input.to_ascii_lowercase();
+    let start = find_tag_open(&lower, tag)?;
+    let content_start = start + lower[start..].find('>')? + 1;
+    let content_end = content_start + lower[content_start..].find(&format!("</{tag}>"))?;
+    Some(&input[content_start..content_end])
+}
+
+fn remove_tag_blocks(input: &str, tag: &str) -> String {
     let close = format!("</{tag}>");
     let mut remaining = input.to_string();
     let mut out = String::new();
 
     loop {
         let lower = remaining.to_ascii_lowercase();
-        let Some(start) = lower.find(&open) else {
+        let Some(start) = find_tag_open(&lower, tag) else {
             out.push_str(&remaining);
             break;
         };
@@ -404,8 +709,342 @@ fn truncate_chars(input: &str, max_chars: usize) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{is_private_ip, reject_private_host_resolution, WebFetchError};
-    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    use super::{
+        fetch_allowlisted_text_with_options, host_matches_allowlist, html_to_text, is_private_ip,
+        normalize_allowlist_entry, reject_private_host_resolution, sanitize_tracking,
+        validate_allowlist, WebFetchError, WebFetchOptions, DEFAULT_TRACKING_PARAMS,
+    };
+    use std::io::{Read, Write};
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, TcpListener};
+    use std::thread;
+
+    /// Serves one canned response per accepted connection. Each response is built from the
+    /// listener's own port so redirects can point back at the server.
+    fn spawn_server(responses: Vec<fn(u16) -> String>) -> (u16, thread::JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind test server");
+        let port = listener.local_addr().expect("local addr").port();
+        let handle = thread::spawn(move || {
+            for respond in responses {
+                let (mut stream, _) = listener.accept().expect("accept");
+                let mut buf = [0_u8; 2048];
+                let _ = stream.read(&mut buf);
+                let _ = stream.write_all(respond(port).as_bytes());
+            }
+        });
+        (port, handle)
+    }
+
+    fn redirect_to(location: String) -> String {
+        format!(
+            "HTTP/1.1 302 Found\r\nLocation: {location}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+        )
+    }
+
+    fn ok_text(_port: u16) -> String {
+        let body = "final page";
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    #[test]
+    fn follows_redirect_within_allowlist() {
+        let (port, handle) = spawn_server(vec![
+            |port| redirect_to(format!("http://127.0.0.1:{port}/final")),
+            ok_text,
+        ]);
+        let result = fetch_allowlisted_text_with_options(
+            &format!("http://127.0.0.1:{port}/start"),
+            &["127.0.0.1".to_string()],
+            WebFetchOptions::default(),
+        )
+        .expect("redirect within allowlist");
+        handle.join().expect("server");
+        assert!(result.url.ends_with("/final"));
+        assert_eq!(result.content_text, "final page");
+    }
+
+    #[test]
+    fn rejects_redirect_off_allowlist() {
+        let (port, handle) = spawn_server(vec![|port| {
+            redirect_to(format!("http://localhost:{port}/elsewhere"))
+        }]);
+        let err = fetch_allowlisted_text_with_options(
+            &format!("http://127.0.0.1:{port}/start"),
+            &["127.0.0.1".to_string()],
+            WebFetchOptions::default(),
+        )
+        .expect_err("redirect leaves allowlist");
+        handle.join().expect("server");
+        assert!(matches!(&err, WebFetchError::RedirectOffAllowlist(host) if host == "localhost"));
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn oversized_body_is_aborted_as_too_large() {
+        let (port, handle) = spawn_server(vec![|_| {
+            // No Content-Length, so curl can't reject up front and the stream cap must trip.
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nConnection: close\r\n\r\n{}",
+                "x".repeat(256 * 1024)
+            )
+        }]);
+        let err = fetch_allowlisted_text_with_options(
+            &format!("http://127.0.0.1:{port}/big"),
+            &["127.0.0.1".to_string()],
+            WebFetchOptions {
+                max_bytes: 4_096,
+                ..WebFetchOptions::default()
+            },
+        )
+        .expect_err("oversized body");
+        handle.join().expect("server");
+        assert!(matches!(err, WebFetchError::TooLarge));
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn slow_body_times_out() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind test server");
+        let port = listener.local_addr().expect("local addr").port();
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept");
+            let mut buf = [0_u8; 2048];
+            let _ = stream.read(&mut buf);
+            let _ = stream.write_all(
+                b"HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 10\r\n\r\nslow",
+            );
+            thread::sleep(std::time::Duration::from_millis(1_500));
+        });
+        let err = fetch_allowlisted_text_with_options(
+            &format!("http://127.0.0.1:{port}/slow"),
+            &["127.0.0.1".to_string()],
+            WebFetchOptions {
+                timeout_ms: 300,
+                ..WebFetchOptions::default()
+            },
+        )
+        .expect_err("slow body");
+        handle.join().expect("server");
+        assert!(matches!(err, WebFetchError::Timeout));
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn text_page_is_accepted() {
+        let (port, handle) = spawn_server(vec![ok_text]);
+        let result = fetch_allowlisted_text_with_options(
+            &format!("http://127.0.0.1:{port}/page"),
+            &["127.0.0.1".to_string()],
+            WebFetchOptions::default(),
+        )
+        .expect("text page");
+        handle.join().expect("server");
+        assert_eq!(result.content_type, "text/plain");
+        assert_eq!(result.content_text, "final page");
+    }
+
+    #[test]
+    fn binary_response_is_rejected_with_its_type() {
+        let (port, handle) = spawn_server(vec![|_| {
+            "HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: 8\r\nConnection: close\r\n\r\n\u{89}PNG\r\n\u{1a}\n"
+                .to_string()
+        }]);
+        let err = fetch_allowlisted_text_with_options(
+            &format!("http://127.0.0.1:{port}/logo"),
+            &["127.0.0.1".to_string()],
+            WebFetchOptions::default(),
+        )
+        .expect_err("binary response");
+        handle.join().expect("server");
+        assert!(matches!(&err, WebFetchError::UnsupportedContentType(t) if t == "image/png"));
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn stops_after_configured_redirect_limit() {
+        let (port, handle) = spawn_server(vec![|port| {
+            redirect_to(format!("http://127.0.0.1:{port}/again"))
+        }]);
+        let err = fetch_allowlisted_text_with_options(
+            &format!("http://127.0.0.1:{port}/start"),
+            &["127.0.0.1".to_string()],
+            WebFetchOptions {
+                max_redirects: 0,
+                ..WebFetchOptions::default()
+            },
+        )
+        .expect_err("redirect limit");
+        handle.join().expect("server");
+        assert!(matches!(err, WebFetchError::TooManyRedirects));
+    }
+
+    const MONITOR_FIXTURE_HTML: &str = r#"<!doctype html>
+<html>
+  <head>
+    <title>Pricing</title>
+    <style>body { color: red; }</style>
+    <script>window.analytics = { session: "abc123" };</script>
+  </head>
+  <body>
+    <header><a href="/">Acme</a> <span>Signed in as guest</span></header>
+    <nav class="top"><a href="/docs">Docs</a> <a href="/blog">Blog</a></nav>
+    <main id="content">
+      <h1>Pricing</h1>
+      <p>Starter plan: $10&nbsp;/ month.</p>
+      <p>Team plan: $25 &amp; up.</p>
+      <aside>Related: <a href="/faq">FAQ</a></aside>
+      <form><input name="email"> Subscribe</form>
+    </main>
+    <footer>&copy; 2026 Acme. Build 9f8e7d.</footer>
+    <noscript>Enable JavaScript</noscript>
+  </body>
+</html>"#;
+
+    #[test]
+    fn html_extraction_keeps_main_content_and_drops_boilerplate() {
+        assert_eq!(
+            html_to_text(MONITOR_FIXTURE_HTML),
+            "Pricing Starter plan: $10 / month. Team plan: $25 & up. Subscribe"
+        );
+    }
+
+    #[test]
+    fn html_extraction_keeps_a_page_wrapped_in_a_form() {
+        let html = r#"<body><form id="aspnetForm" method="post"><h1>Rates</h1><p>Basic: $5</p></form></body>"#;
+        assert_eq!(html_to_text(html), "Rates Basic: $5");
+    }
+
+    #[test]
+    fn html_extraction_ignores_chrome_churn() {
+        let churned = MONITOR_FIXTURE_HTML
+            .replace("Build 9f8e7d", "Build 0a1b2c")
+            .replace("session: \"abc123\"", "session: \"zzz999\"")
+            .replace("Signed in as guest", "Signed in as Sam");
+        assert_eq!(html_to_text(&churned), html_to_text(MONITOR_FIXTURE_HTML));
+    }
+
+    #[test]
+    fn html_extraction_respects_tag_boundaries_without_main() {
+        let html = "<body><navbar>Keep me</navbar><nav>Drop me</nav><p>Body</p></body>";
+        assert_eq!(html_to_text(html), "Keep me Body");
+    }
+
+    #[test]
+    fn raw_mode_keeps_full_html() {
+        let (port, handle) = spawn_server(vec![|_| {
+            let body = "<html><nav>Menu</nav><main>Hi</main></html>";
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+        }]);
+        let result = fetch_allowlisted_text_with_options(
+            &format!("http://127.0.0.1:{port}/raw"),
+            &["127.0.0.1".to_string()],
+            WebFetchOptions {
+                raw_html: true,
+                ..WebFetchOptions::default()
+            },
+        )
+        .expect("raw fetch");
+        handle.join().expect("server");
+        assert_eq!(
+            result.content_text,
+            "<html><nav>Menu</nav><main>Hi</main></html>"
+        );
+    }
+
+    #[test]
+    fn pages_differing_only_in_tracking_hash_as_unchanged() {
+        let (port, handle) = spawn_server(vec![
+            |_| {
+                let body = r#"<main><a href="https://shop.example.com/sale?id=7&amp;utm_source=mail&amp;fbclid=AbC1">Sale</a><img src="https://t.example.com/p.gif?u=1" width="1" height="1"></main>"#;
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+            },
+            |_| {
+                let body = r#"<main><a href="https://shop.example.com/sale?id=7&amp;utm_source=rss&amp;fbclid=XyZ9">Sale</a><img src="https://t.example.com/p.gif?u=2" width=1 height=1></main>"#;
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+            },
+        ]);
+        let fetch = || {
+            fetch_allowlisted_text_with_options(
+                &format!("http://127.0.0.1:{port}/page"),
+                &["127.0.0.1".to_string()],
+                WebFetchOptions {
+                    raw_html: true,
+                    ..WebFetchOptions::default()
+                },
+            )
+            .expect("fetch")
+        };
+        let first = fetch();
+        let second = fetch();
+        handle.join().expect("server");
+        assert_eq!(first.content_hash, second.content_hash);
+        assert_eq!(
+            first.content_text,
+            r#"<main><a href="https://shop.example.com/sale?id=7">Sale</a></main>"#
+        );
+
+        let custom = sanitize_tracking(
+            "https://example.com/a?session=1&keep=2 https://example.com/b?utm_source=x",
+            &["session".to_string()],
+        );
+        assert_eq!(
+            custom,
+            "https://example.com/a?keep=2 https://example.com/b?utm_source=x"
+        );
+        assert!(DEFAULT_TRACKING_PARAMS.contains(&"utm_*"));
+    }
+
+    #[test]
+    fn exact_allowlist_entry_does_not_cover_subdomains() {
+        let allowlist = vec!["api.example.com".to_string()];
+        assert!(host_matches_allowlist("api.example.com", &allowlist));
+        assert!(host_matches_allowlist("API.Example.com", &allowlist));
+        assert!(!host_matches_allowlist("v2.api.example.com", &allowlist));
+        assert!(!host_matches_allowlist("example.com", &allowlist));
+    }
+
+    #[test]
+    fn web_fetch_keeps_subdomain_match_for_plain_entries() {
+        let allowlist = vec!["example.com".to_string()];
+        assert!(validate_allowlist("www.example.com", &allowlist).is_ok());
+        assert!(validate_allowlist("example.com", &allowlist).is_ok());
+        assert!(validate_allowlist("badexample.com", &allowlist).is_err());
+        assert!(!host_matches_allowlist("www.example.com", &allowlist));
+    }
+
+    #[test]
+    fn wildcard_allowlist_entry_covers_subdomains_only() {
+        let allowlist = vec![normalize_allowlist_entry(" *.Example.com ").expect("wildcard")];
+        assert_eq!(allowlist[0], "*.example.com");
+        assert!(host_matches_allowlist("api.example.com", &allowlist));
+        assert!(host_matches_allowlist("v2.api.example.com", &allowlist));
+        assert!(!host_matches_allowlist("example.com", &allowlist));
+        assert!(!host_matches_allowlist("badexample.com", &allowlist));
+    }
+
+    #[test]
+    fn overly_broad_or_malformed_wildcards_are_rejected() {
+        assert!(normalize_allowlist_entry("*").is_err());
+        assert!(normalize_allowlist_entry("*.").is_err());
+        assert!(normalize_allowlist_entry("*.com").is_err());
+        assert!(normalize_allowlist_entry("api.*.example.com").is_err());
+        assert!(normalize_allowlist_entry("*.*.example.com").is_err());
+        assert_eq!(
+            normalize_allowlist_entry("Example.com.").expect("exact"),
+            "example.com"
+        );
+    }
 
     #[test]
     fn rejects_private_ipv4_hosts() {
@@ -1,5 +1,33 @@
 use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const DEFAULT_MAX_DELIVERIES_PER_MINUTE: i64 = 30;
+pub const MAX_DELIVERIES_PER_MINUTE_LIMIT: i64 = 600;
+const RATE_LIMIT_WINDOW_MS: i64 = 60_000;
+/// Dead letters beyond this count, or older than the retention window, are pruned on insert.
+pub const MAX_WEBHOOK_DEAD_LETTERS: i64 = 200;
+const WEBHOOK_DEAD_LETTER_RETENTION_MS: i64 = 14 * crate::db::MS_PER_DAY;
+pub const MAX_WEBHOOK_SCHEMA_FIELDS: usize = 32;
+const WEBHOOK_SCHEMA_TYPES: [&str; 7] = [
+    "string", "number", "integer", "boolean", "object", "array", "null",
+];
+
+/// Lightweight shape check for webhook bodies: each listed dot path must exist and, when a
+/// type is given, hold a value of that JSON type. Not a full JSON Schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayloadSchema {
+    pub required: Vec<WebhookPayloadField>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayloadField {
+    pub path: String,
+    #[serde(rename = "type", default)]
+    pub value_type: Option<String>,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -12,8 +40,10 @@ pub struct WebhookTriggerRecord {
     pub signature_mode: String,
     pub description: String,
     pub max_payload_bytes: i64,
+    pub max_deliveries_per_minute: i64,
     pub allowed_content_types: Vec<String>,
     pub provider_kind: String,
+    pub payload_schema: Option<WebhookPayloadSchema>,
     pub last_event_at_ms: Option<i64>,
     pub last_error: Option<String>,
     pub created_at_ms: i64,
@@ -38,12 +68,38 @@ pub struct WebhookTriggerEventRecord {
     pub run_id: Option<String>,
 }
 
+/// A webhook delivery that was accepted but could not be turned into a run. The body is kept
+/// so the run can be created again once the cause is fixed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDeadLetterRecord {
+    pub id: String,
+    pub trigger_id: String,
+    pub delivery_id: String,
+    pub event_idempotency_key: String,
+    pub relay_channel: Option<String>,
+    pub reason: String,
+    pub body_json: String,
+    pub status: String,
+    pub retry_count: i64,
+    pub run_id: Option<String>,
+    pub created_at_ms: i64,
+    pub updated_at_ms: i64,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateWebhookTriggerInput {
     pub autopilot_id: String,
     pub description: Option<String>,
     pub max_payload_bytes: Option<i64>,
+    #[serde(default)]
+    pub max_deliveries_per_minute: Option<i64>,
+    #[serde(default)]
+    pub signature_mode: Option<String>, // terminus_hmac_sha256|stripe|github_sha256
+    /// Stripe mode only: the `whsec_` secret Stripe shows for the endpoint.
+    #[serde(default)]
+    pub signing_secret: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -62,6 +118,7 @@ pub struct WebhookTriggerCreateInternal {
     pub signature_mode: String,
     pub description: String,
     pub max_payload_bytes: i64,
+    pub max_deliveries_per_minute: i64,
     pub allowed_content_types_json: String,
     pub plan_json: String,
     pub provider_kind: String,
@@ -92,9 +149,11 @@ pub struct WebhookTriggerRouteConfig {
     pub status: String,
     pub signature_mode: String,
     pub max_payload_bytes: i64,
+    pub max_deliveries_per_minute: i64,
     pub allowed_content_types: Vec<String>,
     pub plan_json: String,
     pub provider_kind: String,
+    pub payload_schema: Option<WebhookPayloadSchema>,
 }
 
 pub fn list_webhook_triggers(
@@ -106,7 +165,8 @@ pub fn list_webhook_triggers(
     let mut sql = String::from(
         "SELECT id, autopilot_id, status, endpoint_path, signature_mode, description,
                 max_payload_bytes, allowed_content_types_json, provider_kind,
-                last_event_at_ms, last_error, created_at_ms, updated_at_ms
+                last_event_at_ms, last_error, created_at_ms, updated_at_ms,
+                max_deliveries_per_minute, payload_schema_json
          FROM webhook_triggers",
     );
     if autopilot_id.is_some() {
@@ -150,7 +210,8 @@ pub fn get_webhook_trigger(
         .query_row(
             "SELECT id, autopilot_id, status, endpoint_path, signature_mode, description,
                     max_payload_bytes, allowed_content_types_json, provider_kind,
-                    last_event_at_ms, last_error, created_at_ms, updated_at_ms
+                    last_event_at_ms, last_error, created_at_ms, updated_at_ms,
+                    max_deliveries_per_minute, payload_schema_json
              FROM webhook_triggers WHERE id = ?1",
             params![trigger_id],
             |row| map_webhook_trigger_row(row, relay_base_url, secret_lookup),
@@ -170,8 +231,8 @@ pub fn create_webhook_trigger(
             "INSERT INTO webhook_triggers (
                id, autopilot_id, status, endpoint_path, signature_mode, description,
                max_payload_bytes, allowed_content_types_json, plan_json, provider_kind,
-               created_at_ms, updated_at_ms
-             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+               created_at_ms, updated_at_ms, max_deliveries_per_minute
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
             params![
                 payload.id,
                 payload.autopilot_id,
@@ -185,9 +246,15 @@ pub fn create_webhook_trigger(
                 payload.provider_kind,
                 payload.created_at_ms,
                 payload.updated_at_ms,
+                payload.max_deliveries_per_minute,
             ],
         )
         .map_err(|e| format!("Failed to create webhook trigger: {e}"))?;
+    crate::db::record_onboarding_milestone(
+        connection,
+        crate::db::OnboardingMilestone::WebhookTriggerCreated,
+        payload.created_at_ms,
+    )?;
     get_webhook_trigger(connection, &payload.id, relay_base_url, secret_lookup)?
         .ok_or_else(|| "Webhook trigger was created but could not be reloaded.".to_string())
 }
@@ -203,6 +270,7 @@ pub fn update_webhook_trigger_status(
             "UPDATE webhook_triggers
              SET status = ?1,
                  last_error = ?2,
+                 paused_by_bulk = 0,
                  updated_at_ms = strftime('%s','now') * 1000
              WHERE id = ?3",
             params![status, error, trigger_id],
@@ -211,6 +279,194 @@ pub fn update_webhook_trigger_status(
     Ok(())
 }
 
+/// Stores (or with `None`, clears) the trigger's payload schema. Callers normalize first.
+pub fn update_webhook_trigger_payload_schema(
+    connection: &Connection,
+    trigger_id: &str,
+    schema: Option<&WebhookPayloadSchema>,
+    now_ms: i64,
+) -> Result<(), String> {
+    let schema_json = schema
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| format!("Failed to encode webhook payload schema: {e}"))?;
+    let updated = connection
+        .execute(
+            "UPDATE webhook_triggers SET payload_schema_json = ?1, updated_at_ms = ?2 WHERE id = ?3",
+            params![schema_json, now_ms, trigger_id],
+        )
+        .map_err(|e| format!("Failed to update webhook payload schema: {e}"))?;
+    if updated == 0 {
+        return Err("Webhook trigger not found.".to_string());
+    }
+    Ok(())
+}
+
+/// Trims paths and lowercases types, rejecting empty paths, unknown types, duplicate paths and
+/// oversized specs. An empty field list normalizes to `None` (accept any JSON).
+pub fn normalize_webhook_payload_schema(
+    schema: WebhookPayloadSchema,
+) -> Result<Option<WebhookPayloadSchema>, String> {
+    if schema.required.len() > MAX_WEBHOOK_SCHEMA_FIELDS {
+        return Err(format!(
+            "Webhook payload schema can list at most {MAX_WEBHOOK_SCHEMA_FIELDS} fields."
+        ));
+    }
+    let mut required: Vec<WebhookPayloadField> = Vec::new();
+    for field in schema.required {
+        let path = field.path.trim().to_string();
+        if path.is_empty() || path.split('.').any(|segment| segment.trim().is_empty()) {
+            return Err(format!("Webhook payload field path is invalid: {path:?}."));
+        }
+        if required.iter().any(|existing| existing.path == path) {
+            return Err(format!("Webhook payload field `{path}` is listed twice."));
+        }
+        let value_type = field
+            .value_type
+            .map(|v| v.trim().to_ascii_lowercase())
+            .filter(|v| !v.is_empty());
+        if let Some(value_type) = value_type.as_deref() {
+            if !WEBHOOK_SCHEMA_TYPES.contains(&value_type) {
+                return Err(format!(
+                    "Webhook payload field `{path}` has unknown type {value_type:?}."
+                ));
+            }
+        }
+        required.push(WebhookPayloadField { path, value_type });
+    }
+    Ok(if required.is_empty() {
+        None
+    } else {
+        Some(WebhookPayloadSchema { required })
+    })
+}
+
+/// Checks `payload` against `schema`, naming the first offending path on failure.
+pub fn validate_webhook_payload(
+    schema: &WebhookPayloadSchema,
+    payload: &Value,
+) -> Result<(), String> {
+    for field in &schema.required {
+        let value = field
+            .path
+            .split('.')
+            .try_fold(payload, |current, segment| current.get(segment))
+            .ok_or_else(|| {
+                format!(
+                    "Webhook payload is missing required field `{}`.",
+                    field.path
+                )
+            })?;
+        if let Some(expected) = field.value_type.as_deref() {
+            let matches = match expected {
+                "string" => value.is_string(),
+                "number" => value.is_number(),
+                "integer" => value.is_i64() || value.is_u64(),
+                "boolean" => value.is_boolean(),
+                "object" => value.is_object(),
+                "array" => value.is_array(),
+                "null" => value.is_null(),
+                _ => true,
+            };
+            if !matches {
+                return Err(format!(
+                    "Webhook payload field `{}` must be of type {expected}.",
+                    field.path
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_payload_schema(raw: Option<String>) -> Option<WebhookPayloadSchema> {
+    raw.and_then(|json| serde_json::from_str(&json).ok())
+}
+
+/// Pauses every active trigger (optionally only for one Autopilot) and marks them as paused
+/// in bulk, so `resume_bulk_paused_webhook_triggers` can restore exactly this set. Returns the
+/// number of triggers paused.
+pub fn pause_all_webhook_triggers(
+    connection: &mut Connection,
+    autopilot_id: Option<&str>,
+    now: i64,
+) -> Result<usize, String> {
+    let tx = connection
+        .transaction()
+        .map_err(|e| format!("Failed to start webhook trigger pause: {e}"))?;
+    let paused = tx
+        .execute(
+            "UPDATE webhook_triggers
+             SET status = 'paused', paused_by_bulk = 1, updated_at_ms = ?1
+             WHERE status = 'active' AND (?2 IS NULL OR autopilot_id = ?2)",
+            params![now, autopilot_id],
+        )
+        .map_err(|e| format!("Failed to pause webhook triggers: {e}"))?;
+    tx.commit()
+        .map_err(|e| format!("Failed to commit webhook trigger pause: {e}"))?;
+    Ok(paused)
+}
+
+/// Re-activates only the triggers a bulk pause switched off. Triggers paused individually
+/// stay paused. Returns the number of triggers restored.
+pub fn resume_bulk_paused_webhook_triggers(
+    connection: &mut Connection,
+    autopilot_id: Option<&str>,
+    now: i64,
+) -> Result<usize, String> {
+    let tx = connection
+        .transaction()
+        .map_err(|e| format!("Failed to start webhook trigger restore: {e}"))?;
+    let restored = tx
+        .execute(
+            "UPDATE webhook_triggers
+             SET status = 'active', paused_by_bulk = 0, updated_at_ms = ?1
+             WHERE status = 'paused' AND paused_by_bulk = 1
+               AND (?2 IS NULL OR autopilot_id = ?2)",
+            params![now, autopilot_id],
+        )
+        .map_err(|e| format!("Failed to restore webhook triggers: {e}"))?;
+    tx.commit()
+        .map_err(|e| format!("Failed to commit webhook trigger restore: {e}"))?;
+    Ok(restored)
+}
+
+/// Marks the signing secret for `trigger_id` as replaced at `at_ms`.
+pub fn record_webhook_trigger_secret_rotated(
+    connection: &Connection,
+    trigger_id: &str,
+    at_ms: i64,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "UPDATE webhook_triggers
+             SET secret_rotated_at_ms = ?1, updated_at_ms = ?1
+             WHERE id = ?2",
+            params![at_ms, trigger_id],
+        )
+        .map_err(|e| format!("Failed to record webhook secret rotation: {e}"))?;
+    Ok(())
+}
+
+/// `(trigger_id, autopilot_id, secret_set_at_ms)` for every trigger. Secrets that were never
+/// rotated date from trigger creation.
+pub fn list_webhook_trigger_secret_ages(
+    connection: &Connection,
+) -> Result<Vec<(String, String, i64)>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT id, autopilot_id, COALESCE(secret_rotated_at_ms, created_at_ms)
+             FROM webhook_triggers
+             ORDER BY created_at_ms ASC",
+        )
+        .map_err(|e| format!("Failed to prepare webhook secret query: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .map_err(|e| format!("Failed to query webhook secrets: {e}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse webhook secrets: {e}"))
+}
+
 pub fn get_webhook_trigger_route_config(
     connection: &Connection,
     trigger_id: &str,
@@ -218,7 +474,8 @@ pub fn get_webhook_trigger_route_config(
     connection
         .query_row(
             "SELECT id, autopilot_id, status, signature_mode, max_payload_bytes,
-                    allowed_content_types_json, plan_json, provider_kind
+                    allowed_content_types_json, plan_json, provider_kind,
+                    max_deliveries_per_minute, payload_schema_json
              FROM webhook_triggers WHERE id = ?1",
             params![trigger_id],
             |row| {
@@ -231,9 +488,11 @@ pub fn get_webhook_trigger_route_config(
                     status: row.get(2)?,
                     signature_mode: row.get(3)?,
                     max_payload_bytes: row.get(4)?,
+                    max_deliveries_per_minute: row.get(8)?,
                     allowed_content_types,
                     plan_json: row.get(6)?,
                     provider_kind: row.get(7)?,
+                    payload_schema: parse_payload_schema(row.get(9)?),
                 })
             },
         )
@@ -281,6 +540,35 @@ pub fn list_webhook_trigger_events(
     Ok(out)
 }
 
+/// Authenticated deliveries for `trigger_id` received in the minute up to `now`, excluding
+/// `exclude_event_id` and deliveries that were already rate-limited, so a flood does not lock the
+/// trigger out once the source calms down. Unsigned or badly signed deliveries never count.
+pub fn count_recent_webhook_deliveries(
+    connection: &Connection,
+    trigger_id: &str,
+    exclude_event_id: &str,
+    now: i64,
+) -> Result<i64, String> {
+    connection
+        .query_row(
+            "SELECT COUNT(*) FROM webhook_trigger_events
+             WHERE trigger_id = ?1
+               AND received_at_ms > ?2
+               AND received_at_ms <= ?3
+               AND authenticated = 1
+               AND status != 'rate_limited'
+               AND id != ?4",
+            params![
+                trigger_id,
+                now - RATE_LIMIT_WINDOW_MS,
+                now,
+                exclude_event_id
+            ],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to count recent webhook deliveries: {e}"))
+}
+
 pub fn insert_webhook_trigger_event(
     connection: &Connection,
     payload: &WebhookTriggerEventInsert,
@@ -310,6 +598,20 @@ pub fn insert_webhook_trigger_event(
     Ok(changed > 0)
 }
 
+/// Marks a delivery as having passed signature checks, so it counts toward the rate limit.
+pub fn mark_webhook_trigger_event_authenticated(
+    connection: &Connection,
+    event_id: &str,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "UPDATE webhook_trigger_events SET authenticated = 1 WHERE id = ?1",
+            params![event_id],
+        )
+        .map_err(|e| format!("Failed to update webhook trigger event: {e}"))?;
+    Ok(())
+}
+
 pub fn update_webhook_trigger_event_status(
     connection: &Connection,
     trigger_id: &str,
@@ -354,6 +656,147 @@ pub fn touch_webhook_trigger_delivery(
     Ok(())
 }
 
+pub fn insert_webhook_dead_letter(
+    connection: &Connection,
+    record: &WebhookDeadLetterRecord,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "INSERT INTO webhook_dead_letters (
+               id, trigger_id, delivery_id, event_idempotency_key, relay_channel, reason,
+               body_json, status, retry_count, run_id, created_at_ms, updated_at_ms
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                record.id,
+                record.trigger_id,
+                record.delivery_id,
+                record.event_idempotency_key,
+                record.relay_channel,
+                record.reason,
+                record.body_json,
+                record.status,
+                record.retry_count,
+                record.run_id,
+                record.created_at_ms,
+                record.updated_at_ms
+            ],
+        )
+        .map_err(|e| format!("Failed to record webhook dead letter: {e}"))?;
+    prune_webhook_dead_letters(connection, record.created_at_ms, false)?;
+    Ok(())
+}
+
+pub fn get_webhook_dead_letter(
+    connection: &Connection,
+    id: &str,
+) -> Result<Option<WebhookDeadLetterRecord>, String> {
+    connection
+        .query_row(
+            "SELECT id, trigger_id, delivery_id, event_idempotency_key, relay_channel, reason,
+                    body_json, status, retry_count, run_id, created_at_ms, updated_at_ms
+             FROM webhook_dead_letters WHERE id = ?1",
+            params![id],
+            map_webhook_dead_letter_row,
+        )
+        .optional()
+        .map_err(|e| format!("Failed to load webhook dead letter: {e}"))
+}
+
+pub fn list_webhook_dead_letters(
+    connection: &Connection,
+    limit: usize,
+) -> Result<Vec<WebhookDeadLetterRecord>, String> {
+    let mut stmt = connection
+        .prepare(
+            "SELECT id, trigger_id, delivery_id, event_idempotency_key, relay_channel, reason,
+                    body_json, status, retry_count, run_id, created_at_ms, updated_at_ms
+             FROM webhook_dead_letters
+             ORDER BY created_at_ms DESC
+             LIMIT ?1",
+        )
+        .map_err(|e| format!("Failed to prepare webhook dead letter query: {e}"))?;
+    let rows = stmt
+        .query_map(
+            params![limit.clamp(1, MAX_WEBHOOK_DEAD_LETTERS as usize) as i64],
+            map_webhook_dead_letter_row,
+        )
+        .map_err(|e| format!("Failed to query webhook dead letters: {e}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse webhook dead letters: {e}"))
+}
+
+/// Records the outcome of a retry: `run_id` marks the dead letter as retried, otherwise the
+/// failure reason is refreshed and it stays pending.
+pub fn record_webhook_dead_letter_retry(
+    connection: &Connection,
+    id: &str,
+    run_id: Option<&str>,
+    reason: Option<&str>,
+    now: i64,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "UPDATE webhook_dead_letters
+             SET status = CASE WHEN ?1 IS NULL THEN status ELSE 'retried' END,
+                 run_id = COALESCE(?1, run_id),
+                 reason = COALESCE(?2, reason),
+                 retry_count = retry_count + 1,
+                 updated_at_ms = ?3
+             WHERE id = ?4",
+            params![run_id, reason, now, id],
+        )
+        .map_err(|e| format!("Failed to update webhook dead letter: {e}"))?;
+    Ok(())
+}
+
+/// Drops dead letters past the retention window or beyond the row cap. Returns how many rows
+/// were (or, on a dry run, would be) removed.
+pub fn prune_webhook_dead_letters(
+    connection: &Connection,
+    now: i64,
+    dry_run: bool,
+) -> Result<i64, String> {
+    let filter = "FROM webhook_dead_letters
+             WHERE created_at_ms < ?1
+                OR id NOT IN (
+                  SELECT id FROM webhook_dead_letters
+                  ORDER BY created_at_ms DESC
+                  LIMIT ?2
+                )";
+    let args = params![
+        now - WEBHOOK_DEAD_LETTER_RETENTION_MS,
+        MAX_WEBHOOK_DEAD_LETTERS
+    ];
+    if dry_run {
+        return connection
+            .query_row(&format!("SELECT COUNT(*) {filter}"), args, |row| row.get(0))
+            .map_err(|e| format!("Failed to count webhook dead letters: {e}"));
+    }
+    connection
+        .execute(&format!("DELETE {filter}"), args)
+        .map(|deleted| deleted as i64)
+        .map_err(|e| format!("Failed to prune webhook dead letters: {e}"))
+}
+
+fn map_webhook_dead_letter_row(
+    row: &rusqlite::Row<'_>,
+) -> rusqlite::Result<WebhookDeadLetterRecord> {
+    Ok(WebhookDeadLetterRecord {
+        id: row.get(0)?,
+        trigger_id: row.get(1)?,
+        delivery_id: row.get(2)?,
+        event_idempotency_key: row.get(3)?,
+        relay_channel: row.get(4)?,
+        reason: row.get(5)?,
+        body_json: row.get(6)?,
+        status: row.get(7)?,
+        retry_count: row.get(8)?,
+        run_id: row.get(9)?,
+        created_at_ms: row.get(10)?,
+        updated_at_ms: row.get(11)?,
+    })
+}
+
 fn map_webhook_trigger_row(
     row: &rusqlite::Row<'_>,
     relay_base_url: &str,
@@ -376,8 +819,10 @@ fn map_webhook_trigger_row(
         signature_mode: row.get(4)?,
         description: row.get(5)?,
         max_payload_bytes: row.get(6)?,
+        max_deliveries_per_minute: row.get(13)?,
         allowed_content_types,
         provider_kind: row.get(8)?,
+        payload_schema: parse_payload_schema(row.get(14)?),
         last_event_at_ms: row.get(9)?,
         last_error: row.get(10)?,
         created_at_ms: row.get(11)?,
@@ -414,6 +859,7 @@ mod tests {
                 signature_mode: "terminus_hmac_sha256".to_string(),
                 description: "Webhook for tests".to_string(),
                 max_payload_bytes: 32_768,
+                max_deliveries_per_minute: DEFAULT_MAX_DELIVERIES_PER_MINUTE,
                 allowed_content_types_json: "[\"application/json\"]".to_string(),
                 plan_json: "{\"schema_version\":\"1.0\"}".to_string(),
                 provider_kind: "openai".to_string(),
@@ -427,6 +873,8 @@ mod tests {
         assert_eq!(created.status, "active");
         assert!(created.secret_configured);
         assert!(created.endpoint_url.contains("/hooks/abc"));
+        let onboarding = crate::db::get_onboarding_state(&conn).expect("onboarding");
+        assert_eq!(onboarding.first_webhook_trigger_created_at_ms, Some(10));
 
         update_webhook_trigger_status(&conn, "wh_1", "paused", Some("Paused"))
             .expect("update status");
@@ -485,4 +933,118 @@ mod tests {
             .expect("count");
         assert_eq!(count, 1);
     }
+
+    #[test]
+    fn bulk_pause_and_restore_leaves_individually_paused_triggers_alone() {
+        let mut conn = setup_connection();
+        conn.execute(
+            "INSERT INTO autopilots (id, name, created_at) VALUES ('auto_other', 'Other', 1)",
+            [],
+        )
+        .expect("insert autopilot");
+        for (id, autopilot_id, status) in [
+            ("wh_1", "auto_test", "active"),
+            ("wh_2", "auto_test", "paused"),
+            ("wh_3", "auto_other", "active"),
+        ] {
+            conn.execute(
+                "INSERT INTO webhook_triggers (
+                   id, autopilot_id, status, endpoint_path, created_at_ms, updated_at_ms
+                 ) VALUES (?1, ?2, ?3, ?4, 1, 1)",
+                params![id, autopilot_id, status, format!("hooks/{id}")],
+            )
+            .expect("insert trigger");
+        }
+        let status_of = |conn: &Connection, id: &str| -> String {
+            conn.query_row(
+                "SELECT status FROM webhook_triggers WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .expect("status")
+        };
+
+        assert_eq!(
+            pause_all_webhook_triggers(&mut conn, Some("auto_test"), 5).expect("scoped pause"),
+            1
+        );
+        assert_eq!(status_of(&conn, "wh_3"), "active");
+        assert_eq!(
+            pause_all_webhook_triggers(&mut conn, None, 6).expect("pause"),
+            1
+        );
+        assert_eq!(
+            pause_all_webhook_triggers(&mut conn, None, 7).expect("repeat pause"),
+            0
+        );
+        for id in ["wh_1", "wh_2", "wh_3"] {
+            assert_eq!(status_of(&conn, id), "paused");
+        }
+
+        assert_eq!(
+            resume_bulk_paused_webhook_triggers(&mut conn, None, 8).expect("restore"),
+            2
+        );
+        assert_eq!(status_of(&conn, "wh_1"), "active");
+        assert_eq!(status_of(&conn, "wh_2"), "paused");
+        assert_eq!(status_of(&conn, "wh_3"), "active");
+        assert_eq!(
+            resume_bulk_paused_webhook_triggers(&mut conn, None, 9).expect("repeat restore"),
+            0
+        );
+    }
+
+    #[test]
+    fn payload_schema_accepts_conforming_and_names_offending_path() {
+        let schema = normalize_webhook_payload_schema(WebhookPayloadSchema {
+            required: vec![
+                WebhookPayloadField {
+                    path: " event ".to_string(),
+                    value_type: Some("String".to_string()),
+                },
+                WebhookPayloadField {
+                    path: "data.id".to_string(),
+                    value_type: Some("integer".to_string()),
+                },
+                WebhookPayloadField {
+                    path: "data".to_string(),
+                    value_type: None,
+                },
+            ],
+        })
+        .expect("normalize")
+        .expect("schema");
+        assert_eq!(schema.required[0].path, "event");
+        assert_eq!(schema.required[0].value_type.as_deref(), Some("string"));
+
+        let conforming = serde_json::json!({"event": "order.created", "data": {"id": 42}});
+        validate_webhook_payload(&schema, &conforming).expect("conforming");
+
+        let missing = serde_json::json!({"event": "order.created", "data": {}});
+        let err = validate_webhook_payload(&schema, &missing).expect_err("missing");
+        assert!(err.contains("`data.id`"));
+        let wrong_type = serde_json::json!({"event": 7, "data": {"id": 42}});
+        let err = validate_webhook_payload(&schema, &wrong_type).expect_err("type");
+        assert!(err.contains("`event`") && err.contains("string"));
+
+        assert!(normalize_webhook_payload_schema(WebhookPayloadSchema {
+            required: vec![WebhookPayloadField {
+                path: "data..id".to_string(),
+                value_type: None,
+            }],
+        })
+        .is_err());
+        assert!(normalize_webhook_payload_schema(WebhookPayloadSchema {
+            required: vec![WebhookPayloadField {
+                path: "data".to_string(),
+                value_type: Some("date".to_string()),
+            }],
+        })
+        .is_err());
+        assert_eq!(
+            normalize_webhook_payload_schema(WebhookPayloadSchema { required: vec![] })
+                .expect("empty"),
+            None
+        );
+    }
 }
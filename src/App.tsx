@@ -86,6 +86,7 @@ function normalizeDraft(raw: unknown): IntentDraftResponse {
       inboxSourceText: plan.inboxSourceText ?? plan.inbox_source_text ?? null,
       recipientHints: plan.recipientHints ?? plan.recipient_hints ?? [],
       apiCallRequest: plan.apiCallRequest ?? plan.api_call_request ?? null,
+      apiCallRequests: plan.apiCallRequests ?? plan.api_call_requests ?? {},
     },
     preview: {
       reads: value.preview?.reads ?? [],
@@ -94,6 +95,11 @@ function normalizeDraft(raw: unknown): IntentDraftResponse {
       estimatedSpend: value.preview?.estimatedSpend ?? value.preview?.estimated_spend ?? "",
       primaryCta: value.preview?.primaryCta ?? value.preview?.primary_cta ?? "Run now",
     },
+    warnings: (value.warnings ?? []).map((warning: any) => ({
+      code: warning.code ?? "",
+      message: warning.message ?? "",
+    })),
+    degraded: value.degraded ?? false,
   };
 }
 
@@ -676,6 +682,13 @@ export function App() {
             "auto_inbox_watch_microsoft365",
           watcherLastTickMs: payload.watcherLastTickMs ?? payload.watcher_last_tick_ms ?? null,
           missedRunsCount: payload.missedRunsCount ?? payload.missed_runs_count ?? 0,
+          maxCatchUpCycles: payload.maxCatchUpCycles ?? payload.max_catch_up_cycles ?? 3,
+          relaySyncBatchSize: payload.relaySyncBatchSize ?? payload.relay_sync_batch_size ?? 20,
+          relayConnectTimeoutSeconds:
+            payload.relayConnectTimeoutSeconds ?? payload.relay_connect_timeout_seconds ?? 10,
+          relayReadTimeoutSeconds:
+            payload.relayReadTimeoutSeconds ?? payload.relay_read_timeout_seconds ?? 30,
+          relayMaxRetries: payload.relayMaxRetries ?? payload.relay_max_retries ?? 2,
         });
       })
       .catch((err) => {
@@ -699,6 +712,8 @@ export function App() {
           lastError: payload.lastError ?? payload.last_error ?? null,
           consecutiveFailures: payload.consecutiveFailures ?? payload.consecutive_failures ?? 0,
           updatedAtMs: payload.updatedAtMs ?? payload.updated_at_ms ?? 0,
+          renewalWindowMs: payload.renewalWindowMs ?? payload.renewal_window_ms ?? 86400000,
+          nextRenewalAtMs: payload.nextRenewalAtMs ?? payload.next_renewal_at_ms ?? null,
         };
         setGmailPubSubStatus(normalized);
         setGmailPubSubTopicName((prev) => prev || normalized.topicName || "");
@@ -744,6 +759,11 @@ export function App() {
         watcherMaxItems: next.watcherMaxItems,
         gmailAutopilotId: next.gmailAutopilotId,
         microsoftAutopilotId: next.microsoftAutopilotId,
+        maxCatchUpCycles: next.maxCatchUpCycles,
+        relaySyncBatchSize: next.relaySyncBatchSize,
+        relayConnectTimeoutSeconds: next.relayConnectTimeoutSeconds,
+        relayReadTimeoutSeconds: next.relayReadTimeoutSeconds,
+        relayMaxRetries: next.relayMaxRetries,
       },
     })
       .then(() => {
@@ -779,6 +799,9 @@ export function App() {
           quietHoursEndLocal: payload.quietHoursEndLocal ?? payload.quiet_hours_end_local ?? 9,
           allowOutsideQuietHours:
             payload.allowOutsideQuietHours ?? payload.allow_outside_quiet_hours ?? false,
+          recipientCooldownMinutes:
+            payload.recipientCooldownMinutes ?? payload.recipient_cooldown_minutes ?? 0,
+          selfSendOutcomes: payload.selfSendOutcomes ?? payload.self_send_outcomes ?? false,
           updatedAtMs: payload.updatedAtMs ?? payload.updated_at_ms ?? 0,
         };
         setSendPolicy(normalized);
@@ -800,6 +823,8 @@ export function App() {
         quietHoursStartLocal: next.quietHoursStartLocal,
         quietHoursEndLocal: next.quietHoursEndLocal,
         allowOutsideQuietHours: next.allowOutsideQuietHours,
+        recipientCooldownMinutes: next.recipientCooldownMinutes,
+        selfSendOutcomes: next.selfSendOutcomes,
       },
     })
       .then((payload: any) => {
@@ -824,6 +849,12 @@ export function App() {
             payload.allowOutsideQuietHours ??
             payload.allow_outside_quiet_hours ??
             next.allowOutsideQuietHours,
+          recipientCooldownMinutes:
+            payload.recipientCooldownMinutes ??
+            payload.recipient_cooldown_minutes ??
+            next.recipientCooldownMinutes,
+          selfSendOutcomes:
+            payload.selfSendOutcomes ?? payload.self_send_outcomes ?? next.selfSendOutcomes,
           updatedAtMs: payload.updatedAtMs ?? payload.updated_at_ms ?? Date.now(),
         });
         setConnectionsMessage("Send policy updated.");
@@ -1197,6 +1228,8 @@ export function App() {
           lastError: payload.lastError ?? payload.last_error ?? null,
           consecutiveFailures: payload.consecutiveFailures ?? payload.consecutive_failures ?? 0,
           updatedAtMs: payload.updatedAtMs ?? payload.updated_at_ms ?? Date.now(),
+          renewalWindowMs: payload.renewalWindowMs ?? payload.renewal_window_ms ?? 86400000,
+          nextRenewalAtMs: payload.nextRenewalAtMs ?? payload.next_renewal_at_ms ?? null,
         });
         setConnectionsMessage("Gmail PubSub trigger saved. Renew watch to activate.");
       })
@@ -2534,6 +2567,15 @@ export function App() {
               <section className="draft-preview" aria-label="Run plan preview">
                 <p className="draft-kind">{classifiedLabel}</p>
                 <p className="draft-reason">{draft.classificationReason}</p>
+                {draft.degraded && (
+                  <button
+                    type="button"
+                    onClick={() => generateDraft(draft.kind)}
+                    disabled={intentLoading}
+                  >
+                    Retry full plan
+                  </button>
+                )}
                 <div className="intent-actions">
                   {draft.kind === "one_off_run" ? (
                     <button
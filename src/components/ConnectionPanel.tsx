@@ -510,6 +510,9 @@ export function ConnectionPanel(props: Props) {
           {gmailPubSubStatus.watchExpirationMs
             ? ` • expires ${new Date(gmailPubSubStatus.watchExpirationMs).toLocaleString()}`
             : ""}
+          {gmailPubSubStatus.nextRenewalAtMs
+            ? ` • renews ${new Date(gmailPubSubStatus.nextRenewalAtMs).toLocaleString()}`
+            : ""}
           {gmailPubSubStatus.lastEventAtMs
             ? ` • last event ${new Date(gmailPubSubStatus.lastEventAtMs).toLocaleString()}`
             : ""}
@@ -693,6 +696,21 @@ export function ConnectionPanel(props: Props) {
               <option value="yes">Yes</option>
             </select>
           </label>
+          <label>
+            <span>Email outcomes to me</span>
+            <select
+              value={sendPolicy.selfSendOutcomes ? "yes" : "no"}
+              onChange={(event) =>
+                saveSendPolicy({
+                  ...sendPolicy,
+                  selfSendOutcomes: event.target.value === "yes",
+                })
+              }
+            >
+              <option value="no">No</option>
+              <option value="yes">Yes</option>
+            </select>
+          </label>
         </div>
       )}
       {connectionsMessage && <p className="connection-message">{connectionsMessage}</p>}
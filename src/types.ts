@@ -7,7 +7,10 @@ export interface HomeSurface {
   cta: string;
 }
 
+export type HomeSnapshotRange = "today" | "week" | "all_time";
+
 export interface HomeSnapshot {
+  range?: HomeSnapshotRange;
   surfaces: HomeSurface[];
   runner: {
     mode: "app_open" | "background";
@@ -40,6 +43,20 @@ export interface IntentDraftResponse {
   classificationReason: string;
   plan: AutopilotPlan;
   preview: IntentDraftPreview;
+  warnings: PlanWarning[];
+  degraded: boolean;
+}
+
+export interface PlanWarning {
+  code: string;
+  message: string;
+}
+
+export interface PlanValidationRecord {
+  valid: boolean;
+  plan: AutopilotPlan | null;
+  error: string | null;
+  warnings: PlanWarning[];
 }
 
 export interface EmailConnectionRecord {
@@ -73,6 +90,44 @@ export interface RunnerControlRecord {
   microsoftAutopilotId: string;
   watcherLastTickMs: number | null;
   missedRunsCount: number;
+  maxCatchUpCycles: number;
+  relaySyncBatchSize: number;
+  relayConnectTimeoutSeconds: number;
+  relayReadTimeoutSeconds: number;
+  relayMaxRetries: number;
+}
+
+export interface AppSettingsRecord {
+  globalPause: boolean;
+  disallowedPrimitives: PrimitiveId[];
+  updatedAtMs: number;
+}
+
+export interface RunnerCycleLogRecord {
+  id: string;
+  trigger: "manual" | "background" | string;
+  watcherStatus: string;
+  relaySyncStatus: string;
+  providersPolled: number;
+  fetched: number;
+  deduped: number;
+  startedRuns: number;
+  failed: number;
+  resumedDueRuns: number;
+  relayDecisionsApplied: number;
+  missedRunsDetected: number;
+  catchUpCyclesRun: number;
+  createdAtMs: number;
+}
+
+export interface RelayCallbackEventRecord {
+  requestId: string;
+  approvalId: string;
+  decision: string;
+  status: "received" | "applied" | string;
+  channel?: string | null;
+  actorLabel?: string | null;
+  createdAtMs: number;
 }
 
 export interface GmailPubSubStatusRecord {
@@ -88,6 +143,8 @@ export interface GmailPubSubStatusRecord {
   lastError: string | null;
   consecutiveFailures: number;
   updatedAtMs: number;
+  renewalWindowMs: number;
+  nextRenewalAtMs: number | null;
 }
 
 export interface GmailPubSubEventRecord {
@@ -116,6 +173,14 @@ export interface OnboardingStateRecord {
   completedAtMs: number | null;
   dismissedAtMs: number | null;
   firstSuccessfulRunAtMs: number | null;
+  firstAutopilotCreatedAtMs: number | null;
+  firstApprovalResolvedAtMs: number | null;
+  firstWebhookTriggerCreatedAtMs: number | null;
+}
+
+export interface OnboardingRecommendation {
+  recipe: RecipeKind;
+  intent: string;
 }
 
 export interface VoiceConfigRecord {
@@ -126,20 +191,138 @@ export interface VoiceConfigRecord {
   updatedAtMs: number;
 }
 
+export interface VoicePreview {
+  tone: string;
+  length: string;
+  humor: string;
+  sampleText: string;
+}
+
 export interface AutopilotVoiceConfigRecord extends VoiceConfigRecord {
   autopilotId: string;
   enabled: boolean;
 }
 
+export interface AuditLogRecord {
+  id: string;
+  actor: string;
+  action: string;
+  target: string;
+  beforeExcerpt: string | null;
+  afterExcerpt: string | null;
+  createdAtMs: number;
+}
+
+export interface RecipeModelOverrideRecord {
+  providerKind: string;
+  recipeKind: string;
+  model: string;
+  updatedAtMs: number;
+}
+
+export interface RelayPingRecord {
+  id: string;
+  reachable: boolean;
+  status: "ok" | "auth_required" | "server_error" | "dns_failed" | "refused" | "timeout" | "unreachable" | string;
+  latencyMs: number;
+  httpStatus: number | null;
+  message: string;
+  createdAtMs: number;
+}
+
+export interface RelayPingResponse {
+  latest: RelayPingRecord;
+  recent: RelayPingRecord[];
+  uptimePercent: number;
+}
+
 export interface TransportStatusRecord {
   mode: "hosted_relay" | "byok_local" | "mock" | string;
   relayConfigured: boolean;
   relayUrl: string;
+  localBaseUrl?: string | null;
+}
+
+export interface ProviderConcurrencySnapshotRecord {
+  provider: "openai" | "anthropic" | "gemini" | string;
+  inFlight: number;
+  maxConcurrent: number;
+}
+
+export interface LogEntryRecord {
+  level: "info" | "warn" | "error";
+  module: string;
+  message: string;
+  createdAtMs: number;
+}
+
+export interface AppHealthcheckRecord {
+  overall: "ok" | "degraded" | "error";
+  issues: string[];
+  transport: TransportStatusRecord;
+  relayCallbackReady: boolean;
+  emailConnections: EmailConnectionRecord[];
+  gmailPubsub: GmailPubSubStatusRecord;
+  backgroundEnabled: boolean;
+  staleSecretCount: number;
+}
+
+export interface AutopilotTransportConfigRecord {
+  autopilotId: string;
+  transportMode: "inherit" | "mock" | "local_http" | "relay" | string;
+  updatedAtMs: number;
+}
+
+export interface AutopilotContextLimitsRecord {
+  autopilotId: string;
+  inboxTextMaxChars: number | null;
+  dailySourceMaxItems: number | null;
+  summarizeOverflow: boolean;
+  updatedAtMs: number;
+}
+
+export interface AutopilotWebTrackingRecord {
+  autopilotId: string;
+  trackingParams: string[] | null;
+  updatedAtMs: number;
+}
+
+export type ApprovalChannel = "local_ui" | "relay" | "email";
+
+export interface ApprovalChannelPolicyRecord {
+  autopilotId: string;
+  allowedChannels: ApprovalChannel[];
+  updatedAtMs: number;
+}
+
+export interface LocalTransportProbeRecord {
+  baseUrl: string;
+  reachable: boolean;
+  latencyMs: number;
+  httpStatus?: number | null;
+  message: string;
 }
 
 export interface ApiKeyRefStatusRecord {
   refName: string;
   configured: boolean;
+  createdAtMs?: number | null;
+  updatedAtMs?: number | null;
+  lastUsedAtMs?: number | null;
+}
+
+export interface SecretRotationWarningRecord {
+  kind: "api_key_ref" | "webhook_secret";
+  name: string;
+  autopilotId?: string | null;
+  secretSetAtMs: number;
+  ageDays: number;
+  severity: "warning" | "critical";
+}
+
+export interface KeychainBundleExportRecord {
+  bundle: string;
+  entryCount: number;
 }
 
 export interface CodexOauthStatusRecord {
@@ -181,11 +364,15 @@ export interface RelayApprovalSyncStatusRecord {
   lastError: string | null;
   lastProcessedCount: number;
   totalProcessedCount: number;
+  dedupedByOtherChannel: number;
+  streamingSupported: boolean | null;
+  streamProbedAtMs: number | null;
 }
 
 export interface RelayApprovalSyncTickRecord {
   status: RelayApprovalSyncStatusRecord;
   appliedCount: number;
+  dedupedByOtherChannel: number;
 }
 
 export interface RelayDeviceRecord {
@@ -205,17 +392,37 @@ export interface RelayRoutingPolicyRecord {
   updatedAtMs: number;
 }
 
+export type WebhookPayloadFieldType =
+  | "string"
+  | "number"
+  | "integer"
+  | "boolean"
+  | "object"
+  | "array"
+  | "null";
+
+export interface WebhookPayloadField {
+  path: string;
+  type?: WebhookPayloadFieldType | null;
+}
+
+export interface WebhookPayloadSchema {
+  required: WebhookPayloadField[];
+}
+
 export interface WebhookTriggerRecord {
   id: string;
   autopilotId: string;
   status: "active" | "paused" | "error" | string;
   endpointPath: string;
   endpointUrl: string;
-  signatureMode: "terminus_hmac_sha256" | string;
+  signatureMode: "terminus_hmac_sha256" | "stripe" | "github_sha256" | string;
   description: string;
   maxPayloadBytes: number;
+  maxDeliveriesPerMinute: number;
   allowedContentTypes: string[];
   providerKind: string;
+  payloadSchema: WebhookPayloadSchema | null;
   lastEventAtMs: number | null;
   lastError: string | null;
   createdAtMs: number;
@@ -229,7 +436,7 @@ export interface WebhookTriggerEventRecord {
   deliveryId: string;
   eventIdempotencyKey: string;
   receivedAtMs: number;
-  status: "accepted" | "rejected" | "duplicate" | "queued" | "failed_validation" | string;
+  status: "accepted" | "rejected" | "duplicate" | "queued" | "failed_validation" | "rate_limited" | "dead_lettered" | string;
   httpStatus: number | null;
   headersRedactedJson: string;
   payloadExcerpt: string;
@@ -238,10 +445,27 @@ export interface WebhookTriggerEventRecord {
   runId: string | null;
 }
 
+export interface WebhookDeadLetterRecord {
+  id: string;
+  triggerId: string;
+  deliveryId: string;
+  eventIdempotencyKey: string;
+  relayChannel: string | null;
+  reason: string;
+  bodyJson: string;
+  status: "pending" | "retried" | string;
+  retryCount: number;
+  runId: string | null;
+  createdAtMs: number;
+  updatedAtMs: number;
+}
+
 export interface WebhookTriggerCreateInput {
   autopilotId: string;
   description?: string;
   maxPayloadBytes?: number;
+  maxDeliveriesPerMinute?: number;
+  signatureMode?: "terminus_hmac_sha256" | "stripe" | "github_sha256";
 }
 
 export interface WebhookTriggerCreateResponse {
@@ -257,6 +481,19 @@ export interface WebhookIngestResult {
   message: string;
 }
 
+export interface WebhookSignatureSimulation {
+  triggerId: string;
+  signatureMode: string;
+  signature: string;
+  signatureTsMs: number | null;
+}
+
+export interface WebhookRunPreview {
+  triggerId: string;
+  plan: AutopilotPlan;
+  preview: IntentDraftPreview;
+}
+
 export interface AutopilotSendPolicyRecord {
   autopilotId: string;
   allowSending: boolean;
@@ -265,6 +502,8 @@ export interface AutopilotSendPolicyRecord {
   quietHoursStartLocal: number;
   quietHoursEndLocal: number;
   allowOutsideQuietHours: boolean;
+  recipientCooldownMinutes: number;
+  selfSendOutcomes: boolean;
   updatedAtMs: number;
 }
 
@@ -285,6 +524,7 @@ export type RunHealthStatus =
   | "waiting_for_clarification"
   | "retrying_transient"
   | "retrying_stuck"
+  | "paused"
   | "policy_blocked"
   | "provider_misconfigured"
   | "source_unreachable"
@@ -320,6 +560,136 @@ export interface RunDiagnosticRecord {
   createdAtMs: number;
 }
 
+export interface RunProgressEvent {
+  runId: string;
+  stepIndex: number;
+  stepId: string | null;
+  state: string;
+}
+
+export interface MemoryCardRecord {
+  card_id: string;
+  autopilot_id: string;
+  card_type:
+    | "format_preference"
+    | "source_preference"
+    | "suppression_rationale"
+    | "recurring_entities"
+    | string;
+  title: string;
+  content: Record<string, unknown>;
+  confidence: number;
+  created_from_run_id: string | null;
+  updated_at_ms: number;
+  version: number;
+  manual: boolean;
+}
+
+export interface RunStepDetailRecord {
+  step_id: string;
+  label: string;
+  primitive: string;
+  state: string;
+  started_at_ms: number | null;
+  finished_at_ms: number | null;
+  outcome_excerpt: string | null;
+  approval_id: string | null;
+}
+
+export interface RunStepCostRecord {
+  step_id: string;
+  label: string;
+  usd_cents_actual: number;
+  usd_cents_estimate: number;
+  provider: string | null;
+  model: string | null;
+  input_tokens: number;
+  output_tokens: number;
+}
+
+export interface RunCostBreakdownRecord {
+  run_id: string;
+  usd_cents_actual: number;
+  steps: RunStepCostRecord[];
+}
+
+export interface ReceiptStepDiffRecord {
+  step_id: string;
+  label: string;
+  change: "unchanged" | "changed" | "added" | "removed";
+  state_a: string | null;
+  state_b: string | null;
+  spend_usd_cents_a: number;
+  spend_usd_cents_b: number;
+  approval_a: string | null;
+  approval_b: string | null;
+  outcome_a: string | null;
+  outcome_b: string | null;
+}
+
+export interface RunReceiptComparisonRecord {
+  run_id_a: string;
+  run_id_b: string;
+  terminal_state_a: string;
+  terminal_state_b: string;
+  total_spend_usd_cents_a: number;
+  total_spend_usd_cents_b: number;
+  spend_delta_usd_cents: number;
+  approvals_a: number;
+  approvals_b: number;
+  steps: ReceiptStepDiffRecord[];
+  changed_step_ids: string[];
+}
+
+export interface QueuedRunRecord {
+  run_id: string;
+  autopilot_id: string;
+  state: "ready" | "retrying" | "paused";
+  priority: "interactive" | "normal" | "background";
+  provider_kind: string;
+  due_at_ms: number | null;
+  wait_reason:
+    | "ready"
+    | "concurrency_limit"
+    | "retry_backoff"
+    | "provider_retry_after"
+    | "quiet_hours"
+    | "recipient_cooldown"
+    | "schedule";
+  wait_detail: string | null;
+}
+
+export interface ApprovalContextRecord {
+  approval_id: string;
+  run_id: string;
+  payload_type: string;
+  run_intent: string;
+  step_label: string;
+  risk_tier: RiskTier | null;
+  draft_excerpt: string | null;
+  recipients: string[];
+  estimated_impact: string;
+}
+
+export interface PrimitiveGuardEventRecord {
+  id: string;
+  run_id: string;
+  step_id: string;
+  primitive: string;
+  decision: "allow" | "deny" | string;
+  reason: string;
+  created_at_ms: number;
+}
+
+export interface RunExplanation {
+  runId: string;
+  runState: string;
+  causeCode: string;
+  cause: string;
+  suggestion: string;
+  retryable: boolean;
+}
+
 export interface ApplyInterventionResult {
   ok: boolean;
   runId: string;
@@ -327,7 +697,7 @@ export interface ApplyInterventionResult {
   updatedRunState?: string | null;
 }
 
-export type MissionTemplateKind = "daily_brief_multi_source";
+export type MissionTemplateKind = "daily_brief_multi_source" | "multi_recipe";
 
 export type MissionStatus =
   | "draft"
@@ -395,11 +765,45 @@ export interface MissionContractStatus {
   readyToComplete: boolean;
 }
 
+export interface MissionStageInput {
+  recipe: "inbox_triage" | "daily_brief" | string;
+  intent: string;
+  sources?: string[];
+  inputText?: string | null;
+}
+
+export interface MissionStageProgress {
+  stageIndex: number;
+  stageKey: string;
+  recipe: string;
+  intent: string;
+  runId?: string | null;
+  status: "pending" | string;
+}
+
 export interface MissionDetail {
   mission: MissionRecord;
   childRuns: MissionRunLink[];
   events: MissionEventRecord[];
   contract: MissionContractStatus;
+  stages: MissionStageProgress[];
+}
+
+export interface MissionTemplateRecord {
+  id: string;
+  name: string;
+  templateKind: MissionTemplateKind | string;
+  parameters: string[];
+  draft: MissionDraft;
+  stages: MissionStageInput[];
+  createdAtMs: number;
+  updatedAtMs: number;
+}
+
+export interface MissionTemplateInstance {
+  templateId: string;
+  draft: MissionDraft;
+  stages: MissionStageInput[];
 }
 
 export interface MissionTickResult {
@@ -407,7 +811,12 @@ export interface MissionTickResult {
   childRunsTicked: number;
 }
 
-export type RecipeKind = "website_monitor" | "inbox_triage" | "daily_brief" | "custom";
+export type RecipeKind =
+  | "website_monitor"
+  | "inbox_triage"
+  | "daily_brief"
+  | "custom"
+  | "webhook";
 
 export type RiskTier = "low" | "medium" | "high";
 
@@ -441,6 +850,16 @@ export interface PlanStep {
   riskTier: RiskTier;
 }
 
+export interface ApiCallRequestConfig {
+  url: string;
+  method: string;
+  headerKeyRef: string;
+  authHeaderName: string;
+  authScheme: string;
+  bodyJson?: string | null;
+  responseFields?: string[];
+}
+
 export interface AutopilotPlan {
   schemaVersion: "1.0";
   recipe: RecipeKind;
@@ -448,17 +867,219 @@ export interface AutopilotPlan {
   provider: ProviderMetadata;
   webSourceUrl?: string | null;
   webAllowedDomains?: string[];
+  webRawHtml?: boolean;
   inboxSourceText?: string | null;
+  eventContext?: unknown;
   dailySources?: string[];
-  apiCallRequest?: {
-    url: string;
-    method: string;
-    headerKeyRef: string;
-    authHeaderName: string;
-    authScheme: string;
-    bodyJson?: string | null;
-  } | null;
+  apiCallRequest?: ApiCallRequestConfig | null;
+  apiCallRequests?: Record<string, ApiCallRequestConfig>;
   recipientHints?: string[];
   allowedPrimitives: PrimitiveId[];
   steps: PlanStep[];
 }
+
+export interface GuidancePreviewRecord {
+  normalizedInstruction: string;
+  mode: "applied" | "proposed_rule" | "needs_approval";
+  message: string;
+  proposedRule: string | null;
+}
+
+export interface BatchRunItemInput {
+  autopilotId: string;
+  recipe: RecipeKind;
+  intent: string;
+  provider: ProviderMetadata["id"];
+}
+
+export interface BatchRunItemResult {
+  index: number;
+  autopilotId: string;
+  status: "started" | "failed" | "skipped_cap" | string;
+  runId: string | null;
+  estimatedCostUsdCents: number | null;
+  error: string | null;
+}
+
+export interface BatchRunRecord {
+  startedCount: number;
+  failedCount: number;
+  skippedCount: number;
+  items: BatchRunItemResult[];
+}
+
+export interface AutopilotSendStatsRecord {
+  autopilotId: string;
+  dayBucket: number;
+  sendsToday: number;
+  maxSendsPerDay: number;
+  remainingToday: number;
+}
+
+export interface RecipientCooldownRecord {
+  autopilotId: string;
+  recipient: string;
+  lastSentAtMs: number;
+  cooldownUntilMs: number;
+}
+
+export interface ProviderTraceRecord {
+  id: string;
+  correlationId: string | null;
+  providerKind: string;
+  model: string;
+  status: "ok" | "error";
+  requestInput: string;
+  responseText: string | null;
+  errorMessage: string | null;
+  latencyMs: number;
+  createdAtMs: number;
+}
+
+export interface IntentKindOverrideRecord {
+  phrase: string;
+  kind: "one_off_run" | "draft_autopilot";
+  createdAtMs: number;
+  updatedAtMs: number;
+}
+
+export interface WebhookEndpointSetupRecord {
+  triggerId: string;
+  endpointUrl: string;
+  signatureMode: string;
+  signatureHeader: string;
+  timestampHeader: string | null;
+  instructions: string;
+  sampleCurl: string;
+}
+
+export interface MissionListFilter {
+  status?: MissionStatus;
+  createdAfterMs?: number;
+  createdBeforeMs?: number;
+}
+
+export interface EmailLabelRecord {
+  provider: string;
+  labelId: string;
+  name: string;
+  fetchedAtMs: number;
+}
+
+export interface AutopilotTriageLabelRecord {
+  autopilotId: string;
+  provider: string;
+  labelId: string;
+  labelName: string;
+  updatedAtMs: number;
+}
+
+export interface ActivityFeedItem {
+  id: string;
+  source: "run" | "autopilot";
+  autopilotId: string | null;
+  runId: string | null;
+  kind: string;
+  message: string;
+  fromState: string | null;
+  toState: string | null;
+  createdAtMs: number;
+}
+
+export interface InboxWatcherStats {
+  provider: string;
+  fetched: number;
+  deduped: number;
+  startedRuns: number;
+  failed: number;
+  lastRunAtMs: number;
+}
+
+export interface SchemaStatus {
+  currentVersion: string;
+  storedVersion: string | null;
+  upToDate: boolean;
+  migratedFromVersion: string | null;
+  lastMigratedAtMs: number | null;
+  lastBootAtMs: number | null;
+  migratedOnLastBoot: boolean;
+}
+
+export interface LearningCompactionSummary {
+  autopilot_id: string | null;
+  dry_run: boolean;
+  decision_events_deleted: number;
+  adaptation_log_deleted: number;
+  run_evaluations_deleted: number;
+  primitive_guard_events_deleted: number;
+  memory_cards_decayed: number;
+  memory_cards_deleted: number;
+}
+
+export interface MaintenanceSummary {
+  dryRun: boolean;
+  learning: LearningCompactionSummary;
+  webhookDeadLettersDeleted: number;
+  auditLogDeleted: number;
+  relayPingLogDeleted: number;
+  providerTracesDeleted: number;
+  runnerCycleLogDeleted: number;
+  totalDeleted: number;
+}
+
+export interface ApprovalTtlRecord {
+  autopilotId: string;
+  highTtlMs: number | null;
+  mediumTtlMs: number | null;
+  lowTtlMs: number | null;
+  updatedAtMs: number;
+}
+
+export type RunState =
+  | "ready"
+  | "running"
+  | "needs_approval"
+  | "needs_clarification"
+  | "retrying"
+  | "paused"
+  | "succeeded"
+  | "failed"
+  | "blocked"
+  | "canceled";
+
+export interface RunRecord {
+  id: string;
+  autopilot_id: string;
+  idempotency_key: string;
+  provider_kind: "open_ai" | "anthropic" | "gemini";
+  provider_tier: ProviderTier;
+  state: RunState;
+  priority: "interactive" | "normal" | "background";
+  current_step_index: number;
+  retry_count: number;
+  max_retries: number;
+  next_retry_backoff_ms: number | null;
+  next_retry_at_ms: number | null;
+  backoff_base_ms: number;
+  backoff_max_ms: number;
+  resume_after_ms: number | null;
+  pause_reason: string | null;
+  soft_cap_approved: boolean;
+  usd_cents_estimate: number;
+  usd_cents_actual: number;
+  failure_reason: string | null;
+  plan: AutopilotPlan;
+}
+
+export interface RunWithPlanRecord {
+  run: RunRecord;
+  stored_schema_version: string | null;
+}
+
+export interface RelayRoutingPreviewItem {
+  deviceId: string;
+  deviceLabel: string;
+  status: string;
+  wouldReceiveDecisions: boolean;
+  reason: string;
+}
@@ -130,6 +130,18 @@ export function normalizeOnboardingStateRecord(row: unknown): OnboardingStateRec
       (value.firstSuccessfulRunAtMs as number | null) ??
       (value.first_successful_run_at_ms as number | null) ??
       null,
+    firstAutopilotCreatedAtMs:
+      (value.firstAutopilotCreatedAtMs as number | null) ??
+      (value.first_autopilot_created_at_ms as number | null) ??
+      null,
+    firstApprovalResolvedAtMs:
+      (value.firstApprovalResolvedAtMs as number | null) ??
+      (value.first_approval_resolved_at_ms as number | null) ??
+      null,
+    firstWebhookTriggerCreatedAtMs:
+      (value.firstWebhookTriggerCreatedAtMs as number | null) ??
+      (value.first_webhook_trigger_created_at_ms as number | null) ??
+      null,
   };
 }
 